@@ -1,6 +1,9 @@
-use crate::square::Square;
+use crate::board_error::BoardError;
+use crate::square::{format_square, get_column_and_row_from_square_name, get_square_color};
 use std::fmt;
 use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::{Index, IndexMut};
 
 /// Represents a game board that holds pieces of a certain type.
 ///
@@ -9,8 +12,12 @@ use std::fmt::Display;
 /// # Type Parameters
 ///
 /// * `P` - The type of pieces that can be placed on the board.
+///
+/// Squares are stored in a single contiguous `Vec`, indexed by `row * width + col`,
+/// which keeps lookups and clones of the board cheap.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Board<P> {
-    squares: Vec<Square<P>>,
+    squares: Vec<Option<P>>,
     width: usize,
     height: usize,
 }
@@ -18,8 +25,9 @@ pub struct Board<P> {
 impl<P> Board<P> {
     /// Create a new board of any size,
     ///
-    /// # Panics
-    /// The function will panic if either height or width is 0
+    /// # Errors
+    /// Returns an error message if either height or width is 0, see `try_build` for an
+    /// error that can be matched on.
     ///
     /// # Example
     /// ```
@@ -30,6 +38,27 @@ impl<P> Board<P> {
     ///assert!(board.is_ok());
     /// ```
     pub fn build(width: usize, height: usize) -> Result<Board<P>, String> {
+        Self::try_build(width, height).map_err(|e| e.to_string())
+    }
+
+    /// Create a new board of any size,
+    ///
+    /// # Errors
+    /// * `BoardError::EmptyDimension` - If either height or width is 0.
+    /// * `BoardError::TooLarge` - If the squares would take more memory than can be
+    ///   allocated.
+    ///
+    /// # Example
+    /// ```
+    /// use game_board::{Board, BoardError};
+    ///
+    /// assert!(Board::<u8>::try_build(8, 8).is_ok());
+    /// assert_eq!(
+    ///     Some(BoardError::EmptyDimension { width: 0, height: 8 }),
+    ///     Board::<u8>::try_build(0, 8).err()
+    /// );
+    /// ```
+    pub fn try_build(width: usize, height: usize) -> Result<Board<P>, BoardError> {
         Ok(Board {
             squares: Board::generate_board(width, height)?,
             width,
@@ -85,9 +114,30 @@ impl<P> Board<P> {
     /// assert!(piece.is_some())
     /// ```
     pub fn get_piece_at_space(&self, col: usize, row: usize) -> Option<&P> {
-        self.validate_col_and_row(col, row);
-        let square_index = self.get_square_index(col, row);
-        self.squares[square_index].get_piece()
+        self.try_get_piece_at_space(col, row)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// get piece at square, without panicking on squares outside the board
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<&P>)` - The piece at the specified position, if there is one.
+    /// * `Err(BoardError::OutOfBounds)` - If the given column or row are outside the
+    ///   bounds of the board.
+    ///
+    /// # Example
+    /// ```
+    /// use game_board::Board;
+    ///
+    /// let board = Board::<u8>::build(3, 3).unwrap();
+    ///
+    /// assert_eq!(Ok(None), board.try_get_piece_at_space(2, 2));
+    /// assert!(board.try_get_piece_at_space(3, 2).is_err());
+    /// ```
+    pub fn try_get_piece_at_space(&self, col: usize, row: usize) -> Result<Option<&P>, BoardError> {
+        let square_index = self.try_get_square_index(col, row)?;
+        Ok(self.squares[square_index].as_ref())
     }
 
     /// Places a piece at the given square
@@ -120,9 +170,31 @@ impl<P> Board<P> {
     /// assert!(piece.is_some());
     /// ```
     pub fn place_piece(&mut self, piece: P, col: usize, row: usize) {
-        self.validate_col_and_row(col, row);
-        let square_index = self.get_square_index(col, row);
-        self.squares[square_index].place_piece(piece);
+        self.try_place_piece(piece, col, row)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Places a piece at the given square, without panicking on squares outside the board
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the piece was placed.
+    /// * `Err(BoardError::OutOfBounds)` - If the given column or row are outside the
+    ///   bounds of the board, the piece is dropped and the board is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use game_board::Board;
+    ///
+    /// let mut board = Board::<u8>::build(3, 3).unwrap();
+    ///
+    /// assert!(board.try_place_piece(1, 2, 2).is_ok());
+    /// assert!(board.try_place_piece(1, 0, 3).is_err());
+    /// ```
+    pub fn try_place_piece(&mut self, piece: P, col: usize, row: usize) -> Result<(), BoardError> {
+        let square_index = self.try_get_square_index(col, row)?;
+        self.squares[square_index] = Some(piece);
+        Ok(())
     }
 
     /// Removes a piece from the given square
@@ -159,26 +231,49 @@ impl<P> Board<P> {
     /// assert!(piece.is_some());
     /// ```
     pub fn remove_piece(&mut self, col: usize, row: usize) -> Option<P> {
-        self.validate_col_and_row(col, row);
-        let square_index = self.get_square_index(col, row);
-        self.squares[square_index].clear_piece()
+        self.try_remove_piece(col, row)
+            .unwrap_or_else(|e| panic!("{e}"))
     }
 
-    fn generate_board(width: usize, height: usize) -> Result<Vec<Square<P>>, String> {
-        if width == 0 || height == 0 {
-            return Err(String::from(
-                "Height and Width must be positive integers greater then 0",
-            ));
-        }
-
-        let mut spaces = Vec::with_capacity(width * height);
+    /// Removes a piece from the given square, without panicking on squares outside the
+    /// board
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<P>)` - The removed piece, if there was one.
+    /// * `Err(BoardError::OutOfBounds)` - If the given column or row are outside the
+    ///   bounds of the board.
+    ///
+    /// # Example
+    /// ```
+    /// use game_board::Board;
+    ///
+    /// let mut board = Board::<u8>::build(3, 3).unwrap();
+    /// board.place_piece(1, 2, 2);
+    ///
+    /// assert_eq!(Ok(Some(1)), board.try_remove_piece(2, 2));
+    /// assert!(board.try_remove_piece(2, 3).is_err());
+    /// ```
+    pub fn try_remove_piece(&mut self, col: usize, row: usize) -> Result<Option<P>, BoardError> {
+        let square_index = self.try_get_square_index(col, row)?;
+        Ok(self.squares[square_index].take())
+    }
 
-        for row in 0..height {
-            for col in 0..width {
-                let square = Square::build(col, row);
-                spaces.push(square);
-            }
+    fn generate_board(width: usize, height: usize) -> Result<Vec<Option<P>>, BoardError> {
+        if width == 0 || height == 0 {
+            return Err(BoardError::EmptyDimension { width, height });
         }
+        let square_count = width
+            .checked_mul(height)
+            .filter(|count| {
+                count
+                    .checked_mul(size_of::<Option<P>>())
+                    .is_some_and(|bytes| bytes <= isize::MAX as usize)
+            })
+            .ok_or(BoardError::TooLarge { width, height })?;
+
+        let mut spaces = Vec::with_capacity(square_count);
+        spaces.resize_with(square_count, || None);
 
         Ok(spaces)
     }
@@ -187,36 +282,141 @@ impl<P> Board<P> {
         col + row * self.width
     }
 
-    fn validate_col_and_row(&self, col: usize, row: usize) {
-        if col >= self.width {
-            panic!("column outside of board bounds");
-        }
-        if row >= self.height {
-            panic!("row is outside of board bounds");
+    fn try_get_square_index(&self, col: usize, row: usize) -> Result<usize, BoardError> {
+        if col >= self.width || row >= self.height {
+            return Err(BoardError::OutOfBounds {
+                col,
+                row,
+                width: self.width,
+                height: self.height,
+            });
         }
+        Ok(self.get_square_index(col, row))
+    }
+
+    fn get_square_index_or_panic(&self, col: usize, row: usize) -> usize {
+        self.try_get_square_index(col, row)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn get_square_index_by_name(&self, name: &str) -> usize {
+        let (col, row) = get_column_and_row_from_square_name(name)
+            .unwrap_or_else(|_| panic!("{name} is not the name of a square"));
+        self.get_square_index_or_panic(col, row)
     }
 }
 
-impl<P: Display> Display for Board<P> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut board_string = String::new();
+/// Indexes the board by `(col, row)`, giving the square's piece, if any.
+///
+/// # Panics
+///
+/// Like `get_piece_at_space`, indexing panics on squares outside the board.
+///
+/// # Example
+/// ```
+/// use game_board::Board;
+///
+/// let mut board = Board::<u8>::build(8, 8).unwrap();
+/// board[(4, 1)] = Some(1);
+///
+/// assert_eq!(Some(1), board[(4, 1)]);
+/// assert_eq!(Some(&1), board["e2"].as_ref());
+/// assert_eq!(None, board[(4, 2)]);
+/// ```
+impl<P> Index<(usize, usize)> for Board<P> {
+    type Output = Option<P>;
+
+    fn index(&self, (col, row): (usize, usize)) -> &Self::Output {
+        &self.squares[self.get_square_index_or_panic(col, row)]
+    }
+}
+
+impl<P> IndexMut<(usize, usize)> for Board<P> {
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut Self::Output {
+        let square_index = self.get_square_index_or_panic(col, row);
+        &mut self.squares[square_index]
+    }
+}
+
+/// Indexes the board by square name, such as `"e2"`, see `get_column_and_row_from_square_name`.
+///
+/// # Panics
+///
+/// Indexing panics if the name is not a square name or the square is outside the board.
+impl<P> Index<&str> for Board<P> {
+    type Output = Option<P>;
+
+    fn index(&self, name: &str) -> &Self::Output {
+        &self.squares[self.get_square_index_by_name(name)]
+    }
+}
 
-        let mut lines = vec![String::new(); self.get_height()];
+impl<P> IndexMut<&str> for Board<P> {
+    fn index_mut(&mut self, name: &str) -> &mut Self::Output {
+        let square_index = self.get_square_index_by_name(name);
+        &mut self.squares[square_index]
+    }
+}
 
-        for y in 0..self.get_height() {
-            for x in 0..self.get_width() {
-                let square_index = self.get_square_index(x, y);
-                let square = &self.squares[square_index];
-                lines[y].push_str(format!("{square}",).as_str());
+impl<P: Hash> Board<P> {
+    /// A hash of the board's size and the pieces on its squares.
+    ///
+    /// Like a Zobrist hash, each occupied square contributes a key derived from its index
+    /// and its piece, and the keys are combined with XOR. Boards holding the same pieces on
+    /// the same squares always have the same hash, so it can be compared between polls to
+    /// skip redrawing a board that has not changed.
+    ///
+    /// The hash is stable within a build of the program, but should not be stored.
+    ///
+    /// # Example
+    /// ```
+    /// use game_board::Board;
+    ///
+    /// let mut board = Board::<u8>::build(8, 8).unwrap();
+    /// let empty = board.get_content_hash();
+    ///
+    /// board.place_piece(1, 4, 0);
+    /// assert_ne!(empty, board.get_content_hash());
+    ///
+    /// board.remove_piece(4, 0);
+    /// assert_eq!(empty, board.get_content_hash());
+    /// ```
+    pub fn get_content_hash(&self) -> u64 {
+        let mut hash = mix((self.width as u64) << 32 ^ self.height as u64);
+        for (square_index, square) in self.squares.iter().enumerate() {
+            if let Some(piece) = square {
+                let mut hasher = DefaultHasher::new();
+                piece.hash(&mut hasher);
+                hash ^= mix(hasher.finish() ^ mix(square_index as u64));
             }
         }
+        hash
+    }
+}
 
-        for line in lines.iter().rev() {
-            board_string.push_str(line.as_str());
-            board_string.push('\n');
+/// Scrambles a value with the SplitMix64 finalizer, so that similar inputs give unrelated keys.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl<P: Display> Display for Board<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in (0..self.get_height()).rev() {
+            for col in 0..self.get_width() {
+                let square_index = self.get_square_index(col, row);
+                format_square(
+                    f,
+                    get_square_color(col, row),
+                    self.squares[square_index].as_ref(),
+                )?;
+            }
+            writeln!(f)?;
         }
 
-        write!(f, "{}", board_string)
+        Ok(())
     }
 }
 
@@ -281,6 +481,65 @@ mod tests {
         assert!(board.get_piece_at_space(1, 1).is_none());
     }
 
+    #[test]
+    fn cloned_board_is_independent() {
+        let mut board = Board::<u8>::build(3, 3).unwrap();
+        board.place_piece(1, 0, 0);
+
+        let mut cloned_board = board.clone();
+        assert_eq!(board, cloned_board);
+
+        cloned_board.remove_piece(0, 0);
+        cloned_board.place_piece(2, 2, 2);
+        assert_eq!(Some(&1), board.get_piece_at_space(0, 0));
+        assert!(board.get_piece_at_space(2, 2).is_none());
+        assert_ne!(board, cloned_board);
+    }
+
+    #[test]
+    fn pieces_are_stored_by_row_then_column() {
+        let mut board = Board::<u8>::build(3, 2).unwrap();
+        board.place_piece(7, 2, 1);
+
+        assert_eq!(Some(7), board.squares[5]);
+        assert_eq!(Some(&7), board.get_piece_at_space(2, 1));
+    }
+
+    #[test]
+    fn try_methods_report_squares_out_of_bounds() {
+        let mut board = Board::<u8>::try_build(2, 3).unwrap();
+        let out_of_bounds = BoardError::OutOfBounds {
+            col: 2,
+            row: 0,
+            width: 2,
+            height: 3,
+        };
+
+        assert_eq!(Err(out_of_bounds), board.try_place_piece(1, 2, 0));
+        assert_eq!(Err(out_of_bounds), board.try_get_piece_at_space(2, 0));
+        assert_eq!(Err(out_of_bounds), board.try_remove_piece(2, 0));
+        assert_eq!(Board::<u8>::build(2, 3).unwrap(), board);
+    }
+
+    #[test]
+    fn try_build_rejects_empty_and_oversized_boards() {
+        assert_eq!(
+            Some(BoardError::EmptyDimension {
+                width: 0,
+                height: 3
+            }),
+            Board::<u8>::try_build(0, 3).err()
+        );
+        assert_eq!(
+            Some(BoardError::TooLarge {
+                width: usize::MAX,
+                height: 2
+            }),
+            Board::<u8>::try_build(usize::MAX, 2).err()
+        );
+        assert!(Board::<u64>::try_build(usize::MAX / 4, 1).is_err());
+    }
+
     #[test]
     #[should_panic]
     fn can_not_access_square_out_of_bounds_place_piece() {
@@ -305,4 +564,52 @@ mod tests {
     fn can_not_access_square_out_of_bounds_remove_piece() {
         Board::<MockPiece>::build(1, 1).unwrap().remove_piece(0, 1);
     }
+
+    #[test]
+    fn indexing_reads_and_writes_squares() {
+        let mut board = Board::<u8>::build(3, 3).unwrap();
+        board["c3"] = Some(7);
+        board[(0, 1)] = Some(1);
+
+        assert_eq!(Some(&7), board.get_piece_at_space(2, 2));
+        assert_eq!(Some(1), board["a2"]);
+        assert_eq!(Some(1), board[(0, 1)].take());
+        assert_eq!(None, board.get_piece_at_space(0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn can_not_index_square_out_of_bounds() {
+        let mut board = Board::<u8>::build(3, 3).unwrap();
+        board[(3, 0)] = Some(1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn can_not_index_invalid_square_name() {
+        let board = Board::<u8>::build(3, 3).unwrap();
+        let _ = board["3c"];
+    }
+
+    #[test]
+    fn content_hash_depends_on_pieces_and_squares() {
+        let mut board_a = Board::<u8>::build(8, 8).unwrap();
+        let mut board_b = Board::<u8>::build(8, 8).unwrap();
+        board_a.place_piece(1, 0, 0);
+        board_a.place_piece(2, 1, 0);
+        board_b.place_piece(2, 1, 0);
+        board_b.place_piece(1, 0, 0);
+        assert_eq!(board_a.get_content_hash(), board_b.get_content_hash());
+
+        board_b.place_piece(1, 0, 0);
+        board_b.place_piece(2, 0, 0);
+        board_b.place_piece(1, 1, 0);
+        assert_ne!(board_a.get_content_hash(), board_b.get_content_hash());
+
+        let wider = Board::<u8>::build(16, 4).unwrap();
+        assert_ne!(
+            Board::<u8>::build(8, 8).unwrap().get_content_hash(),
+            wider.get_content_hash()
+        );
+    }
 }
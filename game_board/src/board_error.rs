@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The ways an operation on a `Board` can fail.
+///
+/// # Example
+/// ```
+/// use game_board::{Board, BoardError};
+///
+/// let board = Board::<u8>::try_build(8, 8).unwrap();
+/// assert_eq!(
+///     Err(BoardError::OutOfBounds { col: 8, row: 0, width: 8, height: 8 }),
+///     board.try_get_piece_at_space(8, 0)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardError {
+    /// The board was given a width or height of 0.
+    EmptyDimension { width: usize, height: usize },
+    /// The board has more squares than can be allocated.
+    TooLarge { width: usize, height: usize },
+    /// A square outside the bounds of the board was accessed.
+    OutOfBounds {
+        col: usize,
+        row: usize,
+        width: usize,
+        height: usize,
+    },
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardError::EmptyDimension { .. } => write!(
+                f,
+                "Height and Width must be positive integers greater then 0"
+            ),
+            BoardError::TooLarge { width, height } => {
+                write!(f, "a {width}x{height} board has too many squares")
+            }
+            BoardError::OutOfBounds {
+                col,
+                row,
+                width,
+                height,
+            } => write!(
+                f,
+                "square ({col}, {row}) is outside of the bounds of a {width}x{height} board"
+            ),
+        }
+    }
+}
+
+impl Error for BoardError {}
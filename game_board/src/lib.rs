@@ -1,9 +1,13 @@
 mod board;
+mod board_error;
 mod color;
 mod square;
+mod square_set;
 
 pub use board::Board;
+pub use board_error::BoardError;
 pub use color::SquareColor;
 pub use square::get_column_and_row_from_square_name;
 pub use square::get_square_name_from_row_and_col;
 pub use square::Square;
+pub use square_set::SquareSet;
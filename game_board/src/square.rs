@@ -61,8 +61,8 @@ pub fn get_square_name_from_row_and_col(column: usize, row: usize) -> String {
 /// # Arguments
 ///
 /// * `name` - A `&str` representing the coordinate in simple_chess notation,
-///            with alphabetic characters for the column and numeric
-///            characters for the row. Examples include "a1", "b2", "z2", etc.
+///   with alphabetic characters for the column and numeric
+///   characters for the row. Examples include "a1", "b2", "z2", etc.
 ///
 /// # Returns
 ///
@@ -187,13 +187,8 @@ impl<P> Square<P> {
     /// ```
     ///
     pub fn build(column: usize, row: usize) -> Self {
-        let color = if (column + row) % 2 == 1 {
-            SquareColor::White
-        } else {
-            SquareColor::Black
-        };
         Square {
-            color,
+            color: get_square_color(column, row),
             piece: None,
             column,
             row,
@@ -302,18 +297,34 @@ impl<P> Square<P> {
 
 impl<P: Display> Display for Square<P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let square_color = match &self.color {
-            SquareColor::White => "\x1b[100m",
-            SquareColor::Black => "",
-        };
-        let inner_char = match &self.piece {
-            Some(piece) => piece.to_string(),
-            None => " ".to_string(),
-        };
-        write!(f, "{} {} \x1b[0m", square_color, inner_char)
+        format_square(f, self.color, self.piece.as_ref())
+    }
+}
+
+pub(crate) fn get_square_color(column: usize, row: usize) -> SquareColor {
+    if (column + row) % 2 == 1 {
+        SquareColor::White
+    } else {
+        SquareColor::Black
     }
 }
 
+pub(crate) fn format_square<P: Display>(
+    f: &mut Formatter<'_>,
+    color: SquareColor,
+    piece: Option<&P>,
+) -> fmt::Result {
+    let square_color = match color {
+        SquareColor::White => "\x1b[100m",
+        SquareColor::Black => "",
+    };
+    let inner_char = match piece {
+        Some(piece) => piece.to_string(),
+        None => " ".to_string(),
+    };
+    write!(f, "{} {} \x1b[0m", square_color, inner_char)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,147 @@
+/// A set of squares on a board, stored as a bit mask.
+///
+/// Each square of the board is a single bit, indexed by `row * width + col` like the
+/// squares of a `Board`, so membership checks are a single bit test. Boards of any size
+/// are supported, the mask grows by one 64 bit word for every 64 squares.
+///
+/// # Example
+/// ```
+/// use game_board::SquareSet;
+///
+/// let mut set = SquareSet::new(8, 8);
+/// set.insert(4, 3);
+/// set.insert(4, 2);
+///
+/// assert!(set.contains(4, 3));
+/// assert!(!set.contains(3, 4));
+/// assert_eq!(vec![(4, 2), (4, 3)], set.iter().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SquareSet {
+    bits: Vec<u64>,
+    width: usize,
+    height: usize,
+}
+
+impl SquareSet {
+    /// Creates an empty set for a board of the given size.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: vec![0; (width * height).div_ceil(64)],
+            width,
+            height,
+        }
+    }
+
+    /// the width of the board the set belongs to
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// the height of the board the set belongs to
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Adds a square to the set.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the given column or row are outside the bounds
+    /// of the board.
+    pub fn insert(&mut self, col: usize, row: usize) {
+        let index = self.get_index(col, row);
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+
+    /// Removes a square from the set.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the given column or row are outside the bounds
+    /// of the board.
+    pub fn remove(&mut self, col: usize, row: usize) {
+        let index = self.get_index(col, row);
+        self.bits[index / 64] &= !(1 << (index % 64));
+    }
+
+    /// Returns `true` if the square is in the set.
+    ///
+    /// Squares outside of the board are never in the set.
+    pub fn contains(&self, col: usize, row: usize) -> bool {
+        if col >= self.width || row >= self.height {
+            return false;
+        }
+        let index = col + row * self.width;
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Returns the number of squares in the set.
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns `true` if the set has no squares.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    /// Returns an iterator over the squares in the set as `(col, row)` tuples, ordered by
+    /// row and then by column.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.width * self.height)
+            .filter(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+            .map(|index| (index % self.width, index / self.width))
+    }
+
+    fn get_index(&self, col: usize, row: usize) -> usize {
+        if col >= self.width || row >= self.height {
+            panic!(
+                "Square ({}, {}) is outside of a {}x{} board",
+                col, row, self.width, self.height
+            );
+        }
+        col + row * self.width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_boards_span_multiple_words() {
+        let mut set = SquareSet::new(12, 12);
+        set.insert(0, 0);
+        set.insert(11, 11);
+        set.insert(5, 6);
+
+        assert_eq!(3, set.len());
+        assert!(set.contains(11, 11));
+        assert_eq!(
+            vec![(0, 0), (5, 6), (11, 11)],
+            set.iter().collect::<Vec<_>>()
+        );
+
+        set.remove(11, 11);
+        assert!(!set.contains(11, 11));
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn squares_outside_the_board_are_not_contained() {
+        let set = SquareSet::new(8, 8);
+        assert!(set.is_empty());
+        assert!(!set.contains(8, 0));
+        assert!(!set.contains(0, 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn inserting_outside_the_board_panics() {
+        SquareSet::new(8, 8).insert(8, 0);
+    }
+}
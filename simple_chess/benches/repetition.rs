@@ -0,0 +1,77 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use game_board::Board;
+use simple_chess::piece::ChessPiece;
+use simple_chess::piece::PieceType::Knight;
+use simple_chess::repetition::{PositionKey, RepetitionTable};
+use simple_chess::Color::{Black, White};
+use simple_chess::{ChessGame, ChessMoveType, Color};
+
+/// Knight shuffles that never capture or move a pawn, so no position is ever cleared
+/// from the history.
+fn build_knight_shuffle() -> Vec<ChessMoveType> {
+    let knight_move =
+        |from: (usize, usize), to: (usize, usize), color: Color| ChessMoveType::Move {
+            original_position: from,
+            new_position: to,
+            piece: ChessPiece::new(Knight, color),
+            taken_piece: None,
+            promotion: None,
+        };
+
+    let mut moves = Vec::new();
+    for _ in 0..25 {
+        moves.push(knight_move((6, 0), (5, 2), White));
+        moves.push(knight_move((1, 7), (2, 5), Black));
+        moves.push(knight_move((1, 0), (2, 2), White));
+        moves.push(knight_move((6, 7), (5, 5), Black));
+        moves.push(knight_move((5, 2), (6, 0), White));
+        moves.push(knight_move((2, 5), (1, 7), Black));
+        moves.push(knight_move((2, 2), (1, 0), White));
+        moves.push(knight_move((5, 5), (6, 7), Black));
+    }
+    moves
+}
+
+fn repetition_table(moves: &[ChessMoveType]) -> usize {
+    let mut game = ChessGame::new();
+    let mut table = RepetitionTable::new();
+    let mut max_count = table.record(PositionKey::new(&game));
+    for chess_move in moves {
+        game.make_move(*chess_move);
+        max_count = max_count.max(table.record(PositionKey::new(&game)));
+    }
+    max_count
+}
+
+fn full_board_history(moves: &[ChessMoveType]) -> usize {
+    let mut game = ChessGame::new();
+    let mut history: Vec<Board<ChessPiece>> = vec![game.get_board().clone()];
+    let mut max_count = 1;
+    for chess_move in moves {
+        game.make_move(*chess_move);
+        let board = game.get_board().clone();
+        let count = history
+            .iter()
+            .filter(|previous| **previous == board)
+            .count()
+            + 1;
+        max_count = max_count.max(count);
+        history.push(board);
+    }
+    max_count
+}
+
+fn bench_repetition_detection(c: &mut Criterion) {
+    let moves = build_knight_shuffle();
+    let mut group = c.benchmark_group("repetition_detection");
+    group.bench_function("repetition_table", |b| {
+        b.iter(|| repetition_table(black_box(&moves)))
+    });
+    group.bench_function("full_board_history", |b| {
+        b.iter(|| full_board_history(black_box(&moves)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_repetition_detection);
+criterion_main!(benches);
@@ -0,0 +1,205 @@
+use crate::Color;
+
+/// The early end of a game decided by an `Adjudicator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjudication {
+    /// The losing side resigned.
+    Resignation { winner: Color },
+    /// The game was agreed drawn.
+    Draw,
+}
+
+/// Ends decided games early in self-play and engine matches.
+///
+/// After every ply the runner records the evaluation of the position, and the adjudicator
+/// tracks how long the evaluation has stayed decisive or level:
+///
+/// - A side resigns once the evaluation has been at least `threshold` centipawns against
+///   it for `plies` consecutive plies.
+/// - The game is drawn once, after `min_ply` plies, the evaluation has stayed within
+///   `margin` centipawns of equal for `plies` consecutive plies.
+///
+/// Both rules are disabled until they are set.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::adjudication::{Adjudication, Adjudicator};
+/// use simple_chess::Color;
+///
+/// // resign if the evaluation is below -9 pawns for 4 plies
+/// let mut adjudicator = Adjudicator::new().set_resign_rule(900, 4);
+///
+/// assert_eq!(None, adjudicator.record_score(-950));
+/// assert_eq!(None, adjudicator.record_score(-1000));
+/// assert_eq!(None, adjudicator.record_score(-920));
+/// assert_eq!(
+///     Some(Adjudication::Resignation { winner: Color::Black }),
+///     adjudicator.record_score(-990)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Adjudicator {
+    resign_rule: Option<ResignRule>,
+    draw_rule: Option<DrawRule>,
+    ply: usize,
+    white_losing_plies: usize,
+    black_losing_plies: usize,
+    level_plies: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ResignRule {
+    threshold: i32,
+    plies: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DrawRule {
+    min_ply: usize,
+    margin: i32,
+    plies: usize,
+}
+
+impl Adjudicator {
+    /// Creates an `Adjudicator` with both rules disabled, which never ends a game.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the rule for resigning lost games.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The evaluation, in centipawns, a side must be behind by to resign.
+    /// * `plies` - The number of consecutive plies the evaluation must stay that bad.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Adjudicator` with the resign rule set.
+    pub fn set_resign_rule(mut self, threshold: i32, plies: usize) -> Self {
+        self.resign_rule = Some(ResignRule { threshold, plies });
+        self
+    }
+
+    /// Sets the rule for drawing level games.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_ply` - The number of plies that must be played before a draw is adjudicated.
+    /// * `margin` - The largest evaluation, in centipawns, either way that counts as level.
+    /// * `plies` - The number of consecutive plies the evaluation must stay level.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Adjudicator` with the draw rule set.
+    pub fn set_draw_rule(mut self, min_ply: usize, margin: i32, plies: usize) -> Self {
+        self.draw_rule = Some(DrawRule {
+            min_ply,
+            margin,
+            plies,
+        });
+        self
+    }
+
+    /// Records the evaluation after a ply and returns the adjudication if the game should
+    /// end.
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The evaluation of the position in centipawns from White's point of view,
+    ///   positive values favor White.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Adjudication)` - If one of the rules ends the game.
+    /// * `None` - If the game should continue.
+    pub fn record_score(&mut self, score: i32) -> Option<Adjudication> {
+        self.ply += 1;
+
+        if let Some(rule) = self.resign_rule {
+            self.white_losing_plies =
+                next_streak(self.white_losing_plies, score <= -rule.threshold);
+            self.black_losing_plies = next_streak(self.black_losing_plies, score >= rule.threshold);
+            if self.white_losing_plies >= rule.plies {
+                return Some(Adjudication::Resignation {
+                    winner: Color::Black,
+                });
+            }
+            if self.black_losing_plies >= rule.plies {
+                return Some(Adjudication::Resignation {
+                    winner: Color::White,
+                });
+            }
+        }
+
+        if let Some(rule) = self.draw_rule {
+            self.level_plies = next_streak(self.level_plies, score.abs() <= rule.margin);
+            if self.ply >= rule.min_ply && self.level_plies >= rule.plies {
+                return Some(Adjudication::Draw);
+            }
+        }
+
+        None
+    }
+
+    /// Forgets every recorded evaluation, keeping the rules, so the adjudicator can be used
+    /// for a new game.
+    pub fn reset(&mut self) {
+        self.ply = 0;
+        self.white_losing_plies = 0;
+        self.black_losing_plies = 0;
+        self.level_plies = 0;
+    }
+}
+
+fn next_streak(streak: usize, condition: bool) -> usize {
+    if condition {
+        streak + 1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resignation_needs_consecutive_plies() {
+        let mut adjudicator = Adjudicator::new().set_resign_rule(900, 3);
+
+        assert_eq!(None, adjudicator.record_score(1000));
+        assert_eq!(None, adjudicator.record_score(1000));
+        assert_eq!(None, adjudicator.record_score(500));
+        assert_eq!(None, adjudicator.record_score(1000));
+        assert_eq!(None, adjudicator.record_score(1000));
+        assert_eq!(
+            Some(Adjudication::Resignation {
+                winner: Color::White
+            }),
+            adjudicator.record_score(900)
+        );
+    }
+
+    #[test]
+    fn draws_wait_for_the_minimum_ply() {
+        let mut adjudicator = Adjudicator::new().set_draw_rule(6, 20, 2);
+
+        for _ in 0..5 {
+            assert_eq!(None, adjudicator.record_score(10));
+        }
+        assert_eq!(Some(Adjudication::Draw), adjudicator.record_score(-15));
+
+        adjudicator.reset();
+        assert_eq!(None, adjudicator.record_score(0));
+    }
+
+    #[test]
+    fn disabled_rules_never_end_the_game() {
+        let mut adjudicator = Adjudicator::new();
+        for _ in 0..100 {
+            assert_eq!(None, adjudicator.record_score(-5000));
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::chess_game_state_analyzer::{is_in_check, is_square_attacked};
+use crate::codec::standard_algebraic_notation::encode_move_as_string;
+use crate::engine::evaluation::piece_value;
+use crate::engine::{Engine, SearchResult, MATE_SCORE};
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, ChessMoveType};
+use game_board::get_square_name_from_row_and_col;
+
+/// A move suggested by the engine, described for a human reader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateMove {
+    chess_move: ChessMoveType,
+    san: String,
+    score: i32,
+    rationale: String,
+    principal_variation: Vec<ChessMoveType>,
+}
+
+impl CandidateMove {
+    /// Returns the suggested move.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.chess_move
+    }
+
+    /// Returns the move in Standard Algebraic Notation, such as `Nf3` or `exd5+`.
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Returns the engine's score of the move in centipawns, from the point of view of the
+    /// player making the move.
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns a short description of what the move does, such as `Forks the King and Rook`.
+    pub fn get_rationale(&self) -> &str {
+        &self.rationale
+    }
+
+    /// Returns the line the engine expects to follow, starting with the move.
+    pub fn get_principal_variation(&self) -> &[ChessMoveType] {
+        &self.principal_variation
+    }
+}
+
+/// Returns the engine's top moves in the current position, best first.
+///
+/// Every legal move is searched to the engine's depth, then the best `count` moves are
+/// tagged with their SAN, score and a short rationale generated from the tactical motifs
+/// the move creates, such as captures, forks and threats.
+///
+/// # Arguments
+///
+/// * `game` - The game to suggest moves for, the game is not modified.
+/// * `engine` - The engine used to score the moves.
+/// * `count` - The maximum number of moves to return.
+///
+/// # Returns
+///
+/// * `Vec<CandidateMove>` - Up to `count` moves, ordered from best to worst.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::candidate_moves::get_candidate_moves;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::Engine;
+///
+/// let game = build_game_from_string("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+/// let candidates = get_candidate_moves(&game, &mut Engine::new().set_depth(2), 3);
+///
+/// assert_eq!(3, candidates.len());
+/// assert_eq!("Rxd5", candidates[0].get_san());
+/// assert_eq!("Captures the Queen on d5", candidates[0].get_rationale());
+/// ```
+pub fn get_candidate_moves(
+    game: &ChessGame,
+    engine: &mut Engine,
+    count: usize,
+) -> Vec<CandidateMove> {
+    engine
+        .search_root_moves(game, engine.get_depth())
+        .into_iter()
+        .take(count)
+        .filter_map(|result| build_candidate_move(game, result))
+        .collect()
+}
+
+fn build_candidate_move(game: &ChessGame, result: SearchResult) -> Option<CandidateMove> {
+    let chess_move = *result.get_best_move()?;
+    Some(CandidateMove {
+        chess_move,
+        san: encode_move_as_string(game, &chess_move),
+        score: result.get_score(),
+        rationale: describe_move(game, &chess_move, &result),
+        principal_variation: result.get_principal_variation().to_vec(),
+    })
+}
+
+fn describe_move(game: &ChessGame, chess_move: &ChessMoveType, result: &SearchResult) -> String {
+    let mut after = game.clone();
+    after.apply_move(*chess_move);
+    let opponent = after.get_current_players_turn();
+    let gives_check = is_in_check(opponent, after.get_board());
+
+    if gives_check && after.get_cached_legal_moves().is_empty() {
+        return String::from("Delivers checkmate");
+    }
+    if result.is_mate_score() && result.get_score() > 0 {
+        let moves_to_mate = (MATE_SCORE - result.get_score() + 1) / 2;
+        return format!("Forces mate in {}", moves_to_mate);
+    }
+
+    let description = match chess_move {
+        ChessMoveType::Castle { .. } => String::from("Castles the King to safety"),
+        ChessMoveType::EnPassant { .. } => String::from("Captures the Pawn en passant"),
+        ChessMoveType::Move {
+            new_position,
+            piece,
+            taken_piece,
+            promotion,
+            ..
+        } => describe_piece_move(&after, *new_position, piece, taken_piece, promotion),
+    };
+
+    if gives_check && description != "Gives check" {
+        format!("{} with check", description)
+    } else {
+        description
+    }
+}
+
+fn describe_piece_move(
+    after: &ChessGame,
+    new_position: (usize, usize),
+    piece: &ChessPiece,
+    taken_piece: &Option<ChessPiece>,
+    promotion: &Option<ChessPiece>,
+) -> String {
+    let board = after.get_board();
+    let mover = board
+        .get_piece_at_space(new_position.0, new_position.1)
+        .copied()
+        .unwrap_or(*piece);
+    let square_name = get_square_name_from_row_and_col(new_position.0, new_position.1);
+
+    if let Some(promotion) = promotion {
+        return format!("Promotes to a {:?}", promotion.get_piece_type());
+    }
+    if let Some(taken_piece) = taken_piece {
+        return format!(
+            "Captures the {:?} on {}",
+            taken_piece.get_piece_type(),
+            square_name
+        );
+    }
+
+    let targets = get_threatened_pieces(after, new_position, &mover);
+    if targets.len() >= 2 {
+        return format!(
+            "Forks the {:?} and {:?}",
+            targets[0].get_piece_type(),
+            targets[1].get_piece_type()
+        );
+    }
+    if is_in_check(after.get_current_players_turn(), board) {
+        return String::from("Gives check");
+    }
+    if let Some(target) = targets.first() {
+        return format!("Threatens the {:?}", target.get_piece_type());
+    }
+    if mover.get_piece_type() != PieceType::King
+        && is_square_attacked(
+            board,
+            new_position.0,
+            new_position.1,
+            mover.get_color().opposite(),
+        )
+        && !is_square_attacked(board, new_position.0, new_position.1, mover.get_color())
+    {
+        return format!(
+            "Leaves the {:?} undefended on {}",
+            mover.get_piece_type(),
+            square_name
+        );
+    }
+
+    match mover.get_piece_type() {
+        PieceType::Pawn => format!("Advances the Pawn to {}", square_name),
+        PieceType::Knight | PieceType::Bishop => {
+            format!(
+                "Develops the {:?} to {}",
+                mover.get_piece_type(),
+                square_name
+            )
+        }
+        piece_type => format!("Moves the {:?} to {}", piece_type, square_name),
+    }
+}
+
+/// Returns the enemy pieces the piece on the given square attacks that are either worth
+/// more than it or undefended, most valuable first.
+fn get_threatened_pieces(
+    after: &ChessGame,
+    position: (usize, usize),
+    mover: &ChessPiece,
+) -> Vec<ChessPiece> {
+    let board = after.get_board();
+    let mut targets: Vec<ChessPiece> = mover
+        .possible_moves_with_ruleset(
+            position,
+            board,
+            after.get_en_passant_target(),
+            after.get_ruleset(),
+        )
+        .into_iter()
+        .filter_map(|chess_move| match chess_move {
+            ChessMoveType::Move {
+                new_position,
+                taken_piece: Some(taken_piece),
+                ..
+            } => {
+                let is_valuable = taken_piece.get_piece_type() == PieceType::King
+                    || piece_value(taken_piece.get_piece_type())
+                        > piece_value(mover.get_piece_type());
+                let is_undefended = !is_square_attacked(
+                    board,
+                    new_position.0,
+                    new_position.1,
+                    taken_piece.get_color(),
+                );
+                if is_valuable || is_undefended {
+                    Some(taken_piece)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+        .collect();
+
+    targets.sort_by_key(|target| match target.get_piece_type() {
+        PieceType::King => i32::MIN,
+        piece_type => -piece_value(piece_type),
+    });
+    targets.dedup();
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    fn find_candidate<'a>(candidates: &'a [CandidateMove], san: &str) -> &'a CandidateMove {
+        candidates
+            .iter()
+            .find(|candidate| candidate.get_san() == san)
+            .unwrap_or_else(|| panic!("{} is not a candidate", san))
+    }
+
+    #[test]
+    fn candidates_are_sorted_and_limited() {
+        let game = ChessGame::new();
+        let candidates = get_candidate_moves(&game, &mut Engine::new().set_depth(1), 5);
+
+        assert_eq!(5, candidates.len());
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].get_score() >= pair[1].get_score()));
+    }
+
+    #[test]
+    fn checkmate_and_forks_are_described() {
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let candidates = get_candidate_moves(&game, &mut Engine::new().set_depth(1), 1);
+        assert_eq!("Ra8#", candidates[0].get_san());
+        assert_eq!("Delivers checkmate", candidates[0].get_rationale());
+
+        let game = build_game_from_string("r3k3/8/8/3N4/8/8/8/4K3 w - - 0 1").unwrap();
+        let candidates = get_candidate_moves(&game, &mut Engine::new().set_depth(1), 30);
+        assert_eq!(
+            "Forks the King and Rook with check",
+            find_candidate(&candidates, "Nc7+").get_rationale()
+        );
+    }
+
+    #[test]
+    fn quiet_moves_are_described() {
+        let game = ChessGame::new();
+        let candidates = get_candidate_moves(&game, &mut Engine::new().set_depth(1), 20);
+
+        assert_eq!(
+            "Develops the Knight to f3",
+            find_candidate(&candidates, "Nf3").get_rationale()
+        );
+        assert_eq!(
+            "Advances the Pawn to e4",
+            find_candidate(&candidates, "e4").get_rationale()
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::attack_map::{get_attacked_squares, AttackMap};
+use crate::chess_game_move_analyzer::get_legal_moves_for_color;
+use crate::piece::ChessPiece;
+use crate::position::Position;
+use crate::{ChessGame, Color};
+use game_board::SquareSet;
+
+/// A value for every square of a board, such as how strongly each player controls it.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::square_control_heatmap;
+/// use simple_chess::ChessGame;
+///
+/// let heatmap = square_control_heatmap(&ChessGame::new());
+///
+/// // f3 is attacked by three white pieces, f6 by three black pieces
+/// assert_eq!(3, heatmap.get_value(5, 2));
+/// assert_eq!(-3, heatmap.get_value(5, 5));
+/// assert_eq!(0, heatmap.get_value(4, 3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heatmap {
+    width: usize,
+    height: usize,
+    values: Vec<i32>,
+}
+
+impl Heatmap {
+    /// Returns the width of the board the heatmap covers.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the board the heatmap covers.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the value of a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column of the square.
+    /// * `row` - The row of the square.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the square is outside of the board.
+    pub fn get_value(&self, col: usize, row: usize) -> i32 {
+        assert!(
+            col < self.width && row < self.height,
+            "the square ({col}, {row}) is outside of the board"
+        );
+        self.values[row * self.width + col]
+    }
+
+    /// Returns the values of a row, from the first column to the last.
+    pub fn get_row(&self, row: usize) -> &[i32] {
+        &self.values[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Returns the largest absolute value of any square, `0` for an empty heatmap, which
+    /// user interfaces can scale their colors by.
+    pub fn get_max_magnitude(&self) -> i32 {
+        self.values
+            .iter()
+            .map(|value| value.abs())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Computes how strongly each player controls every square of the current position.
+///
+/// The value of a square is the number of white pieces attacking it minus the number of
+/// black pieces attacking it, as counted by `AttackMap`, so positive squares are
+/// controlled by White and negative squares by Black.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+///
+/// # Returns
+///
+/// The `Heatmap` of the net attackers of every square.
+pub fn square_control_heatmap(game: &ChessGame) -> Heatmap {
+    let board = game.get_board();
+    let attack_map = AttackMap::new(board);
+    let (width, height) = (board.get_width(), board.get_height());
+    let mut values = Vec::with_capacity(width * height);
+    for row in 0..height {
+        for col in 0..width {
+            values.push(
+                attack_map.get_attack_count(col, row, Color::White) as i32
+                    - attack_map.get_attack_count(col, row, Color::Black) as i32,
+            );
+        }
+    }
+    Heatmap {
+        width,
+        height,
+        values,
+    }
+}
+
+/// How active a single piece is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PieceActivity {
+    piece: ChessPiece,
+    attacked_squares: SquareSet,
+    legal_moves: usize,
+}
+
+impl PieceActivity {
+    /// Returns the piece.
+    pub fn get_piece(&self) -> ChessPiece {
+        self.piece
+    }
+
+    /// Returns the squares the piece attacks, whether they are empty or occupied.
+    pub fn get_attacked_squares(&self) -> &SquareSet {
+        &self.attacked_squares
+    }
+
+    /// Returns the number of legal moves the piece has, counted as if it were its
+    /// player's turn.
+    pub fn get_legal_moves(&self) -> usize {
+        self.legal_moves
+    }
+}
+
+/// Describes how active the piece on a square is in the current position of a game.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+/// * `position` - The square of the piece.
+///
+/// # Returns
+///
+/// * `Some(PieceActivity)` - The activity of the piece.
+/// * `None` - If the square is empty.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::piece_activity;
+/// use simple_chess::position::Position;
+/// use simple_chess::ChessGame;
+///
+/// let game = ChessGame::new();
+/// let knight = piece_activity(&game, Position::new(6, 0)).unwrap();
+///
+/// // the knight on g1 attacks e2, f3 and h3, and can move to the two empty ones
+/// assert_eq!(3, knight.get_attacked_squares().len());
+/// assert_eq!(2, knight.get_legal_moves());
+/// ```
+pub fn piece_activity(game: &ChessGame, position: Position) -> Option<PieceActivity> {
+    let board = game.get_board();
+    let square = position.get_col_and_row();
+    let piece = *board.get_piece_at_space(square.0, square.1)?;
+
+    let mut attacked_squares = SquareSet::new(board.get_width(), board.get_height());
+    for (col, row) in get_attacked_squares(board, square, piece) {
+        attacked_squares.insert(col, row);
+    }
+
+    let legal_moves = get_legal_moves_for_color(&mut game.clone(), piece.get_color())
+        .iter()
+        .filter(|chess_move| chess_move.get_original_position() == square)
+        .count();
+
+    Some(PieceActivity {
+        piece,
+        attacked_squares,
+        legal_moves,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn pinned_pieces_attack_squares_they_can_not_move_to() {
+        // the knight on e2 is pinned by the rook on e8
+        let game = build_game_from_string("k3r3/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+        let knight = piece_activity(&game, Position::new(4, 1)).unwrap();
+
+        assert_eq!(6, knight.get_attacked_squares().len());
+        assert_eq!(0, knight.get_legal_moves());
+        assert!(piece_activity(&game, Position::new(4, 2)).is_none());
+
+        let heatmap = square_control_heatmap(&game);
+        // e2 is defended by the king as often as the rook attacks it
+        assert_eq!(0, heatmap.get_value(4, 1));
+        assert_eq!(&[0, 0, 1, 0, -1, 0, 1, 0], heatmap.get_row(2));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::chess_board::ChessBoard;
+use crate::chess_game_state_analyzer::is_square_attacked;
+use crate::piece::PieceType;
+use crate::{ChessGame, Color};
+
+/// The bonus for each pawn sheltering the king.
+const SHELTER_PAWN_BONUS: i32 = 15;
+
+/// The penalty for each file next to the king without any pawns.
+const OPEN_FILE_PENALTY: i32 = 25;
+
+/// The penalty for each file next to the king with only opposing pawns.
+const HALF_OPEN_FILE_PENALTY: i32 = 15;
+
+/// The penalty for each square around the king the opponent attacks.
+const ATTACKED_SQUARE_PENALTY: i32 = 10;
+
+/// How safe a player's king is, judged by the pawns in front of it and the lines leading
+/// to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KingSafety {
+    king_square: (usize, usize),
+    shelter_pawns: usize,
+    open_files: usize,
+    half_open_files: usize,
+    attacked_squares: usize,
+}
+
+impl KingSafety {
+    /// Returns the square of the king.
+    pub fn get_king_square(&self) -> (usize, usize) {
+        self.king_square
+    }
+
+    /// Returns the number of the player's pawns on the king's file and the files next to
+    /// it, one or two rows in front of the king.
+    pub fn get_shelter_pawns(&self) -> usize {
+        self.shelter_pawns
+    }
+
+    /// Returns the number of files among the king's file and the files next to it without
+    /// any pawns.
+    pub fn get_open_files(&self) -> usize {
+        self.open_files
+    }
+
+    /// Returns the number of files among the king's file and the files next to it with
+    /// only opposing pawns, which the opponent's rooks can still use against the king.
+    pub fn get_half_open_files(&self) -> usize {
+        self.half_open_files
+    }
+
+    /// Returns the number of squares next to the king the opponent attacks.
+    pub fn get_attacked_squares(&self) -> usize {
+        self.attacked_squares
+    }
+
+    /// Returns a score for the safety of the king in centipawns, higher when the king is
+    /// safer. Shelter pawns add to the score, open and half-open files and attacked squares
+    /// take from it.
+    pub fn get_score(&self) -> i32 {
+        self.shelter_pawns as i32 * SHELTER_PAWN_BONUS
+            - self.open_files as i32 * OPEN_FILE_PENALTY
+            - self.half_open_files as i32 * HALF_OPEN_FILE_PENALTY
+            - self.attacked_squares as i32 * ATTACKED_SQUARE_PENALTY
+    }
+}
+
+/// Judges how safe a player's king is in the current position of a game.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+/// * `color` - The player whose king to judge.
+///
+/// # Returns
+///
+/// * `Some(KingSafety)` - The safety of the player's king.
+/// * `None` - If the player has no king, or several in study mode.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::king_safety;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::Color;
+///
+/// // White's castled king keeps its pawns, Black's king has lost the d, e and f pawns
+/// let game = build_game_from_string("r3k2r/ppp3pp/8/8/8/8/PPPPPPPP/R4RK1 w - - 0 1").unwrap();
+/// let white = king_safety(&game, Color::White).unwrap();
+/// let black = king_safety(&game, Color::Black).unwrap();
+///
+/// assert_eq!(3, white.get_shelter_pawns());
+/// assert_eq!(3, black.get_half_open_files());
+/// assert!(white.get_score() > black.get_score());
+/// ```
+pub fn king_safety(game: &ChessGame, color: Color) -> Option<KingSafety> {
+    let board = game.get_board();
+    let mut kings = board
+        .pieces_of(color)
+        .filter(|(_, piece)| piece.get_piece_type() == PieceType::King);
+    let king = kings.next()?;
+    if kings.next().is_some() {
+        return None;
+    }
+    let (king_col, king_row) = (king.0.get_col(), king.0.get_row());
+
+    let mut safety = KingSafety {
+        king_square: (king_col, king_row),
+        shelter_pawns: 0,
+        open_files: 0,
+        half_open_files: 0,
+        attacked_squares: 0,
+    };
+    let first_col = king_col.saturating_sub(1);
+    let last_col = (king_col + 1).min(board.get_width() - 1);
+    for col in first_col..=last_col {
+        let mut has_own_pawn = false;
+        let mut has_opposing_pawn = false;
+        for row in 0..board.get_height() {
+            let Some(piece) = board.get_piece_at_space(col, row) else {
+                continue;
+            };
+            if piece.get_piece_type() != PieceType::Pawn {
+                continue;
+            }
+            if piece.get_color() == color {
+                has_own_pawn = true;
+                let distance = (row as i32 - king_row as i32) * color.forward();
+                if (1..=2).contains(&distance) {
+                    safety.shelter_pawns += 1;
+                }
+            } else {
+                has_opposing_pawn = true;
+            }
+        }
+        match (has_own_pawn, has_opposing_pawn) {
+            (false, false) => safety.open_files += 1,
+            (false, true) => safety.half_open_files += 1,
+            _ => {}
+        }
+    }
+
+    for row in king_row.saturating_sub(1)..=(king_row + 1).min(board.get_height() - 1) {
+        for col in first_col..=last_col {
+            if (col, row) != (king_col, king_row)
+                && is_square_attacked(board, col, row, color.opposite())
+            {
+                safety.attacked_squares += 1;
+            }
+        }
+    }
+    Some(safety)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn half_open_files_and_attacked_squares_weaken_the_king() {
+        // the white pawn on g5 leaves Black's g file half open and the bishop attacks f7
+        let game = build_game_from_string("6k1/5p1p/8/6P1/8/1B6/8/K7 b - - 0 1").unwrap();
+        let safety = king_safety(&game, Color::Black).unwrap();
+
+        assert_eq!((6, 7), safety.get_king_square());
+        assert_eq!(2, safety.get_shelter_pawns());
+        assert_eq!(1, safety.get_half_open_files());
+        assert_eq!(0, safety.get_open_files());
+        assert_eq!(1, safety.get_attacked_squares());
+    }
+}
@@ -0,0 +1,253 @@
+use crate::chess_game_state_analyzer::{is_square_attacked, GameState};
+use crate::piece::{ChessPiece, PieceType};
+use crate::position::{find_king, offset_square};
+use crate::{ChessGame, Color};
+use game_board::Board;
+use std::fmt::{Display, Formatter};
+
+/// The offsets a knight jumps by.
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+/// A classic checkmate pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatePattern {
+    /// A rook or queen mates a king on its back rank, trapped by its own pieces in front of
+    /// it.
+    BackRank,
+    /// A knight mates a king surrounded by its own pieces.
+    Smothered,
+    /// A rook or queen mates a king on the edge file, with a knight covering its escape
+    /// squares and one of its own pieces beside it.
+    Anastasia,
+    /// A rook next to a cornered king mates it, defended by a knight that also covers the
+    /// king's escape square.
+    Arabian,
+}
+
+impl MatePattern {
+    /// Returns the name of the pattern, such as `"Back-rank mate"`.
+    pub fn get_name(&self) -> &str {
+        match self {
+            MatePattern::BackRank => "Back-rank mate",
+            MatePattern::Smothered => "Smothered mate",
+            MatePattern::Anastasia => "Anastasia's mate",
+            MatePattern::Arabian => "Arabian mate",
+        }
+    }
+}
+
+impl Display for MatePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+/// Returns the classic patterns a checkmate matches.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+///
+/// # Returns
+///
+/// The patterns the position matches, empty if the player to move is not checkmated or the
+/// mate does not follow a known pattern.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::mate_patterns::{find_mate_patterns, MatePattern};
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// let smothered = build_game_from_string("6rk/5Npp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+/// assert_eq!(vec![MatePattern::Smothered], find_mate_patterns(&smothered));
+/// ```
+pub fn find_mate_patterns(game: &ChessGame) -> Vec<MatePattern> {
+    let color = game.get_current_players_turn();
+    if !matches!(game.clone().get_game_state(), GameState::Checkmate { .. }) {
+        return Vec::new();
+    }
+    let board = game.get_board();
+    let Some(king) = find_king(board, color) else {
+        return Vec::new();
+    };
+
+    let checkers = get_attackers(board, king, color.opposite());
+    let neighbors = get_neighbors(board, king);
+    let is_own_piece = |square: &(usize, usize)| get_piece_color(board, *square) == Some(color);
+    let is_knight_covering = neighbors.iter().any(|&square| {
+        get_knight_squares(board, square).into_iter().any(|from| {
+            board.get_piece_at_space(from.0, from.1)
+                == Some(&ChessPiece::new(PieceType::Knight, color.opposite()))
+        })
+    });
+    let line_checker = checkers
+        .iter()
+        .find(|(_, piece)| matches!(piece.get_piece_type(), PieceType::Rook | PieceType::Queen));
+
+    let mut patterns = Vec::new();
+
+    if let Some(&(square, _)) = line_checker {
+        let forward_row = color.relative_rank(1, board.get_height()) as i32;
+        let escape_squares: Vec<(usize, usize)> = neighbors
+            .iter()
+            .copied()
+            .filter(|&(_, row)| row as i32 == forward_row)
+            .collect();
+        if king.1 == color.relative_rank(0, board.get_height())
+            && square.1 == king.1
+            && escape_squares.iter().any(is_own_piece)
+            && escape_squares.iter().all(|&(col, row)| {
+                is_own_piece(&(col, row)) || is_square_attacked(board, col, row, color.opposite())
+            })
+        {
+            patterns.push(MatePattern::BackRank);
+        }
+
+        let is_edge_file = king.0 == 0 || king.0 == board.get_width() - 1;
+        if is_edge_file
+            && square.0 == king.0
+            && is_knight_covering
+            && neighbors.iter().any(is_own_piece)
+        {
+            patterns.push(MatePattern::Anastasia);
+        }
+    }
+
+    if checkers.len() == 1
+        && checkers[0].1.get_piece_type() == PieceType::Knight
+        && neighbors.iter().all(is_own_piece)
+    {
+        patterns.push(MatePattern::Smothered);
+    }
+
+    let is_corner = (king.0 == 0 || king.0 == board.get_width() - 1)
+        && (king.1 == 0 || king.1 == board.get_height() - 1);
+    let adjacent_rook = checkers.iter().find(|((col, row), piece)| {
+        piece.get_piece_type() == PieceType::Rook
+            && col.abs_diff(king.0) + row.abs_diff(king.1) == 1
+    });
+    if let Some(&(rook, _)) = adjacent_rook {
+        let is_defended_by_knight = get_knight_squares(board, rook).into_iter().any(|from| {
+            board.get_piece_at_space(from.0, from.1)
+                == Some(&ChessPiece::new(PieceType::Knight, color.opposite()))
+        });
+        if is_corner && is_defended_by_knight {
+            patterns.push(MatePattern::Arabian);
+        }
+    }
+
+    patterns
+}
+
+/// Returns the pieces of a color that attack a square holding a piece of the other color.
+fn get_attackers(
+    board: &Board<ChessPiece>,
+    square: (usize, usize),
+    by_color: Color,
+) -> Vec<((usize, usize), ChessPiece)> {
+    let mut attackers = Vec::new();
+    for row in 0..board.get_height() {
+        for col in 0..board.get_width() {
+            if let Some(piece) = board.get_piece_at_space(col, row) {
+                if piece.get_color() == by_color
+                    && piece
+                        .possible_moves((col, row), board, None)
+                        .iter()
+                        .any(|chess_move| chess_move.get_new_position() == square)
+                {
+                    attackers.push(((col, row), *piece));
+                }
+            }
+        }
+    }
+    attackers
+}
+
+fn get_neighbors(board: &Board<ChessPiece>, square: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut neighbors = Vec::new();
+    for col_offset in -1..=1 {
+        for row_offset in -1..=1 {
+            if col_offset == 0 && row_offset == 0 {
+                continue;
+            }
+            if let Some(neighbor) = offset_square(board, square, (col_offset, row_offset)) {
+                neighbors.push(neighbor);
+            }
+        }
+    }
+    neighbors
+}
+
+fn get_knight_squares(board: &Board<ChessPiece>, square: (usize, usize)) -> Vec<(usize, usize)> {
+    KNIGHT_OFFSETS
+        .iter()
+        .filter_map(|&offset| offset_square(board, square, offset))
+        .collect()
+}
+
+fn get_piece_color(board: &Board<ChessPiece>, square: (usize, usize)) -> Option<Color> {
+    board
+        .get_piece_at_space(square.0, square.1)
+        .map(|piece| piece.get_color())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    fn patterns(fen: &str) -> Vec<MatePattern> {
+        find_mate_patterns(&build_game_from_string(fen).unwrap())
+    }
+
+    #[test]
+    fn back_rank_mate() {
+        assert_eq!(
+            vec![MatePattern::BackRank],
+            patterns("3R2k1/5ppp/8/8/8/8/8/6K1 b - - 0 1")
+        );
+        // black mating white on white's back rank
+        assert_eq!(
+            vec![MatePattern::BackRank],
+            patterns("6k1/8/8/8/8/8/5PPP/3r2K1 w - - 0 1")
+        );
+    }
+
+    #[test]
+    fn anastasias_mate() {
+        assert_eq!(
+            vec![MatePattern::Anastasia],
+            patterns("8/4N1pk/8/8/8/8/8/6KR b - - 0 1")
+        );
+    }
+
+    #[test]
+    fn arabian_mate() {
+        assert_eq!(
+            vec![MatePattern::Arabian],
+            patterns("7k/7R/5N2/8/8/8/8/6K1 b - - 0 1")
+        );
+    }
+
+    #[test]
+    fn positions_that_are_not_checkmate_have_no_pattern() {
+        // the back rank check can be blocked
+        assert!(patterns("3R2k1/5ppp/8/8/8/8/4r3/6K1 b - - 0 1").is_empty());
+        assert!(patterns("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_empty());
+    }
+
+    #[test]
+    fn names_are_displayed() {
+        assert_eq!("Anastasia's mate", MatePattern::Anastasia.to_string());
+    }
+}
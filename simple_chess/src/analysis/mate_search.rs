@@ -0,0 +1,132 @@
+use crate::chess_game_state_analyzer::is_current_player_in_check;
+use crate::{ChessGame, ChessMoveType};
+
+/// Searches for a forced checkmate for the player to move.
+///
+/// Unlike the engine's search this does not evaluate positions, it only looks for lines
+/// where every reply of the defender still loses to checkmate, which is what puzzle
+/// solvers need. Checks are tried first, as most mating lines are built from them.
+///
+/// # Arguments
+///
+/// * `game` - The game to search, the game is not modified.
+/// * `max_depth` - The largest number of moves of the attacking player to look for a mate
+///   in, `2` finds mates in one and two.
+///
+/// # Returns
+///
+/// * `Some(Vec<ChessMoveType>)` - The shortest forced mate, with the defender's most
+///   stubborn replies, ending with the mating move.
+/// * `None` - If there is no forced mate within `max_depth` moves.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::find_mate;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::codec::standard_algebraic_notation::encode_move_as_string;
+///
+/// let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/2R3K1 w - - 0 1").unwrap();
+/// let line = find_mate(&game, 3).unwrap();
+///
+/// assert_eq!(1, line.len());
+/// assert_eq!("Rc8#", encode_move_as_string(&game, &line[0]));
+/// ```
+pub fn find_mate(game: &ChessGame, max_depth: usize) -> Option<Vec<ChessMoveType>> {
+    (1..=max_depth).find_map(|depth| find_mate_in(game, depth))
+}
+
+/// Finds a mate in at most `depth` moves, not necessarily the shortest one.
+fn find_mate_in(game: &ChessGame, depth: usize) -> Option<Vec<ChessMoveType>> {
+    let mut game = game.clone();
+    for chess_move in get_ordered_moves(&mut game) {
+        let mut after_move = game.clone();
+        after_move.apply_move(chess_move);
+
+        let replies = after_move.get_cached_legal_moves().to_vec();
+        if replies.is_empty() {
+            if is_current_player_in_check(&after_move) {
+                return Some(vec![chess_move]);
+            }
+            continue;
+        }
+        if depth == 1 {
+            continue;
+        }
+
+        // every reply has to lose, the line follows the one holding out longest
+        let mut longest_defense: Option<Vec<ChessMoveType>> = None;
+        for reply in replies {
+            let mut after_reply = after_move.clone();
+            after_reply.apply_move(reply);
+            let Some(line) = find_mate(&after_reply, depth - 1) else {
+                longest_defense = None;
+                break;
+            };
+            if longest_defense
+                .as_ref()
+                .is_none_or(|longest| line.len() + 1 > longest.len())
+            {
+                longest_defense = Some([vec![reply], line].concat());
+            }
+        }
+        if let Some(defense) = longest_defense {
+            return Some([vec![chess_move], defense].concat());
+        }
+    }
+    None
+}
+
+/// Returns the legal moves of the position with the checks first.
+fn get_ordered_moves(game: &mut ChessGame) -> Vec<ChessMoveType> {
+    let (mut checks, quiet): (Vec<ChessMoveType>, Vec<ChessMoveType>) = game
+        .get_cached_legal_moves()
+        .to_vec()
+        .into_iter()
+        .partition(|chess_move| {
+            let mut after_move = game.clone();
+            after_move.apply_move(*chess_move);
+            is_current_player_in_check(&after_move)
+        });
+    checks.extend(quiet);
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::encode_move_as_string;
+
+    fn encode_line(game: &ChessGame, line: &[ChessMoveType]) -> Vec<String> {
+        let mut game = game.clone();
+        line.iter()
+            .map(|chess_move| {
+                let san = encode_move_as_string(&game, chess_move);
+                game.apply_move(*chess_move);
+                san
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mates_in_two_are_found_with_the_defense() {
+        // the rooks close in on the king, which can only step to g8
+        let game = build_game_from_string("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1").unwrap();
+
+        assert_eq!(None, find_mate(&game, 1));
+        let line = find_mate(&game, 2).unwrap();
+        assert_eq!(3, line.len());
+        assert!(encode_line(&game, &line)[2].ends_with('#'));
+    }
+
+    #[test]
+    fn positions_without_a_forced_mate_return_none() {
+        let game = build_game_from_string("4k3/8/8/8/8/8/8/4K2N w - - 0 1").unwrap();
+        assert_eq!(None, find_mate(&game, 2));
+
+        // moves such as Qc7 leave the king without moves, but not in check
+        let stalemate = build_game_from_string("k7/8/1Q6/8/8/8/8/K7 w - - 0 1").unwrap();
+        assert_eq!(None, find_mate(&stalemate, 1));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::chess_board::ChessBoard;
+use crate::chess_game_move_analyzer::get_legal_moves_for_color;
+use crate::engine::evaluation::piece_value;
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, Color};
+use game_board::Board;
+
+/// The values of the pieces used to count material.
+///
+/// The default values are the engine's, in centipawns: a pawn is worth 100, a knight 320, a
+/// bishop 330, a rook 500 and a queen 900. Kings are worth nothing, as both players always
+/// have one.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::PieceValues;
+/// use simple_chess::piece::PieceType;
+///
+/// // the classic 1, 3, 3, 5, 9 values
+/// let values = PieceValues::new()
+///     .set_value(PieceType::Pawn, 1)
+///     .set_value(PieceType::Knight, 3)
+///     .set_value(PieceType::Bishop, 3)
+///     .set_value(PieceType::Rook, 5)
+///     .set_value(PieceType::Queen, 9);
+///
+/// assert_eq!(3, values.get_value(PieceType::Bishop));
+/// assert_eq!(0, values.get_value(PieceType::King));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PieceValues {
+    pawn: i32,
+    knight: i32,
+    bishop: i32,
+    rook: i32,
+    queen: i32,
+    archbishop: i32,
+    chancellor: i32,
+    king: i32,
+}
+
+impl PieceValues {
+    /// Creates the default piece values, the same as the engine's.
+    pub fn new() -> Self {
+        Self {
+            pawn: piece_value(PieceType::Pawn),
+            knight: piece_value(PieceType::Knight),
+            bishop: piece_value(PieceType::Bishop),
+            rook: piece_value(PieceType::Rook),
+            queen: piece_value(PieceType::Queen),
+            archbishop: piece_value(PieceType::Archbishop),
+            chancellor: piece_value(PieceType::Chancellor),
+            king: piece_value(PieceType::King),
+        }
+    }
+
+    /// Sets the value of a piece type.
+    ///
+    /// # Arguments
+    ///
+    /// * `piece_type` - The piece type to set the value of.
+    /// * `value` - The value of one piece of the type.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `PieceValues` with the value set.
+    pub fn set_value(mut self, piece_type: PieceType, value: i32) -> Self {
+        match piece_type {
+            PieceType::Pawn => self.pawn = value,
+            PieceType::Knight => self.knight = value,
+            PieceType::Bishop => self.bishop = value,
+            PieceType::Rook => self.rook = value,
+            PieceType::Queen => self.queen = value,
+            PieceType::Archbishop => self.archbishop = value,
+            PieceType::Chancellor => self.chancellor = value,
+            PieceType::King => self.king = value,
+        }
+        self
+    }
+
+    /// Returns the value of a piece type.
+    pub fn get_value(&self, piece_type: PieceType) -> i32 {
+        match piece_type {
+            PieceType::Pawn => self.pawn,
+            PieceType::Knight => self.knight,
+            PieceType::Bishop => self.bishop,
+            PieceType::Rook => self.rook,
+            PieceType::Queen => self.queen,
+            PieceType::Archbishop => self.archbishop,
+            PieceType::Chancellor => self.chancellor,
+            PieceType::King => self.king,
+        }
+    }
+}
+
+impl Default for PieceValues {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counts the material on a board.
+///
+/// # Arguments
+///
+/// * `board` - The board to count the material of.
+/// * `values` - The value of each piece type.
+///
+/// # Returns
+///
+/// The value of White's pieces minus the value of Black's pieces, positive when White is
+/// ahead.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::{material_balance, PieceValues};
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// // White is a rook up
+/// let game = build_game_from_string("4k3/pp6/8/8/8/8/PP6/R3K3 w - - 0 1").unwrap();
+/// assert_eq!(500, material_balance(game.get_board(), &PieceValues::new()));
+/// ```
+pub fn material_balance(board: &Board<ChessPiece>, values: &PieceValues) -> i32 {
+    board
+        .pieces()
+        .map(|(_, piece)| {
+            let value = values.get_value(piece.get_piece_type());
+            match piece.get_color() {
+                Color::White => value,
+                Color::Black => -value,
+            }
+        })
+        .sum()
+}
+
+/// Counts the legal moves a player has in the current position of a game.
+///
+/// The moves are counted as if it were the player's turn, so the mobility of both players
+/// can be compared in any position.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+/// * `color` - The player whose moves to count.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::mobility;
+/// use simple_chess::{ChessGame, Color};
+///
+/// let game = ChessGame::new();
+/// assert_eq!(20, mobility(&game, Color::White));
+/// assert_eq!(20, mobility(&game, Color::Black));
+/// ```
+pub fn mobility(game: &ChessGame, color: Color) -> usize {
+    let mut game = game.clone();
+    if game.get_current_players_turn() == color {
+        game.get_cached_legal_moves().len()
+    } else {
+        get_legal_moves_for_color(&mut game, color).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn material_is_counted_with_the_given_values() {
+        let game = build_game_from_string("3qk3/8/8/8/8/8/8/RR2K3 w - - 0 1").unwrap();
+        assert_eq!(100, material_balance(game.get_board(), &PieceValues::new()));
+
+        let values = PieceValues::new()
+            .set_value(PieceType::Rook, 5)
+            .set_value(PieceType::Queen, 9);
+        assert_eq!(1, material_balance(game.get_board(), &values));
+    }
+
+    #[test]
+    fn mobility_only_counts_legal_moves() {
+        // the knight on e2 is pinned, so only the white king can move
+        let game = build_game_from_string("k3r3/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(4, mobility(&game, Color::White));
+        assert_eq!(15, mobility(&game, Color::Black));
+    }
+}
@@ -0,0 +1,17 @@
+pub mod candidate_moves;
+pub mod heatmap;
+pub mod king_safety;
+pub mod mate_patterns;
+pub mod mate_search;
+pub mod material;
+pub mod pawn_structure;
+pub mod review;
+pub mod tactics;
+pub mod tutorial;
+
+pub use heatmap::{piece_activity, square_control_heatmap, Heatmap, PieceActivity};
+pub use king_safety::{king_safety, KingSafety};
+pub use mate_search::find_mate;
+pub use material::{material_balance, mobility, PieceValues};
+pub use pawn_structure::{pawn_structure, PawnStructure};
+pub use review::annotate_game;
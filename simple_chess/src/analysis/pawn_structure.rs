@@ -0,0 +1,151 @@
+use crate::chess_board::ChessBoard;
+use crate::piece::{ChessPiece, PieceType};
+use crate::Color;
+use game_board::Board;
+
+/// The weaknesses and strengths of one player's pawns.
+///
+/// A pawn may be listed under more than one heading, an isolated pawn can be doubled and
+/// passed at the same time.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PawnStructure {
+    doubled: Vec<(usize, usize)>,
+    isolated: Vec<(usize, usize)>,
+    backward: Vec<(usize, usize)>,
+    passed: Vec<(usize, usize)>,
+}
+
+impl PawnStructure {
+    /// Returns the squares of the doubled pawns, the pawns with another pawn of the same
+    /// player behind them on their file. Three pawns on a file count as two doubled pawns.
+    pub fn get_doubled(&self) -> &[(usize, usize)] {
+        &self.doubled
+    }
+
+    /// Returns the squares of the isolated pawns, the pawns with no pawn of the same player
+    /// on a neighbouring file.
+    pub fn get_isolated(&self) -> &[(usize, usize)] {
+        &self.isolated
+    }
+
+    /// Returns the squares of the backward pawns, the pawns whose neighbours on both sides
+    /// have all advanced past them and which can not move up safely because an opposing
+    /// pawn guards the square in front of them.
+    pub fn get_backward(&self) -> &[(usize, usize)] {
+        &self.backward
+    }
+
+    /// Returns the squares of the passed pawns, the pawns with no opposing pawn in front of
+    /// them on their own or a neighbouring file.
+    pub fn get_passed(&self) -> &[(usize, usize)] {
+        &self.passed
+    }
+}
+
+/// Describes the pawn structure of one player.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at.
+/// * `color` - The player whose pawns to describe.
+///
+/// # Returns
+///
+/// The `PawnStructure`, each list ordered by square from a1 upwards.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::pawn_structure;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::Color;
+///
+/// // doubled pawns on the c file and an isolated pawn on a2, which is also passed
+/// let game = build_game_from_string("4k3/8/3p4/8/3P4/2P5/P1P5/4K3 w - - 0 1").unwrap();
+/// let structure = pawn_structure(game.get_board(), Color::White);
+///
+/// assert_eq!(&[(2, 2)], structure.get_doubled());
+/// assert_eq!(&[(0, 1)], structure.get_isolated());
+/// assert_eq!(&[(0, 1)], structure.get_passed());
+/// ```
+pub fn pawn_structure(board: &Board<ChessPiece>, color: Color) -> PawnStructure {
+    let height = board.get_height();
+    let mut own_pawns = get_pawns(board, color);
+    own_pawns.sort_by_key(|&(col, row)| (row, col));
+    let opposing_pawns = get_pawns(board, color.opposite());
+    // how far a pawn has advanced, counted from the player's back rank
+    let progress = |row: usize| color.relative_rank(row, height);
+
+    let mut structure = PawnStructure::default();
+    for &(col, row) in &own_pawns {
+        let neighbours: Vec<&(usize, usize)> = own_pawns
+            .iter()
+            .filter(|(other_col, _)| other_col.abs_diff(col) == 1)
+            .collect();
+
+        let is_doubled = own_pawns
+            .iter()
+            .any(|&(other_col, other_row)| other_col == col && progress(other_row) < progress(row));
+        if is_doubled {
+            structure.doubled.push((col, row));
+        }
+
+        if neighbours.is_empty() {
+            structure.isolated.push((col, row));
+        } else if neighbours
+            .iter()
+            .all(|(_, other_row)| progress(*other_row) > progress(row))
+            && is_stop_square_guarded(&opposing_pawns, (col, row), color)
+        {
+            structure.backward.push((col, row));
+        }
+
+        let is_passed = !opposing_pawns.iter().any(|&(other_col, other_row)| {
+            other_col.abs_diff(col) <= 1 && progress(other_row) > progress(row)
+        });
+        if is_passed {
+            structure.passed.push((col, row));
+        }
+    }
+    structure
+}
+
+fn get_pawns(board: &Board<ChessPiece>, color: Color) -> Vec<(usize, usize)> {
+    board
+        .pieces_of(color)
+        .filter(|(_, piece)| piece.get_piece_type() == PieceType::Pawn)
+        .map(|(position, _)| (position.get_col(), position.get_row()))
+        .collect()
+}
+
+/// Checks if an opposing pawn guards the square in front of a pawn.
+fn is_stop_square_guarded(
+    opposing_pawns: &[(usize, usize)],
+    (col, row): (usize, usize),
+    color: Color,
+) -> bool {
+    let stop_row = row as i32 + color.forward();
+    // opposing pawns guard the stop square from one row further on
+    let guard_row = stop_row + color.forward();
+    opposing_pawns.iter().any(|&(other_col, other_row)| {
+        other_col.abs_diff(col) == 1 && other_row as i32 == guard_row
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn backward_pawns_are_held_back_by_an_opposing_pawn() {
+        // the d6 pawn is behind the pawns on c5 and e5 and the d5 square is guarded from e4
+        let game = build_game_from_string("4k3/8/3p4/2p1p3/4P3/8/8/4K3 b - - 0 1").unwrap();
+        let structure = pawn_structure(game.get_board(), Color::Black);
+
+        assert_eq!(&[(3, 5)], structure.get_backward());
+        assert!(structure.get_isolated().is_empty());
+        assert!(structure.get_doubled().is_empty());
+        assert_eq!(&[(2, 4)], structure.get_passed());
+    }
+}
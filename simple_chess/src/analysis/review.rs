@@ -0,0 +1,448 @@
+use crate::analysis::mate_patterns::{find_mate_patterns, MatePattern};
+use crate::codec::standard_algebraic_notation::encode_move_as_string;
+use crate::engine::Engine;
+use crate::{ChessGame, ChessMoveType, Color};
+use std::fmt::{Display, Formatter};
+
+/// The centipawn loss from which a move is an inaccuracy.
+const INACCURACY_THRESHOLD: i32 = 50;
+
+/// The centipawn loss from which a move is a mistake.
+const MISTAKE_THRESHOLD: i32 = 100;
+
+/// The centipawn loss from which a move is a blunder.
+const BLUNDER_THRESHOLD: i32 = 300;
+
+/// The engine's verdict on a single move of a reviewed game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlyReview {
+    ply: usize,
+    color: Color,
+    chess_move: ChessMoveType,
+    san: String,
+    is_forced: bool,
+    best_score: i32,
+    played_score: i32,
+    mate_patterns: Vec<MatePattern>,
+}
+
+impl PlyReview {
+    /// Returns the number of the ply in the reviewed moves, starting at `0`.
+    pub fn get_ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Returns the color of the player who made the move.
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    /// Returns the move that was played.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.chess_move
+    }
+
+    /// Returns the move in Standard Algebraic Notation.
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Returns `true` if the move was the only legal move in the position.
+    pub fn is_forced(&self) -> bool {
+        self.is_forced
+    }
+
+    /// Returns the score of the best move in the position, from the point of view of the
+    /// player making the move.
+    pub fn get_best_score(&self) -> i32 {
+        self.best_score
+    }
+
+    /// Returns the score of the move that was played, from the point of view of the player
+    /// making the move.
+    pub fn get_played_score(&self) -> i32 {
+        self.played_score
+    }
+
+    /// Returns how many centipawns worse the played move was than the best move, `0` when
+    /// the best move was played.
+    pub fn get_centipawn_loss(&self) -> i32 {
+        self.best_score - self.played_score
+    }
+
+    /// Returns the classic patterns matched by the checkmate the move delivers, empty if
+    /// the move is not checkmate or the mate follows no known pattern.
+    pub fn get_mate_patterns(&self) -> &[MatePattern] {
+        &self.mate_patterns
+    }
+}
+
+/// The engine's review of a sequence of moves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameReview {
+    plies: Vec<PlyReview>,
+}
+
+impl GameReview {
+    /// Returns the review of every move, in the order they were played.
+    pub fn get_plies(&self) -> &[PlyReview] {
+        &self.plies
+    }
+
+    /// Returns the average centipawn loss of one player's moves.
+    ///
+    /// Forced moves are left out, a player who had only one legal move made no decision
+    /// and should neither be rewarded nor punished for it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` - The average loss of the player's unforced moves.
+    /// * `None` - If the player made no unforced moves.
+    pub fn get_average_centipawn_loss(&self, color: Color) -> Option<f64> {
+        let losses: Vec<i32> = self
+            .plies
+            .iter()
+            .filter(|ply| ply.color == color && !ply.is_forced)
+            .map(PlyReview::get_centipawn_loss)
+            .collect();
+        if losses.is_empty() {
+            None
+        } else {
+            Some(losses.iter().sum::<i32>() as f64 / losses.len() as f64)
+        }
+    }
+}
+
+/// Reviews a sequence of moves played from a position.
+///
+/// Every legal move of each position is searched to the engine's depth, and the move that
+/// was played is compared with the best one. Plies where only one legal move existed are
+/// tagged as forced and are not searched, and checkmates are tagged with the mate patterns
+/// they match.
+///
+/// # Arguments
+///
+/// * `game` - The game whose current position the moves were played from, the game is not
+///   modified.
+/// * `moves` - The moves to review, in the order they were played.
+/// * `engine` - The engine used to score the moves.
+///
+/// # Panics
+///
+/// Panics if a move is not legal in the position it is played in.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::review::review_moves;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessMoveType, Color};
+///
+/// // the rook on g1 leaves the black king on h8 a single move
+/// let game = build_game_from_string("7k/8/8/8/8/8/8/K5R1 b - - 0 1").unwrap();
+/// let forced = ChessMoveType::Move {
+///     original_position: (7, 7),
+///     new_position: (7, 6),
+///     piece: ChessPiece::new(PieceType::King, Color::Black),
+///     taken_piece: None,
+///     promotion: None,
+/// };
+///
+/// let review = review_moves(&game, &[forced], &mut Engine::new().set_depth(1));
+/// assert!(review.get_plies()[0].is_forced());
+/// assert_eq!(None, review.get_average_centipawn_loss(Color::Black));
+/// ```
+pub fn review_moves(game: &ChessGame, moves: &[ChessMoveType], engine: &mut Engine) -> GameReview {
+    let mut game = game.clone();
+    let mut plies = Vec::with_capacity(moves.len());
+
+    for (ply, chess_move) in moves.iter().enumerate() {
+        let color = game.get_current_players_turn();
+        let is_forced = game.is_forced_move();
+        let (best_score, played_score) = if is_forced {
+            (0, 0)
+        } else {
+            let results = engine.search_root_moves(&game, engine.get_depth());
+            let played_score = results
+                .iter()
+                .find(|result| result.get_best_move() == Some(chess_move))
+                .map(|result| result.get_score())
+                .unwrap_or_else(|| panic!("{} is not a legal move", chess_move));
+            (results[0].get_score(), played_score)
+        };
+
+        let san = encode_move_as_string(&game, chess_move);
+        game.apply_move(*chess_move);
+        let mate_patterns = if san.ends_with('#') {
+            find_mate_patterns(&game)
+        } else {
+            Vec::new()
+        };
+        plies.push(PlyReview {
+            ply,
+            color,
+            chess_move: *chess_move,
+            san,
+            is_forced,
+            best_score,
+            played_score,
+            mate_patterns,
+        });
+    }
+
+    GameReview { plies }
+}
+
+/// How good a move was, judged by how many centipawns it lost against the best move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveClassification {
+    /// The best move, or the only legal move.
+    Best,
+    /// A move losing less than 50 centipawns.
+    Good,
+    /// A move losing at least 50 centipawns.
+    Inaccuracy,
+    /// A move losing at least 100 centipawns.
+    Mistake,
+    /// A move losing at least 300 centipawns.
+    Blunder,
+}
+
+impl MoveClassification {
+    /// Classifies a move by the centipawns it lost against the best move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::analysis::review::MoveClassification;
+    ///
+    /// assert_eq!(MoveClassification::Best, MoveClassification::from_centipawn_loss(0));
+    /// assert_eq!(MoveClassification::Mistake, MoveClassification::from_centipawn_loss(150));
+    /// ```
+    pub fn from_centipawn_loss(centipawn_loss: i32) -> Self {
+        match centipawn_loss {
+            loss if loss >= BLUNDER_THRESHOLD => MoveClassification::Blunder,
+            loss if loss >= MISTAKE_THRESHOLD => MoveClassification::Mistake,
+            loss if loss >= INACCURACY_THRESHOLD => MoveClassification::Inaccuracy,
+            loss if loss > 0 => MoveClassification::Good,
+            _ => MoveClassification::Best,
+        }
+    }
+
+    /// Returns the symbol the classification is written with after a move in a game
+    /// score, such as `"??"` for a blunder, empty for best and good moves.
+    pub fn get_symbol(&self) -> &str {
+        match self {
+            MoveClassification::Best | MoveClassification::Good => "",
+            MoveClassification::Inaccuracy => "?!",
+            MoveClassification::Mistake => "?",
+            MoveClassification::Blunder => "??",
+        }
+    }
+}
+
+impl Display for MoveClassification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MoveClassification::Best => "best",
+            MoveClassification::Good => "good",
+            MoveClassification::Inaccuracy => "inaccuracy",
+            MoveClassification::Mistake => "mistake",
+            MoveClassification::Blunder => "blunder",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A move of an annotated game with the engine's verdict on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveAnnotation {
+    review: PlyReview,
+    classification: MoveClassification,
+}
+
+impl MoveAnnotation {
+    /// Returns the engine's review of the move, with the scores it was classified by.
+    pub fn get_review(&self) -> &PlyReview {
+        &self.review
+    }
+
+    /// Returns how good the move was.
+    pub fn get_classification(&self) -> MoveClassification {
+        self.classification
+    }
+
+    /// Returns the move in Standard Algebraic Notation.
+    pub fn get_san(&self) -> &str {
+        self.review.get_san()
+    }
+
+    /// Returns how many centipawns worse the move was than the best move.
+    pub fn get_centipawn_loss(&self) -> i32 {
+        self.review.get_centipawn_loss()
+    }
+}
+
+/// Annotates every move of a game, classifying each as best, good, an inaccuracy, a
+/// mistake or a blunder by its centipawn loss.
+///
+/// The moves are replayed from the position the game started from and reviewed with
+/// `review_moves`, using the built-in engine searching to the given depth.
+///
+/// # Arguments
+///
+/// * `game` - The game to annotate, the game is not modified.
+/// * `depth` - The depth, in plies, to search every position to.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::annotate_game;
+/// use simple_chess::analysis::review::MoveClassification;
+/// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+/// use simple_chess::ChessGame;
+///
+/// let mut game = ChessGame::new();
+/// for san in ["e4", "e5", "Qh5", "Ke7", "Qxe5#"] {
+///     let chess_move = decode_move_from_string(&game, san).unwrap();
+///     game.make_move(chess_move);
+/// }
+///
+/// let annotations = annotate_game(&game, 2);
+/// assert_eq!(5, annotations.len());
+/// assert_eq!("Ke7", annotations[3].get_san());
+/// assert_eq!(MoveClassification::Blunder, annotations[3].get_classification());
+/// assert_eq!(MoveClassification::Best, annotations[4].get_classification());
+/// ```
+pub fn annotate_game(game: &ChessGame, depth: u32) -> Vec<MoveAnnotation> {
+    let mut engine = Engine::new().set_depth(depth);
+    review_moves(&game.get_starting_game(), game.get_moves(), &mut engine)
+        .plies
+        .into_iter()
+        .map(|review| MoveAnnotation {
+            classification: MoveClassification::from_centipawn_loss(review.get_centipawn_loss()),
+            review,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+    use crate::piece::{ChessPiece, PieceType};
+
+    fn queen_move(
+        from: (usize, usize),
+        to: (usize, usize),
+        taken: Option<PieceType>,
+    ) -> ChessMoveType {
+        ChessMoveType::Move {
+            original_position: from,
+            new_position: to,
+            piece: ChessPiece::new(PieceType::Queen, Color::White),
+            taken_piece: taken.map(|piece_type| ChessPiece::new(piece_type, Color::Black)),
+            promotion: None,
+        }
+    }
+
+    #[test]
+    fn missed_captures_lose_centipawns() {
+        let game = build_game_from_string("4k3/8/8/3r4/8/8/8/3QK3 w - - 0 1").unwrap();
+        let mut engine = Engine::new().set_depth(1);
+
+        let best = review_moves(
+            &game,
+            &[queen_move((3, 0), (3, 4), Some(PieceType::Rook))],
+            &mut engine,
+        );
+        assert_eq!(0, best.get_plies()[0].get_centipawn_loss());
+        assert_eq!("Qxd5", best.get_plies()[0].get_san());
+        assert!(best.get_plies()[0].get_mate_patterns().is_empty());
+
+        let miss = review_moves(&game, &[queen_move((3, 0), (0, 3), None)], &mut engine);
+        assert!(miss.get_plies()[0].get_centipawn_loss() > 400);
+        assert!(!miss.get_plies()[0].is_forced());
+        assert_eq!(
+            Some(miss.get_plies()[0].get_centipawn_loss() as f64),
+            miss.get_average_centipawn_loss(Color::White)
+        );
+    }
+
+    #[test]
+    fn forced_moves_are_not_counted() {
+        let game = build_game_from_string("7k/8/8/8/8/8/8/K5R1 b - - 0 1").unwrap();
+        let king_move = ChessMoveType::Move {
+            original_position: (7, 7),
+            new_position: (7, 6),
+            piece: ChessPiece::new(PieceType::King, Color::Black),
+            taken_piece: None,
+            promotion: None,
+        };
+        let rook_move = ChessMoveType::Move {
+            original_position: (6, 0),
+            new_position: (6, 1),
+            piece: ChessPiece::new(PieceType::Rook, Color::White),
+            taken_piece: None,
+            promotion: None,
+        };
+
+        let review = review_moves(
+            &game,
+            &[king_move, rook_move],
+            &mut Engine::new().set_depth(1),
+        );
+        assert!(review.get_plies()[0].is_forced());
+        assert!(!review.get_plies()[1].is_forced());
+        assert_eq!(None, review.get_average_centipawn_loss(Color::Black));
+        assert!(review.get_average_centipawn_loss(Color::White).is_some());
+    }
+
+    #[test]
+    fn checkmates_are_tagged_with_their_pattern() {
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/3R2K1 w - - 0 1").unwrap();
+        let back_rank = ChessMoveType::Move {
+            original_position: (3, 0),
+            new_position: (3, 7),
+            piece: ChessPiece::new(PieceType::Rook, Color::White),
+            taken_piece: None,
+            promotion: None,
+        };
+
+        let review = review_moves(&game, &[back_rank], &mut Engine::new().set_depth(1));
+        assert_eq!("Rd8#", review.get_plies()[0].get_san());
+        assert_eq!(
+            &[MatePattern::BackRank],
+            review.get_plies()[0].get_mate_patterns()
+        );
+    }
+
+    #[test]
+    fn games_from_a_position_are_annotated_from_their_start() {
+        let mut game = build_game_from_string("4k3/8/8/3r4/8/8/8/R2QK2R w KQ - 0 1").unwrap();
+        for san in ["O-O", "Rxd1", "Rfxd1"] {
+            let chess_move = decode_move_from_string(&game, san).unwrap();
+            game.make_move(chess_move);
+        }
+
+        let annotations = annotate_game(&game, 1);
+        let sans: Vec<&str> = annotations.iter().map(MoveAnnotation::get_san).collect();
+        assert_eq!(vec!["O-O", "Rxd1", "Rfxd1"], sans);
+        // castling misses the undefended rook on d5, and leaves the queen to be taken
+        assert_eq!(
+            MoveClassification::Blunder,
+            annotations[0].get_classification()
+        );
+        assert_eq!(
+            MoveClassification::Best,
+            annotations[1].get_classification()
+        );
+        assert_eq!(
+            MoveClassification::Best,
+            annotations[2].get_classification()
+        );
+    }
+}
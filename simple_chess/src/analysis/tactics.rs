@@ -0,0 +1,451 @@
+use crate::attack_map::get_attacked_squares;
+use crate::chess_game_state_analyzer::is_square_attacked;
+use crate::engine::evaluation::piece_value;
+use crate::piece::{ChessPiece, PieceType};
+use crate::position::{find_king, offset_square};
+use crate::Color;
+use game_board::Board;
+
+/// The directions rooks move in.
+const ORTHOGONAL_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+/// The directions bishops move in.
+const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// A piece that can not move off a line without exposing its king to the piece behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pin {
+    pinned_square: (usize, usize),
+    pinned_piece: ChessPiece,
+    pinned_by_square: (usize, usize),
+    pinned_by: ChessPiece,
+    king_square: (usize, usize),
+}
+
+impl Pin {
+    /// Returns the square of the pinned piece.
+    pub fn get_pinned_square(&self) -> (usize, usize) {
+        self.pinned_square
+    }
+
+    /// Returns the pinned piece.
+    pub fn get_pinned_piece(&self) -> ChessPiece {
+        self.pinned_piece
+    }
+
+    /// Returns the square of the rook, bishop or queen pinning the piece.
+    pub fn get_pinned_by_square(&self) -> (usize, usize) {
+        self.pinned_by_square
+    }
+
+    /// Returns the rook, bishop or queen pinning the piece.
+    pub fn get_pinned_by(&self) -> ChessPiece {
+        self.pinned_by
+    }
+
+    /// Returns the square of the king the piece is pinned against.
+    pub fn get_king_square(&self) -> (usize, usize) {
+        self.king_square
+    }
+}
+
+/// A line piece attacking a valuable piece with a less valuable piece behind it, which is
+/// won once the front piece moves out of the way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Skewer {
+    attacker_square: (usize, usize),
+    attacker: ChessPiece,
+    front_square: (usize, usize),
+    front_piece: ChessPiece,
+    rear_square: (usize, usize),
+    rear_piece: ChessPiece,
+}
+
+impl Skewer {
+    /// Returns the square of the rook, bishop or queen giving the skewer.
+    pub fn get_attacker_square(&self) -> (usize, usize) {
+        self.attacker_square
+    }
+
+    /// Returns the rook, bishop or queen giving the skewer.
+    pub fn get_attacker(&self) -> ChessPiece {
+        self.attacker
+    }
+
+    /// Returns the square of the attacked piece that has to move.
+    pub fn get_front_square(&self) -> (usize, usize) {
+        self.front_square
+    }
+
+    /// Returns the attacked piece that has to move.
+    pub fn get_front_piece(&self) -> ChessPiece {
+        self.front_piece
+    }
+
+    /// Returns the square of the piece left behind.
+    pub fn get_rear_square(&self) -> (usize, usize) {
+        self.rear_square
+    }
+
+    /// Returns the piece left behind.
+    pub fn get_rear_piece(&self) -> ChessPiece {
+        self.rear_piece
+    }
+}
+
+/// A piece attacking two or more pieces at once, each of them the king, more valuable than
+/// the attacker or undefended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fork {
+    square: (usize, usize),
+    piece: ChessPiece,
+    targets: Vec<((usize, usize), ChessPiece)>,
+}
+
+impl Fork {
+    /// Returns the square of the forking piece.
+    pub fn get_square(&self) -> (usize, usize) {
+        self.square
+    }
+
+    /// Returns the forking piece.
+    pub fn get_piece(&self) -> ChessPiece {
+        self.piece
+    }
+
+    /// Returns the attacked pieces and their squares.
+    pub fn get_targets(&self) -> &[((usize, usize), ChessPiece)] {
+        &self.targets
+    }
+}
+
+/// A piece that is attacked and not defended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HangingPiece {
+    square: (usize, usize),
+    piece: ChessPiece,
+}
+
+impl HangingPiece {
+    /// Returns the square of the hanging piece.
+    pub fn get_square(&self) -> (usize, usize) {
+        self.square
+    }
+
+    /// Returns the hanging piece.
+    pub fn get_piece(&self) -> ChessPiece {
+        self.piece
+    }
+}
+
+/// Finds the pieces of a player that are pinned against their own king.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at.
+/// * `color` - The player whose pieces may be pinned.
+///
+/// # Returns
+///
+/// A `Pin` for every pinned piece, empty if the player has no king on the board.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::tactics::absolute_pins;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::piece::PieceType;
+/// use simple_chess::Color;
+///
+/// // the knight on e2 is pinned by the rook on e8
+/// let game = build_game_from_string("k3r3/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+/// let pins = absolute_pins(game.get_board(), Color::White);
+///
+/// assert_eq!(1, pins.len());
+/// assert_eq!((4, 1), pins[0].get_pinned_square());
+/// assert_eq!(PieceType::Rook, pins[0].get_pinned_by().get_piece_type());
+/// ```
+pub fn absolute_pins(board: &Board<ChessPiece>, color: Color) -> Vec<Pin> {
+    let Some(king_square) = find_king(board, color) else {
+        return Vec::new();
+    };
+
+    let mut pins = Vec::new();
+    for direction in ORTHOGONAL_DIRECTIONS.into_iter().chain(DIAGONAL_DIRECTIONS) {
+        let pieces = cast_ray(board, king_square, direction, 2);
+        if let [(pinned_square, pinned_piece), (pinned_by_square, pinned_by)] = pieces[..] {
+            if pinned_piece.get_color() == color
+                && pinned_by.get_color() != color
+                && moves_along(pinned_by.get_piece_type(), direction)
+            {
+                pins.push(Pin {
+                    pinned_square,
+                    pinned_piece,
+                    pinned_by_square,
+                    pinned_by,
+                    king_square,
+                });
+            }
+        }
+    }
+    pins
+}
+
+/// Finds the skewers given by the rooks, bishops and queens of a player.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at.
+/// * `color` - The player giving the skewers.
+///
+/// # Returns
+///
+/// A `Skewer` for every line along which a piece of the other player is attacked with a
+/// less valuable piece of theirs behind it. A king in front counts as the most valuable
+/// piece.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::tactics::skewers;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::piece::PieceType;
+/// use simple_chess::Color;
+///
+/// // the bishop checks the king on d5, and takes the rook on g8 once it moves
+/// let game = build_game_from_string("6r1/8/8/3k4/8/8/B7/6K1 b - - 0 1").unwrap();
+/// let found = skewers(game.get_board(), Color::White);
+///
+/// assert_eq!(1, found.len());
+/// assert_eq!(PieceType::King, found[0].get_front_piece().get_piece_type());
+/// assert_eq!((6, 7), found[0].get_rear_square());
+/// ```
+pub fn skewers(board: &Board<ChessPiece>, color: Color) -> Vec<Skewer> {
+    let mut skewers = Vec::new();
+    for (attacker_square, attacker) in get_pieces(board, color) {
+        for direction in ORTHOGONAL_DIRECTIONS.into_iter().chain(DIAGONAL_DIRECTIONS) {
+            if !moves_along(attacker.get_piece_type(), direction) {
+                continue;
+            }
+            let pieces = cast_ray(board, attacker_square, direction, 2);
+            if let [(front_square, front_piece), (rear_square, rear_piece)] = pieces[..] {
+                if front_piece.get_color() != color
+                    && rear_piece.get_color() != color
+                    && get_value(front_piece) > get_value(rear_piece)
+                {
+                    skewers.push(Skewer {
+                        attacker_square,
+                        attacker,
+                        front_square,
+                        front_piece,
+                        rear_square,
+                        rear_piece,
+                    });
+                }
+            }
+        }
+    }
+    skewers
+}
+
+/// Finds the pieces of a player that fork two or more pieces of the other player.
+///
+/// A piece counts as forked if it is the king, is worth more than the forking piece or is
+/// not defended, so attacking two defended pawns with a knight is not a fork.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at.
+/// * `color` - The player whose pieces may be forking.
+///
+/// # Returns
+///
+/// A `Fork` for every forking piece.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::tactics::forks;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::Color;
+///
+/// // the knight on c7 forks the king on e8 and the rook on a8
+/// let game = build_game_from_string("r3k3/2N5/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+/// let found = forks(game.get_board(), Color::White);
+///
+/// assert_eq!(1, found.len());
+/// assert_eq!((2, 6), found[0].get_square());
+/// assert_eq!(2, found[0].get_targets().len());
+/// ```
+pub fn forks(board: &Board<ChessPiece>, color: Color) -> Vec<Fork> {
+    let mut forks = Vec::new();
+    for (square, piece) in get_pieces(board, color) {
+        let targets: Vec<((usize, usize), ChessPiece)> = get_attacked_squares(board, square, piece)
+            .into_iter()
+            .filter_map(|at| get_piece_at(board, at).map(|target| (at, target)))
+            .filter(|(at, target)| {
+                target.get_color() != color
+                    && (get_value(*target) > get_value(piece)
+                        || !is_square_attacked(board, at.0, at.1, color.opposite()))
+            })
+            .collect();
+        if targets.len() >= 2 {
+            forks.push(Fork {
+                square,
+                piece,
+                targets,
+            });
+        }
+    }
+    forks
+}
+
+/// Finds the pieces of a player that are attacked by the other player and not defended.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at.
+/// * `color` - The player whose pieces may be hanging.
+///
+/// # Returns
+///
+/// A `HangingPiece` for every attacked and undefended piece other than the king.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::tactics::hanging_pieces;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::Color;
+///
+/// // the rook on a6 defends the pawn on a2, the bishop on h5 is attacked and undefended
+/// let game = build_game_from_string("4k3/8/r7/7b/8/8/p6Q/4K3 w - - 0 1").unwrap();
+/// let hanging = hanging_pieces(game.get_board(), Color::Black);
+///
+/// assert_eq!(1, hanging.len());
+/// assert_eq!((7, 4), hanging[0].get_square());
+/// ```
+pub fn hanging_pieces(board: &Board<ChessPiece>, color: Color) -> Vec<HangingPiece> {
+    get_pieces(board, color)
+        .into_iter()
+        .filter(|(square, piece)| {
+            piece.get_piece_type() != PieceType::King
+                && is_square_attacked(board, square.0, square.1, color.opposite())
+                && !is_square_attacked(board, square.0, square.1, color)
+        })
+        .map(|(square, piece)| HangingPiece { square, piece })
+        .collect()
+}
+
+/// Returns up to `limit` pieces met walking from a square in a direction.
+fn cast_ray(
+    board: &Board<ChessPiece>,
+    from: (usize, usize),
+    direction: (i32, i32),
+    limit: usize,
+) -> Vec<((usize, usize), ChessPiece)> {
+    let mut pieces = Vec::new();
+    let mut current = from;
+    while let Some(next) = offset_square(board, current, direction) {
+        if let Some(piece) = get_piece_at(board, next) {
+            pieces.push((next, piece));
+            if pieces.len() == limit {
+                break;
+            }
+        }
+        current = next;
+    }
+    pieces
+}
+
+/// Returns `true` if a piece of the given type slides along the direction.
+fn moves_along(piece_type: PieceType, direction: (i32, i32)) -> bool {
+    let is_diagonal = direction.0 != 0 && direction.1 != 0;
+    match piece_type {
+        PieceType::Queen => true,
+        PieceType::Rook | PieceType::Chancellor => !is_diagonal,
+        PieceType::Bishop | PieceType::Archbishop => is_diagonal,
+        _ => false,
+    }
+}
+
+/// Returns the value of a piece, counting the king as more valuable than any other.
+fn get_value(piece: ChessPiece) -> i32 {
+    match piece.get_piece_type() {
+        PieceType::King => i32::MAX,
+        piece_type => piece_value(piece_type),
+    }
+}
+
+fn get_pieces(board: &Board<ChessPiece>, color: Color) -> Vec<((usize, usize), ChessPiece)> {
+    (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| (col, row)))
+        .filter_map(|at| get_piece_at(board, at).map(|piece| (at, piece)))
+        .filter(|(_, piece)| piece.get_color() == color)
+        .collect()
+}
+
+fn get_piece_at(board: &Board<ChessPiece>, (col, row): (usize, usize)) -> Option<ChessPiece> {
+    board.get_piece_at_space(col, row).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    fn board(fen: &str) -> Board<ChessPiece> {
+        build_game_from_string(fen).unwrap().get_board().clone()
+    }
+
+    #[test]
+    fn pins_need_a_line_piece_of_the_right_kind() {
+        // a rook can not pin along a diagonal
+        let rook = board("k7/8/8/8/7r/8/5N2/4K3 w - - 0 1");
+        assert!(absolute_pins(&rook, Color::White).is_empty());
+
+        let bishop = board("k7/8/8/8/7b/8/5N2/4K3 w - - 0 1");
+        let pins = absolute_pins(&bishop, Color::White);
+        assert_eq!(1, pins.len());
+        assert_eq!((4, 0), pins[0].get_king_square());
+        assert_eq!((7, 3), pins[0].get_pinned_by_square());
+        assert!(absolute_pins(&bishop, Color::Black).is_empty());
+    }
+
+    #[test]
+    fn skewers_need_a_less_valuable_piece_behind() {
+        let rook_in_front = board("k7/8/8/8/q2r3R/8/8/K7 b - - 0 1");
+        assert!(skewers(&rook_in_front, Color::White).is_empty());
+
+        let queen_in_front = board("k7/8/8/8/r2q3R/8/8/K7 b - - 0 1");
+        let found = skewers(&queen_in_front, Color::White);
+        assert_eq!(1, found.len());
+        assert_eq!((3, 3), found[0].get_front_square());
+        assert_eq!(PieceType::Rook, found[0].get_rear_piece().get_piece_type());
+    }
+
+    #[test]
+    fn defended_pieces_of_lower_value_are_not_forked() {
+        // the knight on d5 attacks two pawns defended by the king
+        let defended_pawns = board("3k4/2p1p3/8/3N4/8/8/8/4K3 w - - 0 1");
+        assert!(forks(&defended_pawns, Color::White).is_empty());
+
+        // a pawn forking a knight and a bishop
+        let minor_pieces = board("8/8/8/2n1b3/3P4/8/8/k3K3 b - - 0 1");
+        let found = forks(&minor_pieces, Color::White);
+        assert_eq!(1, found.len());
+        assert_eq!(PieceType::Pawn, found[0].get_piece().get_piece_type());
+    }
+
+    #[test]
+    fn defended_and_unattacked_pieces_are_not_hanging() {
+        let safe = board("4k3/8/8/8/8/2n5/8/R3K3 b - - 0 1");
+        assert!(hanging_pieces(&safe, Color::Black).is_empty());
+        assert!(hanging_pieces(&safe, Color::White).is_empty());
+
+        let attacked = board("4k3/8/8/8/8/n7/8/R3K3 b - - 0 1");
+        let hanging = hanging_pieces(&attacked, Color::Black);
+        assert_eq!(1, hanging.len());
+        assert_eq!(PieceType::Knight, hanging[0].get_piece().get_piece_type());
+    }
+}
@@ -0,0 +1,318 @@
+use crate::annotations::{Arrow, BoardAnnotations};
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, ChessMoveType, Color};
+use game_board::get_square_name_from_row_and_col;
+
+/// A square a piece can reach in one move, with the path it takes to get there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReachableSquare {
+    square: (usize, usize),
+    path: Vec<(usize, usize)>,
+    captured_piece: Option<ChessPiece>,
+    explanation: String,
+}
+
+impl ReachableSquare {
+    /// Returns the square the piece can move to.
+    pub fn get_square(&self) -> (usize, usize) {
+        self.square
+    }
+
+    /// Returns the squares the piece passes on its way, ending with the square it moves to.
+    ///
+    /// Knights jump and kings step, so their path is only the square they move to.
+    pub fn get_path(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+
+    /// Returns the piece captured by moving to the square, if any.
+    pub fn get_captured_piece(&self) -> Option<ChessPiece> {
+        self.captured_piece
+    }
+
+    /// Returns a sentence explaining how the piece reaches the square, such as
+    /// `"The knight jumps to f3."`.
+    pub fn get_explanation(&self) -> &str {
+        &self.explanation
+    }
+}
+
+/// The squares a piece can reach from where it stands, for drawing teaching diagrams.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieceTutorial {
+    piece: ChessPiece,
+    square: (usize, usize),
+    reachable_squares: Vec<ReachableSquare>,
+}
+
+impl PieceTutorial {
+    /// Returns the piece the tutorial is about.
+    pub fn get_piece(&self) -> ChessPiece {
+        self.piece
+    }
+
+    /// Returns the square the piece stands on.
+    pub fn get_square(&self) -> (usize, usize) {
+        self.square
+    }
+
+    /// Returns every square the piece can reach, in the order its moves are generated.
+    pub fn get_reachable_squares(&self) -> &[ReachableSquare] {
+        &self.reachable_squares
+    }
+
+    /// Builds annotations drawing an arrow from the piece to every square it can reach, with
+    /// the squares it can capture on highlighted.
+    pub fn to_annotations(&self) -> BoardAnnotations {
+        let mut annotations = BoardAnnotations::new();
+        for reachable in &self.reachable_squares {
+            annotations.add_arrow(Arrow::new(
+                self.square,
+                reachable.square,
+                self.piece.get_color(),
+                1.0,
+            ));
+            if reachable.captured_piece.is_some() {
+                annotations.add_highlight(reachable.square);
+            }
+        }
+        annotations
+    }
+}
+
+/// Builds a tutorial showing how the piece on a square moves.
+///
+/// The piece's moves are generated on its own, as if it were the piece's turn, so the board
+/// may be empty apart from the piece or hold any pieces needed for the lesson, with or
+/// without kings. Castling is not included, and a pawn that promotes lists its promotion
+/// square once.
+///
+/// # Arguments
+///
+/// * `game` - The game holding the board, the game is not modified.
+/// * `col` - The column of the piece.
+/// * `row` - The row of the piece.
+///
+/// # Returns
+///
+/// * `Some(PieceTutorial)` - The squares the piece can reach.
+/// * `None` - If the square is empty or not on the board.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::analysis::tutorial::build_piece_tutorial;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// // a rook on d4 next to a black knight on f4 and a white pawn on d6
+/// let game = build_game_from_string("8/8/3P4/8/3R1n2/8/8/8 w - - 0 1").unwrap();
+/// let tutorial = build_piece_tutorial(&game, 3, 3).unwrap();
+///
+/// let capture = tutorial
+///     .get_reachable_squares()
+///     .iter()
+///     .find(|reachable| reachable.get_square() == (5, 3))
+///     .unwrap();
+/// assert_eq!(&[(4, 3), (5, 3)], capture.get_path());
+/// assert_eq!(
+///     "The rook slides 2 squares horizontally to f4 and captures the black knight.",
+///     capture.get_explanation()
+/// );
+/// assert_eq!(9, tutorial.get_reachable_squares().len());
+/// ```
+pub fn build_piece_tutorial(game: &ChessGame, col: usize, row: usize) -> Option<PieceTutorial> {
+    let board = game.get_board();
+    let piece = *board.try_get_piece_at_space(col, row).ok()??;
+    let moves = piece.possible_moves_with_ruleset(
+        (col, row),
+        board,
+        game.get_en_passant_target(),
+        game.get_ruleset(),
+    );
+
+    let mut reachable_squares: Vec<ReachableSquare> = Vec::new();
+    for chess_move in &moves {
+        let square = chess_move.get_new_position();
+        if reachable_squares
+            .iter()
+            .any(|reachable| reachable.square == square)
+        {
+            continue;
+        }
+        reachable_squares.push(ReachableSquare {
+            square,
+            path: get_path((col, row), square, piece.get_piece_type()),
+            captured_piece: get_captured_piece(chess_move),
+            explanation: explain_move(piece, (col, row), chess_move),
+        });
+    }
+
+    Some(PieceTutorial {
+        piece,
+        square: (col, row),
+        reachable_squares,
+    })
+}
+
+/// Returns the squares passed from one square to another, walking along the line between
+/// them unless the piece jumps or steps.
+fn get_path(
+    from: (usize, usize),
+    to: (usize, usize),
+    piece_type: PieceType,
+) -> Vec<(usize, usize)> {
+    let col_delta = to.0 as i32 - from.0 as i32;
+    let row_delta = to.1 as i32 - from.1 as i32;
+    let is_line = col_delta == 0 || row_delta == 0 || col_delta.abs() == row_delta.abs();
+    if piece_type == PieceType::Knight || piece_type == PieceType::King || !is_line {
+        return vec![to];
+    }
+
+    let steps = col_delta.abs().max(row_delta.abs());
+    (1..=steps)
+        .map(|step| {
+            (
+                (from.0 as i32 + col_delta.signum() * step) as usize,
+                (from.1 as i32 + row_delta.signum() * step) as usize,
+            )
+        })
+        .collect()
+}
+
+fn get_captured_piece(chess_move: &ChessMoveType) -> Option<ChessPiece> {
+    match chess_move {
+        ChessMoveType::Move { taken_piece, .. } => *taken_piece,
+        ChessMoveType::EnPassant { taken_piece, .. } => Some(*taken_piece),
+        ChessMoveType::Castle { .. } => None,
+    }
+}
+
+fn explain_move(piece: ChessPiece, from: (usize, usize), chess_move: &ChessMoveType) -> String {
+    let to = chess_move.get_new_position();
+    let to_name = get_square_name_from_row_and_col(to.0, to.1);
+    let distance = (to.0 as i32 - from.0 as i32)
+        .abs()
+        .max((to.1 as i32 - from.1 as i32).abs());
+    let piece_name = get_piece_name(piece.get_piece_type());
+
+    let movement = match piece.get_piece_type() {
+        PieceType::Knight => format!("The knight jumps to {to_name}"),
+        PieceType::King => format!("The king steps to {to_name}"),
+        PieceType::Pawn => match chess_move {
+            ChessMoveType::EnPassant { .. } => {
+                format!("The pawn captures en passant by moving diagonally to {to_name}")
+            }
+            _ if from.0 != to.0 => format!("The pawn moves diagonally to {to_name}"),
+            _ if distance == 2 => {
+                format!("The pawn moves two squares forward from its starting rank to {to_name}")
+            }
+            _ => format!("The pawn steps forward to {to_name}"),
+        },
+        _ => format!(
+            "The {piece_name} slides {distance} square{} {} to {to_name}",
+            if distance == 1 { "" } else { "s" },
+            get_direction(from, to)
+        ),
+    };
+
+    let capture = match get_captured_piece(chess_move) {
+        Some(taken) => format!(
+            " and captures the {} {}",
+            get_color_name(taken.get_color()),
+            get_piece_name(taken.get_piece_type())
+        ),
+        None => String::new(),
+    };
+    let promotion = match chess_move {
+        ChessMoveType::Move {
+            promotion: Some(_), ..
+        } => ", where it promotes to a queen, rook, bishop or knight",
+        _ => "",
+    };
+
+    format!("{movement}{capture}{promotion}.")
+}
+
+fn get_direction(from: (usize, usize), to: (usize, usize)) -> &'static str {
+    if from.1 == to.1 {
+        "horizontally"
+    } else if from.0 == to.0 {
+        "vertically"
+    } else {
+        "diagonally"
+    }
+}
+
+fn get_piece_name(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => "pawn",
+        PieceType::Rook => "rook",
+        PieceType::Knight => "knight",
+        PieceType::Bishop => "bishop",
+        PieceType::Queen => "queen",
+        PieceType::Archbishop => "archbishop",
+        PieceType::Chancellor => "chancellor",
+        PieceType::King => "king",
+    }
+}
+
+fn get_color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    fn find(tutorial: &PieceTutorial, square: (usize, usize)) -> &ReachableSquare {
+        tutorial
+            .get_reachable_squares()
+            .iter()
+            .find(|reachable| reachable.get_square() == square)
+            .unwrap()
+    }
+
+    #[test]
+    fn knights_jump_over_pieces() {
+        let game = build_game_from_string("8/8/8/8/8/PPP5/PNP5/PPP5 w - - 0 1").unwrap();
+        let tutorial = build_piece_tutorial(&game, 1, 1).unwrap();
+
+        assert_eq!(4, tutorial.get_reachable_squares().len());
+        let jump = find(&tutorial, (2, 3));
+        assert_eq!(&[(2, 3)], jump.get_path());
+        assert_eq!("The knight jumps to c4.", jump.get_explanation());
+    }
+
+    #[test]
+    fn pawns_explain_double_steps_and_promotions() {
+        let game = build_game_from_string("1n6/P7/8/8/8/8/4P3/8 w - - 0 1").unwrap();
+
+        let pawn = build_piece_tutorial(&game, 4, 1).unwrap();
+        let double_step = find(&pawn, (4, 3));
+        assert_eq!(&[(4, 2), (4, 3)], double_step.get_path());
+        assert_eq!(
+            "The pawn moves two squares forward from its starting rank to e4.",
+            double_step.get_explanation()
+        );
+
+        let promoting = build_piece_tutorial(&game, 0, 6).unwrap();
+        assert_eq!(2, promoting.get_reachable_squares().len());
+        assert_eq!(
+            "The pawn moves diagonally to b8 and captures the black knight, where it promotes to a queen, rook, bishop or knight.",
+            find(&promoting, (1, 7)).get_explanation()
+        );
+        assert_eq!(1, promoting.to_annotations().get_highlights().len());
+        assert_eq!(2, promoting.to_annotations().get_arrows().len());
+    }
+
+    #[test]
+    fn empty_squares_have_no_tutorial() {
+        let game = build_game_from_string("8/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        assert!(build_piece_tutorial(&game, 0, 0).is_none());
+        assert!(build_piece_tutorial(&game, 8, 0).is_none());
+    }
+}
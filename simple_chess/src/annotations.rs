@@ -0,0 +1,206 @@
+use crate::engine::SearchResult;
+use crate::{ChessGame, Color};
+
+/// An arrow drawn on the board from one square to another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arrow {
+    from: (usize, usize),
+    to: (usize, usize),
+    color: Color,
+    opacity: f32,
+}
+
+impl Arrow {
+    /// Creates a new `Arrow`.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The square the arrow starts on.
+    /// * `to` - The square the arrow points to.
+    /// * `color` - The color of the side the arrow belongs to.
+    /// * `opacity` - How opaque the arrow is, clamped to `0.0..=1.0`.
+    pub fn new(from: (usize, usize), to: (usize, usize), color: Color, opacity: f32) -> Self {
+        Self {
+            from,
+            to,
+            color,
+            opacity: opacity.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the square the arrow starts on.
+    pub fn get_from(&self) -> (usize, usize) {
+        self.from
+    }
+
+    /// Returns the square the arrow points to.
+    pub fn get_to(&self) -> (usize, usize) {
+        self.to
+    }
+
+    /// Returns the color of the side the arrow belongs to.
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    /// Returns how opaque the arrow is, between `0.0` and `1.0`.
+    pub fn get_opacity(&self) -> f32 {
+        self.opacity
+    }
+}
+
+/// Arrows and highlighted squares to draw on top of a board.
+///
+/// `BoardAnnotations` only describes what to draw, it is up to the UI layer to render it.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::annotations::{Arrow, BoardAnnotations};
+/// use simple_chess::Color::White;
+///
+/// let mut annotations = BoardAnnotations::new();
+/// annotations.add_arrow(Arrow::new((4, 1), (4, 3), White, 1.0));
+/// annotations.add_highlight((4, 3));
+///
+/// assert_eq!(1, annotations.get_arrows().len());
+/// assert_eq!(&[(4, 3)], annotations.get_highlights());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BoardAnnotations {
+    arrows: Vec<Arrow>,
+    highlights: Vec<(usize, usize)>,
+}
+
+impl BoardAnnotations {
+    /// Creates an empty `BoardAnnotations`.
+    pub fn new() -> Self {
+        Self {
+            arrows: Vec::new(),
+            highlights: Vec::new(),
+        }
+    }
+
+    /// Builds annotations visualizing the principal variation of a search.
+    ///
+    /// Every move of the principal variation gets an arrow, colored by the side playing it,
+    /// with the opacity fading for moves further into the line. The squares of the best
+    /// move are highlighted.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game that was searched, used to tell which side plays each move.
+    /// * `result` - The `SearchResult` to visualize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::annotations::BoardAnnotations;
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let game = ChessGame::new();
+    /// let result = Engine::new().search(&game, 2);
+    /// let annotations = BoardAnnotations::from_search_result(&game, &result);
+    ///
+    /// assert_eq!(2, annotations.get_arrows().len());
+    /// assert_eq!(1.0, annotations.get_arrows()[0].get_opacity());
+    /// assert_eq!(2, annotations.get_highlights().len());
+    /// ```
+    pub fn from_search_result(game: &ChessGame, result: &SearchResult) -> Self {
+        let mut annotations = Self::new();
+        let principal_variation = result.get_principal_variation();
+        let mut color = game.get_current_players_turn();
+
+        for (index, chess_move) in principal_variation.iter().enumerate() {
+            let opacity = 1.0 - index as f32 / principal_variation.len() as f32;
+            annotations.add_arrow(Arrow::new(
+                chess_move.get_original_position(),
+                chess_move.get_new_position(),
+                color,
+                opacity,
+            ));
+            color = color.opposite();
+        }
+
+        if let Some(best_move) = result.get_best_move() {
+            annotations.add_highlight(best_move.get_original_position());
+            annotations.add_highlight(best_move.get_new_position());
+        }
+
+        annotations
+    }
+
+    /// Adds an arrow.
+    pub fn add_arrow(&mut self, arrow: Arrow) {
+        self.arrows.push(arrow);
+    }
+
+    /// Adds a highlighted square.
+    pub fn add_highlight(&mut self, square: (usize, usize)) {
+        self.highlights.push(square);
+    }
+
+    /// Returns the arrows in the order they were added.
+    pub fn get_arrows(&self) -> &[Arrow] {
+        &self.arrows
+    }
+
+    /// Returns the highlighted squares in the order they were added.
+    pub fn get_highlights(&self) -> &[(usize, usize)] {
+        &self.highlights
+    }
+
+    /// Returns `true` if there is nothing to draw.
+    pub fn is_empty(&self) -> bool {
+        self.arrows.is_empty() && self.highlights.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::engine::Engine;
+    use crate::Color::{Black, White};
+
+    #[test]
+    fn arrows_alternate_colors_and_fade() {
+        let game = build_game_from_string("4k3/8/8/3q4/8/8/3R4/4K3 b - - 0 1").unwrap();
+        let result = Engine::new().search(&game, 3);
+        let annotations = BoardAnnotations::from_search_result(&game, &result);
+
+        let arrows = annotations.get_arrows();
+        assert_eq!(3, arrows.len());
+        assert_eq!(Black, arrows[0].get_color());
+        assert_eq!(White, arrows[1].get_color());
+        assert_eq!(Black, arrows[2].get_color());
+        assert!(arrows
+            .windows(2)
+            .all(|pair| pair[0].get_opacity() > pair[1].get_opacity()));
+        assert!(arrows[2].get_opacity() > 0.0);
+
+        let best_move = result.get_best_move().unwrap();
+        assert_eq!(
+            &[
+                best_move.get_original_position(),
+                best_move.get_new_position()
+            ],
+            annotations.get_highlights()
+        );
+    }
+
+    #[test]
+    fn result_without_moves_has_no_annotations() {
+        let checkmate = build_game_from_string("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        let result = Engine::new().search(&checkmate, 2);
+
+        assert!(BoardAnnotations::from_search_result(&checkmate, &result).is_empty());
+    }
+
+    #[test]
+    fn opacity_is_clamped() {
+        assert_eq!(1.0, Arrow::new((0, 0), (0, 1), White, 3.0).get_opacity());
+        assert_eq!(0.0, Arrow::new((0, 0), (0, 1), White, -1.0).get_opacity());
+    }
+}
@@ -0,0 +1,276 @@
+use crate::chess_board::ChessBoard;
+use crate::piece::{ChessPiece, PieceType};
+use crate::position::offset_square;
+use crate::Color;
+use game_board::{Board, SquareSet};
+
+/// The offsets a knight jumps by.
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+/// The directions rooks move in.
+const ORTHOGONAL_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+/// The directions bishops move in.
+const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// The pieces attacking every square of a position, for both players.
+///
+/// The map is computed once for a board, after which the attackers of any square can be
+/// looked up without generating moves again, for example to shade threatened squares or
+/// to count the attacks around a king.
+///
+/// A square is attacked by a piece if the piece could capture an opposing piece standing
+/// on it, so pawns attack diagonally forward, and squares holding a piece of the same
+/// color as the attacker count as defended.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::attack_map::AttackMap;
+/// use simple_chess::ChessGame;
+/// use simple_chess::Color;
+///
+/// let game = ChessGame::new();
+/// let attack_map = AttackMap::new(game.get_board());
+///
+/// // f3 is attacked by the pawns on e2 and g2 and the knight on g1
+/// assert_eq!(3, attack_map.get_attack_count(5, 2, Color::White));
+/// assert!(!attack_map.is_attacked(4, 3, Color::White));
+/// assert_eq!(22, attack_map.get_attacked_squares(Color::White).len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttackMap {
+    width: usize,
+    height: usize,
+    white_attackers: Vec<Vec<(usize, usize)>>,
+    black_attackers: Vec<Vec<(usize, usize)>>,
+}
+
+impl AttackMap {
+    /// Computes the attack map of a board.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board to compute the attacks of.
+    pub fn new(board: &Board<ChessPiece>) -> Self {
+        let width = board.get_width();
+        let height = board.get_height();
+        let mut attack_map = Self {
+            width,
+            height,
+            white_attackers: vec![Vec::new(); width * height],
+            black_attackers: vec![Vec::new(); width * height],
+        };
+
+        for (position, piece) in board.pieces() {
+            let square = position.get_col_and_row();
+            for (target_col, target_row) in get_attacked_squares(board, square, *piece) {
+                let index = target_row * width + target_col;
+                match piece.get_color() {
+                    Color::White => attack_map.white_attackers[index].push(square),
+                    Color::Black => attack_map.black_attackers[index].push(square),
+                }
+            }
+        }
+        attack_map
+    }
+
+    /// Returns the squares of the pieces of a player attacking a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column of the square.
+    /// * `row` - The row of the square.
+    /// * `color` - The player whose attacking pieces to return.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the square is outside of the board.
+    pub fn get_attackers(&self, col: usize, row: usize, color: Color) -> &[(usize, usize)] {
+        assert!(
+            col < self.width && row < self.height,
+            "the square ({col}, {row}) is outside of the board"
+        );
+        let index = row * self.width + col;
+        match color {
+            Color::White => &self.white_attackers[index],
+            Color::Black => &self.black_attackers[index],
+        }
+    }
+
+    /// Returns the number of pieces of a player attacking a square.
+    pub fn get_attack_count(&self, col: usize, row: usize, color: Color) -> usize {
+        self.get_attackers(col, row, color).len()
+    }
+
+    /// Returns `true` if at least one piece of a player attacks a square.
+    pub fn is_attacked(&self, col: usize, row: usize, color: Color) -> bool {
+        !self.get_attackers(col, row, color).is_empty()
+    }
+
+    /// Returns every square attacked by at least one piece of a player.
+    pub fn get_attacked_squares(&self, color: Color) -> SquareSet {
+        let mut squares = SquareSet::new(self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.is_attacked(col, row, color) {
+                    squares.insert(col, row);
+                }
+            }
+        }
+        squares
+    }
+}
+
+/// Returns the squares of the pieces of a player attacking a square, without computing a
+/// full `AttackMap`.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at.
+/// * `col` - The column of the square.
+/// * `row` - The row of the square.
+/// * `color` - The player whose attacking pieces to return.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::attack_map::attackers_of;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::Color;
+///
+/// let game = build_game_from_string("4k3/8/8/3q4/8/8/3R4/N3K3 w - - 0 1").unwrap();
+///
+/// // the queen on d5 is attacked by the rook on d2 only, the knight on a1 does not reach it
+/// assert_eq!(vec![(3, 1)], attackers_of(game.get_board(), 3, 4, Color::White));
+/// ```
+pub fn attackers_of(
+    board: &Board<ChessPiece>,
+    col: usize,
+    row: usize,
+    color: Color,
+) -> Vec<(usize, usize)> {
+    let mut attackers = Vec::new();
+    for piece_row in 0..board.get_height() {
+        for piece_col in 0..board.get_width() {
+            if let Some(piece) = board.get_piece_at_space(piece_col, piece_row) {
+                if piece.get_color() == color
+                    && get_attacked_squares(board, (piece_col, piece_row), *piece)
+                        .contains(&(col, row))
+                {
+                    attackers.push((piece_col, piece_row));
+                }
+            }
+        }
+    }
+    attackers
+}
+
+/// Returns the squares a piece attacks, whether they are empty or occupied.
+pub(crate) fn get_attacked_squares(
+    board: &Board<ChessPiece>,
+    square: (usize, usize),
+    piece: ChessPiece,
+) -> Vec<(usize, usize)> {
+    let piece_type = piece.get_piece_type();
+    let offsets: Vec<(i32, i32)> = match piece_type {
+        PieceType::Pawn => {
+            let forward = piece.get_color().forward();
+            vec![(-1, forward), (1, forward)]
+        }
+        PieceType::Knight | PieceType::Archbishop | PieceType::Chancellor => {
+            KNIGHT_OFFSETS.to_vec()
+        }
+        PieceType::King => ORTHOGONAL_DIRECTIONS
+            .into_iter()
+            .chain(DIAGONAL_DIRECTIONS)
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut squares: Vec<(usize, usize)> = offsets
+        .into_iter()
+        .filter_map(|offset| offset_square(board, square, offset))
+        .collect();
+
+    let directions: &[(i32, i32)] = match piece_type {
+        PieceType::Rook | PieceType::Chancellor => &ORTHOGONAL_DIRECTIONS,
+        PieceType::Bishop | PieceType::Archbishop => &DIAGONAL_DIRECTIONS,
+        PieceType::Queen => &[
+            (0, 1),
+            (0, -1),
+            (1, 0),
+            (-1, 0),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ],
+        _ => &[],
+    };
+    for &direction in directions {
+        let mut current = square;
+        while let Some(next) = offset_square(board, current, direction) {
+            squares.push(next);
+            if board.get_piece_at_space(next.0, next.1).is_some() {
+                break;
+            }
+            current = next;
+        }
+    }
+    squares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_game_state_analyzer::is_square_attacked;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn the_map_agrees_with_is_square_attacked() {
+        let game = build_game_from_string(
+            "r1bqk2r/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQK2R w KQkq - 1 5",
+        )
+        .unwrap();
+        let board = game.get_board();
+        let attack_map = AttackMap::new(board);
+
+        for row in 0..8 {
+            for col in 0..8 {
+                for color in [Color::White, Color::Black] {
+                    assert_eq!(
+                        is_square_attacked(board, col, row, color),
+                        attack_map.is_attacked(col, row, color),
+                        "({col}, {row}) by {color:?}"
+                    );
+                    let mut attackers = attackers_of(board, col, row, color);
+                    attackers.sort();
+                    let mut mapped = attack_map.get_attackers(col, row, color).to_vec();
+                    mapped.sort();
+                    assert_eq!(attackers, mapped);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn line_pieces_stop_at_the_first_piece() {
+        let game = build_game_from_string("4k3/8/8/8/R2p3r/8/8/4K3 w - - 0 1").unwrap();
+        let attack_map = AttackMap::new(game.get_board());
+
+        // the pawn on d4 is attacked by the rook on a4 and defended by the rook on h4
+        assert_eq!(&[(0, 3)], attack_map.get_attackers(3, 3, Color::White));
+        assert_eq!(&[(7, 3)], attack_map.get_attackers(3, 3, Color::Black));
+        assert!(!attack_map.is_attacked(4, 3, Color::White));
+        assert!(!attack_map.is_attacked(2, 3, Color::Black));
+    }
+}
@@ -0,0 +1,859 @@
+use crate::chess_board::ChessBoard;
+use crate::engine::attack_tables::{KING_ATTACKS, KNIGHT_ATTACKS, PAWN_ATTACKS};
+use crate::piece::{ChessPiece, PieceType};
+use crate::ruleset::Ruleset;
+use crate::square_name::SquareName;
+use crate::{ChessMoveType, Color};
+use game_board::Board;
+use std::sync::OnceLock;
+
+const ORTHOGONAL_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// The piece types in the order of the bitboards of each color.
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+const PROMOTION_OPTIONS: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+];
+
+/// The magic multipliers of the rook attacks of each square, found by trying sparse random
+/// numbers until every relevant occupancy of the square maps to its own attack set.
+const ROOK_MAGICS: [u64; 64] = [
+    0x4080_0840_0010_2080,
+    0x0440_2004_4000_1003,
+    0x2100_0811_0040_2000,
+    0x0480_0802_5000_8084,
+    0x0600_3008_0420_4200,
+    0x0300_0208_0C00_0100,
+    0x0080_0080_0100_0200,
+    0x4200_0024_0204_4091,
+    0x0088_8000_8840_0020,
+    0x0804_4010_0040_2008,
+    0x0000_8020_0080_1000,
+    0x8025_000D_0070_0021,
+    0x0089_0005_0208_0010,
+    0x0002_8004_0080_0200,
+    0x3041_0002_0004_0100,
+    0x0005_0010_4100_0082,
+    0x0002_8880_0440_0164,
+    0x1020_0140_0030_0242,
+    0x1000_8080_1000_2008,
+    0x0200_8180_1800_3004,
+    0x0480_3100_0501_6800,
+    0x0000_8080_0400_0200,
+    0x0081_0400_0842_1031,
+    0x00C1_0200_0400_8041,
+    0x4024_9020_800A_4000,
+    0xA0A1_4001_8020_0088,
+    0x0811_1001_8020_0088,
+    0x3810_1000_8080_0800,
+    0x081C_0024_8008_0080,
+    0x2822_0400_8002_0080,
+    0x0060_1004_0008_4241,
+    0x0082_0102_0020_8044,
+    0x0540_8040_0480_0020,
+    0x004C_400C_8100_2108,
+    0x0000_1000_8280_2000,
+    0x1010_0008_0080_1081,
+    0x0088_0400_8080_0800,
+    0x2844_0004_8080_0200,
+    0x0091_0110_8400_0842,
+    0x0001_0000_8100_0042,
+    0x20C0_0080_2040_8002,
+    0x0000_4020_1004_4008,
+    0x8081_0010_2001_0040,
+    0x0582_0008_1042_0020,
+    0x0200_0800_0400_8080,
+    0x000C_0002_0080_8004,
+    0x0201_0002_0001_0104,
+    0x0040_1094_1842_0001,
+    0x0050_4000_2080_0080,
+    0x0001_4000_80A0_0180,
+    0x0140_1001_8060_0180,
+    0x0400_8008_0010_0480,
+    0x0002_1008_0100_0500,
+    0x580C_0200_8004_0080,
+    0x9000_0230_4841_0400,
+    0x0240_8104_0080_4200,
+    0x0809_2142_8000_9101,
+    0x0001_0080_2010_4001,
+    0x0440_2000_4011_000D,
+    0x8811_0010_0004_0821,
+    0x0081_0002_0410_0801,
+    0x0221_0002_0804_0001,
+    0x0002_0001_20C8_0402,
+    0x0004_1041_0124_0982,
+];
+
+/// The magic multipliers of the bishop attacks of each square, found like `ROOK_MAGICS`.
+const BISHOP_MAGICS: [u64; 64] = [
+    0x0262_2022_0403_0022,
+    0x0484_0424_1C02_2204,
+    0x0190_1122_1A24_10A0,
+    0x5020_9200_4154_0000,
+    0x0E3C_1028_8200_2180,
+    0x4080_9004_A000_C024,
+    0x0941_0128_2004_0380,
+    0x9808_4044_0401_4088,
+    0x8083_0460_0242_0208,
+    0x1000_0238_0569_0200,
+    0x4030_2202_0404_2420,
+    0x0208_3424_0880_8089,
+    0x00A0_1088_2020_0800,
+    0x0020_0208_4208_0201,
+    0x6120_0901_08A0_0409,
+    0x0000_0040_4410_0808,
+    0x00A0_2004_A042_0202,
+    0x4010_0220_0911_0520,
+    0x0032_4050_0880_8100,
+    0x0044_0112_0212_0101,
+    0x0C0C_00C2_88A0_0001,
+    0x5207_0002_0080_9424,
+    0x0400_4001_1110_9000,
+    0x0140_8002_0041_3862,
+    0x0804_1000_0620_0800,
+    0x0444_1080_051B_0802,
+    0x0100_3009_0A04_8200,
+    0x0340_8020_0802_0020,
+    0x0080_4040_8401_0040,
+    0x0002_0202_1C08_8200,
+    0x0008_0100_A041_2800,
+    0x0000_4044_4104_0220,
+    0x2304_04C0_1420_0210,
+    0x0208_2484_0010_0130,
+    0x0201_0401_0018_8800,
+    0x0008_4008_2002_0200,
+    0x4024_1004_0040_8082,
+    0x8020_2083_0000_2400,
+    0x0004_8802_0000_4100,
+    0x4009_0C03_0040_2104,
+    0x080C_5004_0410_1002,
+    0x002E_0801_0480_0908,
+    0x8069_0844_0200_1020,
+    0x00C8_0020_1800_8100,
+    0x02A0_8409_0210_1400,
+    0x0844_0400_8200_440C,
+    0x2004_2808_5900_0040,
+    0x0004_0822_2022_0044,
+    0x0800_8228_02C0_0016,
+    0x0001_4400_8838_0120,
+    0x8000_3204_9404_48C5,
+    0x0020_4062_1054_0000,
+    0x808A_0020_0850_4000,
+    0x1840_2003_9202_0000,
+    0x0008_4224_080A_0200,
+    0x9185_2818_0100_2100,
+    0x2012_0020_8828_0802,
+    0x4122_8684_0441_0410,
+    0x0820_0008_4844_1018,
+    0x4800_5800_0842_0E00,
+    0x1000_0A20_9262_0202,
+    0x0110_2810_2005_0310,
+    0x0400_2852_0408_4612,
+    0x8421_2040_8A02_8020,
+];
+
+static MAGIC_TABLES: OnceLock<MagicTables> = OnceLock::new();
+
+/// Returns `true` if a game with the given board and ruleset is standard chess, which can
+/// use the bitboard fast path for check detection.
+pub(crate) fn is_standard_chess(board: &Board<ChessPiece>, ruleset: &Ruleset) -> bool {
+    board.get_width() == 8
+        && board.get_height() == 8
+        && *ruleset == Ruleset::new()
+        && has_only_standard_pieces(board)
+}
+
+/// A standard 8x8 board stored as bitboards, one `u64` per piece type and color.
+///
+/// Bit `col + row * 8` of a bitboard is set when the square holds such a piece, the same
+/// index as the `SquareName` discriminant and the tables in
+/// [`crate::engine::attack_tables`]. Sliding pieces use magic bitboards, so the attacks of
+/// a rook, bishop or queen are a table lookup instead of a walk along each ray.
+///
+/// Games of standard chess generate their moves from bitboards and use them to test the
+/// legality of moves.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::bitboard::Bitboards;
+/// use simple_chess::piece::PieceType;
+/// use simple_chess::square_name::SquareName;
+/// use simple_chess::{ChessGame, Color};
+///
+/// let game = ChessGame::new();
+/// let bitboards = Bitboards::from_board(game.get_board()).unwrap();
+///
+/// assert_eq!(0xFF00, bitboards.get_pieces(Color::White, PieceType::Pawn));
+/// assert_eq!(32, bitboards.get_occupied().count_ones());
+/// assert!(bitboards.is_square_attacked(SquareName::F3, Color::White));
+/// assert_eq!(game.get_board(), &bitboards.to_board());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitboards {
+    pieces: [[u64; 6]; 2],
+    occupied: u64,
+}
+
+impl Bitboards {
+    /// Converts a board to bitboards.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Bitboards)` - The bitboards of the board.
+    /// * `None` - If the board is not 8x8 or holds an archbishop or chancellor.
+    pub fn from_board(board: &Board<ChessPiece>) -> Option<Self> {
+        if board.get_width() != 8 || board.get_height() != 8 || !has_only_standard_pieces(board) {
+            return None;
+        }
+        let mut bitboards = Self {
+            pieces: [[0; 6]; 2],
+            occupied: 0,
+        };
+        for row in 0..8 {
+            for col in 0..8 {
+                if let Some(piece) = board.get_piece_at_space(col, row) {
+                    bitboards.set(piece, get_square((col, row)));
+                }
+            }
+        }
+        Some(bitboards)
+    }
+
+    /// Converts the bitboards back to a board.
+    pub fn to_board(&self) -> Board<ChessPiece> {
+        let mut board = Board::build(8, 8).unwrap();
+        for square in 0..64 {
+            if let Some(piece) = self.get_piece_at_index(square) {
+                board.place_piece(piece, square % 8, square / 8);
+            }
+        }
+        board
+    }
+
+    /// Returns the squares holding pieces of a type and color.
+    pub fn get_pieces(&self, color: Color, piece_type: PieceType) -> u64 {
+        self.pieces[get_color_index(color)][get_kind(piece_type)]
+    }
+
+    /// Returns the squares holding pieces of a color.
+    pub fn get_color_occupancy(&self, color: Color) -> u64 {
+        self.pieces[get_color_index(color)]
+            .iter()
+            .fold(0, |occupied, pieces| occupied | pieces)
+    }
+
+    /// Returns the squares holding any piece.
+    pub fn get_occupied(&self) -> u64 {
+        self.occupied
+    }
+
+    /// Returns the piece on a square, if there is one.
+    pub fn get_piece_at(&self, square: SquareName) -> Option<ChessPiece> {
+        self.get_piece_at_index(square as usize)
+    }
+
+    /// Returns `true` if a piece of the given color attacks the square.
+    pub fn is_square_attacked(&self, square: SquareName, by_color: Color) -> bool {
+        self.is_index_attacked(square as usize, by_color)
+    }
+
+    /// Returns `true` if any king of the given color is attacked.
+    pub fn is_in_check(&self, color: Color) -> bool {
+        let mut kings = self.pieces[get_color_index(color)][get_kind(PieceType::King)];
+        while kings != 0 {
+            let square = kings.trailing_zeros() as usize;
+            if self.is_index_attacked(square, color.opposite()) {
+                return true;
+            }
+            kings &= kings - 1;
+        }
+        false
+    }
+
+    /// Returns a copy of the bitboards with a move made on them.
+    pub(crate) fn after_move(&self, chess_move: &ChessMoveType) -> Self {
+        let mut bitboards = *self;
+        match chess_move {
+            ChessMoveType::Move {
+                original_position,
+                new_position,
+                piece,
+                promotion,
+                ..
+            } => {
+                bitboards.clear(get_square(*original_position));
+                bitboards.clear(get_square(*new_position));
+                bitboards.set(&promotion.unwrap_or(*piece), get_square(*new_position));
+            }
+            ChessMoveType::EnPassant {
+                original_position,
+                new_position,
+                piece,
+                taken_piece_position,
+                promotion,
+                ..
+            } => {
+                bitboards.clear(get_square(*original_position));
+                bitboards.clear(get_square(*taken_piece_position));
+                bitboards.set(&promotion.unwrap_or(*piece), get_square(*new_position));
+            }
+            ChessMoveType::Castle {
+                rook_original_position,
+                rook_new_position,
+                king_original_position,
+                king_new_position,
+            } => {
+                let color = if self.pieces[0][get_kind(PieceType::King)]
+                    & bit(get_square(*king_original_position))
+                    != 0
+                {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                bitboards.clear(get_square(*king_original_position));
+                bitboards.clear(get_square(*rook_original_position));
+                bitboards.set(
+                    &ChessPiece::new(PieceType::King, color),
+                    get_square(*king_new_position),
+                );
+                bitboards.set(
+                    &ChessPiece::new(PieceType::Rook, color),
+                    get_square(*rook_new_position),
+                );
+            }
+        }
+        bitboards
+    }
+
+    /// Generates the moves of a player's pieces without checking whether they leave the
+    /// king in check, castling is left to the caller as it depends on the castling rights.
+    pub(crate) fn get_pseudo_legal_moves(
+        &self,
+        color: Color,
+        en_passant_target: Option<(usize, usize)>,
+    ) -> Vec<ChessMoveType> {
+        let color_index = get_color_index(color);
+        let own = self.get_color_occupancy(color);
+        let mut moves = Vec::new();
+
+        for (kind, piece_type) in PIECE_TYPES.iter().enumerate() {
+            if *piece_type == PieceType::Pawn {
+                continue;
+            }
+            let piece = ChessPiece::new(*piece_type, color);
+            let mut pieces = self.pieces[color_index][kind];
+            while pieces != 0 {
+                let from = pieces.trailing_zeros() as usize;
+                let attacks = match piece_type {
+                    PieceType::Knight => KNIGHT_ATTACKS[from],
+                    PieceType::Bishop => bishop_attacks_from_index(from, self.occupied),
+                    PieceType::Rook => rook_attacks_from_index(from, self.occupied),
+                    PieceType::Queen => {
+                        bishop_attacks_from_index(from, self.occupied)
+                            | rook_attacks_from_index(from, self.occupied)
+                    }
+                    _ => KING_ATTACKS[from],
+                };
+                let mut targets = attacks & !own;
+                while targets != 0 {
+                    let to = targets.trailing_zeros() as usize;
+                    moves.push(ChessMoveType::Move {
+                        original_position: get_position(from),
+                        new_position: get_position(to),
+                        piece,
+                        taken_piece: self.get_piece_at_index(to),
+                        promotion: None,
+                    });
+                    targets &= targets - 1;
+                }
+                pieces &= pieces - 1;
+            }
+        }
+
+        self.add_pawn_moves(color, en_passant_target, &mut moves);
+        moves
+    }
+
+    /// Returns the pawn that can be taken en passant on the target square, and its square.
+    fn get_en_passant_capture(
+        &self,
+        color: Color,
+        en_passant_target: Option<(usize, usize)>,
+    ) -> Option<(ChessPiece, (usize, usize))> {
+        let (col, row) = en_passant_target?;
+        let pawn_row = row.checked_add_signed(-color.forward() as isize)?;
+        if col >= 8 || pawn_row >= 8 {
+            return None;
+        }
+        let pawn = ChessPiece::new(PieceType::Pawn, color.opposite());
+        (self.get_piece_at_index(get_square((col, pawn_row))) == Some(pawn))
+            .then_some((pawn, (col, pawn_row)))
+    }
+
+    fn add_pawn_moves(
+        &self,
+        color: Color,
+        en_passant_target: Option<(usize, usize)>,
+        moves: &mut Vec<ChessMoveType>,
+    ) {
+        let pawn = ChessPiece::new(PieceType::Pawn, color);
+        let opponent = self.get_color_occupancy(color.opposite());
+        let (starting_row, promotion_row) = match color {
+            Color::White => (1, 7),
+            Color::Black => (6, 0),
+        };
+        let en_passant = self.get_en_passant_capture(color, en_passant_target);
+
+        let mut pawns = self.pieces[get_color_index(color)][get_kind(PieceType::Pawn)];
+        while pawns != 0 {
+            let from = pawns.trailing_zeros() as usize;
+            let (col, row) = get_position(from);
+            let mut add_move = |to: usize, taken_piece: Option<ChessPiece>| {
+                let new_position = get_position(to);
+                let promotions: Vec<Option<ChessPiece>> = if new_position.1 == promotion_row {
+                    PROMOTION_OPTIONS
+                        .iter()
+                        .map(|option| Some(ChessPiece::new(*option, color)))
+                        .collect()
+                } else {
+                    vec![None]
+                };
+                for promotion in promotions {
+                    moves.push(ChessMoveType::Move {
+                        original_position: (col, row),
+                        new_position,
+                        piece: pawn,
+                        taken_piece,
+                        promotion,
+                    });
+                }
+            };
+
+            let next_row = row as i32 + color.forward();
+            if (0..8).contains(&next_row) {
+                let one_step = get_square((col, next_row as usize));
+                if self.occupied & bit(one_step) == 0 {
+                    add_move(one_step, None);
+                    let two_steps_row = row as i32 + 2 * color.forward();
+                    if row == starting_row && (0..8).contains(&two_steps_row) {
+                        let two_steps = get_square((col, two_steps_row as usize));
+                        if self.occupied & bit(two_steps) == 0 {
+                            add_move(two_steps, None);
+                        }
+                    }
+                }
+            }
+
+            let mut captures = PAWN_ATTACKS[get_color_index(color)][from] & opponent;
+            while captures != 0 {
+                let to = captures.trailing_zeros() as usize;
+                add_move(to, self.get_piece_at_index(to));
+                captures &= captures - 1;
+            }
+
+            if let Some((taken_piece, taken_piece_position)) = en_passant {
+                if taken_piece_position.1 == row && taken_piece_position.0.abs_diff(col) == 1 {
+                    moves.push(ChessMoveType::EnPassant {
+                        original_position: (col, row),
+                        new_position: (taken_piece_position.0, next_row as usize),
+                        piece: pawn,
+                        taken_piece,
+                        taken_piece_position,
+                        promotion: None,
+                    });
+                }
+            }
+            pawns &= pawns - 1;
+        }
+    }
+
+    fn is_index_attacked(&self, square: usize, by_color: Color) -> bool {
+        let attackers = &self.pieces[get_color_index(by_color)];
+
+        // a pawn attacks the square if a pawn of the other color on it would attack the pawn
+        if PAWN_ATTACKS[get_color_index(by_color.opposite())][square]
+            & attackers[get_kind(PieceType::Pawn)]
+            != 0
+            || KNIGHT_ATTACKS[square] & attackers[get_kind(PieceType::Knight)] != 0
+            || KING_ATTACKS[square] & attackers[get_kind(PieceType::King)] != 0
+        {
+            return true;
+        }
+
+        let queens = attackers[get_kind(PieceType::Queen)];
+        let orthogonal = attackers[get_kind(PieceType::Rook)] | queens;
+        let diagonal = attackers[get_kind(PieceType::Bishop)] | queens;
+        rook_attacks_from_index(square, self.occupied) & orthogonal != 0
+            || bishop_attacks_from_index(square, self.occupied) & diagonal != 0
+    }
+
+    fn get_piece_at_index(&self, square: usize) -> Option<ChessPiece> {
+        if self.occupied & bit(square) == 0 {
+            return None;
+        }
+        for color in [Color::White, Color::Black] {
+            for (kind, piece_type) in PIECE_TYPES.iter().enumerate() {
+                if self.pieces[get_color_index(color)][kind] & bit(square) != 0 {
+                    return Some(ChessPiece::new(*piece_type, color));
+                }
+            }
+        }
+        None
+    }
+
+    fn set(&mut self, piece: &ChessPiece, square: usize) {
+        self.pieces[get_color_index(piece.get_color())][get_kind(piece.get_piece_type())] |=
+            bit(square);
+        self.occupied |= bit(square);
+    }
+
+    fn clear(&mut self, square: usize) {
+        let mask = !bit(square);
+        for pieces in self.pieces.iter_mut().flatten() {
+            *pieces &= mask;
+        }
+        self.occupied &= mask;
+    }
+}
+
+/// Returns the squares a rook on the given square attacks, stopping at the first occupied
+/// square in each direction.
+///
+/// # Arguments
+///
+/// * `square` - The square of the rook.
+/// * `occupied` - The bitboard of every occupied square.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::bitboard::rook_attacks;
+/// use simple_chess::square_name::SquareName;
+///
+/// assert_eq!(14, rook_attacks(SquareName::A1, 0).count_ones());
+///
+/// // a piece on a4 blocks the file, a4 itself is still attacked
+/// let blocked = rook_attacks(SquareName::A1, 1 << SquareName::A4 as u8);
+/// assert_eq!(10, blocked.count_ones());
+/// ```
+pub fn rook_attacks(square: SquareName, occupied: u64) -> u64 {
+    rook_attacks_from_index(square as usize, occupied)
+}
+
+/// Returns the squares a bishop on the given square attacks, stopping at the first
+/// occupied square in each direction.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::bitboard::bishop_attacks;
+/// use simple_chess::square_name::SquareName;
+///
+/// assert_eq!(13, bishop_attacks(SquareName::D4, 0).count_ones());
+/// ```
+pub fn bishop_attacks(square: SquareName, occupied: u64) -> u64 {
+    bishop_attacks_from_index(square as usize, occupied)
+}
+
+/// Returns the squares a queen on the given square attacks, the union of the rook and
+/// bishop attacks.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::bitboard::queen_attacks;
+/// use simple_chess::square_name::SquareName;
+///
+/// assert_eq!(27, queen_attacks(SquareName::D4, 0).count_ones());
+/// ```
+pub fn queen_attacks(square: SquareName, occupied: u64) -> u64 {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
+
+fn rook_attacks_from_index(square: usize, occupied: u64) -> u64 {
+    let tables = get_magic_tables();
+    tables.rook[square].lookup(&tables.attacks, occupied)
+}
+
+fn bishop_attacks_from_index(square: usize, occupied: u64) -> u64 {
+    let tables = get_magic_tables();
+    tables.bishop[square].lookup(&tables.attacks, occupied)
+}
+
+/// The magic multiplier of one square, mapping every relevant occupancy to a slot of the
+/// shared attack table.
+#[derive(Debug, Clone, Copy)]
+struct Magic {
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl Magic {
+    fn get_index(&self, occupied: u64) -> usize {
+        ((occupied & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+
+    fn lookup(&self, attacks: &[u64], occupied: u64) -> u64 {
+        attacks[self.offset + self.get_index(occupied)]
+    }
+}
+
+struct MagicTables {
+    rook: Vec<Magic>,
+    bishop: Vec<Magic>,
+    attacks: Vec<u64>,
+}
+
+/// Returns the magic tables, filling the attack table on first use.
+fn get_magic_tables() -> &'static MagicTables {
+    MAGIC_TABLES.get_or_init(|| {
+        let mut attacks = Vec::new();
+        let rook = (0..64)
+            .map(|square| {
+                build_magic(
+                    square,
+                    ROOK_MAGICS[square],
+                    &ORTHOGONAL_DIRECTIONS,
+                    &mut attacks,
+                )
+            })
+            .collect();
+        let bishop = (0..64)
+            .map(|square| {
+                build_magic(
+                    square,
+                    BISHOP_MAGICS[square],
+                    &DIAGONAL_DIRECTIONS,
+                    &mut attacks,
+                )
+            })
+            .collect();
+        MagicTables {
+            rook,
+            bishop,
+            attacks,
+        }
+    })
+}
+
+/// Appends the attacks of a square for every relevant occupancy to the shared table.
+fn build_magic(
+    square: usize,
+    magic: u64,
+    directions: &[(i32, i32)],
+    attacks: &mut Vec<u64>,
+) -> Magic {
+    let mask = get_relevant_occupancy(square, directions);
+    let bits = mask.count_ones();
+    let magic = Magic {
+        mask,
+        magic,
+        shift: 64 - bits,
+        offset: attacks.len(),
+    };
+    attacks.resize(attacks.len() + (1 << bits), 0);
+
+    // every subset of the mask, enumerated with the carry-rippler trick
+    let mut occupied = 0u64;
+    loop {
+        attacks[magic.offset + magic.get_index(occupied)] =
+            get_sliding_attacks(square, occupied, directions);
+        occupied = occupied.wrapping_sub(mask) & mask;
+        if occupied == 0 {
+            break;
+        }
+    }
+    magic
+}
+
+/// Returns the squares whose occupancy changes the attacks of a slider, every square of
+/// its rays except the last one before the edge of the board.
+fn get_relevant_occupancy(square: usize, directions: &[(i32, i32)]) -> u64 {
+    let mut mask = 0;
+    for direction in directions {
+        let mut col = (square % 8) as i32 + direction.0;
+        let mut row = (square / 8) as i32 + direction.1;
+        while (0..8).contains(&(col + direction.0)) && (0..8).contains(&(row + direction.1)) {
+            mask |= bit((col + row * 8) as usize);
+            col += direction.0;
+            row += direction.1;
+        }
+    }
+    mask
+}
+
+/// Returns the attacks of a slider by walking its rays, used to fill the magic tables.
+fn get_sliding_attacks(square: usize, occupied: u64, directions: &[(i32, i32)]) -> u64 {
+    let mut attacks = 0;
+    for direction in directions {
+        let mut col = (square % 8) as i32 + direction.0;
+        let mut row = (square / 8) as i32 + direction.1;
+        while (0..8).contains(&col) && (0..8).contains(&row) {
+            let square_bit = bit((col + row * 8) as usize);
+            attacks |= square_bit;
+            if occupied & square_bit != 0 {
+                break;
+            }
+            col += direction.0;
+            row += direction.1;
+        }
+    }
+    attacks
+}
+
+fn bit(square: usize) -> u64 {
+    1 << square
+}
+
+fn get_square(position: (usize, usize)) -> usize {
+    position.0 + position.1 * 8
+}
+
+fn get_position(square: usize) -> (usize, usize) {
+    (square % 8, square / 8)
+}
+
+fn get_color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn get_kind(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+        PieceType::Archbishop | PieceType::Chancellor => {
+            unreachable!("bitboards only hold the pieces of standard chess")
+        }
+    }
+}
+
+/// Returns `true` if a board holds no archbishops or chancellors.
+pub(crate) fn has_only_standard_pieces(board: &Board<ChessPiece>) -> bool {
+    board.pieces().all(|(_, piece)| {
+        !matches!(
+            piece.get_piece_type(),
+            PieceType::Archbishop | PieceType::Chancellor
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_game_state_analyzer::is_square_attacked;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::random_position::PositionRng;
+
+    const FENS: [&str; 4] = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    ];
+
+    #[test]
+    fn attacks_match_the_generic_path() {
+        for fen in FENS {
+            let game = build_game_from_string(fen).unwrap();
+            let board = game.get_board();
+            let bitboards = Bitboards::from_board(board).unwrap();
+            assert_eq!(board, &bitboards.to_board());
+            for square in SquareName::all() {
+                let (col, row) = square.get_col_and_row();
+                for color in [Color::White, Color::Black] {
+                    assert_eq!(
+                        is_square_attacked(board, col, row, color),
+                        bitboards.is_square_attacked(*square, color),
+                        "{} square {} by {:?}",
+                        fen,
+                        square,
+                        color
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn magic_attacks_match_walking_the_rays() {
+        let mut rng = PositionRng::new(9);
+        for _ in 0..200 {
+            let occupied = rng.next_u64() & rng.next_u64();
+            for square in 0..64 {
+                assert_eq!(
+                    get_sliding_attacks(square, occupied, &ORTHOGONAL_DIRECTIONS),
+                    rook_attacks_from_index(square, occupied)
+                );
+                assert_eq!(
+                    get_sliding_attacks(square, occupied, &DIAGONAL_DIRECTIONS),
+                    bishop_attacks_from_index(square, occupied)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pseudo_legal_moves_match_the_pieces() {
+        let fens = FENS
+            .iter()
+            .copied()
+            .chain(["4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1"]);
+        for fen in fens {
+            let game = build_game_from_string(fen).unwrap();
+            let board = game.get_board();
+            let color = game.get_current_players_turn();
+
+            let mut expected = Vec::new();
+            for square in SquareName::all() {
+                let (col, row) = square.get_col_and_row();
+                if let Some(piece) = board.get_piece_at_space(col, row) {
+                    if piece.get_color() == color {
+                        expected.extend(piece.possible_moves_with_ruleset(
+                            (col, row),
+                            board,
+                            game.get_en_passant_target(),
+                            game.get_ruleset(),
+                        ));
+                    }
+                }
+            }
+            let moves = Bitboards::from_board(board)
+                .unwrap()
+                .get_pseudo_legal_moves(color, game.get_en_passant_target());
+
+            assert_eq!(expected.len(), moves.len(), "{fen}");
+            assert!(expected.iter().all(|m| moves.contains(m)), "{fen}");
+        }
+    }
+
+    #[test]
+    fn only_standard_games_use_the_fast_path() {
+        let ruleset = Ruleset::new();
+        assert!(is_standard_chess(&Board::build(8, 8).unwrap(), &ruleset));
+        assert!(!is_standard_chess(&Board::build(10, 8).unwrap(), &ruleset));
+        assert_eq!(None, Bitboards::from_board(&Board::build(10, 8).unwrap()));
+    }
+}
@@ -0,0 +1,493 @@
+use crate::bitboard::has_only_standard_pieces;
+use crate::chess_game_move_analyzer::get_legal_moves;
+use crate::chess_move::ChessMoveType;
+use crate::error::ChessError;
+use crate::piece::PieceType;
+use crate::zobrist::get_en_passant_target;
+use crate::{ChessGame, Color};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::path::Path;
+
+const ENTRY_SIZE: usize = 16;
+const RANDOM_KEY_COUNT: usize = 781;
+const CASTLING_KEY_INDEX: usize = 768;
+const EN_PASSANT_KEY_INDEX: usize = 772;
+const TURN_KEY_INDEX: usize = 780;
+const RANDOM_KEY_SEED: u64 = 0x0B00_C5EE_D5A1_7B0C;
+
+/// A single entry of a Polyglot opening book.
+///
+/// Entries are stored in the file as 16 big-endian bytes: the position key, the move, the
+/// weight of the move and a learn value that is kept but not used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookEntry {
+    key: u64,
+    raw_move: u16,
+    weight: u16,
+    learn: u32,
+}
+
+impl BookEntry {
+    /// Creates a new `BookEntry`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The Polyglot key of the position, see `Book::get_key`.
+    /// * `raw_move` - The move in Polyglot's 16-bit encoding, see `encode_polyglot_move`.
+    /// * `weight` - How often the move should be played relative to the other moves of the
+    ///   position.
+    /// * `learn` - Learning data written by some book tools.
+    pub fn new(key: u64, raw_move: u16, weight: u16, learn: u32) -> Self {
+        Self {
+            key,
+            raw_move,
+            weight,
+            learn,
+        }
+    }
+
+    /// Returns the Polyglot key of the position.
+    pub fn get_key(&self) -> u64 {
+        self.key
+    }
+
+    /// Returns the move in Polyglot's 16-bit encoding.
+    pub fn get_raw_move(&self) -> u16 {
+        self.raw_move
+    }
+
+    /// Returns the weight of the move.
+    pub fn get_weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// Returns the learn value of the entry.
+    pub fn get_learn(&self) -> u32 {
+        self.learn
+    }
+}
+
+/// A move found in an opening book, with its weight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookMove {
+    chess_move: ChessMoveType,
+    weight: u16,
+}
+
+impl BookMove {
+    /// Returns the move.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.chess_move
+    }
+
+    /// Returns the weight of the move, moves with a greater weight should be played more
+    /// often.
+    pub fn get_weight(&self) -> u16 {
+        self.weight
+    }
+}
+
+/// An opening book in the Polyglot `.bin` format.
+///
+/// Positions are looked up by a Zobrist style key computed with Polyglot's layout of 781
+/// random numbers. Books written by other tools use the random numbers published with
+/// Polyglot, pass that table to `set_random_keys` before looking up positions in them. Books
+/// only contain positions of standard 8x8 chess.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::book::{encode_polyglot_move, Book, BookEntry};
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessGame, ChessMoveType, Color};
+///
+/// let game = ChessGame::new();
+/// let e4 = ChessMoveType::Move {
+///     original_position: (4, 1),
+///     new_position: (4, 3),
+///     piece: ChessPiece::new(PieceType::Pawn, Color::White),
+///     taken_piece: None,
+///     promotion: None,
+/// };
+///
+/// let key = Book::new().get_key(&game);
+/// let book = Book::from_entries(vec![BookEntry::new(key, encode_polyglot_move(&e4), 10, 0)]);
+///
+/// let book_moves = game.book_moves(&book);
+/// assert_eq!(1, book_moves.len());
+/// assert_eq!(&e4, book_moves[0].get_move());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Book {
+    entries: Vec<BookEntry>,
+    random_keys: Vec<u64>,
+}
+
+impl Book {
+    /// Creates an empty book.
+    pub fn new() -> Self {
+        Self::from_entries(Vec::new())
+    }
+
+    /// Creates a book from a list of entries, in any order.
+    pub fn from_entries(mut entries: Vec<BookEntry>) -> Self {
+        entries.sort_by_key(|entry| entry.key);
+        Self {
+            entries,
+            random_keys: (0..RANDOM_KEY_COUNT as u64).map(random_key).collect(),
+        }
+    }
+
+    /// Reads a book from the contents of a Polyglot `.bin` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Book)` - The book read from the bytes.
+    /// * `Err(BookError)` - If the length of the bytes is not a whole number of entries.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BookError> {
+        if !bytes.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(BookError {
+                reason: format!(
+                    "book length of {} bytes is not a multiple of {}",
+                    bytes.len(),
+                    ENTRY_SIZE
+                ),
+            });
+        }
+
+        let entries = bytes
+            .chunks_exact(ENTRY_SIZE)
+            .map(|chunk| BookEntry {
+                key: u64::from_be_bytes(chunk[0..8].try_into().unwrap()),
+                raw_move: u16::from_be_bytes(chunk[8..10].try_into().unwrap()),
+                weight: u16::from_be_bytes(chunk[10..12].try_into().unwrap()),
+                learn: u32::from_be_bytes(chunk[12..16].try_into().unwrap()),
+            })
+            .collect();
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Reads a book from a Polyglot `.bin` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Book)` - The book read from the file.
+    /// * `Err(BookError)` - If the file can not be read or is not a valid book.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, BookError> {
+        let bytes = std::fs::read(path.as_ref()).map_err(|error| BookError {
+            reason: format!("could not read {}: {}", path.as_ref().display(), error),
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Writes the book in the Polyglot `.bin` format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.entries.len() * ENTRY_SIZE);
+        for entry in &self.entries {
+            bytes.extend(entry.key.to_be_bytes());
+            bytes.extend(entry.raw_move.to_be_bytes());
+            bytes.extend(entry.weight.to_be_bytes());
+            bytes.extend(entry.learn.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Sets the 781 random numbers positions are keyed with.
+    ///
+    /// # Arguments
+    ///
+    /// * `random_keys` - The random numbers, in the order of Polyglot's `Random64` table.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Book` with the random numbers set.
+    pub fn set_random_keys(mut self, random_keys: [u64; RANDOM_KEY_COUNT]) -> Self {
+        self.random_keys = random_keys.to_vec();
+        self
+    }
+
+    /// Returns every entry of the book, sorted by key.
+    pub fn get_entries(&self) -> &[BookEntry] {
+        &self.entries
+    }
+
+    /// Returns `true` if the book has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the Polyglot key of the current position of a game.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board of the game is not 8x8 or holds an archbishop or chancellor.
+    pub fn get_key(&self, game: &ChessGame) -> u64 {
+        let board = game.get_board();
+        assert!(
+            board.get_width() == 8 && board.get_height() == 8,
+            "Polyglot books only support 8x8 boards"
+        );
+        assert!(
+            has_only_standard_pieces(board),
+            "Polyglot books only support the pieces of standard chess"
+        );
+
+        let mut key = 0;
+        for row in 0..8 {
+            for col in 0..8 {
+                if let Some(piece) = board.get_piece_at_space(col, row) {
+                    let kind = get_piece_kind(piece.get_piece_type()) * 2
+                        + usize::from(piece.get_color() == Color::White);
+                    key ^= self.random_keys[64 * kind + 8 * row + col];
+                }
+            }
+        }
+
+        let (white_long, white_short, black_long, black_short) = game.get_castling_rights();
+        for (offset, right) in [white_short, white_long, black_short, black_long]
+            .into_iter()
+            .enumerate()
+        {
+            if right {
+                key ^= self.random_keys[CASTLING_KEY_INDEX + offset];
+            }
+        }
+
+        if let Some((col, _)) = get_en_passant_target(game) {
+            key ^= self.random_keys[EN_PASSANT_KEY_INDEX + col];
+        }
+
+        if game.get_current_players_turn() == Color::White {
+            key ^= self.random_keys[TURN_KEY_INDEX];
+        }
+        key
+    }
+
+    /// Returns the legal book moves of the current position of a game, heaviest first.
+    ///
+    /// Entries whose move is not legal in the position, which can happen when two positions
+    /// share a key, are skipped. Boards that are not 8x8, or that hold an archbishop or
+    /// chancellor, never have book moves.
+    pub fn get_moves(&self, game: &ChessGame) -> Vec<BookMove> {
+        let board = game.get_board();
+        if board.get_width() != 8 || board.get_height() != 8 || !has_only_standard_pieces(board) {
+            return Vec::new();
+        }
+
+        let key = self.get_key(game);
+        let start = self.entries.partition_point(|entry| entry.key < key);
+        let entries: Vec<&BookEntry> = self.entries[start..]
+            .iter()
+            .take_while(|entry| entry.key == key)
+            .collect();
+        if entries.is_empty() {
+            return Vec::new();
+        }
+
+        let legal_moves = get_legal_moves(&mut game.clone());
+        let mut book_moves: Vec<BookMove> = entries
+            .into_iter()
+            .filter_map(|entry| {
+                legal_moves
+                    .iter()
+                    .find(|chess_move| encode_polyglot_move(chess_move) == entry.raw_move)
+                    .map(|chess_move| BookMove {
+                        chess_move: *chess_move,
+                        weight: entry.weight,
+                    })
+            })
+            .collect();
+        book_moves.sort_by_key(|book_move| std::cmp::Reverse(book_move.weight));
+        book_moves
+    }
+}
+
+impl Default for Book {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encodes a move in Polyglot's 16-bit move format.
+///
+/// The bits hold, from lowest to highest, the file and rank of the destination, the file
+/// and rank of the origin and the promotion piece. Castling is encoded as the king moving
+/// onto the square of its rook.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::book::encode_polyglot_move;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessMoveType, Color};
+///
+/// let e4 = ChessMoveType::Move {
+///     original_position: (4, 1),
+///     new_position: (4, 3),
+///     piece: ChessPiece::new(PieceType::Pawn, Color::White),
+///     taken_piece: None,
+///     promotion: None,
+/// };
+/// assert_eq!(0x031C, encode_polyglot_move(&e4));
+/// ```
+pub fn encode_polyglot_move(chess_move: &ChessMoveType) -> u16 {
+    let (from, to, promotion) = match chess_move {
+        ChessMoveType::Move {
+            original_position,
+            new_position,
+            promotion,
+            ..
+        }
+        | ChessMoveType::EnPassant {
+            original_position,
+            new_position,
+            promotion,
+            ..
+        } => (*original_position, *new_position, *promotion),
+        ChessMoveType::Castle {
+            king_original_position,
+            rook_original_position,
+            ..
+        } => (*king_original_position, *rook_original_position, None),
+    };
+
+    let promotion = match promotion.map(|piece| piece.get_piece_type()) {
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(PieceType::Queen) => 4,
+        _ => 0,
+    };
+    (to.0 | to.1 << 3 | from.0 << 6 | from.1 << 9 | promotion << 12) as u16
+}
+
+/// Error returned when an opening book can not be read.
+pub struct BookError {
+    reason: String,
+}
+
+impl Display for BookError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid opening book: {}", self.reason)
+    }
+}
+
+impl Debug for BookError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BookError: {}", self.reason)
+    }
+}
+
+impl Error for BookError {}
+
+impl From<BookError> for ChessError {
+    fn from(error: BookError) -> Self {
+        ChessError::ParseError(error.reason)
+    }
+}
+
+/// Returns Polyglot's index of a piece type, pawns first and kings last.
+fn get_piece_kind(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+        PieceType::Archbishop | PieceType::Chancellor => {
+            unreachable!("Polyglot books only hold the pieces of standard chess")
+        }
+    }
+}
+
+/// Produces the random number for an index of the key table using the SplitMix64 finalizer.
+fn random_key(index: u64) -> u64 {
+    let mut z = RANDOM_KEY_SEED.wrapping_add(index.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+
+    #[test]
+    fn book_moves_are_found_and_sorted_by_weight() {
+        let game = ChessGame::new();
+        let key = Book::new().get_key(&game);
+        let e4 = decode_move_from_string(&game, "e4").unwrap();
+        let d4 = decode_move_from_string(&game, "d4").unwrap();
+
+        let book = Book::from_entries(vec![
+            BookEntry::new(key, encode_polyglot_move(&e4), 5, 0),
+            BookEntry::new(key, encode_polyglot_move(&d4), 20, 0),
+            BookEntry::new(key ^ 1, encode_polyglot_move(&e4), 50, 0),
+        ]);
+
+        let book_moves = game.book_moves(&book);
+        assert_eq!(2, book_moves.len());
+        assert_eq!(&d4, book_moves[0].get_move());
+        assert_eq!(20, book_moves[0].get_weight());
+        assert_eq!(&e4, book_moves[1].get_move());
+    }
+
+    #[test]
+    fn books_round_trip_through_bytes() {
+        let book = Book::from_entries(vec![
+            BookEntry::new(0x0123_4567_89AB_CDEF, 0x031C, 7, 42),
+            BookEntry::new(1, 2, 3, 4),
+        ]);
+        let bytes = book.to_bytes();
+        assert_eq!(32, bytes.len());
+        assert_eq!(
+            book.get_entries(),
+            Book::from_bytes(&bytes).unwrap().get_entries()
+        );
+
+        assert!(Book::from_bytes(&bytes[..15]).is_err());
+    }
+
+    #[test]
+    fn castling_is_encoded_as_the_king_taking_its_rook() {
+        let game = build_game_from_string("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        let short_castle = get_legal_moves(&mut game.clone())
+            .into_iter()
+            .find(|m| {
+                matches!(
+                    m,
+                    ChessMoveType::Castle {
+                        rook_original_position: (7, 0),
+                        ..
+                    }
+                )
+            })
+            .unwrap();
+
+        // e1 to h1
+        assert_eq!(4 << 6 | 7, encode_polyglot_move(&short_castle));
+    }
+
+    #[test]
+    fn key_depends_on_side_to_move_and_castling_rights() {
+        let book = Book::new();
+        let white = build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let black = build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+        let no_castling = build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+
+        assert_ne!(book.get_key(&white), book.get_key(&black));
+        assert_ne!(book.get_key(&white), book.get_key(&no_castling));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::piece::ChessPiece;
+use crate::position::Position;
+use crate::Color;
+use game_board::Board;
+
+/// Iterators over the squares and pieces of a chess board.
+///
+/// The squares are visited rank by rank from White's side, and from the a-file to the
+/// last file within a rank, so `a1` comes first and the top right corner last. This saves
+/// writing nested loops over the columns and rows of the board.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_board::ChessBoard;
+/// use simple_chess::position::Position;
+/// use simple_chess::{ChessGame, Color};
+///
+/// let game = ChessGame::new();
+/// let board = game.get_board();
+///
+/// assert_eq!(64, board.squares().count());
+/// assert_eq!(32, board.pieces().count());
+///
+/// let (first, rook) = board.pieces_of(Color::Black).next().unwrap();
+/// assert_eq!(Position::new(0, 6), first);
+/// assert_eq!("a7", first.to_string());
+/// assert_eq!(Color::Black, rook.get_color());
+/// ```
+pub trait ChessBoard {
+    /// Returns every square with the piece standing on it, if any.
+    fn squares(&self) -> impl Iterator<Item = (Position, Option<&ChessPiece>)>;
+
+    /// Returns the occupied squares with their pieces.
+    fn pieces(&self) -> impl Iterator<Item = (Position, &ChessPiece)> {
+        self.squares()
+            .filter_map(|(position, piece)| Some((position, piece?)))
+    }
+
+    /// Returns the occupied squares with the pieces of one player.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player whose pieces are returned.
+    fn pieces_of(&self, color: Color) -> impl Iterator<Item = (Position, &ChessPiece)> {
+        self.pieces()
+            .filter(move |(_, piece)| piece.get_color() == color)
+    }
+}
+
+impl ChessBoard for Board<ChessPiece> {
+    fn squares(&self) -> impl Iterator<Item = (Position, Option<&ChessPiece>)> {
+        let width = self.get_width();
+        (0..width * self.get_height()).map(move |index| {
+            let (col, row) = (index % width, index / width);
+            (Position::new(col, row), self.get_piece_at_space(col, row))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::piece::PieceType;
+
+    #[test]
+    fn squares_are_visited_rank_by_rank() {
+        let board = Board::<ChessPiece>::build(3, 2).unwrap();
+        let names: Vec<String> = board
+            .squares()
+            .map(|(position, _)| position.to_string())
+            .collect();
+
+        assert_eq!(vec!["a1", "b1", "c1", "a2", "b2", "c2"], names);
+    }
+
+    #[test]
+    fn pieces_are_filtered_by_color() {
+        let game = build_game_from_string("4k3/8/8/8/8/8/3PP3/4K3 w - - 0 1").unwrap();
+        let board = game.get_board();
+
+        let white: Vec<_> = board
+            .pieces_of(Color::White)
+            .map(|(position, piece)| (position.to_string(), piece.get_piece_type()))
+            .collect();
+        assert_eq!(
+            vec![
+                (String::from("e1"), PieceType::King),
+                (String::from("d2"), PieceType::Pawn),
+                (String::from("e2"), PieceType::Pawn),
+            ],
+            white
+        );
+        assert_eq!(1, board.pieces_of(Color::Black).count());
+        assert_eq!(60, board.squares().filter(|(_, p)| p.is_none()).count());
+    }
+}
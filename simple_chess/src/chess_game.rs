@@ -1,14 +1,38 @@
+use crate::adjudication::Adjudication;
+use crate::analysis::candidate_moves::{get_candidate_moves, CandidateMove};
+use crate::bitboard::is_standard_chess;
+use crate::book::{Book, BookMove};
 use crate::chess_game::DrawReason::{FiftyMoveRule, InsufficientPieces, Repetition};
-use crate::chess_game_state_analyzer::{get_game_state, is_insufficient_material, GameState};
+use crate::chess_game_move_analyzer::get_legal_moves;
+use crate::chess_game_state_analyzer::{get_game_state, is_current_player_in_check, GameState};
 use crate::chess_move::ChessMoveType;
-use crate::codec::binary::encode_board_as_binary;
+use crate::clock::ChessClock;
+use crate::codec::binary::{decode_game_from_binary, encode_game_as_binary, BinaryDecodeError};
+use crate::codec::forsyth_edwards_notation::encode_game_as_string;
+use crate::codec::json::encode_game_state_as_json;
+use crate::codec::long_algebraic_notation::encode_move_as_string;
+use crate::codec::standard_algebraic_notation::encode_move_as_string as encode_move_as_san;
+use crate::engine::Engine;
+use crate::error::ChessError;
+use crate::game_result::{GameResult, Termination};
+use crate::history::MoveRecord;
+use crate::legality::{get_illegal_move_reason, IllegalMoveReason};
+use crate::observer::{GameObserver, Observers};
 use crate::piece::ChessPiece;
-use crate::piece::PieceType::{Bishop, King, Knight, Pawn, Queen, Rook};
+use crate::piece::PieceType::{King, Pawn, Rook};
+use crate::repetition::{PositionKey, RepetitionTable};
+use crate::ruleset::Ruleset;
+use crate::scoring::{Outcome, Scoring};
+use crate::setup::BoardSetup;
+use crate::validation::{validate, InvariantViolation};
+use crate::variant::{StandardChess, Variant};
 use crate::Color;
 use crate::Color::{Black, White};
-use game_board::Board;
-use std::collections::HashMap;
+use game_board::{Board, SquareSet};
+use std::sync::Arc;
+use std::time::Duration;
 
+#[derive(Clone)]
 pub struct ChessGame {
     board: Board<ChessPiece>,
     current_players_turn: Color,
@@ -19,9 +43,25 @@ pub struct ChessGame {
     can_black_castle_short: bool,
     can_black_castle_long: bool,
     moves: Vec<ChessMoveType>,
-    previous_board_states: Vec<Vec<u8>>,
+    en_passant_target: Option<(usize, usize)>,
+    repetition_table: RepetitionTable,
+    ruleset: Ruleset,
+    legal_moves_cache: Option<Vec<ChessMoveType>>,
+    game_state_cache: Option<GameState>,
+    is_standard_chess: bool,
+    variant: Arc<dyn Variant>,
+    checks_given: (usize, usize),
+    captured_pieces: (Vec<ChessPiece>, Vec<ChessPiece>),
+    observers: Observers,
+    history: Arc<Vec<MoveRecord>>,
+    ending: Option<(Adjudication, Termination)>,
+    draw_offer: Option<Color>,
+    scoring: Scoring,
 }
 
+/// The number of half-moves without a pawn move or capture after which a draw can be claimed.
+const FIFTY_MOVE_RULE_LIMIT: usize = 100;
+
 #[derive(Debug)]
 pub enum DrawReason {
     InsufficientPieces,
@@ -30,19 +70,9 @@ pub enum DrawReason {
 }
 
 fn build_board_with_starting_position() -> Board<ChessPiece> {
-    let mut board = Board::<ChessPiece>::build(8, 8).unwrap();
-
-    for (col, piece_type) in [Rook, Knight, Bishop, Queen, King, Bishop, Knight, Rook]
-        .iter()
-        .enumerate()
-    {
-        board.place_piece(ChessPiece::new(*piece_type, Black), col, 7);
-        board.place_piece(ChessPiece::new(Pawn, Black), col, 6);
-        board.place_piece(ChessPiece::new(Pawn, White), col, 1);
-        board.place_piece(ChessPiece::new(*piece_type, White), col, 0);
-    }
-
-    board
+    BoardSetup::standard()
+        .build_board()
+        .expect("the standard setup is a valid board")
 }
 
 impl ChessGame {
@@ -63,7 +93,7 @@ impl ChessGame {
     /// let game = ChessGame::new();
     /// ```
     pub fn new() -> ChessGame {
-        ChessGame {
+        let mut game = ChessGame {
             board: build_board_with_starting_position(),
             current_players_turn: White,
             turn_number: 1,
@@ -73,10 +103,27 @@ impl ChessGame {
             can_black_castle_short: true,
             can_black_castle_long: true,
             moves: Vec::new(),
-            previous_board_states: Vec::new(),
-        }
+            en_passant_target: None,
+            repetition_table: RepetitionTable::new(),
+            ruleset: Ruleset::new(),
+            legal_moves_cache: None,
+            game_state_cache: None,
+            is_standard_chess: false,
+            variant: Arc::new(StandardChess),
+            checks_given: (0, 0),
+            captured_pieces: (Vec::new(), Vec::new()),
+            observers: Observers::default(),
+            history: Arc::default(),
+            ending: None,
+            draw_offer: None,
+            scoring: Scoring::Standard,
+        };
+        game.is_standard_chess = is_standard_chess(&game.board, &game.ruleset);
+        game.record_position();
+        game
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn build(
         board: Board<ChessPiece>,
         current_players_turn: Color,
@@ -88,7 +135,17 @@ impl ChessGame {
         can_black_castle_long: bool,
         moves: Vec<ChessMoveType>,
     ) -> ChessGame {
-        ChessGame {
+        let mut captured_pieces = (Vec::new(), Vec::new());
+        for chess_move in &moves {
+            if let Some(taken_piece) = get_taken_piece(chess_move) {
+                match taken_piece.get_color() {
+                    Black => captured_pieces.0.push(taken_piece),
+                    White => captured_pieces.1.push(taken_piece),
+                }
+            }
+        }
+        let en_passant_target = moves.last().and_then(get_double_step_target);
+        let mut game = ChessGame {
             board,
             current_players_turn,
             turn_number,
@@ -98,10 +155,202 @@ impl ChessGame {
             can_black_castle_short,
             can_black_castle_long,
             moves,
-            previous_board_states: vec![], // TODO generate previous board states from moves
+            en_passant_target,
+            repetition_table: RepetitionTable::new(), // TODO generate previous positions from moves
+            ruleset: Ruleset::new(),
+            legal_moves_cache: None,
+            game_state_cache: None,
+            is_standard_chess: false,
+            variant: Arc::new(StandardChess),
+            checks_given: (0, 0),
+            captured_pieces,
+            observers: Observers::default(),
+            history: Arc::default(),
+            ending: None,
+            draw_offer: None,
+            scoring: Scoring::Standard,
+        };
+        game.is_standard_chess = is_standard_chess(&game.board, &game.ruleset);
+        game.record_position();
+        game
+    }
+
+    pub(crate) fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.ruleset = ruleset;
+        self.invalidate_caches();
+        self.is_standard_chess = is_standard_chess(&self.board, &self.ruleset);
+    }
+
+    pub(crate) fn set_variant(&mut self, variant: Arc<dyn Variant>) {
+        self.variant = variant;
+        self.invalidate_caches();
+    }
+
+    /// Returns how the end of the game is scored.
+    pub fn get_scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Sets how the end of the game is scored, such as Armageddon where a draw counts as a
+    /// win for one side. Games are scored the standard way unless set.
+    pub fn set_scoring(&mut self, scoring: Scoring) {
+        self.scoring = scoring;
+    }
+
+    /// Returns the scored outcome of the game once it is over, see `Scoring`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Outcome)` - The outcome of a finished game.
+    /// * `None` - If the game is still in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::scoring::Outcome;
+    /// use simple_chess::{ChessGame, Color};
+    ///
+    /// let mut game = ChessGame::new();
+    /// assert_eq!(None, game.get_outcome());
+    ///
+    /// game.resign(Color::Black).unwrap();
+    /// assert_eq!(Some(Outcome::Win { winner: Color::White }), game.get_outcome());
+    /// ```
+    pub fn get_outcome(&mut self) -> Option<Outcome> {
+        let scoring = self.scoring;
+        scoring.get_outcome(self.state())
+    }
+
+    /// Returns the result of the game once it is over, who won it under the game's
+    /// `Scoring` and how it ended.
+    ///
+    /// While the game is played its `GameState` tells whether the player to move is in
+    /// check, the result only exists once there is nothing left to play.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(GameResult)` - The result of a finished game.
+    /// * `None` - If the game is still in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::adjudication::Adjudication;
+    /// use simple_chess::game_result::Termination;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// game.adjudicate(Adjudication::Draw).unwrap();
+    ///
+    /// let result = game.get_result().unwrap();
+    /// assert!(result.is_draw());
+    /// assert_eq!(Termination::Adjudication, result.get_termination());
+    /// ```
+    pub fn get_result(&mut self) -> Option<GameResult> {
+        let scoring = self.scoring;
+        let result = GameResult::from_state(self.state(), scoring)?;
+        match self.ending {
+            Some((_, termination)) => Some(GameResult::new(result.get_winner(), termination)),
+            None => Some(result),
+        }
+    }
+
+    pub(crate) fn set_en_passant_target(&mut self, en_passant_target: Option<(usize, usize)>) {
+        self.en_passant_target = en_passant_target;
+        self.invalidate_caches();
+        // the position was recorded without the en passant capture
+        self.repetition_table.clear();
+        self.record_position();
+    }
+
+    pub(crate) fn set_checks_given(&mut self, white: usize, black: usize) {
+        self.checks_given = (white, black);
+        self.invalidate_caches();
+    }
+
+    pub(crate) fn get_repetition_table(&self) -> &RepetitionTable {
+        &self.repetition_table
+    }
+
+    pub(crate) fn set_repetition_table(&mut self, repetition_table: RepetitionTable) {
+        self.repetition_table = repetition_table;
+    }
+
+    pub(crate) fn get_ending(&self) -> Option<(Adjudication, Termination)> {
+        self.ending
+    }
+
+    pub(crate) fn set_ending(&mut self, ending: Option<(Adjudication, Termination)>) {
+        self.ending = ending;
+        self.invalidate_caches();
+    }
+
+    /// Returns how many checks a player has given.
+    ///
+    /// Checks are only counted in variants with a check limit, such as Three-check, and are
+    /// always `0` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::Color;
+    ///
+    /// let game = build_game_from_string("4k3/8/8/8/8/8/8/4K3 w - - 2+1 0 1").unwrap();
+    /// assert_eq!(1, game.get_checks_given(Color::White));
+    /// assert_eq!(2, game.get_checks_given(Color::Black));
+    /// ```
+    pub fn get_checks_given(&self, color: Color) -> usize {
+        match color {
+            White => self.checks_given.0,
+            Black => self.checks_given.1,
+        }
+    }
+
+    /// Returns the pieces a player has captured, in the order they were taken.
+    ///
+    /// Only captures in the game's move history are included, so a game loaded from a
+    /// position without its moves starts with no captured pieces.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player who made the captures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::piece::{ChessPiece, PieceType};
+    /// use simple_chess::{ChessGame, ChessMoveType, Color};
+    ///
+    /// let mut game = build_game_from_string("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+    /// game.make_move(ChessMoveType::Move {
+    ///     original_position: (4, 3),
+    ///     new_position: (3, 4),
+    ///     piece: ChessPiece::new(PieceType::Pawn, Color::White),
+    ///     taken_piece: Some(ChessPiece::new(PieceType::Pawn, Color::Black)),
+    ///     promotion: None,
+    /// });
+    ///
+    /// assert_eq!(
+    ///     &[ChessPiece::new(PieceType::Pawn, Color::Black)],
+    ///     game.get_captured_pieces(Color::White)
+    /// );
+    /// assert!(game.get_captured_pieces(Color::Black).is_empty());
+    /// ```
+    pub fn get_captured_pieces(&self, color: Color) -> &[ChessPiece] {
+        match color {
+            White => &self.captured_pieces.0,
+            Black => &self.captured_pieces.1,
         }
     }
 
+    /// Returns the variant the game is played under, `StandardChess` unless another variant
+    /// was set with the `ChessGameBuilder`.
+    pub fn get_variant(&self) -> &dyn Variant {
+        self.variant.as_ref()
+    }
+
     /// Get board
     ///
     /// # Returns
@@ -129,6 +378,7 @@ impl ChessGame {
     /// // Modify the board or make moves
     /// ```
     pub fn get_board_mut(&mut self) -> &mut Board<ChessPiece> {
+        self.invalidate_caches();
         &mut self.board
     }
 
@@ -204,6 +454,135 @@ impl ChessGame {
         &self.moves
     }
 
+    /// Returns the board as it was after the given number of moves.
+    ///
+    /// The position is found by taking back moves from the current board, use a
+    /// `GameViewer` to step through many positions of a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The number of moves played to reach the position, `0` for the position the
+    ///   game started from.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Board<ChessPiece>)` - The board after `ply` moves.
+    /// * `None` - If fewer moves have been played.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// if let GameState::InProgress { legal_moves, .. } = game.get_game_state() {
+    ///     game.make_move(legal_moves[0]);
+    /// }
+    ///
+    /// assert_eq!(Some(ChessGame::new().get_board().clone()), game.position_at(0));
+    /// assert_eq!(Some(game.get_board().clone()), game.position_at(1));
+    /// assert_eq!(None, game.position_at(2));
+    /// ```
+    pub fn position_at(&self, ply: usize) -> Option<Board<ChessPiece>> {
+        let mut board = self.board.clone();
+        for chess_move in self.moves.get(ply..)?.iter().rev() {
+            chess_move.undo_move(&mut board);
+        }
+        Some(board)
+    }
+
+    /// Rebuilds the game as it was before its first move, so its moves can be played again
+    /// from the start.
+    ///
+    /// The board, turn and turn number are restored exactly. Castling rights are restored
+    /// for every king and rook that were still on their starting squares and either still
+    /// have the right or lost it during the game, so a right that was missing from the
+    /// start but would have been lost anyway may be restored. The halfmove clock starts at
+    /// zero and the en passant square of the starting position is only known for games
+    /// without moves.
+    pub(crate) fn get_starting_game(&self) -> ChessGame {
+        let board = self
+            .position_at(0)
+            .expect("every game has a starting position");
+        let is_odd = self.moves.len() % 2 == 1;
+        let current_players_turn = if is_odd {
+            self.current_players_turn.opposite()
+        } else {
+            self.current_players_turn
+        };
+        let black_moves = match current_players_turn {
+            White => self.moves.len() / 2,
+            Black => self.moves.len().div_ceil(2),
+        };
+        let turn_number = self.turn_number.saturating_sub(black_moves).max(1);
+
+        let height = board.get_height();
+        let has_piece = |piece_type, color: Color, col| {
+            board.get_piece_at_space(col, color.relative_rank(0, height))
+                == Some(&ChessPiece::new(piece_type, color))
+        };
+        let has_king = |color: Color| (0..board.get_width()).any(|col| has_piece(King, color, col));
+        let last_col = board.get_width() - 1;
+        let possible_rights = (
+            has_king(White) && has_piece(Rook, White, last_col),
+            has_king(White) && has_piece(Rook, White, 0),
+            has_king(Black) && has_piece(Rook, Black, last_col),
+            has_king(Black) && has_piece(Rook, Black, 0),
+        );
+
+        let build = |rights: (bool, bool, bool, bool)| {
+            let mut game = ChessGame::build(
+                board.clone(),
+                current_players_turn,
+                turn_number,
+                0,
+                rights.0,
+                rights.1,
+                rights.2,
+                rights.3,
+                Vec::new(),
+            );
+            game.ruleset = self.ruleset;
+            game.variant = Arc::clone(&self.variant);
+            if self.moves.is_empty() {
+                game.en_passant_target = self.en_passant_target;
+            }
+            game.is_standard_chess = is_standard_chess(&game.board, &game.ruleset);
+            game
+        };
+
+        // a right kept through a replay of the moves but missing now was never there
+        let mut replay = build(possible_rights);
+        for chess_move in &self.moves {
+            replay.apply_move(*chess_move);
+        }
+        let restore =
+            |possible: bool, replayed: bool, current: bool| possible && (current || !replayed);
+        build((
+            restore(
+                possible_rights.0,
+                replay.can_white_castle_short,
+                self.can_white_castle_short,
+            ),
+            restore(
+                possible_rights.1,
+                replay.can_white_castle_long,
+                self.can_white_castle_long,
+            ),
+            restore(
+                possible_rights.2,
+                replay.can_black_castle_short,
+                self.can_black_castle_short,
+            ),
+            restore(
+                possible_rights.3,
+                replay.can_black_castle_long,
+                self.can_black_castle_long,
+            ),
+        ))
+    }
+
     /// Get the last move made in the game.
     ///
     /// # Returns
@@ -223,7 +602,50 @@ impl ChessGame {
         self.moves.last()
     }
 
-    /// Get the fifty-move rule counter
+    /// Returns the square a pawn passed over with a double step on the last move, the
+    /// square a pawn of the player to move would capture it on en passant.
+    ///
+    /// The square is kept whether or not a pawn can make the capture, like the en passant
+    /// field of FEN. Games built from FEN keep the square of the FEN string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// game.make_move(decode_move_from_string(&game, "e4").unwrap());
+    /// assert_eq!(Some((4, 2)), game.get_en_passant_target());
+    ///
+    /// let game = build_game_from_string("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 12").unwrap();
+    /// assert_eq!(Some((3, 5)), game.get_en_passant_target());
+    /// assert!(game.get_moves().is_empty());
+    /// ```
+    pub fn get_en_passant_target(&self) -> Option<(usize, usize)> {
+        self.en_passant_target
+    }
+
+    /// Get the rule parameters used to generate moves for this game.
+    ///
+    /// # Returns
+    ///
+    /// `&Ruleset`: A reference to the games `Ruleset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessGame;
+    /// use simple_chess::ruleset::Ruleset;
+    /// let chess_game = ChessGame::new();
+    /// assert_eq!(&Ruleset::new(), chess_game.get_ruleset());
+    /// ```
+    pub fn get_ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+
+    /// Get the fifty-move rule counter, the same as `get_halfmove_clock`.
     ///
     /// # Returns
     ///
@@ -243,6 +665,32 @@ impl ChessGame {
         self.fifty_move_rule_counter
     }
 
+    /// Returns the halfmove clock, the number of half-moves since the last pawn move or
+    /// capture.
+    ///
+    /// A draw can be claimed once it reaches 100, see `can_claim_draw`, and the game is
+    /// drawn without a claim once it reaches 150, see `GameState::SeventyFiveMoveRule`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+    ///
+    /// let mut game = build_game_from_string("r3k3/p7/8/8/8/8/8/4K2R w Kq - 12 40").unwrap();
+    /// assert_eq!(12, game.get_halfmove_clock());
+    ///
+    /// // castling is neither a pawn move nor a capture
+    /// game.make_move(decode_move_from_string(&game, "O-O").unwrap());
+    /// assert_eq!(13, game.get_halfmove_clock());
+    ///
+    /// game.make_move(decode_move_from_string(&game, "a5").unwrap());
+    /// assert_eq!(0, game.get_halfmove_clock());
+    /// ```
+    pub fn get_halfmove_clock(&self) -> usize {
+        self.fifty_move_rule_counter
+    }
+
     /// Executes a given move on the simple_chess board.
     ///
     /// # Arguments
@@ -261,12 +709,349 @@ impl ChessGame {
     ///
     /// * `GameState` - The new state of the game after the move is applied, which includes checks for checks, checkmates, and draws.
     ///
+    /// If the game has ended by resignation or agreement the move is not made, and the
+    /// final state of the game is returned.
+    ///
     pub fn make_move(&mut self, chess_move: ChessMoveType) -> GameState {
+        self.play_move(chess_move, None)
+    }
+
+    /// Makes a move and records the time the player took on the game's clock, keeping the
+    /// time they have left in the move's `MoveRecord`.
+    ///
+    /// If the player ran out of time the move is not made, and the game ends with a win for
+    /// the opponent by `Termination::TimeForfeit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chess_move` - The move to make.
+    /// * `clock` - The clock of the game, running for the player making the move.
+    /// * `elapsed` - How long the player took to make the move.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GameState)` - The new state of the game after the move, or its final state if
+    ///   the player ran out of time.
+    /// * `Err(ChessError::IllegalMove)` - If the clock is running for the other player, the
+    ///   game and the clock are unchanged.
+    /// * `Err(ChessError::GameOver)` - If the game has already ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::clock::{ChessClock, TimeControl};
+    /// use simple_chess::ChessGame;
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use std::time::Duration;
+    ///
+    /// let mut game = ChessGame::new();
+    /// let mut clock = ChessClock::new(TimeControl::new(Duration::from_secs(60), Duration::ZERO));
+    /// if let GameState::InProgress { legal_moves, .. } = game.get_game_state() {
+    ///     game.make_timed_move(legal_moves[0], &mut clock, Duration::from_secs(2)).unwrap();
+    /// }
+    ///
+    /// let record = game.get_history().next().unwrap();
+    /// assert_eq!(Some(Duration::from_secs(58)), record.get_remaining_time());
+    /// ```
+    pub fn make_timed_move(
+        &mut self,
+        chess_move: ChessMoveType,
+        clock: &mut ChessClock,
+        elapsed: Duration,
+    ) -> Result<GameState, ChessError> {
+        self.check_not_ended()?;
+        if clock.get_current_turn() != self.current_players_turn {
+            return Err(ChessError::IllegalMove(format!(
+                "the clock is running for {:?}",
+                clock.get_current_turn()
+            )));
+        }
+
+        let remaining_time = clock.record_move(elapsed);
+        if let Some(flagged) = clock.get_flagged_player() {
+            return Ok(self.end_game(
+                Adjudication::Resignation {
+                    winner: flagged.opposite(),
+                },
+                Termination::TimeForfeit,
+            ));
+        }
+        Ok(self.play_move(chess_move, Some(remaining_time)))
+    }
+
+    /// Returns a record of every move made with `make_move`, `try_make_move` or
+    /// `make_timed_move`, in the order they were played.
+    ///
+    /// Moves that were already played when the game was created, such as those given to
+    /// the `ChessGameBuilder`, have no record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// if let GameState::InProgress { legal_moves, .. } = game.get_game_state() {
+    ///     let knight_move = legal_moves
+    ///         .into_iter()
+    ///         .find(|chess_move| chess_move.get_new_position() == (5, 2))
+    ///         .unwrap();
+    ///     game.make_move(knight_move);
+    /// }
+    ///
+    /// let sans: Vec<&str> = game.get_history().map(|record| record.get_san()).collect();
+    /// assert_eq!(vec!["Nf3"], sans);
+    /// ```
+    pub fn get_history(&self) -> impl Iterator<Item = &MoveRecord> {
+        self.history.iter()
+    }
+
+    fn play_move(
+        &mut self,
+        chess_move: ChessMoveType,
+        remaining_time: Option<Duration>,
+    ) -> GameState {
+        if self.ending.is_some() {
+            return self.get_game_state();
+        }
+        let san = encode_move_as_san(self, &chess_move);
+        let color = self.current_players_turn;
+        let move_number = self.turn_number;
+        // moving instead of accepting declines the opponent's offer
+        if self.draw_offer == Some(color.opposite()) {
+            self.draw_offer = None;
+        }
+
+        self.apply_move(chess_move);
+        let state = self.get_game_state();
+
+        let is_checkmate = matches!(state, GameState::Checkmate { .. });
+        let record = MoveRecord::new(
+            chess_move,
+            san,
+            encode_game_as_string(self),
+            color,
+            move_number,
+            remaining_time,
+            is_checkmate || matches!(state, GameState::Check { .. }),
+            is_checkmate,
+        );
+        Arc::make_mut(&mut self.history).push(record);
+
+        if !self.observers.is_empty() {
+            let mut observers = std::mem::take(&mut self.observers);
+            observers.notify(self, &chess_move, &state);
+            self.observers = observers;
+        }
+        state
+    }
+
+    /// Subscribes an observer to the events of the game, such as moves, captures and the
+    /// end of the game.
+    ///
+    /// Observers are notified of every move made with `make_move` or `try_make_move` after
+    /// they subscribed, see `GameObserver` for the events.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The observer to notify.
+    pub fn subscribe(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Makes a move after checking it is legal, unlike `make_move` which trusts the caller
+    /// to pass one of the legal moves of the position.
+    ///
+    /// # Arguments
+    ///
+    /// * `chess_move` - The move to make.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GameState)` - The new state of the game after the move.
+    /// * `Err(ChessError::IllegalMove)` - If the move is not legal, the game is unchanged.
+    /// * `Err(ChessError::GameOver)` - If the game has ended by resignation or agreement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::piece::{ChessPiece, PieceType};
+    /// use simple_chess::{ChessError, ChessGame, ChessMoveType, Color};
+    ///
+    /// let mut game = ChessGame::new();
+    /// let pawn_move = |to| ChessMoveType::Move {
+    ///     original_position: (4, 1),
+    ///     new_position: to,
+    ///     piece: ChessPiece::new(PieceType::Pawn, Color::White),
+    ///     taken_piece: None,
+    ///     promotion: None,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Err(ChessError::IllegalMove(String::from("e2e5"))),
+    ///     game.try_make_move(pawn_move((4, 4)))
+    /// );
+    /// assert!(game.try_make_move(pawn_move((4, 3))).is_ok());
+    /// ```
+    pub fn try_make_move(&mut self, chess_move: ChessMoveType) -> Result<GameState, ChessError> {
+        if self.ending.is_some() {
+            return Err(ChessError::GameOver);
+        }
+        if !self.is_move_legal(&chess_move) {
+            return Err(ChessError::IllegalMove(encode_move_as_string(&chess_move)));
+        }
+        Ok(self.make_move(chess_move))
+    }
+
+    /// Offers the opponent a draw.
+    ///
+    /// The offer stands until the opponent accepts it with `accept_draw`, declines it with
+    /// `decline_draw` or makes a move.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player offering the draw.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the offer was made.
+    /// * `Err(ChessError::GameOver)` - If the game has already ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::{ChessError, ChessGame, Color};
+    ///
+    /// let mut game = ChessGame::new();
+    /// game.offer_draw(Color::White).unwrap();
+    ///
+    /// assert_eq!(Ok(GameState::DrawAgreed), game.accept_draw());
+    /// assert_eq!(Err(ChessError::GameOver), game.offer_draw(Color::Black));
+    /// ```
+    pub fn offer_draw(&mut self, color: Color) -> Result<(), ChessError> {
+        self.check_not_ended()?;
+        self.draw_offer = Some(color);
+        Ok(())
+    }
+
+    /// Returns the player whose draw offer is waiting for an answer, if any.
+    pub fn get_draw_offer(&self) -> Option<Color> {
+        self.draw_offer
+    }
+
+    /// Accepts the pending draw offer, ending the game.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GameState::DrawAgreed)` - The final state of the game.
+    /// * `Err(ChessError::GameOver)` - If the game has already ended.
+    /// * `Err(ChessError::IllegalMove)` - If no draw has been offered.
+    pub fn accept_draw(&mut self) -> Result<GameState, ChessError> {
+        self.check_not_ended()?;
+        if self.draw_offer.is_none() {
+            return Err(ChessError::IllegalMove(String::from(
+                "no draw has been offered",
+            )));
+        }
+        Ok(self.end_game(Adjudication::Draw, Termination::DrawAgreement))
+    }
+
+    /// Declines the pending draw offer, if any, and the game goes on.
+    pub fn decline_draw(&mut self) {
+        self.draw_offer = None;
+    }
+
+    /// Resigns the game for a player, ending it with a win for the opponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player resigning.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GameState::Resigned)` - The final state of the game.
+    /// * `Err(ChessError::GameOver)` - If the game has already ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::{ChessError, ChessGame, Color};
+    ///
+    /// let mut game = ChessGame::new();
+    /// let state = game.resign(Color::White).unwrap();
+    ///
+    /// assert_eq!(GameState::Resigned { winner: Color::Black }, state);
+    /// assert_eq!(GameState::Resigned { winner: Color::Black }, game.get_game_state());
+    /// ```
+    pub fn resign(&mut self, color: Color) -> Result<GameState, ChessError> {
+        self.check_not_ended()?;
+        Ok(self.end_game(
+            Adjudication::Resignation {
+                winner: color.opposite(),
+            },
+            Termination::Resignation,
+        ))
+    }
+
+    /// Ends the game with the decision of an arbiter or an `Adjudicator`.
+    ///
+    /// A win is reported as `GameState::Resigned` and a draw as `GameState::DrawAgreed`.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The decided result of the game.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GameState)` - The final state of the game.
+    /// * `Err(ChessError::GameOver)` - If the game has already ended.
+    pub fn adjudicate(&mut self, result: Adjudication) -> Result<GameState, ChessError> {
+        self.check_not_ended()?;
+        Ok(self.end_game(result, Termination::Adjudication))
+    }
+
+    fn check_not_ended(&mut self) -> Result<(), ChessError> {
+        match self.get_game_state() {
+            GameState::InProgress { .. } | GameState::Check { .. } => Ok(()),
+            _ => Err(ChessError::GameOver),
+        }
+    }
+
+    fn end_game(&mut self, result: Adjudication, termination: Termination) -> GameState {
+        self.ending = Some((result, termination));
+        self.invalidate_caches();
+        chess_log!(Info, "the game was ended by {result:?}");
+        self.draw_offer = None;
+        let state = self.get_game_state();
+        if !self.observers.is_empty() {
+            let mut observers = std::mem::take(&mut self.observers);
+            observers.notify_game_ended(self, &state);
+            self.observers = observers;
+        }
+        state
+    }
+
+    /// Returns the final state of a game that ended by resignation or agreement.
+    pub(crate) fn get_ending_state(&self) -> Option<GameState> {
+        self.ending.map(|(ending, _)| match ending {
+            Adjudication::Resignation { winner } => GameState::Resigned { winner },
+            Adjudication::Draw => GameState::DrawAgreed,
+        })
+    }
+
+    /// Applies a move and updates the game's bookkeeping without analyzing the resulting
+    /// position, for callers such as perft that generate the legal moves themselves.
+    pub(crate) fn apply_move(&mut self, chess_move: ChessMoveType) {
+        self.invalidate_caches();
         chess_move.make_move(&mut self.board);
         if self.current_players_turn == Black {
             self.turn_number += 1;
         }
 
+        self.update_halfmove_clock(&chess_move);
         match chess_move {
             ChessMoveType::Move {
                 taken_piece,
@@ -276,10 +1061,7 @@ impl ChessGame {
                 ..
             } => {
                 if taken_piece.is_some() || piece.get_piece_type() == Pawn {
-                    self.fifty_move_rule_counter = 0;
-                    self.previous_board_states = vec![];
-                } else {
-                    self.fifty_move_rule_counter += 1;
+                    self.repetition_table.clear();
                 }
 
                 self.update_castling_rights(taken_piece, piece, original_position, new_position);
@@ -295,20 +1077,46 @@ impl ChessGame {
                         self.can_black_castle_long = false;
                     }
                 }
-                self.fifty_move_rule_counter = 0;
+                // the lost castling rights mean no earlier position can repeat
+                self.repetition_table.clear();
             }
             _ => {
-                self.fifty_move_rule_counter = 0;
-                self.previous_board_states = vec![];
+                self.repetition_table.clear();
+            }
+        }
+
+        if let Some(taken_piece) = get_taken_piece(&chess_move) {
+            match self.current_players_turn {
+                White => self.captured_pieces.0.push(taken_piece),
+                Black => self.captured_pieces.1.push(taken_piece),
             }
         }
 
+        self.en_passant_target = get_double_step_target(&chess_move);
         self.moves.push(chess_move);
-        self.previous_board_states
-            .push(encode_board_as_binary(self.get_board()));
         self.current_players_turn = self.current_players_turn.opposite();
+        self.record_position();
+
+        if self.variant.get_check_limit().is_some() && is_current_player_in_check(self) {
+            match self.current_players_turn {
+                White => self.checks_given.1 += 1,
+                Black => self.checks_given.0 += 1,
+            }
+        }
+    }
 
-        self.get_game_state()
+    /// Resets the halfmove clock on a pawn move or capture, and counts every other move.
+    fn update_halfmove_clock(&mut self, chess_move: &ChessMoveType) {
+        let is_pawn_move = match chess_move {
+            ChessMoveType::Move { piece, .. } => piece.get_piece_type() == Pawn,
+            ChessMoveType::EnPassant { .. } => true,
+            ChessMoveType::Castle { .. } => false,
+        };
+        if is_pawn_move || get_taken_piece(chess_move).is_some() {
+            self.fifty_move_rule_counter = 0;
+        } else {
+            self.fifty_move_rule_counter += 1;
+        }
     }
 
     fn update_castling_rights(
@@ -380,11 +1188,48 @@ impl ChessGame {
     /// `GameState`: The current state of the game, which can be calculated
     /// based on various factors like board configuration, move history, etc.
     ///
-    /// This method internally calls a function to determine the game state
-    /// and returns the result.
+    /// The state is computed once per position and cached until the next move, see
+    /// `state` to borrow it without cloning the legal moves.
     ///
     pub fn get_game_state(&mut self) -> GameState {
-        get_game_state(self)
+        self.state().clone()
+    }
+
+    /// Returns the current state of the game.
+    ///
+    /// The state and the legal moves it holds are computed the first time they are asked
+    /// for in a position and cached until the next move, so user interfaces can call this
+    /// on every redraw.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// assert!(matches!(game.state(), GameState::InProgress { .. }));
+    /// assert_eq!(20, game.legal_moves().len());
+    /// ```
+    pub fn state(&mut self) -> &GameState {
+        chess_count!(CacheHits, self.game_state_cache.is_some());
+        if self.game_state_cache.is_none() {
+            chess_count!(CacheMisses, 1);
+            let state = get_game_state(self);
+            self.game_state_cache = Some(state);
+        }
+        self.game_state_cache
+            .as_ref()
+            .expect("the state was computed above")
+    }
+
+    /// Returns the legal moves of the current position.
+    ///
+    /// The moves are generated the first time they are asked for in a position and cached
+    /// until the next move. Unlike the moves in `GameState`, they are returned even when
+    /// the game is over, for example by resignation.
+    pub fn legal_moves(&mut self) -> &[ChessMoveType] {
+        self.get_cached_legal_moves()
     }
 
     ///
@@ -403,10 +1248,10 @@ impl ChessGame {
     ///
     ///
     pub fn can_claim_draw(&self) -> Option<DrawReason> {
-        if self.fifty_move_rule_counter >= 100 {
+        if self.fifty_move_rule_counter >= FIFTY_MOVE_RULE_LIMIT {
             return Some(FiftyMoveRule);
         }
-        if is_insufficient_material(self.get_board()) {
+        if self.variant.is_insufficient_material(self.get_board()) {
             return Some(InsufficientPieces);
         }
         if self.can_claim_draw_by_repetition() {
@@ -415,23 +1260,370 @@ impl ChessGame {
         None
     }
 
+    /// Returns the legal moves of the piece on the given square.
+    ///
+    /// The legal moves of the position are generated once and cached until the next move,
+    /// so asking about every square of the board in the same turn is cheap.
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column of the square the piece is on.
+    /// * `row` - The row of the square the piece is on.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<ChessMoveType>` - The legal moves, empty if the square is empty, holds a
+    ///   piece of the player not on move, or the piece has no legal moves. A pawn that
+    ///   promotes has one move for each piece it can promote to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// assert_eq!(2, game.legal_moves_from(6, 0).len());
+    /// assert!(game.legal_moves_from(6, 7).is_empty());
+    /// ```
+    pub fn legal_moves_from(&mut self, col: usize, row: usize) -> Vec<ChessMoveType> {
+        self.get_cached_legal_moves()
+            .iter()
+            .filter(|chess_move| chess_move.get_original_position() == (col, row))
+            .copied()
+            .collect()
+    }
+
+    /// Returns `true` if a move is one of the legal moves of the current position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::piece::{ChessPiece, PieceType};
+    /// use simple_chess::{ChessGame, ChessMoveType, Color};
+    ///
+    /// let mut game = ChessGame::new();
+    /// let knight_move = |to| ChessMoveType::Move {
+    ///     original_position: (6, 0),
+    ///     new_position: to,
+    ///     piece: ChessPiece::new(PieceType::Knight, Color::White),
+    ///     taken_piece: None,
+    ///     promotion: None,
+    /// };
+    ///
+    /// assert!(game.is_move_legal(&knight_move((5, 2))));
+    /// assert!(!game.is_move_legal(&knight_move((6, 2))));
+    /// ```
+    pub fn is_move_legal(&mut self, chess_move: &ChessMoveType) -> bool {
+        self.get_cached_legal_moves().contains(chess_move)
+    }
+
+    /// Explains why a move can not be played in the current position, so that a user
+    /// interface can tell the player why their move was rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `chess_move` - The move to check.
+    ///
+    /// # Returns
+    ///
+    /// * `None` - If the move is legal.
+    /// * `Some(IllegalMoveReason)` - Why the move is not legal, such as the piece being
+    ///   pinned or the castling path being attacked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::piece::{ChessPiece, PieceType};
+    /// use simple_chess::{ChessGame, ChessMoveType, Color};
+    ///
+    /// let mut game = ChessGame::new();
+    /// let rook_move = ChessMoveType::Move {
+    ///     original_position: (0, 0),
+    ///     new_position: (0, 1),
+    ///     piece: ChessPiece::new(PieceType::Rook, Color::White),
+    ///     taken_piece: None,
+    ///     promotion: None,
+    /// };
+    ///
+    /// let reason = game.get_illegal_move_reason(&rook_move).unwrap();
+    /// assert_eq!("a2 is occupied by your own pawn", reason.to_string());
+    /// ```
+    pub fn get_illegal_move_reason(
+        &mut self,
+        chess_move: &ChessMoveType,
+    ) -> Option<IllegalMoveReason> {
+        get_illegal_move_reason(self, chess_move)
+    }
+
+    /// Checks the current position against the rules of chess, such as each player having
+    /// one king and no pawns standing on a back rank.
+    ///
+    /// Positions reached by playing legal moves are always valid, so this is mostly useful
+    /// for positions built by hand and for property tests.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the position is valid.
+    /// * `Err(Vec<InvariantViolation>)` - Every rule the position breaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::ChessGame;
+    ///
+    /// assert!(ChessGame::new().validate().is_ok());
+    ///
+    /// // both kings are in check
+    /// let game = build_game_from_string("4k3/4R3/8/8/8/8/4r3/4K3 w - - 0 1").unwrap();
+    /// assert!(game.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<InvariantViolation>> {
+        validate(self)
+    }
+
+    /// Returns the squares the piece on the given square can legally move to.
+    ///
+    /// The legal moves of the current position are generated once and cached until the next
+    /// move is made, so UIs can call this every time a piece is selected without regenerating
+    /// the full move list. Castling moves are included as the king's destination square.
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column of the square the piece is on.
+    /// * `row` - The row of the square the piece is on.
+    ///
+    /// # Returns
+    ///
+    /// * `SquareSet` - The legal destinations, empty if the square is empty, holds a piece
+    ///   of the player not on move, or the piece has no legal moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// let destinations = game.destinations_from(4, 1);
+    ///
+    /// assert_eq!(vec![(4, 2), (4, 3)], destinations.iter().collect::<Vec<_>>());
+    /// assert!(game.destinations_from(4, 0).is_empty());
+    /// ```
+    pub fn destinations_from(&mut self, col: usize, row: usize) -> SquareSet {
+        let mut destinations = SquareSet::new(self.board.get_width(), self.board.get_height());
+        for chess_move in self.legal_moves_from(col, row) {
+            let (new_col, new_row) = chess_move.get_new_position();
+            destinations.insert(new_col, new_row);
+        }
+        destinations
+    }
+
+    /// Returns `true` if the player to move has exactly one legal move.
+    ///
+    /// Forced moves involve no decision, so game reviews leave them out of accuracy
+    /// statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = build_game_from_string("7k/8/8/8/8/8/8/K5R1 b - - 0 1").unwrap();
+    /// assert!(game.is_forced_move());
+    /// assert!(!ChessGame::new().is_forced_move());
+    /// ```
+    pub fn is_forced_move(&mut self) -> bool {
+        self.get_cached_legal_moves().len() == 1
+    }
+
+    /// Returns the engine's top moves in the current position, each tagged with its SAN,
+    /// score and a short human readable rationale.
+    ///
+    /// This is intended for "show me my options" style teaching UIs, see
+    /// [`get_candidate_moves`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The engine used to score the moves, searching to its configured depth.
+    /// * `count` - The maximum number of moves to return.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let game = ChessGame::new();
+    /// let candidates = game.candidate_moves(&mut Engine::new().set_depth(1), 3);
+    /// assert_eq!(3, candidates.len());
+    /// ```
+    pub fn candidate_moves(&self, engine: &mut Engine, count: usize) -> Vec<CandidateMove> {
+        get_candidate_moves(self, engine, count)
+    }
+
+    /// Returns the moves an opening book suggests in the current position, heaviest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `book` - The opening book to look the position up in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::book::Book;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let game = ChessGame::new();
+    /// assert!(game.book_moves(&Book::new()).is_empty());
+    /// ```
+    pub fn book_moves(&self, book: &Book) -> Vec<BookMove> {
+        book.get_moves(self)
+    }
+
+    /// Returns the current position as a single JSON document for front ends, holding the
+    /// FEN, the legal moves in SAN and UCI notation, check flags, the game state and the
+    /// last move.
+    ///
+    /// Games played with a clock can include it with
+    /// [`encode_game_state_as_json`](crate::codec::json::encode_game_state_as_json).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessGame;
+    ///
+    /// let json = ChessGame::new().to_json_state();
+    /// assert!(json.contains(r#""state":"in_progress","in_check":false"#));
+    /// assert!(json.contains(r#""uci":"e2e4""#));
+    /// ```
+    pub fn to_json_state(&self) -> String {
+        encode_game_state_as_json(self, None)
+    }
+
+    /// Saves the game, including its move history, to a compact binary blob that can be
+    /// resumed with `ChessGame::from_bytes`.
+    ///
+    /// Games played with a clock can save it too with
+    /// [`encode_game_as_binary`](crate::codec::binary::encode_game_as_binary).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessGame;
+    ///
+    /// let bytes = ChessGame::new().to_bytes();
+    /// assert!(ChessGame::from_bytes(&bytes).is_ok());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_game_as_binary(self, None)
+    }
+
+    /// Resumes a game saved with `ChessGame::to_bytes` or
+    /// [`encode_game_as_binary`](crate::codec::binary::encode_game_as_binary).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChessGame)` - The saved game, ready to continue from where it was saved.
+    /// * `Err(BinaryDecodeError)` - If the bytes are not a saved game, were saved by a newer
+    ///   version of the format or are damaged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::encode_game_as_string;
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// if let GameState::InProgress { legal_moves, .. } = game.get_game_state() {
+    ///     game.make_move(legal_moves[0]);
+    /// }
+    ///
+    /// let resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+    /// assert_eq!(encode_game_as_string(&game), encode_game_as_string(&resumed));
+    /// assert_eq!(game.get_moves(), resumed.get_moves());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<ChessGame, BinaryDecodeError> {
+        decode_game_from_binary(bytes).map(|(game, _)| game)
+    }
+
+    /// Returns `true` if the game is standard 8x8 chess, which lets move generation use the
+    /// bitboard fast path for check detection.
+    pub(crate) fn is_standard_chess(&self) -> bool {
+        self.is_standard_chess
+    }
+
+    /// Clears the legal moves and state cached for the current position.
+    fn invalidate_caches(&mut self) {
+        self.legal_moves_cache = None;
+        self.game_state_cache = None;
+    }
+
+    /// Returns the legal moves of the current position, generating them only if they have
+    /// not been generated since the last move.
+    pub(crate) fn get_cached_legal_moves(&mut self) -> &[ChessMoveType] {
+        chess_count!(CacheHits, self.legal_moves_cache.is_some());
+        if self.legal_moves_cache.is_none() {
+            chess_count!(CacheMisses, 1);
+            let legal_moves = get_legal_moves(self);
+            chess_log!(Trace, "generated {} legal moves", legal_moves.len());
+            self.legal_moves_cache = Some(legal_moves);
+        }
+        self.legal_moves_cache.as_deref().unwrap_or_default()
+    }
+
     fn can_claim_draw_by_repetition(&self) -> bool {
-        let mut previous_board_states: HashMap<Vec<u8>, usize> = HashMap::new();
-        for previous_state in &self.previous_board_states {
-            match previous_board_states.get(previous_state) {
-                None => {
-                    previous_board_states.insert(previous_state.clone(), 1);
-                }
-                Some(count) => {
-                    if *count > 2 {
-                        return true;
-                    }
-                    let new_count = count + 1;
-                    previous_board_states.insert(previous_state.clone(), new_count);
-                }
-            }
+        self.repetition_table.get_max_count() >= 3
+    }
+
+    /// Returns the number of times the current position has occurred in the game.
+    ///
+    /// Positions are compared by the pieces on the board, the side to move, castling rights
+    /// and the en passant square, so the count only includes positions played since the last
+    /// capture, pawn move or castle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessGame;
+    ///
+    /// let game = ChessGame::new();
+    /// assert_eq!(1, game.get_repetition_count());
+    /// ```
+    pub fn get_repetition_count(&self) -> usize {
+        self.repetition_table.get_count(&PositionKey::new(self))
+    }
+
+    fn record_position(&mut self) {
+        let key = PositionKey::new(self);
+        self.repetition_table.record(key);
+    }
+}
+
+fn get_taken_piece(chess_move: &ChessMoveType) -> Option<ChessPiece> {
+    match chess_move {
+        ChessMoveType::Move { taken_piece, .. } => *taken_piece,
+        ChessMoveType::EnPassant { taken_piece, .. } => Some(*taken_piece),
+        ChessMoveType::Castle { .. } => None,
+    }
+}
+
+/// Returns the square a pawn passed over if the move is a pawn's double step.
+fn get_double_step_target(chess_move: &ChessMoveType) -> Option<(usize, usize)> {
+    match chess_move {
+        ChessMoveType::Move {
+            original_position,
+            new_position,
+            piece,
+            ..
+        } if piece.get_piece_type() == Pawn
+            && original_position.0 == new_position.0
+            && original_position.1.abs_diff(new_position.1) == 2 =>
+        {
+            Some((new_position.0, (original_position.1 + new_position.1) / 2))
         }
-        false
+        _ => None,
     }
 }
 
@@ -444,7 +1636,10 @@ impl Default for ChessGame {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::codec::forsyth_edwards_notation::encode_game_as_string;
+    use crate::clock::TimeControl;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+    use crate::piece::PieceType::{Knight, Queen};
 
     #[test]
     fn new_game_start_correctly() {
@@ -455,4 +1650,170 @@ mod tests {
             fen_string
         );
     }
+
+    #[test]
+    fn position_repeated_three_times_can_be_claimed_as_a_draw() {
+        let mut game = ChessGame::new();
+        let knight_move =
+            |from: (usize, usize), to: (usize, usize), color: Color| ChessMoveType::Move {
+                original_position: from,
+                new_position: to,
+                piece: ChessPiece::new(Knight, color),
+                taken_piece: None,
+                promotion: None,
+            };
+
+        for _ in 0..2 {
+            assert!(game.can_claim_draw().is_none());
+            game.make_move(knight_move((6, 0), (5, 2), White));
+            game.make_move(knight_move((6, 7), (5, 5), Black));
+            game.make_move(knight_move((5, 2), (6, 0), White));
+            game.make_move(knight_move((5, 5), (6, 7), Black));
+        }
+
+        assert_eq!(3, game.get_repetition_count());
+        assert!(matches!(game.can_claim_draw(), Some(Repetition)));
+    }
+
+    #[test]
+    fn destinations_are_recomputed_after_a_move() {
+        let mut game = ChessGame::new();
+        assert_eq!(2, game.destinations_from(1, 0).len());
+
+        game.make_move(ChessMoveType::Move {
+            original_position: (4, 1),
+            new_position: (4, 3),
+            piece: ChessPiece::new(Pawn, White),
+            taken_piece: None,
+            promotion: None,
+        });
+
+        assert!(game.destinations_from(1, 0).is_empty());
+        assert_eq!(2, game.destinations_from(1, 7).len());
+        assert!(game.destinations_from(4, 7).is_empty());
+    }
+
+    #[test]
+    fn castling_is_a_legal_move_of_the_king() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+
+        let king_moves = game.legal_moves_from(4, 0);
+        let castle = king_moves
+            .iter()
+            .find(|chess_move| matches!(chess_move, ChessMoveType::Castle { .. }))
+            .copied()
+            .unwrap();
+        assert_eq!(6, king_moves.len());
+        assert!(game.is_move_legal(&castle));
+
+        game.make_move(castle);
+        assert!(!game.is_move_legal(&castle));
+    }
+
+    #[test]
+    fn captured_pieces_are_tracked_per_side() {
+        let mut game = build_game_from_string("4k3/8/8/3pP3/8/8/1q6/R3K3 w - d6 0 1").unwrap();
+        let white_pawn = ChessPiece::new(Pawn, White);
+        let black_pawn = ChessPiece::new(Pawn, Black);
+        game.make_move(ChessMoveType::EnPassant {
+            original_position: (4, 4),
+            new_position: (3, 5),
+            piece: white_pawn,
+            taken_piece: black_pawn,
+            taken_piece_position: (3, 4),
+            promotion: None,
+        });
+        game.make_move(ChessMoveType::Move {
+            original_position: (1, 1),
+            new_position: (0, 0),
+            piece: ChessPiece::new(Queen, Black),
+            taken_piece: Some(ChessPiece::new(Rook, White)),
+            promotion: None,
+        });
+
+        assert_eq!(&[black_pawn], game.get_captured_pieces(White));
+        assert_eq!(
+            &[ChessPiece::new(Rook, White)],
+            game.get_captured_pieces(Black)
+        );
+
+        let rebuilt = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+        assert_eq!(&[black_pawn], rebuilt.get_captured_pieces(White));
+        assert_eq!(1, rebuilt.get_captured_pieces(Black).len());
+    }
+
+    #[test]
+    fn a_draw_offer_lapses_when_the_opponent_moves() {
+        let mut game = ChessGame::new();
+        let e4 = decode_move_from_string(&game, "e4").unwrap();
+        game.offer_draw(White).unwrap();
+        game.make_move(e4);
+        assert_eq!(Some(White), game.get_draw_offer());
+
+        let e5 = decode_move_from_string(&game, "e5").unwrap();
+        game.make_move(e5);
+        assert_eq!(None, game.get_draw_offer());
+        assert!(game.accept_draw().is_err());
+    }
+
+    #[test]
+    fn no_moves_can_be_made_after_a_resignation() {
+        let mut game = ChessGame::new();
+        let e4 = decode_move_from_string(&game, "e4").unwrap();
+        game.resign(Black).unwrap();
+
+        assert_eq!(Err(ChessError::GameOver), game.try_make_move(e4));
+        assert_eq!(GameState::Resigned { winner: White }, game.make_move(e4));
+        assert!(game.get_moves().is_empty());
+        assert_eq!(
+            Err(ChessError::GameOver),
+            game.adjudicate(Adjudication::Draw)
+        );
+    }
+
+    #[test]
+    fn a_player_who_runs_out_of_time_loses_without_moving() {
+        let mut game = ChessGame::new();
+        let mut clock = ChessClock::new(TimeControl::new(Duration::from_secs(60), Duration::ZERO));
+        let e4 = decode_move_from_string(&game, "e4").unwrap();
+
+        let mut black_clock = clock.clone();
+        black_clock.record_move(Duration::from_secs(1));
+        assert!(matches!(
+            game.make_timed_move(e4, &mut black_clock, Duration::from_secs(1)),
+            Err(ChessError::IllegalMove(_))
+        ));
+
+        let state = game.make_timed_move(e4, &mut clock, Duration::from_secs(61));
+        assert_eq!(Ok(GameState::Resigned { winner: Black }), state);
+        assert!(game.get_moves().is_empty());
+        let result = game.get_result().unwrap();
+        assert_eq!(Some(Black), result.get_winner());
+        assert_eq!(Termination::TimeForfeit, result.get_termination());
+        assert_eq!(
+            Err(ChessError::GameOver),
+            game.make_timed_move(e4, &mut clock, Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn the_cached_state_follows_the_game() {
+        let mut game = ChessGame::new();
+        assert!(matches!(
+            game.state(),
+            GameState::InProgress { turn: White, .. }
+        ));
+
+        let e4 = decode_move_from_string(&game, "e4").unwrap();
+        game.make_move(e4);
+        assert!(matches!(
+            game.state(),
+            GameState::InProgress { turn: Black, .. }
+        ));
+        assert_eq!(20, game.legal_moves().len());
+
+        game.resign(Black).unwrap();
+        assert_eq!(&GameState::Resigned { winner: White }, game.state());
+        assert_eq!(20, game.legal_moves().len());
+    }
 }
@@ -1,7 +1,12 @@
 use crate::chess_move::ChessMoveType;
+use crate::error::ChessError;
 use crate::piece::ChessPiece;
+use crate::ruleset::Ruleset;
+use crate::scoring::Scoring;
+use crate::variant::Variant;
 use crate::{ChessGame, Color};
 use game_board::Board;
+use std::sync::Arc;
 
 /// The `ChessGameBuilder` struct is used to construct a `ChessGame`
 /// instance. It employs the builder pattern to set up various
@@ -21,6 +26,11 @@ pub struct ChessGameBuilder {
     can_black_castle_short: Option<bool>,
     can_black_castle_long: Option<bool>,
     moves: Option<Vec<ChessMoveType>>,
+    en_passant_target: Option<(usize, usize)>,
+    ruleset: Option<Ruleset>,
+    variant: Option<Arc<dyn Variant>>,
+    checks_given: Option<(usize, usize)>,
+    scoring: Option<Scoring>,
 }
 
 impl ChessGameBuilder {
@@ -46,6 +56,11 @@ impl ChessGameBuilder {
             can_black_castle_short: None,
             can_black_castle_long: None,
             moves: None,
+            en_passant_target: None,
+            ruleset: None,
+            variant: None,
+            checks_given: None,
+            scoring: None,
         }
     }
 
@@ -58,7 +73,7 @@ impl ChessGameBuilder {
     /// # Returns
     ///
     /// * `Ok(ChessGame)` - If the necessary components (board and current player's turn) are set
-    /// * `Err(&str)` - If any of the necessary components is missing
+    /// * `Err(ChessError::InvalidBoard)` - If any of the necessary components is missing
     ///
     /// # Examples
     ///
@@ -90,23 +105,39 @@ impl ChessGameBuilder {
     ///
     /// assert!(game_result.is_ok());
     /// ```
-    pub fn build<'a>(self) -> Result<ChessGame, &'a str> {
-        if let (Some(board), Some(current_players_turn)) = (self.board, self.current_players_turn) {
-            let game = ChessGame::build(
-                board,
-                current_players_turn,
-                self.turn_number.unwrap_or(0),
-                self.fifty_move_rule_counter.unwrap_or(0),
-                self.can_white_castle_short.unwrap_or(true),
-                self.can_white_castle_long.unwrap_or(true),
-                self.can_black_castle_short.unwrap_or(true),
-                self.can_black_castle_long.unwrap_or(true),
-                self.moves.unwrap_or_default(),
-            );
-            Ok(game)
-        } else {
-            Err("Not all necessary elements are set")
+    pub fn build(self) -> Result<ChessGame, ChessError> {
+        let board = self
+            .board
+            .ok_or_else(|| ChessError::InvalidBoard(String::from("no board was set")))?;
+        let current_players_turn = self.current_players_turn.ok_or_else(|| {
+            ChessError::InvalidBoard(String::from("the player to move was not set"))
+        })?;
+
+        let mut game = ChessGame::build(
+            board,
+            current_players_turn,
+            self.turn_number.unwrap_or(0),
+            self.fifty_move_rule_counter.unwrap_or(0),
+            self.can_white_castle_short.unwrap_or(true),
+            self.can_white_castle_long.unwrap_or(true),
+            self.can_black_castle_short.unwrap_or(true),
+            self.can_black_castle_long.unwrap_or(true),
+            self.moves.unwrap_or_default(),
+        );
+        game.set_ruleset(self.ruleset.unwrap_or_default());
+        if self.en_passant_target.is_some() {
+            game.set_en_passant_target(self.en_passant_target);
+        }
+        if let Some(variant) = self.variant {
+            game.set_variant(variant);
+        }
+        if let Some((white, black)) = self.checks_given {
+            game.set_checks_given(white, black);
+        }
+        if let Some(scoring) = self.scoring {
+            game.set_scoring(scoring);
         }
+        Ok(game)
     }
 
     /// Sets the board for the `ChessGame`.
@@ -215,6 +246,87 @@ impl ChessGameBuilder {
         self.moves = Some(moves);
         self
     }
+
+    /// Sets the square a pawn passed over with a double step on the last move, which a
+    /// pawn of the player to move may capture en passant.
+    ///
+    /// Without a target square the target is taken from the last of the moves, if it was
+    /// a double step.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The square passed over, such as `(4, 2)` for e3.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `ChessGameBuilder` instance with the en passant target set.
+    pub fn set_en_passant_target(mut self, target: (usize, usize)) -> Self {
+        self.en_passant_target = Some(target);
+        self
+    }
+
+    /// Sets the rule parameters used to generate moves in the `ChessGame`.
+    ///
+    /// This is useful for custom board sizes and handicap setups, where pawns
+    /// start or promote on ranks other than the standard ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `ruleset` - A `Ruleset` describing the rules of the game.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `ChessGameBuilder` instance with the ruleset set.
+    pub fn set_ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.ruleset = Some(ruleset);
+        self
+    }
+
+    /// Sets the variant the `ChessGame` is played under.
+    ///
+    /// Games are played under `StandardChess` when no variant is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The `Variant` deciding the variant specific rules of the game.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `ChessGameBuilder` instance with the variant set.
+    pub fn set_variant<V: Variant + 'static>(mut self, variant: V) -> Self {
+        self.variant = Some(Arc::new(variant));
+        self
+    }
+
+    /// Sets how the end of the `ChessGame` is scored, see `ChessGame::set_scoring`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scoring` - The `Scoring` of the game, such as Armageddon.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `ChessGameBuilder` instance with the scoring set.
+    pub fn set_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = Some(scoring);
+        self
+    }
+
+    /// Sets how many checks each player has given so far, for variants such as Three-check
+    /// that count them.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - The number of checks white has given.
+    /// * `black` - The number of checks black has given.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `ChessGameBuilder` instance with the check counts set.
+    pub fn set_checks_given(mut self, white: usize, black: usize) -> Self {
+        self.checks_given = Some((white, black));
+        self
+    }
 }
 
 impl Default for ChessGameBuilder {
@@ -1,8 +1,17 @@
-use crate::chess_game_state_analyzer::is_in_check;
-use crate::piece::ChessPiece;
+use crate::attack_map::attackers_of;
+use crate::bitboard::Bitboards;
+use crate::chess_board::ChessBoard;
+use crate::chess_game_state_analyzer::{
+    is_in_check, is_square_attacked, DIAGONAL_DIRECTIONS, ORTHOGONAL_DIRECTIONS,
+};
 use crate::piece::PieceType::King;
-use crate::ChessMoveType::{Castle, Move};
+use crate::piece::{ChessPiece, PieceType};
+use crate::position::Position;
+use crate::ChessMoveType::Castle;
 use crate::{ChessGame, ChessMoveType, Color};
+use game_board::Board;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 ///
 /// Returns a vector of legal moves for the current player's turn in the given simple_chess game.
@@ -17,36 +26,527 @@ use crate::{ChessGame, ChessMoveType, Color};
 /// player can make without putting their king in check.
 pub fn get_legal_moves(game: &mut ChessGame) -> Vec<ChessMoveType> {
     let current_turn = game.get_current_players_turn();
+    get_legal_moves_for_color(game, current_turn)
+}
 
+/// Returns the moves a player could legally make if it were their turn, used to compare
+/// the options of both players in the same position.
+pub(crate) fn get_legal_moves_for_color(
+    game: &mut ChessGame,
+    current_turn: Color,
+) -> Vec<ChessMoveType> {
+    chess_span!("legal_moves", color = ?current_turn);
     let all_moves = get_all_moves_for_color(current_turn, game);
+    chess_span!("legality_filter", moves = all_moves.len());
+    keep_legal_moves(game, all_moves, current_turn)
+}
+
+/// Returns the moves of the current player without checking that they are legal.
+///
+/// Pseudo-legal moves follow the movement rules of the pieces, castling rights and en
+/// passant, but may leave the player's own king in check. Engines can search these moves
+/// and check each one with `filter_legal` only once it is reached, skipping the check for
+/// moves that are cut off.
+///
+/// # Arguments
+///
+/// * `game` - The game to generate the moves of.
+///
+/// # Returns
+///
+/// The pseudo-legal moves, in the order `get_legal_moves` would return them.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_move_analyzer::{filter_legal, pseudo_legal_moves};
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// // the bishop on e2 is pinned to the king by the rook on e8
+/// let mut game = build_game_from_string("4r1k1/8/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+/// let moves = pseudo_legal_moves(&game);
+///
+/// assert_eq!(13, moves.len());
+/// assert_eq!(4, filter_legal(&mut game, moves).len());
+/// ```
+pub fn pseudo_legal_moves(game: &ChessGame) -> Vec<ChessMoveType> {
+    get_all_moves_for_color(game.get_current_players_turn(), game)
+}
+
+/// Keeps the moves of the current player that are legal, such as moves that do not leave
+/// their king in check.
+///
+/// # Arguments
+///
+/// * `game` - The game the moves were generated in.
+/// * `moves` - Pseudo-legal moves of the current player, such as some of the moves of
+///   `pseudo_legal_moves`.
+///
+/// # Returns
+///
+/// The legal moves in the order they were given. A variant's `filter_legal_moves` is
+/// given only these moves.
+pub fn filter_legal(game: &mut ChessGame, moves: Vec<ChessMoveType>) -> Vec<ChessMoveType> {
+    let current_turn = game.get_current_players_turn();
+    keep_legal_moves(game, moves, current_turn)
+}
+
+/// Returns the legal moves of the current player that capture a piece, including en
+/// passant and promotions that capture.
+///
+/// Only the captures are checked for legality, which makes this cheaper than filtering the
+/// result of `get_legal_moves` when an engine only searches captures, such as in a
+/// quiescence search. A variant's `filter_legal_moves` is given only the captures.
+///
+/// # Arguments
+///
+/// * `game` - The game to generate the moves of.
+///
+/// # Returns
+///
+/// The legal captures, in the order `get_legal_moves` would return them.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_move_analyzer::{generate_captures, generate_quiet_moves};
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// let mut game = build_game_from_string("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+///
+/// assert_eq!(1, generate_captures(&mut game).len());
+/// assert_eq!(6, generate_quiet_moves(&mut game).len());
+/// ```
+pub fn generate_captures(game: &mut ChessGame) -> Vec<ChessMoveType> {
+    generate_stage(game, |_, chess_move| is_capture(chess_move))
+}
+
+/// Returns the legal moves of the current player that do not capture, including castling
+/// and promotions that do not capture.
+///
+/// Together with `generate_captures` these are all of the legal moves.
+///
+/// # Arguments
+///
+/// * `game` - The game to generate the moves of.
+///
+/// # Returns
+///
+/// The legal quiet moves, in the order `get_legal_moves` would return them.
+pub fn generate_quiet_moves(game: &mut ChessGame) -> Vec<ChessMoveType> {
+    generate_stage(game, |_, chess_move| !is_capture(chess_move))
+}
+
+/// Returns the legal moves of the current player that put the opponent in check, both
+/// captures and quiet moves.
+///
+/// # Arguments
+///
+/// * `game` - The game to generate the moves of.
+///
+/// # Returns
+///
+/// The legal checking moves, in the order `get_legal_moves` would return them.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_move_analyzer::generate_checks;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// let mut game = build_game_from_string("4k3/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+///
+/// // Ra8+ and Rh8+
+/// assert_eq!(2, generate_checks(&mut game).len());
+/// ```
+pub fn generate_checks(game: &mut ChessGame) -> Vec<ChessMoveType> {
+    let opponent = game.get_current_players_turn().opposite();
+    generate_stage(game, |game, chess_move| {
+        let mut board = game.get_board().clone();
+        chess_move.make_move(&mut board);
+        is_in_check(opponent, &board)
+    })
+}
+
+/// Returns the legal moves of the current player when they are in check, all of which get
+/// the king out of check, or no moves when they are not in check.
+///
+/// # Arguments
+///
+/// * `game` - The game to generate the moves of.
+///
+/// # Returns
+///
+/// The moves escaping check, empty if the player is not in check or is checkmated.
+pub fn generate_evasions(game: &mut ChessGame) -> Vec<ChessMoveType> {
+    let current_turn = game.get_current_players_turn();
+    if !is_in_check(current_turn, game.get_board()) {
+        return Vec::new();
+    }
+    get_legal_moves_for_color(game, current_turn)
+}
+
+/// A legal move together with what it does to the opponent's king.
+///
+/// The check flags are what SAN writes as the `+` and `#` suffixes, so user interfaces and
+/// trainers can show them without playing each move and asking for the game state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnnotatedMove {
+    chess_move: ChessMoveType,
+    checkers: usize,
+    is_checkmate: bool,
+}
+
+impl AnnotatedMove {
+    /// Returns the move.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.chess_move
+    }
+
+    /// Returns the number of pieces giving check after the move, `0` when it gives no check.
+    pub fn get_checkers(&self) -> usize {
+        self.checkers
+    }
+
+    /// Returns `true` if the move puts the opponent in check, including checkmate.
+    pub fn gives_check(&self) -> bool {
+        self.checkers > 0
+    }
+
+    /// Returns `true` if the move checks the opponent with two pieces at once, so only a
+    /// king move gets out of check.
+    pub fn gives_double_check(&self) -> bool {
+        self.checkers > 1
+    }
+
+    /// Returns `true` if the move checkmates the opponent.
+    pub fn is_checkmate(&self) -> bool {
+        self.is_checkmate
+    }
+
+    /// Returns the suffix SAN adds to the move, `#` for checkmate, `+` for check and an
+    /// empty string otherwise.
+    pub fn get_check_suffix(&self) -> &'static str {
+        if self.is_checkmate {
+            "#"
+        } else if self.gives_check() {
+            "+"
+        } else {
+            ""
+        }
+    }
+}
+
+/// Returns the legal moves of the current player, each annotated with whether it gives
+/// check, double check or checkmate.
+///
+/// Only the moves giving check are played out to look for checkmate, the others are
+/// judged on a copy of the board.
+///
+/// # Arguments
+///
+/// * `game` - The game to generate the moves of.
+///
+/// # Returns
+///
+/// The annotated legal moves, in the order `get_legal_moves` would return them.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_move_analyzer::get_annotated_moves;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// let mut game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+/// let moves = get_annotated_moves(&mut game);
+///
+/// // Ra8# is the only check
+/// let checks: Vec<_> = moves.iter().filter(|annotated| annotated.gives_check()).collect();
+/// assert_eq!(1, checks.len());
+/// assert!(checks[0].is_checkmate());
+/// ```
+pub fn get_annotated_moves(game: &mut ChessGame) -> Vec<AnnotatedMove> {
+    get_legal_moves(game)
+        .into_iter()
+        .map(|chess_move| annotate_move(game, chess_move))
+        .collect()
+}
+
+/// Annotates a move of the current player with whether it gives check, double check or
+/// checkmate.
+///
+/// # Arguments
+///
+/// * `game` - The game the move is about to be played in, the game is not modified.
+/// * `chess_move` - The move to annotate, it should be legal in the current position.
+///
+/// # Returns
+///
+/// The `AnnotatedMove`.
+pub fn annotate_move(game: &ChessGame, chess_move: ChessMoveType) -> AnnotatedMove {
+    let mut annotated = AnnotatedMove {
+        chess_move,
+        checkers: 0,
+        is_checkmate: false,
+    };
+    if game.get_ruleset().is_study_mode() {
+        return annotated;
+    }
+
+    let current_turn = game.get_current_players_turn();
+    let mut board = game.get_board().clone();
+    chess_move.make_move(&mut board);
+    if !is_in_check(current_turn.opposite(), &board) {
+        return annotated;
+    }
+
+    annotated.checkers = board
+        .pieces_of(current_turn.opposite())
+        .filter(|(_, piece)| piece.get_piece_type() == King)
+        .map(|(position, _)| {
+            attackers_of(&board, position.get_col(), position.get_row(), current_turn).len()
+        })
+        .sum();
+    let mut after = game.clone();
+    after.apply_move(chess_move);
+    annotated.is_checkmate = after.get_cached_legal_moves().is_empty();
+    annotated
+}
+
+/// Returns the legal moves of the current player that `is_in_stage` keeps, checking only
+/// those moves for legality.
+fn generate_stage(
+    game: &mut ChessGame,
+    is_in_stage: impl Fn(&ChessGame, &ChessMoveType) -> bool,
+) -> Vec<ChessMoveType> {
+    let current_turn = game.get_current_players_turn();
+    let moves = get_all_moves_for_color(current_turn, game)
+        .into_iter()
+        .filter(|chess_move| is_in_stage(game, chess_move))
+        .collect();
+    keep_legal_moves(game, moves, current_turn)
+}
+
+fn is_capture(chess_move: &ChessMoveType) -> bool {
+    match chess_move {
+        ChessMoveType::Move { taken_piece, .. } => taken_piece.is_some(),
+        ChessMoveType::EnPassant { .. } => true,
+        Castle { .. } => false,
+    }
+}
+
+/// Removes the moves a player may not make, such as moves leaving their king in check.
+fn keep_legal_moves(
+    game: &mut ChessGame,
+    all_moves: Vec<ChessMoveType>,
+    current_turn: Color,
+) -> Vec<ChessMoveType> {
+    let legal_moves = if game.get_ruleset().is_study_mode() {
+        all_moves
+    } else if let Some(bitboards) = get_standard_bitboards(game) {
+        let is_legal = |possible_move: &ChessMoveType| {
+            !bitboards
+                .after_move(possible_move)
+                .is_in_check(current_turn)
+        };
+
+        #[cfg(feature = "rayon")]
+        let legal_moves = all_moves.into_par_iter().filter(is_legal).collect();
+        #[cfg(not(feature = "rayon"))]
+        let legal_moves = all_moves.into_iter().filter(is_legal).collect();
+        legal_moves
+    } else {
+        filter_moves_keeping_king_safe(game, all_moves, current_turn)
+    };
+
+    game.get_variant().filter_legal_moves(game, legal_moves)
+}
+
+/// Removes the moves leaving the king in check.
+///
+/// Most moves are judged by the check and pin masks of the position without touching the
+/// board. Moves of the king, castling and en passant can uncover attacks the masks do not
+/// describe, so they are made on the board and undone.
+fn filter_moves_keeping_king_safe(
+    game: &mut ChessGame,
+    all_moves: Vec<ChessMoveType>,
+    current_turn: Color,
+) -> Vec<ChessMoveType> {
+    let masks = LegalityMasks::new(game.get_board(), current_turn);
+    let board = game.get_board_mut();
     all_moves
         .into_iter()
         .filter(|possible_move| {
-            let board = game.get_board_mut();
+            if let Some(is_legal) = masks.as_ref().and_then(|m| m.is_legal(possible_move)) {
+                return is_legal;
+            }
             possible_move.make_move(board);
             let in_check = is_in_check(current_turn, board);
             possible_move.undo_move(board);
             !in_check
         })
-        .collect::<Vec<ChessMoveType>>()
+        .collect()
 }
 
-fn get_all_moves_for_color(color: Color, game: &mut ChessGame) -> Vec<ChessMoveType> {
-    let mut moves: Vec<ChessMoveType> = Vec::new();
-    let board = game.get_board();
+/// The squares the pieces of a player may move to without leaving their king in check.
+struct LegalityMasks {
+    width: usize,
+    king: (usize, usize),
+    checkers: usize,
+    /// the squares capturing or blocking the piece giving check, every square when the king
+    /// is not in check
+    check_mask: Vec<bool>,
+    /// the pinned pieces with the squares between the king and the pinning piece, which
+    /// they may not leave
+    pins: Vec<((usize, usize), Vec<bool>)>,
+}
 
-    for row in 0..board.get_height() {
-        for col in 0..board.get_width() {
-            if let Some(piece) = board.get_piece_at_space(col, row) {
-                if piece.get_color() == color {
-                    moves.append(&mut piece.possible_moves(
-                        (col, row),
-                        board,
-                        game.get_last_move(),
-                    ));
+impl LegalityMasks {
+    /// Finds the checks and pins of a player's king, `None` unless they have exactly one
+    /// king.
+    fn new(board: &Board<ChessPiece>, color: Color) -> Option<Self> {
+        let (width, height) = (board.get_width(), board.get_height());
+        let king_piece = ChessPiece::new(King, color);
+        let mut kings = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (col, row)))
+            .filter(|&(col, row)| board.get_piece_at_space(col, row) == Some(&king_piece));
+        let king = kings.next()?;
+        if kings.next().is_some() {
+            return None;
+        }
+
+        let checkers = attackers_of(board, king.0, king.1, color.opposite());
+        let mut check_mask = vec![checkers.is_empty(); width * height];
+        for &checker in &checkers {
+            for (col, row) in get_squares_between(king, checker).chain([checker]) {
+                check_mask[col + row * width] = true;
+            }
+        }
+
+        let mut pins = Vec::new();
+        for direction in ORTHOGONAL_DIRECTIONS.into_iter().chain(DIAGONAL_DIRECTIONS) {
+            let is_diagonal = direction.0 != 0 && direction.1 != 0;
+            let mut ray = vec![false; width * height];
+            let mut own_piece = None;
+            for (col, row) in Position::from(king)
+                .ray(direction, width, height)
+                .map(<(usize, usize)>::from)
+            {
+                ray[col + row * width] = true;
+                let Some(piece) = board.get_piece_at_space(col, row) else {
+                    continue;
+                };
+                if piece.get_color() == color && own_piece.is_none() {
+                    own_piece = Some((col, row));
+                    continue;
+                }
+                if piece.get_color() != color && slides_along(piece, is_diagonal) {
+                    if let Some(pinned) = own_piece {
+                        pins.push((pinned, ray));
+                    }
                 }
+                break;
             }
         }
+
+        Some(Self {
+            width,
+            king,
+            checkers: checkers.len(),
+            check_mask,
+            pins,
+        })
+    }
+
+    /// Returns whether a move keeps the king safe, `None` if the move has to be made to
+    /// tell.
+    fn is_legal(&self, chess_move: &ChessMoveType) -> Option<bool> {
+        let ChessMoveType::Move {
+            original_position,
+            new_position: (col, row),
+            ..
+        } = chess_move
+        else {
+            return None;
+        };
+        if *original_position == self.king {
+            return None;
+        }
+        // only the king can escape two checks at once
+        if self.checkers > 1 {
+            return Some(false);
+        }
+        let square = col + row * self.width;
+        let is_on_pin_ray = self
+            .pins
+            .iter()
+            .find(|(pinned, _)| pinned == original_position)
+            .is_none_or(|(_, ray)| ray[square]);
+        Some(self.check_mask[square] && is_on_pin_ray)
+    }
+}
+
+/// Returns `true` if a piece attacks along straight lines in the given kind of direction.
+fn slides_along(piece: &ChessPiece, is_diagonal: bool) -> bool {
+    match piece.get_piece_type() {
+        PieceType::Queen => true,
+        PieceType::Rook | PieceType::Chancellor => !is_diagonal,
+        PieceType::Bishop | PieceType::Archbishop => is_diagonal,
+        _ => false,
+    }
+}
+
+/// Returns the squares strictly between two squares on the same line, or nothing if the
+/// squares are not on a line.
+fn get_squares_between(
+    from: (usize, usize),
+    to: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    let col_distance = to.0 as i32 - from.0 as i32;
+    let row_distance = to.1 as i32 - from.1 as i32;
+    let is_on_line =
+        col_distance == 0 || row_distance == 0 || col_distance.abs() == row_distance.abs();
+    let steps = if is_on_line {
+        col_distance.abs().max(row_distance.abs())
+    } else {
+        0
+    };
+    let direction = (col_distance.signum(), row_distance.signum());
+    (1..steps).map(move |step| {
+        (
+            (from.0 as i32 + direction.0 * step) as usize,
+            (from.1 as i32 + direction.1 * step) as usize,
+        )
+    })
+}
+
+/// Returns the bitboards of a game of standard chess, `None` once a promotion has put an
+/// archbishop or chancellor on its board.
+fn get_standard_bitboards(game: &ChessGame) -> Option<Bitboards> {
+    if game.is_standard_chess() {
+        Bitboards::from_board(game.get_board())
+    } else {
+        None
+    }
+}
+
+fn get_all_moves_for_color(color: Color, game: &ChessGame) -> Vec<ChessMoveType> {
+    if let Some(bitboards) = get_standard_bitboards(game) {
+        let mut moves = bitboards.get_pseudo_legal_moves(color, game.get_en_passant_target());
+        moves.extend(generate_possible_castling_moves(color, game));
+        return moves;
+    }
+
+    let mut moves: Vec<ChessMoveType> = Vec::new();
+    let board = game.get_board();
+
+    for (position, piece) in board.pieces_of(color) {
+        moves.append(&mut piece.possible_moves_with_ruleset(
+            position.into(),
+            board,
+            game.get_en_passant_target(),
+            game.get_ruleset(),
+        ));
     }
 
     let castling_moves = generate_possible_castling_moves(color, game);
@@ -55,7 +555,20 @@ fn get_all_moves_for_color(color: Color, game: &mut ChessGame) -> Vec<ChessMoveT
     moves
 }
 
-fn generate_possible_castling_moves(color: Color, game: &mut ChessGame) -> Vec<ChessMoveType> {
+/// Returns `true` if a king on a column has room to castle to a side of the board.
+///
+/// The king moves `distance` squares towards the rook and the rook jumps to the square it
+/// crossed last, so at least one square must be left between the king's new square and the
+/// corner. On narrow boards, such as 5x5, the king is too close to the corners to castle.
+pub(crate) fn has_room_to_castle(col: usize, width: usize, short: bool, distance: usize) -> bool {
+    if short {
+        col + distance + 1 < width
+    } else {
+        col > distance
+    }
+}
+
+fn generate_possible_castling_moves(color: Color, game: &ChessGame) -> Vec<ChessMoveType> {
     let castling_rights = game.get_castling_rights();
     let (long_castle, short_castle) = match color {
         Color::White => (castling_rights.0, castling_rights.1),
@@ -63,64 +576,56 @@ fn generate_possible_castling_moves(color: Color, game: &mut ChessGame) -> Vec<C
     };
 
     let mut moves = Vec::new();
-    let board = game.get_board_mut();
-    let row = match color {
-        Color::White => 0,
-        Color::Black => board.get_height() - 1,
-    };
+    let board = game.get_board();
+    let row = color.relative_rank(0, board.get_height());
+    let distance = game.get_ruleset().get_castling_distance();
 
-    if is_in_check(color, board) {
+    let study_mode = game.get_ruleset().is_study_mode();
+    if !study_mode && is_in_check(color, board) {
         return moves;
     }
+    // the king may not cross an attacked square, its new square is checked with the other
+    // moves
+    let is_path_safe = |mut crossed: std::ops::Range<usize>| {
+        study_mode || crossed.all(|col| !is_square_attacked(board, col, row, color.opposite()))
+    };
     if long_castle {
         for col in 1..board.get_width() - 1 {
             if let Some(piece) = board.get_piece_at_space(col, row) {
-                if piece.get_piece_type() != King || piece.get_color() != color {
+                if piece.get_piece_type() != King
+                    || piece.get_color() != color
+                    || !has_room_to_castle(col, board.get_width(), false, distance)
+                {
                     break;
                 }
-                let tmp_move = Move {
-                    original_position: (col, row),
-                    new_position: (col - 1, row),
-                    piece: ChessPiece::new(King, color),
-                    taken_piece: None,
-                    promotion: None,
-                };
-                tmp_move.make_move(board);
-                if !is_in_check(color, board) {
+                if is_path_safe(col - distance + 1..col) {
                     moves.push(Castle {
                         rook_original_position: (0, row),
-                        rook_new_position: (col - 1, row),
+                        rook_new_position: (col - distance + 1, row),
                         king_original_position: (col, row),
-                        king_new_position: (col - 2, row),
+                        king_new_position: (col - distance, row),
                     })
                 }
-                tmp_move.undo_move(board);
             }
         }
     }
     if short_castle {
         for col in (0..board.get_width() - 1).rev() {
             if let Some(piece) = board.get_piece_at_space(col, row) {
-                if piece.get_piece_type() != King || piece.get_color() != color {
+                if piece.get_piece_type() != King
+                    || piece.get_color() != color
+                    || !has_room_to_castle(col, board.get_width(), true, distance)
+                {
                     break;
                 }
-                let tmp_move = Move {
-                    original_position: (col, row),
-                    new_position: (col + 1, row),
-                    piece: ChessPiece::new(King, color),
-                    taken_piece: None,
-                    promotion: None,
-                };
-                tmp_move.make_move(board);
-                if !is_in_check(color, board) {
+                if is_path_safe(col + 1..col + distance) {
                     moves.push(Castle {
                         rook_original_position: (board.get_width() - 1, row),
-                        rook_new_position: (col + 1, row),
+                        rook_new_position: (col + distance - 1, row),
                         king_original_position: (col, row),
-                        king_new_position: (col + 2, row),
+                        king_new_position: (col + distance, row),
                     });
                 }
-                tmp_move.undo_move(board);
             }
         }
     }
@@ -133,9 +638,27 @@ mod tests {
     use super::*;
     use crate::codec::forsyth_edwards_notation::build_game_from_string;
     use crate::piece::PieceType::{Bishop, Knight, Pawn, Queen, Rook};
+    use crate::ruleset::{PawnRules, Ruleset};
+    use crate::validation::InvariantViolation;
+    use crate::ChessGameBuilder;
     use crate::ChessMoveType::Move;
     use crate::Color::{Black, White};
 
+    #[test]
+    fn annotated_moves_count_the_checking_pieces() {
+        // Nf6 checks with the knight and uncovers the rook on e1
+        let mut game = build_game_from_string("4k3/8/8/8/4N3/8/8/4RK2 w - - 0 1").unwrap();
+        let moves = get_annotated_moves(&mut game);
+        let knight_to_f6 = moves
+            .iter()
+            .find(|annotated| annotated.get_move().get_new_position() == (5, 5))
+            .unwrap();
+
+        assert!(knight_to_f6.gives_double_check());
+        assert!(!knight_to_f6.is_checkmate());
+        assert_eq!("+", knight_to_f6.get_check_suffix());
+    }
+
     #[test]
     fn get_legal_moves_for_starting_position() {
         let mut game = ChessGame::new();
@@ -354,4 +877,298 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn filtering_pseudo_legal_moves_gives_the_legal_moves() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let mut game = build_game_from_string(fen).unwrap();
+        let moves = pseudo_legal_moves(&game);
+
+        assert_eq!(get_legal_moves(&mut game), filter_legal(&mut game, moves));
+    }
+
+    #[test]
+    fn pseudo_legal_moves_can_leave_the_king_in_check() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+        let moves = pseudo_legal_moves(&game);
+        let legal_moves = filter_legal(&mut game, moves.clone());
+
+        assert_eq!(5, moves.len());
+        assert_eq!(3, legal_moves.len());
+        assert!(legal_moves
+            .iter()
+            .all(|chess_move| moves.contains(chess_move)));
+    }
+
+    fn build_large_game(pieces: &[(PieceType, Color, usize, usize)]) -> ChessGame {
+        build_game_of_size(10, 10, false, pieces)
+    }
+
+    fn build_game_of_size(
+        width: usize,
+        height: usize,
+        castling: bool,
+        pieces: &[(PieceType, Color, usize, usize)],
+    ) -> ChessGame {
+        let mut board = Board::build(width, height).unwrap();
+        for &(piece_type, color, col, row) in pieces {
+            board.place_piece(ChessPiece::new(piece_type, color), col, row);
+        }
+        ChessGameBuilder::new()
+            .set_board(board)
+            .set_current_turn(White)
+            .set_castle_rights(castling, castling, castling, castling)
+            .build()
+            .unwrap()
+    }
+
+    fn get_moves_to(moves: &[ChessMoveType], from: (usize, usize)) -> Vec<(usize, usize)> {
+        moves
+            .iter()
+            .filter(|chess_move| chess_move.get_original_position() == from)
+            .map(|chess_move| chess_move.get_new_position())
+            .collect()
+    }
+
+    #[test]
+    fn rules_follow_the_size_of_small_boards() {
+        let mut game = build_game_of_size(
+            5,
+            5,
+            true,
+            &[
+                (King, White, 2, 0),
+                (Rook, White, 0, 0),
+                (Rook, White, 4, 0),
+                (Pawn, White, 1, 1),
+                (Pawn, White, 4, 3),
+                (King, Black, 2, 4),
+                (Pawn, Black, 0, 3),
+            ],
+        );
+        let moves = get_legal_moves(&mut game);
+
+        // the king is too close to the corners to castle
+        assert!(!moves.iter().any(|m| matches!(m, Castle { .. })));
+        assert!(game
+            .validate()
+            .unwrap_err()
+            .iter()
+            .all(|v| matches!(v, InvariantViolation::InvalidCastlingRights { .. })));
+        assert_eq!(vec![(1, 2), (1, 3)], get_moves_to(&moves, (1, 1)));
+        let promotions: Vec<_> = moves
+            .iter()
+            .filter(|m| m.get_original_position() == (4, 3))
+            .filter(|m| {
+                matches!(
+                    m,
+                    Move {
+                        promotion: Some(_),
+                        ..
+                    }
+                )
+            })
+            .collect();
+        assert_eq!(4, promotions.len());
+
+        let pawn_move = moves.iter().find(|m| m.get_new_position() == (1, 2));
+        game.make_move(*pawn_move.unwrap());
+        let black_moves = get_legal_moves(&mut game);
+        assert!(get_moves_to(&black_moves, (0, 3)).contains(&(0, 1)));
+    }
+
+    #[test]
+    fn rules_follow_the_size_of_large_boards() {
+        let mut game = build_game_of_size(
+            10,
+            10,
+            true,
+            &[
+                (King, White, 5, 0),
+                (Rook, White, 0, 0),
+                (Rook, White, 9, 0),
+                (Pawn, White, 2, 1),
+                (Pawn, White, 1, 8),
+                (King, Black, 5, 9),
+                (Rook, Black, 0, 9),
+                (Rook, Black, 9, 9),
+            ],
+        );
+        let moves = get_legal_moves(&mut game);
+
+        let castles: Vec<_> = moves
+            .iter()
+            .filter(|m| matches!(m, Castle { .. }))
+            .copied()
+            .collect();
+        assert_eq!(
+            vec![
+                Castle {
+                    rook_original_position: (0, 0),
+                    rook_new_position: (4, 0),
+                    king_original_position: (5, 0),
+                    king_new_position: (3, 0),
+                },
+                Castle {
+                    rook_original_position: (9, 0),
+                    rook_new_position: (6, 0),
+                    king_original_position: (5, 0),
+                    king_new_position: (7, 0),
+                },
+            ],
+            castles
+        );
+        assert_eq!(vec![(2, 2), (2, 3)], get_moves_to(&moves, (2, 1)));
+        // the pawn promotes on the tenth rank, moving forwards or taking the rook
+        let pawn_moves: Vec<_> = moves
+            .iter()
+            .filter(|m| m.get_original_position() == (1, 8))
+            .collect();
+        assert_eq!(8, pawn_moves.len());
+        assert!(pawn_moves.iter().all(|m| matches!(
+            m,
+            Move {
+                promotion: Some(_),
+                ..
+            }
+        )));
+        assert_eq!(Ok(()), game.validate());
+    }
+
+    /// Checks the masks against making every move and looking for a check.
+    fn assert_masks_match_making_moves(game: &mut ChessGame) -> Vec<ChessMoveType> {
+        let moves = pseudo_legal_moves(game);
+        let expected: Vec<ChessMoveType> = moves
+            .iter()
+            .filter(|chess_move| {
+                let mut board = game.get_board().clone();
+                chess_move.make_move(&mut board);
+                !is_in_check(White, &board)
+            })
+            .copied()
+            .collect();
+        let legal_moves = filter_legal(game, moves);
+        assert_eq!(expected, legal_moves);
+        legal_moves
+    }
+
+    #[test]
+    fn pinned_pieces_stay_on_the_pin_ray() {
+        let mut game = build_large_game(&[
+            (King, White, 0, 0),
+            (Rook, White, 0, 3),
+            (Bishop, White, 2, 2),
+            (Rook, Black, 0, 9),
+            (Queen, Black, 6, 6),
+            (King, Black, 9, 9),
+        ]);
+        let legal_moves = assert_masks_match_making_moves(&mut game);
+
+        // the rook may only move along the file and the bishop along the diagonal
+        assert!(legal_moves
+            .iter()
+            .filter(|chess_move| chess_move.get_original_position() == (0, 3))
+            .all(|chess_move| chess_move.get_new_position().0 == 0));
+        assert!(legal_moves
+            .iter()
+            .filter(|chess_move| chess_move.get_original_position() == (2, 2))
+            .all(|chess_move| {
+                let (col, row) = chess_move.get_new_position();
+                col == row
+            }));
+    }
+
+    #[test]
+    fn checks_must_be_blocked_or_captured() {
+        let mut game = build_large_game(&[
+            (King, White, 4, 0),
+            (Knight, White, 2, 1),
+            (Rook, White, 8, 8),
+            (Bishop, Black, 8, 4),
+            (King, Black, 9, 9),
+        ]);
+        let legal_moves = assert_masks_match_making_moves(&mut game);
+        assert!(legal_moves.iter().any(|m| m.get_new_position() == (8, 4)));
+
+        // a knight and a rook checking together leave only king moves
+        let mut game = build_large_game(&[
+            (King, White, 4, 0),
+            (Queen, White, 0, 5),
+            (Knight, Black, 5, 2),
+            (Rook, Black, 4, 7),
+            (King, Black, 9, 9),
+        ]);
+        let legal_moves = assert_masks_match_making_moves(&mut game);
+        assert!(legal_moves
+            .iter()
+            .all(|chess_move| chess_move.get_original_position() == (4, 0)));
+    }
+
+    #[test]
+    fn stages_split_the_legal_moves() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let mut game = build_game_from_string(fen).unwrap();
+        let legal_moves = get_legal_moves(&mut game);
+        let captures = generate_captures(&mut game);
+        let quiet_moves = generate_quiet_moves(&mut game);
+
+        assert_eq!(8, captures.len());
+        assert_eq!(legal_moves.len(), captures.len() + quiet_moves.len());
+        for chess_move in captures.iter().chain(&quiet_moves) {
+            assert!(legal_moves.contains(chess_move));
+        }
+        assert!(generate_evasions(&mut game).is_empty());
+    }
+
+    #[test]
+    fn checks_and_evasions_are_legal() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/3P4/r3K3 w - - 0 1").unwrap();
+        let evasions = generate_evasions(&mut game);
+        assert_eq!(get_legal_moves(&mut game), evasions);
+        assert_eq!(2, evasions.len());
+
+        // every move of the knight uncovers the rook
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/4N3/4R1K1 w - - 0 1").unwrap();
+        let checks = generate_checks(&mut game);
+        assert_eq!(5, checks.len());
+        assert!(checks.iter().all(|chess_move| {
+            let mut board = game.get_board().clone();
+            chess_move.make_move(&mut board);
+            is_in_check(Black, &board)
+        }));
+    }
+
+    #[test]
+    fn legal_moves_respect_the_games_ruleset() {
+        let mut board = Board::build(5, 5).unwrap();
+        board.place_piece(ChessPiece::new(Pawn, White), 2, 1);
+        let mut game = ChessGameBuilder::new()
+            .set_board(board)
+            .set_current_turn(White)
+            .set_ruleset(
+                Ruleset::new().set_pawn_rules(
+                    PawnRules::new()
+                        .set_can_double_step(false)
+                        .set_promotion_rank_offset(2),
+                ),
+            )
+            .build()
+            .unwrap();
+
+        let legal_moves = get_legal_moves(&mut game);
+        assert_eq!(4, legal_moves.len());
+        for legal_move in legal_moves {
+            match legal_move {
+                Move {
+                    new_position,
+                    promotion,
+                    ..
+                } => {
+                    assert_eq!((2, 2), new_position);
+                    assert!(promotion.is_some());
+                }
+                _ => panic!("expected a pawn move"),
+            }
+        }
+    }
 }
@@ -1,19 +1,25 @@
-use crate::chess_game_move_analyzer;
+use crate::chess_board::ChessBoard;
+use crate::engine::attack_tables::{get_squares, KING_ATTACKS, KNIGHT_ATTACKS, PAWN_ATTACKS};
 use crate::piece::PieceType::King;
 use crate::piece::{ChessPiece, PieceType};
-use crate::ChessMoveType::Move;
 use crate::{ChessGame, ChessMoveType, Color};
 use game_board::Board;
 
 /// Represents the current state of a simple_chess game.
 ///
 /// The `GameState` enum is used to track the status of an ongoing simple_chess game.
-/// It can be one of four possible states:
+/// It can be one of nine possible states:
 ///
 /// - `InProgress`: The game is actively being played, with available legal moves for the current turn.
 /// - `Check`: The current player is in check, meaning their king is under threat but has legal moves to counter.
 /// - `Checkmate`: The current player's king is in check and there are no legal moves to escape, resulting in a victory for the opponent.
 /// - `Stalemate`: The game is in a state where the current player has no legal moves, but their king is not in check, resulting in a draw.
+/// - `VariantWin`: A player has met a win condition of the game's variant, such as reaching the center in King of the Hill.
+/// - `Resigned`: A player resigned, or the game was adjudicated as won.
+/// - `DrawAgreed`: The players agreed to a draw, or the game was adjudicated as drawn.
+/// - `DeadPosition`: No sequence of legal moves can lead to checkmate, so the game is drawn.
+/// - `SeventyFiveMoveRule`: Seventy-five moves by each player were made without a pawn move or
+///   capture, so the game is drawn.
 ///
 /// # Enum Variants
 ///
@@ -21,7 +27,15 @@ use game_board::Board;
 /// - `Check`: Holds a vector of legal moves and indicates whose turn it is.
 /// - `Checkmate`: Indicates the winning player's color.
 /// - `Stalemate`: Indicates the game has ended in a draw.
-#[derive(Debug, PartialEq)]
+/// - `VariantWin`: Indicates the winning player's color.
+/// - `Resigned`: Indicates the winning player's color.
+/// - `DrawAgreed`: Indicates the game has ended in a draw.
+/// - `DeadPosition`: Indicates the game has ended in a draw.
+/// - `SeventyFiveMoveRule`: Indicates the game has ended in a draw.
+///
+/// The state describes the game as it is played. Once it is over, `ChessGame::get_result`
+/// returns a `GameResult` with the scored winner and how the game ended.
+#[derive(Debug, Clone, PartialEq)]
 pub enum GameState {
     InProgress {
         legal_moves: Vec<ChessMoveType>,
@@ -35,8 +49,21 @@ pub enum GameState {
         winner: Color,
     },
     Stalemate,
+    VariantWin {
+        winner: Color,
+    },
+    Resigned {
+        winner: Color,
+    },
+    DrawAgreed,
+    DeadPosition,
+    SeventyFiveMoveRule,
 }
 
+/// The number of half-moves without a pawn move or capture after which the game is drawn
+/// without either player having to claim it.
+pub const SEVENTY_FIVE_MOVE_RULE_LIMIT: usize = 150;
+
 /// Determines the current state of a simple_chess game.
 ///
 /// The `get_game_state` function analyzes the simple_chess game to determine
@@ -48,6 +75,12 @@ pub enum GameState {
 ///   moves left, resulting in the opponent's victory.
 /// - `Stalemate`: If the current player has no legal moves, and their king is not
 ///   in check, resulting in a draw.
+/// - `VariantWin`: If a player has met a win condition of the game's variant, which is
+///   checked before any of the other states.
+/// - `SeventyFiveMoveRule`: If the player to move has legal moves and the halfmove clock has
+///   reached `SEVENTY_FIVE_MOVE_RULE_LIMIT`, a checkmate on the last of those moves stands.
+/// - `DeadPosition`: If the player to move has legal moves but neither player can ever
+///   checkmate, see `is_dead_position`.
 ///
 /// # Parameters
 ///
@@ -58,8 +91,26 @@ pub enum GameState {
 ///
 /// - `GameState`: Enum variant representing the current state of the simple_chess game.
 pub fn get_game_state(game: &mut ChessGame) -> GameState {
-    let legal_moves = chess_game_move_analyzer::get_legal_moves(game);
-    if is_in_check(game.get_current_players_turn(), game.get_board()) {
+    if let Some(state) = game.get_ending_state() {
+        return state;
+    }
+
+    if let Some(winner) = game.get_variant().get_winner(game) {
+        return GameState::VariantWin { winner };
+    }
+
+    let legal_moves = game.get_cached_legal_moves().to_vec();
+    // study mode has no checkmate, so there is nothing to draw the game for
+    if !legal_moves.is_empty() && !game.get_ruleset().is_study_mode() {
+        if game.get_halfmove_clock() >= SEVENTY_FIVE_MOVE_RULE_LIMIT {
+            return GameState::SeventyFiveMoveRule;
+        }
+        if game.get_variant().is_dead_position(game.get_board()) {
+            return GameState::DeadPosition;
+        }
+    }
+
+    if is_current_player_in_check(game) {
         if legal_moves.is_empty() {
             GameState::Checkmate {
                 winner: game.get_current_players_turn().opposite(),
@@ -85,7 +136,7 @@ pub fn get_game_state(game: &mut ChessGame) -> GameState {
 /// Checks if the player of the specified color is in check.
 ///
 /// This function evaluates the board to determine if the player's king is under threat from any opposing pieces.
-/// It scans through all pieces of the opposing color and checks if any have a legal move that can capture the king.
+/// It locates the king and uses `is_square_attacked` to check if any opposing piece attacks its square.
 ///
 /// # Parameters
 ///
@@ -96,35 +147,194 @@ pub fn get_game_state(game: &mut ChessGame) -> GameState {
 ///
 /// - `bool`: Returns `true` if the player's king is in check, meaning it is under threat. Returns `false` otherwise.
 pub fn is_in_check(color: Color, board: &Board<ChessPiece>) -> bool {
-    for row in 0..board.get_height() {
-        for col in 0..board.get_width() {
-            if let Some(piece) = board.get_piece_at_space(col, row) {
-                if piece.get_color() == color.opposite() {
-                    let moves = piece.possible_moves((col, row), board, None);
-                    for m in moves {
-                        match m {
-                            Move { taken_piece, .. } => {
-                                if let Some(taken_piece) = taken_piece {
-                                    if taken_piece.get_piece_type() == King {
-                                        return true;
-                                    }
-                                }
-                            }
-                            _ => return false,
-                        }
-                    }
-                }
+    board.pieces_of(color).any(|(position, piece)| {
+        piece.get_piece_type() == King
+            && is_square_attacked(
+                board,
+                position.get_col(),
+                position.get_row(),
+                color.opposite(),
+            )
+    })
+}
+
+/// Checks if the player to move is in check, which is never the case in study mode where
+/// a player may have any number of kings.
+pub(crate) fn is_current_player_in_check(game: &ChessGame) -> bool {
+    !game.get_ruleset().is_study_mode()
+        && is_in_check(game.get_current_players_turn(), game.get_board())
+}
+
+/// Checks if a square is attacked by any piece of the given color.
+///
+/// Rather than generating every move for the attacking side, this function looks outwards
+/// from the square: it casts rays for rooks, bishops, queens and the compound pieces of
+/// Capablanca chess, and checks the fixed offsets knights, pawns and kings attack from, which are looked up in precomputed tables
+/// on a standard 8x8 board.
+///
+/// # Parameters
+///
+/// - `board`: A reference to the `Board` containing simple_chess pieces.
+/// - `col`: The column of the square to check.
+/// - `row`: The row of the square to check.
+/// - `by_color`: The `Color` of the pieces that may be attacking the square.
+///
+/// # Returns
+///
+/// - `bool`: Returns `true` if at least one piece of `by_color` attacks the square.
+///
+/// # Example
+/// ```
+/// use simple_chess::chess_game_state_analyzer::is_square_attacked;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::Color;
+/// use game_board::Board;
+///
+/// let mut board = Board::build(8, 8).unwrap();
+/// board.place_piece(ChessPiece::new(PieceType::Rook, Color::Black), 0, 7);
+///
+/// assert!(is_square_attacked(&board, 0, 0, Color::Black));
+/// assert!(!is_square_attacked(&board, 1, 0, Color::Black));
+/// ```
+pub fn is_square_attacked(
+    board: &Board<ChessPiece>,
+    col: usize,
+    row: usize,
+    by_color: Color,
+) -> bool {
+    let is_attacker = |offset: (i32, i32), piece_types: &[PieceType]| -> bool {
+        match get_piece_at_offset(board, (col, row), offset) {
+            Some(piece) => {
+                piece.get_color() == by_color && piece_types.contains(&piece.get_piece_type())
             }
+            None => false,
+        }
+    };
+
+    // on a standard board the pawns, knights and kings that could attack the square are
+    // looked up in the precomputed tables
+    if board.get_width() == 8 && board.get_height() == 8 {
+        let square = col + row * 8;
+        let has_attacker = |bitboard: u64, piece_types: &[PieceType]| {
+            get_squares(bitboard).any(|(attacker_col, attacker_row)| {
+                board
+                    .get_piece_at_space(attacker_col, attacker_row)
+                    .is_some_and(|piece| {
+                        piece.get_color() == by_color
+                            && piece_types.contains(&piece.get_piece_type())
+                    })
+            })
+        };
+        // a pawn attacks the square if a pawn of the other color on it would attack the pawn
+        let pawn_table = match by_color {
+            Color::White => &PAWN_ATTACKS[1],
+            Color::Black => &PAWN_ATTACKS[0],
+        };
+        if has_attacker(pawn_table[square], &[PieceType::Pawn])
+            || has_attacker(KNIGHT_ATTACKS[square], KNIGHT_JUMPERS)
+            || has_attacker(KING_ATTACKS[square], &[King])
+        {
+            return true;
+        }
+    } else {
+        // pawns attack forwards, so an attacking pawn stands one row behind the square
+        let pawn_row_offset = -by_color.forward();
+        let is_leaper_attacker = [(-1, pawn_row_offset), (1, pawn_row_offset)]
+            .into_iter()
+            .any(|offset| is_attacker(offset, &[PieceType::Pawn]))
+            || KNIGHT_OFFSETS
+                .into_iter()
+                .any(|offset| is_attacker(offset, KNIGHT_JUMPERS))
+            || ORTHOGONAL_DIRECTIONS
+                .into_iter()
+                .chain(DIAGONAL_DIRECTIONS)
+                .any(|offset| is_attacker(offset, &[King]));
+        if is_leaper_attacker {
+            return true;
         }
     }
-    false
+
+    let is_slider_attacker = |direction: (i32, i32), piece_types: &[PieceType]| -> bool {
+        let mut distance = 1;
+        loop {
+            let offset = (direction.0 * distance, direction.1 * distance);
+            if !is_on_board(board, (col, row), offset) {
+                return false;
+            }
+            if let Some(piece) = get_piece_at_offset(board, (col, row), offset) {
+                return piece.get_color() == by_color
+                    && piece_types.contains(&piece.get_piece_type());
+            }
+            distance += 1;
+        }
+    };
+
+    ORTHOGONAL_DIRECTIONS.into_iter().any(|direction| {
+        is_slider_attacker(
+            direction,
+            &[PieceType::Rook, PieceType::Queen, PieceType::Chancellor],
+        )
+    }) || DIAGONAL_DIRECTIONS.into_iter().any(|direction| {
+        is_slider_attacker(
+            direction,
+            &[PieceType::Bishop, PieceType::Queen, PieceType::Archbishop],
+        )
+    })
+}
+
+/// The pieces that attack by jumping like a knight.
+const KNIGHT_JUMPERS: &[PieceType] = &[
+    PieceType::Knight,
+    PieceType::Archbishop,
+    PieceType::Chancellor,
+];
+
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [
+    (1, 2),
+    (1, -2),
+    (-1, 2),
+    (-1, -2),
+    (2, 1),
+    (2, -1),
+    (-2, 1),
+    (-2, -1),
+];
+
+pub(crate) const ORTHOGONAL_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+pub(crate) const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+fn is_on_board(board: &Board<ChessPiece>, position: (usize, usize), offset: (i32, i32)) -> bool {
+    let col = position.0 as i32 + offset.0;
+    let row = position.1 as i32 + offset.1;
+    col >= 0 && row >= 0 && col < board.get_width() as i32 && row < board.get_height() as i32
+}
+
+fn get_piece_at_offset(
+    board: &Board<ChessPiece>,
+    position: (usize, usize),
+    offset: (i32, i32),
+) -> Option<&ChessPiece> {
+    if !is_on_board(board, position, offset) {
+        return None;
+    }
+    board.get_piece_at_space(
+        (position.0 as i32 + offset.0) as usize,
+        (position.1 as i32 + offset.1) as usize,
+    )
 }
 
 /// Determines if there is insufficient material on the board to continue the game.
 ///
-/// The `is_insufficient_material` function checks if both players have insufficient material
-/// to reach a checkmate. The game would end in a draw if neither player can checkmate the opponent,
-/// regardless of the moves made.
+/// The `is_insufficient_material` function looks at both armies together and checks that
+/// neither player can win the game, whatever moves are made. Only kings, knights and
+/// bishops may be left on the board, and one of the following must hold:
+///
+/// - No knights are left and every bishop, of either player, stands on squares of the same
+///   color. Such bishops can never attack a king on the other color, so no checkmate is
+///   possible at all, however many bishops there are.
+/// - Each player has at most one knight or bishop, apart from a player with two knights
+///   against a bare king, which can not force checkmate.
 ///
 /// # Parameters
 ///
@@ -135,38 +345,209 @@ pub fn is_in_check(color: Color, board: &Board<ChessPiece>) -> bool {
 ///
 /// - `bool`: Returns `true` if both players have insufficient material to reach checkmate.
 ///   Otherwise, it returns `false`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::is_insufficient_material;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// // bishops on c1 and f5 both stand on dark squares
+/// let same_colored = build_game_from_string("4k3/8/8/5b2/8/8/8/2B1K3 w - - 0 1").unwrap();
+/// assert!(is_insufficient_material(same_colored.get_board()));
+///
+/// // bishops on c1 and f1 can cover every square between them
+/// let bishop_pair = build_game_from_string("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1").unwrap();
+/// assert!(!is_insufficient_material(bishop_pair.get_board()));
+/// ```
 pub fn is_insufficient_material(board: &Board<ChessPiece>) -> bool {
-    let mut white_pieces = vec![];
-    let mut black_pieces = vec![];
-    for col in 0..board.get_width() {
-        for row in 0..board.get_height() {
-            if let Some(piece) = board.get_piece_at_space(col, row) {
-                match piece.get_color() {
-                    Color::White => white_pieces.push(piece),
-                    Color::Black => black_pieces.push(piece),
-                }
+    // the minor pieces of each player, and the square colors their bishops stand on
+    let mut white_minor_pieces = vec![];
+    let mut black_minor_pieces = vec![];
+    let mut bishop_square_colors = vec![];
+    for (position, piece) in board.pieces() {
+        match piece.get_piece_type() {
+            King => continue,
+            PieceType::Bishop => {
+                bishop_square_colors.push((position.get_col() + position.get_row()) % 2)
             }
+            PieceType::Knight => {}
+            _ => return false,
+        }
+        match piece.get_color() {
+            Color::White => white_minor_pieces.push(piece.get_piece_type()),
+            Color::Black => black_minor_pieces.push(piece.get_piece_type()),
         }
     }
 
-    let check = |pieces: &Vec<&ChessPiece>| -> bool {
-        if pieces.len() < 2 {
-            return true;
-        } else if pieces.len() == 2 {
-            let piece_type_a = pieces[0].get_piece_type();
-            let piece_type_b = pieces[1].get_piece_type();
-
-            let other = if piece_type_a == King {
-                piece_type_b
-            } else {
-                piece_type_a
-            };
-            return matches!(other, PieceType::Knight | PieceType::Bishop);
-        }
-        false
+    let has_knights = white_minor_pieces
+        .iter()
+        .chain(black_minor_pieces.iter())
+        .any(|piece_type| *piece_type == PieceType::Knight);
+    if !has_knights
+        && bishop_square_colors
+            .iter()
+            .all(|square_color| *square_color == bishop_square_colors[0])
+    {
+        return true;
+    }
+
+    let cannot_win = |pieces: &Vec<PieceType>, opponent_pieces: &Vec<PieceType>| -> bool {
+        match pieces.as_slice() {
+            [] | [_] => true,
+            [PieceType::Knight, PieceType::Knight] => opponent_pieces.is_empty(),
+            _ => false,
+        }
     };
 
-    check(&white_pieces) && check(&black_pieces)
+    cannot_win(&white_minor_pieces, &black_minor_pieces)
+        && cannot_win(&black_minor_pieces, &white_minor_pieces)
+}
+
+/// Determines if no sequence of legal moves can lead to checkmate, which under the FIDE
+/// rules ends the game in a draw immediately.
+///
+/// Unlike `is_insufficient_material`, which also covers positions a player can not force a
+/// win in, this only returns `true` when checkmate is impossible whatever both players do.
+/// Two kinds of dead positions are detected:
+///
+/// - Only kings, at most one knight and no bishops, or kings and bishops that all stand on
+///   squares of the same color.
+/// - Only kings and pawns, where every pawn is blocked by another pawn and has nothing to
+///   capture, and neither king can reach a square next to an opposing pawn. Pawns can then
+///   never move again, and kings alone can not give check.
+///
+/// Other locked positions, such as pawn walls with bishops behind them, are not detected.
+///
+/// # Parameters
+///
+/// - `board`: The board to check.
+///
+/// # Returns
+///
+/// - `bool`: `true` if the position is dead, `false` if checkmate may still be possible.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::{is_dead_position, GameState};
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+///
+/// // a closed pawn chain that neither king can get through
+/// let mut game = build_game_from_string("8/4k3/8/p1p1p1p1/P1P1P1P1/8/4K3/8 w - - 0 1").unwrap();
+/// assert!(is_dead_position(game.get_board()));
+/// assert_eq!(GameState::DeadPosition, game.get_game_state());
+///
+/// // the pawns on the a-file and b-file are not blocked
+/// let open = build_game_from_string("8/4k3/8/2p1p1p1/P1P1P1P1/8/4K3/8 w - - 0 1").unwrap();
+/// assert!(!is_dead_position(open.get_board()));
+/// ```
+pub fn is_dead_position(board: &Board<ChessPiece>) -> bool {
+    let mut kings = vec![];
+    let mut pawns = vec![];
+    let mut knights = 0;
+    let mut bishop_square_colors = vec![];
+    for (position, piece) in board.pieces() {
+        let (col, row) = position.get_col_and_row();
+        match piece.get_piece_type() {
+            King => kings.push(((col, row), piece.get_color())),
+            PieceType::Pawn => pawns.push(((col, row), piece.get_color())),
+            PieceType::Knight => knights += 1,
+            PieceType::Bishop => bishop_square_colors.push((col + row) % 2),
+            _ => return false,
+        }
+    }
+
+    if pawns.is_empty() {
+        return match (knights, bishop_square_colors.as_slice()) {
+            (0, []) | (1, []) => true,
+            (0, [first, rest @ ..]) => rest.iter().all(|square_color| square_color == first),
+            _ => false,
+        };
+    }
+    if knights > 0 || !bishop_square_colors.is_empty() {
+        return false;
+    }
+
+    let is_pawn_locked = |&((col, row), color): &((usize, usize), Color)| -> bool {
+        let direction = pawn_direction(color);
+        let blocked = get_piece_at_offset(board, (col, row), (0, direction))
+            .is_some_and(|piece| piece.get_piece_type() == PieceType::Pawn);
+        let can_capture = [-1, 1].into_iter().any(|side| {
+            get_piece_at_offset(board, (col, row), (side, direction))
+                .is_some_and(|piece| piece.get_color() != color)
+        });
+        blocked && !can_capture
+    };
+    if !pawns.iter().all(is_pawn_locked) {
+        return false;
+    }
+
+    kings
+        .iter()
+        .all(|&(position, color)| !can_king_reach_opposing_pawn(board, position, color))
+}
+
+/// The row offset of a pawn's forward move.
+fn pawn_direction(color: Color) -> i32 {
+    match color {
+        Color::White => 1,
+        Color::Black => -1,
+    }
+}
+
+/// Walks every square a king can reach past locked pawns, checking whether it can get next
+/// to a pawn of the other player.
+fn can_king_reach_opposing_pawn(
+    board: &Board<ChessPiece>,
+    start: (usize, usize),
+    color: Color,
+) -> bool {
+    let is_opposing_pawn = |piece: &ChessPiece| {
+        piece.get_piece_type() == PieceType::Pawn && piece.get_color() != color
+    };
+    let is_attacked_by_pawn = |position: (usize, usize)| {
+        let direction = pawn_direction(color.opposite());
+        [-1, 1].into_iter().any(|side| {
+            get_piece_at_offset(board, position, (side, -direction)).is_some_and(is_opposing_pawn)
+        })
+    };
+    let king_offsets = [
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ];
+
+    let mut visited = vec![false; board.get_width() * board.get_height()];
+    visited[start.1 * board.get_width() + start.0] = true;
+    let mut to_visit = vec![start];
+    while let Some(position) = to_visit.pop() {
+        for offset in king_offsets {
+            if !is_on_board(board, position, offset) {
+                continue;
+            }
+            let next = (
+                (position.0 as i32 + offset.0) as usize,
+                (position.1 as i32 + offset.1) as usize,
+            );
+            match board.get_piece_at_space(next.0, next.1) {
+                Some(piece) if is_opposing_pawn(piece) => return true,
+                Some(piece) if piece.get_piece_type() == PieceType::Pawn => continue,
+                _ => {}
+            }
+            let index = next.1 * board.get_width() + next.0;
+            if !visited[index] && !is_attacked_by_pawn(next) {
+                visited[index] = true;
+                to_visit.push(next);
+            }
+        }
+    }
+    false
 }
 
 #[cfg(test)]
@@ -174,6 +555,7 @@ mod tests {
     use super::*;
     use crate::chess_game_state_analyzer::GameState::{Check, Checkmate, InProgress, Stalemate};
     use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::play_moves;
     use crate::Color::{Black, White};
 
     #[test]
@@ -204,7 +586,7 @@ mod tests {
     fn game_is_in_stalemate() {
         let mut game = build_game_from_string("k7/7R/8/8/8/8/8/1RK5 b - - 0 1").unwrap();
         match get_game_state(&mut game) {
-            Stalemate {} => (),
+            Stalemate => (),
             _ => panic!("Game state is not in progress."),
         }
     }
@@ -243,4 +625,185 @@ mod tests {
         let game = build_game_from_string("k7/8/bN6/8/8/8/8/K7 b - - 0 1").unwrap();
         assert!(is_insufficient_material(game.get_board()));
     }
+
+    #[test]
+    fn bishops_on_one_square_color_are_insufficient_material() {
+        let both_sides = build_game_from_string("k7/8/8/8/8/8/2b5/KB6 b - - 0 1").unwrap();
+        assert!(is_insufficient_material(both_sides.get_board()));
+
+        let three_bishops = build_game_from_string("k7/8/8/8/8/8/2b5/KB1B4 b - - 0 1").unwrap();
+        assert!(is_insufficient_material(three_bishops.get_board()));
+
+        let opposite_colors = build_game_from_string("k7/8/8/8/8/8/2b5/KBB5 b - - 0 1").unwrap();
+        assert!(!is_insufficient_material(opposite_colors.get_board()));
+    }
+
+    #[test]
+    fn two_knights_can_only_win_against_other_pieces() {
+        let bare_king = build_game_from_string("k7/8/8/8/8/8/8/KNN5 b - - 0 1").unwrap();
+        assert!(is_insufficient_material(bare_king.get_board()));
+
+        let against_a_knight = build_game_from_string("kn6/8/8/8/8/8/8/KNN5 b - - 0 1").unwrap();
+        assert!(!is_insufficient_material(against_a_knight.get_board()));
+
+        let bishop_and_knight = build_game_from_string("k7/8/8/8/8/8/8/KBN5 b - - 0 1").unwrap();
+        assert!(!is_insufficient_material(bishop_and_knight.get_board()));
+    }
+
+    #[test]
+    fn lone_minor_pieces_and_same_colored_bishops_are_dead() {
+        for fen in [
+            "k7/8/8/8/8/8/8/K7 w - - 0 1",
+            "k7/8/8/8/8/8/8/KN6 w - - 0 1",
+            "k7/8/8/8/8/8/2b5/KB1B4 w - - 0 1",
+        ] {
+            let mut game = build_game_from_string(fen).unwrap();
+            assert!(is_dead_position(game.get_board()), "{fen}");
+            assert_eq!(GameState::DeadPosition, get_game_state(&mut game), "{fen}");
+        }
+
+        for fen in [
+            "k7/8/8/8/8/8/8/KNN5 w - - 0 1",
+            "k7/8/8/8/8/8/1b6/KB6 w - - 0 1",
+            "kn6/8/8/8/8/8/8/KB6 w - - 0 1",
+        ] {
+            let game = build_game_from_string(fen).unwrap();
+            assert!(!is_dead_position(game.get_board()), "{fen}");
+        }
+    }
+
+    #[test]
+    fn pawn_walls_are_dead_only_if_the_kings_are_sealed_off() {
+        let wall = "8/3k4/8/1p1p1p1p/pPpPpPpP/P1P1P1P1/8/3K4 w - - 0 1";
+        assert!(is_dead_position(
+            build_game_from_string(wall).unwrap().get_board()
+        ));
+
+        // the kings can walk around the blocked pawns and attack them
+        let gap = "8/8/8/k1p5/2P5/8/8/K7 w - - 0 1";
+        assert!(!is_dead_position(
+            build_game_from_string(gap).unwrap().get_board()
+        ));
+
+        // the pawn on c4 can capture on d5
+        let capture = "8/3k4/8/3p4/2P5/2p5/8/3K4 w - - 0 1";
+        assert!(!is_dead_position(
+            build_game_from_string(capture).unwrap().get_board()
+        ));
+
+        // a rook could break through
+        let rook = "8/3k4/8/1p1p1p1p/pPpPpPpP/P1P1P1P1/8/3K3R w - - 0 1";
+        assert!(!is_dead_position(
+            build_game_from_string(rook).unwrap().get_board()
+        ));
+    }
+
+    #[test]
+    fn the_seventy_five_move_rule_ends_the_game_unless_it_is_checkmate() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/8/R3K3 w - - 149 100").unwrap();
+        assert!(matches!(get_game_state(&mut game), InProgress { .. }));
+
+        game.make_move(ChessMoveType::Move {
+            original_position: (0, 0),
+            new_position: (0, 1),
+            piece: ChessPiece::new(PieceType::Rook, White),
+            taken_piece: None,
+            promotion: None,
+        });
+        assert_eq!(150, game.get_halfmove_clock());
+        assert_eq!(GameState::SeventyFiveMoveRule, get_game_state(&mut game));
+
+        let mut mated = build_game_from_string("k6R/pp6/8/8/8/8/8/8 b - - 150 100").unwrap();
+        assert_eq!(Checkmate { winner: White }, get_game_state(&mut mated));
+    }
+
+    #[test]
+    fn pawns_only_attack_diagonally_forward() {
+        let game = build_game_from_string("8/8/8/8/3P4/8/8/8 w - - 0 1").unwrap();
+        let board = game.get_board();
+
+        assert!(is_square_attacked(board, 2, 4, White));
+        assert!(is_square_attacked(board, 4, 4, White));
+        assert!(!is_square_attacked(board, 3, 4, White));
+        assert!(!is_square_attacked(board, 2, 2, White));
+        assert!(!is_square_attacked(board, 2, 4, Black));
+    }
+
+    #[test]
+    fn knights_and_kings_attack_their_offsets() {
+        let game = build_game_from_string("8/8/8/8/8/8/8/n6k w - - 0 1").unwrap();
+        let board = game.get_board();
+
+        assert!(is_square_attacked(board, 1, 2, Black));
+        assert!(is_square_attacked(board, 2, 1, Black));
+        assert!(is_square_attacked(board, 6, 1, Black));
+        assert!(is_square_attacked(board, 7, 1, Black));
+        assert!(!is_square_attacked(board, 5, 1, Black));
+        assert!(!is_square_attacked(board, 1, 1, Black));
+    }
+
+    #[test]
+    fn sliding_pieces_are_blocked() {
+        let game = build_game_from_string("8/8/8/8/8/8/1P6/q1B4R w - - 0 1").unwrap();
+        let board = game.get_board();
+
+        assert!(is_square_attacked(board, 1, 0, Black));
+        assert!(!is_square_attacked(board, 3, 0, Black));
+        assert!(is_square_attacked(board, 0, 7, Black));
+        assert!(!is_square_attacked(board, 2, 2, Black));
+        assert!(is_square_attacked(board, 3, 0, White));
+        assert!(is_square_attacked(board, 2, 0, White));
+        assert!(!is_square_attacked(board, 1, 0, White));
+    }
+
+    #[test]
+    fn king_attacked_by_queen_on_diagonal_is_in_check() {
+        let game = build_game_from_string("8/8/8/8/8/2q5/8/K7 w - - 0 1").unwrap();
+        assert!(is_in_check(White, game.get_board()));
+        assert!(!is_in_check(Black, game.get_board()));
+    }
+
+    #[test]
+    fn promotion_captures_deliver_check() {
+        let mut game = build_game_from_string("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        play_moves(&mut game, &["bxa8=Q+"]);
+        assert!(is_in_check(Black, game.get_board()));
+        assert!(matches!(get_game_state(&mut game), Check { .. }));
+
+        let mut game = build_game_from_string("r7/1Pk5/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        play_moves(&mut game, &["bxa8=N+"]);
+        assert!(is_in_check(Black, game.get_board()));
+
+        // promoting to a rook does not attack the king on the diagonal
+        let mut game = build_game_from_string("r7/1Pk5/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        play_moves(&mut game, &["bxa8=R"]);
+        assert!(!is_in_check(Black, game.get_board()));
+    }
+
+    #[test]
+    fn en_passant_captures_can_discover_check() {
+        let mut game = build_game_from_string("8/8/8/R2pP2k/8/8/8/4K3 w - d6 0 1").unwrap();
+        assert!(!is_in_check(Black, game.get_board()));
+
+        play_moves(&mut game, &["exd6+"]);
+        assert!(game.get_board().get_piece_at_space(3, 4).is_none());
+        assert!(is_in_check(Black, game.get_board()));
+        assert!(matches!(get_game_state(&mut game), Check { .. }));
+    }
+
+    #[test]
+    fn study_mode_positions_are_never_in_check() {
+        let mut game = build_game_from_string("k7/8/8/8/8/2q5/8/K6K w - - 0 1").unwrap();
+        assert!(matches!(get_game_state(&mut game.clone()), Check { .. }));
+
+        game.set_ruleset(crate::ruleset::Ruleset::new().set_study_mode(true));
+        match get_game_state(&mut game) {
+            InProgress { legal_moves, .. } => assert_eq!(6, legal_moves.len()),
+            _ => panic!("Game state is not in progress."),
+        };
+
+        let mut no_kings = build_game_from_string("8/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        no_kings.set_ruleset(crate::ruleset::Ruleset::new().set_study_mode(true));
+        assert_eq!(Stalemate, get_game_state(&mut no_kings));
+    }
 }
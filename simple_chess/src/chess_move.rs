@@ -1,5 +1,7 @@
+use crate::engine::static_exchange::see_capture;
 use crate::piece::ChessPiece;
-use game_board::{get_square_name_from_row_and_col, Board};
+use crate::position::Position;
+use game_board::Board;
 use std::fmt::{Display, Formatter};
 
 /// Represents different types of simple_chess moves.
@@ -185,6 +187,125 @@ impl ChessMoveType {
         }
     }
 
+    /// Returns the square the moving piece starts on, for castling this is the king's square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ChessMoveType;
+    ///
+    /// let castle = ChessMoveType::Castle {
+    ///     rook_original_position: (7, 0),
+    ///     rook_new_position: (5, 0),
+    ///     king_original_position: (4, 0),
+    ///     king_new_position: (6, 0),
+    /// };
+    /// assert_eq!((4, 0), castle.get_original_position());
+    /// assert_eq!((6, 0), castle.get_new_position());
+    /// ```
+    pub fn get_original_position(&self) -> (usize, usize) {
+        match self {
+            ChessMoveType::Move {
+                original_position, ..
+            } => *original_position,
+            ChessMoveType::EnPassant {
+                original_position, ..
+            } => *original_position,
+            ChessMoveType::Castle {
+                king_original_position,
+                ..
+            } => *king_original_position,
+        }
+    }
+
+    /// Returns the square the moving piece ends on, for castling this is the king's square.
+    pub fn get_new_position(&self) -> (usize, usize) {
+        match self {
+            ChessMoveType::Move { new_position, .. } => *new_position,
+            ChessMoveType::EnPassant { new_position, .. } => *new_position,
+            ChessMoveType::Castle {
+                king_new_position, ..
+            } => *king_new_position,
+        }
+    }
+
+    /// Returns the square the moving piece starts on as a `Position`, like
+    /// `get_original_position`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::position::Position;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// let knight_move = game.legal_moves_from(6, 0)[0];
+    /// assert_eq!(Position::new(6, 0), knight_move.get_from());
+    /// assert_eq!("g1", knight_move.get_from().to_string());
+    /// ```
+    pub fn get_from(&self) -> Position {
+        Position::from(self.get_original_position())
+    }
+
+    /// Returns the square the moving piece ends on as a `Position`, like
+    /// `get_new_position`.
+    pub fn get_to(&self) -> Position {
+        Position::from(self.get_new_position())
+    }
+
+    /// Evaluates the material a capture wins or loses with static exchange evaluation (SEE).
+    ///
+    /// The capture is made, then both sides take turns recapturing on the square with their
+    /// least valuable attacker for as long as doing so does not lose material. Unlike
+    /// `see`, the capture itself is forced, so a capture that loses material is negative.
+    /// Promotions are valued by the pieces captured only.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board the move is about to be made on, the board is not modified.
+    ///
+    /// # Returns
+    ///
+    /// * `i32` - The material, in centipawns, the moving side wins, or `0` for moves that
+    ///   do not capture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+    ///
+    /// // the pawn on e6 is defended by the pawn on d7
+    /// let game = build_game_from_string("4k3/3p4/4p3/8/8/8/8/4QK2 w - - 0 1").unwrap();
+    /// let capture = decode_move_from_string(&game, "Qxe6+").unwrap();
+    ///
+    /// assert_eq!(-800, capture.exchange_value(game.get_board()));
+    /// ```
+    pub fn exchange_value(&self, board: &Board<ChessPiece>) -> i32 {
+        match self {
+            ChessMoveType::Move {
+                original_position,
+                new_position,
+                taken_piece: Some(_),
+                ..
+            } => see_capture(board, *original_position, *new_position),
+            ChessMoveType::EnPassant {
+                original_position,
+                new_position,
+                taken_piece,
+                taken_piece_position,
+                ..
+            } => {
+                // the exchange continues on the square the pawn moves to
+                let mut board = board.clone();
+                board.remove_piece(taken_piece_position.0, taken_piece_position.1);
+                board.place_piece(*taken_piece, new_position.0, new_position.1);
+                see_capture(&board, *original_position, *new_position)
+            }
+            _ => 0,
+        }
+    }
+
     fn place_piece(
         board: &mut Board<ChessPiece>,
         new_position: &(usize, usize),
@@ -224,9 +345,9 @@ impl Display for ChessMoveType {
                     f,
                     "{:?} at {} {} at {} {}",
                     piece.get_piece_type(),
-                    get_square_name_from_row_and_col(original_position.0, original_position.1),
+                    Position::from(*original_position),
                     take_string,
-                    get_square_name_from_row_and_col(new_position.0, new_position.1),
+                    Position::from(*new_position),
                     promotion_string
                 )
             }
@@ -0,0 +1,401 @@
+use crate::Color;
+use std::time::Duration;
+
+/// Describes how much time a player has for the game.
+///
+/// A `TimeControl` is made up of the time a player starts with and the
+/// increment that is added to their clock after each move they make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    initial_time: Duration,
+    increment: Duration,
+}
+
+impl TimeControl {
+    /// Creates a new `TimeControl`.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_time` - The time a player starts the game with.
+    /// * `increment` - The time added to a player's clock after each of their moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use simple_chess::clock::TimeControl;
+    ///
+    /// let blitz = TimeControl::new(Duration::from_secs(300), Duration::from_secs(2));
+    /// assert_eq!(Duration::from_secs(300), blitz.get_initial_time());
+    /// ```
+    pub fn new(initial_time: Duration, increment: Duration) -> Self {
+        Self {
+            initial_time,
+            increment,
+        }
+    }
+
+    /// Returns the time a player starts the game with.
+    pub fn get_initial_time(&self) -> Duration {
+        self.initial_time
+    }
+
+    /// Returns the time added to a player's clock after each of their moves.
+    pub fn get_increment(&self) -> Duration {
+        self.increment
+    }
+
+    /// Returns the time control formatted as a PGN `TimeControl` tag value, such as `300+2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use simple_chess::clock::TimeControl;
+    ///
+    /// let blitz = TimeControl::new(Duration::from_secs(300), Duration::from_secs(2));
+    /// assert_eq!("300+2", blitz.as_pgn_string());
+    ///
+    /// let sudden_death = TimeControl::new(Duration::from_secs(60), Duration::ZERO);
+    /// assert_eq!("60", sudden_death.as_pgn_string());
+    /// ```
+    pub fn as_pgn_string(&self) -> String {
+        if self.increment.is_zero() {
+            format!("{}", self.initial_time.as_secs())
+        } else {
+            format!(
+                "{}+{}",
+                self.initial_time.as_secs(),
+                self.increment.as_secs()
+            )
+        }
+    }
+
+    /// Parses a PGN `TimeControl` tag value written by `as_pgn_string`, such as `300+2`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(TimeControl)` - The time control.
+    /// * `None` - If the value is not a whole number of seconds with an optional increment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use simple_chess::clock::TimeControl;
+    ///
+    /// let blitz = TimeControl::new(Duration::from_secs(300), Duration::from_secs(2));
+    /// assert_eq!(Some(blitz), TimeControl::from_pgn_str("300+2"));
+    /// assert_eq!(None, TimeControl::from_pgn_str("40/7200:3600"));
+    /// ```
+    pub fn from_pgn_str(value: &str) -> Option<Self> {
+        let (initial_time, increment) = value.split_once('+').unwrap_or((value, "0"));
+        Some(Self::new(
+            Duration::from_secs(initial_time.trim().parse().ok()?),
+            Duration::from_secs(increment.trim().parse().ok()?),
+        ))
+    }
+}
+
+/// A chess clock tracking the remaining time of both players.
+///
+/// Each color has its own `TimeControl`, which allows for time odds matches where one
+/// player is given less time than the other.
+///
+/// The clock does not measure time itself, the caller reports how long each move took
+/// with `record_move`. This keeps the clock usable with any time source and
+/// deterministic in tests.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use simple_chess::clock::{ChessClock, TimeControl};
+/// use simple_chess::Color::{Black, White};
+///
+/// let mut clock = ChessClock::with_time_odds(
+///     TimeControl::new(Duration::from_secs(300), Duration::ZERO),
+///     TimeControl::new(Duration::from_secs(60), Duration::ZERO),
+/// );
+///
+/// clock.record_move(Duration::from_secs(10));
+/// assert_eq!(Duration::from_secs(290), clock.get_remaining_time(White));
+/// assert_eq!(Black, clock.get_current_turn());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChessClock {
+    white_time_control: TimeControl,
+    black_time_control: TimeControl,
+    white_remaining_time: Duration,
+    black_remaining_time: Duration,
+    current_turn: Color,
+    flagged: Option<Color>,
+}
+
+impl ChessClock {
+    /// Creates a new `ChessClock` where both players use the same time control.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_control` - The `TimeControl` used by both players.
+    pub fn new(time_control: TimeControl) -> Self {
+        Self::with_time_odds(time_control, time_control)
+    }
+
+    /// Creates a new `ChessClock` where each player has their own time control.
+    ///
+    /// # Arguments
+    ///
+    /// * `white_time_control` - The `TimeControl` used by White.
+    /// * `black_time_control` - The `TimeControl` used by Black.
+    pub fn with_time_odds(
+        white_time_control: TimeControl,
+        black_time_control: TimeControl,
+    ) -> Self {
+        Self {
+            white_time_control,
+            black_time_control,
+            white_remaining_time: white_time_control.get_initial_time(),
+            black_remaining_time: black_time_control.get_initial_time(),
+            current_turn: Color::White,
+            flagged: None,
+        }
+    }
+
+    /// Recreates a clock part way through a game, for restoring a saved game.
+    pub(crate) fn restore(
+        white_time_control: TimeControl,
+        black_time_control: TimeControl,
+        white_remaining_time: Duration,
+        black_remaining_time: Duration,
+        current_turn: Color,
+        flagged: Option<Color>,
+    ) -> Self {
+        Self {
+            white_time_control,
+            black_time_control,
+            white_remaining_time,
+            black_remaining_time,
+            current_turn,
+            flagged,
+        }
+    }
+
+    /// Returns the time control used by the given color.
+    pub fn get_time_control(&self, color: Color) -> &TimeControl {
+        match color {
+            Color::White => &self.white_time_control,
+            Color::Black => &self.black_time_control,
+        }
+    }
+
+    /// Returns the time the given color has left on their clock.
+    pub fn get_remaining_time(&self, color: Color) -> Duration {
+        match color {
+            Color::White => self.white_remaining_time,
+            Color::Black => self.black_remaining_time,
+        }
+    }
+
+    /// Returns the color whose clock is currently running.
+    pub fn get_current_turn(&self) -> Color {
+        self.current_turn
+    }
+
+    /// Returns the color that ran out of time, if any.
+    pub fn get_flagged_player(&self) -> Option<Color> {
+        self.flagged
+    }
+
+    /// Returns `true` if the players have different time controls.
+    pub fn is_time_odds(&self) -> bool {
+        self.white_time_control != self.black_time_control
+    }
+
+    /// Records a move made by the player whose clock is running.
+    ///
+    /// The elapsed time is taken off the player's clock, if they still have time left their
+    /// increment is added and the clock switches to the other player. If the player ran out
+    /// of time they are flagged and the clock stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - How long the player took to make their move.
+    ///
+    /// # Returns
+    ///
+    /// * `Duration` - The time the player has left after the move.
+    pub fn record_move(&mut self, elapsed: Duration) -> Duration {
+        if self.flagged.is_some() {
+            return self.get_remaining_time(self.current_turn);
+        }
+
+        let increment = self.get_time_control(self.current_turn).get_increment();
+        let remaining_time = match self.current_turn {
+            Color::White => &mut self.white_remaining_time,
+            Color::Black => &mut self.black_remaining_time,
+        };
+
+        if elapsed >= *remaining_time {
+            *remaining_time = Duration::ZERO;
+            self.flagged = Some(self.current_turn);
+            return Duration::ZERO;
+        }
+
+        *remaining_time = *remaining_time - elapsed + increment;
+        let remaining_time = *remaining_time;
+        self.current_turn = self.current_turn.opposite();
+        remaining_time
+    }
+
+    /// Returns the PGN tag pairs describing the clock.
+    ///
+    /// When both players share a time control a single `TimeControl` tag is emitted,
+    /// otherwise `WhiteTimeControl` and `BlackTimeControl` tags are used. The remaining
+    /// time of each player is emitted as `WhiteClock` and `BlackClock` in `h:mm:ss` format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use simple_chess::clock::{ChessClock, TimeControl};
+    ///
+    /// let clock = ChessClock::with_time_odds(
+    ///     TimeControl::new(Duration::from_secs(300), Duration::from_secs(3)),
+    ///     TimeControl::new(Duration::from_secs(180), Duration::from_secs(2)),
+    /// );
+    ///
+    /// let tags = clock.get_pgn_tags();
+    /// assert!(tags.contains(&("WhiteTimeControl".to_string(), "300+3".to_string())));
+    /// assert!(tags.contains(&("BlackClock".to_string(), "0:03:00".to_string())));
+    /// ```
+    pub fn get_pgn_tags(&self) -> Vec<(String, String)> {
+        let mut tags = Vec::new();
+
+        if self.is_time_odds() {
+            tags.push((
+                String::from("WhiteTimeControl"),
+                self.white_time_control.as_pgn_string(),
+            ));
+            tags.push((
+                String::from("BlackTimeControl"),
+                self.black_time_control.as_pgn_string(),
+            ));
+        } else {
+            tags.push((
+                String::from("TimeControl"),
+                self.white_time_control.as_pgn_string(),
+            ));
+        }
+
+        tags.push((
+            String::from("WhiteClock"),
+            format_clock_time(self.white_remaining_time),
+        ));
+        tags.push((
+            String::from("BlackClock"),
+            format_clock_time(self.black_remaining_time),
+        ));
+
+        tags
+    }
+}
+
+fn format_clock_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color::{Black, White};
+
+    fn minutes(minutes: u64) -> Duration {
+        Duration::from_secs(minutes * 60)
+    }
+
+    #[test]
+    fn players_start_with_their_own_initial_time() {
+        let clock = ChessClock::with_time_odds(
+            TimeControl::new(minutes(5), Duration::ZERO),
+            TimeControl::new(minutes(1), Duration::ZERO),
+        );
+
+        assert!(clock.is_time_odds());
+        assert_eq!(minutes(5), clock.get_remaining_time(White));
+        assert_eq!(minutes(1), clock.get_remaining_time(Black));
+        assert_eq!(White, clock.get_current_turn());
+    }
+
+    #[test]
+    fn each_player_receives_their_own_increment() {
+        let mut clock = ChessClock::with_time_odds(
+            TimeControl::new(minutes(5), Duration::from_secs(5)),
+            TimeControl::new(minutes(5), Duration::ZERO),
+        );
+
+        assert_eq!(
+            Duration::from_secs(295),
+            clock.record_move(Duration::from_secs(10))
+        );
+        assert_eq!(
+            Duration::from_secs(290),
+            clock.record_move(Duration::from_secs(10))
+        );
+        assert_eq!(White, clock.get_current_turn());
+    }
+
+    #[test]
+    fn player_is_flagged_when_time_runs_out() {
+        let mut clock = ChessClock::new(TimeControl::new(minutes(1), Duration::from_secs(1)));
+
+        clock.record_move(Duration::from_secs(1));
+        assert_eq!(Duration::ZERO, clock.record_move(minutes(2)));
+        assert_eq!(Some(Black), clock.get_flagged_player());
+
+        // the clock stops once a player is flagged
+        clock.record_move(Duration::from_secs(1));
+        assert_eq!(Duration::from_secs(60), clock.get_remaining_time(White));
+        assert_eq!(Black, clock.get_current_turn());
+    }
+
+    #[test]
+    fn symmetric_clock_emits_single_time_control_tag() {
+        let clock = ChessClock::new(TimeControl::new(minutes(90), Duration::from_secs(30)));
+
+        assert!(!clock.is_time_odds());
+        assert_eq!(
+            vec![
+                (String::from("TimeControl"), String::from("5400+30")),
+                (String::from("WhiteClock"), String::from("1:30:00")),
+                (String::from("BlackClock"), String::from("1:30:00")),
+            ],
+            clock.get_pgn_tags()
+        );
+    }
+
+    #[test]
+    fn time_odds_clock_emits_tags_per_color() {
+        let mut clock = ChessClock::with_time_odds(
+            TimeControl::new(minutes(10), Duration::ZERO),
+            TimeControl::new(minutes(3), Duration::from_secs(2)),
+        );
+        clock.record_move(Duration::from_secs(75));
+
+        assert_eq!(
+            vec![
+                (String::from("WhiteTimeControl"), String::from("600")),
+                (String::from("BlackTimeControl"), String::from("180+2")),
+                (String::from("WhiteClock"), String::from("0:08:45")),
+                (String::from("BlackClock"), String::from("0:03:00")),
+            ],
+            clock.get_pgn_tags()
+        );
+    }
+}
@@ -1,5 +1,35 @@
-use crate::piece::ChessPiece;
+use crate::adjudication::Adjudication;
+use crate::chess_move::ChessMoveType;
+use crate::clock::{ChessClock, TimeControl};
+use crate::error::ChessError;
+use crate::game_result::Termination;
+use crate::piece::{ChessPiece, PieceType};
+use crate::repetition::RepetitionTable;
+use crate::ruleset::{PawnRules, Ruleset};
+use crate::scoring::Scoring;
+use crate::variant::{Capablanca, Horde, KingOfTheHill, StandardChess, ThreeCheck, Variant};
+use crate::zobrist::get_en_passant_target;
+use crate::{ChessGame, ChessGameBuilder, Color};
 use game_board::Board;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::time::Duration;
+
+/// The bytes every saved game starts with.
+const MAGIC: &[u8; 3] = b"SCG";
+
+/// The version of the saved game format written by `encode_game_as_binary`.
+///
+/// The version is bumped whenever the layout changes, older versions are still read.
+const FORMAT_VERSION: u8 = 5;
+
+const MOVE_TAG: u8 = 0;
+const EN_PASSANT_TAG: u8 = 1;
+const CASTLE_TAG: u8 = 2;
+
+/// The board code of a square holding an archbishop or chancellor, followed by the low four
+/// bits of the piece's code.
+const WIDE_PIECE_CODE: u8 = 0b0001;
 
 /// Encodes a simple_chess board into a binary vector representation.
 ///
@@ -30,33 +60,789 @@ use game_board::Board;
 ///   |White |0    |
 ///   |Black |1    |
 ///
+/// - Archbishops and chancellors, whose codes do not fit in 4 bits, take two codes: `0001`
+///   followed by `000{color}` for an archbishop or `001{color}` for a chancellor.
 pub fn encode_board_as_binary(board: &Board<ChessPiece>) -> Vec<u8> {
-    let mut encoded_board = Vec::new();
+    pack_codes(&get_board_codes(board), false)
+}
 
-    let mut first = true;
-    let mut current: u8 = 0;
+/// Returns the 4 bit codes of the squares of a board, from the top row down.
+fn get_board_codes(board: &Board<ChessPiece>) -> Vec<u8> {
+    let mut codes = Vec::new();
     for row in (0..board.get_height()).rev() {
         for col in 0..board.get_width() {
-            let binary = if let Some(piece) = board.get_piece_at_space(col, row) {
-                piece.as_binary()
-            } else {
-                0b0000
-            };
-
-            current |= binary;
-            if first {
-                current <<= 4;
-            } else {
-                encoded_board.push(current);
-                current = 0;
+            match board
+                .get_piece_at_space(col, row)
+                .map(|piece| piece.as_binary())
+            {
+                Some(code) if code > 0b1111 => codes.extend([WIDE_PIECE_CODE, code & 0b1111]),
+                Some(code) => codes.push(code),
+                None => codes.push(0b0000),
             }
-            first = !first;
         }
     }
+    codes
+}
 
+/// Packs 4 bit codes two to a byte, the first in the high bits. An odd last code is
+/// dropped unless `pad` is set, when it is kept in the high bits of a final byte.
+fn pack_codes(codes: &[u8], pad: bool) -> Vec<u8> {
+    let mut encoded_board = Vec::new();
+    for pair in codes.chunks(2) {
+        match pair {
+            [first, second] => encoded_board.push(first << 4 | second),
+            [last] if pad => encoded_board.push(last << 4),
+            _ => {}
+        }
+    }
     encoded_board
 }
 
+/// Encodes a full game position into a compact binary representation.
+///
+/// The encoding starts with the board as produced by `encode_board_as_binary`, followed by
+/// a byte holding the side to move and castling rights, and a byte holding the en passant
+/// target column. Two positions produce the same bytes only if the same pieces are on the
+/// same squares, the same side is to move and the same castling and en passant captures are
+/// available, which makes the encoding suitable for detecting repeated positions.
+///
+/// # Arguments
+///
+/// * `game` - A reference to the `ChessGame` whose current position will be encoded.
+///
+/// # Returns
+///
+/// A `Vec<u8>` where:
+/// - The leading bytes are the encoded board.
+/// - The next byte has bit 0 set if it is Black's turn, and bits 1 to 4 set for White long,
+///   White short, Black long and Black short castling rights respectively.
+/// - The final byte is `0` if no en passant capture is possible, otherwise the column of the
+///   en passant target square plus one.
+pub fn encode_position_as_binary(game: &ChessGame) -> Vec<u8> {
+    let mut encoded_position = encode_board_as_binary(game.get_board());
+
+    let mut flags: u8 = 0;
+    if game.get_current_players_turn() == Color::Black {
+        flags |= 0b1;
+    }
+    let (wl, ws, bl, bs) = game.get_castling_rights();
+    for (index, right) in [wl, ws, bl, bs].iter().enumerate() {
+        if *right {
+            flags |= 0b10 << index;
+        }
+    }
+    encoded_position.push(flags);
+
+    let en_passant_column = match get_en_passant_target(game) {
+        Some((col, _)) => col as u8 + 1,
+        None => 0,
+    };
+    encoded_position.push(en_passant_column);
+
+    encoded_position
+}
+
+/// Saves a game, its move history and optionally its clock to a compact binary blob.
+///
+/// Unlike PGN or JSON the blob needs no text parsing to read back, which keeps resuming a
+/// game cheap on embedded and mobile devices. It holds everything needed to continue the
+/// game exactly where it was saved, including the positions counted towards repetition
+/// draws.
+///
+/// Numbers are written as LEB128 variable length integers and squares as their index on
+/// the board, so a move takes five or six bytes. The layout is:
+///
+/// - The bytes `SCG` and the format version.
+/// - The board width and height, followed by the board encoded as by
+///   `encode_board_as_binary`, padded to a whole byte.
+/// - A byte holding the side to move and castling rights, laid out as in
+///   `encode_position_as_binary`.
+/// - The turn number, the fifty-move rule counter, the variant name and the checks each
+///   player has given.
+/// - The ruleset, the scoring and how the game ended if it was resigned, agreed drawn or
+///   adjudicated.
+/// - The moves played, the recorded positions and their counts, and the clock if there is
+///   one.
+///
+/// # Arguments
+///
+/// * `game` - The game to save.
+/// * `clock` - The clock the game is played with, if any.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::clock::{ChessClock, TimeControl};
+/// use simple_chess::codec::binary::{decode_game_from_binary, encode_game_as_binary};
+/// use simple_chess::ChessGame;
+/// use std::time::Duration;
+///
+/// let mut clock = ChessClock::new(TimeControl::new(Duration::from_secs(180), Duration::ZERO));
+/// clock.record_move(Duration::from_secs(5));
+///
+/// let bytes = encode_game_as_binary(&ChessGame::new(), Some(&clock));
+/// let (_, resumed_clock) = decode_game_from_binary(&bytes).unwrap();
+/// assert_eq!(Some(clock), resumed_clock);
+/// ```
+pub fn encode_game_as_binary(game: &ChessGame, clock: Option<&ChessClock>) -> Vec<u8> {
+    let board = game.get_board();
+    let width = board.get_width();
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(FORMAT_VERSION);
+
+    write_number(&mut bytes, width as u64);
+    write_number(&mut bytes, board.get_height() as u64);
+    bytes.extend(encode_padded_board(board));
+
+    let position = encode_position_as_binary(game);
+    bytes.push(position[position.len() - 2]);
+    write_number(&mut bytes, game.get_turn_number() as u64);
+    write_number(&mut bytes, game.get_50_move_rule_counter() as u64);
+    write_string(&mut bytes, game.get_variant().get_name());
+    write_number(&mut bytes, game.get_checks_given(Color::White) as u64);
+    write_number(&mut bytes, game.get_checks_given(Color::Black) as u64);
+
+    let ruleset = game.get_ruleset();
+    let pawn_rules = ruleset.get_pawn_rules();
+    bytes.push(ruleset.is_study_mode() as u8 | (pawn_rules.can_double_step() as u8) << 1);
+    write_number(&mut bytes, pawn_rules.get_starting_rank_offset() as u64);
+    write_number(&mut bytes, pawn_rules.get_promotion_rank_offset() as u64);
+    write_number(&mut bytes, ruleset.get_castling_distance() as u64);
+    bytes.push(match game.get_scoring() {
+        Scoring::Standard => 0,
+        Scoring::Armageddon {
+            draw_winner: Color::White,
+        } => 1,
+        Scoring::Armageddon {
+            draw_winner: Color::Black,
+        } => 2,
+    });
+    encode_ending(&mut bytes, game.get_ending());
+
+    write_number(&mut bytes, game.get_moves().len() as u64);
+    for chess_move in game.get_moves() {
+        encode_move(&mut bytes, chess_move, width);
+    }
+    match game.get_en_passant_target() {
+        Some((column, row)) => {
+            bytes.push(1);
+            write_number(&mut bytes, column as u64);
+            write_number(&mut bytes, row as u64);
+        }
+        None => bytes.push(0),
+    }
+
+    let entries = game.get_repetition_table().get_entries();
+    write_number(&mut bytes, entries.len() as u64);
+    for (hash, encoded_position, count) in entries {
+        bytes.extend(hash.to_le_bytes());
+        write_number(&mut bytes, encoded_position.len() as u64);
+        bytes.extend(encoded_position);
+        write_number(&mut bytes, count as u64);
+    }
+
+    match clock {
+        Some(clock) => {
+            bytes.push(1);
+            encode_clock(&mut bytes, clock);
+        }
+        None => bytes.push(0),
+    }
+
+    bytes
+}
+
+/// Resumes a game saved with `encode_game_as_binary`.
+///
+/// # Arguments
+///
+/// * `bytes` - The saved game.
+///
+/// # Returns
+///
+/// * `Ok((ChessGame, Option<ChessClock>))` - The saved game and its clock, if one was saved.
+/// * `Err(BinaryDecodeError)` - If the bytes are not a saved game, were saved by a newer
+///   version of the format or are damaged.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::binary::decode_game_from_binary;
+///
+/// assert!(decode_game_from_binary(b"not a saved game").is_err());
+/// ```
+pub fn decode_game_from_binary(
+    bytes: &[u8],
+) -> Result<(ChessGame, Option<ChessClock>), BinaryDecodeError> {
+    let mut reader = ByteReader::new(bytes);
+    if reader.read_bytes(MAGIC.len())? != MAGIC {
+        return Err(BinaryDecodeError::new(String::from(
+            "the bytes are not a saved game",
+        )));
+    }
+    let version = reader.read_byte()?;
+    if version > FORMAT_VERSION {
+        return Err(BinaryDecodeError::new(format!(
+            "format version {version} is newer than the supported version {FORMAT_VERSION}"
+        )));
+    }
+
+    let width = reader.read_usize()?;
+    let height = reader.read_usize()?;
+    // the squares are read before the board is built, so a damaged size can not allocate
+    // more memory than the saved game holds
+    let square_count = width.saturating_mul(height);
+    if reader.get_remaining() < square_count.div_ceil(2) {
+        return Err(BinaryDecodeError::new(String::from(
+            "the saved game ends unexpectedly",
+        )));
+    }
+    let mut board =
+        Board::try_build(width, height).map_err(|e| BinaryDecodeError::new(e.to_string()))?;
+    let mut codes = CodeReader::new();
+    for index in 0..square_count {
+        let code = match codes.read_code(&mut reader)? {
+            WIDE_PIECE_CODE => 0b1_0000 | codes.read_code(&mut reader)?,
+            code => code,
+        };
+        if let Some(piece) = decode_piece(code)? {
+            board.place_piece(piece, index % width, height - 1 - index / width);
+        }
+    }
+
+    let flags = reader.read_byte()?;
+    let current_turn = if flags & 0b1 == 0 {
+        Color::White
+    } else {
+        Color::Black
+    };
+    let [white_long, white_short, black_long, black_short] =
+        [0, 1, 2, 3].map(|index| flags & (0b10 << index) != 0);
+    let turn_number = reader.read_usize()?;
+    let fifty_move_rule_counter = reader.read_usize()?;
+    let variant_name = reader.read_string()?;
+    let checks_given = (reader.read_usize()?, reader.read_usize()?);
+
+    let ruleset_flags = reader.read_byte()?;
+    let pawn_rules = PawnRules::new()
+        .set_can_double_step(ruleset_flags & 0b10 != 0)
+        .set_starting_rank_offset(reader.read_usize()?)
+        .set_promotion_rank_offset(reader.read_usize()?);
+    let mut ruleset = Ruleset::new()
+        .set_pawn_rules(pawn_rules)
+        .set_study_mode(ruleset_flags & 0b1 != 0);
+    // versions before the third only saved games where the king castles two squares
+    if version >= 3 {
+        ruleset = ruleset.set_castling_distance(reader.read_usize()?);
+    }
+    // versions before the fourth only saved games with standard scoring
+    let scoring = match version {
+        ..=3 => Scoring::Standard,
+        _ => match reader.read_byte()? {
+            0 => Scoring::Standard,
+            1 => Scoring::Armageddon {
+                draw_winner: Color::White,
+            },
+            2 => Scoring::Armageddon {
+                draw_winner: Color::Black,
+            },
+            code => {
+                return Err(BinaryDecodeError::new(format!(
+                    "invalid scoring code {code}"
+                )))
+            }
+        },
+    };
+    // versions before the fifth did not save how resigned and agreed games ended
+    let ending = match version {
+        ..=4 => None,
+        _ => decode_ending(&mut reader)?,
+    };
+
+    let move_count = reader.read_usize()?;
+    let mut moves = Vec::new();
+    for _ in 0..move_count {
+        moves.push(decode_move(&mut reader, width, height)?);
+    }
+    // the first version derived the en passant target from the last move
+    let en_passant_target = match version {
+        1 => None,
+        _ => match reader.read_byte()? {
+            0 => None,
+            1 => Some((reader.read_usize()?, reader.read_usize()?)),
+            flag => {
+                return Err(BinaryDecodeError::new(format!(
+                    "invalid en passant flag {flag}"
+                )))
+            }
+        },
+    };
+
+    let mut repetition_table = RepetitionTable::new();
+    for _ in 0..reader.read_usize()? {
+        let hash = u64::from_le_bytes(
+            reader
+                .read_bytes(8)?
+                .try_into()
+                .expect("eight bytes were read"),
+        );
+        let length = reader.read_usize()?;
+        let encoded_position = reader.read_bytes(length)?.to_vec();
+        repetition_table.set_count(hash, encoded_position, reader.read_usize()?);
+    }
+
+    let clock = match reader.read_byte()? {
+        0 => None,
+        1 => Some(decode_clock(&mut reader)?),
+        flag => return Err(BinaryDecodeError::new(format!("invalid clock flag {flag}"))),
+    };
+    if !reader.is_finished() {
+        return Err(BinaryDecodeError::new(String::from(
+            "unexpected bytes after the end of the saved game",
+        )));
+    }
+
+    let mut builder = ChessGameBuilder::new()
+        .set_board(board)
+        .set_current_turn(current_turn)
+        .set_turn_number(turn_number)
+        .set_fifty_move_rule_counter(fifty_move_rule_counter)
+        .set_castle_rights(white_short, white_long, black_short, black_long)
+        .set_moves(moves)
+        .set_ruleset(ruleset)
+        .set_scoring(scoring)
+        .set_checks_given(checks_given.0, checks_given.1);
+    if let Some(target) = en_passant_target {
+        builder = builder.set_en_passant_target(target);
+    }
+    let mut game = set_variant_by_name(builder, &variant_name)?
+        .build()
+        .map_err(|e| BinaryDecodeError::new(e.to_string()))?;
+    game.set_repetition_table(repetition_table);
+    game.set_ending(ending);
+
+    Ok((game, clock))
+}
+
+/// Encodes the board like `encode_board_as_binary`, keeping an odd last code in the high
+/// bits of a final byte.
+fn encode_padded_board(board: &Board<ChessPiece>) -> Vec<u8> {
+    pack_codes(&get_board_codes(board), true)
+}
+
+pub(crate) fn encode_move(bytes: &mut Vec<u8>, chess_move: &ChessMoveType, width: usize) {
+    let square = |bytes: &mut Vec<u8>, (col, row): (usize, usize)| {
+        write_number(bytes, (row * width + col) as u64)
+    };
+    let code = |piece: Option<&ChessPiece>| piece.map(|piece| piece.as_binary()).unwrap_or(0);
+    // the moving and taken pieces share a byte, unless one of them has a code wider than 4
+    // bits, when a zero byte is followed by a byte for each
+    let pieces = |bytes: &mut Vec<u8>, piece: &ChessPiece, taken_piece: Option<&ChessPiece>| {
+        let (piece, taken_piece) = (code(Some(piece)), code(taken_piece));
+        if piece > 0b1111 || taken_piece > 0b1111 {
+            bytes.extend([0, piece, taken_piece]);
+        } else {
+            bytes.push(piece << 4 | taken_piece);
+        }
+    };
+
+    match chess_move {
+        ChessMoveType::Move {
+            original_position,
+            new_position,
+            piece,
+            taken_piece,
+            promotion,
+        } => {
+            bytes.push(MOVE_TAG);
+            square(bytes, *original_position);
+            square(bytes, *new_position);
+            pieces(bytes, piece, taken_piece.as_ref());
+            bytes.push(code(promotion.as_ref()));
+        }
+        ChessMoveType::EnPassant {
+            original_position,
+            new_position,
+            piece,
+            taken_piece,
+            taken_piece_position,
+            promotion,
+        } => {
+            bytes.push(EN_PASSANT_TAG);
+            square(bytes, *original_position);
+            square(bytes, *new_position);
+            square(bytes, *taken_piece_position);
+            pieces(bytes, piece, Some(taken_piece));
+            bytes.push(code(promotion.as_ref()));
+        }
+        ChessMoveType::Castle {
+            rook_original_position,
+            rook_new_position,
+            king_original_position,
+            king_new_position,
+        } => {
+            bytes.push(CASTLE_TAG);
+            square(bytes, *rook_original_position);
+            square(bytes, *rook_new_position);
+            square(bytes, *king_original_position);
+            square(bytes, *king_new_position);
+        }
+    }
+}
+
+pub(crate) fn decode_move(
+    reader: &mut ByteReader,
+    width: usize,
+    height: usize,
+) -> Result<ChessMoveType, BinaryDecodeError> {
+    let square = |reader: &mut ByteReader| {
+        let index = reader.read_usize()?;
+        if index >= width * height {
+            return Err(BinaryDecodeError::new(format!(
+                "square {index} is outside the board"
+            )));
+        }
+        Ok((index % width, index / width))
+    };
+    let required = |piece: Option<ChessPiece>| {
+        piece.ok_or_else(|| BinaryDecodeError::new(String::from("a move is missing its piece")))
+    };
+    let pieces = |reader: &mut ByteReader| -> Result<(u8, u8), BinaryDecodeError> {
+        match reader.read_byte()? {
+            0 => Ok((reader.read_byte()?, reader.read_byte()?)),
+            pieces => Ok((pieces >> 4, pieces & 0b1111)),
+        }
+    };
+
+    match reader.read_byte()? {
+        MOVE_TAG => {
+            let original_position = square(reader)?;
+            let new_position = square(reader)?;
+            let (piece, taken_piece) = pieces(reader)?;
+            Ok(ChessMoveType::Move {
+                original_position,
+                new_position,
+                piece: required(decode_piece(piece)?)?,
+                taken_piece: decode_piece(taken_piece)?,
+                promotion: decode_piece(reader.read_byte()?)?,
+            })
+        }
+        EN_PASSANT_TAG => {
+            let original_position = square(reader)?;
+            let new_position = square(reader)?;
+            let taken_piece_position = square(reader)?;
+            let (piece, taken_piece) = pieces(reader)?;
+            Ok(ChessMoveType::EnPassant {
+                original_position,
+                new_position,
+                piece: required(decode_piece(piece)?)?,
+                taken_piece: required(decode_piece(taken_piece)?)?,
+                taken_piece_position,
+                promotion: decode_piece(reader.read_byte()?)?,
+            })
+        }
+        CASTLE_TAG => Ok(ChessMoveType::Castle {
+            rook_original_position: square(reader)?,
+            rook_new_position: square(reader)?,
+            king_original_position: square(reader)?,
+            king_new_position: square(reader)?,
+        }),
+        tag => Err(BinaryDecodeError::new(format!("invalid move tag {tag}"))),
+    }
+}
+
+/// Turns a piece code from `ChessPiece::as_binary` back into a piece, `0` being an empty
+/// square.
+fn decode_piece(code: u8) -> Result<Option<ChessPiece>, BinaryDecodeError> {
+    let color = if code & 0b1 == 0 {
+        Color::White
+    } else {
+        Color::Black
+    };
+    let piece_type = match code >> 1 {
+        0 if code == 0 => return Ok(None),
+        1 => PieceType::Pawn,
+        2 => PieceType::Rook,
+        3 => PieceType::Knight,
+        4 => PieceType::Bishop,
+        5 => PieceType::King,
+        6 => PieceType::Queen,
+        8 => PieceType::Archbishop,
+        9 => PieceType::Chancellor,
+        _ => {
+            return Err(BinaryDecodeError::new(format!(
+                "invalid piece code {code:#06b}"
+            )))
+        }
+    };
+    Ok(Some(ChessPiece::new(piece_type, color)))
+}
+
+/// The terminations of games ended early, in the order of their codes.
+const TERMINATIONS: [Termination; 9] = [
+    Termination::Checkmate,
+    Termination::Stalemate,
+    Termination::VariantWin,
+    Termination::Resignation,
+    Termination::DrawAgreement,
+    Termination::Adjudication,
+    Termination::DeadPosition,
+    Termination::SeventyFiveMoveRule,
+    Termination::TimeForfeit,
+];
+
+fn encode_ending(bytes: &mut Vec<u8>, ending: Option<(Adjudication, Termination)>) {
+    let Some((adjudication, termination)) = ending else {
+        bytes.push(0);
+        return;
+    };
+    bytes.push(match adjudication {
+        Adjudication::Resignation {
+            winner: Color::White,
+        } => 1,
+        Adjudication::Resignation {
+            winner: Color::Black,
+        } => 2,
+        Adjudication::Draw => 3,
+    });
+    let code = TERMINATIONS
+        .iter()
+        .position(|known| *known == termination)
+        .expect("every termination has a code");
+    bytes.push(code as u8);
+}
+
+fn decode_ending(
+    reader: &mut ByteReader,
+) -> Result<Option<(Adjudication, Termination)>, BinaryDecodeError> {
+    let adjudication = match reader.read_byte()? {
+        0 => return Ok(None),
+        1 => Adjudication::Resignation {
+            winner: Color::White,
+        },
+        2 => Adjudication::Resignation {
+            winner: Color::Black,
+        },
+        3 => Adjudication::Draw,
+        code => {
+            return Err(BinaryDecodeError::new(format!(
+                "invalid ending code {code}"
+            )))
+        }
+    };
+    let code = reader.read_byte()?;
+    let termination = *TERMINATIONS
+        .get(code as usize)
+        .ok_or_else(|| BinaryDecodeError::new(format!("invalid termination code {code}")))?;
+    Ok(Some((adjudication, termination)))
+}
+
+fn encode_clock(bytes: &mut Vec<u8>, clock: &ChessClock) {
+    for color in [Color::White, Color::Black] {
+        let time_control = clock.get_time_control(color);
+        write_number(bytes, time_control.get_initial_time().as_millis() as u64);
+        write_number(bytes, time_control.get_increment().as_millis() as u64);
+        write_number(bytes, clock.get_remaining_time(color).as_millis() as u64);
+    }
+
+    let mut flags = (clock.get_current_turn() == Color::Black) as u8;
+    match clock.get_flagged_player() {
+        Some(Color::White) => flags |= 0b10,
+        Some(Color::Black) => flags |= 0b100,
+        None => {}
+    }
+    bytes.push(flags);
+}
+
+fn decode_clock(reader: &mut ByteReader) -> Result<ChessClock, BinaryDecodeError> {
+    let mut read_player = || -> Result<(TimeControl, Duration), BinaryDecodeError> {
+        let initial_time = Duration::from_millis(reader.read_number()?);
+        let increment = Duration::from_millis(reader.read_number()?);
+        let remaining_time = Duration::from_millis(reader.read_number()?);
+        Ok((TimeControl::new(initial_time, increment), remaining_time))
+    };
+    let (white_time_control, white_remaining_time) = read_player()?;
+    let (black_time_control, black_remaining_time) = read_player()?;
+
+    let flags = reader.read_byte()?;
+    let current_turn = if flags & 0b1 == 0 {
+        Color::White
+    } else {
+        Color::Black
+    };
+    let flagged = match flags >> 1 {
+        0 => None,
+        0b01 => Some(Color::White),
+        0b10 => Some(Color::Black),
+        _ => {
+            return Err(BinaryDecodeError::new(String::from(
+                "both players can not be flagged",
+            )))
+        }
+    };
+
+    Ok(ChessClock::restore(
+        white_time_control,
+        black_time_control,
+        white_remaining_time,
+        black_remaining_time,
+        current_turn,
+        flagged,
+    ))
+}
+
+fn set_variant_by_name(
+    builder: ChessGameBuilder,
+    name: &str,
+) -> Result<ChessGameBuilder, BinaryDecodeError> {
+    let variants: [&dyn Variant; 5] = [
+        &StandardChess,
+        &KingOfTheHill,
+        &Horde,
+        &ThreeCheck,
+        &Capablanca,
+    ];
+    match variants
+        .iter()
+        .position(|variant| variant.get_name() == name)
+    {
+        Some(0) => Ok(builder.set_variant(StandardChess)),
+        Some(1) => Ok(builder.set_variant(KingOfTheHill)),
+        Some(2) => Ok(builder.set_variant(Horde)),
+        Some(3) => Ok(builder.set_variant(ThreeCheck)),
+        Some(4) => Ok(builder.set_variant(Capablanca)),
+        _ => Err(BinaryDecodeError::new(format!("unknown variant '{name}'"))),
+    }
+}
+
+pub(crate) fn write_number(bytes: &mut Vec<u8>, mut number: u64) {
+    while number >= 0x80 {
+        bytes.push((number as u8 & 0x7f) | 0x80);
+        number >>= 7;
+    }
+    bytes.push(number as u8);
+}
+
+fn write_string(bytes: &mut Vec<u8>, string: &str) {
+    write_number(bytes, string.len() as u64);
+    bytes.extend(string.as_bytes());
+}
+
+/// Reads the fields of a saved game in order, failing if the bytes run out.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub(crate) fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], BinaryDecodeError> {
+        let end = self.position.saturating_add(length);
+        if end > self.bytes.len() {
+            return Err(BinaryDecodeError::new(String::from(
+                "the saved game ends unexpectedly",
+            )));
+        }
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_byte(&mut self) -> Result<u8, BinaryDecodeError> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    pub(crate) fn read_number(&mut self) -> Result<u64, BinaryDecodeError> {
+        let mut number = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            number |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(number);
+            }
+        }
+        Err(BinaryDecodeError::new(String::from(
+            "a number is too large",
+        )))
+    }
+
+    pub(crate) fn read_usize(&mut self) -> Result<usize, BinaryDecodeError> {
+        let number = self.read_number()?;
+        usize::try_from(number)
+            .map_err(|_| BinaryDecodeError::new(format!("{number} is too large")))
+    }
+
+    fn read_string(&mut self) -> Result<String, BinaryDecodeError> {
+        let length = self.read_usize()?;
+        String::from_utf8(self.read_bytes(length)?.to_vec())
+            .map_err(|_| BinaryDecodeError::new(String::from("a name is not valid UTF-8")))
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    fn get_remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+}
+
+/// Reads the 4 bit codes of a board, two from each byte.
+struct CodeReader {
+    low_bits: Option<u8>,
+}
+
+impl CodeReader {
+    fn new() -> Self {
+        Self { low_bits: None }
+    }
+
+    fn read_code(&mut self, reader: &mut ByteReader) -> Result<u8, BinaryDecodeError> {
+        match self.low_bits.take() {
+            Some(code) => Ok(code),
+            None => {
+                let byte = reader.read_byte()?;
+                self.low_bits = Some(byte & 0b1111);
+                Ok(byte >> 4)
+            }
+        }
+    }
+}
+
+/// Error returned when a saved game can not be decoded.
+pub struct BinaryDecodeError {
+    reason: String,
+}
+
+impl BinaryDecodeError {
+    pub(crate) fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for BinaryDecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Binary Decode Error: {}", self.reason)
+    }
+}
+
+impl Debug for BinaryDecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BinaryDecodeError: {}", self.reason)
+    }
+}
+
+impl Error for BinaryDecodeError {}
+
+impl From<BinaryDecodeError> for ChessError {
+    fn from(error: BinaryDecodeError) -> Self {
+        ChessError::ParseError(error.reason)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -76,14 +862,14 @@ mod tests {
             assert_eq!(0b10011101, encoded[1]);
             assert_eq!(0b10111001, encoded[2]);
             assert_eq!(0b01110101, encoded[3]);
-            for i in 4..8 {
-                assert_eq!(0b00110011, encoded[i]);
+            for byte in &encoded[4..8] {
+                assert_eq!(0b00110011, *byte);
             }
-            for i in 8..24 {
-                assert_eq!(0b00000000, encoded[i]);
+            for byte in &encoded[8..24] {
+                assert_eq!(0b00000000, *byte);
             }
-            for i in 24..28 {
-                assert_eq!(0b00100010, encoded[i]);
+            for byte in &encoded[24..28] {
+                assert_eq!(0b00100010, *byte);
             }
             assert_eq!(0b01000110, encoded[28]);
             assert_eq!(0b10001100, encoded[29]);
@@ -102,4 +888,183 @@ mod tests {
             assert_eq!(0b00001011, encoded[0]);
         }
     }
+
+    mod position_encoding_tests {
+        use super::super::*;
+        use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+        #[test]
+        fn encode_starting_position_with_rights() {
+            let game = ChessGame::new();
+            let encoded = encode_position_as_binary(&game);
+
+            assert_eq!(34, encoded.len());
+            assert_eq!(encode_board_as_binary(game.get_board()), encoded[..32]);
+            assert_eq!(0b00011110, encoded[32]);
+            assert_eq!(0, encoded[33]);
+        }
+
+        #[test]
+        fn encode_side_to_move_and_en_passant() {
+            let game = build_game_from_string("4k3/8/8/8/3pP3/8/8/4K3 b Qk e3 0 1").unwrap();
+            let encoded = encode_position_as_binary(&game);
+
+            assert_eq!(0b00010011, encoded[32]);
+            assert_eq!(5, encoded[33]);
+        }
+    }
+
+    mod saved_game_tests {
+        use super::super::*;
+        use crate::chess_game_state_analyzer::GameState;
+        use crate::codec::forsyth_edwards_notation::{
+            build_game_from_string, encode_game_as_string,
+        };
+        use crate::codec::standard_algebraic_notation::play_moves;
+
+        #[test]
+        fn games_resume_where_they_were_saved() {
+            let mut game =
+                build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1 +1+0").unwrap();
+            play_moves(
+                &mut game,
+                &["O-O", "O-O-O", "Ra7", "Rd7", "Rfa1", "Rh2", "Ra8+"],
+            );
+
+            let mut resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(
+                encode_game_as_string(&game),
+                encode_game_as_string(&resumed)
+            );
+            assert_eq!(game.get_moves(), resumed.get_moves());
+            assert_eq!("Three-check", resumed.get_variant().get_name());
+            assert_eq!(
+                game.get_checks_given(Color::White),
+                resumed.get_checks_given(Color::White)
+            );
+            assert_eq!(game.get_game_state(), resumed.get_game_state());
+        }
+
+        #[test]
+        fn capablanca_games_survive_saving() {
+            let mut game = crate::setup::BoardSetup::capablanca().build_game().unwrap();
+            play_moves(
+                &mut game,
+                &["e4", "e5", "d4", "d5", "c4", "c5", "f4", "f5", "g4"],
+            );
+
+            let mut resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(
+                encode_game_as_string(&game),
+                encode_game_as_string(&resumed)
+            );
+            assert_eq!(game.get_moves(), resumed.get_moves());
+            assert_eq!("Capablanca", resumed.get_variant().get_name());
+            assert_eq!(3, resumed.get_ruleset().get_castling_distance());
+            assert_eq!(game.get_game_state(), resumed.get_game_state());
+        }
+
+        #[test]
+        fn scoring_survives_saving() {
+            let mut game = ChessGame::new();
+            let armageddon = Scoring::Armageddon {
+                draw_winner: Color::Black,
+            };
+            game.set_scoring(armageddon);
+
+            let resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(armageddon, resumed.get_scoring());
+        }
+
+        #[test]
+        fn resigned_games_stay_over() {
+            let mut game = ChessGame::new();
+            game.resign(Color::White).unwrap();
+
+            let mut resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(
+                GameState::Resigned {
+                    winner: Color::Black
+                },
+                resumed.get_game_state()
+            );
+            let chess_move = resumed.legal_moves()[0];
+            assert_eq!(Err(ChessError::GameOver), resumed.try_make_move(chess_move));
+            assert_eq!(
+                Termination::Resignation,
+                resumed.get_result().unwrap().get_termination()
+            );
+        }
+
+        #[test]
+        fn en_passant_targets_survive_saving() {
+            let game = build_game_from_string("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+
+            let resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(Some((4, 2)), resumed.get_en_passant_target());
+            assert_eq!(
+                encode_game_as_string(&game),
+                encode_game_as_string(&resumed)
+            );
+        }
+
+        #[test]
+        fn repetitions_survive_saving() {
+            let mut game = ChessGame::new();
+            let knight_moves = [
+                ((6, 0), (5, 2)),
+                ((6, 7), (5, 5)),
+                ((5, 2), (6, 0)),
+                ((5, 5), (6, 7)),
+            ];
+            for (from, to) in knight_moves {
+                let piece = *game.get_board().get_piece_at_space(from.0, from.1).unwrap();
+                game.make_move(ChessMoveType::Move {
+                    original_position: from,
+                    new_position: to,
+                    piece,
+                    taken_piece: None,
+                    promotion: None,
+                });
+            }
+            assert_eq!(2, game.get_repetition_count());
+
+            let resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(2, resumed.get_repetition_count());
+            assert_eq!(game.to_bytes(), resumed.to_bytes());
+        }
+
+        #[test]
+        fn boards_with_an_odd_number_of_squares_keep_their_last_square() {
+            let mut board = Board::build(3, 3).unwrap();
+            board.place_piece(ChessPiece::new(PieceType::King, Color::White), 2, 0);
+            board.place_piece(ChessPiece::new(PieceType::King, Color::Black), 0, 2);
+            let game = ChessGameBuilder::new()
+                .set_board(board)
+                .set_current_turn(Color::White)
+                .build()
+                .unwrap();
+
+            let resumed = ChessGame::from_bytes(&game.to_bytes()).unwrap();
+            assert_eq!(
+                Some(&ChessPiece::new(PieceType::King, Color::White)),
+                resumed.get_board().get_piece_at_space(2, 0)
+            );
+        }
+
+        #[test]
+        fn damaged_or_newer_saves_are_rejected() {
+            let bytes = ChessGame::new().to_bytes();
+
+            assert!(ChessGame::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+            let mut newer = bytes.clone();
+            newer[3] = FORMAT_VERSION + 1;
+            assert!(ChessGame::from_bytes(&newer).is_err());
+
+            let mut trailing = bytes;
+            trailing.push(0);
+            assert!(ChessGame::from_bytes(&trailing).is_err());
+        }
+    }
 }
@@ -0,0 +1,313 @@
+use crate::chess_game::ChessGame;
+use crate::codec::forsyth_edwards_notation;
+use crate::codec::standard_algebraic_notation::SanParser;
+use crate::error::ChessError;
+use crate::ChessMoveType;
+
+/// An operation of an EPD record, an opcode such as `bm` with its operands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdOperation {
+    opcode: String,
+    operands: Vec<String>,
+}
+
+impl EpdOperation {
+    /// Creates an operation.
+    pub fn new(opcode: &str, operands: &[&str]) -> Self {
+        Self {
+            opcode: opcode.to_string(),
+            operands: operands.iter().map(|operand| operand.to_string()).collect(),
+        }
+    }
+
+    /// Returns the opcode, such as `bm` or `id`.
+    pub fn get_opcode(&self) -> &str {
+        &self.opcode
+    }
+
+    /// Returns the operands, without the quotes of string operands.
+    pub fn get_operands(&self) -> &[String] {
+        &self.operands
+    }
+}
+
+/// A position in EPD (Extended Position Description) with its operations.
+///
+/// The position is the first four fields of FEN, the move counters are given by the
+/// optional `hmvc` and `fmvn` operations. Common operations are `bm` for the best moves
+/// of the position, `am` for moves to avoid, and `id` naming the position.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::extended_position_description::{decode_record_from_string, EpdRecord};
+/// use simple_chess::ChessGame;
+///
+/// let record = decode_record_from_string(r#"4k3/8/8/8/8/8/8/R3K3 w Q - bm Ra8+; id "mate.001";"#).unwrap();
+/// assert_eq!(Some("mate.001"), record.get_id());
+/// assert_eq!(Some(&[String::from("Ra8+")][..]), record.get_operands("bm"));
+///
+/// let record = EpdRecord::from_game(&ChessGame::new()).set_operation("id", &["start"]);
+/// assert_eq!(
+///     r#"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - id "start";"#,
+///     record.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdRecord {
+    position: String,
+    operations: Vec<EpdOperation>,
+}
+
+impl EpdRecord {
+    /// Creates a record of the current position of a game, without operations.
+    pub fn from_game(game: &ChessGame) -> Self {
+        let fen = forsyth_edwards_notation::encode_game_as_string(game);
+        Self {
+            position: get_position_fields(&fen).join(" "),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns the position as the first four fields of FEN.
+    pub fn get_position(&self) -> &str {
+        &self.position
+    }
+
+    /// Returns the operations in the order they are written.
+    pub fn get_operations(&self) -> &[EpdOperation] {
+        &self.operations
+    }
+
+    /// Returns the operands of the first operation with an opcode.
+    pub fn get_operands(&self, opcode: &str) -> Option<&[String]> {
+        self.operations
+            .iter()
+            .find(|operation| operation.opcode == opcode)
+            .map(|operation| &operation.operands[..])
+    }
+
+    /// Returns the name of the position given by the `id` operation.
+    pub fn get_id(&self) -> Option<&str> {
+        self.get_operands("id")
+            .and_then(|operands| operands.first())
+            .map(String::as_str)
+    }
+
+    /// Sets an operation, replacing any operation with the same opcode.
+    ///
+    /// # Arguments
+    ///
+    /// * `opcode` - The opcode, such as `bm`.
+    /// * `operands` - The operands, such as the best moves in SAN.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `EpdRecord` with the operation set.
+    pub fn set_operation(mut self, opcode: &str, operands: &[&str]) -> Self {
+        let operation = EpdOperation::new(opcode, operands);
+        match self.operations.iter_mut().find(|o| o.opcode == opcode) {
+            Some(existing) => *existing = operation,
+            None => self.operations.push(operation),
+        }
+        self
+    }
+
+    /// Builds a game from the position, with the move counters of the `hmvc` and `fmvn`
+    /// operations when they are given.
+    pub fn build_game(&self) -> Result<ChessGame, ChessError> {
+        let counter = |opcode, default| {
+            self.get_operands(opcode)
+                .and_then(|operands| operands.first())
+                .map_or(default, String::as_str)
+        };
+        let fen = format!(
+            "{} {} {}",
+            self.position,
+            counter("hmvc", "0"),
+            counter("fmvn", "1")
+        );
+        forsyth_edwards_notation::build_game_from_string(&fen)
+    }
+
+    /// Returns the moves of the `bm` operation, empty without one.
+    pub fn get_best_moves(&self) -> Result<Vec<ChessMoveType>, ChessError> {
+        self.decode_moves("bm")
+    }
+
+    /// Returns the moves of the `am` operation, empty without one.
+    pub fn get_avoid_moves(&self) -> Result<Vec<ChessMoveType>, ChessError> {
+        self.decode_moves("am")
+    }
+
+    fn decode_moves(&self, opcode: &str) -> Result<Vec<ChessMoveType>, ChessError> {
+        let Some(operands) = self.get_operands(opcode) else {
+            return Ok(Vec::new());
+        };
+        let game = self.build_game()?;
+        let parser = SanParser::new().set_lenient(true);
+        operands
+            .iter()
+            .map(|san| parser.parse(&game, san))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for EpdRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", encode_record_as_string(self))
+    }
+}
+
+/// Parses a line of EPD.
+///
+/// # Arguments
+///
+/// * `epd` - The line, four position fields followed by operations ending in `;`.
+///
+/// # Returns
+///
+/// * `Ok(EpdRecord)` - The record.
+/// * `Err(ChessError::InvalidFen)` - If the position is not valid.
+/// * `Err(ChessError::ParseError)` - If a string operand is not closed.
+pub fn decode_record_from_string(epd: &str) -> Result<EpdRecord, ChessError> {
+    let epd = epd.trim();
+    let mut fields = epd.splitn(5, char::is_whitespace);
+    let position: Vec<&str> = fields.by_ref().take(4).collect();
+    if position.len() < 4 {
+        return Err(ChessError::InvalidFen(format!(
+            "'{epd}' does not have the four fields of a position"
+        )));
+    }
+    let record = EpdRecord {
+        position: position.join(" "),
+        operations: decode_operations(fields.next().unwrap_or_default())?,
+    };
+    record.build_game()?;
+    Ok(record)
+}
+
+/// Writes a record as a line of EPD.
+///
+/// Operands of `id` and the comment opcodes `c0` to `c9` are quoted, as are operands
+/// holding spaces or semicolons.
+pub fn encode_record_as_string(record: &EpdRecord) -> String {
+    let mut epd = record.position.clone();
+    for operation in &record.operations {
+        epd.push(' ');
+        epd.push_str(&operation.opcode);
+        let is_string = operation.opcode == "id"
+            || (operation.opcode.len() == 2
+                && operation.opcode.starts_with('c')
+                && operation.opcode.ends_with(|c: char| c.is_ascii_digit()));
+        for operand in &operation.operands {
+            epd.push(' ');
+            if is_string || operand.contains([' ', ';', '"']) || operand.is_empty() {
+                epd.push_str(&format!("\"{}\"", operand.replace('"', "'")));
+            } else {
+                epd.push_str(operand);
+            }
+        }
+        epd.push(';');
+    }
+    epd
+}
+
+fn get_position_fields(fen: &str) -> Vec<&str> {
+    fen.split_whitespace().take(4).collect()
+}
+
+fn decode_operations(text: &str) -> Result<Vec<EpdOperation>, ChessError> {
+    let mut operations = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            None => break,
+            Some(';') => {
+                if let Some((opcode, operands)) = tokens.split_first() {
+                    operations.push(EpdOperation {
+                        opcode: opcode.clone(),
+                        operands: operands.to_vec(),
+                    });
+                }
+                tokens.clear();
+            }
+            Some('"') => {
+                let string: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                tokens.push(string);
+            }
+            Some(c) => {
+                let mut token = c.to_string();
+                while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != ';') {
+                    token.push(c);
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    if !tokens.is_empty() {
+        return Err(ChessError::ParseError(format!(
+            "the operation '{}' does not end with ';'",
+            tokens.join(" ")
+        )));
+    }
+    Ok(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_survive_encoding() {
+        let epd = r#"r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - bm Qxf7#; am Qxe5+ Qh4; id "scholar; mate"; c0 "Qxf7#=10, Qh3=1";"#;
+        let record = decode_record_from_string(epd).unwrap();
+
+        assert_eq!(4, record.get_operations().len());
+        assert_eq!(Some("scholar; mate"), record.get_id());
+        assert_eq!(
+            Some(&[String::from("Qxe5+"), String::from("Qh4")][..]),
+            record.get_operands("am")
+        );
+        assert_eq!(epd, encode_record_as_string(&record));
+    }
+
+    #[test]
+    fn move_counters_come_from_operations() {
+        let record =
+            decode_record_from_string("4k3/8/8/8/8/8/8/4K3 b - - hmvc 12; fmvn 40;").unwrap();
+        let game = record.build_game().unwrap();
+
+        assert_eq!(12, game.get_halfmove_clock());
+        assert_eq!(40, game.get_turn_number());
+    }
+
+    #[test]
+    fn moves_are_decoded_in_the_position() {
+        let record =
+            decode_record_from_string("4k3/8/8/8/8/8/8/R3K3 w Q - bm Ra8+; am Kd1;").unwrap();
+
+        assert_eq!(
+            (0, 7),
+            record.get_best_moves().unwrap()[0].get_new_position()
+        );
+        assert_eq!(
+            (3, 0),
+            record.get_avoid_moves().unwrap()[0].get_new_position()
+        );
+    }
+
+    #[test]
+    fn invalid_records_are_errors() {
+        assert!(decode_record_from_string("4k3/8/8/8 w").is_err());
+        assert!(decode_record_from_string("4k3/8/8/8/8/8/8/4K3 w - - bm Kd1").is_err());
+        assert!(
+            decode_record_from_string("4k3/8/8/8/8/8/8/4K3 w - - bm Ra8;")
+                .unwrap()
+                .get_best_moves()
+                .is_err()
+        );
+    }
+}
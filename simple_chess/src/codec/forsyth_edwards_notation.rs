@@ -1,12 +1,11 @@
 use crate::chess_game::ChessGame;
 use crate::chess_game_builder::ChessGameBuilder;
+use crate::error::ChessError;
 use crate::piece::{ChessPiece, PieceType};
-use crate::ChessMoveType;
-use crate::ChessMoveType::EnPassant;
+use crate::ruleset::Ruleset;
+use crate::variant::{Capablanca, ThreeCheck, Variant};
 use crate::Color::{Black, White};
 use game_board::Board;
-use std::error::Error;
-use std::fmt::{Debug, Display, Formatter};
 
 /// Encodes the current state of the simple_chess game as a string in FEN (Forsyth-Edwards Notation) format.
 ///
@@ -24,6 +23,10 @@ use std::fmt::{Debug, Display, Formatter};
 /// 5. The number of half-moves since the last capture or pawn advance, for the fifty-move rule.
 /// 6. The full move number, starting from 1 and incremented after Black's turn.
 ///
+/// Games of a variant that counts checks, such as Three-check, get an extra field after the
+/// en passant target square with the checks White and Black have left to give, such as `3+3`,
+/// as used by lichess.
+///
 /// # Arguments
 ///
 /// * `game` - A reference to the `ChessGame` instance representing the current state of the game.
@@ -32,12 +35,21 @@ use std::fmt::{Debug, Display, Formatter};
 ///
 /// A `String` representing the current state of the simple_chess game.
 pub fn encode_game_as_string(game: &ChessGame) -> String {
+    let remaining_checks = match game.get_variant().get_check_limit() {
+        Some(limit) => format!(
+            " {}+{}",
+            limit.saturating_sub(game.get_checks_given(White)),
+            limit.saturating_sub(game.get_checks_given(Black))
+        ),
+        None => String::new(),
+    };
     format!(
-        "{} {} {} {} {} {}",
+        "{} {} {} {}{} {} {}",
         get_board_as_fen_string(game),
         get_current_turn_char(game),
         get_castling_rights(game),
         get_en_passent(game),
+        remaining_checks,
         game.get_50_move_rule_counter(),
         game.get_turn_number()
     )
@@ -48,6 +60,14 @@ pub fn encode_game_as_string(game: &ChessGame) -> String {
 /// This function parses the FEN string and constructs the game state, including the board layout,
 /// current turn, castling rights, en passant target square, half-move counter, and full move number.
 ///
+/// A Three-check game is built when the string holds a check count, either as the checks left
+/// to give after the en passant target square (`3+3`), or as the checks given at the end of the
+/// string (`+0+0`). Both styles are used by lichess.
+///
+/// A Capablanca game is built when the ranks are 10 squares long, where `A` and `C` stand for
+/// the archbishop and chancellor, such as
+/// `rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1`.
+///
 /// # Arguments
 ///
 /// * `fen_string` - A string slice representing the state of the simple_chess game in FEN format.
@@ -55,7 +75,7 @@ pub fn encode_game_as_string(game: &ChessGame) -> String {
 /// # Returns
 ///
 /// A `Result` which is `Ok` if the `ChessGame` was built successfully, or an `Err` containing
-/// a `ForsythEdwardsNotationError` if the FEN string is invalid or cannot be parsed.
+/// a `ChessError::InvalidFen` if the FEN string is invalid or cannot be parsed.
 ///
 /// # Example
 /// ```
@@ -65,10 +85,10 @@ pub fn encode_game_as_string(game: &ChessGame) -> String {
 /// let game = build_game_from_string(starting_position_string);
 /// assert!(game.is_ok());
 /// ```
-pub fn build_game_from_string(fen_string: &str) -> Result<ChessGame, ForsythEdwardsNotationError> {
+pub fn build_game_from_string(fen_string: &str) -> Result<ChessGame, ChessError> {
     let fen_string = fen_string.trim();
     if fen_string.is_empty() {
-        return Err(ForsythEdwardsNotationError::new(
+        return Err(ChessError::InvalidFen(
             "argument must be a string in Forsyth–Edwards Notation".to_string(),
         ));
     }
@@ -82,98 +102,166 @@ pub fn build_game_from_string(fen_string: &str) -> Result<ChessGame, ForsythEdwa
         parse_turn_number_from_string,
     ];
 
-    let mut parts = fen_string.split(" ");
+    let mut parts: Vec<&str> = fen_string.split(" ").collect();
     let mut builder = ChessGameBuilder::new();
+    if let Some((white, black)) = take_checks_given(&mut parts)? {
+        builder = builder
+            .set_variant(ThreeCheck)
+            .set_checks_given(white, black);
+    }
+    let mut parts = parts.into_iter();
 
     for step in steps {
         if let Some(next) = parts.next() {
-            builder = match step(builder, next) {
-                Ok(g) => g,
-                Err(e) => return Err(e),
-            };
+            builder = step(builder, next)?;
         } else {
-            return Err(ForsythEdwardsNotationError::new(
+            return Err(ChessError::InvalidFen(
                 "Missing some parts of the string".to_string(),
             ));
         }
     }
 
-    match builder.build() {
-        Ok(g) => Ok(g),
-        Err(e) => Err(ForsythEdwardsNotationError::new(e.to_string())),
+    builder.build()
+}
+
+/// Removes the Three-check field from the parts of a FEN string, returning the checks each
+/// player has given.
+fn take_checks_given(parts: &mut Vec<&str>) -> Result<Option<(usize, usize)>, ChessError> {
+    let parse = |count: &str| {
+        count.parse::<usize>().map_err(|_| {
+            ChessError::InvalidFen(format!(
+                "unable to parse '{count}' into unsigned int for check count"
+            ))
+        })
+    };
+    let limit = ThreeCheck.get_check_limit().unwrap_or_default();
+
+    if let Some(given) = parts.last().and_then(|part| part.strip_prefix('+')) {
+        if let Some((white, black)) = given.split_once('+') {
+            let checks_given = (parse(white)?, parse(black)?);
+            parts.pop();
+            return Ok(Some(checks_given));
+        }
+    }
+    if let Some((white, black)) = parts.get(4).and_then(|part| part.split_once('+')) {
+        let checks_given = (
+            limit.saturating_sub(parse(white)?),
+            limit.saturating_sub(parse(black)?),
+        );
+        parts.remove(4);
+        return Ok(Some(checks_given));
     }
+    Ok(None)
 }
 
 fn parse_board_from_string(
     builder: ChessGameBuilder,
     board_as_fen_string: &str,
-) -> Result<ChessGameBuilder, ForsythEdwardsNotationError> {
-    let mut board = Board::build(8, 8).unwrap();
-
-    let mut files = board_as_fen_string.split("/");
-
-    let mut col = 0;
-    for row in (0..8).rev() {
-        let file = files.next().unwrap();
-
-        for c in file.chars() {
-            match c {
-                '1'..='8' => {
-                    col += c.to_digit(10).unwrap() as usize;
-                }
-                _ => {
-                    let piece = match c {
-                        'P' => ChessPiece::new(PieceType::Pawn, White),
-                        'p' => ChessPiece::new(PieceType::Pawn, Black),
-                        'R' => ChessPiece::new(PieceType::Rook, White),
-                        'r' => ChessPiece::new(PieceType::Rook, Black),
-                        'N' => ChessPiece::new(PieceType::Knight, White),
-                        'n' => ChessPiece::new(PieceType::Knight, Black),
-                        'B' => ChessPiece::new(PieceType::Bishop, White),
-                        'b' => ChessPiece::new(PieceType::Bishop, Black),
-                        'Q' => ChessPiece::new(PieceType::Queen, White),
-                        'q' => ChessPiece::new(PieceType::Queen, Black),
-                        'K' => ChessPiece::new(PieceType::King, White),
-                        'k' => ChessPiece::new(PieceType::King, Black),
-                        _ => {
-                            return Err(ForsythEdwardsNotationError::new(format!(
-                                "Unexpected char '{c}' in file '{file}' of piece placement data"
-                            )))
-                        }
-                    };
-                    board.place_piece(piece, col, row);
-                    col += 1;
-                }
-            }
-        }
-
-        if col != 8 {
-            return Err(ForsythEdwardsNotationError::new(format!(
-                "File '{file}' was not 8 spaces long in piece placement data"
+) -> Result<ChessGameBuilder, ChessError> {
+    let ranks: Vec<&str> = board_as_fen_string.split("/").collect();
+    if ranks.len() != 8 {
+        return Err(ChessError::InvalidFen(format!(
+            "piece placement data '{board_as_fen_string}' does not have 8 ranks"
+        )));
+    }
+    let squares = ranks
+        .iter()
+        .map(|rank| parse_rank(rank))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Capablanca positions are the only ones with 10 files
+    let width = if squares[0].len() == 10 { 10 } else { 8 };
+    let mut board = Board::build(width, 8).unwrap();
+    for ((rank, rank_squares), row) in ranks.iter().zip(squares).zip((0..8).rev()) {
+        if rank_squares.len() != width {
+            return Err(ChessError::InvalidFen(format!(
+                "File '{rank}' was not {width} spaces long in piece placement data"
             )));
         }
+        for (col, piece) in rank_squares.into_iter().enumerate() {
+            if let Some(piece) = piece {
+                board.place_piece(piece, col, row);
+            }
+        }
+    }
 
-        col = 0;
+    let builder = builder.set_board(board);
+    if width == 10 {
+        Ok(builder
+            .set_variant(Capablanca)
+            .set_ruleset(Ruleset::capablanca()))
+    } else {
+        Ok(builder)
     }
+}
 
-    Ok(builder.set_board(board))
+/// Reads the squares of one rank of piece placement data, where runs of empty squares may be
+/// longer than 9 on wide boards.
+fn parse_rank(rank: &str) -> Result<Vec<Option<ChessPiece>>, ChessError> {
+    let mut squares = Vec::new();
+    let mut empty_squares = 0;
+    for c in rank.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            if empty_squares == 0 && digit == 0 {
+                return Err(ChessError::InvalidFen(format!(
+                    "Unexpected char '{c}' in file '{rank}' of piece placement data"
+                )));
+            }
+            empty_squares = empty_squares * 10 + digit as usize;
+            if empty_squares > 10 {
+                return Err(ChessError::InvalidFen(format!(
+                    "File '{rank}' is too long in piece placement data"
+                )));
+            }
+            continue;
+        }
+        squares.extend(std::iter::repeat_n(None, empty_squares));
+        empty_squares = 0;
+
+        let piece = match c {
+            'P' => ChessPiece::new(PieceType::Pawn, White),
+            'p' => ChessPiece::new(PieceType::Pawn, Black),
+            'R' => ChessPiece::new(PieceType::Rook, White),
+            'r' => ChessPiece::new(PieceType::Rook, Black),
+            'N' => ChessPiece::new(PieceType::Knight, White),
+            'n' => ChessPiece::new(PieceType::Knight, Black),
+            'B' => ChessPiece::new(PieceType::Bishop, White),
+            'b' => ChessPiece::new(PieceType::Bishop, Black),
+            'Q' => ChessPiece::new(PieceType::Queen, White),
+            'q' => ChessPiece::new(PieceType::Queen, Black),
+            'K' => ChessPiece::new(PieceType::King, White),
+            'k' => ChessPiece::new(PieceType::King, Black),
+            'A' => ChessPiece::new(PieceType::Archbishop, White),
+            'a' => ChessPiece::new(PieceType::Archbishop, Black),
+            'C' => ChessPiece::new(PieceType::Chancellor, White),
+            'c' => ChessPiece::new(PieceType::Chancellor, Black),
+            _ => {
+                return Err(ChessError::InvalidFen(format!(
+                    "Unexpected char '{c}' in file '{rank}' of piece placement data"
+                )))
+            }
+        };
+        squares.push(Some(piece));
+    }
+    squares.extend(std::iter::repeat_n(None, empty_squares));
+    Ok(squares)
 }
 
 fn parse_current_turn_from_string(
     builder: ChessGameBuilder,
     current_turn_string: &str,
-) -> Result<ChessGameBuilder, ForsythEdwardsNotationError> {
+) -> Result<ChessGameBuilder, ChessError> {
     match current_turn_string {
         "w" => Ok(builder.set_current_turn(White)),
         "b" => Ok(builder.set_current_turn(Black)),
-        _ => Err(ForsythEdwardsNotationError::new(format!("encountered unexpected token parsing turn from FEN string, Expected 'w' or 'b', received {current_turn_string}")))
+        _ => Err(ChessError::InvalidFen(format!("encountered unexpected token parsing turn from FEN string, Expected 'w' or 'b', received {current_turn_string}")))
     }
 }
 
 fn parse_castling_rights_from_string(
     builder: ChessGameBuilder,
     castling_rights_string: &str,
-) -> Result<ChessGameBuilder, ForsythEdwardsNotationError> {
+) -> Result<ChessGameBuilder, ChessError> {
     let (mut ws, mut wl, mut bs, mut bl) = (false, false, false, false);
     if castling_rights_string != "-" {
         for c in castling_rights_string.chars() {
@@ -183,7 +271,7 @@ fn parse_castling_rights_from_string(
                 'k' => bs = true,
                 'q' => bl = true,
                 _ => {
-                    return Err(ForsythEdwardsNotationError::new(format!(
+                    return Err(ChessError::InvalidFen(format!(
                         "Unexpected char '{c}' in castling rights string"
                     )))
                 }
@@ -197,29 +285,16 @@ fn parse_castling_rights_from_string(
 fn parse_en_passant_option_from_string(
     builder: ChessGameBuilder,
     en_passent_option_string: &str,
-) -> Result<ChessGameBuilder, ForsythEdwardsNotationError> {
+) -> Result<ChessGameBuilder, ChessError> {
     if en_passent_option_string == "-" {
         Ok(builder)
     } else {
         match game_board::get_column_and_row_from_square_name(en_passent_option_string) {
-            Ok((col, row)) => {
-                let pawn_color = if row < 3 { White } else { Black };
-                let (original_row, new_row) = match pawn_color {
-                    White => (row - 1, row + 1),
-                    Black => (row + 1, row - 1),
-                };
-
-                let m = ChessMoveType::Move {
-                    original_position: (col, original_row),
-                    new_position: (col, new_row),
-                    piece: ChessPiece::new(PieceType::Pawn, pawn_color),
-                    taken_piece: None,
-                    promotion: None,
-                };
-                let moves = vec![m];
-                Ok(builder.set_moves(moves))
-            }
-            Err(e) => Err(ForsythEdwardsNotationError::new(format!("unable to parse en passant square '{en_passent_option_string}' into a board position: {}", e)))
+            Ok((col, row)) if row == 2 || row == 5 => Ok(builder.set_en_passant_target((col, row))),
+            Ok(_) => Err(ChessError::InvalidFen(format!(
+                "en passant square '{en_passent_option_string}' is not on the third or sixth rank"
+            ))),
+            Err(e) => Err(ChessError::InvalidFen(format!("unable to parse en passant square '{en_passent_option_string}' into a board position: {}", e)))
         }
     }
 }
@@ -227,10 +302,10 @@ fn parse_en_passant_option_from_string(
 fn parse_half_turn_counter_from_string(
     builder: ChessGameBuilder,
     half_turn_counter_string: &str,
-) -> Result<ChessGameBuilder, ForsythEdwardsNotationError> {
+) -> Result<ChessGameBuilder, ChessError> {
     match half_turn_counter_string.parse() {
         Ok(half_turn) => Ok(builder.set_fifty_move_rule_counter(half_turn)),
-        Err(_) => Err(ForsythEdwardsNotationError::new(format!(
+        Err(_) => Err(ChessError::InvalidFen(format!(
             "Unable to parse '{half_turn_counter_string}' into unsigned int for half turn count"
         ))),
     }
@@ -239,10 +314,10 @@ fn parse_half_turn_counter_from_string(
 fn parse_turn_number_from_string(
     builder: ChessGameBuilder,
     turn_number_string: &str,
-) -> Result<ChessGameBuilder, ForsythEdwardsNotationError> {
+) -> Result<ChessGameBuilder, ChessError> {
     match turn_number_string.parse() {
         Ok(turn_number) => Ok(builder.set_turn_number(turn_number)),
-        Err(_) => Err(ForsythEdwardsNotationError::new(format!(
+        Err(_) => Err(ChessError::InvalidFen(format!(
             "unable to parse '{turn_number_string}' into unsigned int for turn count"
         ))),
     }
@@ -314,43 +389,27 @@ fn get_castling_rights(game: &ChessGame) -> String {
 }
 
 fn get_en_passent(game: &ChessGame) -> String {
-    if let Some(EnPassant {
-        new_position: (col, row),
-        ..
-    }) = game.get_last_move()
-    {
-        game_board::get_square_name_from_row_and_col(*col, *row)
-    } else {
-        String::from("-")
-    }
-}
-
-pub struct ForsythEdwardsNotationError {
-    reason: String,
-}
-
-impl ForsythEdwardsNotationError {
-    fn new(reason: String) -> Self {
-        Self { reason }
-    }
-}
-
-impl Display for ForsythEdwardsNotationError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Forsyth-Edwards Notation Error: {}", self.reason)
-    }
-}
-
-impl Debug for ForsythEdwardsNotationError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Forsyth-EdwardsNotationError: {}", self.reason)
+    match game.get_en_passant_target() {
+        Some((col, row)) => game_board::get_square_name_from_row_and_col(col, row),
+        None => String::from("-"),
     }
 }
 
-impl Error for ForsythEdwardsNotationError {}
+/// The error FEN parsing used to return, FEN strings that can not be parsed are now reported
+/// as `ChessError::InvalidFen`.
+#[deprecated(note = "use ChessError, FEN parsing errors are ChessError::InvalidFen")]
+pub type ForsythEdwardsNotationError = ChessError;
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn invalid_fen_reason(error: ChessError) -> String {
+        match error {
+            ChessError::InvalidFen(reason) => reason,
+            error => panic!("expected an invalid FEN error, got {error:?}"),
+        }
+    }
 
     mod decoding_tests {
         use super::super::*;
@@ -366,10 +425,61 @@ mod tests {
                 fen_string
             );
         }
+
+        #[test]
+        fn check_counts_build_three_check_games() {
+            let remaining = build_game_from_string("4k3/8/8/8/8/8/8/4K3 w - - 3+1 4 20").unwrap();
+            assert_eq!("Three-check", remaining.get_variant().get_name());
+            assert_eq!(0, remaining.get_checks_given(White));
+            assert_eq!(2, remaining.get_checks_given(Black));
+            assert_eq!(20, remaining.get_turn_number());
+
+            let given = build_game_from_string("4k3/8/8/8/8/8/8/4K3 w - - 4 20 +0+2").unwrap();
+            assert_eq!(
+                "4k3/8/8/8/8/8/8/4K3 w - - 3+1 4 20",
+                encode_game_as_string(&given)
+            );
+            assert!(build_game_from_string("4k3/8/8/8/8/8/8/4K3 w - - x+1 0 1").is_err());
+        }
+
+        #[test]
+        fn ten_files_build_capablanca_games() {
+            let fen = "r1abqkbcnr/pppp1ppppp/2n7/4p5/4P5/7N2/PPPP1PPPPP/RNABQKBC1R w KQkq e6 0 3";
+            let game = build_game_from_string(fen).unwrap();
+            assert_eq!("Capablanca", game.get_variant().get_name());
+            assert_eq!(3, game.get_ruleset().get_castling_distance());
+            assert_eq!(
+                Some(&ChessPiece::new(PieceType::Chancellor, White)),
+                game.get_board().get_piece_at_space(7, 0)
+            );
+            assert_eq!(fen, encode_game_as_string(&game));
+
+            assert!(build_game_from_string("9/8/8/8/8/8/8/8 w - - 0 1").is_err());
+            assert!(build_game_from_string("11/8/8/8/8/8/8/8 w - - 0 1").is_err());
+        }
+    }
+
+    mod malformed_input_tests {
+        use super::super::*;
+
+        #[test]
+        fn malformed_boards_are_errors_rather_than_panics() {
+            for fen in [
+                "8/8/8 w - - 0 1",
+                "rnbqkbnrp/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e1 0 1",
+            ] {
+                assert!(matches!(
+                    build_game_from_string(fen),
+                    Err(ChessError::InvalidFen(_))
+                ));
+            }
+        }
     }
 
     mod encoding_tests {
         use super::super::*;
+        use super::invalid_fen_reason;
         use crate::piece::PieceType::{Bishop, King, Knight, Pawn, Queen, Rook};
 
         #[test]
@@ -382,7 +492,7 @@ mod tests {
                 Err(e) => {
                     assert_eq!(
                         "argument must be a string in Forsyth–Edwards Notation",
-                        e.reason
+                        invalid_fen_reason(e)
                     )
                 }
             }
@@ -393,7 +503,7 @@ mod tests {
             let starting_position_as_fen_string =
                 "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
             let game = build_game_from_string(starting_position_as_fen_string);
-            assert_eq!(game.is_ok(), true);
+            assert!(game.is_ok());
             let game = game.unwrap();
 
             let expected_piece_type = [Rook, Knight, Bishop, Queen, King, Bishop, Knight, Rook];
@@ -478,7 +588,7 @@ mod tests {
                 Err(e) => {
                     assert_eq!(
                         "File 'ppppppp' was not 8 spaces long in piece placement data",
-                        e.reason
+                        invalid_fen_reason(e)
                     )
                 }
             }
@@ -494,7 +604,7 @@ mod tests {
                 Err(e) => {
                     assert_eq!(
                         "Unexpected char 'f' in file 'fppppppp' of piece placement data",
-                        e.reason
+                        invalid_fen_reason(e)
                     )
                 }
             }
@@ -515,7 +625,7 @@ mod tests {
             let game_builder = ChessGameBuilder::new();
             match parse_current_turn_from_string(game_builder, "J") {
                 Ok(_) => panic!("expected error"),
-                Err(e) => assert_eq!("encountered unexpected token parsing turn from FEN string, Expected 'w' or 'b', received J", e.reason),
+                Err(e) => assert_eq!("encountered unexpected token parsing turn from FEN string, Expected 'w' or 'b', received J", invalid_fen_reason(e)),
             }
         }
 
@@ -546,55 +656,45 @@ mod tests {
             let game_builder = ChessGameBuilder::new();
             match parse_castling_rights_from_string(game_builder, "KQn") {
                 Ok(_) => panic!("expected error"),
-                Err(e) => assert_eq!("Unexpected char 'n' in castling rights string", e.reason),
+                Err(e) => assert_eq!(
+                    "Unexpected char 'n' in castling rights string",
+                    invalid_fen_reason(e)
+                ),
             }
         }
 
         #[test]
         fn parse_fen_en_passant_string() {
-            // White Pawn
-            let mut game_builder = ChessGameBuilder::new();
-            game_builder = game_builder.set_board(Board::build(8, 8).unwrap());
-            game_builder = game_builder.set_current_turn(White);
-            game_builder = parse_en_passant_option_from_string(game_builder, "e3").unwrap();
-
-            let game = game_builder.build().unwrap();
-            if let Some(ChessMoveType::Move {
-                original_position,
-                new_position,
-                piece,
-                taken_piece,
-                promotion,
-            }) = game.get_last_move()
-            {
-                assert_eq!(&(4, 1), original_position);
-                assert_eq!(&(4, 3), new_position);
-                assert_eq!(ChessPiece::new(Pawn, White), *piece);
-                assert!(taken_piece.is_none());
-                assert!(promotion.is_none());
+            for (square, target) in [("e3", (4, 2)), ("e6", (4, 5))] {
+                let mut game_builder = ChessGameBuilder::new();
+                game_builder = game_builder.set_board(Board::build(8, 8).unwrap());
+                game_builder = game_builder.set_current_turn(White);
+                game_builder = parse_en_passant_option_from_string(game_builder, square).unwrap();
+
+                let game = game_builder.build().unwrap();
+                assert_eq!(Some(target), game.get_en_passant_target());
+                assert!(game.get_moves().is_empty());
             }
+        }
 
-            // Black Pawn
-            let mut game_builder = ChessGameBuilder::new();
-            game_builder = game_builder.set_board(Board::build(8, 8).unwrap());
-            game_builder = game_builder.set_current_turn(White);
-            game_builder = parse_en_passant_option_from_string(game_builder, "e6").unwrap();
-
-            let game = game_builder.build().unwrap();
-            if let Some(ChessMoveType::Move {
-                original_position,
-                new_position,
-                piece,
-                taken_piece,
-                promotion,
-            }) = game.get_last_move()
-            {
-                assert_eq!(&(4, 6), original_position);
-                assert_eq!(&(4, 4), new_position);
-                assert_eq!(ChessPiece::new(Pawn, Black), *piece);
-                assert!(taken_piece.is_none());
-                assert!(promotion.is_none());
-            }
+        #[test]
+        fn en_passant_squares_survive_encoding() {
+            let fen = "rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 3";
+            let game = build_game_from_string(fen).unwrap();
+            assert_eq!(fen, encode_game_as_string(&game));
+
+            let mut game = ChessGame::new();
+            let double_step = game
+                .legal_moves()
+                .iter()
+                .copied()
+                .find(|m| m.get_new_position() == (3, 3))
+                .unwrap();
+            game.make_move(double_step);
+            assert_eq!(
+                "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 1",
+                encode_game_as_string(&game)
+            );
         }
 
         #[test]
@@ -605,7 +705,7 @@ mod tests {
             game_builder = parse_en_passant_option_from_string(game_builder, "-").unwrap();
 
             let game = game_builder.build().unwrap();
-            assert!(game.get_last_move().is_none());
+            assert!(game.get_en_passant_target().is_none());
         }
 
         #[test]
@@ -615,7 +715,7 @@ mod tests {
                 Ok(_) => panic!("expected error"),
                 Err(e) => assert_eq!(
                     "unable to parse en passant square '_' into a board position: Invalid input",
-                    e.reason
+                    invalid_fen_reason(e)
                 ),
             }
         }
@@ -638,7 +738,7 @@ mod tests {
                 Ok(_) => panic!("expected error"),
                 Err(e) => assert_eq!(
                     "Unable to parse '_' into unsigned int for half turn count",
-                    e.reason
+                    invalid_fen_reason(e)
                 ),
             }
         }
@@ -661,7 +761,7 @@ mod tests {
                 Ok(_) => panic!("expected error"),
                 Err(e) => assert_eq!(
                     "unable to parse 'ns' into unsigned int for turn count",
-                    e.reason
+                    invalid_fen_reason(e)
                 ),
             }
         }
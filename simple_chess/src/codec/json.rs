@@ -0,0 +1,191 @@
+use crate::chess_game_state_analyzer::GameState;
+use crate::clock::ChessClock;
+use crate::codec::{
+    forsyth_edwards_notation, long_algebraic_notation, standard_algebraic_notation,
+};
+use crate::{ChessGame, ChessMoveType, Color};
+use game_board::get_square_name_from_row_and_col;
+
+/// Encodes the current position of a game as a single JSON document for front ends.
+///
+/// The document holds everything a typical web client needs to render the position and
+/// let the player move in one round trip:
+///
+/// - `fen`: The position in Forsyth-Edwards Notation.
+/// - `turn`: The player to move, `"white"` or `"black"`.
+/// - `state`: One of `"in_progress"`, `"check"`, `"checkmate"`, `"stalemate"` or
+///   `"variant_win"`.
+/// - `in_check`: Whether the player to move is in check.
+/// - `winner`: The winning player, or `null` while nobody has won. Under Armageddon scoring a
+///   drawn game has a winner too, see `Scoring`.
+/// - `draw_claim`: The reason a draw can be claimed, such as `"Repetition"`, or `null`.
+/// - `legal_moves`: Every legal move with its `san`, `uci`, `from` and `to` squares and
+///   whether it `gives_check`.
+/// - `last_move`: The `uci`, `from` and `to` of the last move, or `null`.
+/// - `clocks`: The remaining time of each player in milliseconds, or `null` without a clock.
+///
+/// # Arguments
+///
+/// * `game` - The game to encode, the game is not modified.
+/// * `clock` - The clock of the game, if it is played with one.
+///
+/// # Returns
+///
+/// A `String` holding the JSON document.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::json::encode_game_state_as_json;
+/// use simple_chess::ChessGame;
+///
+/// let json = encode_game_state_as_json(&ChessGame::new(), None);
+/// assert!(json.starts_with(r#"{"fen":"rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1","turn":"white","state":"in_progress""#));
+/// assert!(json.contains(r#"{"san":"Nf3","uci":"g1f3","from":"g1","to":"f3","gives_check":false}"#));
+/// ```
+pub fn encode_game_state_as_json(game: &ChessGame, clock: Option<&ChessClock>) -> String {
+    let mut game = game.clone();
+    let state = game.get_game_state();
+    let (state_name, in_check, legal_moves) = match &state {
+        GameState::InProgress { legal_moves, .. } => ("in_progress", false, &legal_moves[..]),
+        GameState::Check { legal_moves, .. } => ("check", true, &legal_moves[..]),
+        GameState::Checkmate { .. } => ("checkmate", true, &[][..]),
+        GameState::Stalemate => ("stalemate", false, &[][..]),
+        GameState::VariantWin { .. } => ("variant_win", false, &[][..]),
+        GameState::Resigned { .. } => ("resigned", false, &[][..]),
+        GameState::DrawAgreed => ("draw_agreed", false, &[][..]),
+        GameState::DeadPosition => ("dead_position", false, &[][..]),
+        GameState::SeventyFiveMoveRule => ("seventy_five_move_rule", false, &[][..]),
+    };
+    let winner = game.get_outcome().and_then(|outcome| outcome.get_winner());
+
+    let legal_moves = encode_moves_as_json(&game, legal_moves);
+
+    let last_move = match game.get_last_move() {
+        Some(chess_move) => format!(
+            r#"{{"uci":{},{}}}"#,
+            encode_string(&long_algebraic_notation::encode_move_as_string(chess_move)),
+            encode_squares(chess_move)
+        ),
+        None => String::from("null"),
+    };
+
+    let clocks = match clock {
+        Some(clock) => format!(
+            r#"{{"white_ms":{},"black_ms":{}}}"#,
+            clock.get_remaining_time(Color::White).as_millis(),
+            clock.get_remaining_time(Color::Black).as_millis()
+        ),
+        None => String::from("null"),
+    };
+
+    let draw_claim = match game.can_claim_draw() {
+        Some(reason) => encode_string(&format!("{:?}", reason)),
+        None => String::from("null"),
+    };
+
+    format!(
+        r#"{{"fen":{},"turn":{},"state":"{}","in_check":{},"winner":{},"draw_claim":{},"legal_moves":{},"last_move":{},"clocks":{}}}"#,
+        encode_string(&forsyth_edwards_notation::encode_game_as_string(&game)),
+        encode_color(game.get_current_players_turn()),
+        state_name,
+        in_check,
+        winner
+            .map(encode_color)
+            .unwrap_or_else(|| String::from("null")),
+        draw_claim,
+        legal_moves,
+        last_move,
+        clocks
+    )
+}
+
+/// Encodes moves of the current position of a game as a JSON array, each move with its
+/// `san`, `uci`, `from` and `to` squares and whether it `gives_check`.
+pub(crate) fn encode_moves_as_json(game: &ChessGame, moves: &[ChessMoveType]) -> String {
+    let moves: Vec<String> = moves
+        .iter()
+        .map(|chess_move| {
+            let san = standard_algebraic_notation::encode_move_as_string(game, chess_move);
+            let gives_check = san.ends_with('+') || san.ends_with('#');
+            format!(
+                r#"{{"san":{},"uci":{},{},"gives_check":{}}}"#,
+                encode_string(&san),
+                encode_string(&long_algebraic_notation::encode_move_as_string(chess_move)),
+                encode_squares(chess_move),
+                gives_check
+            )
+        })
+        .collect();
+    format!("[{}]", moves.join(","))
+}
+
+fn encode_squares(chess_move: &ChessMoveType) -> String {
+    let (from, to) = (
+        chess_move.get_original_position(),
+        chess_move.get_new_position(),
+    );
+    format!(
+        r#""from":{},"to":{}"#,
+        encode_string(&get_square_name_from_row_and_col(from.0, from.1)),
+        encode_string(&get_square_name_from_row_and_col(to.0, to.1))
+    )
+}
+
+fn encode_color(color: Color) -> String {
+    match color {
+        Color::White => String::from(r#""white""#),
+        Color::Black => String::from(r#""black""#),
+    }
+}
+
+/// Encodes a string as a JSON string literal, escaping quotes, backslashes and control
+/// characters.
+fn encode_string(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() + 2);
+    encoded.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            c if c.is_control() => encoded.push_str(&format!("\\u{:04x}", c as u32)),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TimeControl;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use std::time::Duration;
+
+    #[test]
+    fn checkmate_has_a_winner_and_no_moves() {
+        let game = build_game_from_string("R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 1").unwrap();
+        let json = encode_game_state_as_json(&game, None);
+
+        assert!(json.contains(r#""state":"checkmate","in_check":true,"winner":"white""#));
+        assert!(json.contains(r#""legal_moves":[]"#));
+        assert!(json.contains(r#""last_move":null,"clocks":null"#));
+    }
+
+    #[test]
+    fn checks_and_clocks_are_included() {
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let clock = ChessClock::new(TimeControl::new(Duration::from_secs(60), Duration::ZERO));
+        let json = encode_game_state_as_json(&game, Some(&clock));
+
+        assert!(json
+            .contains(r#"{"san":"Ra8#","uci":"a1a8","from":"a1","to":"a8","gives_check":true}"#));
+        assert!(json.ends_with(r#""clocks":{"white_ms":60000,"black_ms":60000}}"#));
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(r#""a\"b\\c\u0001""#, encode_string("a\"b\\c\u{1}"));
+    }
+}
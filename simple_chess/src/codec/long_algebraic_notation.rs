@@ -0,0 +1,112 @@
+use crate::error::ChessError;
+use crate::piece::PieceType;
+use crate::{ChessGame, ChessMoveType};
+use game_board::get_square_name_from_row_and_col;
+
+/// Encodes a move in long algebraic notation, the coordinate format used by the UCI
+/// protocol.
+///
+/// The move is written as its origin square followed by its destination square, with the
+/// promotion piece appended in lowercase. Castling is written as the move of the king.
+///
+/// # Arguments
+///
+/// * `chess_move` - The move to encode.
+///
+/// # Returns
+///
+/// A `String` such as `e2e4`, `e1g1` or `e7e8q`.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::long_algebraic_notation::encode_move_as_string;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessMoveType, Color};
+///
+/// let promotion = ChessMoveType::Move {
+///     original_position: (4, 6),
+///     new_position: (4, 7),
+///     piece: ChessPiece::new(PieceType::Pawn, Color::White),
+///     taken_piece: None,
+///     promotion: Some(ChessPiece::new(PieceType::Queen, Color::White)),
+/// };
+///
+/// assert_eq!("e7e8q", encode_move_as_string(&promotion));
+/// ```
+pub fn encode_move_as_string(chess_move: &ChessMoveType) -> String {
+    let (from, to) = (
+        chess_move.get_original_position(),
+        chess_move.get_new_position(),
+    );
+    let promotion = match chess_move {
+        ChessMoveType::Move { promotion, .. } | ChessMoveType::EnPassant { promotion, .. } => {
+            match promotion.map(|piece| piece.get_piece_type()) {
+                Some(PieceType::Queen) => "q",
+                Some(PieceType::Rook) => "r",
+                Some(PieceType::Bishop) => "b",
+                Some(PieceType::Knight) => "n",
+                Some(PieceType::Archbishop) => "a",
+                Some(PieceType::Chancellor) => "c",
+                _ => "",
+            }
+        }
+        ChessMoveType::Castle { .. } => "",
+    };
+    format!(
+        "{}{}{}",
+        get_square_name_from_row_and_col(from.0, from.1),
+        get_square_name_from_row_and_col(to.0, to.1),
+        promotion
+    )
+}
+
+/// Finds the legal move of the current position a long algebraic notation string describes.
+///
+/// # Arguments
+///
+/// * `game` - The game the move is about to be played in, the game is not modified.
+/// * `lan` - The move, such as `e2e4` or `e7e8q`, in either case.
+///
+/// # Returns
+///
+/// * `Ok(ChessMoveType)` - The move described.
+/// * `Err(ChessError::IllegalMove)` - If no legal move matches the string.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::long_algebraic_notation::decode_move_from_string;
+/// use simple_chess::ChessGame;
+///
+/// let knight_move = decode_move_from_string(&ChessGame::new(), "g1f3").unwrap();
+/// assert_eq!((5, 2), knight_move.get_new_position());
+/// ```
+pub fn decode_move_from_string(game: &ChessGame, lan: &str) -> Result<ChessMoveType, ChessError> {
+    let lan = lan.trim().to_ascii_lowercase();
+    game.clone()
+        .get_cached_legal_moves()
+        .iter()
+        .find(|chess_move| encode_move_as_string(chess_move) == lan)
+        .copied()
+        .ok_or(ChessError::IllegalMove(lan))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_game_move_analyzer::get_legal_moves;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn castling_is_written_as_the_king_move() {
+        let mut game = build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let castles: Vec<String> = get_legal_moves(&mut game)
+            .iter()
+            .filter(|chess_move| matches!(chess_move, ChessMoveType::Castle { .. }))
+            .map(encode_move_as_string)
+            .collect();
+
+        assert_eq!(2, castles.len());
+        assert!(castles.contains(&String::from("e1g1")));
+        assert!(castles.contains(&String::from("e1c1")));
+    }
+}
@@ -1,2 +1,8 @@
 pub mod binary;
+pub mod extended_position_description;
 pub mod forsyth_edwards_notation;
+pub mod json;
+pub mod long_algebraic_notation;
+pub mod portable_game_notation;
+pub mod standard_algebraic_notation;
+pub mod url;
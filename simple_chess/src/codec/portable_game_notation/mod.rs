@@ -0,0 +1,315 @@
+use crate::chess_game::ChessGame;
+use crate::codec::forsyth_edwards_notation;
+use crate::codec::standard_algebraic_notation::{self, SanParser};
+use crate::error::ChessError;
+use crate::Color;
+
+pub mod reader;
+
+pub use reader::{GameSummary, PgnReader};
+
+/// The tags every PGN game starts with, in the order they are written.
+const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// The position standard games start from.
+const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Encodes a game in PGN (Portable Game Notation).
+///
+/// The seven tag roster is always written, with `?` for the tags that are not given. The
+/// `Result` tag is taken from the result of the game, and finished games get a
+/// `Termination` tag unless one is given. Games that did not start from the standard
+/// position get `SetUp` and `FEN` tags. The moves are written in Standard
+/// Algebraic Notation followed by the result.
+///
+/// # Arguments
+///
+/// * `game` - The game to encode, the game is not modified.
+/// * `tags` - Tag pairs to write, such as `("White", "Carlsen")` or the tags of
+///   `ChessClock::get_pgn_tags`.
+///
+/// # Returns
+///
+/// A `String` holding the tag pairs, an empty line and the movetext.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::portable_game_notation::encode_game_as_string;
+/// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+/// use simple_chess::ChessGame;
+///
+/// let mut game = ChessGame::new();
+/// for san in ["e4", "e5"] {
+///     game.make_move(decode_move_from_string(&game, san).unwrap());
+/// }
+///
+/// let pgn = encode_game_as_string(&game, &[("White".to_string(), "Ian".to_string())]);
+/// assert!(pgn.starts_with("[Event \"?\"]\n[Site \"?\"]\n[Date \"?\"]\n[Round \"?\"]\n[White \"Ian\"]\n"));
+/// assert!(pgn.ends_with("\n\n1. e4 e5 *\n"));
+/// ```
+pub fn encode_game_as_string(game: &ChessGame, tags: &[(String, String)]) -> String {
+    let game_result = game.clone().get_result();
+    let result = game_result.map_or("*", |game_result| game_result.as_pgn_str());
+    let mut replay = game.get_starting_game();
+    let starting_fen = forsyth_edwards_notation::encode_game_as_string(&replay);
+
+    let mut pgn = String::new();
+    for name in SEVEN_TAG_ROSTER {
+        let value = match name {
+            "Result" => result,
+            _ => tags
+                .iter()
+                .find(|(tag, _)| tag == name)
+                .map_or("?", |(_, value)| value.as_str()),
+        };
+        pgn.push_str(&encode_tag(name, value));
+    }
+    if starting_fen != STARTING_FEN {
+        pgn.push_str(&encode_tag("SetUp", "1"));
+        pgn.push_str(&encode_tag("FEN", &starting_fen));
+    }
+    let has_termination = tags.iter().any(|(name, _)| name == "Termination");
+    if let (Some(game_result), false) = (game_result, has_termination) {
+        pgn.push_str(&encode_tag(
+            "Termination",
+            game_result.get_termination().as_pgn_str(),
+        ));
+    }
+    for (name, value) in tags {
+        let is_written =
+            SEVEN_TAG_ROSTER.contains(&name.as_str()) || name == "SetUp" || name == "FEN";
+        if !is_written {
+            pgn.push_str(&encode_tag(name, value));
+        }
+    }
+    pgn.push('\n');
+
+    let mut movetext = Vec::new();
+    for (index, chess_move) in game.get_moves().iter().enumerate() {
+        let color = replay.get_current_players_turn();
+        if color == Color::White {
+            movetext.push(format!("{}.", replay.get_turn_number()));
+        } else if index == 0 {
+            movetext.push(format!("{}...", replay.get_turn_number()));
+        }
+        movetext.push(standard_algebraic_notation::encode_move_as_string(
+            &replay, chess_move,
+        ));
+        replay.apply_move(*chess_move);
+    }
+    movetext.push(result.to_string());
+    pgn.push_str(&movetext.join(" "));
+    pgn.push('\n');
+    pgn
+}
+
+/// Builds a game from the first game of a PGN (Portable Game Notation) string.
+///
+/// The game starts from the position of the `FEN` tag when there is one, and from the
+/// standard position otherwise. Comments, recursive variations, numeric annotation glyphs
+/// and move suffixes such as `!?` are skipped, and the moves are read with a lenient
+/// `SanParser`.
+///
+/// # Arguments
+///
+/// * `pgn` - The PGN string, which may hold only movetext.
+///
+/// # Returns
+///
+/// * `Ok(ChessGame)` - The game after every move of the movetext.
+/// * `Err(ChessError)` - If the `FEN` tag is invalid or a move cannot be played.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::portable_game_notation::build_game_from_string;
+///
+/// let game = build_game_from_string("1. e4 {the king's pawn} e5 2. Nf3 (2. f4 exf4) Nc6 *").unwrap();
+/// assert_eq!(4, game.get_moves().len());
+/// ```
+pub fn build_game_from_string(pgn: &str) -> Result<ChessGame, ChessError> {
+    build_game_from_tags(&decode_tags(pgn), pgn)
+}
+
+/// Builds a game from the movetext of a PGN string, starting from the `FEN` tag if one of
+/// the tags is a `FEN` tag.
+fn build_game_from_tags(tags: &[(String, String)], pgn: &str) -> Result<ChessGame, ChessError> {
+    let mut game = match tags.iter().find(|(name, _)| name == "FEN") {
+        Some((_, fen)) => forsyth_edwards_notation::build_game_from_string(fen)?,
+        None => ChessGame::new(),
+    };
+
+    let parser = SanParser::new().set_lenient(true);
+    for san in get_movetext_moves(pgn) {
+        let chess_move = parser.parse(&game, &san)?;
+        game.make_move(chess_move);
+    }
+    Ok(game)
+}
+
+/// Reads the tag pairs at the start of a PGN string.
+///
+/// # Arguments
+///
+/// * `pgn` - The PGN string.
+///
+/// # Returns
+///
+/// The name and value of every tag pair before the movetext, in the order they are written.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::portable_game_notation::decode_tags;
+///
+/// let tags = decode_tags("[White \"Ian\"]\n[Result \"1-0\"]\n\n1. e4 1-0");
+/// assert_eq!(
+///     vec![("White".to_string(), "Ian".to_string()), ("Result".to_string(), "1-0".to_string())],
+///     tags
+/// );
+/// ```
+pub fn decode_tags(pgn: &str) -> Vec<(String, String)> {
+    pgn.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with('['))
+        .filter_map(decode_tag)
+        .collect()
+}
+
+fn decode_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((
+        name.to_string(),
+        value.replace("\\\"", "\"").replace("\\\\", "\\"),
+    ))
+}
+
+fn encode_tag(name: &str, value: &str) -> String {
+    format!(
+        "[{} \"{}\"]\n",
+        name,
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Returns the moves of the movetext of a PGN string, without move numbers, comments,
+/// variations, annotation glyphs or the result.
+fn get_movetext_moves(pgn: &str) -> Vec<String> {
+    let movetext: String = pgn
+        .lines()
+        .skip_while(|line| line.trim().is_empty() || line.trim_start().starts_with('['))
+        .map(|line| line.split_once(';').map_or(line, |(before, _)| before))
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    let mut text = String::new();
+    let mut in_comment = false;
+    let mut variation_depth = 0;
+    for c in movetext.chars() {
+        match c {
+            '{' => in_comment = true,
+            '}' => in_comment = false,
+            '(' if !in_comment => variation_depth += 1,
+            ')' if !in_comment => variation_depth -= 1,
+            c if !in_comment && variation_depth == 0 => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace()
+        .map(strip_move_number)
+        .filter(|token| !token.is_empty() && !token.starts_with('$'))
+        .filter(|token| !matches!(*token, "*" | "1-0" | "0-1" | "1/2-1/2"))
+        .map(|token| token.trim_end_matches(['!', '?']).to_string())
+        .collect()
+}
+
+/// Removes a move number such as `12.` or `12...` from the start of a token, which may be
+/// written without a space before the move.
+fn strip_move_number(token: &str) -> &str {
+    match token.split_once('.') {
+        Some((number, rest)) if number.chars().all(|c| c.is_ascii_digit()) => {
+            rest.trim_start_matches('.')
+        }
+        _ => token,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adjudication::Adjudication;
+    use crate::codec::standard_algebraic_notation::play_moves;
+    use crate::scoring::Scoring;
+
+    #[test]
+    fn encoded_games_decode_to_the_same_moves() {
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &["f3", "e5", "g4", "Qh4#"]);
+
+        let pgn = encode_game_as_string(&game, &[]);
+        assert!(pgn.contains("[Result \"0-1\"]"));
+        assert!(pgn.ends_with("\n\n1. f3 e5 2. g4 Qh4# 0-1\n"));
+
+        let decoded = build_game_from_string(&pgn).unwrap();
+        assert_eq!(game.get_moves(), decoded.get_moves());
+    }
+
+    #[test]
+    fn games_from_a_position_keep_their_fen() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12";
+        let mut game = forsyth_edwards_notation::build_game_from_string(fen).unwrap();
+        play_moves(&mut game, &["Kd7", "e4"]);
+
+        let pgn = encode_game_as_string(&game, &[]);
+        assert!(pgn.contains(&format!("[SetUp \"1\"]\n[FEN \"{fen}\"]\n")));
+        assert!(pgn.ends_with("\n\n12... Kd7 13. e4 *\n"));
+        assert_eq!(
+            game.get_board(),
+            build_game_from_string(&pgn).unwrap().get_board()
+        );
+    }
+
+    #[test]
+    fn armageddon_draws_are_written_as_wins() {
+        let mut game =
+            forsyth_edwards_notation::build_game_from_string("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1")
+                .unwrap();
+        game.set_scoring(Scoring::Armageddon {
+            draw_winner: Color::Black,
+        });
+
+        let pgn = encode_game_as_string(&game, &[]);
+        assert!(pgn.contains("[Result \"0-1\"]"));
+    }
+
+    #[test]
+    fn finished_games_get_a_termination_tag() {
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &["e4", "e5"]);
+        assert!(!encode_game_as_string(&game, &[]).contains("Termination"));
+
+        game.adjudicate(Adjudication::Resignation {
+            winner: Color::White,
+        })
+        .unwrap();
+        let pgn = encode_game_as_string(&game, &[]);
+        assert!(pgn.contains("[Result \"1-0\"]\n[Termination \"adjudication\"]\n"));
+        assert!(pgn.ends_with("1. e4 e5 1-0\n"));
+    }
+
+    #[test]
+    fn annotations_are_skipped() {
+        let pgn = "[Event \"Casual\"]\n\n1.e4! $1 e5?! ; a line comment\n2. Nf3 {a (comment)} (2. f4 (2. d4) exf4) 2... Nc6 1/2-1/2";
+        let game = build_game_from_string(pgn).unwrap();
+        assert_eq!(4, game.get_moves().len());
+    }
+
+    #[test]
+    fn illegal_moves_are_an_error() {
+        assert!(matches!(
+            build_game_from_string("1. e4 e4"),
+            Err(ChessError::IllegalMove(_))
+        ));
+    }
+}
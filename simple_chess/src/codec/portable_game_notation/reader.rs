@@ -0,0 +1,286 @@
+use crate::chess_game::ChessGame;
+use crate::codec::portable_game_notation::{build_game_from_tags, decode_tag};
+use crate::error::ChessError;
+use std::io::{self, BufRead, Lines};
+
+/// The tags and movetext of one game read by a `PgnReader`.
+///
+/// The moves are not played until `build_game` is called, so games can be searched by
+/// their tags without the cost of replaying them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSummary {
+    tags: Vec<(String, String)>,
+    movetext: String,
+}
+
+impl GameSummary {
+    /// Returns the tag pairs in the order they are written.
+    pub fn get_tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the value of a tag, `None` if the game does not have the tag.
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the name of the white player.
+    pub fn get_white(&self) -> Option<&str> {
+        self.get_tag("White")
+    }
+
+    /// Returns the name of the black player.
+    pub fn get_black(&self) -> Option<&str> {
+        self.get_tag("Black")
+    }
+
+    /// Returns the result, such as `1-0` or `*`.
+    pub fn get_result(&self) -> Option<&str> {
+        self.get_tag("Result")
+    }
+
+    /// Returns the ECO code of the opening, such as `B90`.
+    pub fn get_eco(&self) -> Option<&str> {
+        self.get_tag("ECO")
+    }
+
+    /// Returns the date the game was played, such as `2024.03.??`.
+    pub fn get_date(&self) -> Option<&str> {
+        self.get_tag("Date")
+    }
+
+    /// Returns the movetext as it was read.
+    pub fn get_movetext(&self) -> &str {
+        &self.movetext
+    }
+
+    /// Plays the moves of the game, like `build_game_from_string`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChessGame)` - The game after every move of the movetext.
+    /// * `Err(ChessError)` - If the `FEN` tag is invalid or a move cannot be played.
+    pub fn build_game(&self) -> Result<ChessGame, ChessError> {
+        build_game_from_tags(&self.tags, &self.movetext)
+    }
+}
+
+/// Reads the games of a PGN file one at a time.
+///
+/// Only the game being read is held in memory, so files with millions of games can be
+/// searched. Games can be filtered by their tags, games that do not match every filter are
+/// skipped without their moves being played.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::portable_game_notation::PgnReader;
+///
+/// let pgn = "[White \"Ian\"]\n[Black \"Magnus\"]\n[Result \"0-1\"]\n\n1. f3 e5 2. g4 Qh4# 0-1\n\n\
+///            [White \"Magnus\"]\n[Black \"Ian\"]\n[Result \"1/2-1/2\"]\n\n1. e4 e5 1/2-1/2\n";
+///
+/// let wins: Vec<_> = PgnReader::new(pgn.as_bytes())
+///     .set_result("0-1")
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(1, wins.len());
+/// assert_eq!(4, wins[0].build_game().unwrap().get_moves().len());
+/// ```
+pub struct PgnReader<R> {
+    lines: Lines<R>,
+    next_line: Option<String>,
+    player: Option<String>,
+    eco: Option<String>,
+    result: Option<String>,
+    dates: Option<(String, String)>,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    /// Creates a reader of every game.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The PGN to read, such as a `BufReader` of a file.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            next_line: None,
+            player: None,
+            eco: None,
+            result: None,
+            dates: None,
+        }
+    }
+
+    /// Keeps only games where either player's name contains `name`, ignoring case.
+    pub fn set_player(mut self, name: &str) -> Self {
+        self.player = Some(name.to_lowercase());
+        self
+    }
+
+    /// Keeps only games whose ECO code starts with `eco`, so `B` keeps every opening of
+    /// the B volume and `B90` only that opening.
+    pub fn set_eco(mut self, eco: &str) -> Self {
+        self.eco = Some(eco.to_string());
+        self
+    }
+
+    /// Keeps only games with a result, such as `1-0`, `0-1`, `1/2-1/2` or `*`.
+    pub fn set_result(mut self, result: &str) -> Self {
+        self.result = Some(result.to_string());
+        self
+    }
+
+    /// Keeps only games played between two dates, both included.
+    ///
+    /// Dates are written like the `Date` tag, such as `2024.03.15`. Unknown parts of a
+    /// game's date, written `??`, count as zero.
+    pub fn set_date_range(mut self, from: &str, to: &str) -> Self {
+        self.dates = Some((from.to_string(), to.to_string()));
+        self
+    }
+
+    fn is_match(&self, game: &GameSummary) -> bool {
+        let player = self.player.as_ref().is_none_or(|name| {
+            [game.get_white(), game.get_black()]
+                .into_iter()
+                .flatten()
+                .any(|player| player.to_lowercase().contains(name))
+        });
+        let eco = self.eco.as_ref().is_none_or(|eco| {
+            game.get_eco()
+                .is_some_and(|game_eco| game_eco.starts_with(eco.as_str()))
+        });
+        let result = self
+            .result
+            .as_ref()
+            .is_none_or(|result| game.get_result() == Some(result.as_str()));
+        let date = self.dates.as_ref().is_none_or(|(from, to)| {
+            game.get_date().is_some_and(|date| {
+                let date = date.replace('?', "0");
+                *from <= date && date <= *to
+            })
+        });
+        player && eco && result && date
+    }
+
+    /// Reads the lines of the next game, `None` once there are no more games.
+    fn read_game(&mut self) -> io::Result<Option<GameSummary>> {
+        let mut tags = Vec::new();
+        let mut movetext = String::new();
+        let mut in_comment = false;
+        loop {
+            let line = match self.next_line.take() {
+                Some(line) => line,
+                None => match self.lines.next() {
+                    Some(line) => line?,
+                    None => break,
+                },
+            };
+            let line = line.trim();
+            if !in_comment && line.starts_with('[') {
+                // a tag after the movetext starts the next game
+                if !movetext.is_empty() {
+                    self.next_line = Some(line.to_string());
+                    break;
+                }
+                tags.extend(decode_tag(line));
+            } else if !line.is_empty() {
+                for c in line.chars() {
+                    match c {
+                        '{' => in_comment = true,
+                        '}' => in_comment = false,
+                        _ => {}
+                    }
+                }
+                movetext.push_str(line);
+                movetext.push('\n');
+            }
+        }
+        if tags.is_empty() && movetext.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(GameSummary { tags, movetext }))
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = io::Result<GameSummary>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.read_game() {
+                Ok(Some(game)) if !self.is_match(&game) => continue,
+                read => return read.transpose(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATABASE: &str = r#"[Event "Club"]
+[Date "2023.11.04"]
+[White "Morphy, Paul"]
+[Black "Duke Karl"]
+[Result "1-0"]
+[ECO "C41"]
+
+1. e4 e5 2. Nf3 d6 {Philidor's
+[defence]} 3. d4 Bg4 1-0
+
+[Event "Club"]
+[Date "2024.??.??"]
+[White "Anderssen, Adolf"]
+[Black "Morphy, Paul"]
+[Result "1/2-1/2"]
+[ECO "B20"]
+
+1. e4 c5 1/2-1/2
+[Event "Study"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"]
+[Result "*"]
+
+1. e4 *
+"#;
+
+    fn read(reader: PgnReader<&[u8]>) -> Vec<GameSummary> {
+        reader.collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn games_are_split_at_their_tags() {
+        let games = read(PgnReader::new(DATABASE.as_bytes()));
+
+        assert_eq!(3, games.len());
+        assert_eq!(Some("Duke Karl"), games[0].get_black());
+        assert!(games[0].get_movetext().contains("[defence]"));
+        assert_eq!(6, games[0].build_game().unwrap().get_moves().len());
+        assert_eq!(Some("B20"), games[1].get_eco());
+        assert_eq!(1, games[2].build_game().unwrap().get_moves().len());
+    }
+
+    #[test]
+    fn filters_select_games_by_their_tags() {
+        let reader = || PgnReader::new(DATABASE.as_bytes());
+
+        assert_eq!(2, read(reader().set_player("morphy")).len());
+        assert_eq!(1, read(reader().set_player("morphy").set_eco("B")).len());
+        assert_eq!(1, read(reader().set_result("*")).len());
+        let games = read(reader().set_date_range("2024.01.01", "2024.12.31"));
+        assert!(games.is_empty());
+        let games = read(reader().set_date_range("2023.01.01", "2024.12.31"));
+        assert_eq!(2, games.len());
+    }
+
+    #[test]
+    fn empty_input_has_no_games() {
+        assert!(read(PgnReader::new("\n\n".as_bytes())).is_empty());
+    }
+}
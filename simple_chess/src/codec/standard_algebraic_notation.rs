@@ -0,0 +1,515 @@
+use crate::chess_game::ChessGame;
+use crate::chess_game_move_analyzer::annotate_move;
+use crate::error::ChessError;
+use crate::piece::PieceType::Pawn;
+use crate::ChessMoveType;
+use game_board::get_square_name_from_row_and_col;
+
+/// Encodes a move as a string in SAN (Standard Algebraic Notation).
+///
+/// The move is described relative to the current position of the game, which is used to
+/// disambiguate between pieces of the same type that can move to the same square and to
+/// add the `+` and `#` suffixes for check and checkmate.
+///
+/// # Arguments
+///
+/// * `game` - The game the move is about to be played in, the game is not modified.
+/// * `chess_move` - The move to encode, it should be legal in the current position.
+///
+/// # Returns
+///
+/// A `String` such as `e4`, `Nbd7`, `exd5`, `e8=Q+` or `O-O-O`.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::standard_algebraic_notation::encode_move_as_string;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessGame, ChessMoveType, Color};
+///
+/// let game = ChessGame::new();
+/// let knight_move = ChessMoveType::Move {
+///     original_position: (6, 0),
+///     new_position: (5, 2),
+///     piece: ChessPiece::new(PieceType::Knight, Color::White),
+///     taken_piece: None,
+///     promotion: None,
+/// };
+///
+/// assert_eq!("Nf3", encode_move_as_string(&game, &knight_move));
+/// ```
+pub fn encode_move_as_string(game: &ChessGame, chess_move: &ChessMoveType) -> String {
+    let mut game = game.clone();
+    let mut san = match chess_move {
+        ChessMoveType::Castle {
+            king_original_position,
+            king_new_position,
+            ..
+        } => {
+            if king_new_position.0 > king_original_position.0 {
+                String::from("O-O")
+            } else {
+                String::from("O-O-O")
+            }
+        }
+        ChessMoveType::Move {
+            original_position,
+            new_position,
+            piece,
+            taken_piece,
+            promotion,
+        } => encode_piece_move(
+            &mut game,
+            *original_position,
+            *new_position,
+            piece.get_piece_type() == Pawn,
+            taken_piece.is_some(),
+            promotion.map(|promotion| promotion.as_fen_char().to_ascii_uppercase()),
+        ),
+        ChessMoveType::EnPassant {
+            original_position,
+            new_position,
+            promotion,
+            ..
+        } => encode_piece_move(
+            &mut game,
+            *original_position,
+            *new_position,
+            true,
+            true,
+            promotion.map(|promotion| promotion.as_fen_char().to_ascii_uppercase()),
+        ),
+    };
+
+    san.push_str(annotate_move(&game, *chess_move).get_check_suffix());
+    san
+}
+
+fn encode_piece_move(
+    game: &mut ChessGame,
+    original_position: (usize, usize),
+    new_position: (usize, usize),
+    is_pawn: bool,
+    is_capture: bool,
+    promotion: Option<char>,
+) -> String {
+    let mut san = String::new();
+    let original_square =
+        get_square_name_from_row_and_col(original_position.0, original_position.1);
+    let original_file: String = original_square
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+
+    if is_pawn {
+        if is_capture {
+            san.push_str(&original_file);
+        }
+    } else {
+        let piece = game
+            .get_board()
+            .get_piece_at_space(original_position.0, original_position.1)
+            .copied();
+        if let Some(piece) = piece {
+            san.push(piece.as_fen_char().to_ascii_uppercase());
+            san.push_str(&get_disambiguation(
+                game,
+                original_position,
+                new_position,
+                &original_square,
+                &original_file,
+            ));
+        }
+    }
+
+    if is_capture {
+        san.push('x');
+    }
+    san.push_str(&get_square_name_from_row_and_col(
+        new_position.0,
+        new_position.1,
+    ));
+    if let Some(promotion) = promotion {
+        san.push('=');
+        san.push(promotion);
+    }
+    san
+}
+
+/// Returns the file, rank or square needed to tell the moving piece apart from other pieces
+/// of the same type that can also legally move to the destination.
+fn get_disambiguation(
+    game: &mut ChessGame,
+    original_position: (usize, usize),
+    new_position: (usize, usize),
+    original_square: &str,
+    original_file: &str,
+) -> String {
+    let piece_type = match game
+        .get_board()
+        .get_piece_at_space(original_position.0, original_position.1)
+    {
+        Some(piece) => piece.get_piece_type(),
+        None => return String::new(),
+    };
+
+    let rivals: Vec<(usize, usize)> = game
+        .get_cached_legal_moves()
+        .iter()
+        .filter_map(|legal_move| match legal_move {
+            ChessMoveType::Move {
+                original_position: rival_position,
+                new_position: rival_destination,
+                piece,
+                ..
+            } if piece.get_piece_type() == piece_type
+                && *rival_destination == new_position
+                && *rival_position != original_position =>
+            {
+                Some(*rival_position)
+            }
+            _ => None,
+        })
+        .collect();
+
+    if rivals.is_empty() {
+        String::new()
+    } else if rivals.iter().all(|rival| rival.0 != original_position.0) {
+        original_file.to_string()
+    } else if rivals.iter().all(|rival| rival.1 != original_position.1) {
+        original_square[original_file.len()..].to_string()
+    } else {
+        original_square.to_string()
+    }
+}
+
+/// Reads moves written in SAN, strictly or leniently.
+///
+/// A strict parser only accepts moves written exactly as `encode_move_as_string` writes
+/// them, including the `+` and `#` suffixes. A lenient parser also accepts the sloppiness
+/// common in move text written by people:
+///
+/// - Missing or wrong check and checkmate suffixes, and `!` and `?` annotations.
+/// - Castling written with zeros or lowercase letters, such as `0-0` or `o-o-o`.
+/// - En passant captures followed by `e.p.` or `ep`.
+/// - Piece letters written in lowercase, such as `nf3`. A lowercase `b` is read as a bishop
+///   only when no pawn on the b-file can make the move.
+/// - Captures without the `x`, and promotions without the `=` or in lowercase, such as
+///   `e8q`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::standard_algebraic_notation::SanParser;
+/// use simple_chess::ChessGame;
+///
+/// let game = ChessGame::new();
+/// let lenient = SanParser::new().set_lenient(true);
+///
+/// assert_eq!("Nf3", lenient.normalize(&game, "nf3").unwrap());
+/// assert!(SanParser::new().parse(&game, "nf3").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SanParser {
+    lenient: bool,
+}
+
+impl SanParser {
+    /// Creates a strict parser.
+    pub fn new() -> Self {
+        Self { lenient: false }
+    }
+
+    /// Sets whether the parser accepts common mistakes in move text.
+    pub fn set_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Returns `true` if the parser accepts common mistakes in move text.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Finds the legal move of the current position a SAN string describes.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game the move is about to be played in, the game is not modified.
+    /// * `san` - The move, such as `Nbd7` or `exd5`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChessMoveType)` - The move described.
+    /// * `Err(ChessError::IllegalMove)` - If no legal move matches the string.
+    /// * `Err(ChessError::ParseError)` - If the string is empty, or the lenient parser
+    ///   matches it to more than one legal move.
+    pub fn parse(&self, game: &ChessGame, san: &str) -> Result<ChessMoveType, ChessError> {
+        let san = san.trim();
+        if san.is_empty() {
+            return Err(ChessError::ParseError(String::from("the move is empty")));
+        }
+
+        let mut game = game.clone();
+        let candidates: Vec<(ChessMoveType, String)> = game
+            .get_cached_legal_moves()
+            .to_vec()
+            .into_iter()
+            .map(|legal_move| (legal_move, encode_move_as_string(&game, &legal_move)))
+            .collect();
+
+        if !self.lenient {
+            return candidates
+                .into_iter()
+                .find(|(_, candidate)| candidate == san)
+                .map(|(legal_move, _)| legal_move)
+                .ok_or_else(|| ChessError::IllegalMove(san.to_string()));
+        }
+
+        let mut readings = vec![relax(san)];
+        if san.starts_with(['n', 'b', 'r', 'q', 'k']) {
+            let mut uppercased = san.to_string();
+            uppercased[..1].make_ascii_uppercase();
+            readings.push(relax(&uppercased));
+        }
+
+        let mut matches: Vec<ChessMoveType> = Vec::new();
+        for (legal_move, candidate) in &candidates {
+            if readings.contains(&relax(candidate)) && !matches.contains(legal_move) {
+                matches.push(*legal_move);
+            }
+        }
+        match matches.as_slice() {
+            [] => Err(ChessError::IllegalMove(san.to_string())),
+            [chess_move] => Ok(*chess_move),
+            _ => Err(ChessError::ParseError(format!(
+                "'{san}' matches more than one legal move"
+            ))),
+        }
+    }
+
+    /// Reads a move and writes it back in strict SAN, for cleaning up imported move text.
+    ///
+    /// # Returns
+    ///
+    /// The move in strict SAN, or the error `parse` returns for the string.
+    pub fn normalize(&self, game: &ChessGame, san: &str) -> Result<String, ChessError> {
+        self.parse(game, san)
+            .map(|chess_move| encode_move_as_string(game, &chess_move))
+    }
+}
+
+/// Finds the legal move of the current position a strict SAN string describes.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+/// use simple_chess::ChessGame;
+///
+/// let e4 = decode_move_from_string(&ChessGame::new(), "e4").unwrap();
+/// assert_eq!((4, 3), e4.get_new_position());
+/// ```
+pub fn decode_move_from_string(game: &ChessGame, san: &str) -> Result<ChessMoveType, ChessError> {
+    SanParser::new().parse(game, san)
+}
+
+/// Strips the parts of a SAN string people commonly get wrong, leaving only what identifies
+/// the move.
+fn relax(san: &str) -> String {
+    let mut relaxed: String = san
+        .chars()
+        .filter(|c| {
+            !c.is_whitespace() && !matches!(c, '+' | '#' | '!' | '?' | 'x' | 'X' | '=' | ':')
+        })
+        .collect();
+    for suffix in ["e.p.", "ep"] {
+        if let Some(stripped) = relaxed.strip_suffix(suffix) {
+            relaxed = stripped.to_string();
+        }
+    }
+    if relaxed.chars().all(|c| matches!(c, '0' | 'o' | 'O' | '-')) {
+        return relaxed.replace(['0', 'o'], "O");
+    }
+
+    // a promotion piece written in lowercase, such as e8q
+    let mut chars: Vec<char> = relaxed.chars().collect();
+    if chars.len() >= 2
+        && chars[chars.len() - 2].is_ascii_digit()
+        && matches!(chars[chars.len() - 1], 'n' | 'b' | 'r' | 'q')
+    {
+        let last = chars.len() - 1;
+        chars[last] = chars[last].to_ascii_uppercase();
+    }
+    chars.into_iter().collect()
+}
+
+/// Plays moves written in SAN on a game, panicking if one of them can not be played.
+#[cfg(test)]
+pub(crate) fn play_moves(game: &mut ChessGame, sans: &[&str]) {
+    for san in sans {
+        let chess_move = decode_move_from_string(game, san).unwrap();
+        game.make_move(chess_move);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::long_algebraic_notation as lan;
+    use crate::piece::ChessPiece;
+    use crate::piece::PieceType::{Knight, Pawn, Queen, Rook};
+    use crate::Color::{Black, White};
+
+    #[test]
+    fn pawn_moves_and_captures() {
+        let game =
+            build_game_from_string("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2")
+                .unwrap();
+
+        let push = lan::decode_move_from_string(&game, "e4e5").unwrap();
+        assert_eq!("e5", encode_move_as_string(&game, &push));
+
+        let capture = lan::decode_move_from_string(&game, "e4d5").unwrap();
+        assert_eq!("exd5", encode_move_as_string(&game, &capture));
+    }
+
+    #[test]
+    fn en_passant_is_a_pawn_capture() {
+        let game = build_game_from_string("4k3/8/8/3Pp3/8/8/8/4K3 w - e6 0 1").unwrap();
+        let en_passant = lan::decode_move_from_string(&game, "d5e6").unwrap();
+        assert_eq!("dxe6", encode_move_as_string(&game, &en_passant));
+    }
+
+    #[test]
+    fn promotion_with_check() {
+        let game = build_game_from_string("k7/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let promotion = ChessMoveType::Move {
+            original_position: (4, 6),
+            new_position: (4, 7),
+            piece: ChessPiece::new(Pawn, White),
+            taken_piece: None,
+            promotion: Some(ChessPiece::new(Queen, White)),
+        };
+        assert_eq!("e8=Q+", encode_move_as_string(&game, &promotion));
+    }
+
+    #[test]
+    fn pieces_are_disambiguated_by_file_then_rank() {
+        let game = build_game_from_string("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1").unwrap();
+        let rook_move = lan::decode_move_from_string(&game, "a1d1").unwrap();
+        assert_eq!("Rad1", encode_move_as_string(&game, &rook_move));
+
+        let game = build_game_from_string("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1").unwrap();
+        let rook_move = lan::decode_move_from_string(&game, "a5a3").unwrap();
+        assert_eq!("R5a3", encode_move_as_string(&game, &rook_move));
+
+        let game = build_game_from_string("4k3/8/8/8/8/1N3N2/8/1N2K3 w - - 0 1").unwrap();
+        let knight_move = lan::decode_move_from_string(&game, "b3d2").unwrap();
+        assert_eq!("Nb3d2", encode_move_as_string(&game, &knight_move));
+    }
+
+    #[test]
+    fn castling_and_checkmate() {
+        let game = build_game_from_string("r3k3/8/8/8/8/8/8/4K3 b q - 0 1").unwrap();
+        let castle = ChessMoveType::Castle {
+            rook_original_position: (0, 7),
+            rook_new_position: (3, 7),
+            king_original_position: (4, 7),
+            king_new_position: (2, 7),
+        };
+        assert_eq!("O-O-O", encode_move_as_string(&game, &castle));
+
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let mate = ChessMoveType::Move {
+            original_position: (0, 0),
+            new_position: (0, 7),
+            piece: ChessPiece::new(Rook, White),
+            taken_piece: None,
+            promotion: None,
+        };
+        assert_eq!("Ra8#", encode_move_as_string(&game, &mate));
+
+        let game = build_game_from_string("4k3/8/8/8/8/8/8/4K1n1 b - - 0 1").unwrap();
+        let knight_move = ChessMoveType::Move {
+            original_position: (6, 0),
+            new_position: (5, 2),
+            piece: ChessPiece::new(Knight, Black),
+            taken_piece: None,
+            promotion: None,
+        };
+        assert_eq!("Nf3+", encode_move_as_string(&game, &knight_move));
+    }
+
+    mod decoding_tests {
+        use super::super::*;
+        use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+        fn normalize(fen: &str, san: &str) -> Result<String, ChessError> {
+            let game = build_game_from_string(fen).unwrap();
+            SanParser::new().set_lenient(true).normalize(&game, san)
+        }
+
+        #[test]
+        fn strict_parsing_needs_exact_san() {
+            let game = build_game_from_string("4k3/8/8/8/8/8/8/4K1n1 b - - 0 1").unwrap();
+            assert_eq!(
+                (5, 2),
+                decode_move_from_string(&game, "Nf3+")
+                    .unwrap()
+                    .get_new_position()
+            );
+            assert_eq!(
+                Err(ChessError::IllegalMove(String::from("Nf3"))),
+                decode_move_from_string(&game, "Nf3")
+            );
+        }
+
+        #[test]
+        fn lenient_parsing_fixes_suffixes_and_castling() {
+            let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+            assert_eq!("O-O", normalize(fen, "0-0").unwrap());
+            assert_eq!("O-O-O", normalize(fen, "o-o-o").unwrap());
+            assert_eq!("Rxa8+", normalize(fen, "Ra8!").unwrap());
+
+            let mate = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+            assert_eq!("Ra8#", normalize(mate, "Ra8+").unwrap());
+        }
+
+        #[test]
+        fn lenient_parsing_reads_en_passant_and_promotions() {
+            let fen = "4k3/8/8/3Pp3/8/8/8/4K3 w - e6 0 1";
+            assert_eq!("dxe6", normalize(fen, "dxe6 e.p.").unwrap());
+            assert_eq!("dxe6", normalize(fen, "de6ep").unwrap());
+
+            let fen = "k7/4P3/8/8/8/8/8/4K3 w - - 0 1";
+            assert_eq!("e8=Q+", normalize(fen, "e8q").unwrap());
+            assert_eq!("e8=N", normalize(fen, "e8=n").unwrap());
+        }
+
+        #[test]
+        fn lowercase_bishops_must_be_unambiguous() {
+            let pawn_or_bishop = "4k3/8/8/8/8/2n5/1P1B4/4K3 w - - 0 1";
+            assert!(matches!(
+                normalize(pawn_or_bishop, "bxc3"),
+                Err(ChessError::ParseError(_))
+            ));
+            assert_eq!("Bxc3", normalize(pawn_or_bishop, "Bxc3").unwrap());
+
+            let bishop_only = "4k3/8/8/8/8/2n5/3B4/4K3 w - - 0 1";
+            assert_eq!("Bxc3", normalize(bishop_only, "bxc3").unwrap());
+        }
+
+        #[test]
+        fn unknown_moves_are_illegal() {
+            assert!(matches!(
+                normalize("4k3/8/8/8/8/8/8/4K3 w - - 0 1", "Qd4"),
+                Err(ChessError::IllegalMove(_))
+            ));
+            assert!(matches!(
+                normalize("4k3/8/8/8/8/8/8/4K3 w - - 0 1", " "),
+                Err(ChessError::ParseError(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use crate::codec::forsyth_edwards_notation::build_game_from_string;
+use crate::error::ChessError;
+use crate::ChessGame;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The FEN fields lichess leaves out of short analysis links, in order after the piece
+/// placement.
+const DEFAULT_FEN_FIELDS: [&str; 5] = ["w", "-", "-", "0", "1"];
+
+/// What a chess website link points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChessUrl {
+    /// A position given in the link itself, such as a lichess analysis board.
+    Position { fen: String },
+    /// A game played on lichess, identified by its eight character id.
+    LichessGame { id: String },
+    /// A game played on chess.com, identified by its numeric id.
+    ChessComGame { id: String },
+}
+
+/// Reads a link to lichess or chess.com.
+///
+/// The following links are understood:
+///
+/// - lichess analysis and editor links with a position, such as
+///   `https://lichess.org/analysis/standard/8/8/8/4k3/8/8/8/4K3_w_-_-_0_1`.
+/// - lichess game links, such as `https://lichess.org/abcdEFGH/black#32`.
+/// - chess.com links with a `fen` query parameter, such as
+///   `https://www.chess.com/analysis?fen=...`.
+/// - chess.com game links, such as `https://www.chess.com/game/live/123456789`.
+///
+/// # Arguments
+///
+/// * `url` - The link to read, with or without the `https://` scheme.
+///
+/// # Returns
+///
+/// The position or game id the link points to, or a `ChessUrlError` if the link is not a
+/// link to a position or game on a known site.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::url::{parse_url, ChessUrl};
+///
+/// assert_eq!(
+///     ChessUrl::LichessGame { id: String::from("abcdEFGH") },
+///     parse_url("https://lichess.org/abcdEFGHijkl#12").unwrap()
+/// );
+/// assert_eq!(
+///     ChessUrl::ChessComGame { id: String::from("123456789") },
+///     parse_url("chess.com/game/live/123456789").unwrap()
+/// );
+/// ```
+pub fn parse_url(url: &str) -> Result<ChessUrl, ChessUrlError> {
+    let url = url.trim();
+    let without_scheme = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let without_fragment = without_scheme
+        .split_once('#')
+        .map_or(without_scheme, |(rest, _)| rest);
+    let (location, query) = without_fragment
+        .split_once('?')
+        .unwrap_or((without_fragment, ""));
+    let (host, path) = location.split_once('/').unwrap_or((location, ""));
+    let host = host.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match host {
+        "lichess.org" => parse_lichess_url(&segments, query),
+        "chess.com" => parse_chess_com_url(&segments, query),
+        _ => Err(ChessUrlError::new(format!(
+            "links to '{host}' are not supported"
+        ))),
+    }
+}
+
+/// Builds a game from a link to a position on lichess or chess.com.
+///
+/// Links to played games only hold the id of the game, the game itself has to be
+/// downloaded from the site, so those links return an error naming the id.
+///
+/// # Arguments
+///
+/// * `url` - The link to build the game from.
+///
+/// # Returns
+///
+/// The `ChessGame` of the linked position, or a `ChessUrlError` if the link does not hold a
+/// valid position.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::encode_game_as_string;
+/// use simple_chess::codec::url::build_game_from_url;
+///
+/// let game = build_game_from_url(
+///     "https://lichess.org/analysis/standard/rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR_b_KQkq_-_0_1",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+///     encode_game_as_string(&game)
+/// );
+/// ```
+pub fn build_game_from_url(url: &str) -> Result<ChessGame, ChessUrlError> {
+    match parse_url(url)? {
+        ChessUrl::Position { fen } => build_game_from_string(&fen)
+            .map_err(|e| ChessUrlError::new(format!("the linked position is invalid: {e}"))),
+        ChessUrl::LichessGame { id } => Err(ChessUrlError::new(format!(
+            "the link is to lichess game '{id}', which must be downloaded from lichess"
+        ))),
+        ChessUrl::ChessComGame { id } => Err(ChessUrlError::new(format!(
+            "the link is to chess.com game '{id}', which must be downloaded from chess.com"
+        ))),
+    }
+}
+
+fn parse_lichess_url(segments: &[&str], query: &str) -> Result<ChessUrl, ChessUrlError> {
+    if let Some(fen) = get_query_parameter(query, "fen") {
+        return Ok(ChessUrl::Position {
+            fen: complete_fen(&fen),
+        });
+    }
+
+    match segments.first() {
+        Some(&"analysis") | Some(&"editor") => {
+            let position: Vec<&str> = segments[1..]
+                .iter()
+                .copied()
+                .skip_while(|s| matches!(*s, "standard" | "fromPosition"))
+                .collect();
+            let fen = if position.is_empty() {
+                String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+            } else {
+                complete_fen(&percent_decode(&position.join("/")).replace('_', " "))
+            };
+            Ok(ChessUrl::Position { fen })
+        }
+        Some(id) if (id.len() == 8 || id.len() == 12) && is_alphanumeric(id) => {
+            // twelve character ids include the player's color, the game is the first eight
+            Ok(ChessUrl::LichessGame {
+                id: id[..8].to_string(),
+            })
+        }
+        _ => Err(ChessUrlError::new(String::from(
+            "the lichess link is not to a game or a position",
+        ))),
+    }
+}
+
+fn parse_chess_com_url(segments: &[&str], query: &str) -> Result<ChessUrl, ChessUrlError> {
+    if let Some(fen) = get_query_parameter(query, "fen") {
+        return Ok(ChessUrl::Position {
+            fen: complete_fen(&fen),
+        });
+    }
+
+    match segments.last() {
+        Some(id)
+            if segments.contains(&"game")
+                && !id.is_empty()
+                && id.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            Ok(ChessUrl::ChessComGame { id: id.to_string() })
+        }
+        _ => Err(ChessUrlError::new(String::from(
+            "the chess.com link is not to a game or a position",
+        ))),
+    }
+}
+
+/// Returns the decoded value of a query parameter, where `+` stands for a space.
+fn get_query_parameter(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(&value.replace('+', " ")))
+}
+
+/// Appends the default values of any FEN fields missing after the piece placement.
+fn complete_fen(fen: &str) -> String {
+    let mut fields: Vec<&str> = fen.split_whitespace().collect();
+    let given = fields.len().max(1);
+    fields.extend(DEFAULT_FEN_FIELDS.iter().skip(given - 1));
+    fields.join(" ")
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn is_alphanumeric(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Error returned when a link does not point to a chess position or game.
+pub struct ChessUrlError {
+    reason: String,
+}
+
+impl ChessUrlError {
+    fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for ChessUrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsupported chess link: {}", self.reason)
+    }
+}
+
+impl Debug for ChessUrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ChessUrlError: {}", self.reason)
+    }
+}
+
+impl Error for ChessUrlError {}
+
+impl From<ChessUrlError> for ChessError {
+    fn from(error: ChessUrlError) -> Self {
+        ChessError::ParseError(error.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(fen: &str) -> ChessUrl {
+        ChessUrl::Position {
+            fen: String::from(fen),
+        }
+    }
+
+    #[test]
+    fn lichess_position_links() {
+        assert_eq!(
+            position("8/8/8/4k3/8/8/8/4K3 b - - 0 1"),
+            parse_url("lichess.org/analysis/8/8/8/4k3/8/8/8/4K3_b").unwrap()
+        );
+        assert_eq!(
+            position("8/8/8/4k3/8/8/8/4K3 w - - 3 40"),
+            parse_url("https://lichess.org/editor/8/8/8/4k3/8/8/8/4K3%20w%20-%20-%203%2040")
+                .unwrap()
+        );
+        assert_eq!(
+            position("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+            parse_url("https://lichess.org/analysis").unwrap()
+        );
+    }
+
+    #[test]
+    fn lichess_game_links() {
+        assert_eq!(
+            ChessUrl::LichessGame {
+                id: String::from("q7ZvsdUF")
+            },
+            parse_url("https://lichess.org/q7ZvsdUF/black#32").unwrap()
+        );
+        assert!(parse_url("https://lichess.org/study/abc").is_err());
+    }
+
+    #[test]
+    fn chess_com_links() {
+        assert_eq!(
+            position("8/8/8/4k3/8/8/8/4K3 w - - 0 1"),
+            parse_url("https://www.chess.com/analysis?fen=8%2F8%2F8%2F4k3%2F8%2F8%2F8%2F4K3+w+-+-+0+1&flip=false")
+                .unwrap()
+        );
+        assert_eq!(
+            ChessUrl::ChessComGame {
+                id: String::from("98765")
+            },
+            parse_url("https://www.chess.com/live/game/98765?username=someone").unwrap()
+        );
+        assert!(parse_url("https://www.chess.com/member/someone").is_err());
+    }
+
+    #[test]
+    fn other_sites_and_game_links_do_not_build_games() {
+        assert!(build_game_from_url("https://example.com/analysis").is_err());
+        assert!(build_game_from_url("https://lichess.org/q7ZvsdUF").is_err());
+        assert!(build_game_from_url("https://lichess.org/analysis/not_a_fen").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Color::{Black, White};
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub enum Color {
     White,
     Black,
@@ -26,4 +26,59 @@ impl Color {
             Black => White,
         }
     }
+
+    /// Returns the direction pawns of this color move in, `1` for White moving up the
+    /// board and `-1` for Black moving down it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::Color::{Black, White};
+    ///
+    /// assert_eq!(1, White.forward());
+    /// assert_eq!(-1, Black.forward());
+    /// ```
+    pub fn forward(&self) -> i32 {
+        match self {
+            White => 1,
+            Black => -1,
+        }
+    }
+
+    /// Returns the row of a rank counted from this color's own back rank.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank` - The rank counted from the color's back rank, `0` is the back rank itself.
+    /// * `board_height` - The height of the board.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::Color::{Black, White};
+    ///
+    /// assert_eq!(1, White.relative_rank(1, 8));
+    /// assert_eq!(6, Black.relative_rank(1, 8));
+    /// assert_eq!(9, Black.relative_rank(0, 10));
+    /// ```
+    pub fn relative_rank(&self, rank: usize, board_height: usize) -> usize {
+        match self {
+            White => rank,
+            Black => board_height - 1 - rank,
+        }
+    }
+
+    /// Returns the row pawns of this color promote on, the opponent's back rank.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::Color::{Black, White};
+    ///
+    /// assert_eq!(7, White.promotion_rank(8));
+    /// assert_eq!(0, Black.promotion_rank(8));
+    /// ```
+    pub fn promotion_rank(&self, board_height: usize) -> usize {
+        self.relative_rank(board_height - 1, board_height)
+    }
 }
@@ -0,0 +1,519 @@
+use crate::chess_board::ChessBoard;
+use crate::engine::evaluation::piece_value;
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, Color};
+use game_board::Board;
+use std::sync::OnceLock;
+
+/// The bonus a side with a won ending gets on top of its material, so the engine prefers
+/// trading into a won ending over keeping material in an unclear one.
+const WON_ENDING_BONUS: i32 = 500;
+
+/// The number of entries in the KPK bitbase, for two sides to move, 64 squares for each
+/// king and a pawn on one of the files `a` to `d` and one of the rows `1` to `6`.
+const KPK_SIZE: usize = 2 * 64 * 64 * 4 * 8;
+
+// the states of a position in the KPK bitbase, the side with the pawn either wins or not
+const INVALID: u8 = 0;
+const UNKNOWN: u8 = 1;
+const DRAW: u8 = 2;
+const WIN: u8 = 4;
+
+static KPK_BITBASE: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// The endings with a known way to play them.
+///
+/// The strong side is the player with the extra material, who is trying to win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endgame {
+    /// King and pawn against a lone king, won or drawn depending on the position.
+    KingAndPawn { strong_side: Color },
+    /// King and rook against a lone king, always won.
+    KingAndRook { strong_side: Color },
+    /// King and queen against a lone king, always won.
+    KingAndQueen { strong_side: Color },
+    /// King, bishop and knight against a lone king, won by driving the king into a corner
+    /// of the bishop's color.
+    BishopAndKnight { strong_side: Color },
+    /// Both players have kings, rooks and possibly pawns, and at least one rook each.
+    RookEnding,
+}
+
+impl Endgame {
+    /// Returns the player trying to win, `None` for rook endings where both may be.
+    pub fn get_strong_side(&self) -> Option<Color> {
+        match self {
+            Endgame::KingAndPawn { strong_side }
+            | Endgame::KingAndRook { strong_side }
+            | Endgame::KingAndQueen { strong_side }
+            | Endgame::BishopAndKnight { strong_side } => Some(*strong_side),
+            Endgame::RookEnding => None,
+        }
+    }
+}
+
+/// Recognizes the ending on a board from the pieces left on it.
+///
+/// # Arguments
+///
+/// * `board` - The board to look at, a standard 8x8 board with one king for each player.
+///
+/// # Returns
+///
+/// * `Some(Endgame)` - The ending the material belongs to.
+/// * `None` - If the material is not one of the recognized endings.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::endgame::{classify, Endgame};
+/// use simple_chess::Color;
+///
+/// let game = build_game_from_string("8/8/8/4k3/8/8/8/2B1KN2 w - - 0 1").unwrap();
+/// assert_eq!(
+///     Some(Endgame::BishopAndKnight {
+///         strong_side: Color::White
+///     }),
+///     classify(game.get_board())
+/// );
+///
+/// let game = build_game_from_string("8/5pk1/8/r7/8/8/R4PK1/8 w - - 0 1").unwrap();
+/// assert_eq!(Some(Endgame::RookEnding), classify(game.get_board()));
+/// ```
+pub fn classify(board: &Board<ChessPiece>) -> Option<Endgame> {
+    if board.get_width() != 8 || board.get_height() != 8 {
+        return None;
+    }
+    let white = get_piece_types(board, Color::White);
+    let black = get_piece_types(board, Color::Black);
+    if count(&white, PieceType::King) != 1 || count(&black, PieceType::King) != 1 {
+        return None;
+    }
+
+    let only_rooks_and_pawns = |pieces: &[PieceType]| {
+        pieces.iter().all(|piece_type| {
+            matches!(
+                piece_type,
+                PieceType::King | PieceType::Rook | PieceType::Pawn
+            )
+        }) && count(pieces, PieceType::Rook) > 0
+    };
+    if only_rooks_and_pawns(&white) && only_rooks_and_pawns(&black) {
+        return Some(Endgame::RookEnding);
+    }
+
+    let (strong_side, strong) = match (white.len(), black.len()) {
+        (_, 1) if white.len() > 1 => (Color::White, white),
+        (1, _) if black.len() > 1 => (Color::Black, black),
+        _ => return None,
+    };
+    let mut extra: Vec<PieceType> = strong
+        .into_iter()
+        .filter(|piece_type| *piece_type != PieceType::King)
+        .collect();
+    extra.sort_by_key(|piece_type| piece_value(*piece_type));
+    match extra[..] {
+        [PieceType::Pawn] => Some(Endgame::KingAndPawn { strong_side }),
+        [PieceType::Rook] => Some(Endgame::KingAndRook { strong_side }),
+        [PieceType::Queen] => Some(Endgame::KingAndQueen { strong_side }),
+        [PieceType::Knight, PieceType::Bishop] => Some(Endgame::BishopAndKnight { strong_side }),
+        _ => None,
+    }
+}
+
+/// Looks up whether the side with the pawn wins a king and pawn against king ending.
+///
+/// The answer comes from a bitbase of every such position, which is computed the first
+/// time it is needed. The side with the pawn wins if it can promote without losing the
+/// new queen, otherwise the position is a draw.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+///
+/// # Returns
+///
+/// * `Some(true)` - If the side with the pawn wins with best play.
+/// * `Some(false)` - If the position is a draw.
+/// * `None` - If the game is not a king and pawn against king ending.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::endgame::probe_kpk;
+///
+/// // with the opposition the white king escorts the pawn home
+/// let won = build_game_from_string("8/4k3/8/4K3/4P3/8/8/8 b - - 0 1").unwrap();
+/// assert_eq!(Some(true), probe_kpk(&won));
+///
+/// // with White to move Black keeps the opposition
+/// let drawn = build_game_from_string("8/4k3/8/4K3/4P3/8/8/8 w - - 0 1").unwrap();
+/// assert_eq!(Some(false), probe_kpk(&drawn));
+/// ```
+pub fn probe_kpk(game: &ChessGame) -> Option<bool> {
+    let board = game.get_board();
+    let Some(Endgame::KingAndPawn { strong_side }) = classify(board) else {
+        return None;
+    };
+
+    // the bitbase is stored with a white pawn on one of the files a to d
+    let (mut pawn, mut strong_king, mut weak_king) = ((0, 0), (0, 0), (0, 0));
+    for (position, piece) in board.pieces() {
+        let square = (
+            position.get_col(),
+            strong_side.relative_rank(position.get_row(), 8),
+        );
+        match (piece.get_piece_type(), piece.get_color() == strong_side) {
+            (PieceType::Pawn, _) => pawn = square,
+            (_, true) => strong_king = square,
+            (_, false) => weak_king = square,
+        }
+    }
+    if pawn.1 == 0 || pawn.1 == 7 {
+        return None;
+    }
+    if pawn.0 > 3 {
+        for square in [&mut pawn, &mut strong_king, &mut weak_king] {
+            square.0 = 7 - square.0;
+        }
+    }
+
+    let strong_to_move = game.get_current_players_turn() == strong_side;
+    let index = get_kpk_index(strong_to_move, strong_king, weak_king, pawn);
+    Some(get_kpk_bitbase()[index] == WIN)
+}
+
+/// Returns the corners a lone king has to be driven into to be mated by a bishop and a
+/// knight, the two corners of the bishop's color.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+///
+/// # Returns
+///
+/// * `Some` - The two corners, as `(col, row)`.
+/// * `None` - If the game is not a king, bishop and knight against king ending.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::endgame::get_mating_corners;
+///
+/// // the bishop on c1 moves on dark squares, so the king is mated on a1 or h8
+/// let game = build_game_from_string("8/8/8/4k3/8/8/8/2B1KN2 w - - 0 1").unwrap();
+/// assert_eq!(Some([(0, 0), (7, 7)]), get_mating_corners(&game));
+/// ```
+pub fn get_mating_corners(game: &ChessGame) -> Option<[(usize, usize); 2]> {
+    let board = game.get_board();
+    let Some(Endgame::BishopAndKnight { .. }) = classify(board) else {
+        return None;
+    };
+    let (bishop, _) = board
+        .pieces()
+        .find(|(_, piece)| piece.get_piece_type() == PieceType::Bishop)?;
+    if (bishop.get_col() + bishop.get_row()) % 2 == 0 {
+        Some([(0, 0), (7, 7)])
+    } else {
+        Some([(7, 0), (0, 7)])
+    }
+}
+
+/// Scores a recognized ending for the engine, from the point of view of the side to move.
+///
+/// Drawn king and pawn endings score `0`. In won endings the strong side gets a bonus for
+/// pushing the lone king to the edge, or for a bishop and knight into a corner it can be
+/// mated in, and for bringing its own king close, so the search makes progress towards
+/// mate even when the mate is too far away to see.
+///
+/// # Arguments
+///
+/// * `game` - The game to score, the game is not modified.
+///
+/// # Returns
+///
+/// * `Some(i32)` - The score of the ending.
+/// * `None` - If the position is not a recognized ending, or is a rook ending, which is
+///   left to the normal evaluation.
+pub fn evaluate(game: &ChessGame) -> Option<i32> {
+    let board = game.get_board();
+    let endgame = classify(board)?;
+    let strong_side = endgame.get_strong_side()?;
+
+    let mut material = 0;
+    let (mut strong_king, mut weak_king) = ((0, 0), (0, 0));
+    let mut pawn_rank = 0;
+    for (position, piece) in board.pieces() {
+        let square = position.get_col_and_row();
+        match piece.get_piece_type() {
+            PieceType::King if piece.get_color() == strong_side => strong_king = square,
+            PieceType::King => weak_king = square,
+            PieceType::Pawn => pawn_rank = strong_side.relative_rank(square.1, 8) as i32,
+            _ => {}
+        }
+        material += piece_value(piece.get_piece_type());
+    }
+
+    let score = match endgame {
+        Endgame::KingAndPawn { .. } => {
+            if !probe_kpk(game)? {
+                return Some(0);
+            }
+            material + WON_ENDING_BONUS + pawn_rank * 20
+        }
+        Endgame::BishopAndKnight { .. } => {
+            let corner_distance = get_mating_corners(game)?
+                .iter()
+                .map(|corner| get_manhattan_distance(weak_king, *corner))
+                .min()?;
+            material + WON_ENDING_BONUS + (14 - corner_distance) * 20
+                - get_manhattan_distance(strong_king, weak_king) * 4
+        }
+        _ => {
+            material + WON_ENDING_BONUS + get_center_distance(weak_king) * 10
+                - get_manhattan_distance(strong_king, weak_king) * 4
+        }
+    };
+    if game.get_current_players_turn() == strong_side {
+        Some(score)
+    } else {
+        Some(-score)
+    }
+}
+
+fn get_piece_types(board: &Board<ChessPiece>, color: Color) -> Vec<PieceType> {
+    board
+        .pieces_of(color)
+        .map(|(_, piece)| piece.get_piece_type())
+        .collect()
+}
+
+fn count(pieces: &[PieceType], piece_type: PieceType) -> usize {
+    pieces.iter().filter(|other| **other == piece_type).count()
+}
+
+fn get_manhattan_distance(a: (usize, usize), b: (usize, usize)) -> i32 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as i32
+}
+
+/// Returns how far a square is from the four center squares, `0` in the center and `6` in
+/// the corners.
+fn get_center_distance(square: (usize, usize)) -> i32 {
+    let from_center = |coordinate: usize| {
+        if coordinate < 4 {
+            3 - coordinate
+        } else {
+            coordinate - 4
+        }
+    };
+    (from_center(square.0) + from_center(square.1)) as i32
+}
+
+fn get_chebyshev_distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+fn get_kpk_index(
+    strong_to_move: bool,
+    strong_king: (usize, usize),
+    weak_king: (usize, usize),
+    pawn: (usize, usize),
+) -> usize {
+    let strong_king = strong_king.1 * 8 + strong_king.0;
+    let weak_king = weak_king.1 * 8 + weak_king.0;
+    (((pawn.1 * 4 + pawn.0) * 64 + strong_king) * 64 + weak_king) * 2 + strong_to_move as usize
+}
+
+fn get_king_moves(square: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+    (-1i32..=1)
+        .flat_map(|col| (-1i32..=1).map(move |row| (col, row)))
+        .filter(|offset| *offset != (0, 0))
+        .filter_map(move |(col, row)| {
+            let col = square.0 as i32 + col;
+            let row = square.1 as i32 + row;
+            ((0..8).contains(&col) && (0..8).contains(&row)).then_some((col as usize, row as usize))
+        })
+}
+
+fn is_attacked_by_pawn(square: (usize, usize), pawn: (usize, usize)) -> bool {
+    square.1 == pawn.1 + 1 && square.0.abs_diff(pawn.0) == 1
+}
+
+fn get_kpk_bitbase() -> &'static [u8] {
+    KPK_BITBASE.get_or_init(build_kpk_bitbase)
+}
+
+/// Computes the KPK bitbase by retrograde analysis.
+///
+/// The positions decided by a single move are marked first, then every other position is
+/// decided from the positions its moves lead to until nothing changes. The positions left
+/// undecided are draws.
+fn build_kpk_bitbase() -> Vec<u8> {
+    let mut positions = Vec::with_capacity(KPK_SIZE / 2);
+    let mut bitbase = vec![INVALID; KPK_SIZE];
+    for pawn_row in 1..7 {
+        for pawn_col in 0..4 {
+            for strong_king in 0..64 {
+                for weak_king in 0..64 {
+                    for strong_to_move in [false, true] {
+                        let position = (
+                            strong_to_move,
+                            (strong_king % 8, strong_king / 8),
+                            (weak_king % 8, weak_king / 8),
+                            (pawn_col, pawn_row),
+                        );
+                        let state = get_initial_kpk_state(position);
+                        let (strong_to_move, strong_king, weak_king, pawn) = position;
+                        bitbase[get_kpk_index(strong_to_move, strong_king, weak_king, pawn)] =
+                            state;
+                        if state == UNKNOWN {
+                            positions.push(position);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &position in &positions {
+            let (strong_to_move, strong_king, weak_king, pawn) = position;
+            let index = get_kpk_index(strong_to_move, strong_king, weak_king, pawn);
+            if bitbase[index] == UNKNOWN {
+                let state = get_kpk_state(position, &bitbase);
+                if state != UNKNOWN {
+                    bitbase[index] = state;
+                    changed = true;
+                }
+            }
+        }
+    }
+    bitbase
+}
+
+type KpkPosition = (bool, (usize, usize), (usize, usize), (usize, usize));
+
+fn get_initial_kpk_state((strong_to_move, strong_king, weak_king, pawn): KpkPosition) -> u8 {
+    if get_chebyshev_distance(strong_king, weak_king) <= 1
+        || strong_king == pawn
+        || weak_king == pawn
+        || (strong_to_move && is_attacked_by_pawn(weak_king, pawn))
+    {
+        return INVALID;
+    }
+
+    let promotion_square = (pawn.0, pawn.1 + 1);
+    if strong_to_move
+        && pawn.1 == 6
+        && strong_king != promotion_square
+        && (get_chebyshev_distance(weak_king, promotion_square) > 1
+            || get_chebyshev_distance(strong_king, promotion_square) == 1)
+    {
+        // the pawn promotes and the new queen can not be taken
+        return WIN;
+    }
+
+    if !strong_to_move {
+        let has_safe_square = get_king_moves(weak_king).any(|square| {
+            get_chebyshev_distance(square, strong_king) > 1 && !is_attacked_by_pawn(square, pawn)
+        });
+        if !has_safe_square {
+            // checkmate or stalemate
+            return if is_attacked_by_pawn(weak_king, pawn) {
+                WIN
+            } else {
+                DRAW
+            };
+        }
+        if get_chebyshev_distance(weak_king, pawn) == 1
+            && get_chebyshev_distance(strong_king, pawn) > 1
+        {
+            // the pawn is taken
+            return DRAW;
+        }
+    }
+    UNKNOWN
+}
+
+fn get_kpk_state(
+    (strong_to_move, strong_king, weak_king, pawn): KpkPosition,
+    bitbase: &[u8],
+) -> u8 {
+    let mut reachable = INVALID;
+    if strong_to_move {
+        for square in get_king_moves(strong_king) {
+            reachable |= bitbase[get_kpk_index(false, square, weak_king, pawn)];
+        }
+        let push = (pawn.0, pawn.1 + 1);
+        if pawn.1 < 6 && push != strong_king && push != weak_king {
+            reachable |= bitbase[get_kpk_index(false, strong_king, weak_king, push)];
+            let double_push = (pawn.0, pawn.1 + 2);
+            if pawn.1 == 1 && double_push != strong_king && double_push != weak_king {
+                reachable |= bitbase[get_kpk_index(false, strong_king, weak_king, double_push)];
+            }
+        }
+        if reachable & WIN != 0 {
+            WIN
+        } else if reachable & UNKNOWN != 0 {
+            UNKNOWN
+        } else {
+            DRAW
+        }
+    } else {
+        for square in get_king_moves(weak_king) {
+            reachable |= bitbase[get_kpk_index(true, strong_king, square, pawn)];
+        }
+        if reachable & DRAW != 0 {
+            DRAW
+        } else if reachable & UNKNOWN != 0 {
+            UNKNOWN
+        } else {
+            WIN
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    fn probe(fen: &str) -> Option<bool> {
+        probe_kpk(&build_game_from_string(fen).unwrap())
+    }
+
+    #[test]
+    fn kpk_positions_are_looked_up_for_either_color() {
+        // a king on the sixth rank in front of its pawn always wins
+        assert_eq!(Some(true), probe("4k3/8/4K3/8/4P3/8/8/8 w - - 0 1"));
+        assert_eq!(Some(true), probe("8/8/8/2p5/8/2k5/8/2K5 w - - 0 1"));
+        // the defending king reaches the corner in front of a rook pawn
+        assert_eq!(Some(false), probe("k7/8/8/8/8/8/P7/K7 w - - 0 1"));
+        assert_eq!(Some(false), probe("8/8/8/8/8/8/7p/5K1k b - - 0 1"));
+        // the black king is too far away to stop the pawn
+        assert_eq!(Some(true), probe("8/8/8/8/P6k/8/8/K7 w - - 0 1"));
+        assert_eq!(None, probe("4k3/8/8/8/8/8/PP6/4K3 w - - 0 1"));
+    }
+
+    #[test]
+    fn lone_kings_are_scored_better_in_the_center() {
+        let centralized = build_game_from_string("8/8/8/3k4/8/8/8/R3K3 w - - 0 1").unwrap();
+        let cornered = build_game_from_string("k7/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+
+        assert!(evaluate(&cornered).unwrap() > evaluate(&centralized).unwrap());
+    }
+
+    #[test]
+    fn bishop_and_knight_drive_the_king_to_the_right_corner() {
+        // the bishop on c1 is dark squared, so a1 is a mating corner and h1 is not
+        let right_corner = build_game_from_string("8/8/8/8/8/8/2K5/k1B2N2 w - - 0 1").unwrap();
+        let wrong_corner = build_game_from_string("8/8/8/8/8/8/5K2/2B2N1k w - - 0 1").unwrap();
+
+        assert!(evaluate(&right_corner).unwrap() > evaluate(&wrong_corner).unwrap());
+    }
+}
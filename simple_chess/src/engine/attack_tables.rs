@@ -0,0 +1,164 @@
+use crate::square_name::SquareName;
+use crate::Color;
+
+/// The squares attacked by a knight on each square of an 8x8 board.
+///
+/// Each entry is a bitboard where bit `col + row * 8` is set for every attacked square, the
+/// same index as the `SquareName` discriminant. The table is computed at compile time, so
+/// looking up attacks needs no initialization at runtime.
+pub const KNIGHT_ATTACKS: [u64; 64] = build_table(&[
+    (1, 2),
+    (1, -2),
+    (-1, 2),
+    (-1, -2),
+    (2, 1),
+    (2, -1),
+    (-2, 1),
+    (-2, -1),
+]);
+
+/// The squares attacked by a king on each square of an 8x8 board, indexed like
+/// `KNIGHT_ATTACKS`.
+pub const KING_ATTACKS: [u64; 64] = build_table(&[
+    (0, 1),
+    (0, -1),
+    (1, 0),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+]);
+
+/// The squares attacked by a pawn on each square of an 8x8 board, White's table first then
+/// Black's, indexed like `KNIGHT_ATTACKS`.
+pub const PAWN_ATTACKS: [[u64; 64]; 2] = [
+    build_table(&[(-1, 1), (1, 1)]),
+    build_table(&[(-1, -1), (1, -1)]),
+];
+
+/// Returns the squares a knight on the given square attacks.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::attack_tables::knight_attacks;
+/// use simple_chess::square_name::SquareName;
+///
+/// let attacks = knight_attacks(SquareName::A1);
+/// assert_eq!(2, attacks.count_ones());
+/// assert_ne!(0, attacks & (1 << SquareName::B3 as u8));
+/// ```
+pub fn knight_attacks(square: SquareName) -> u64 {
+    KNIGHT_ATTACKS[square as usize]
+}
+
+/// Returns the squares a king on the given square attacks.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::attack_tables::king_attacks;
+/// use simple_chess::square_name::SquareName;
+///
+/// assert_eq!(8, king_attacks(SquareName::E4).count_ones());
+/// assert_eq!(3, king_attacks(SquareName::H8).count_ones());
+/// ```
+pub fn king_attacks(square: SquareName) -> u64 {
+    KING_ATTACKS[square as usize]
+}
+
+/// Returns the squares a pawn of the given color on the given square attacks.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::attack_tables::pawn_attacks;
+/// use simple_chess::square_name::SquareName;
+/// use simple_chess::Color;
+///
+/// let expected = (1 << SquareName::D5 as u8) | (1 << SquareName::F5 as u8);
+/// assert_eq!(expected, pawn_attacks(Color::White, SquareName::E4));
+/// ```
+pub fn pawn_attacks(color: Color, square: SquareName) -> u64 {
+    let index = match color {
+        Color::White => 0,
+        Color::Black => 1,
+    };
+    PAWN_ATTACKS[index][square as usize]
+}
+
+/// Returns the `(col, row)` of every square set in a bitboard of an 8x8 board, lowest
+/// index first.
+pub(crate) fn get_squares(mut bitboard: u64) -> impl Iterator<Item = (usize, usize)> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let square = bitboard.trailing_zeros() as usize;
+        bitboard &= bitboard - 1;
+        Some((square % 8, square / 8))
+    })
+}
+
+const fn build_table(offsets: &[(i32, i32)]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let col = (square % 8) as i32;
+        let row = (square / 8) as i32;
+        let mut i = 0;
+        while i < offsets.len() {
+            let target_col = col + offsets[i].0;
+            let target_row = row + offsets[i].1;
+            if target_col >= 0 && target_col < 8 && target_row >= 0 && target_row < 8 {
+                table[square] |= 1 << (target_col + target_row * 8);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bit(square: SquareName) -> u64 {
+        1 << square as u8
+    }
+
+    #[test]
+    fn knight_attacks_stay_on_the_board() {
+        assert_eq!(
+            bit(SquareName::B3) | bit(SquareName::C2),
+            knight_attacks(SquareName::A1)
+        );
+        assert_eq!(8, knight_attacks(SquareName::D4).count_ones());
+        assert_eq!(2, knight_attacks(SquareName::H8).count_ones());
+    }
+
+    #[test]
+    fn king_attacks_surround_the_square() {
+        assert_eq!(
+            bit(SquareName::A2) | bit(SquareName::B1) | bit(SquareName::B2),
+            king_attacks(SquareName::A1)
+        );
+        assert_eq!(5, king_attacks(SquareName::E1).count_ones());
+    }
+
+    #[test]
+    fn pawn_attacks_depend_on_color() {
+        assert_eq!(
+            bit(SquareName::B3),
+            pawn_attacks(Color::White, SquareName::A2)
+        );
+        assert_eq!(
+            bit(SquareName::D3) | bit(SquareName::F3),
+            pawn_attacks(Color::Black, SquareName::E4)
+        );
+        assert_eq!(0, pawn_attacks(Color::White, SquareName::E8));
+        assert_eq!(0, pawn_attacks(Color::Black, SquareName::E1));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::engine::{Engine, SearchResult};
+use crate::ChessMoveType;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Called by the engine each time it finishes searching a depth, see
+/// `Engine::set_info_callback`.
+pub type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+/// The progress of a search, reported after each completed depth.
+///
+/// Scores are in centipawns from the point of view of the side to move in the searched
+/// position, positive values favor the side to move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchInfo {
+    depth: u32,
+    score: i32,
+    nodes: u64,
+    elapsed: Duration,
+    principal_variation: Vec<ChessMoveType>,
+}
+
+impl SearchInfo {
+    pub(crate) fn new(
+        depth: u32,
+        score: i32,
+        nodes: u64,
+        elapsed: Duration,
+        principal_variation: Vec<ChessMoveType>,
+    ) -> Self {
+        Self {
+            depth,
+            score,
+            nodes,
+            elapsed,
+            principal_variation,
+        }
+    }
+
+    /// Returns the depth, in plies, that has just been completed.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the score of the position at this depth in centipawns.
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns the number of positions visited since the search started.
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Returns the time since the search started.
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of positions visited per second since the search started.
+    pub fn get_nodes_per_second(&self) -> u64 {
+        let millis = self.elapsed.as_millis().max(1) as u64;
+        self.nodes * 1000 / millis
+    }
+
+    /// Returns the sequence of moves the search expects to be played at this depth,
+    /// starting with the best move.
+    pub fn get_principal_variation(&self) -> &[ChessMoveType] {
+        &self.principal_variation
+    }
+}
+
+/// A search running on a background thread, started with `Engine::search_in_background` or
+/// `Engine::ponder`.
+///
+/// The search goes on deepening until it is stopped or reaches `MAX_SEARCH_DEPTH`. Dropping
+/// the handle stops the search without waiting for it.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::Engine;
+/// use simple_chess::ChessGame;
+/// use std::sync::mpsc;
+///
+/// let (sender, depths) = mpsc::channel();
+/// let engine = Engine::new().set_info_callback(move |info| {
+///     let _ = sender.send(info.get_depth());
+/// });
+/// let handle = engine.search_in_background(&ChessGame::new());
+///
+/// // let the engine think until it has finished the second depth
+/// while depths.recv().unwrap() < 2 {}
+/// let (_engine, result) = handle.stop();
+///
+/// assert!(result.get_depth() >= 2);
+/// assert!(result.get_best_move().is_some());
+/// ```
+pub struct SearchHandle {
+    stop_signal: Arc<AtomicBool>,
+    thread: Option<JoinHandle<(Engine, SearchResult)>>,
+    ponder_move: Option<ChessMoveType>,
+}
+
+impl SearchHandle {
+    pub(crate) fn new(
+        stop_signal: Arc<AtomicBool>,
+        thread: JoinHandle<(Engine, SearchResult)>,
+        ponder_move: Option<ChessMoveType>,
+    ) -> Self {
+        Self {
+            stop_signal,
+            thread: Some(thread),
+            ponder_move,
+        }
+    }
+
+    /// Stops the search and waits for the search thread to finish.
+    ///
+    /// The depth being searched when the search is stopped is thrown away, so the result is
+    /// that of the deepest completed depth. The first depth is always completed, so the
+    /// result has a best move unless the position has no legal moves.
+    ///
+    /// # Returns
+    ///
+    /// * `(Engine, SearchResult)` - The engine, with the transposition table filled by the
+    ///   search, and the result of the search.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search thread panicked, for example in a custom `Evaluator`.
+    pub fn stop(mut self) -> (Engine, SearchResult) {
+        self.stop_signal.store(true, Ordering::Relaxed);
+        self.thread
+            .take()
+            .expect("the search thread is only taken when stopping")
+            .join()
+            .expect("the search thread panicked")
+    }
+
+    /// Returns `true` if the search has finished on its own, so `stop` returns at once.
+    pub fn is_finished(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_none_or(|thread| thread.is_finished())
+    }
+
+    /// Returns `true` if the search is pondering on the opponent's expected reply.
+    pub fn is_pondering(&self) -> bool {
+        self.ponder_move.is_some()
+    }
+
+    /// Returns the expected reply of the opponent the search is pondering on, `None` if the
+    /// search is not pondering.
+    ///
+    /// If the opponent plays this move the search can go on and its result is the answer
+    /// to it, if they play any other move the search should be stopped and its result
+    /// thrown away.
+    pub fn get_ponder_move(&self) -> Option<&ChessMoveType> {
+        self.ponder_move.as_ref()
+    }
+}
+
+impl Drop for SearchHandle {
+    fn drop(&mut self) {
+        self.stop_signal.store(true, Ordering::Relaxed);
+    }
+}
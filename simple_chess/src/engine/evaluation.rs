@@ -0,0 +1,263 @@
+use crate::endgame;
+use crate::piece::{ChessPiece, PieceType};
+use crate::ChessGame;
+
+/// Scores a position for the engine's search.
+///
+/// Implement this trait to plug a custom evaluation into the `Engine` or the analysis
+/// helpers built on top of it.
+///
+/// Scores are in centipawns from the point of view of the side to move, positive values
+/// favor the side to move. The search handles checkmate and stalemate itself, so an
+/// evaluator only needs to score positions where the game is still going.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::evaluation::Evaluator;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::ChessGame;
+///
+/// /// Scores every position as equal.
+/// struct Indifferent;
+///
+/// impl Evaluator for Indifferent {
+///     fn evaluate(&self, _game: &ChessGame) -> i32 {
+///         0
+///     }
+/// }
+///
+/// let mut engine = Engine::new().set_evaluator(Indifferent);
+/// assert_eq!(0, engine.search(&ChessGame::new(), 2).get_score());
+/// ```
+pub trait Evaluator: Send + Sync {
+    /// Returns the score of the current position of the game.
+    fn evaluate(&self, game: &ChessGame) -> i32;
+}
+
+/// Scores positions by counting material only.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&self, game: &ChessGame) -> i32 {
+        sum_for_side_to_move(game, |piece, _, _| piece_value(piece.get_piece_type()))
+    }
+}
+
+/// The engine's default evaluation, material plus piece-square tables.
+///
+/// The piece-square tables reward pieces for standing on good squares, such as knights in
+/// the center and pawns close to promotion. They are written for an 8x8 board, on boards
+/// of any other size only material is counted.
+///
+/// Endings with a known way to play them, such as king and rook against king, are scored
+/// by `endgame::evaluate` instead, which knows which king and pawn endings are drawn and
+/// steers the search towards mate in the won ones.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::evaluation::{DefaultEvaluator, Evaluator};
+///
+/// let centralized = build_game_from_string("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1").unwrap();
+/// let cornered = build_game_from_string("4k3/8/8/8/8/8/8/N3K3 w - - 0 1").unwrap();
+///
+/// let evaluator = DefaultEvaluator;
+/// assert!(evaluator.evaluate(&centralized) > evaluator.evaluate(&cornered));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultEvaluator;
+
+impl Evaluator for DefaultEvaluator {
+    fn evaluate(&self, game: &ChessGame) -> i32 {
+        let board = game.get_board();
+        let use_tables = board.get_width() == 8 && board.get_height() == 8;
+        if use_tables {
+            if let Some(score) = endgame::evaluate(game) {
+                return score;
+            }
+        }
+
+        sum_for_side_to_move(game, |piece, col, row| {
+            let mut value = piece_value(piece.get_piece_type());
+            if use_tables {
+                value += get_piece_square_value(piece, col, row);
+            }
+            value
+        })
+    }
+}
+
+/// Returns the material value of a piece type in centipawns.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::evaluation::piece_value;
+/// use simple_chess::piece::PieceType;
+///
+/// assert_eq!(900, piece_value(PieceType::Queen));
+/// ```
+pub fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 100,
+        PieceType::Knight => 320,
+        PieceType::Bishop => 330,
+        PieceType::Rook => 500,
+        PieceType::Queen => 900,
+        PieceType::Archbishop => 850,
+        PieceType::Chancellor => 875,
+        PieceType::King => 0,
+    }
+}
+
+fn sum_for_side_to_move<F>(game: &ChessGame, value_of: F) -> i32
+where
+    F: Fn(&ChessPiece, usize, usize) -> i32,
+{
+    let board = game.get_board();
+    let side_to_move = game.get_current_players_turn();
+    let mut score = 0;
+    for row in 0..board.get_height() {
+        for col in 0..board.get_width() {
+            if let Some(piece) = board.get_piece_at_space(col, row) {
+                let value = value_of(piece, col, row);
+                if piece.get_color() == side_to_move {
+                    score += value;
+                } else {
+                    score -= value;
+                }
+            }
+        }
+    }
+    score
+}
+
+fn get_piece_square_value(piece: &ChessPiece, col: usize, row: usize) -> i32 {
+    // the tables are written from white's point of view with the eighth rank first
+    let index = (7 - piece.get_color().relative_rank(row, 8)) * 8 + col;
+    let table = match piece.get_piece_type() {
+        PieceType::Pawn => &PAWN_TABLE,
+        PieceType::Knight => &KNIGHT_TABLE,
+        PieceType::Bishop => &BISHOP_TABLE,
+        PieceType::Rook => &ROOK_TABLE,
+        PieceType::Queen => &QUEEN_TABLE,
+        // the compound pieces want the centre like the knights they partly are
+        PieceType::Archbishop | PieceType::Chancellor => &KNIGHT_TABLE,
+        PieceType::King => &KING_TABLE,
+    };
+    table[index]
+}
+
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const KING_TABLE: [i32; 64] = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn starting_position_is_balanced() {
+        let game = ChessGame::new();
+        assert_eq!(0, MaterialEvaluator.evaluate(&game));
+        assert_eq!(0, DefaultEvaluator.evaluate(&game));
+    }
+
+    #[test]
+    fn scores_are_from_the_side_to_move() {
+        let white_to_move = build_game_from_string("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+        let black_to_move = build_game_from_string("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap();
+
+        assert_eq!(900, MaterialEvaluator.evaluate(&white_to_move));
+        assert_eq!(-900, MaterialEvaluator.evaluate(&black_to_move));
+        assert_eq!(
+            DefaultEvaluator.evaluate(&white_to_move),
+            -DefaultEvaluator.evaluate(&black_to_move)
+        );
+    }
+
+    #[test]
+    fn tables_are_mirrored_for_black() {
+        // the knights keep the positions out of the king and pawn ending, which is drawn
+        let white_pawn = build_game_from_string("4k1n1/8/3P4/8/8/8/8/4K1N1 w - - 0 1").unwrap();
+        let black_pawn = build_game_from_string("4k1n1/8/8/8/8/3p4/8/4K1N1 b - - 0 1").unwrap();
+
+        assert_eq!(
+            DefaultEvaluator.evaluate(&white_pawn),
+            DefaultEvaluator.evaluate(&black_pawn)
+        );
+        assert!(DefaultEvaluator.evaluate(&white_pawn) > 100);
+    }
+}
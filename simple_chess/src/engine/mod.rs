@@ -0,0 +1,1422 @@
+pub mod attack_tables;
+pub mod background;
+pub mod evaluation;
+pub mod move_ordering;
+pub mod search_limits;
+pub mod skill_level;
+pub mod snapshot;
+pub mod static_exchange;
+pub mod test_suite;
+pub mod time_manager;
+pub mod transposition_table;
+
+use crate::chess_game_move_analyzer::get_legal_moves;
+use crate::chess_game_state_analyzer::is_current_player_in_check;
+use crate::codec::long_algebraic_notation::encode_move_as_string;
+use crate::engine::background::{InfoCallback, SearchHandle, SearchInfo};
+use crate::engine::evaluation::{DefaultEvaluator, Evaluator};
+use crate::engine::move_ordering::{mvv_lva, MoveOrderer, MoveOrdering};
+use crate::engine::search_limits::SearchLimits;
+use crate::engine::skill_level::SkillLevel;
+use crate::engine::snapshot::AnalysisSnapshot;
+use crate::engine::static_exchange::see_capture;
+use crate::engine::time_manager::TimeLimits;
+use crate::engine::transposition_table::{Bound, TranspositionEntry, TranspositionTable};
+use crate::error::ChessError;
+use crate::random_position::PositionRng;
+use crate::zobrist::hash_position;
+use crate::{ChessGame, ChessMoveType};
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// The score of a position where the side to move has been checkmated.
+///
+/// Mate scores are adjusted by the number of plies to the mate, so a faster mate
+/// always scores better than a slower one.
+pub const MATE_SCORE: i32 = 1_000_000;
+
+const MATE_THRESHOLD: i32 = MATE_SCORE - 1000;
+
+/// The deepest a search without a fixed depth goes before it stops on its own.
+pub const MAX_SEARCH_DEPTH: u32 = 64;
+
+/// How many positions are visited between checks of whether a search has been stopped or
+/// run out of time.
+const STOP_CHECK_INTERVAL: u64 = 1024;
+
+/// The result of a search.
+///
+/// Scores are in centipawns from the point of view of the side to move in the
+/// searched position, positive values favor the side to move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    best_move: Option<ChessMoveType>,
+    score: i32,
+    principal_variation: Vec<ChessMoveType>,
+    depth: u32,
+    nodes: u64,
+}
+
+impl SearchResult {
+    /// Returns the best move found, `None` if the position has no legal moves.
+    pub fn get_best_move(&self) -> Option<&ChessMoveType> {
+        self.best_move.as_ref()
+    }
+
+    /// Returns the score of the position in centipawns.
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns the sequence of moves the search expects to be played, starting with the
+    /// best move.
+    pub fn get_principal_variation(&self) -> &[ChessMoveType] {
+        &self.principal_variation
+    }
+
+    /// Returns the depth, in plies, the position was searched to.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the number of positions visited during the search.
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Returns the reply the search expects from the opponent after the best move, which
+    /// the engine can ponder on while the opponent thinks, see `Engine::ponder`.
+    pub fn get_ponder_move(&self) -> Option<&ChessMoveType> {
+        self.principal_variation.get(1)
+    }
+
+    /// Returns `true` if the score is a forced mate for either side.
+    pub fn is_mate_score(&self) -> bool {
+        self.score.abs() > MATE_THRESHOLD
+    }
+}
+
+/// One of the best lines of a multi-PV analysis, see `Engine::analyse`.
+///
+/// Scores are in centipawns from the point of view of the side to move in the analysed
+/// position, positive values favor the side to move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    rank: usize,
+    score: i32,
+    principal_variation: Vec<ChessMoveType>,
+}
+
+impl Line {
+    /// Returns the rank of the line, `1` for the best line.
+    pub fn get_rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Returns the first move of the line.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.principal_variation[0]
+    }
+
+    /// Returns the score of the line in centipawns.
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns the moves the search expects to be played, starting with the move of the
+    /// line.
+    pub fn get_principal_variation(&self) -> &[ChessMoveType] {
+        &self.principal_variation
+    }
+
+    /// Returns `true` if the score is a forced mate for either side.
+    pub fn is_mate_score(&self) -> bool {
+        self.score.abs() > MATE_THRESHOLD
+    }
+}
+
+/// A simple chess engine searching the legal move tree with alpha-beta pruning and a
+/// transposition table.
+///
+/// Once the search depth runs out the engine keeps searching captures and promotions until
+/// the position is quiet, so it never stops in the middle of an exchange.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::Engine;
+///
+/// // white can win the undefended queen
+/// let game = build_game_from_string("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+/// let result = Engine::new().search(&game, 2);
+///
+/// assert_eq!((3, 4), result.get_best_move().unwrap().get_new_position());
+/// ```
+pub struct Engine {
+    nodes: u64,
+    depth: u32,
+    evaluator: Box<dyn Evaluator>,
+    move_ordering: Box<dyn MoveOrdering>,
+    transposition_table: TranspositionTable,
+    info_callback: Option<InfoCallback>,
+    stop_signal: Option<Arc<AtomicBool>>,
+    soft_deadline: Option<Instant>,
+    hard_deadline: Option<Instant>,
+    node_limit: Option<u64>,
+    seed: Option<u64>,
+    rng: Option<PositionRng>,
+    is_deterministic: bool,
+    skill_level: Option<SkillLevel>,
+    can_stop: bool,
+    is_stopped: bool,
+}
+
+impl Engine {
+    /// Creates a new `Engine` with a default search depth of 3 plies, scoring positions with
+    /// the `DefaultEvaluator` and ordering moves with a `MoveOrderer`.
+    pub fn new() -> Self {
+        Self {
+            nodes: 0,
+            depth: 3,
+            evaluator: Box::new(DefaultEvaluator),
+            move_ordering: Box::new(MoveOrderer::new()),
+            transposition_table: TranspositionTable::default(),
+            info_callback: None,
+            stop_signal: None,
+            soft_deadline: None,
+            hard_deadline: None,
+            node_limit: None,
+            seed: None,
+            rng: None,
+            is_deterministic: false,
+            skill_level: None,
+            can_stop: false,
+            is_stopped: false,
+        }
+    }
+
+    /// Sets the transposition table used to remember positions that have already been
+    /// searched.
+    ///
+    /// The table is kept between searches, so positions searched for one move can be
+    /// reused when searching the next.
+    ///
+    /// # Arguments
+    ///
+    /// * `transposition_table` - The `TranspositionTable` to use, a table created with a
+    ///   size of `0` disables it.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the transposition table set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::transposition_table::TranspositionTable;
+    /// use simple_chess::engine::Engine;
+    ///
+    /// let engine = Engine::new().set_transposition_table(TranspositionTable::with_capacity_mb(64));
+    /// ```
+    pub fn set_transposition_table(mut self, transposition_table: TranspositionTable) -> Self {
+        self.transposition_table = transposition_table;
+        self
+    }
+
+    /// Returns the transposition table used by the search.
+    pub fn get_transposition_table(&self) -> &TranspositionTable {
+        &self.transposition_table
+    }
+
+    /// Removes every position from the transposition table, for example when starting a
+    /// new game.
+    pub fn clear_transposition_table(&mut self) {
+        self.transposition_table.clear();
+    }
+
+    /// Sets the evaluation used to score positions at the end of the search.
+    ///
+    /// # Arguments
+    ///
+    /// * `evaluator` - The `Evaluator` to use.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the evaluator set.
+    pub fn set_evaluator<E: Evaluator + 'static>(mut self, evaluator: E) -> Self {
+        self.evaluator = Box::new(evaluator);
+        self
+    }
+
+    /// Sets the order the moves of each position are searched in.
+    ///
+    /// The ordering is cleared at the start of every search. The move stored for a
+    /// position in the transposition table is always searched first.
+    ///
+    /// # Arguments
+    ///
+    /// * `move_ordering` - The `MoveOrdering` to use.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the move ordering set.
+    pub fn set_move_ordering<M: MoveOrdering + 'static>(mut self, move_ordering: M) -> Self {
+        self.move_ordering = Box::new(move_ordering);
+        self
+    }
+
+    /// Sets a callback told about the progress of every search, called each time a depth
+    /// has been searched, such as to print `info` lines for a GUI.
+    ///
+    /// # Arguments
+    ///
+    /// * `info_callback` - The callback, given the `SearchInfo` of the completed depth.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the callback set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut engine = Engine::new().set_info_callback(|info| {
+    ///     println!("info depth {} score cp {} nodes {}", info.get_depth(), info.get_score(), info.get_nodes());
+    /// });
+    /// engine.search(&ChessGame::new(), 2);
+    /// ```
+    pub fn set_info_callback<F: FnMut(&SearchInfo) + Send + 'static>(
+        mut self,
+        info_callback: F,
+    ) -> Self {
+        self.info_callback = Some(Box::new(info_callback));
+        self
+    }
+
+    /// Breaks ties between moves at random, so the engine does not always play the same
+    /// move among moves it finds equally good.
+    ///
+    /// The moves of every position are shuffled before they are ordered, so moves the
+    /// `MoveOrdering` scores the same are searched in a random order, and the first of
+    /// several equally good moves is kept. Without a seed the moves are never shuffled.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the seed set.
+    pub fn set_random_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = Some(PositionRng::new(seed));
+        self
+    }
+
+    /// Makes every search independent of the searches before it, so the same search of the
+    /// same position always gives the same result, for tests and regression comparisons.
+    ///
+    /// A deterministic engine clears its transposition table and starts its random number
+    /// generator from the seed again at the start of every search. Searches run on the
+    /// calling thread, but a search stopped by a time limit or with
+    /// `SearchHandle::stop` can still stop at a different point each time, so
+    /// reproducible searches should be limited by depth or nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_deterministic` - `true` to make every search independent.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with deterministic mode set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::search_limits::SearchLimits;
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut engine = Engine::new().set_random_seed(7).set_deterministic(true);
+    /// let limits = SearchLimits::new().set_nodes(2_000);
+    ///
+    /// let first = engine.search_with_limits(&ChessGame::new(), &limits);
+    /// let second = engine.search_with_limits(&ChessGame::new(), &limits);
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn set_deterministic(mut self, is_deterministic: bool) -> Self {
+        self.is_deterministic = is_deterministic;
+        self
+    }
+
+    /// Returns `true` if every search is independent of the searches before it.
+    pub fn is_deterministic(&self) -> bool {
+        self.is_deterministic
+    }
+
+    /// Weakens the engine to play at a skill level, see `SkillLevel`.
+    ///
+    /// # Arguments
+    ///
+    /// * `skill_level` - The `SkillLevel` to play at.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the skill level set.
+    pub fn set_skill_level(mut self, skill_level: SkillLevel) -> Self {
+        self.skill_level = Some(skill_level);
+        self
+    }
+
+    /// Returns the skill level the engine plays at, `None` if it plays at full strength.
+    pub fn get_skill_level(&self) -> Option<&SkillLevel> {
+        self.skill_level.as_ref()
+    }
+
+    /// Sets the depth used by analysis helpers that do not take a depth of their own.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The number of plies to search.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Engine` with the depth set.
+    pub fn set_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Returns the depth used by analysis helpers that do not take a depth of their own.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Searches the current position of a game to a fixed depth.
+    ///
+    /// The position is searched with iterative deepening, one ply deeper at a time up to
+    /// `depth`, reusing the transposition table between iterations.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is searched, the game is not modified.
+    /// * `depth` - The number of plies to search.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchResult` - The best move, score and principal variation found.
+    pub fn search(&mut self, game: &ChessGame, depth: u32) -> SearchResult {
+        self.start_search();
+        let depth = self.limit_depth(depth);
+        self.deepen(game, depth.min(1), depth)
+    }
+
+    /// Chooses the move to play in the current position of a game.
+    ///
+    /// At full strength this is the best move of a search to the engine's depth. With a
+    /// skill level the search goes no deeper than the skill's depth, and with the skill's
+    /// mistake chance a random move scoring within the mistake margin of the best move is
+    /// chosen instead. The chance is drawn from the engine's random seed if one is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is searched, the game is not modified.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ChessMoveType)` - The move to play.
+    /// * `None` - If the position has no legal moves.
+    pub fn choose_move(&mut self, game: &ChessGame) -> Option<ChessMoveType> {
+        let Some(skill_level) = self
+            .skill_level
+            .filter(|skill| skill.get_mistake_chance() > 0)
+        else {
+            return self.search(game, self.depth).best_move;
+        };
+
+        let results = self.search_root_moves(game, self.depth);
+        let best = results.first()?;
+        let mut time_seeded_rng = None;
+        let rng = match self.rng.as_mut() {
+            Some(rng) => rng,
+            None => time_seeded_rng.insert(PositionRng::new(get_time_seed())),
+        };
+        if (rng.next_below(100) as u32) < skill_level.get_mistake_chance() {
+            let mistakes: Vec<&SearchResult> = results[1..]
+                .iter()
+                .filter(|result| best.score - result.score <= skill_level.get_mistake_margin())
+                .collect();
+            if !mistakes.is_empty() {
+                return mistakes[rng.next_below(mistakes.len())].best_move;
+            }
+        }
+        best.best_move
+    }
+
+    /// Searches the current position of a game until one of the limits is reached.
+    ///
+    /// The position is searched one ply deeper at a time, see `SearchLimits` for when the
+    /// search stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is searched, the game is not modified.
+    /// * `limits` - When to stop the search.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchResult` - The best move, score and principal variation of the deepest
+    ///   completed depth.
+    pub fn search_with_limits(&mut self, game: &ChessGame, limits: &SearchLimits) -> SearchResult {
+        self.start_search();
+        if let Some(time_limits) = limits.get_time_limits() {
+            let start = Instant::now();
+            self.soft_deadline = Some(start + time_limits.get_soft_limit());
+            self.hard_deadline = Some(start + time_limits.get_hard_limit());
+        }
+        self.node_limit = limits.get_nodes();
+
+        let depth = self.limit_depth(limits.get_depth().unwrap_or(MAX_SEARCH_DEPTH));
+        let result = self.deepen(game, depth.min(1), depth);
+        self.soft_deadline = None;
+        self.hard_deadline = None;
+        self.node_limit = None;
+        result
+    }
+
+    /// Searches the current position of a game for as long as the time limits allow.
+    ///
+    /// The position is searched one ply deeper at a time, no further depth is started once
+    /// the soft limit has passed, and the depth being searched is given up once the hard
+    /// limit has passed. The first depth is always completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is searched, the game is not modified.
+    /// * `time_limits` - How long the search may take, usually from a `TimeManager`.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchResult` - The best move, score and principal variation of the deepest
+    ///   completed depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::clock::{ChessClock, TimeControl};
+    /// use simple_chess::engine::time_manager::TimeManager;
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::{ChessGame, Color};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let clock = ChessClock::new(TimeControl::new(Duration::from_secs(3), Duration::ZERO));
+    /// let limits = TimeManager::from_clock(&clock, Color::White).get_limits();
+    ///
+    /// let start = Instant::now();
+    /// let result = Engine::new().search_with_time_limits(&ChessGame::new(), limits);
+    ///
+    /// assert!(result.get_best_move().is_some());
+    /// assert!(start.elapsed() < Duration::from_secs(3));
+    /// ```
+    pub fn search_with_time_limits(
+        &mut self,
+        game: &ChessGame,
+        time_limits: TimeLimits,
+    ) -> SearchResult {
+        self.search_with_limits(game, &SearchLimits::new().set_time_limits(time_limits))
+    }
+
+    /// Starts searching the current position of a game on a background thread, like the
+    /// `go infinite` command of UCI.
+    ///
+    /// The search deepens one ply at a time until it is stopped with `SearchHandle::stop`
+    /// or reaches `MAX_SEARCH_DEPTH`, reporting each completed depth to the info callback.
+    /// The engine is moved onto the search thread and handed back when the search is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is searched, the game is not modified.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchHandle` - The handle to stop the search with.
+    pub fn search_in_background(self, game: &ChessGame) -> SearchHandle {
+        self.spawn_search(game.clone(), None)
+    }
+
+    /// Starts searching the position after the opponent's expected reply on a background
+    /// thread, so the engine thinks while the opponent does.
+    ///
+    /// If the opponent plays the expected reply the search can go on as the search for the
+    /// engine's answer, otherwise it should be stopped and its result thrown away. The
+    /// expected reply is usually the `SearchResult::get_ponder_move` of the engine's last
+    /// search.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game where it is the opponent's turn, the game is not modified.
+    /// * `expected_reply` - The move the opponent is expected to play.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SearchHandle)` - The handle to stop the search with.
+    /// * `Err(ChessError::IllegalMove)` - If the expected reply is not a legal move in the
+    ///   current position of the game.
+    /// * `Err(ChessError::GameOver)` - If the game has ended by resignation or agreement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let mut game = ChessGame::new();
+    /// let mut engine = Engine::new();
+    /// let result = engine.search(&game, 3);
+    /// game.make_move(*result.get_best_move().unwrap());
+    ///
+    /// let expected_reply = *result.get_ponder_move().unwrap();
+    /// let handle = engine.ponder(&game, expected_reply).unwrap();
+    /// assert_eq!(Some(&expected_reply), handle.get_ponder_move());
+    ///
+    /// // the opponent played the expected reply, so the search answers it
+    /// game.make_move(expected_reply);
+    /// let (_engine, answer) = handle.stop();
+    /// assert!(game.legal_moves().contains(answer.get_best_move().unwrap()));
+    /// ```
+    pub fn ponder(
+        self,
+        game: &ChessGame,
+        expected_reply: ChessMoveType,
+    ) -> Result<SearchHandle, ChessError> {
+        let mut game = game.clone();
+        if game.get_ending().is_some() {
+            return Err(ChessError::GameOver);
+        }
+        if !game.is_move_legal(&expected_reply) {
+            return Err(ChessError::IllegalMove(encode_move_as_string(
+                &expected_reply,
+            )));
+        }
+        game.apply_move(expected_reply);
+        Ok(self.spawn_search(game, Some(expected_reply)))
+    }
+
+    fn spawn_search(mut self, game: ChessGame, ponder_move: Option<ChessMoveType>) -> SearchHandle {
+        let stop_signal = Arc::new(AtomicBool::new(false));
+        self.stop_signal = Some(Arc::clone(&stop_signal));
+        let thread = thread::spawn(move || {
+            self.start_search();
+            let depth = self.limit_depth(MAX_SEARCH_DEPTH);
+            let result = self.deepen(&game, 1, depth);
+            self.stop_signal = None;
+            (self, result)
+        });
+        SearchHandle::new(stop_signal, thread, ponder_move)
+    }
+
+    /// Resets the node count and move ordering, and in deterministic mode everything else a
+    /// search learns from the searches before it.
+    fn start_search(&mut self) {
+        self.nodes = 0;
+        self.move_ordering.clear();
+        if self.is_deterministic {
+            self.transposition_table.clear();
+            self.rng = self.seed.map(PositionRng::new);
+        }
+    }
+
+    /// Lowers a depth to the depth of the skill level, if there is one.
+    fn limit_depth(&self, depth: u32) -> u32 {
+        self.skill_level
+            .map_or(depth, |skill_level| depth.min(skill_level.get_depth()))
+    }
+
+    /// Returns the evaluation of a position, with the noise of the skill level added.
+    fn evaluate(&self, game: &ChessGame) -> i32 {
+        let score = self.evaluator.evaluate(game);
+        let noise = self
+            .skill_level
+            .map_or(0, |skill_level| skill_level.get_evaluation_noise());
+        if noise == 0 {
+            return score;
+        }
+        let key = hash_position(game) ^ self.seed.unwrap_or_default();
+        let random = PositionRng::new(key).next_u64() % (2 * noise as u64 + 1);
+        score + random as i32 - noise
+    }
+
+    /// Searches each depth from `first_depth` to `depth` in turn, counting nodes on top of
+    /// the nodes already counted.
+    ///
+    /// A stopped search returns the result of the deepest completed depth.
+    fn deepen(&mut self, game: &ChessGame, first_depth: u32, depth: u32) -> SearchResult {
+        chess_span!("search", depth);
+        let mut game = game.clone();
+        chess_count!(Clones, 1);
+        let start = Instant::now();
+
+        // searching each depth in turn fills the transposition table with best moves that
+        // are searched first at the next depth, which makes the final search much cheaper
+        let mut score = 0;
+        let mut principal_variation = Vec::new();
+        let mut completed_depth = first_depth.saturating_sub(1);
+        for iteration_depth in first_depth..=depth {
+            chess_span!("search_depth", depth = iteration_depth);
+            // the first depth is always completed, so a stopped search still has a move
+            self.can_stop = iteration_depth > first_depth;
+            let (iteration_score, iteration_line) = self.negamax(
+                &mut game,
+                iteration_depth,
+                0,
+                -MATE_SCORE - 1,
+                MATE_SCORE + 1,
+            );
+            if self.is_stopped {
+                break;
+            }
+            (score, principal_variation) = (iteration_score, iteration_line);
+            completed_depth = iteration_depth;
+            chess_log!(
+                Debug,
+                "searched depth {iteration_depth}: score {score}, {} nodes",
+                self.nodes
+            );
+
+            if let Some(info_callback) = self.info_callback.as_mut() {
+                info_callback(&SearchInfo::new(
+                    iteration_depth,
+                    score,
+                    self.nodes,
+                    start.elapsed(),
+                    principal_variation.clone(),
+                ));
+            }
+            if self
+                .soft_deadline
+                .is_some_and(|soft_deadline| Instant::now() >= soft_deadline)
+            {
+                break;
+            }
+        }
+        self.can_stop = false;
+        self.is_stopped = false;
+
+        SearchResult {
+            best_move: principal_variation.first().copied(),
+            score,
+            principal_variation,
+            depth: completed_depth,
+            nodes: self.nodes,
+        }
+    }
+
+    /// Checks whether the search has reached its node limit, and every few positions whether
+    /// it has been told to stop or has passed its hard time limit, returning `true` once it
+    /// has.
+    fn should_stop(&mut self) -> bool {
+        if !self.is_stopped
+            && self.can_stop
+            && self.node_limit.is_some_and(|limit| self.nodes > limit)
+        {
+            self.is_stopped = true;
+        }
+        if !self.is_stopped && self.can_stop && self.nodes.is_multiple_of(STOP_CHECK_INTERVAL) {
+            self.is_stopped = self
+                .stop_signal
+                .as_ref()
+                .is_some_and(|stop_signal| stop_signal.load(Ordering::Relaxed))
+                || self
+                    .hard_deadline
+                    .is_some_and(|hard_deadline| Instant::now() >= hard_deadline);
+        }
+        self.is_stopped
+    }
+
+    /// Saves the state of a search so the analysis can be resumed later with
+    /// `resume_analysis`, see `AnalysisSnapshot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position was searched.
+    /// * `result` - The result of searching the position with this engine.
+    pub fn save_analysis(&self, game: &ChessGame, result: &SearchResult) -> AnalysisSnapshot {
+        AnalysisSnapshot::new(
+            game.clone(),
+            result.clone(),
+            self.transposition_table.clone(),
+        )
+    }
+
+    /// Carries on the search of a saved analysis to a greater depth.
+    ///
+    /// The engine's transposition table is replaced with the table of the snapshot, and
+    /// only the depths after the snapshot's depth are searched. The nodes of the result
+    /// include the nodes searched before the snapshot was taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - The saved analysis.
+    /// * `depth` - The number of plies to search, if the snapshot was already searched to
+    ///   this depth its result is returned without searching.
+    ///
+    /// # Returns
+    ///
+    /// * `SearchResult` - The best move, score and principal variation found.
+    pub fn resume_analysis(&mut self, snapshot: &AnalysisSnapshot, depth: u32) -> SearchResult {
+        self.transposition_table = snapshot.get_transposition_table().clone();
+        if depth <= snapshot.get_depth() {
+            return snapshot.get_result().clone();
+        }
+        self.nodes = snapshot.get_result().nodes;
+        self.deepen(snapshot.get_game(), snapshot.get_depth() + 1, depth)
+    }
+
+    /// Searches every legal move of the current position of a game.
+    ///
+    /// Unlike `search`, no move is pruned at the root, so every legal move receives an exact
+    /// score. This is slower than `search` but allows ranking all of the options.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is searched, the game is not modified.
+    /// * `depth` - The number of plies to search, including the root move.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<SearchResult>` - One result per legal move, with the principal variation
+    ///   starting with that move, ordered from best to worst.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let results = Engine::new().search_root_moves(&ChessGame::new(), 1);
+    /// assert_eq!(20, results.len());
+    /// ```
+    pub fn search_root_moves(&mut self, game: &ChessGame, depth: u32) -> Vec<SearchResult> {
+        self.start_search();
+        let mut game = game.clone();
+        let depth = self.limit_depth(depth).max(1);
+        let mut results: Vec<SearchResult> = get_legal_moves(&mut game)
+            .into_iter()
+            .map(|chess_move| {
+                self.nodes = 0;
+                let mut next = game.clone();
+                next.apply_move(chess_move);
+                let (score, line) =
+                    self.negamax(&mut next, depth - 1, 1, -MATE_SCORE - 1, MATE_SCORE + 1);
+
+                let mut principal_variation = vec![chess_move];
+                principal_variation.extend(line);
+                SearchResult {
+                    best_move: Some(chess_move),
+                    score: -score,
+                    principal_variation,
+                    depth,
+                    nodes: self.nodes,
+                }
+            })
+            .collect();
+
+        results.sort_by_key(|result| Reverse(result.score));
+        results
+    }
+
+    /// Finds the best lines of the current position of a game, each starting with a
+    /// different move.
+    ///
+    /// The best line is found first, then the position is searched again without its first
+    /// move to find the second best line, and so on. Each search deepens one ply at a time up
+    /// to `depth` like `search`, and only the best line of each is searched exactly, which
+    /// makes this faster than `search_root_moves` when only a few lines are wanted.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose current position is analysed, the game is not modified.
+    /// * `depth` - The number of plies to search, including the first move of each line.
+    /// * `multipv` - The number of lines to find.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Line>` - Up to `multipv` lines, the best first, fewer when the position has
+    ///   fewer legal moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::engine::Engine;
+    ///
+    /// // Ra8 mates, any other move lets Black live on
+    /// let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    /// let lines = Engine::new().analyse(&game, 2, 3);
+    ///
+    /// assert_eq!(3, lines.len());
+    /// assert_eq!((0, 7), lines[0].get_move().get_new_position());
+    /// assert!(lines[0].is_mate_score());
+    /// assert!(!lines[1].is_mate_score());
+    /// assert!(lines[1].get_score() >= lines[2].get_score());
+    /// ```
+    pub fn analyse(&mut self, game: &ChessGame, depth: u32, multipv: usize) -> Vec<Line> {
+        self.start_search();
+        let mut game = game.clone();
+        let mut remaining_moves = get_legal_moves(&mut game);
+
+        let mut lines = Vec::new();
+        while lines.len() < multipv && !remaining_moves.is_empty() {
+            let (score, principal_variation) = self.search_root(
+                &mut game,
+                &mut remaining_moves,
+                self.limit_depth(depth).max(1),
+            );
+            remaining_moves.retain(|chess_move| *chess_move != principal_variation[0]);
+            lines.push(Line {
+                rank: lines.len() + 1,
+                score,
+                principal_variation,
+            });
+        }
+        lines
+    }
+
+    /// Searches the position from the given root moves with iterative deepening, moving the
+    /// best move of each depth to the front of the moves.
+    fn search_root(
+        &mut self,
+        game: &mut ChessGame,
+        root_moves: &mut [ChessMoveType],
+        depth: u32,
+    ) -> (i32, Vec<ChessMoveType>) {
+        let mut best = (0, Vec::new());
+        for iteration_depth in 1..=depth {
+            let mut alpha = -MATE_SCORE - 1;
+            let mut best_line = Vec::new();
+            for &chess_move in root_moves.iter() {
+                let mut next = game.clone();
+                chess_count!(Clones, 1);
+                next.apply_move(chess_move);
+                let (score, line) =
+                    self.negamax(&mut next, iteration_depth - 1, 1, -MATE_SCORE - 1, -alpha);
+                let score = -score;
+                if score > alpha {
+                    alpha = score;
+                    best_line = Vec::with_capacity(line.len() + 1);
+                    best_line.push(chess_move);
+                    best_line.extend(line);
+                }
+            }
+            if let Some(index) = root_moves.iter().position(|m| *m == best_line[0]) {
+                root_moves[..=index].rotate_right(1);
+            }
+            best = (alpha, best_line);
+        }
+        best
+    }
+
+    fn negamax(
+        &mut self,
+        game: &mut ChessGame,
+        depth: u32,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> (i32, Vec<ChessMoveType>) {
+        self.nodes += 1;
+        chess_count!(Nodes, 1);
+        if self.should_stop() {
+            return (0, Vec::new());
+        }
+
+        if ply > 0 && game.can_claim_draw().is_some() {
+            return (0, Vec::new());
+        }
+
+        let key = hash_position(game);
+        let mut table_move = None;
+        let entry = self.transposition_table.probe(key);
+        chess_count!(CacheHits, entry.is_some());
+        chess_count!(CacheMisses, entry.is_none());
+        if let Some(entry) = entry {
+            table_move = entry.get_best_move().copied();
+            if ply > 0 && entry.get_depth() >= depth {
+                let score = score_from_table(entry.get_score(), ply);
+                let is_cutoff = match entry.get_bound() {
+                    Bound::Exact => true,
+                    Bound::Lower => score >= beta,
+                    Bound::Upper => score <= alpha,
+                };
+                if is_cutoff {
+                    let line = if entry.get_bound() == Bound::Exact {
+                        self.get_line_from_table(game, depth)
+                    } else {
+                        Vec::new()
+                    };
+                    return (score, line);
+                }
+            }
+        }
+
+        let mut legal_moves = get_legal_moves(game);
+        if legal_moves.is_empty() {
+            return (get_score_without_moves(game, ply), Vec::new());
+        }
+
+        if depth == 0 {
+            let score = self.search_captures(game, legal_moves, ply, alpha, beta);
+            return (score, Vec::new());
+        }
+
+        if let Some(rng) = self.rng.as_mut() {
+            shuffle(&mut legal_moves, rng);
+        }
+        self.move_ordering
+            .order_moves(game, &mut legal_moves, ply as usize);
+        // searching the best move from a previous search first produces more cutoffs
+        if let Some(table_move) = table_move {
+            if let Some(index) = legal_moves.iter().position(|m| *m == table_move) {
+                legal_moves[..=index].rotate_right(1);
+            }
+        }
+
+        let original_alpha = alpha;
+        let mut best_score = -MATE_SCORE - 1;
+        let mut best_line: Vec<ChessMoveType> = Vec::new();
+        for chess_move in legal_moves {
+            let mut next = game.clone();
+            chess_count!(Clones, 1);
+            next.apply_move(chess_move);
+            let (score, line) = self.negamax(&mut next, depth - 1, ply + 1, -beta, -alpha);
+            if self.is_stopped {
+                // the scores of a stopped search are not to be trusted or remembered
+                return (0, Vec::new());
+            }
+            let score = -score;
+
+            if score > best_score {
+                best_score = score;
+                best_line = Vec::with_capacity(line.len() + 1);
+                best_line.push(chess_move);
+                best_line.extend(line);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                self.move_ordering
+                    .record_cutoff(game, &chess_move, depth, ply as usize);
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.transposition_table.store(TranspositionEntry::new(
+            key,
+            depth,
+            bound,
+            score_to_table(best_score, ply),
+            best_line.first().copied(),
+        ));
+
+        (best_score, best_line)
+    }
+
+    /// Searches only captures and promotions until the position is quiet.
+    fn quiescence(&mut self, game: &mut ChessGame, ply: i32, alpha: i32, beta: i32) -> i32 {
+        self.nodes += 1;
+        chess_count!(Nodes, 1);
+        if self.should_stop() {
+            return 0;
+        }
+
+        let legal_moves = get_legal_moves(game);
+        if legal_moves.is_empty() {
+            return get_score_without_moves(game, ply);
+        }
+        self.search_captures(game, legal_moves, ply, alpha, beta)
+    }
+
+    fn search_captures(
+        &mut self,
+        game: &mut ChessGame,
+        legal_moves: Vec<ChessMoveType>,
+        ply: i32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        // the side to move does not have to capture, so the static evaluation is a lower
+        // bound on the score of the position
+        let stand_pat = self.evaluate(game);
+        if stand_pat >= beta {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut captures: Vec<(ChessMoveType, i32)> = legal_moves
+            .into_iter()
+            .filter_map(|chess_move| {
+                get_capture_order(game, &chess_move).map(|order| (chess_move, order))
+            })
+            .collect();
+        captures.sort_by_key(|(_, order)| Reverse(*order));
+
+        let mut best_score = stand_pat;
+        for (chess_move, _) in captures {
+            let mut next = game.clone();
+            chess_count!(Clones, 1);
+            next.apply_move(chess_move);
+            let score = -self.quiescence(&mut next, ply + 1, -beta, -alpha);
+
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best_score
+    }
+
+    /// Follows the best moves stored in the transposition table from the given position.
+    fn get_line_from_table(&self, game: &ChessGame, max_length: u32) -> Vec<ChessMoveType> {
+        let mut game = game.clone();
+        let mut line = Vec::new();
+        while line.len() < max_length as usize {
+            let table_move = match self
+                .transposition_table
+                .probe(hash_position(&game))
+                .and_then(|entry| entry.get_best_move())
+            {
+                Some(table_move) => *table_move,
+                None => break,
+            };
+            if !get_legal_moves(&mut game).contains(&table_move) {
+                break;
+            }
+            game.apply_move(table_move);
+            line.push(table_move);
+        }
+        line
+    }
+}
+
+/// Returns a seed from the current time, for engines without a random seed of their own.
+fn get_time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
+}
+
+/// Shuffles moves with the Fisher-Yates shuffle.
+fn shuffle(moves: &mut [ChessMoveType], rng: &mut PositionRng) {
+    for index in (1..moves.len()).rev() {
+        moves.swap(index, rng.next_below(index + 1));
+    }
+}
+
+/// Returns the score of a position where the side to move has no legal moves, either
+/// checkmate or stalemate.
+fn get_score_without_moves(game: &ChessGame, ply: i32) -> i32 {
+    if is_current_player_in_check(game) {
+        -MATE_SCORE + ply
+    } else {
+        0
+    }
+}
+
+/// Returns the order a move is searched in by the quiescence search, higher first, or `None`
+/// if the move is not searched at all.
+///
+/// Captures are ordered by taking the most valuable victim with the least valuable attacker,
+/// see `mvv_lva`. Captures that lose material in the exchange that follows are skipped.
+fn get_capture_order(game: &ChessGame, chess_move: &ChessMoveType) -> Option<i32> {
+    let order = mvv_lva(chess_move)?;
+    if let ChessMoveType::Move {
+        original_position,
+        new_position,
+        taken_piece: Some(_),
+        promotion: None,
+        ..
+    } = chess_move
+    {
+        if see_capture(game.get_board(), *original_position, *new_position) < 0 {
+            return None;
+        }
+    }
+    Some(order)
+}
+
+/// Converts a score relative to the root into one relative to the current position, so mate
+/// scores stay correct when the position is reached at a different ply.
+fn score_to_table(score: i32, ply: i32) -> i32 {
+    if score > MATE_THRESHOLD {
+        score + ply
+    } else if score < -MATE_THRESHOLD {
+        score - ply
+    } else {
+        score
+    }
+}
+
+fn score_from_table(score: i32, ply: i32) -> i32 {
+    if score > MATE_THRESHOLD {
+        score - ply
+    } else if score < -MATE_THRESHOLD {
+        score + ply
+    } else {
+        score
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn finds_mate_in_one() {
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let result = Engine::new().search(&game, 3);
+
+        assert_eq!((0, 7), result.get_best_move().unwrap().get_new_position());
+        assert_eq!(MATE_SCORE - 1, result.get_score());
+        assert!(result.is_mate_score());
+        assert_eq!(1, result.get_principal_variation().len());
+    }
+
+    #[test]
+    fn principal_variation_starts_with_best_move() {
+        let game = ChessGame::new();
+        let result = Engine::new().search(&game, 2);
+
+        assert_eq!(2, result.get_principal_variation().len());
+        assert_eq!(
+            result.get_best_move(),
+            result.get_principal_variation().first()
+        );
+        assert!(result.get_nodes() > 20);
+    }
+
+    #[test]
+    fn root_moves_are_ranked_with_exact_scores() {
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let results = Engine::new().search_root_moves(&game, 1);
+
+        assert_eq!(MATE_SCORE - 1, results[0].get_score());
+        assert!(results[1..].iter().all(|result| !result.is_mate_score()));
+        assert_eq!(
+            Engine::new().search(&game, 1).get_score(),
+            results[0].get_score()
+        );
+    }
+
+    #[test]
+    fn analysed_lines_match_the_ranked_root_moves() {
+        // Kxe2 takes a free knight, every line keeps its own first move
+        let game = build_game_from_string("4k3/pp6/8/8/8/8/4n2P/4K3 w - - 0 1").unwrap();
+        let ranked = Engine::new().search_root_moves(&game, 3);
+        let lines = Engine::new().analyse(&game, 3, 10);
+
+        assert_eq!(ranked.len(), lines.len());
+        assert_eq!((4, 1), lines[0].get_move().get_new_position());
+        for (index, line) in lines.iter().enumerate() {
+            assert_eq!(index + 1, line.get_rank());
+            assert_eq!(ranked[index].get_score(), line.get_score());
+        }
+        let mut first_moves: Vec<_> = lines.iter().map(|line| *line.get_move()).collect();
+        first_moves.dedup();
+        assert_eq!(lines.len(), first_moves.len());
+    }
+
+    #[test]
+    fn stopped_searches_return_the_deepest_completed_depth() {
+        let (sender, infos) = std::sync::mpsc::channel();
+        let engine = Engine::new().set_info_callback(move |info| {
+            let _ = sender.send(info.clone());
+        });
+        let handle = engine.search_in_background(&ChessGame::new());
+        assert!(!handle.is_pondering());
+
+        while infos.recv().unwrap().get_depth() < 3 {}
+        let (mut engine, result) = handle.stop();
+        let last_info = infos.try_iter().last();
+
+        if let Some(last_info) = last_info {
+            assert_eq!(last_info.get_depth(), result.get_depth());
+            assert_eq!(
+                last_info.get_principal_variation(),
+                result.get_principal_variation()
+            );
+        }
+        assert!(result.get_depth() >= 3);
+
+        // the engine searches normally again once it has been handed back
+        assert_eq!(2, engine.search(&ChessGame::new(), 2).get_depth());
+
+        // a reply for the wrong side can not be pondered
+        let mut after_first_move = ChessGame::new();
+        let first_move = after_first_move.legal_moves()[0];
+        after_first_move.make_move(first_move);
+        let reply = after_first_move.legal_moves()[0];
+        assert!(matches!(
+            engine.ponder(&ChessGame::new(), reply),
+            Err(ChessError::IllegalMove(_))
+        ));
+    }
+
+    #[test]
+    fn time_limits_end_the_search() {
+        use std::time::Duration;
+
+        // once the soft limit has passed no further depth is started
+        let limits = TimeLimits::new(Duration::ZERO, Duration::from_secs(60));
+        let result = Engine::new().search_with_time_limits(&ChessGame::new(), limits);
+        assert_eq!(1, result.get_depth());
+
+        // the hard limit gives up on the depth being searched
+        let start = Instant::now();
+        let limits = TimeLimits::fixed(Duration::from_millis(100));
+        let result = Engine::new().search_with_time_limits(&ChessGame::new(), limits);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(result.get_depth() < MAX_SEARCH_DEPTH);
+        assert!(result.get_best_move().is_some());
+    }
+
+    #[test]
+    fn node_limits_are_reproducible_in_deterministic_mode() {
+        let game = build_game_from_string(
+            "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+        )
+        .unwrap();
+        let limits = SearchLimits::new().set_nodes(3_000);
+
+        let mut engine = Engine::new();
+        let first = engine.search_with_limits(&game, &limits);
+        assert!(first.get_nodes() <= 3_001);
+        // the transposition table carries over to the next search
+        let second = engine.search_with_limits(&game, &limits);
+        assert_ne!(first, second);
+
+        let mut engine = Engine::new().set_random_seed(3).set_deterministic(true);
+        let first = engine.search_with_limits(&game, &limits);
+        let second = engine.search_with_limits(&game, &limits);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn skill_levels_make_mistakes_within_the_margin() {
+        // Rxd5 wins the queen, no other move comes close
+        let game = build_game_from_string("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+        let best_move = Engine::new().search(&game, 2).best_move;
+
+        let careless = SkillLevel::new(2)
+            .set_mistake_chance(100)
+            .set_mistake_margin(100);
+        let mut engine = Engine::new().set_random_seed(1).set_skill_level(careless);
+        assert_eq!(best_move, engine.choose_move(&game));
+
+        let blundering = careless.set_mistake_margin(10_000);
+        let mut engine = Engine::new().set_random_seed(1).set_skill_level(blundering);
+        let chess_move = engine.choose_move(&game);
+        assert!(chess_move.is_some());
+        assert_ne!(best_move, chess_move);
+        assert_eq!(2, engine.search(&game, 6).get_depth());
+    }
+
+    #[test]
+    fn custom_evaluator_changes_the_search() {
+        struct PreferPiecesOnTheEdge;
+
+        impl Evaluator for PreferPiecesOnTheEdge {
+            fn evaluate(&self, game: &ChessGame) -> i32 {
+                let board = game.get_board();
+                let edge_pieces = (0..board.get_height())
+                    .flat_map(|row| [(0, row), (board.get_width() - 1, row)])
+                    .filter(|(col, row)| board.get_piece_at_space(*col, *row).is_some())
+                    .count() as i32;
+                // the side that just moved is the opponent of the side to move
+                -edge_pieces
+            }
+        }
+
+        let game = ChessGame::new();
+        let result = Engine::new()
+            .set_evaluator(PreferPiecesOnTheEdge)
+            .search(&game, 1);
+        let new_position = result.get_best_move().unwrap().get_new_position();
+        assert!(new_position == (0, 2) || new_position == (7, 2));
+    }
+
+    #[test]
+    fn transposition_table_reduces_the_nodes_searched() {
+        let game = build_game_from_string(
+            "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+        )
+        .unwrap();
+
+        let with_table = Engine::new().search(&game, 3);
+        let without_table = Engine::new()
+            .set_transposition_table(TranspositionTable::with_capacity_mb(0))
+            .search(&game, 3);
+
+        assert!(with_table.get_nodes() < without_table.get_nodes());
+        assert_eq!(3, with_table.get_principal_variation().len());
+    }
+
+    #[test]
+    fn mate_scores_survive_the_transposition_table() {
+        let mut engine = Engine::new();
+        let game = build_game_from_string("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+
+        assert_eq!(MATE_SCORE - 1, engine.search(&game, 3).get_score());
+        assert_eq!(MATE_SCORE - 1, engine.search(&game, 3).get_score());
+    }
+
+    #[test]
+    fn quiescence_search_sees_recaptures() {
+        // at depth 1 without a quiescence search Qxd5 wins a pawn, exd5 wins the queen back
+        let game = build_game_from_string("4k3/8/4p3/3p4/8/8/3Q4/4K3 w - - 0 1").unwrap();
+        let result = Engine::new().search(&game, 1);
+
+        assert_ne!((3, 4), result.get_best_move().unwrap().get_new_position());
+        assert!(result.get_score() > 0);
+    }
+
+    #[test]
+    fn quiescence_search_finishes_exchanges() {
+        // Rxe5 Rxe5 Rxe5 wins a rook, even at depth 1
+        let game = build_game_from_string("4k3/4r3/8/4r3/8/8/4R3/4R1K1 w - - 0 1").unwrap();
+        let result = Engine::new()
+            .set_evaluator(evaluation::MaterialEvaluator)
+            .search(&game, 1);
+
+        assert_eq!((4, 4), result.get_best_move().unwrap().get_new_position());
+        assert_eq!(500, result.get_score());
+    }
+
+    #[test]
+    fn position_without_legal_moves_has_no_best_move() {
+        let stalemate = build_game_from_string("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        let result = Engine::new().search(&stalemate, 2);
+
+        assert!(result.get_best_move().is_none());
+        assert_eq!(0, result.get_score());
+    }
+}
@@ -0,0 +1,282 @@
+use crate::engine::evaluation::piece_value;
+use crate::piece::PieceType;
+use crate::{ChessGame, ChessMoveType, Color};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// The order score of captures and promotions, which are searched before any quiet move.
+const CAPTURE_ORDER: i32 = 1_000_000;
+
+/// The order scores of the two killer moves of a ply, searched after the captures.
+const KILLER_ORDER: [i32; 2] = [900_000, 800_000];
+
+/// The history score at which every history score is halved, keeping quiet moves below
+/// the killer moves.
+const HISTORY_LIMIT: i32 = 100_000;
+
+/// Decides the order the engine searches the moves of a position in.
+///
+/// Alpha-beta search prunes more of the tree when the best move is searched first, so a
+/// good ordering makes the search faster without changing its result. Implement this trait
+/// to plug a custom ordering into the `Engine`, or call an orderer directly when driving a
+/// search by hand.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::move_ordering::MoveOrdering;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::{ChessGame, ChessMoveType};
+///
+/// /// Searches the moves in the order they were generated.
+/// struct GenerationOrder;
+///
+/// impl MoveOrdering for GenerationOrder {
+///     fn order_moves(&mut self, _game: &ChessGame, _moves: &mut [ChessMoveType], _ply: usize) {}
+/// }
+///
+/// let mut engine = Engine::new().set_move_ordering(GenerationOrder);
+/// assert!(engine.search(&ChessGame::new(), 2).get_best_move().is_some());
+/// ```
+pub trait MoveOrdering: Send + Sync {
+    /// Sorts the moves of the current position of a game, the move to search first first.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game the moves are legal in.
+    /// * `moves` - The moves to sort.
+    /// * `ply` - The number of plies from the root of the search to the position.
+    fn order_moves(&mut self, game: &ChessGame, moves: &mut [ChessMoveType], ply: usize);
+
+    /// Tells the ordering a move was good enough to cut off the search of its position, so
+    /// it can be tried early in similar positions. Does nothing unless implemented.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game the move was played in.
+    /// * `chess_move` - The move causing the cutoff.
+    /// * `depth` - The remaining depth of the search at the position.
+    /// * `ply` - The number of plies from the root of the search to the position.
+    fn record_cutoff(
+        &mut self,
+        _game: &ChessGame,
+        _chess_move: &ChessMoveType,
+        _depth: u32,
+        _ply: usize,
+    ) {
+    }
+
+    /// Forgets everything learned from earlier cutoffs, called at the start of every
+    /// search. Does nothing unless implemented.
+    fn clear(&mut self) {}
+}
+
+/// The player making a quiet move, the square it starts on and the square it ends on.
+type HistoryKey = (Color, (usize, usize), (usize, usize));
+
+/// The engine's default move ordering.
+///
+/// Moves are searched in this order:
+///
+/// 1. Captures and promotions, the most valuable victim taken by the least valuable
+///    attacker first, see `mvv_lva`.
+/// 2. The two killer moves of the ply, quiet moves that cut off the search at the same
+///    ply in another position.
+/// 3. The other quiet moves, by their history score, which grows each time a move from
+///    the same square to the same square cuts off the search, the more so the deeper the
+///    search was.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::move_ordering::{MoveOrderer, MoveOrdering};
+/// use simple_chess::ChessGame;
+///
+/// let mut game = build_game_from_string("4k3/8/8/3q4/8/2N5/8/4K3 w - - 0 1").unwrap();
+/// let mut moves = game.legal_moves().to_vec();
+/// MoveOrderer::new().order_moves(&game, &mut moves, 0);
+///
+/// // Nxd5 takes the queen
+/// assert_eq!((3, 4), moves[0].get_new_position());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MoveOrderer {
+    killers: Vec<[Option<ChessMoveType>; 2]>,
+    history: HashMap<HistoryKey, i32>,
+}
+
+impl MoveOrderer {
+    /// Creates a `MoveOrderer` without killer moves or history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the killer moves of a ply, the most recent first.
+    pub fn get_killers(&self, ply: usize) -> [Option<ChessMoveType>; 2] {
+        self.killers.get(ply).copied().unwrap_or_default()
+    }
+
+    /// Returns the history score of quiet moves of a player from one square to another.
+    pub fn get_history(&self, color: Color, from: (usize, usize), to: (usize, usize)) -> i32 {
+        self.history.get(&(color, from, to)).copied().unwrap_or(0)
+    }
+
+    fn get_order(&self, game: &ChessGame, chess_move: &ChessMoveType, ply: usize) -> i32 {
+        if let Some(order) = mvv_lva(chess_move) {
+            return CAPTURE_ORDER + order;
+        }
+        if let Some(index) = self
+            .get_killers(ply)
+            .iter()
+            .position(|killer| *killer == Some(*chess_move))
+        {
+            return KILLER_ORDER[index];
+        }
+        self.get_history(
+            game.get_current_players_turn(),
+            chess_move.get_original_position(),
+            chess_move.get_new_position(),
+        )
+    }
+}
+
+impl MoveOrdering for MoveOrderer {
+    fn order_moves(&mut self, game: &ChessGame, moves: &mut [ChessMoveType], ply: usize) {
+        moves.sort_by_cached_key(|chess_move| Reverse(self.get_order(game, chess_move, ply)));
+    }
+
+    fn record_cutoff(
+        &mut self,
+        game: &ChessGame,
+        chess_move: &ChessMoveType,
+        depth: u32,
+        ply: usize,
+    ) {
+        // captures are already searched first
+        if mvv_lva(chess_move).is_some() {
+            return;
+        }
+
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; 2]);
+        }
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(*chess_move) {
+            killers[1] = killers[0];
+            killers[0] = Some(*chess_move);
+        }
+
+        let key = (
+            game.get_current_players_turn(),
+            chess_move.get_original_position(),
+            chess_move.get_new_position(),
+        );
+        let score = self.history.entry(key).or_insert(0);
+        *score += (depth * depth) as i32;
+        if *score > HISTORY_LIMIT {
+            for score in self.history.values_mut() {
+                *score /= 2;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.killers.clear();
+        self.history.clear();
+    }
+}
+
+/// Scores a capture or promotion by the most valuable victim, least valuable attacker
+/// (MVV-LVA) rule.
+///
+/// Taking a more valuable piece always scores higher, and among captures of the same piece
+/// the capture by the less valuable piece scores higher. Promotions add the value of the
+/// new piece.
+///
+/// # Arguments
+///
+/// * `chess_move` - The move to score.
+///
+/// # Returns
+///
+/// * `Some(i32)` - The score of a capture or promotion, higher is searched first.
+/// * `None` - If the move neither captures nor promotes.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::move_ordering::mvv_lva;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessMoveType, Color};
+///
+/// let capture = |piece, taken| ChessMoveType::Move {
+///     original_position: (3, 3),
+///     new_position: (4, 4),
+///     piece: ChessPiece::new(piece, Color::White),
+///     taken_piece: Some(ChessPiece::new(taken, Color::Black)),
+///     promotion: None,
+/// };
+///
+/// let pawn_takes_rook = mvv_lva(&capture(PieceType::Pawn, PieceType::Rook));
+/// let queen_takes_rook = mvv_lva(&capture(PieceType::Queen, PieceType::Rook));
+/// let queen_takes_knight = mvv_lva(&capture(PieceType::Queen, PieceType::Knight));
+/// assert!(pawn_takes_rook > queen_takes_rook);
+/// assert!(queen_takes_rook > queen_takes_knight);
+/// ```
+pub fn mvv_lva(chess_move: &ChessMoveType) -> Option<i32> {
+    match chess_move {
+        ChessMoveType::Move {
+            piece,
+            taken_piece,
+            promotion,
+            ..
+        } => {
+            let promotion_value = promotion
+                .map(|promotion| piece_value(promotion.get_piece_type()))
+                .unwrap_or(0);
+            match taken_piece {
+                Some(taken_piece) => Some(
+                    piece_value(taken_piece.get_piece_type()) * 10
+                        - piece_value(piece.get_piece_type())
+                        + promotion_value,
+                ),
+                None if promotion.is_some() => Some(promotion_value),
+                None => None,
+            }
+        }
+        ChessMoveType::EnPassant { .. } => Some(piece_value(PieceType::Pawn) * 9),
+        ChessMoveType::Castle { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+
+    #[test]
+    fn killers_and_history_move_quiet_moves_forward() {
+        let mut game = ChessGame::new();
+        let mut orderer = MoveOrderer::new();
+        let knight_move = decode_move_from_string(&game, "Nf3").unwrap();
+        let pawn_move = decode_move_from_string(&game, "h3").unwrap();
+
+        orderer.record_cutoff(&game, &pawn_move, 2, 1);
+        orderer.record_cutoff(&game, &knight_move, 3, 1);
+        assert_eq!([Some(knight_move), Some(pawn_move)], orderer.get_killers(1));
+        assert_eq!(9, orderer.get_history(Color::White, (6, 0), (5, 2)));
+
+        let mut moves = game.legal_moves().to_vec();
+        orderer.order_moves(&game, &mut moves, 1);
+        assert_eq!(&[knight_move, pawn_move], &moves[..2]);
+
+        // at another ply only the history counts
+        orderer.order_moves(&game, &mut moves, 0);
+        assert_eq!(&[knight_move, pawn_move], &moves[..2]);
+
+        orderer.clear();
+        assert_eq!([None, None], orderer.get_killers(1));
+        assert_eq!(0, orderer.get_history(Color::White, (6, 0), (5, 2)));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::engine::time_manager::TimeLimits;
+use std::time::Duration;
+
+/// When a search started with `Engine::search_with_limits` stops, like the `depth`, `nodes`
+/// and `movetime` arguments of a UCI `go` command.
+///
+/// The search stops at whichever limit it reaches first, and goes on until
+/// `MAX_SEARCH_DEPTH` when no limit is set. The first depth is always completed, so the
+/// search has a move to play even when a node or time limit is reached early.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::search_limits::SearchLimits;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::ChessGame;
+///
+/// let limits = SearchLimits::new().set_depth(4).set_nodes(5_000);
+/// let result = Engine::new().search_with_limits(&ChessGame::new(), &limits);
+///
+/// assert!(result.get_depth() <= 4);
+/// assert!(result.get_best_move().is_some());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    depth: Option<u32>,
+    nodes: Option<u64>,
+    time_limits: Option<TimeLimits>,
+}
+
+impl SearchLimits {
+    /// Creates limits that do not limit the search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of plies to search.
+    pub fn set_depth(mut self, depth: u32) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Sets the number of positions the search may visit, after which it stops in the
+    /// middle of the depth it is searching.
+    ///
+    /// Unlike time limits, a node limit stops the search at the same point every time, so
+    /// results are reproducible.
+    pub fn set_nodes(mut self, nodes: u64) -> Self {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    /// Sets the time the search may take, the search stops in the middle of the depth it
+    /// is searching once the time has passed.
+    pub fn set_move_time(self, move_time: Duration) -> Self {
+        self.set_time_limits(TimeLimits::fixed(move_time))
+    }
+
+    /// Sets the soft and hard time limits of the search, usually from a `TimeManager`.
+    pub fn set_time_limits(mut self, time_limits: TimeLimits) -> Self {
+        self.time_limits = Some(time_limits);
+        self
+    }
+
+    /// Returns the number of plies to search, if limited.
+    pub fn get_depth(&self) -> Option<u32> {
+        self.depth
+    }
+
+    /// Returns the number of positions the search may visit, if limited.
+    pub fn get_nodes(&self) -> Option<u64> {
+        self.nodes
+    }
+
+    /// Returns the time limits of the search, if limited.
+    pub fn get_time_limits(&self) -> Option<TimeLimits> {
+        self.time_limits
+    }
+}
@@ -0,0 +1,204 @@
+/// The Elo, depth, evaluation noise, mistake chance and mistake margin of an Elo band.
+type EloBand = (u32, u32, i32, u32, i32);
+
+/// The settings of each rough Elo band, from the weakest to the strongest.
+const ELO_BANDS: [EloBand; 4] = [
+    (800, 1, 200, 30, 300),
+    (1400, 2, 80, 15, 150),
+    (1900, 4, 20, 5, 50),
+    (2200, 5, 0, 0, 0),
+];
+
+/// How strongly the engine plays, so one engine can offer opponents of different strengths.
+///
+/// A skill level weakens the engine in three ways:
+///
+/// * Searches go no deeper than the skill's depth.
+/// * A random amount, up to the evaluation noise in either direction, is added to the score
+///   of every position the search evaluates. The amount is worked out from the position
+///   and the engine's random seed, so a position always gets the same noise.
+/// * `Engine::choose_move` picks a worse move with the mistake chance, any move scoring no
+///   more than the mistake margin below the best move.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::skill_level::SkillLevel;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::ChessGame;
+///
+/// let mut engine = Engine::new().set_skill_level(SkillLevel::beginner());
+/// let mut game = ChessGame::new();
+/// let chess_move = engine.choose_move(&game).unwrap();
+///
+/// assert!(game.legal_moves().contains(&chess_move));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillLevel {
+    depth: u32,
+    evaluation_noise: i32,
+    mistake_chance: u32,
+    mistake_margin: i32,
+    elo: Option<u32>,
+}
+
+impl SkillLevel {
+    /// Creates a skill level searching to the given depth that does not otherwise weaken the
+    /// engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The deepest the engine searches, in plies, at least `1`.
+    pub fn new(depth: u32) -> Self {
+        Self {
+            depth: depth.max(1),
+            evaluation_noise: 0,
+            mistake_chance: 0,
+            mistake_margin: 0,
+            elo: None,
+        }
+    }
+
+    /// Creates the skill level of a beginner, around 800 Elo, who often misses tactics.
+    pub fn beginner() -> Self {
+        Self::from_band(ELO_BANDS[0])
+    }
+
+    /// Creates the skill level of a club player, around 1400 Elo.
+    pub fn intermediate() -> Self {
+        Self::from_band(ELO_BANDS[1])
+    }
+
+    /// Creates the skill level of a strong club player, around 1900 Elo, who rarely makes a
+    /// mistake.
+    pub fn advanced() -> Self {
+        Self::from_band(ELO_BANDS[2])
+    }
+
+    /// Creates a skill level of roughly the given Elo.
+    ///
+    /// The evaluation noise, mistake chance and mistake margin are interpolated between the
+    /// bands of `beginner`, `intermediate` and `advanced`, and the depth is that of the band
+    /// at or below the Elo. Elos below 800 play like a beginner, and from 2200 up the engine
+    /// only has its depth limited.
+    ///
+    /// # Arguments
+    ///
+    /// * `elo` - The rough strength to play at.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::skill_level::SkillLevel;
+    ///
+    /// let club_player = SkillLevel::from_elo(1650);
+    ///
+    /// assert_eq!(2, club_player.get_depth());
+    /// assert_eq!(50, club_player.get_evaluation_noise());
+    /// assert_eq!(SkillLevel::beginner(), SkillLevel::from_elo(400).set_elo(800));
+    /// ```
+    pub fn from_elo(elo: u32) -> Self {
+        let Some(upper) = ELO_BANDS.iter().position(|band| band.0 > elo) else {
+            return Self::from_band(ELO_BANDS[ELO_BANDS.len() - 1]).set_elo(elo);
+        };
+        if upper == 0 {
+            return Self::from_band(ELO_BANDS[0]).set_elo(elo);
+        }
+
+        let (low_elo, depth, low_noise, low_chance, low_margin) = ELO_BANDS[upper - 1];
+        let (high_elo, _, high_noise, high_chance, high_margin) = ELO_BANDS[upper];
+        let interpolate = |low: i32, high: i32| {
+            low + (high - low) * (elo - low_elo) as i32 / (high_elo - low_elo) as i32
+        };
+        Self {
+            depth,
+            evaluation_noise: interpolate(low_noise, high_noise),
+            mistake_chance: interpolate(low_chance as i32, high_chance as i32) as u32,
+            mistake_margin: interpolate(low_margin, high_margin),
+            elo: Some(elo),
+        }
+    }
+
+    fn from_band((elo, depth, evaluation_noise, mistake_chance, mistake_margin): EloBand) -> Self {
+        Self {
+            depth,
+            evaluation_noise,
+            mistake_chance,
+            mistake_margin,
+            elo: Some(elo),
+        }
+    }
+
+    /// Sets the most, in centipawns, added to or taken from the score of each position.
+    pub fn set_evaluation_noise(mut self, evaluation_noise: i32) -> Self {
+        self.evaluation_noise = evaluation_noise.max(0);
+        self
+    }
+
+    /// Sets the chance, in percent, that `Engine::choose_move` picks a worse move.
+    pub fn set_mistake_chance(mut self, mistake_chance: u32) -> Self {
+        self.mistake_chance = mistake_chance.min(100);
+        self
+    }
+
+    /// Sets how much worse, in centipawns, a move picked by mistake may score than the best
+    /// move.
+    pub fn set_mistake_margin(mut self, mistake_margin: i32) -> Self {
+        self.mistake_margin = mistake_margin.max(0);
+        self
+    }
+
+    /// Sets the rough Elo the skill level plays at, for showing to players.
+    pub fn set_elo(mut self, elo: u32) -> Self {
+        self.elo = Some(elo);
+        self
+    }
+
+    /// Returns the deepest the engine searches, in plies.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the most, in centipawns, added to or taken from the score of each position.
+    pub fn get_evaluation_noise(&self) -> i32 {
+        self.evaluation_noise
+    }
+
+    /// Returns the chance, in percent, that `Engine::choose_move` picks a worse move.
+    pub fn get_mistake_chance(&self) -> u32 {
+        self.mistake_chance
+    }
+
+    /// Returns how much worse, in centipawns, a move picked by mistake may score than the
+    /// best move.
+    pub fn get_mistake_margin(&self) -> i32 {
+        self.mistake_margin
+    }
+
+    /// Returns the rough Elo the skill level plays at, if known.
+    pub fn get_elo(&self) -> Option<u32> {
+        self.elo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elo_bands_are_interpolated() {
+        assert_eq!(SkillLevel::intermediate(), SkillLevel::from_elo(1400));
+        assert_eq!(SkillLevel::advanced(), SkillLevel::from_elo(1900));
+
+        let level = SkillLevel::from_elo(1100);
+        assert_eq!(1, level.get_depth());
+        assert_eq!(140, level.get_evaluation_noise());
+        assert_eq!(23, level.get_mistake_chance());
+        assert_eq!(225, level.get_mistake_margin());
+
+        let strongest = SkillLevel::from_elo(2800);
+        assert_eq!(5, strongest.get_depth());
+        assert_eq!(0, strongest.get_mistake_chance());
+        assert_eq!(Some(2800), strongest.get_elo());
+    }
+}
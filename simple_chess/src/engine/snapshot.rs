@@ -0,0 +1,306 @@
+use crate::codec::binary::{
+    decode_game_from_binary, decode_move, encode_game_as_binary, encode_move, write_number,
+    BinaryDecodeError, ByteReader,
+};
+use crate::engine::transposition_table::{Bound, TranspositionEntry, TranspositionTable};
+use crate::engine::SearchResult;
+use crate::ChessGame;
+use std::mem::size_of;
+
+/// The bytes every saved analysis starts with.
+const MAGIC: &[u8; 3] = b"SCA";
+
+/// The version of the saved analysis format written by `AnalysisSnapshot::to_bytes`.
+const FORMAT_VERSION: u8 = 1;
+
+/// The state of a search, saved so that deep analysis of a position can be stopped and
+/// carried on later, for example between the nights of a correspondence game.
+///
+/// A snapshot holds the analysed game, the result of the deepest completed search and the
+/// engine's transposition table. Resuming a snapshot with `Engine::resume_analysis` restores
+/// the table and searches only the depths that were not yet completed.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::snapshot::AnalysisSnapshot;
+/// use simple_chess::engine::transposition_table::TranspositionTable;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::ChessGame;
+///
+/// let game = ChessGame::new();
+/// let mut engine = Engine::new().set_transposition_table(TranspositionTable::with_capacity_mb(1));
+/// let result = engine.search(&game, 2);
+/// let bytes = engine.save_analysis(&game, &result).to_bytes();
+///
+/// // later, possibly in another process
+/// let snapshot = AnalysisSnapshot::from_bytes(&bytes).unwrap();
+/// let deeper = Engine::new().resume_analysis(&snapshot, 3);
+/// assert_eq!(3, deeper.get_depth());
+/// ```
+#[derive(Clone)]
+pub struct AnalysisSnapshot {
+    game: ChessGame,
+    result: SearchResult,
+    transposition_table: TranspositionTable,
+}
+
+impl AnalysisSnapshot {
+    pub(crate) fn new(
+        game: ChessGame,
+        result: SearchResult,
+        transposition_table: TranspositionTable,
+    ) -> Self {
+        Self {
+            game,
+            result,
+            transposition_table,
+        }
+    }
+
+    /// Returns the game whose current position was analysed.
+    pub fn get_game(&self) -> &ChessGame {
+        &self.game
+    }
+
+    /// Returns the result of the deepest search completed before the snapshot was taken.
+    pub fn get_result(&self) -> &SearchResult {
+        &self.result
+    }
+
+    /// Returns the depth, in plies, the position has been searched to.
+    pub fn get_depth(&self) -> u32 {
+        self.result.depth
+    }
+
+    /// Returns the transposition table of the search.
+    pub fn get_transposition_table(&self) -> &TranspositionTable {
+        &self.transposition_table
+    }
+
+    /// Saves the snapshot in a compact, versioned binary format that can be written to disk.
+    ///
+    /// Only the occupied entries of the transposition table are saved, along with its
+    /// capacity so that every entry returns to its own slot.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let board = self.game.get_board();
+        let width = board.get_width();
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+
+        let game_bytes = encode_game_as_binary(&self.game, None);
+        write_number(&mut bytes, game_bytes.len() as u64);
+        bytes.extend(game_bytes);
+
+        write_number(&mut bytes, self.result.depth as u64);
+        bytes.extend(self.result.score.to_le_bytes());
+        write_number(&mut bytes, self.result.nodes);
+        write_number(&mut bytes, self.result.principal_variation.len() as u64);
+        for chess_move in &self.result.principal_variation {
+            encode_move(&mut bytes, chess_move, width);
+        }
+
+        write_number(&mut bytes, self.transposition_table.get_capacity() as u64);
+        let entries: Vec<&TranspositionEntry> = self.transposition_table.get_entries().collect();
+        write_number(&mut bytes, entries.len() as u64);
+        for entry in entries {
+            bytes.extend(entry.get_key().to_le_bytes());
+            write_number(&mut bytes, entry.get_depth() as u64);
+            bytes.push(match entry.get_bound() {
+                Bound::Exact => 0,
+                Bound::Lower => 1,
+                Bound::Upper => 2,
+            });
+            bytes.extend(entry.get_score().to_le_bytes());
+            match entry.get_best_move() {
+                Some(chess_move) => {
+                    bytes.push(1);
+                    encode_move(&mut bytes, chess_move, width);
+                }
+                None => bytes.push(0),
+            }
+        }
+
+        bytes
+    }
+
+    /// Reads a snapshot saved with `to_bytes`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(AnalysisSnapshot)` - The saved snapshot.
+    /// * `Err(BinaryDecodeError)` - If the bytes are not a saved analysis, were saved by a
+    ///   newer version of the format or are damaged.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AnalysisSnapshot, BinaryDecodeError> {
+        let mut reader = ByteReader::new(bytes);
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(BinaryDecodeError::new(String::from(
+                "the bytes are not a saved analysis",
+            )));
+        }
+        let version = reader.read_byte()?;
+        if version > FORMAT_VERSION {
+            return Err(BinaryDecodeError::new(format!(
+                "format version {version} is newer than the supported version {FORMAT_VERSION}"
+            )));
+        }
+
+        let game_length = reader.read_usize()?;
+        let (game, _) = decode_game_from_binary(reader.read_bytes(game_length)?)?;
+        let width = game.get_board().get_width();
+        let height = game.get_board().get_height();
+
+        let depth = read_depth(&mut reader)?;
+        let score = read_score(&mut reader)?;
+        let nodes = reader.read_number()?;
+        let mut principal_variation = Vec::new();
+        for _ in 0..reader.read_usize()? {
+            principal_variation.push(decode_move(&mut reader, width, height)?);
+        }
+
+        let capacity = reader.read_usize()?;
+        if capacity
+            .checked_mul(size_of::<Option<TranspositionEntry>>())
+            .is_none_or(|size| size > isize::MAX as usize)
+        {
+            return Err(BinaryDecodeError::new(format!(
+                "a transposition table of {capacity} entries is too large"
+            )));
+        }
+        let mut transposition_table = TranspositionTable::with_capacity(capacity);
+        for _ in 0..reader.read_usize()? {
+            let key = u64::from_le_bytes(
+                reader
+                    .read_bytes(8)?
+                    .try_into()
+                    .expect("eight bytes were read"),
+            );
+            let entry_depth = read_depth(&mut reader)?;
+            let bound = match reader.read_byte()? {
+                0 => Bound::Exact,
+                1 => Bound::Lower,
+                2 => Bound::Upper,
+                bound => return Err(BinaryDecodeError::new(format!("invalid bound {bound}"))),
+            };
+            let entry_score = read_score(&mut reader)?;
+            let best_move = match reader.read_byte()? {
+                0 => None,
+                1 => Some(decode_move(&mut reader, width, height)?),
+                flag => {
+                    return Err(BinaryDecodeError::new(format!(
+                        "invalid best move flag {flag}"
+                    )))
+                }
+            };
+            transposition_table.store(TranspositionEntry::new(
+                key,
+                entry_depth,
+                bound,
+                entry_score,
+                best_move,
+            ));
+        }
+        if !reader.is_finished() {
+            return Err(BinaryDecodeError::new(String::from(
+                "unexpected bytes after the end of the saved analysis",
+            )));
+        }
+
+        let result = SearchResult {
+            best_move: principal_variation.first().copied(),
+            score,
+            principal_variation,
+            depth,
+            nodes,
+        };
+        Ok(AnalysisSnapshot::new(game, result, transposition_table))
+    }
+}
+
+fn read_depth(reader: &mut ByteReader) -> Result<u32, BinaryDecodeError> {
+    let depth = reader.read_number()?;
+    u32::try_from(depth).map_err(|_| BinaryDecodeError::new(format!("depth {depth} is too large")))
+}
+
+fn read_score(reader: &mut ByteReader) -> Result<i32, BinaryDecodeError> {
+    Ok(i32::from_le_bytes(
+        reader
+            .read_bytes(4)?
+            .try_into()
+            .expect("four bytes were read"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::{build_game_from_string, encode_game_as_string};
+    use crate::engine::Engine;
+    use crate::zobrist::hash_position;
+
+    fn small_engine() -> Engine {
+        Engine::new().set_transposition_table(TranspositionTable::with_capacity_mb(1))
+    }
+
+    #[test]
+    fn snapshots_keep_the_search_and_its_table() {
+        let game = build_game_from_string(
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+        )
+        .unwrap();
+        let mut engine = small_engine();
+        let result = engine.search(&game, 3);
+        let snapshot = engine.save_analysis(&game, &result);
+
+        let restored = AnalysisSnapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+        assert_eq!(
+            encode_game_as_string(&game),
+            encode_game_as_string(restored.get_game())
+        );
+        assert_eq!(&result, restored.get_result());
+        assert_eq!(
+            engine.get_transposition_table().get_capacity(),
+            restored.get_transposition_table().get_capacity()
+        );
+        assert_eq!(
+            engine.get_transposition_table().get_entries().count(),
+            restored.get_transposition_table().get_entries().count()
+        );
+        let key = hash_position(&game);
+        assert_eq!(
+            engine.get_transposition_table().probe(key),
+            restored.get_transposition_table().probe(key)
+        );
+    }
+
+    #[test]
+    fn resuming_searches_only_the_new_depths() {
+        let game = build_game_from_string("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+        let mut engine = small_engine();
+        let shallow = engine.search(&game, 2);
+        let snapshot = engine.save_analysis(&game, &shallow);
+
+        let mut resumed_engine = small_engine();
+        assert_eq!(shallow, resumed_engine.resume_analysis(&snapshot, 2));
+
+        let resumed = resumed_engine.resume_analysis(&snapshot, 4);
+        let fresh = small_engine().search(&game, 4);
+        assert_eq!(4, resumed.get_depth());
+        assert_eq!(fresh.get_best_move(), resumed.get_best_move());
+        assert!(resumed.get_nodes() - shallow.get_nodes() < fresh.get_nodes());
+    }
+
+    #[test]
+    fn damaged_snapshots_are_rejected() {
+        let game = ChessGame::new();
+        let mut engine = small_engine();
+        let result = engine.search(&game, 1);
+        let bytes = engine.save_analysis(&game, &result).to_bytes();
+
+        assert!(AnalysisSnapshot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(AnalysisSnapshot::from_bytes(b"SCG").is_err());
+        let mut newer = bytes.clone();
+        newer[3] = FORMAT_VERSION + 1;
+        assert!(AnalysisSnapshot::from_bytes(&newer).is_err());
+    }
+}
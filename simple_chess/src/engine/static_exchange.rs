@@ -0,0 +1,267 @@
+use crate::engine::attack_tables::{king_attacks, knight_attacks, pawn_attacks};
+use crate::engine::evaluation::piece_value;
+use crate::piece::{ChessPiece, PieceType};
+use crate::square_name::SquareName;
+use crate::Color;
+use game_board::Board;
+
+/// The value used for a king in exchanges, high enough that capturing with the king
+/// onto a defended square is never worth it.
+const KING_EXCHANGE_VALUE: i32 = 20_000;
+
+/// Evaluates the sequence of captures on a square with static exchange evaluation (SEE).
+///
+/// The piece on the square is captured by the opponent of its owner, then both sides take
+/// turns recapturing with their least valuable attacker. Either side may stop capturing
+/// when continuing would lose material, including the side capturing first, so the result
+/// is never negative. Pieces lined up behind each other on a rank, file
+/// or diagonal join in as the pieces in front of them capture.
+///
+/// # Arguments
+///
+/// * `board` - The board to evaluate the exchange on, the board is not modified.
+/// * `square` - The `(col, row)` of the square the exchange happens on.
+///
+/// # Returns
+///
+/// * `i32` - The material, in centipawns, the side capturing first wins with best play, or
+///   `0` if the square is empty or the piece on it can not be captured.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::static_exchange::see;
+///
+/// // the knight on e5 is attacked by a pawn and only defended by a pawn
+/// let game = build_game_from_string("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1").unwrap();
+/// assert_eq!(220, see(game.get_board(), (4, 4)));
+/// ```
+pub fn see(board: &Board<ChessPiece>, square: (usize, usize)) -> i32 {
+    let color = match board.get_piece_at_space(square.0, square.1) {
+        Some(piece) => piece.get_color().opposite(),
+        None => return 0,
+    };
+    match find_least_valuable_attacker(board, square, color) {
+        Some(attacker) => exchange(board, square, attacker).max(0),
+        None => 0,
+    }
+}
+
+/// Evaluates a capture made by a specific piece, followed by the best sequence of
+/// recaptures.
+///
+/// # Arguments
+///
+/// * `board` - The board to evaluate the exchange on, the board is not modified.
+/// * `from` - The square of the piece making the first capture.
+/// * `to` - The square of the piece being captured.
+pub(crate) fn see_capture(
+    board: &Board<ChessPiece>,
+    from: (usize, usize),
+    to: (usize, usize),
+) -> i32 {
+    if board.get_piece_at_space(to.0, to.1).is_none() {
+        return 0;
+    }
+    exchange(board, to, from)
+}
+
+fn exchange(
+    board: &Board<ChessPiece>,
+    square: (usize, usize),
+    first_attacker: (usize, usize),
+) -> i32 {
+    let mut board = board.clone();
+    let mut gains = vec![exchange_value(
+        board.get_piece_at_space(square.0, square.1).unwrap(),
+    )];
+
+    let mut attacker = first_attacker;
+    loop {
+        let piece = board.remove_piece(attacker.0, attacker.1).unwrap();
+        // the attacking piece now stands on the square, waiting to be recaptured
+        board.place_piece(piece, square.0, square.1);
+        let color = piece.get_color().opposite();
+
+        attacker = match find_least_valuable_attacker(&board, square, color) {
+            Some(next_attacker) => next_attacker,
+            None => break,
+        };
+        gains.push(exchange_value(&piece) - gains.last().unwrap());
+    }
+
+    // each side only continues the exchange when it does not lose material by doing so
+    while gains.len() > 1 {
+        let last = gains.pop().unwrap();
+        let previous = gains.last_mut().unwrap();
+        *previous = -(-*previous).max(last);
+    }
+    gains[0]
+}
+
+fn exchange_value(piece: &ChessPiece) -> i32 {
+    match piece.get_piece_type() {
+        PieceType::King => KING_EXCHANGE_VALUE,
+        piece_type => piece_value(piece_type),
+    }
+}
+
+fn find_least_valuable_attacker(
+    board: &Board<ChessPiece>,
+    square: (usize, usize),
+    color: Color,
+) -> Option<(usize, usize)> {
+    let mut least_valuable: Option<((usize, usize), i32)> = None;
+    for row in 0..board.get_height() {
+        for col in 0..board.get_width() {
+            if (col, row) == square {
+                continue;
+            }
+            if let Some(piece) = board.get_piece_at_space(col, row) {
+                if piece.get_color() != color || !attacks(board, (col, row), piece, square) {
+                    continue;
+                }
+                let value = exchange_value(piece);
+                if least_valuable.is_none_or(|(_, least_value)| value < least_value) {
+                    least_valuable = Some(((col, row), value));
+                }
+            }
+        }
+    }
+    least_valuable.map(|(position, _)| position)
+}
+
+fn attacks(
+    board: &Board<ChessPiece>,
+    from: (usize, usize),
+    piece: &ChessPiece,
+    target: (usize, usize),
+) -> bool {
+    // standard boards look up the attacks of the stepping pieces in the precomputed tables
+    if let (Some(from_square), Some(target_square)) = (
+        get_standard_square(board, from),
+        get_standard_square(board, target),
+    ) {
+        let attacks = match piece.get_piece_type() {
+            PieceType::Pawn => Some(pawn_attacks(piece.get_color(), from_square)),
+            PieceType::Knight => Some(knight_attacks(from_square)),
+            PieceType::King => Some(king_attacks(from_square)),
+            _ => None,
+        };
+        if let Some(attacks) = attacks {
+            return attacks & (1 << target_square as u8) != 0;
+        }
+    }
+
+    let col_delta = target.0 as i32 - from.0 as i32;
+    let row_delta = target.1 as i32 - from.1 as i32;
+
+    match piece.get_piece_type() {
+        PieceType::Pawn => row_delta == piece.get_color().forward() && col_delta.abs() == 1,
+        PieceType::Knight => {
+            matches!((col_delta.abs(), row_delta.abs()), (1, 2) | (2, 1))
+        }
+        PieceType::King => col_delta.abs().max(row_delta.abs()) == 1,
+        PieceType::Rook => (col_delta == 0 || row_delta == 0) && is_path_clear(board, from, target),
+        PieceType::Bishop => {
+            col_delta.abs() == row_delta.abs() && is_path_clear(board, from, target)
+        }
+        PieceType::Queen => {
+            (col_delta == 0 || row_delta == 0 || col_delta.abs() == row_delta.abs())
+                && is_path_clear(board, from, target)
+        }
+        PieceType::Archbishop => {
+            matches!((col_delta.abs(), row_delta.abs()), (1, 2) | (2, 1))
+                || (col_delta.abs() == row_delta.abs() && is_path_clear(board, from, target))
+        }
+        PieceType::Chancellor => {
+            matches!((col_delta.abs(), row_delta.abs()), (1, 2) | (2, 1))
+                || ((col_delta == 0 || row_delta == 0) && is_path_clear(board, from, target))
+        }
+    }
+}
+
+fn get_standard_square(board: &Board<ChessPiece>, square: (usize, usize)) -> Option<SquareName> {
+    if board.get_width() == 8 && board.get_height() == 8 {
+        SquareName::from_col_and_row(square.0, square.1)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if every square strictly between two aligned squares is empty.
+fn is_path_clear(board: &Board<ChessPiece>, from: (usize, usize), to: (usize, usize)) -> bool {
+    let col_step = (to.0 as i32 - from.0 as i32).signum();
+    let row_step = (to.1 as i32 - from.1 as i32).signum();
+    let mut col = from.0 as i32 + col_step;
+    let mut row = from.1 as i32 + row_step;
+    while (col, row) != (to.0 as i32, to.1 as i32) {
+        if board
+            .get_piece_at_space(col as usize, row as usize)
+            .is_some()
+        {
+            return false;
+        }
+        col += col_step;
+        row += row_step;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+
+    fn see_of(fen: &str, square: (usize, usize)) -> i32 {
+        see(build_game_from_string(fen).unwrap().get_board(), square)
+    }
+
+    #[test]
+    fn en_passant_captures_are_exchanged_on_the_square_moved_to() {
+        for (fen, value) in [
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", 100),
+            ("4k3/2p5/8/3pP3/8/8/8/4K3 w - d6 0 1", 0),
+        ] {
+            let game = build_game_from_string(fen).unwrap();
+            let capture = decode_move_from_string(&game, "exd6").unwrap();
+            assert_eq!(value, capture.exchange_value(game.get_board()));
+        }
+    }
+
+    #[test]
+    fn undefended_piece_is_won() {
+        assert_eq!(500, see_of("4k3/8/8/4r3/8/8/4R3/4K3 w - - 0 1", (4, 4)));
+    }
+
+    #[test]
+    fn defended_piece_is_not_worth_taking_with_a_queen() {
+        // Qxd5 exd5 loses the queen for a pawn, so the exchange is not started
+        assert_eq!(0, see_of("4k3/8/4p3/3p4/8/8/3Q4/4K3 w - - 0 1", (3, 4)));
+        assert_eq!(
+            -800,
+            see_capture(
+                build_game_from_string("4k3/8/4p3/3p4/8/8/3Q4/4K3 w - - 0 1")
+                    .unwrap()
+                    .get_board(),
+                (3, 1),
+                (3, 4)
+            )
+        );
+    }
+
+    #[test]
+    fn x_ray_attackers_join_the_exchange() {
+        // two white rooks stacked on the e-file against a rook defended once
+        assert_eq!(500, see_of("4k3/4r3/8/4r3/8/8/4R3/4R1K1 w - - 0 1", (4, 4)));
+        assert_eq!(0, see_of("4k3/4r3/4r3/4r3/8/8/4R3/4R1K1 w - - 0 1", (4, 4)));
+    }
+
+    #[test]
+    fn empty_or_unattacked_squares_are_zero() {
+        assert_eq!(0, see_of("4k3/8/8/8/8/8/8/4K3 w - - 0 1", (4, 4)));
+        assert_eq!(0, see_of("4k3/8/8/4r3/8/8/8/4K3 w - - 0 1", (4, 4)));
+    }
+}
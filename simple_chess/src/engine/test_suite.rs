@@ -0,0 +1,221 @@
+use crate::codec::extended_position_description::{decode_record_from_string, EpdRecord};
+use crate::codec::standard_algebraic_notation::{encode_move_as_string, SanParser};
+use crate::engine::Engine;
+use crate::error::ChessError;
+use crate::ChessMoveType;
+
+/// How the engine did on one position of a test suite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdTestResult {
+    id: Option<String>,
+    played: Option<String>,
+    solved: bool,
+    points: u32,
+    max_points: u32,
+}
+
+impl EpdTestResult {
+    /// Returns the name of the position from its `id` operation.
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the move the engine chose in SAN, `None` if the position has no legal moves.
+    pub fn get_played(&self) -> Option<&str> {
+        self.played.as_deref()
+    }
+
+    /// Returns `true` if the engine chose a best move and avoided the moves to avoid.
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Returns the points the chosen move earned.
+    pub fn get_points(&self) -> u32 {
+        self.points
+    }
+
+    /// Returns the points the best move of the position earns.
+    pub fn get_max_points(&self) -> u32 {
+        self.max_points
+    }
+}
+
+/// How the engine did on a test suite, see `run_epd_suite`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpdSuiteResult {
+    results: Vec<EpdTestResult>,
+}
+
+impl EpdSuiteResult {
+    /// Returns the result of each position in the order of the suite.
+    pub fn get_results(&self) -> &[EpdTestResult] {
+        &self.results
+    }
+
+    /// Returns the number of positions the engine solved.
+    pub fn get_solved(&self) -> usize {
+        self.results.iter().filter(|result| result.solved).count()
+    }
+
+    /// Returns the number of positions in the suite.
+    pub fn get_total(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns the points earned over all positions.
+    pub fn get_points(&self) -> u32 {
+        self.results.iter().map(|result| result.points).sum()
+    }
+
+    /// Returns the points a perfect engine would earn.
+    pub fn get_max_points(&self) -> u32 {
+        self.results.iter().map(|result| result.max_points).sum()
+    }
+}
+
+/// Scores an engine against a suite of test positions in EPD, such as WAC or STS.
+///
+/// Each position is searched to the engine's depth. A position is solved if the chosen
+/// move is one of its `bm` moves and none of its `am` moves. Suites in the style of STS
+/// give points for several moves with a comment such as `c0 "Nf3=10, Nc3=5"`, in which
+/// case the chosen move earns its points from the comment. Otherwise a solved position is
+/// worth one point. Empty lines and lines starting with `#` are skipped.
+///
+/// # Arguments
+///
+/// * `suite` - The suite, one position per line.
+/// * `engine` - The engine to score.
+///
+/// # Returns
+///
+/// * `Ok(EpdSuiteResult)` - The result of each position.
+/// * `Err(ChessError)` - If a line is not valid EPD or names a move that is not legal.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::test_suite::run_epd_suite;
+/// use simple_chess::engine::Engine;
+///
+/// let suite = r#"
+/// 6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id "mate.001";
+/// 7k/8/8/8/8/8/1q6/K7 w - - bm Kxb2; id "capture.001";
+/// "#;
+/// let result = run_epd_suite(suite, &mut Engine::new().set_depth(2)).unwrap();
+///
+/// assert_eq!(2, result.get_solved());
+/// assert_eq!(Some("Ra8#"), result.get_results()[0].get_played());
+/// ```
+pub fn run_epd_suite(suite: &str, engine: &mut Engine) -> Result<EpdSuiteResult, ChessError> {
+    let mut results = Vec::new();
+    for line in suite.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let record = decode_record_from_string(line)?;
+        results.push(run_position(&record, engine)?);
+    }
+    Ok(EpdSuiteResult { results })
+}
+
+fn run_position(record: &EpdRecord, engine: &mut Engine) -> Result<EpdTestResult, ChessError> {
+    let game = record.build_game()?;
+    let best_moves = record.get_best_moves()?;
+    let avoid_moves = record.get_avoid_moves()?;
+    let scored_moves = decode_scored_moves(record)?;
+
+    let result = engine.search(&game, engine.get_depth());
+    let played = result.get_best_move().copied();
+    let solved = played.is_some_and(|played| {
+        (best_moves.is_empty() || best_moves.contains(&played)) && !avoid_moves.contains(&played)
+    });
+
+    let (points, max_points) = if scored_moves.is_empty() {
+        (u32::from(solved), 1)
+    } else {
+        let points = scored_moves
+            .iter()
+            .find(|(chess_move, _)| Some(*chess_move) == played)
+            .map_or(0, |(_, points)| *points);
+        let max_points = scored_moves.iter().map(|(_, points)| *points).max();
+        (points, max_points.unwrap_or_default())
+    };
+
+    Ok(EpdTestResult {
+        id: record.get_id().map(String::from),
+        played: played.map(|played| encode_move_as_string(&game, &played)),
+        solved,
+        points,
+        max_points,
+    })
+}
+
+/// Reads the moves and points of a `c0` comment such as `"Nf3=10, Nc3=5"`, empty when the
+/// comment does not give points.
+fn decode_scored_moves(record: &EpdRecord) -> Result<Vec<(ChessMoveType, u32)>, ChessError> {
+    let Some(comment) = record
+        .get_operands("c0")
+        .and_then(|operands| operands.first())
+    else {
+        return Ok(Vec::new());
+    };
+    let entries: Option<Vec<(&str, u32)>> = comment
+        .split(',')
+        .map(|entry| {
+            let (san, points) = entry.trim().split_once('=')?;
+            Some((san, points.parse().ok()?))
+        })
+        .collect();
+    let Some(entries) = entries else {
+        return Ok(Vec::new());
+    };
+
+    let game = record.build_game()?;
+    let parser = SanParser::new().set_lenient(true);
+    entries
+        .into_iter()
+        .map(|(san, points)| Ok((parser.parse(&game, san)?, points)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn avoided_moves_are_not_solved() {
+        let suite = "7k/8/8/8/8/8/1q6/K7 w - - am Kxb2;\n7k/8/8/8/8/8/1q6/K7 w - - bm Kxb2;";
+        let result = run_epd_suite(suite, &mut Engine::new().set_depth(2)).unwrap();
+
+        assert_eq!(2, result.get_total());
+        assert!(!result.get_results()[0].is_solved());
+        assert!(result.get_results()[1].is_solved());
+    }
+
+    #[test]
+    fn comments_give_points_to_several_moves() {
+        let suite =
+            r#"6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; c0 "Ra8#=10, Ra7=2, Kf2=1"; id "sts.1";"#;
+        let result = run_epd_suite(suite, &mut Engine::new().set_depth(2)).unwrap();
+
+        assert_eq!(1, result.get_solved());
+        assert_eq!(10, result.get_points());
+        assert_eq!(10, result.get_max_points());
+        assert_eq!(Some("sts.1"), result.get_results()[0].get_id());
+    }
+
+    #[test]
+    fn invalid_suites_are_errors() {
+        let mut engine = Engine::new().set_depth(1);
+
+        assert!(run_epd_suite("not a position", &mut engine).is_err());
+        assert!(run_epd_suite("4k3/8/8/8/8/8/8/4K3 w - - bm Qh5;", &mut engine).is_err());
+        assert_eq!(
+            0,
+            run_epd_suite("# no positions\n\n", &mut engine)
+                .unwrap()
+                .get_total()
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::clock::ChessClock;
+use crate::Color;
+use std::time::Duration;
+
+/// The number of moves the remaining time is shared between when the time control does not
+/// say how many moves are left until the next time control.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+/// How many times the soft limit the search may go on for to finish a depth.
+const HARD_LIMIT_FACTOR: u32 = 4;
+
+/// The time kept back by default for the delay between the engine and the clock.
+const DEFAULT_MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+
+/// How long a search may take, see `Engine::search_with_time_limits`.
+///
+/// The search does not start another depth once the soft limit has passed, and gives up on
+/// the depth it is searching once the hard limit has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLimits {
+    soft_limit: Duration,
+    hard_limit: Duration,
+}
+
+impl TimeLimits {
+    /// Creates time limits, the soft limit is lowered to the hard limit if it is greater.
+    ///
+    /// # Arguments
+    ///
+    /// * `soft_limit` - The time after which no further depth is started.
+    /// * `hard_limit` - The time after which the search is stopped.
+    pub fn new(soft_limit: Duration, hard_limit: Duration) -> Self {
+        Self {
+            soft_limit: soft_limit.min(hard_limit),
+            hard_limit,
+        }
+    }
+
+    /// Creates time limits for searching for exactly the given time, like the `movetime`
+    /// argument of UCI.
+    pub fn fixed(move_time: Duration) -> Self {
+        Self::new(move_time, move_time)
+    }
+
+    /// Returns the time after which no further depth is started.
+    pub fn get_soft_limit(&self) -> Duration {
+        self.soft_limit
+    }
+
+    /// Returns the time after which the search is stopped.
+    pub fn get_hard_limit(&self) -> Duration {
+        self.hard_limit
+    }
+}
+
+/// Decides how long the engine may think about a move given the time left on its clock.
+///
+/// The remaining time, less a small overhead, is shared evenly between the moves left until
+/// the next time control, or `30` moves when that is not known, and most of the increment
+/// is added on top. That share is the soft limit, the hard limit allows a few times as much
+/// but never more than the time left on the clock.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::time_manager::TimeManager;
+/// use std::time::Duration;
+///
+/// let limits = TimeManager::new(Duration::from_secs(60), Duration::from_secs(1))
+///     .set_moves_to_go(Some(20))
+///     .set_move_overhead(Duration::ZERO)
+///     .get_limits();
+///
+/// // 60 seconds over 20 moves plus three quarters of the increment
+/// assert_eq!(Duration::from_millis(3750), limits.get_soft_limit());
+/// assert_eq!(Duration::from_secs(15), limits.get_hard_limit());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeManager {
+    remaining_time: Duration,
+    increment: Duration,
+    moves_to_go: Option<u32>,
+    move_overhead: Duration,
+}
+
+impl TimeManager {
+    /// Creates a `TimeManager` for a player with the given time left and increment.
+    ///
+    /// # Arguments
+    ///
+    /// * `remaining_time` - The time left on the player's clock.
+    /// * `increment` - The time added to the player's clock after each move.
+    pub fn new(remaining_time: Duration, increment: Duration) -> Self {
+        Self {
+            remaining_time,
+            increment,
+            moves_to_go: None,
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+        }
+    }
+
+    /// Creates a `TimeManager` for a player from the time left on a game's clock and the
+    /// increment of their time control.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock of the game.
+    /// * `color` - The player the engine is playing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::clock::{ChessClock, TimeControl};
+    /// use simple_chess::engine::time_manager::TimeManager;
+    /// use simple_chess::Color;
+    /// use std::time::Duration;
+    ///
+    /// let clock = ChessClock::new(TimeControl::new(Duration::from_secs(180), Duration::from_secs(2)));
+    /// let manager = TimeManager::from_clock(&clock, Color::White);
+    ///
+    /// assert_eq!(Duration::from_secs(180), manager.get_remaining_time());
+    /// assert_eq!(Duration::from_secs(2), manager.get_increment());
+    /// ```
+    pub fn from_clock(clock: &ChessClock, color: Color) -> Self {
+        Self::new(
+            clock.get_remaining_time(color),
+            clock.get_time_control(color).get_increment(),
+        )
+    }
+
+    /// Creates a `TimeManager` for a player from the arguments of a UCI `go` command, given
+    /// in milliseconds as `wtime`, `btime`, `winc`, `binc` and `movestogo`.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The `go` command, with or without the leading `go`.
+    /// * `color` - The player the engine is playing.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(TimeManager)` - The time manager for the player.
+    /// * `None` - If the command does not give the player's remaining time, or a value is
+    ///   not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::time_manager::TimeManager;
+    /// use simple_chess::Color;
+    /// use std::time::Duration;
+    ///
+    /// let command = "go wtime 120000 btime 90000 winc 1000 binc 1000 movestogo 12";
+    /// let manager = TimeManager::from_uci_go(command, Color::Black).unwrap();
+    ///
+    /// assert_eq!(Duration::from_secs(90), manager.get_remaining_time());
+    /// assert_eq!(Some(12), manager.get_moves_to_go());
+    /// assert!(TimeManager::from_uci_go("go infinite", Color::White).is_none());
+    /// ```
+    pub fn from_uci_go(command: &str, color: Color) -> Option<Self> {
+        let (time_key, increment_key) = match color {
+            Color::White => ("wtime", "winc"),
+            Color::Black => ("btime", "binc"),
+        };
+
+        let mut remaining_time = None;
+        let mut increment = Duration::ZERO;
+        let mut moves_to_go = None;
+        let mut tokens = command.split_whitespace();
+        while let Some(token) = tokens.next() {
+            if token == "go" || ![time_key, increment_key, "movestogo"].contains(&token) {
+                continue;
+            }
+            // engines may be sent negative times when the clock has already run out
+            let value = tokens.next()?.parse::<i64>().ok()?.max(0) as u64;
+            match token {
+                "movestogo" => moves_to_go = Some(value as u32),
+                key if key == time_key => remaining_time = Some(Duration::from_millis(value)),
+                _ => increment = Duration::from_millis(value),
+            }
+        }
+
+        Some(Self::new(remaining_time?, increment).set_moves_to_go(moves_to_go))
+    }
+
+    /// Sets the number of moves left until the next time control, `None` for a time control
+    /// covering the rest of the game.
+    pub fn set_moves_to_go(mut self, moves_to_go: Option<u32>) -> Self {
+        self.moves_to_go = moves_to_go;
+        self
+    }
+
+    /// Sets the time kept back for the delay between the engine deciding on a move and the
+    /// clock being stopped, 50 milliseconds by default.
+    pub fn set_move_overhead(mut self, move_overhead: Duration) -> Self {
+        self.move_overhead = move_overhead;
+        self
+    }
+
+    /// Returns the time left on the player's clock.
+    pub fn get_remaining_time(&self) -> Duration {
+        self.remaining_time
+    }
+
+    /// Returns the time added to the player's clock after each move.
+    pub fn get_increment(&self) -> Duration {
+        self.increment
+    }
+
+    /// Returns the number of moves left until the next time control, if known.
+    pub fn get_moves_to_go(&self) -> Option<u32> {
+        self.moves_to_go
+    }
+
+    /// Returns the time kept back for the delay between the engine and the clock.
+    pub fn get_move_overhead(&self) -> Duration {
+        self.move_overhead
+    }
+
+    /// Returns the time limits for the player's next move.
+    pub fn get_limits(&self) -> TimeLimits {
+        let available_time = self.remaining_time.saturating_sub(self.move_overhead);
+        let moves_to_go = self.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+
+        let soft_limit = available_time / moves_to_go + self.increment * 3 / 4;
+        let hard_limit = (soft_limit * HARD_LIMIT_FACTOR).min(available_time);
+        TimeLimits::new(soft_limit, hard_limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_never_exceed_the_time_left() {
+        // the increment alone is more than the time left on the clock
+        let limits = TimeManager::new(Duration::from_millis(500), Duration::from_secs(2))
+            .set_move_overhead(Duration::from_millis(100))
+            .get_limits();
+        assert_eq!(Duration::from_millis(400), limits.get_hard_limit());
+        assert_eq!(Duration::from_millis(400), limits.get_soft_limit());
+
+        // the last move before the time control may use all of the time left
+        let limits = TimeManager::new(Duration::from_secs(10), Duration::ZERO)
+            .set_moves_to_go(Some(1))
+            .set_move_overhead(Duration::ZERO)
+            .get_limits();
+        assert_eq!(TimeLimits::fixed(Duration::from_secs(10)), limits);
+
+        let flagged = TimeManager::from_uci_go("wtime -20 btime 1000", Color::White).unwrap();
+        assert_eq!(Duration::ZERO, flagged.get_limits().get_hard_limit());
+    }
+}
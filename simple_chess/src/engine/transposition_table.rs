@@ -0,0 +1,218 @@
+use crate::ChessMoveType;
+use std::mem::size_of;
+
+/// How the score of a `TranspositionEntry` relates to the true score of its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// The score is the exact score of the position.
+    Exact,
+    /// The search failed high, the true score is at least the stored score.
+    Lower,
+    /// The search failed low, the true score is at most the stored score.
+    Upper,
+}
+
+/// The result of searching a position, stored so it does not have to be searched again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TranspositionEntry {
+    key: u64,
+    depth: u32,
+    bound: Bound,
+    score: i32,
+    best_move: Option<ChessMoveType>,
+}
+
+impl TranspositionEntry {
+    /// Creates a new `TranspositionEntry`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The Zobrist hash of the position.
+    /// * `depth` - The depth, in plies, the position was searched to.
+    /// * `bound` - How the score relates to the true score of the position.
+    /// * `score` - The score of the position.
+    /// * `best_move` - The best move found in the position, if any.
+    pub fn new(
+        key: u64,
+        depth: u32,
+        bound: Bound,
+        score: i32,
+        best_move: Option<ChessMoveType>,
+    ) -> Self {
+        Self {
+            key,
+            depth,
+            bound,
+            score,
+            best_move,
+        }
+    }
+
+    /// Returns the Zobrist hash of the position.
+    pub fn get_key(&self) -> u64 {
+        self.key
+    }
+
+    /// Returns the depth, in plies, the position was searched to.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns how the score relates to the true score of the position.
+    pub fn get_bound(&self) -> Bound {
+        self.bound
+    }
+
+    /// Returns the score of the position.
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns the best move found in the position, if any.
+    pub fn get_best_move(&self) -> Option<&ChessMoveType> {
+        self.best_move.as_ref()
+    }
+}
+
+/// A fixed size hash table of search results keyed by Zobrist hash.
+///
+/// Different move orders often lead to the same position, the table lets the search reuse
+/// the result of a position it has already searched instead of searching it again. When
+/// two positions map to the same slot the entry searched to the greater depth is kept,
+/// unless the slot belongs to a different position, which is always replaced.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::transposition_table::{Bound, TranspositionEntry, TranspositionTable};
+///
+/// let mut table = TranspositionTable::with_capacity_mb(1);
+/// table.store(TranspositionEntry::new(42, 3, Bound::Exact, 150, None));
+///
+/// assert_eq!(150, table.probe(42).unwrap().get_score());
+/// assert!(table.probe(43).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: Vec<Option<TranspositionEntry>>,
+}
+
+impl TranspositionTable {
+    /// Creates a table using roughly the given number of megabytes of memory.
+    ///
+    /// A size of `0` creates an empty table that never stores anything, which disables
+    /// the table in the search.
+    pub fn with_capacity_mb(megabytes: usize) -> Self {
+        Self::with_capacity(megabytes * 1024 * 1024 / size_of::<Option<TranspositionEntry>>())
+    }
+
+    /// Returns the number of entries the table can hold.
+    pub fn get_capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the entry stored for a position, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The Zobrist hash of the position.
+    pub fn probe(&self, key: u64) -> Option<&TranspositionEntry> {
+        let index = self.get_index(key)?;
+        self.entries[index]
+            .as_ref()
+            .filter(|entry| entry.key == key)
+    }
+
+    /// Stores an entry, replacing the entry in its slot unless that entry is for the same
+    /// position and was searched deeper.
+    pub fn store(&mut self, entry: TranspositionEntry) {
+        let index = match self.get_index(entry.key) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let replace = match &self.entries[index] {
+            Some(existing) => existing.key != entry.key || entry.depth >= existing.depth,
+            None => true,
+        };
+        if replace {
+            self.entries[index] = Some(entry);
+        }
+    }
+
+    /// Creates a table with room for exactly `capacity` entries.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: vec![None; capacity],
+        }
+    }
+
+    /// Returns the entries stored in the table.
+    pub(crate) fn get_entries(&self) -> impl Iterator<Item = &TranspositionEntry> {
+        self.entries.iter().flatten()
+    }
+
+    /// Removes every entry from the table.
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+
+    fn get_index(&self, key: u64) -> Option<usize> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some((key % self.entries.len() as u64) as usize)
+        }
+    }
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::with_capacity_mb(16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deeper_entries_are_kept_for_the_same_position() {
+        let mut table = TranspositionTable::with_capacity_mb(1);
+        table.store(TranspositionEntry::new(7, 5, Bound::Exact, 10, None));
+        table.store(TranspositionEntry::new(7, 2, Bound::Lower, 20, None));
+        assert_eq!(5, table.probe(7).unwrap().get_depth());
+
+        table.store(TranspositionEntry::new(7, 6, Bound::Upper, 30, None));
+        assert_eq!(Bound::Upper, table.probe(7).unwrap().get_bound());
+    }
+
+    #[test]
+    fn colliding_positions_replace_each_other() {
+        let mut table = TranspositionTable::with_capacity_mb(1);
+        let capacity = table.get_capacity() as u64;
+        table.store(TranspositionEntry::new(1, 9, Bound::Exact, 10, None));
+        table.store(TranspositionEntry::new(
+            1 + capacity,
+            1,
+            Bound::Exact,
+            20,
+            None,
+        ));
+
+        assert!(table.probe(1).is_none());
+        assert_eq!(20, table.probe(1 + capacity).unwrap().get_score());
+
+        table.clear();
+        assert!(table.probe(1 + capacity).is_none());
+    }
+
+    #[test]
+    fn empty_table_stores_nothing() {
+        let mut table = TranspositionTable::with_capacity_mb(0);
+        table.store(TranspositionEntry::new(1, 1, Bound::Exact, 10, None));
+
+        assert_eq!(0, table.get_capacity());
+        assert!(table.probe(1).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::square_name::SquareNameError;
+use game_board::BoardError;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The errors returned by the parts of the crate that read positions and play moves.
+///
+/// The other errors of the crate can be converted into a `ChessError` with `?`, so callers
+/// working with several of them can use a single error type. Errors reading text or saved
+/// data become `ParseError`, errors setting up a board become `InvalidBoard` and lines with
+/// an illegal move become `IllegalMove`, the rest have a variant of their own.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::ChessError;
+///
+/// match build_game_from_string("8/8/8 w - - 0 1") {
+///     Err(ChessError::InvalidFen(reason)) => assert!(reason.contains("8 ranks")),
+///     _ => panic!("expected an invalid FEN string"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChessError {
+    /// A string in Forsyth-Edwards Notation could not be parsed, with the reason why.
+    InvalidFen(String),
+    /// A move is not legal in the position it was played in, with the move.
+    IllegalMove(String),
+    /// A square outside the board was used.
+    OutOfBounds { col: usize, row: usize },
+    /// A board could not be created, with the reason why.
+    InvalidBoard(String),
+    /// Text or saved data, such as a square name, a link or a saved game, could not be
+    /// read, with the reason why.
+    ParseError(String),
+    /// A move was made after the game had ended by resignation or agreement.
+    GameOver,
+    /// An engine run over UCI failed, with the `UciError` message.
+    Engine(String),
+    /// A game could not be saved or loaded, with the `StorageError` message.
+    Storage(String),
+    /// A board image could not be produced, with the `RenderError` message.
+    Render(String),
+    /// A scenario script failed, with the `ScenarioError` message.
+    Scenario(String),
+    /// A `GameManager` could not do what was asked, with the `GameManagerError` message.
+    GameManager(String),
+}
+
+impl Display for ChessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChessError::InvalidFen(reason) => write!(f, "Forsyth-Edwards Notation Error: {reason}"),
+            ChessError::IllegalMove(chess_move) => write!(f, "Illegal move: {chess_move}"),
+            ChessError::OutOfBounds { col, row } => {
+                write!(f, "The square ({col}, {row}) is outside of the board")
+            }
+            ChessError::InvalidBoard(reason) => write!(f, "Invalid board: {reason}"),
+            ChessError::ParseError(reason) => write!(f, "Parse error: {reason}"),
+            ChessError::GameOver => write!(f, "The game is over"),
+            ChessError::Engine(message)
+            | ChessError::Storage(message)
+            | ChessError::Render(message)
+            | ChessError::Scenario(message)
+            | ChessError::GameManager(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for ChessError {}
+
+impl From<BoardError> for ChessError {
+    fn from(error: BoardError) -> Self {
+        match error {
+            BoardError::OutOfBounds { col, row, .. } => ChessError::OutOfBounds { col, row },
+            _ => ChessError::InvalidBoard(error.to_string()),
+        }
+    }
+}
+
+impl From<SquareNameError> for ChessError {
+    fn from(error: SquareNameError) -> Self {
+        ChessError::ParseError(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::binary::{decode_game_from_binary, encode_game_as_binary};
+    use crate::position::Position;
+    use crate::square_name::SquareName;
+    use crate::{ChessGame, ChessGameBuilder};
+    use game_board::Board;
+
+    fn place_king(col: usize, row: usize) -> Result<(), ChessError> {
+        let mut board = Board::try_build(8, 8)?;
+        board.try_place_piece(0u8, col, row)?;
+        Ok(())
+    }
+
+    #[test]
+    fn board_errors_convert_with_the_question_mark_operator() {
+        assert!(place_king(7, 7).is_ok());
+        assert_eq!(
+            Err(ChessError::OutOfBounds { col: 8, row: 0 }),
+            place_king(8, 0)
+        );
+    }
+
+    #[test]
+    fn square_name_errors_are_parse_errors() {
+        let error: ChessError = "z9".parse::<SquareName>().unwrap_err().into();
+        assert!(matches!(error, ChessError::ParseError(_)));
+    }
+
+    fn load_and_move_from(bytes: &[u8], square: &str) -> Result<usize, ChessError> {
+        let (game, _) = decode_game_from_binary(bytes)?;
+        let position: Position = square.parse()?;
+        Ok(game.get_moves().len() + position.get_row())
+    }
+
+    #[test]
+    fn errors_of_the_rest_of_the_crate_convert_with_the_question_mark_operator() {
+        assert!(matches!(
+            load_and_move_from(&[0xff], "e4"),
+            Err(ChessError::ParseError(_))
+        ));
+        let bytes = encode_game_as_binary(&ChessGame::new(), None);
+        assert_eq!(Ok(3), load_and_move_from(&bytes, "e4"));
+        assert!(matches!(
+            load_and_move_from(&bytes, "not a square"),
+            Err(ChessError::ParseError(_))
+        ));
+        assert_eq!(
+            Err(ChessError::InvalidBoard(String::from("no board was set"))),
+            ChessGameBuilder::new().build().map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::chess_game_state_analyzer::GameState;
+use crate::scoring::{Outcome, Scoring};
+use crate::Color;
+
+/// How a finished game came to an end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// The player to move was checkmated.
+    Checkmate,
+    /// The player to move had no legal move and was not in check.
+    Stalemate,
+    /// The variant's own winning condition was met, such as a third check.
+    VariantWin,
+    /// A player resigned.
+    Resignation,
+    /// The players agreed to a draw.
+    DrawAgreement,
+    /// An arbiter or an `Adjudicator` decided the result.
+    Adjudication,
+    /// Neither player could checkmate by any sequence of legal moves.
+    DeadPosition,
+    /// Seventy-five moves were played by each player without a pawn move or capture.
+    SeventyFiveMoveRule,
+    /// A player ran out of time on the clock.
+    TimeForfeit,
+}
+
+impl Termination {
+    /// Returns the termination as it is written in the PGN `Termination` tag.
+    ///
+    /// PGN only tells apart games that ended by the rules, `normal`, from those decided by
+    /// an arbiter, `adjudication`, and those lost on time, `time forfeit`.
+    pub fn as_pgn_str(&self) -> &'static str {
+        match self {
+            Termination::Adjudication => "adjudication",
+            Termination::TimeForfeit => "time forfeit",
+            _ => "normal",
+        }
+    }
+}
+
+/// The result of a finished game, who won it and how it ended.
+///
+/// Unlike `GameState`, which describes the position while the game is played, a result only
+/// exists once the game is over, and its winner follows the `Scoring` of the game.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::game_result::Termination;
+/// use simple_chess::{ChessGame, Color};
+///
+/// let mut game = ChessGame::new();
+/// assert!(game.get_result().is_none());
+///
+/// game.resign(Color::White).unwrap();
+/// let result = game.get_result().unwrap();
+/// assert_eq!(Some(Color::Black), result.get_winner());
+/// assert_eq!(Termination::Resignation, result.get_termination());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    winner: Option<Color>,
+    termination: Termination,
+}
+
+impl GameResult {
+    /// Creates a result.
+    ///
+    /// # Arguments
+    ///
+    /// * `winner` - The winning player, `None` for a draw.
+    /// * `termination` - How the game ended.
+    pub fn new(winner: Option<Color>, termination: Termination) -> GameResult {
+        GameResult {
+            winner,
+            termination,
+        }
+    }
+
+    /// Returns the result of a game that is in a state.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state of the game.
+    /// * `scoring` - How the game is scored.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(GameResult)` - The result of a finished game, resignations and agreed draws
+    ///   end by `Resignation` and `DrawAgreement`.
+    /// * `None` - If the game is still in progress.
+    pub fn from_state(state: &GameState, scoring: Scoring) -> Option<GameResult> {
+        let termination = match state {
+            GameState::InProgress { .. } | GameState::Check { .. } => return None,
+            GameState::Checkmate { .. } => Termination::Checkmate,
+            GameState::Stalemate => Termination::Stalemate,
+            GameState::VariantWin { .. } => Termination::VariantWin,
+            GameState::Resigned { .. } => Termination::Resignation,
+            GameState::DrawAgreed => Termination::DrawAgreement,
+            GameState::DeadPosition => Termination::DeadPosition,
+            GameState::SeventyFiveMoveRule => Termination::SeventyFiveMoveRule,
+        };
+        let winner = scoring.get_outcome(state)?.get_winner();
+        Some(GameResult::new(winner, termination))
+    }
+
+    /// Returns the winning player, `None` for a draw.
+    pub fn get_winner(&self) -> Option<Color> {
+        self.winner
+    }
+
+    /// Returns how the game ended.
+    pub fn get_termination(&self) -> Termination {
+        self.termination
+    }
+
+    /// Returns `true` if the game was drawn.
+    pub fn is_draw(&self) -> bool {
+        self.winner.is_none()
+    }
+
+    /// Returns the scored outcome of the game.
+    pub fn get_outcome(&self) -> Outcome {
+        match self.winner {
+            Some(winner) => Outcome::Win { winner },
+            None => Outcome::Draw,
+        }
+    }
+
+    /// Returns the result as it is written in the PGN `Result` tag, `1-0`, `0-1` or
+    /// `1/2-1/2`.
+    pub fn as_pgn_str(&self) -> &'static str {
+        self.get_outcome().as_pgn_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_only_exist_for_finished_games() {
+        let in_progress = GameState::Check {
+            legal_moves: Vec::new(),
+            turn: Color::White,
+        };
+        assert_eq!(
+            None,
+            GameResult::from_state(&in_progress, Scoring::Standard)
+        );
+
+        let stalemate = GameResult::from_state(&GameState::Stalemate, Scoring::Standard).unwrap();
+        assert!(stalemate.is_draw());
+        assert_eq!(Termination::Stalemate, stalemate.get_termination());
+        assert_eq!("1/2-1/2", stalemate.as_pgn_str());
+
+        let armageddon = Scoring::Armageddon {
+            draw_winner: Color::Black,
+        };
+        let stalemate = GameResult::from_state(&GameState::Stalemate, armageddon).unwrap();
+        assert_eq!(Some(Color::Black), stalemate.get_winner());
+        assert_eq!(Termination::Stalemate, stalemate.get_termination());
+        assert_eq!("0-1", stalemate.as_pgn_str());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{ChessMoveType, Color};
+use std::time::Duration;
+
+/// A move of a game together with what is known about it at the time it was played.
+///
+/// Records are kept by the game as moves are made, see `ChessGame::get_history`, so replay
+/// views and exporters can show the notation and position of each move without playing
+/// the game again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRecord {
+    chess_move: ChessMoveType,
+    san: String,
+    fen_after: String,
+    color: Color,
+    move_number: usize,
+    remaining_time: Option<Duration>,
+    is_check: bool,
+    is_checkmate: bool,
+}
+
+impl MoveRecord {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        chess_move: ChessMoveType,
+        san: String,
+        fen_after: String,
+        color: Color,
+        move_number: usize,
+        remaining_time: Option<Duration>,
+        is_check: bool,
+        is_checkmate: bool,
+    ) -> Self {
+        Self {
+            chess_move,
+            san,
+            fen_after,
+            color,
+            move_number,
+            remaining_time,
+            is_check,
+            is_checkmate,
+        }
+    }
+
+    /// Returns the move.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.chess_move
+    }
+
+    /// Returns the move in Standard Algebraic Notation, such as `Nf3` or `exd8=Q#`.
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Returns the position after the move in Forsyth-Edwards Notation.
+    pub fn get_fen_after(&self) -> &str {
+        &self.fen_after
+    }
+
+    /// Returns the player who made the move.
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    /// Returns the number of the move as it is written in a game score, both players' moves
+    /// of a turn sharing a number.
+    pub fn get_move_number(&self) -> usize {
+        self.move_number
+    }
+
+    /// Returns the time the player had left after the move, if it was made with
+    /// `ChessGame::make_timed_move`.
+    pub fn get_remaining_time(&self) -> Option<Duration> {
+        self.remaining_time
+    }
+
+    /// Returns `true` if the move gave check, including checkmate.
+    pub fn is_check(&self) -> bool {
+        self.is_check
+    }
+
+    /// Returns `true` if the move gave checkmate.
+    pub fn is_checkmate(&self) -> bool {
+        self.is_checkmate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::{ChessClock, TimeControl};
+    use crate::codec::standard_algebraic_notation::{decode_move_from_string, play_moves};
+    use crate::{ChessGame, Color};
+    use std::time::Duration;
+
+    #[test]
+    fn records_describe_each_move_as_it_was_played() {
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &["f3", "e5", "g4", "Qh4#"]);
+
+        let history: Vec<_> = game.get_history().collect();
+        assert_eq!(4, history.len());
+        assert_eq!(
+            vec!["f3", "e5", "g4", "Qh4#"],
+            history
+                .iter()
+                .map(|record| record.get_san())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "rnbqkbnr/pppp1ppp/8/4p3/8/5P2/PPPPP1PP/RNBQKBNR w KQkq e6 0 2",
+            history[1].get_fen_after()
+        );
+        assert_eq!(Color::Black, history[3].get_color());
+        assert_eq!(2, history[3].get_move_number());
+        assert!(history[3].is_check() && history[3].is_checkmate());
+        assert!(!history[2].is_check());
+        assert_eq!(None, history[0].get_remaining_time());
+    }
+
+    #[test]
+    fn timed_moves_record_the_time_left() {
+        let mut game = ChessGame::new();
+        let mut clock = ChessClock::new(TimeControl::new(
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+        ));
+        let chess_move = decode_move_from_string(&game, "e4").unwrap();
+        game.make_timed_move(chess_move, &mut clock, Duration::from_secs(5))
+            .unwrap();
+
+        let record = game.get_history().next().unwrap();
+        assert_eq!(Some(Duration::from_secs(56)), record.get_remaining_time());
+        assert_eq!(Color::Black, clock.get_current_turn());
+    }
+}
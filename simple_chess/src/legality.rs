@@ -0,0 +1,612 @@
+use crate::chess_game_state_analyzer::{is_in_check, is_square_attacked};
+use crate::piece::ChessPiece;
+use crate::piece::PieceType::{King, Rook};
+use crate::position::find_king;
+use crate::{ChessGame, ChessMoveType, Color};
+use game_board::{get_square_name_from_row_and_col, Board};
+use std::fmt::{Display, Formatter};
+
+/// Why a move can not be played in the current position of a game.
+///
+/// Squares are given as `(column, row)`, the `Display` implementation describes the reason
+/// in words so that it can be shown to a player.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::legality::IllegalMoveReason;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessMoveType, Color};
+///
+/// let mut game = build_game_from_string("4r1k1/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+/// let knight_move = ChessMoveType::Move {
+///     original_position: (4, 1),
+///     new_position: (2, 2),
+///     piece: ChessPiece::new(PieceType::Knight, Color::White),
+///     taken_piece: None,
+///     promotion: None,
+/// };
+///
+/// let reason = game.get_illegal_move_reason(&knight_move).unwrap();
+/// assert!(matches!(reason, IllegalMoveReason::Pinned { .. }));
+/// assert_eq!(
+///     "the knight on e2 is pinned against the king on e1 by the rook on e8",
+///     reason.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalMoveReason {
+    /// There is no piece on the square the move starts from.
+    NoPiece { square: (usize, usize) },
+    /// The piece on the square the move starts from belongs to the player who is not to move.
+    NotYourTurn {
+        square: (usize, usize),
+        piece: ChessPiece,
+    },
+    /// The move describes a different piece than the one on the square it starts from.
+    WrongPiece {
+        square: (usize, usize),
+        piece: ChessPiece,
+    },
+    /// The destination is occupied by a piece of the player making the move.
+    DestinationOccupied {
+        square: (usize, usize),
+        piece: ChessPiece,
+    },
+    /// The piece does not move that way, or the move does not match the position, for
+    /// example a capture of a piece that is not there.
+    CannotMoveThere {
+        from: (usize, usize),
+        to: (usize, usize),
+        piece: ChessPiece,
+    },
+    /// Moving the piece would expose its king to an attack.
+    Pinned {
+        square: (usize, usize),
+        piece: ChessPiece,
+        king_square: (usize, usize),
+        pinned_by_square: (usize, usize),
+        pinned_by: ChessPiece,
+    },
+    /// The king would be in check after the move, either because it moves into check or
+    /// because the move does not stop a check.
+    KingInCheck {
+        king_square: (usize, usize),
+        attacker_square: (usize, usize),
+        attacker: ChessPiece,
+    },
+    /// The player has lost the right to castle to that side.
+    NoCastlingRights,
+    /// A piece stands between the king and the rook.
+    CastlingPathBlocked { square: (usize, usize) },
+    /// A player may not castle while in check.
+    CastlingOutOfCheck,
+    /// The king would pass through or land on an attacked square.
+    CastlingThroughCheck { square: (usize, usize) },
+    /// The move would be legal in standard chess, but the game's variant forbids it.
+    ForbiddenByVariant,
+}
+
+impl Display for IllegalMoveReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IllegalMoveReason::NoPiece { square } => {
+                write!(f, "there is no piece on {}", square_name(*square))
+            }
+            IllegalMoveReason::NotYourTurn { square, piece } => write!(
+                f,
+                "the {} on {} belongs to {}, who is not to move",
+                piece_name(piece),
+                square_name(*square),
+                color_name(piece.get_color())
+            ),
+            IllegalMoveReason::WrongPiece { square, piece } => write!(
+                f,
+                "the piece on {} is a {}",
+                square_name(*square),
+                piece_name(piece)
+            ),
+            IllegalMoveReason::DestinationOccupied { square, piece } => write!(
+                f,
+                "{} is occupied by your own {}",
+                square_name(*square),
+                piece_name(piece)
+            ),
+            IllegalMoveReason::CannotMoveThere { from, to, piece } => write!(
+                f,
+                "the {} on {} can not move to {}",
+                piece_name(piece),
+                square_name(*from),
+                square_name(*to)
+            ),
+            IllegalMoveReason::Pinned {
+                square,
+                piece,
+                king_square,
+                pinned_by_square,
+                pinned_by,
+            } => write!(
+                f,
+                "the {} on {} is pinned against the king on {} by the {} on {}",
+                piece_name(piece),
+                square_name(*square),
+                square_name(*king_square),
+                piece_name(pinned_by),
+                square_name(*pinned_by_square)
+            ),
+            IllegalMoveReason::KingInCheck {
+                king_square,
+                attacker_square,
+                attacker,
+            } => write!(
+                f,
+                "the king on {} would be in check from the {} on {}",
+                square_name(*king_square),
+                piece_name(attacker),
+                square_name(*attacker_square)
+            ),
+            IllegalMoveReason::NoCastlingRights => {
+                write!(f, "the right to castle to that side has been lost")
+            }
+            IllegalMoveReason::CastlingPathBlocked { square } => write!(
+                f,
+                "castling is blocked by the piece on {}",
+                square_name(*square)
+            ),
+            IllegalMoveReason::CastlingOutOfCheck => write!(f, "you can not castle out of check"),
+            IllegalMoveReason::CastlingThroughCheck { square } => write!(
+                f,
+                "the king can not castle through check, {} is attacked",
+                square_name(*square)
+            ),
+            IllegalMoveReason::ForbiddenByVariant => {
+                write!(f, "the move is not allowed in this variant")
+            }
+        }
+    }
+}
+
+/// Finds why a move is not legal in the current position of a game.
+///
+/// # Returns
+///
+/// * `None` - If the move is legal.
+/// * `Some(IllegalMoveReason)` - The first problem found with the move.
+pub(crate) fn get_illegal_move_reason(
+    game: &mut ChessGame,
+    chess_move: &ChessMoveType,
+) -> Option<IllegalMoveReason> {
+    if game.is_move_legal(chess_move) {
+        return None;
+    }
+
+    let reason = match chess_move {
+        ChessMoveType::Castle {
+            rook_original_position,
+            king_original_position,
+            king_new_position,
+            ..
+        } => get_castling_reason(
+            game,
+            *rook_original_position,
+            *king_original_position,
+            *king_new_position,
+        ),
+        ChessMoveType::Move {
+            original_position,
+            new_position,
+            piece,
+            ..
+        }
+        | ChessMoveType::EnPassant {
+            original_position,
+            new_position,
+            piece,
+            ..
+        } => get_piece_move_reason(game, chess_move, *original_position, *new_position, piece),
+    };
+    Some(reason.unwrap_or(IllegalMoveReason::ForbiddenByVariant))
+}
+
+fn get_piece_move_reason(
+    game: &ChessGame,
+    chess_move: &ChessMoveType,
+    from: (usize, usize),
+    to: (usize, usize),
+    piece: &ChessPiece,
+) -> Option<IllegalMoveReason> {
+    let board = game.get_board();
+    let on_square = get_piece_at(board, from);
+    if let Some(reason) = check_moving_piece(game, from, on_square, piece) {
+        return Some(reason);
+    }
+    if let Some(occupant) = get_piece_at(board, to) {
+        if occupant.get_color() == piece.get_color() {
+            return Some(IllegalMoveReason::DestinationOccupied {
+                square: to,
+                piece: occupant,
+            });
+        }
+    }
+
+    let is_possible = piece
+        .possible_moves_with_ruleset(
+            from,
+            board,
+            game.get_en_passant_target(),
+            game.get_ruleset(),
+        )
+        .contains(chess_move);
+    if !is_possible {
+        return Some(IllegalMoveReason::CannotMoveThere {
+            from,
+            to,
+            piece: *piece,
+        });
+    }
+
+    get_king_safety_reason(game, chess_move, from, piece)
+}
+
+fn get_castling_reason(
+    game: &ChessGame,
+    rook_square: (usize, usize),
+    king_square: (usize, usize),
+    king_destination: (usize, usize),
+) -> Option<IllegalMoveReason> {
+    let board = game.get_board();
+    let color = game.get_current_players_turn();
+    let king = ChessPiece::new(King, color);
+    if let Some(reason) =
+        check_moving_piece(game, king_square, get_piece_at(board, king_square), &king)
+    {
+        return Some(reason);
+    }
+
+    let castling_rights = game.get_castling_rights();
+    let (long, short) = match color {
+        Color::White => (castling_rights.0, castling_rights.1),
+        Color::Black => (castling_rights.2, castling_rights.3),
+    };
+    let is_short = king_destination.0 > king_square.0;
+    if (is_short && !short) || (!is_short && !long) {
+        return Some(IllegalMoveReason::NoCastlingRights);
+    }
+
+    let row = king_square.1;
+    let between = if is_short {
+        king_square.0 + 1..rook_square.0
+    } else {
+        rook_square.0 + 1..king_square.0
+    };
+    for col in between {
+        if board.get_piece_at_space(col, row).is_some() {
+            return Some(IllegalMoveReason::CastlingPathBlocked { square: (col, row) });
+        }
+    }
+
+    if !game.get_ruleset().is_study_mode() {
+        if is_in_check(color, board) {
+            return Some(IllegalMoveReason::CastlingOutOfCheck);
+        }
+        let path: Vec<usize> = if is_short {
+            (king_square.0 + 1..=king_destination.0).collect()
+        } else {
+            (king_destination.0..king_square.0).rev().collect()
+        };
+        for col in path {
+            if is_square_attacked(board, col, row, color.opposite()) {
+                return Some(IllegalMoveReason::CastlingThroughCheck { square: (col, row) });
+            }
+        }
+    }
+
+    let has_rook = get_piece_at(board, rook_square) == Some(ChessPiece::new(Rook, color));
+    if !has_rook || rook_square.1 != row {
+        return Some(IllegalMoveReason::CannotMoveThere {
+            from: king_square,
+            to: king_destination,
+            piece: king,
+        });
+    }
+    None
+}
+
+/// Checks the move starts from a piece of the player to move that matches the move.
+fn check_moving_piece(
+    game: &ChessGame,
+    square: (usize, usize),
+    on_square: Option<ChessPiece>,
+    piece: &ChessPiece,
+) -> Option<IllegalMoveReason> {
+    match on_square {
+        None => Some(IllegalMoveReason::NoPiece { square }),
+        Some(found) if found.get_color() != game.get_current_players_turn() => {
+            Some(IllegalMoveReason::NotYourTurn {
+                square,
+                piece: found,
+            })
+        }
+        Some(found) if found != *piece => Some(IllegalMoveReason::WrongPiece {
+            square,
+            piece: found,
+        }),
+        Some(_) => None,
+    }
+}
+
+/// Explains a move the piece can make that leaves its own king attacked.
+fn get_king_safety_reason(
+    game: &ChessGame,
+    chess_move: &ChessMoveType,
+    from: (usize, usize),
+    piece: &ChessPiece,
+) -> Option<IllegalMoveReason> {
+    if game.get_ruleset().is_study_mode() {
+        return None;
+    }
+    let color = piece.get_color();
+    let before = game.get_board();
+    let mut after = before.clone();
+    chess_move.make_move(&mut after);
+
+    let king_square = find_king(&after, color)?;
+    let (attacker_square, attacker) = find_attackers(&after, king_square, color.opposite())
+        .into_iter()
+        .min_by_key(|(square, _)| {
+            // prefer an attacker the move itself exposed, which makes the moved piece pinned
+            find_attackers(before, king_square, color.opposite())
+                .iter()
+                .any(|(existing, _)| existing == square)
+        })?;
+
+    let was_attacking = find_king(before, color).is_some_and(|king_before| {
+        find_attackers(before, king_before, color.opposite())
+            .iter()
+            .any(|(square, _)| *square == attacker_square)
+    });
+    if piece.get_piece_type() != King && !was_attacking {
+        Some(IllegalMoveReason::Pinned {
+            square: from,
+            piece: *piece,
+            king_square,
+            pinned_by_square: attacker_square,
+            pinned_by: attacker,
+        })
+    } else {
+        Some(IllegalMoveReason::KingInCheck {
+            king_square,
+            attacker_square,
+            attacker,
+        })
+    }
+}
+
+/// Returns every piece of `by_color` attacking a square.
+///
+/// Each piece is tested on its own by turning the other attacking pieces into pieces of the
+/// defending color, which still block lines but no longer attack.
+fn find_attackers(
+    board: &Board<ChessPiece>,
+    square: (usize, usize),
+    by_color: Color,
+) -> Vec<((usize, usize), ChessPiece)> {
+    let pieces: Vec<((usize, usize), ChessPiece)> = (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| (col, row)))
+        .filter_map(|at| get_piece_at(board, at).map(|piece| (at, piece)))
+        .filter(|(_, piece)| piece.get_color() == by_color)
+        .collect();
+
+    pieces
+        .iter()
+        .filter(|(candidate, _)| {
+            let mut alone = board.clone();
+            for (other, piece) in &pieces {
+                if other != candidate {
+                    alone.place_piece(
+                        ChessPiece::new(piece.get_piece_type(), by_color.opposite()),
+                        other.0,
+                        other.1,
+                    );
+                }
+            }
+            is_square_attacked(&alone, square.0, square.1, by_color)
+        })
+        .copied()
+        .collect()
+}
+
+fn get_piece_at(board: &Board<ChessPiece>, (col, row): (usize, usize)) -> Option<ChessPiece> {
+    board
+        .try_get_piece_at_space(col, row)
+        .ok()
+        .flatten()
+        .copied()
+}
+
+fn square_name((col, row): (usize, usize)) -> String {
+    get_square_name_from_row_and_col(col, row)
+}
+
+fn piece_name(piece: &ChessPiece) -> String {
+    format!("{:?}", piece.get_piece_type()).to_lowercase()
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::piece::PieceType::{Knight, Pawn, Queen};
+    use crate::variant::Variant;
+    use crate::ChessGameBuilder;
+
+    fn piece_move(from: (usize, usize), to: (usize, usize), piece: ChessPiece) -> ChessMoveType {
+        ChessMoveType::Move {
+            original_position: from,
+            new_position: to,
+            piece,
+            taken_piece: None,
+            promotion: None,
+        }
+    }
+
+    fn short_castle() -> ChessMoveType {
+        ChessMoveType::Castle {
+            rook_original_position: (7, 0),
+            rook_new_position: (5, 0),
+            king_original_position: (4, 0),
+            king_new_position: (6, 0),
+        }
+    }
+
+    #[test]
+    fn legal_moves_have_no_reason() {
+        let mut game = ChessGame::new();
+        let pawn = ChessPiece::new(Pawn, Color::White);
+        assert_eq!(
+            None,
+            game.get_illegal_move_reason(&piece_move((4, 1), (4, 3), pawn))
+        );
+    }
+
+    #[test]
+    fn moves_of_the_wrong_piece_are_explained() {
+        let mut game = ChessGame::new();
+        let white_knight = ChessPiece::new(Knight, Color::White);
+
+        assert_eq!(
+            Some(IllegalMoveReason::NoPiece { square: (4, 3) }),
+            game.get_illegal_move_reason(&piece_move((4, 3), (4, 4), white_knight))
+        );
+        assert_eq!(
+            Some(IllegalMoveReason::NotYourTurn {
+                square: (6, 7),
+                piece: ChessPiece::new(Knight, Color::Black),
+            }),
+            game.get_illegal_move_reason(&piece_move(
+                (6, 7),
+                (5, 5),
+                ChessPiece::new(Knight, Color::Black)
+            ))
+        );
+        assert_eq!(
+            Some(IllegalMoveReason::DestinationOccupied {
+                square: (4, 1),
+                piece: ChessPiece::new(Pawn, Color::White),
+            }),
+            game.get_illegal_move_reason(&piece_move((6, 0), (4, 1), white_knight))
+        );
+        assert_eq!(
+            Some(IllegalMoveReason::CannotMoveThere {
+                from: (6, 0),
+                to: (6, 2),
+                piece: white_knight,
+            }),
+            game.get_illegal_move_reason(&piece_move((6, 0), (6, 2), white_knight))
+        );
+    }
+
+    #[test]
+    fn moves_that_leave_the_king_in_check_name_the_attacker() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1").unwrap();
+        let king = ChessPiece::new(King, Color::White);
+        let reason = game
+            .get_illegal_move_reason(&piece_move((4, 0), (5, 1), king))
+            .unwrap();
+        assert_eq!(
+            IllegalMoveReason::KingInCheck {
+                king_square: (5, 1),
+                attacker_square: (3, 1),
+                attacker: ChessPiece::new(Queen, Color::Black),
+            },
+            reason
+        );
+        assert_eq!(
+            "the king on f2 would be in check from the queen on d2",
+            reason.to_string()
+        );
+    }
+
+    #[test]
+    fn castling_problems_are_explained() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/8/4K1NR w K - 0 1").unwrap();
+        assert_eq!(
+            Some(IllegalMoveReason::CastlingPathBlocked { square: (6, 0) }),
+            game.get_illegal_move_reason(&short_castle())
+        );
+
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/8/4K2R w - - 0 1").unwrap();
+        assert_eq!(
+            Some(IllegalMoveReason::NoCastlingRights),
+            game.get_illegal_move_reason(&short_castle())
+        );
+
+        let mut game = build_game_from_string("4kr2/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        let reason = game.get_illegal_move_reason(&short_castle()).unwrap();
+        assert_eq!(
+            IllegalMoveReason::CastlingThroughCheck { square: (5, 0) },
+            reason
+        );
+        assert_eq!(
+            "the king can not castle through check, f1 is attacked",
+            reason.to_string()
+        );
+
+        let mut game = build_game_from_string("4r1k1/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(
+            Some(IllegalMoveReason::CastlingOutOfCheck),
+            game.get_illegal_move_reason(&short_castle())
+        );
+        assert_eq!(
+            ChessPiece::new(Rook, Color::White),
+            get_piece_at(game.get_board(), (7, 0)).unwrap()
+        );
+    }
+
+    #[derive(Debug)]
+    struct KingsStayHome;
+
+    impl Variant for KingsStayHome {
+        fn get_name(&self) -> &str {
+            "Kings Stay Home"
+        }
+
+        fn filter_legal_moves(
+            &self,
+            _game: &ChessGame,
+            legal_moves: Vec<ChessMoveType>,
+        ) -> Vec<ChessMoveType> {
+            legal_moves
+                .into_iter()
+                .filter(|chess_move| match chess_move {
+                    ChessMoveType::Move { piece, .. } => piece.get_piece_type() != King,
+                    _ => true,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn variant_restrictions_are_reported() {
+        let position = build_game_from_string("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mut game = ChessGameBuilder::new()
+            .set_board(position.get_board().clone())
+            .set_current_turn(Color::White)
+            .set_variant(KingsStayHome)
+            .build()
+            .unwrap();
+        let king = ChessPiece::new(King, Color::White);
+        assert_eq!(
+            Some(IllegalMoveReason::ForbiddenByVariant),
+            game.get_illegal_move_reason(&piece_move((4, 0), (4, 1), king))
+        );
+    }
+}
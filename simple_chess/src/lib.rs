@@ -1,13 +1,99 @@
+// reports a diagnostic through the log crate, compiled out without the log feature
+#[cfg(feature = "log")]
+macro_rules! chess_log {
+    ($level:ident, $($arg:tt)+) => {
+        if log::Level::$level <= $crate::logging::get_log_level() {
+            log::log!(target: "simple_chess", log::Level::$level, $($arg)+);
+        }
+    };
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! chess_log {
+    ($level:ident, $($arg:tt)+) => {};
+}
+
+// enters a tracing span until the end of the enclosing block
+#[cfg(feature = "tracing")]
+macro_rules! chess_span {
+    ($name:literal $(, $($field:tt)+)?) => {
+        let _span = tracing::trace_span!($name $(, $($field)+)?).entered();
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! chess_span {
+    ($name:literal $(, $($field:tt)+)?) => {};
+}
+
+// adds to one of the profiling counters
+#[cfg(feature = "tracing")]
+macro_rules! chess_count {
+    ($counter:ident, $amount:expr) => {
+        $crate::profiling::increment($crate::profiling::Counter::$counter, $amount as u64)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! chess_count {
+    ($counter:ident, $amount:expr) => {};
+}
+
+pub mod adjudication;
+pub mod analysis;
+pub mod annotations;
+pub mod attack_map;
+pub mod bitboard;
+pub mod book;
+pub mod chess_board;
 mod chess_game;
 pub mod chess_game_builder;
-mod chess_game_move_analyzer;
+pub mod chess_game_move_analyzer;
 pub mod chess_game_state_analyzer;
 mod chess_move;
+pub mod clock;
 pub mod color;
+pub mod endgame;
+pub mod engine;
+pub mod error;
+pub mod game_result;
+pub mod history;
+pub mod legality;
+#[cfg(feature = "log")]
+pub mod logging;
+pub mod match_runner;
+pub mod match_statistics;
+pub mod net;
+pub mod observer;
+pub mod odds;
+pub mod opening_tree;
+pub mod perft;
 pub mod piece;
+pub mod position;
+#[cfg(feature = "tracing")]
+pub mod profiling;
+pub mod puzzle;
+pub mod random_position;
+pub mod rating;
+pub mod render;
+pub mod repetition;
+pub mod ruleset;
+pub mod scenario;
+pub mod scoring;
+pub mod setup;
+pub mod square_name;
+pub mod storage;
+pub mod uci;
+pub mod validation;
+pub mod variant;
+pub mod viewer;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+pub mod zobrist;
 
 pub mod codec;
 pub use chess_game::ChessGame;
 pub use chess_game_builder::ChessGameBuilder;
 pub use chess_move::ChessMoveType;
 pub use color::Color;
+pub use error::ChessError;
@@ -0,0 +1,38 @@
+use log::LevelFilter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The most detailed level simple_chess logs at, on top of the filter of the installed
+/// logger.
+static LOG_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Warn as usize);
+
+/// Sets the most detailed level simple_chess logs at.
+///
+/// Diagnostics are only reported with the `log` feature enabled, through the `log` crate
+/// with the `simple_chess` target, so any logger can show them. The level defaults to
+/// `LevelFilter::Warn`, which keeps the tight loops of move generation and search silent
+/// until more detail is asked for.
+///
+/// # Examples
+///
+/// ```
+/// use log::LevelFilter;
+/// use simple_chess::logging::{get_log_level, set_log_level};
+///
+/// set_log_level(LevelFilter::Debug);
+/// assert_eq!(LevelFilter::Debug, get_log_level());
+/// ```
+pub fn set_log_level(level: LevelFilter) {
+    LOG_LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+/// Returns the most detailed level simple_chess logs at.
+pub fn get_log_level() -> LevelFilter {
+    match LOG_LEVEL.load(Ordering::Relaxed) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
@@ -0,0 +1,430 @@
+use crate::adjudication::{Adjudication, Adjudicator};
+use crate::book::Book;
+use crate::codec::portable_game_notation::encode_game_as_string;
+use crate::engine::search_limits::SearchLimits;
+use crate::engine::Engine;
+use crate::game_result::GameResult;
+use crate::match_statistics::{estimate_elo, EloEstimate, Sprt, SprtDecision};
+use crate::random_position::PositionRng;
+use crate::{ChessGame, ChessMoveType, Color};
+
+/// The number of plies after which a game is adjudicated a draw unless set otherwise.
+const DEFAULT_MAX_PLIES: usize = 400;
+
+/// Plays matches between two engines, for example to compare two versions of an
+/// `Evaluator` while tuning it.
+///
+/// The engines take turns playing White. Games are played in pairs starting from the same
+/// opening with the colors swapped, so neither engine gains from a lucky opening. Each game
+/// ends when it is over by the rules, when a draw could be claimed, when the
+/// `Adjudicator` decides it, or when it reaches the maximum number of plies, which is
+/// adjudicated a draw.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::search_limits::SearchLimits;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::match_runner::MatchRunner;
+///
+/// let mut runner = MatchRunner::new(Engine::new(), Engine::new())
+///     .set_names("New", "Old")
+///     .set_games(2)
+///     .set_search_limits(SearchLimits::new().set_depth(1))
+///     .set_max_plies(20);
+/// let result = runner.run();
+///
+/// assert_eq!(2, result.get_games().len());
+/// assert_eq!(2.0, result.get_score() + result.get_opponent_score());
+/// assert!(result.to_pgn().contains("[White \"Old\"]"));
+/// ```
+pub struct MatchRunner {
+    engines: [Engine; 2],
+    names: [String; 2],
+    games: usize,
+    search_limits: SearchLimits,
+    book: Option<(Book, usize)>,
+    adjudicator: Adjudicator,
+    max_plies: usize,
+    seed: u64,
+}
+
+impl MatchRunner {
+    /// Creates a runner playing two games between the engines, searching each move to a
+    /// depth of 3 plies without an opening book or adjudication.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_engine` - The engine playing White in the first game, whose results the
+    ///   `MatchResult` counts.
+    /// * `second_engine` - The opponent.
+    pub fn new(first_engine: Engine, second_engine: Engine) -> Self {
+        Self {
+            engines: [first_engine, second_engine],
+            names: [String::from("First"), String::from("Second")],
+            games: 2,
+            search_limits: SearchLimits::new().set_depth(3),
+            book: None,
+            adjudicator: Adjudicator::new(),
+            max_plies: DEFAULT_MAX_PLIES,
+            seed: 0,
+        }
+    }
+
+    /// Sets the names of the engines written to the PGN of each game.
+    pub fn set_names(mut self, first_name: &str, second_name: &str) -> Self {
+        self.names = [first_name.to_string(), second_name.to_string()];
+        self
+    }
+
+    /// Sets the number of games to play.
+    pub fn set_games(mut self, games: usize) -> Self {
+        self.games = games;
+        self
+    }
+
+    /// Sets when the engines stop searching each move.
+    pub fn set_search_limits(mut self, search_limits: SearchLimits) -> Self {
+        self.search_limits = search_limits;
+        self
+    }
+
+    /// Sets the opening book the first plies of each game are played from.
+    ///
+    /// Book moves are picked at random, the heavier moves more often, from the runner's
+    /// seed. Once a position has no book moves the engines take over.
+    ///
+    /// # Arguments
+    ///
+    /// * `book` - The book to play from.
+    /// * `plies` - The most plies played from the book.
+    pub fn set_opening_book(mut self, book: Book, plies: usize) -> Self {
+        self.book = Some((book, plies));
+        self
+    }
+
+    /// Sets the rules for ending decided games early. The engines' scores are recorded
+    /// after every move played from a search.
+    pub fn set_adjudicator(mut self, adjudicator: Adjudicator) -> Self {
+        self.adjudicator = adjudicator;
+        self
+    }
+
+    /// Sets the number of plies after which a game is adjudicated a draw, 400 by default.
+    pub fn set_max_plies(mut self, max_plies: usize) -> Self {
+        self.max_plies = max_plies;
+        self
+    }
+
+    /// Sets the seed book moves are picked with, so the openings of a match can be played
+    /// again.
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Plays every game of the match.
+    ///
+    /// # Returns
+    ///
+    /// * `MatchResult` - The games played and the score of the first engine.
+    pub fn run(&mut self) -> MatchResult {
+        let games = (0..self.games).map(|index| self.play_game(index)).collect();
+        MatchResult { games }
+    }
+
+    /// Plays pairs of games until a sequential probability ratio test decides whether the
+    /// first engine is stronger, or until the most games have been played.
+    ///
+    /// The number of games set with `set_games` is ignored, the test is checked after every
+    /// pair of games.
+    ///
+    /// # Arguments
+    ///
+    /// * `sprt` - The test to decide.
+    /// * `max_games` - The most games to play before giving up on a decision.
+    ///
+    /// # Returns
+    ///
+    /// * `(MatchResult, SprtDecision)` - The games played and the decision of the test,
+    ///   `SprtDecision::Continue` if the most games were played without a decision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::engine::search_limits::SearchLimits;
+    /// use simple_chess::engine::Engine;
+    /// use simple_chess::match_runner::MatchRunner;
+    /// use simple_chess::match_statistics::{Sprt, SprtDecision};
+    ///
+    /// let mut runner = MatchRunner::new(Engine::new(), Engine::new())
+    ///     .set_search_limits(SearchLimits::new().set_depth(1))
+    ///     .set_max_plies(10);
+    /// let (result, decision) = runner.run_sprt(&Sprt::new(0.0, 10.0), 4);
+    ///
+    /// assert_eq!(4, result.get_games().len());
+    /// assert_eq!(SprtDecision::Continue, decision);
+    /// ```
+    pub fn run_sprt(&mut self, sprt: &Sprt, max_games: usize) -> (MatchResult, SprtDecision) {
+        let mut result = MatchResult { games: Vec::new() };
+        while result.games.len() < max_games {
+            let index = result.games.len();
+            result.games.push(self.play_game(index));
+            if index.is_multiple_of(2) && index + 1 < max_games {
+                continue;
+            }
+            let decision = sprt.test(result.get_wins(), result.get_draws(), result.get_losses());
+            if decision != SprtDecision::Continue {
+                return (result, decision);
+            }
+        }
+        (result, SprtDecision::Continue)
+    }
+
+    /// Plays one game of the match, the first engine playing White in even games.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the game in the match, counted from `0`.
+    pub fn play_game(&mut self, index: usize) -> MatchGame {
+        let first_engine_color = if index.is_multiple_of(2) {
+            Color::White
+        } else {
+            Color::Black
+        };
+        // both games of a pair open the same way
+        let mut rng = PositionRng::new(self.seed.wrapping_add((index / 2) as u64));
+        let mut adjudicator = self.adjudicator.clone();
+        adjudicator.reset();
+        for engine in &mut self.engines {
+            engine.clear_transposition_table();
+        }
+
+        let mut game = ChessGame::new();
+        let mut ply = 0;
+        while game.get_result().is_none() {
+            if game.can_claim_draw().is_some() || ply >= self.max_plies {
+                let _ = game.adjudicate(Adjudication::Draw);
+                break;
+            }
+
+            if let Some(book_move) = self.pick_book_move(&game, ply, &mut rng) {
+                game.make_move(book_move);
+                ply += 1;
+                continue;
+            }
+
+            let turn = game.get_current_players_turn();
+            let engine = &mut self.engines[usize::from(turn != first_engine_color)];
+            let result = engine.search_with_limits(&game, &self.search_limits);
+            let Some(chess_move) = result.get_best_move().copied() else {
+                break;
+            };
+            game.make_move(chess_move);
+            ply += 1;
+
+            let white_score = match turn {
+                Color::White => result.get_score(),
+                Color::Black => -result.get_score(),
+            };
+            if let Some(adjudication) = adjudicator.record_score(white_score) {
+                let _ = game.adjudicate(adjudication);
+            }
+        }
+
+        let (white_name, black_name) = match first_engine_color {
+            Color::White => (&self.names[0], &self.names[1]),
+            Color::Black => (&self.names[1], &self.names[0]),
+        };
+        MatchGame {
+            result: game
+                .get_result()
+                .expect("games are played until they have a result"),
+            game,
+            first_engine_color,
+            white_name: white_name.clone(),
+            black_name: black_name.clone(),
+            round: index + 1,
+        }
+    }
+
+    fn pick_book_move(
+        &self,
+        game: &ChessGame,
+        ply: usize,
+        rng: &mut PositionRng,
+    ) -> Option<ChessMoveType> {
+        let (book, plies) = self.book.as_ref()?;
+        if ply >= *plies {
+            return None;
+        }
+        let book_moves = book.get_moves(game);
+        let total_weight: usize = book_moves
+            .iter()
+            .map(|book_move| usize::from(book_move.get_weight()))
+            .sum();
+        if total_weight == 0 {
+            return book_moves.first().map(|book_move| *book_move.get_move());
+        }
+
+        let mut pick = rng.next_below(total_weight);
+        for book_move in &book_moves {
+            let weight = usize::from(book_move.get_weight());
+            if pick < weight {
+                return Some(*book_move.get_move());
+            }
+            pick -= weight;
+        }
+        None
+    }
+}
+
+/// A game played by a `MatchRunner`.
+pub struct MatchGame {
+    game: ChessGame,
+    first_engine_color: Color,
+    result: GameResult,
+    white_name: String,
+    black_name: String,
+    round: usize,
+}
+
+impl MatchGame {
+    /// Returns the game as it was played.
+    pub fn get_game(&self) -> &ChessGame {
+        &self.game
+    }
+
+    /// Returns the color the first engine played.
+    pub fn get_first_engine_color(&self) -> Color {
+        self.first_engine_color
+    }
+
+    /// Returns the result of the game.
+    pub fn get_result(&self) -> &GameResult {
+        &self.result
+    }
+
+    /// Returns the points the first engine scored, `1.0` for a win, `0.5` for a draw and
+    /// `0.0` for a loss.
+    pub fn get_score(&self) -> f64 {
+        match self.result.get_winner() {
+            Some(winner) if winner == self.first_engine_color => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        }
+    }
+
+    /// Encodes the game in PGN, with the engines' names and the round of the game.
+    pub fn to_pgn(&self) -> String {
+        let tags = [
+            ("Event", "Engine match"),
+            ("Round", &self.round.to_string()),
+            ("White", &self.white_name),
+            ("Black", &self.black_name),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        encode_game_as_string(&self.game, &tags)
+    }
+}
+
+/// The games of a match and the score of the first engine.
+pub struct MatchResult {
+    games: Vec<MatchGame>,
+}
+
+impl MatchResult {
+    /// Returns the games of the match in the order they were played.
+    pub fn get_games(&self) -> &[MatchGame] {
+        &self.games
+    }
+
+    /// Returns the number of games the first engine won.
+    pub fn get_wins(&self) -> usize {
+        self.count_scores(1.0)
+    }
+
+    /// Returns the number of drawn games.
+    pub fn get_draws(&self) -> usize {
+        self.count_scores(0.5)
+    }
+
+    /// Returns the number of games the first engine lost.
+    pub fn get_losses(&self) -> usize {
+        self.count_scores(0.0)
+    }
+
+    /// Returns the points the first engine scored, a point per win and half a point per
+    /// draw.
+    pub fn get_score(&self) -> f64 {
+        self.games.iter().map(MatchGame::get_score).sum()
+    }
+
+    /// Returns the points the second engine scored.
+    pub fn get_opponent_score(&self) -> f64 {
+        self.games.len() as f64 - self.get_score()
+    }
+
+    /// Estimates how much stronger the first engine is than the second, `None` if no games
+    /// were played, see `estimate_elo`.
+    pub fn estimate_elo(&self) -> Option<EloEstimate> {
+        estimate_elo(self.get_wins(), self.get_draws(), self.get_losses())
+    }
+
+    /// Encodes every game of the match in PGN, separated by empty lines.
+    pub fn to_pgn(&self) -> String {
+        self.games
+            .iter()
+            .map(MatchGame::to_pgn)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn count_scores(&self, score: f64) -> usize {
+        self.games
+            .iter()
+            .filter(|game| game.get_score() == score)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+    use crate::opening_tree::OpeningTree;
+
+    #[test]
+    fn game_pairs_share_their_opening_with_colors_swapped() {
+        let mut tree = OpeningTree::new();
+        let mut game = ChessGame::new();
+        let mut line = Vec::new();
+        for san in ["d4", "d5", "c4"] {
+            let chess_move = decode_move_from_string(&game, san).unwrap();
+            game.make_move(chess_move);
+            line.push(chess_move);
+        }
+        tree.add_line(&line).unwrap();
+
+        let mut runner = MatchRunner::new(Engine::new(), Engine::new())
+            .set_games(2)
+            .set_search_limits(SearchLimits::new().set_depth(1))
+            .set_opening_book(tree.to_book(), 2)
+            .set_max_plies(6);
+        let result = runner.run();
+        let [first, second] = result.get_games() else {
+            panic!("two games are played");
+        };
+
+        assert_eq!(Color::White, first.get_first_engine_color());
+        assert_eq!(Color::Black, second.get_first_engine_color());
+        for match_game in [first, second] {
+            let moves = match_game.get_game().get_moves();
+            assert_eq!(6, moves.len());
+            assert_eq!(line[..2], moves[..2]);
+            assert_eq!(0.5, match_game.get_score());
+        }
+        assert_eq!(2, result.get_draws());
+        assert!(result.to_pgn().contains("[Round \"2\"]"));
+    }
+}
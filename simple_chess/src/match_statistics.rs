@@ -0,0 +1,270 @@
+/// The z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.959_964;
+
+/// The games of each outcome added to the results of an SPRT, so the variance of the score
+/// is never zero.
+const PSEUDO_COUNT: f64 = 0.5;
+
+/// The Elo difference between two players measured by a match, with its error bars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloEstimate {
+    elo: f64,
+    error_margin: f64,
+    likelihood_of_superiority: f64,
+}
+
+impl EloEstimate {
+    /// Returns the estimated Elo difference, positive when the first player is stronger.
+    /// The difference is infinite when one player won every game.
+    pub fn get_elo(&self) -> f64 {
+        self.elo
+    }
+
+    /// Returns the half-width of the 95% confidence interval of the Elo difference.
+    pub fn get_error_margin(&self) -> f64 {
+        self.error_margin
+    }
+
+    /// Returns the likelihood of superiority, the probability that the first player is
+    /// stronger, judged from the decisive games.
+    pub fn get_likelihood_of_superiority(&self) -> f64 {
+        self.likelihood_of_superiority
+    }
+}
+
+/// Estimates the Elo difference between two players from the results of a match.
+///
+/// # Arguments
+///
+/// * `wins` - The games the first player won.
+/// * `draws` - The drawn games.
+/// * `losses` - The games the first player lost.
+///
+/// # Returns
+///
+/// * `Some(EloEstimate)` - The estimated difference.
+/// * `None` - If no games were played.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::match_statistics::estimate_elo;
+///
+/// let estimate = estimate_elo(60, 50, 40).unwrap();
+///
+/// assert_eq!(47, estimate.get_elo().round() as i32);
+/// assert_eq!(46, estimate.get_error_margin().round() as i32);
+/// assert!((estimate.get_likelihood_of_superiority() - 0.977).abs() < 0.001);
+/// ```
+pub fn estimate_elo(wins: usize, draws: usize, losses: usize) -> Option<EloEstimate> {
+    let games = wins + draws + losses;
+    if games == 0 {
+        return None;
+    }
+    let (score, variance) = get_score_and_variance(wins, draws, losses);
+    let elo = score_to_elo(score);
+    let standard_error = (variance / games as f64).sqrt();
+    let error_margin = if elo.is_finite() {
+        (score_to_elo((score + Z_95 * standard_error).min(1.0))
+            - score_to_elo((score - Z_95 * standard_error).max(0.0)))
+            / 2.0
+    } else {
+        f64::INFINITY
+    };
+
+    let decisive_games = (wins + losses) as f64;
+    let likelihood_of_superiority = if decisive_games == 0.0 {
+        0.5
+    } else {
+        0.5 * (1.0 + erf((wins as f64 - losses as f64) / (2.0 * decisive_games).sqrt()))
+    };
+
+    Some(EloEstimate {
+        elo,
+        error_margin,
+        likelihood_of_superiority,
+    })
+}
+
+/// The decision of a sequential probability ratio test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// The change is no better than the lower Elo bound, reject it.
+    AcceptNull,
+    /// The change is at least as good as the upper Elo bound, accept it.
+    AcceptAlternative,
+    /// More games are needed to decide.
+    Continue,
+}
+
+/// A sequential probability ratio test (SPRT), deciding after each game whether a change to
+/// an engine is an improvement.
+///
+/// The test weighs the hypothesis that the first player is `elo0` Elo stronger than the
+/// second, the null hypothesis, against the hypothesis that it is `elo1` Elo stronger. The
+/// log-likelihood ratio of the results so far is compared against bounds set by the
+/// chances of accepting a hypothesis wrongly, `alpha` for the alternative and `beta` for
+/// the null hypothesis, which are 5% by default. The ratio uses the normal approximation
+/// of the score, with half a game of each outcome added to the results so matches where
+/// every game ends the same way, such as a run of wins, still reach a decision.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::match_statistics::{Sprt, SprtDecision};
+///
+/// let sprt = Sprt::new(0.0, 10.0);
+///
+/// assert_eq!(SprtDecision::Continue, sprt.test(60, 50, 40));
+/// assert_eq!(SprtDecision::AcceptAlternative, sprt.test(600, 500, 400));
+/// assert_eq!(SprtDecision::AcceptNull, sprt.test(400, 500, 600));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprt {
+    elo0: f64,
+    elo1: f64,
+    alpha: f64,
+    beta: f64,
+}
+
+impl Sprt {
+    /// Creates a test with 5% chances of accepting either hypothesis wrongly.
+    ///
+    /// # Arguments
+    ///
+    /// * `elo0` - The Elo difference of the null hypothesis.
+    /// * `elo1` - The Elo difference of the alternative hypothesis, greater than `elo0`.
+    pub fn new(elo0: f64, elo1: f64) -> Self {
+        Self {
+            elo0,
+            elo1,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+
+    /// Sets the chances of accepting the alternative hypothesis when the null hypothesis
+    /// holds, `alpha`, and of accepting the null hypothesis when the alternative holds,
+    /// `beta`.
+    pub fn set_error_rates(mut self, alpha: f64, beta: f64) -> Self {
+        self.alpha = alpha;
+        self.beta = beta;
+        self
+    }
+
+    /// Returns the Elo differences of the null and alternative hypotheses.
+    pub fn get_elo_bounds(&self) -> (f64, f64) {
+        (self.elo0, self.elo1)
+    }
+
+    /// Returns the log-likelihood ratios below which the null hypothesis is accepted and
+    /// above which the alternative hypothesis is accepted.
+    pub fn get_bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Returns the log-likelihood ratio of the alternative hypothesis over the null
+    /// hypothesis given the results of a match.
+    ///
+    /// # Arguments
+    ///
+    /// * `wins` - The games the first player won.
+    /// * `draws` - The drawn games.
+    /// * `losses` - The games the first player lost.
+    pub fn get_log_likelihood_ratio(&self, wins: usize, draws: usize, losses: usize) -> f64 {
+        if wins + draws + losses == 0 {
+            return 0.0;
+        }
+        let (wins, draws, losses) = (
+            wins as f64 + PSEUDO_COUNT,
+            draws as f64 + PSEUDO_COUNT,
+            losses as f64 + PSEUDO_COUNT,
+        );
+        let games = wins + draws + losses;
+        let score = (wins + draws / 2.0) / games;
+        let variance =
+            (wins * (1.0 - score).powi(2) + draws * (0.5 - score).powi(2) + losses * score.powi(2))
+                / games;
+        let score0 = elo_to_score(self.elo0);
+        let score1 = elo_to_score(self.elo1);
+        (score1 - score0) * (2.0 * score - score0 - score1) * games / (2.0 * variance)
+    }
+
+    /// Decides the test given the results of a match.
+    pub fn test(&self, wins: usize, draws: usize, losses: usize) -> SprtDecision {
+        let ratio = self.get_log_likelihood_ratio(wins, draws, losses);
+        let (lower, upper) = self.get_bounds();
+        if ratio <= lower {
+            SprtDecision::AcceptNull
+        } else if ratio >= upper {
+            SprtDecision::AcceptAlternative
+        } else {
+            SprtDecision::Continue
+        }
+    }
+}
+
+/// Converts an expected score between `0` and `1` to an Elo difference.
+pub fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Converts an Elo difference to the expected score of the player it favors.
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Returns the mean score per game and its variance, `(0.5, 0.0)` without games.
+fn get_score_and_variance(wins: usize, draws: usize, losses: usize) -> (f64, f64) {
+    let games = (wins + draws + losses) as f64;
+    if games == 0.0 {
+        return (0.5, 0.0);
+    }
+    let (wins, draws, losses) = (wins as f64, draws as f64, losses as f64);
+    let score = (wins + draws / 2.0) / games;
+    let variance =
+        (wins * (1.0 - score).powi(2) + draws * (0.5 - score).powi(2) + losses * score.powi(2))
+            / games;
+    (score, variance)
+}
+
+/// The error function, approximated to within `1.5e-7` (Abramowitz and Stegun 7.1.26).
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let polynomial = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    (1.0 - polynomial * (-x * x).exp()).copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_sided_matches_have_an_infinite_elo_difference() {
+        let estimate = estimate_elo(10, 0, 0).unwrap();
+        assert_eq!(f64::INFINITY, estimate.get_elo());
+        assert_eq!(f64::INFINITY, estimate.get_error_margin());
+        assert!(estimate.get_likelihood_of_superiority() > 0.999);
+
+        let even = estimate_elo(10, 5, 10).unwrap();
+        assert_eq!(0.0, even.get_elo());
+        assert!((even.get_likelihood_of_superiority() - 0.5).abs() < 1e-6);
+        assert!(estimate_elo(0, 0, 0).is_none());
+        assert_eq!(100, score_to_elo(elo_to_score(100.0)).round() as i32);
+    }
+
+    #[test]
+    fn matches_where_every_game_ends_the_same_way_are_decided() {
+        let sprt = Sprt::new(0.0, 5.0);
+        assert_eq!(SprtDecision::AcceptAlternative, sprt.test(200, 0, 0));
+        assert_eq!(SprtDecision::AcceptNull, sprt.test(0, 1000, 0));
+        assert_eq!(SprtDecision::AcceptNull, sprt.test(0, 0, 200));
+        assert_eq!(0.0, sprt.get_log_likelihood_ratio(0, 0, 0));
+    }
+}
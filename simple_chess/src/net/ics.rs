@@ -0,0 +1,598 @@
+use crate::clock::TimeControl;
+use crate::codec::forsyth_edwards_notation::build_game_from_string;
+use crate::codec::long_algebraic_notation::encode_move_as_string;
+use crate::error::ChessError;
+use crate::net::{invalid_data, ProtocolError};
+use crate::{ChessGame, ChessMoveType, Color};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// The prompt the server shows when it is ready for a command.
+const COMMAND_PROMPT: &str = "fics% ";
+
+/// What the logged in player has to do with a game, the relation field of a style 12 line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    /// The player is playing the game and it is their move.
+    MyMove,
+    /// The player is playing the game and it is their opponent's move.
+    OpponentsMove,
+    /// The player is observing a game being played.
+    Observing,
+    /// The player is examining the game.
+    Examining,
+    /// The player is observing a game being examined.
+    ObservingExamined,
+    /// The position was sent on its own, for example by the `refresh` command.
+    Isolated,
+}
+
+impl Relation {
+    fn from_code(code: &str) -> Result<Self, ProtocolError> {
+        match code {
+            "1" => Ok(Relation::MyMove),
+            "-1" => Ok(Relation::OpponentsMove),
+            "0" => Ok(Relation::Observing),
+            "2" => Ok(Relation::Examining),
+            "-2" => Ok(Relation::ObservingExamined),
+            "-3" => Ok(Relation::Isolated),
+            _ => Err(ProtocolError::new(format!("'{code}' is not a relation"))),
+        }
+    }
+}
+
+/// A board update in the style 12 format of ICS servers such as FICS, a line starting with
+/// `<12>` sent after every move of a game the player takes part in.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::net::ics::{Relation, Style12};
+/// use simple_chess::Color;
+///
+/// let line = "<12> rnbqkbnr pppppppp -------- -------- ----P--- -------- PPPP-PPP RNBQKBNR \
+///     B 4 1 1 1 1 0 7 Newton Einstein 1 2 12 39 39 119 122 1 P/e2-e4 (0:06) e4 0";
+/// let style12 = Style12::parse(line).unwrap();
+///
+/// assert_eq!(7, style12.get_game_number());
+/// assert_eq!(Relation::MyMove, style12.get_relation());
+/// assert_eq!(Some(Color::Black), style12.get_my_color());
+/// assert_eq!(Some("e4"), style12.get_previous_move_san());
+///
+/// let game = style12.to_game().unwrap();
+/// assert_eq!(Color::Black, game.get_current_players_turn());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style12 {
+    ranks: Vec<String>,
+    current_turn: Color,
+    double_push_file: Option<usize>,
+    castling_rights: [bool; 4],
+    halfmove_clock: usize,
+    game_number: u32,
+    white_name: String,
+    black_name: String,
+    relation: Relation,
+    time_control: TimeControl,
+    white_remaining_time: Duration,
+    black_remaining_time: Duration,
+    move_number: usize,
+    previous_move: Option<String>,
+    previous_move_san: Option<String>,
+}
+
+impl Style12 {
+    /// Parses a style 12 line.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line, starting with `<12>`. Anything before the `<12>`, such as a
+    ///   command prompt, and any fields after the ones described by the format are ignored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Style12)` - The board update.
+    /// * `Err(ProtocolError)` - If the line is not a style 12 line.
+    pub fn parse(line: &str) -> Result<Self, ProtocolError> {
+        let invalid = |reason: &str| ProtocolError::new(format!("invalid style 12 line, {reason}"));
+        let start = line.find("<12>").ok_or_else(|| invalid("no <12> tag"))?;
+        let fields: Vec<&str> = line[start + 4..].split_whitespace().collect();
+        if fields.len() < 29 {
+            return Err(invalid("too few fields"));
+        }
+
+        let ranks: Vec<String> = fields[..8].iter().map(|rank| rank.to_string()).collect();
+        if ranks.iter().any(|rank| rank.chars().count() != 8) {
+            return Err(invalid("a rank is not eight squares long"));
+        }
+        let current_turn = match fields[8] {
+            "W" => Color::White,
+            "B" => Color::Black,
+            other => return Err(invalid(&format!("'{other}' is not a side to move"))),
+        };
+        let number = |index: usize| -> Result<i64, ProtocolError> {
+            fields[index]
+                .parse()
+                .map_err(|_| invalid(&format!("'{}' is not a number", fields[index])))
+        };
+        let double_push_file = match number(9)? {
+            file @ 0..=7 => Some(file as usize),
+            _ => None,
+        };
+        let mut castling_rights = [false; 4];
+        for (index, right) in castling_rights.iter_mut().enumerate() {
+            *right = number(10 + index)? == 1;
+        }
+        let seconds = |index: usize| number(index).map(|seconds| seconds.max(0) as u64);
+        let optional = |field: &str| (field != "none").then(|| field.to_string());
+
+        Ok(Self {
+            ranks,
+            current_turn,
+            double_push_file,
+            castling_rights,
+            halfmove_clock: number(14)?.max(0) as usize,
+            game_number: number(15)? as u32,
+            white_name: fields[16].to_string(),
+            black_name: fields[17].to_string(),
+            relation: Relation::from_code(fields[18])?,
+            time_control: TimeControl::new(
+                Duration::from_secs(seconds(19)? * 60),
+                Duration::from_secs(seconds(20)?),
+            ),
+            white_remaining_time: Duration::from_secs(seconds(23)?),
+            black_remaining_time: Duration::from_secs(seconds(24)?),
+            move_number: number(25)?.max(1) as usize,
+            previous_move: optional(fields[26]),
+            previous_move_san: optional(fields[28]),
+        })
+    }
+
+    /// Returns the number of the game on the server.
+    pub fn get_game_number(&self) -> u32 {
+        self.game_number
+    }
+
+    /// Returns the handle of the player playing White.
+    pub fn get_white_name(&self) -> &str {
+        &self.white_name
+    }
+
+    /// Returns the handle of the player playing Black.
+    pub fn get_black_name(&self) -> &str {
+        &self.black_name
+    }
+
+    /// Returns what the logged in player has to do with the game.
+    pub fn get_relation(&self) -> Relation {
+        self.relation
+    }
+
+    /// Returns the color the logged in player plays, `None` if they are not playing the
+    /// game.
+    pub fn get_my_color(&self) -> Option<Color> {
+        match self.relation {
+            Relation::MyMove => Some(self.current_turn),
+            Relation::OpponentsMove => Some(self.current_turn.opposite()),
+            _ => None,
+        }
+    }
+
+    /// Returns the color whose turn it is.
+    pub fn get_current_turn(&self) -> Color {
+        self.current_turn
+    }
+
+    /// Returns the time control of the game.
+    pub fn get_time_control(&self) -> &TimeControl {
+        &self.time_control
+    }
+
+    /// Returns the time a player has left on their clock.
+    pub fn get_remaining_time(&self, color: Color) -> Duration {
+        match color {
+            Color::White => self.white_remaining_time,
+            Color::Black => self.black_remaining_time,
+        }
+    }
+
+    /// Returns the number of the move about to be made.
+    pub fn get_move_number(&self) -> usize {
+        self.move_number
+    }
+
+    /// Returns the previous move in the server's verbose notation, such as `P/e2-e4`, if a
+    /// move has been made.
+    pub fn get_previous_move(&self) -> Option<&str> {
+        self.previous_move.as_deref()
+    }
+
+    /// Returns the previous move in Standard Algebraic Notation, if a move has been made.
+    pub fn get_previous_move_san(&self) -> Option<&str> {
+        self.previous_move_san.as_deref()
+    }
+
+    /// Returns the position in Forsyth-Edwards Notation.
+    pub fn to_fen(&self) -> String {
+        let placement: Vec<String> = self
+            .ranks
+            .iter()
+            .map(|rank| {
+                let mut fen_rank = String::new();
+                let mut empty_squares = 0;
+                for square in rank.chars() {
+                    if square == '-' {
+                        empty_squares += 1;
+                        continue;
+                    }
+                    if empty_squares > 0 {
+                        fen_rank.push_str(&empty_squares.to_string());
+                        empty_squares = 0;
+                    }
+                    fen_rank.push(square);
+                }
+                if empty_squares > 0 {
+                    fen_rank.push_str(&empty_squares.to_string());
+                }
+                fen_rank
+            })
+            .collect();
+
+        let castling: String = ['K', 'Q', 'k', 'q']
+            .into_iter()
+            .zip(self.castling_rights)
+            .filter_map(|(symbol, right)| right.then_some(symbol))
+            .collect();
+        // the pawn that was pushed two squares belongs to the player who just moved
+        let en_passant = match (self.double_push_file, self.current_turn) {
+            (Some(file), Color::White) => format!("{}6", (b'a' + file as u8) as char),
+            (Some(file), Color::Black) => format!("{}3", (b'a' + file as u8) as char),
+            (None, _) => String::from("-"),
+        };
+
+        format!(
+            "{} {} {} {} {} {}",
+            placement.join("/"),
+            match self.current_turn {
+                Color::White => "w",
+                Color::Black => "b",
+            },
+            if castling.is_empty() { "-" } else { &castling },
+            en_passant,
+            self.halfmove_clock,
+            self.move_number
+        )
+    }
+
+    /// Builds a game holding the position.
+    ///
+    /// The server only sends the position, so the game has no history of earlier moves,
+    /// and repetitions from before the update are not known to it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChessGame)` - The game in the position.
+    /// * `Err(ChessError)` - If the position is not a valid position.
+    pub fn to_game(&self) -> Result<ChessGame, ChessError> {
+        build_game_from_string(&self.to_fen())
+    }
+}
+
+/// Something the server sent, see `IcsClient::next_event`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IcsEvent {
+    /// The board of a game changed.
+    Board(Style12),
+    /// A game ended, such as `{Game 7 (Newton vs. Einstein) Einstein resigns} 1-0`.
+    GameEnded {
+        game_number: u32,
+        reason: String,
+        result: String,
+    },
+    /// Any other line of text.
+    Text(String),
+}
+
+impl IcsEvent {
+    /// Parses a line sent by the server. Lines that can not be parsed as a board or the end
+    /// of a game are text.
+    pub fn parse(line: &str) -> Self {
+        let line = line.trim().trim_start_matches(COMMAND_PROMPT.trim()).trim();
+        if line.starts_with("<12>") {
+            if let Ok(style12) = Style12::parse(line) {
+                return IcsEvent::Board(style12);
+            }
+        }
+        parse_game_end(line).unwrap_or_else(|| IcsEvent::Text(line.to_string()))
+    }
+}
+
+/// Parses a line such as `{Game 7 (Newton vs. Einstein) Einstein resigns} 1-0`.
+fn parse_game_end(line: &str) -> Option<IcsEvent> {
+    let rest = line.strip_prefix("{Game ")?;
+    let (game_number, rest) = rest.split_once(' ')?;
+    let (_, rest) = rest.split_once(") ")?;
+    let (reason, result) = rest.split_once('}')?;
+    let result = result.trim();
+    if !["1-0", "0-1", "1/2-1/2", "*"].contains(&result) {
+        return None;
+    }
+    Some(IcsEvent::GameEnded {
+        game_number: game_number.parse().ok()?,
+        reason: reason.to_string(),
+        result: result.to_string(),
+    })
+}
+
+/// Encodes a move the way ICS servers accept it, castling as `o-o` or `o-o-o` and
+/// promotions as `e7e8=q`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::long_algebraic_notation::decode_move_from_string;
+/// use simple_chess::net::ics::encode_ics_move;
+/// use simple_chess::ChessGame;
+///
+/// let knight_move = decode_move_from_string(&ChessGame::new(), "g1f3").unwrap();
+/// assert_eq!("g1f3", encode_ics_move(&knight_move));
+/// ```
+pub fn encode_ics_move(chess_move: &ChessMoveType) -> String {
+    match chess_move {
+        ChessMoveType::Castle {
+            rook_original_position,
+            king_original_position,
+            ..
+        } => {
+            if rook_original_position.0 > king_original_position.0 {
+                String::from("o-o")
+            } else {
+                String::from("o-o-o")
+            }
+        }
+        _ => {
+            let mut lan = encode_move_as_string(chess_move);
+            if lan.len() > 4 {
+                lan.insert(4, '=');
+            }
+            lan
+        }
+    }
+}
+
+/// A client of an ICS-style chess server such as FICS, for writing bots that play on it
+/// with this crate's rules.
+///
+/// The client speaks the plain text protocol without timeseal, so servers that require
+/// timeseal will not take the time lost to network lag into account. After logging in the
+/// server is asked for style 12 board updates, which `next_event` turns into
+/// `IcsEvent::Board`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use simple_chess::net::ics::{IcsClient, IcsEvent, Relation};
+///
+/// let mut client = IcsClient::connect("freechess.org:5000").unwrap();
+/// client.login("guest", None).unwrap();
+/// client.send_command("seek 5 0").unwrap();
+///
+/// loop {
+///     if let IcsEvent::Board(board) = client.next_event().unwrap() {
+///         if board.get_relation() == Relation::MyMove {
+///             let mut game = board.to_game().unwrap();
+///             let chess_move = game.legal_moves()[0];
+///             client.send_move(&chess_move).unwrap();
+///         }
+///     }
+/// }
+/// ```
+pub struct IcsClient<S> {
+    stream: S,
+    buffer: Vec<u8>,
+    handle: Option<String>,
+}
+
+impl IcsClient<TcpStream> {
+    /// Connects to a server over TCP.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the server, such as `freechess.org:5000`.
+    pub fn connect<A: ToSocketAddrs>(address: A) -> std::io::Result<Self> {
+        Ok(Self::new(TcpStream::connect(address)?))
+    }
+}
+
+impl<S: Read + Write> IcsClient<S> {
+    /// Creates a client speaking to a server over a stream that is already connected.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buffer: Vec::new(),
+            handle: None,
+        }
+    }
+
+    /// Returns the handle the server gave the player, once logged in.
+    pub fn get_handle(&self) -> Option<&str> {
+        self.handle.as_deref()
+    }
+
+    /// Logs in and asks the server for style 12 board updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The player's handle, or `guest` to play as a guest.
+    /// * `password` - The player's password, `None` for guests.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The handle the server gave the player, for guests a name such as
+    ///   `GuestABCD`.
+    /// * `Err(std::io::Error)` - If the connection failed or the server refused the login.
+    pub fn login(&mut self, username: &str, password: Option<&str>) -> std::io::Result<String> {
+        self.read_until(&["login:"])?;
+        self.send_command(username)?;
+
+        let text = self.read_until(&["password:", "Press return", "Starting FICS session"])?;
+        if text.contains("password:") {
+            let password = password.ok_or_else(|| {
+                invalid_data(ProtocolError::new(format!(
+                    "the server asked for the password of '{username}'"
+                )))
+            })?;
+            self.send_command(password)?;
+        } else if text.contains("Press return") {
+            self.send_command("")?;
+        }
+
+        let text = self.read_until(&[COMMAND_PROMPT, "login:"])?;
+        let handle = text
+            .split("Starting FICS session as ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .map(|handle| handle.trim_end_matches("(U)").to_string())
+            .ok_or_else(|| {
+                invalid_data(ProtocolError::new(format!(
+                    "the server refused the login of '{username}'"
+                )))
+            })?;
+        self.handle = Some(handle.clone());
+
+        self.send_command("set style 12")?;
+        self.send_command("set interface simple_chess")?;
+        Ok(handle)
+    }
+
+    /// Sends a command, such as `seek 5 0` or `resign`, followed by a newline.
+    pub fn send_command(&mut self, command: &str) -> std::io::Result<()> {
+        self.stream.write_all(command.as_bytes())?;
+        self.stream.write_all(b"\n")?;
+        self.stream.flush()
+    }
+
+    /// Sends a move in the notation of `encode_ics_move`.
+    pub fn send_move(&mut self, chess_move: &ChessMoveType) -> std::io::Result<()> {
+        self.send_command(&encode_ics_move(chess_move))
+    }
+
+    /// Waits for the next line from the server.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IcsEvent)` - The event of the line.
+    /// * `Err(std::io::Error)` - If the connection failed or was closed.
+    pub fn next_event(&mut self) -> std::io::Result<IcsEvent> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if line
+                    .trim()
+                    .trim_start_matches(COMMAND_PROMPT.trim())
+                    .trim()
+                    .is_empty()
+                {
+                    continue;
+                }
+                return Ok(IcsEvent::parse(&line));
+            }
+            self.fill_buffer()?;
+        }
+    }
+
+    /// Reads until one of the patterns has been received, returning the text read. Prompts
+    /// do not end with a newline, so this does not wait for whole lines.
+    fn read_until(&mut self, patterns: &[&str]) -> std::io::Result<String> {
+        loop {
+            let text = String::from_utf8_lossy(&self.buffer);
+            if let Some(end) = patterns
+                .iter()
+                .filter_map(|pattern| text.find(pattern).map(|start| start + pattern.len()))
+                .min()
+            {
+                let text = text[..end].to_string();
+                self.buffer.drain(..end);
+                return Ok(text);
+            }
+            self.fill_buffer()?;
+        }
+    }
+
+    fn fill_buffer(&mut self) -> std::io::Result<()> {
+        let mut chunk = [0; 4096];
+        let read = self.stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        self.buffer.extend_from_slice(&chunk[..read]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// A server whose output is written in advance.
+    struct FakeServer {
+        output: Cursor<Vec<u8>>,
+        input: Vec<u8>,
+    }
+
+    impl Read for FakeServer {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.output.read(buf)
+        }
+    }
+
+    impl Write for FakeServer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.input.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn guests_log_in_and_receive_boards() {
+        let output = "Welcome to FICS\n\rlogin: \
+            \"guest\" is not a registered name. Press return to enter the server as \"GuestWXYZ\":\n\r\
+            **** Starting FICS session as GuestWXYZ(U) ****\n\rfics% \n\r\
+            fics% <12> -------k -------- -----K-- -------- -------- -------- p------- -------- \
+            B -1 0 0 0 0 3 12 Einstein GuestWXYZ -1 1 0 1 0 58 59 41 K/e5-f6 (0:01) Kf6 0 1 0\n\r\
+            {Game 12 (Einstein vs. GuestWXYZ) GuestWXYZ checkmated} 1-0\n\r";
+        let mut client = IcsClient::new(FakeServer {
+            output: Cursor::new(output.as_bytes().to_vec()),
+            input: Vec::new(),
+        });
+
+        assert_eq!("GuestWXYZ", client.login("guest", None).unwrap());
+        let IcsEvent::Board(board) = client.next_event().unwrap() else {
+            panic!("a board is sent");
+        };
+        assert_eq!(Some(Color::White), board.get_my_color());
+        assert_eq!("7k/8/5K2/8/8/8/p7/8 b - - 3 41", board.to_fen());
+        assert_eq!(
+            Duration::from_secs(58),
+            board.get_remaining_time(Color::White)
+        );
+        assert_eq!(
+            IcsEvent::GameEnded {
+                game_number: 12,
+                reason: String::from("GuestWXYZ checkmated"),
+                result: String::from("1-0"),
+            },
+            client.next_event().unwrap()
+        );
+        assert!(client.next_event().is_err());
+
+        let sent = String::from_utf8(client.stream.input.clone()).unwrap();
+        assert_eq!("guest\n\nset style 12\nset interface simple_chess\n", sent);
+    }
+}
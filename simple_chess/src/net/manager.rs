@@ -0,0 +1,452 @@
+use crate::error::ChessError;
+use crate::game_result::GameResult;
+use crate::net::{ClientMessage, GameSession, Recipient, ServerMessage};
+use crate::{ChessGame, Color};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+/// Something that happened to one of the games of a `GameManager`, sent to every
+/// subscriber.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    /// A game was created.
+    Created {
+        game_id: String,
+        white: String,
+        black: String,
+    },
+    /// A message is to be sent to a player of a game.
+    Message {
+        game_id: String,
+        player_id: String,
+        message: ServerMessage,
+    },
+    /// A game ended, with its result.
+    Ended {
+        game_id: String,
+        result: Option<GameResult>,
+    },
+    /// A game was removed from the manager.
+    Removed { game_id: String },
+}
+
+/// A game of a `GameManager` with the players of each color.
+struct ManagedGame {
+    session: GameSession,
+    white: String,
+    black: String,
+}
+
+impl ManagedGame {
+    fn get_color(&self, player_id: &str) -> Option<Color> {
+        if self.white == player_id {
+            Some(Color::White)
+        } else if self.black == player_id {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+
+    fn get_player(&self, color: Color) -> &str {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+}
+
+/// Plays many games at once for a server, each between two players known by their ids.
+///
+/// Every game has its own lock, so messages for different games are handled in parallel
+/// while the messages of one game are handled one at a time. Each message is checked to
+/// come from a player of the game and is played for that player's color by a
+/// `GameSession`. The manager does no networking itself, it sends a `GameEvent` to every
+/// subscriber for each message to deliver and each game that is created, ends or is
+/// removed.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::net::manager::{GameEvent, GameManager};
+/// use simple_chess::net::{ClientMessage, ServerMessage};
+/// use simple_chess::ChessGame;
+///
+/// let manager = GameManager::new();
+/// let events = manager.subscribe();
+/// manager.create_game("game-1", ChessGame::new(), "alice", "bob").unwrap();
+///
+/// manager
+///     .submit("game-1", "alice", ClientMessage::Move(String::from("e2e4")))
+///     .unwrap();
+///
+/// assert!(matches!(events.recv().unwrap(), GameEvent::Created { .. }));
+/// let GameEvent::Message { player_id, message, .. } = events.recv().unwrap() else {
+///     panic!("the move is sent to both players");
+/// };
+/// assert_eq!("alice", player_id);
+/// assert!(matches!(message, ServerMessage::State(_)));
+/// ```
+#[derive(Default)]
+pub struct GameManager {
+    games: RwLock<HashMap<String, Arc<Mutex<ManagedGame>>>>,
+    subscribers: Mutex<Vec<Sender<GameEvent>>>,
+}
+
+impl GameManager {
+    /// Creates a manager without games.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a receiver of the events of every game from now on.
+    ///
+    /// Subscribers that drop their receiver are forgotten at the next event.
+    pub fn subscribe(&self) -> Receiver<GameEvent> {
+        let (sender, receiver) = channel();
+        lock(&self.subscribers).push(sender);
+        receiver
+    }
+
+    /// Adds a game between two players.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The id of the game, unique among the games of the manager.
+    /// * `game` - The game to play.
+    /// * `white` - The id of the player playing White.
+    /// * `black` - The id of the player playing Black, a different player from White.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the game was added.
+    /// * `Err(GameManagerError)` - If a game with the id exists, or both colors are given to
+    ///   the same player.
+    pub fn create_game(
+        &self,
+        game_id: &str,
+        game: ChessGame,
+        white: &str,
+        black: &str,
+    ) -> Result<(), GameManagerError> {
+        if white == black {
+            return Err(GameManagerError::SamePlayer(white.to_string()));
+        }
+        {
+            let mut games = self
+                .games
+                .write()
+                .unwrap_or_else(|error| error.into_inner());
+            if games.contains_key(game_id) {
+                return Err(GameManagerError::DuplicateGame(game_id.to_string()));
+            }
+            let managed_game = ManagedGame {
+                session: GameSession::new(game),
+                white: white.to_string(),
+                black: black.to_string(),
+            };
+            games.insert(game_id.to_string(), Arc::new(Mutex::new(managed_game)));
+        }
+
+        self.emit(GameEvent::Created {
+            game_id: game_id.to_string(),
+            white: white.to_string(),
+            black: black.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Handles a message of a player in one of their games.
+    ///
+    /// A message the game does not accept, such as a move out of turn, is not an error: it
+    /// is answered with `ServerMessage::Rejected` to the player like any other reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The id of the game.
+    /// * `player_id` - The id of the player who sent the message.
+    /// * `message` - The message.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(String, ServerMessage)>)` - The replies with the id of the player each is
+    ///   for, also sent to subscribers as `GameEvent::Message`.
+    /// * `Err(GameManagerError)` - If there is no such game or the player does not play in
+    ///   it.
+    pub fn submit(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        message: ClientMessage,
+    ) -> Result<Vec<(String, ServerMessage)>, GameManagerError> {
+        let game = self.get_game(game_id)?;
+        let mut game = lock(&game);
+        let color = game
+            .get_color(player_id)
+            .ok_or_else(|| GameManagerError::NotAPlayer {
+                game_id: game_id.to_string(),
+                player_id: player_id.to_string(),
+            })?;
+
+        let was_finished = game.session.is_finished();
+        let mut replies = Vec::new();
+        for (recipient, reply) in game.session.handle(color, message) {
+            let colors = match recipient {
+                Recipient::Both => vec![Color::White, Color::Black],
+                Recipient::Player(color) => vec![color],
+            };
+            for color in colors {
+                replies.push((game.get_player(color).to_string(), reply.clone()));
+            }
+        }
+
+        // events are sent while the game is locked so subscribers see them in order
+        for (player_id, message) in &replies {
+            self.emit(GameEvent::Message {
+                game_id: game_id.to_string(),
+                player_id: player_id.clone(),
+                message: message.clone(),
+            });
+        }
+        if !was_finished && game.session.is_finished() {
+            self.emit(GameEvent::Ended {
+                game_id: game_id.to_string(),
+                result: game.session.get_game().clone().get_result(),
+            });
+        }
+        Ok(replies)
+    }
+
+    /// Handles a move of a player in long algebraic notation, such as `e2e4`, see `submit`.
+    pub fn submit_move(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        lan: &str,
+    ) -> Result<Vec<(String, ServerMessage)>, GameManagerError> {
+        self.submit(game_id, player_id, ClientMessage::Move(lan.to_string()))
+    }
+
+    /// Reads a game while it is locked.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The id of the game.
+    /// * `read` - Called with the game, its result is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(R)` - What `read` returned.
+    /// * `Err(GameManagerError::UnknownGame)` - If there is no such game.
+    pub fn with_game<R>(
+        &self,
+        game_id: &str,
+        read: impl FnOnce(&ChessGame) -> R,
+    ) -> Result<R, GameManagerError> {
+        let game = self.get_game(game_id)?;
+        let game = lock(&game);
+        Ok(read(game.session.get_game()))
+    }
+
+    /// Returns the color a player plays in a game, `None` if they do not play in it.
+    pub fn get_color(
+        &self,
+        game_id: &str,
+        player_id: &str,
+    ) -> Result<Option<Color>, GameManagerError> {
+        let game = self.get_game(game_id)?;
+        let color = lock(&game).get_color(player_id);
+        Ok(color)
+    }
+
+    /// Returns the ids of every game, sorted.
+    pub fn get_game_ids(&self) -> Vec<String> {
+        let mut game_ids: Vec<String> = self.read_games().keys().cloned().collect();
+        game_ids.sort();
+        game_ids
+    }
+
+    /// Returns the ids of the games a player plays in, sorted.
+    pub fn get_games_of_player(&self, player_id: &str) -> Vec<String> {
+        let games: Vec<(String, Arc<Mutex<ManagedGame>>)> = self
+            .read_games()
+            .iter()
+            .map(|(game_id, game)| (game_id.clone(), game.clone()))
+            .collect();
+        let mut game_ids: Vec<String> = games
+            .into_iter()
+            .filter(|(_, game)| lock(game).get_color(player_id).is_some())
+            .map(|(game_id, _)| game_id)
+            .collect();
+        game_ids.sort();
+        game_ids
+    }
+
+    /// Removes a game, for example once it has ended and been saved.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChessGame)` - The removed game.
+    /// * `Err(GameManagerError::UnknownGame)` - If there is no such game.
+    pub fn remove_game(&self, game_id: &str) -> Result<ChessGame, GameManagerError> {
+        let game = self
+            .games
+            .write()
+            .unwrap_or_else(|error| error.into_inner())
+            .remove(game_id)
+            .ok_or_else(|| GameManagerError::UnknownGame(game_id.to_string()))?;
+        // a message being handled for the game holds its lock until it is done
+        let chess_game = lock(&game).session.get_game().clone();
+        self.emit(GameEvent::Removed {
+            game_id: game_id.to_string(),
+        });
+        Ok(chess_game)
+    }
+
+    fn get_game(&self, game_id: &str) -> Result<Arc<Mutex<ManagedGame>>, GameManagerError> {
+        self.read_games()
+            .get(game_id)
+            .cloned()
+            .ok_or_else(|| GameManagerError::UnknownGame(game_id.to_string()))
+    }
+
+    fn read_games(
+        &self,
+    ) -> std::sync::RwLockReadGuard<'_, HashMap<String, Arc<Mutex<ManagedGame>>>> {
+        self.games.read().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn emit(&self, event: GameEvent) {
+        lock(&self.subscribers).retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+/// Locks a mutex, carrying on if a thread panicked while holding it, as a panicking
+/// message leaves the game as it was before the message.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// Error returned when a `GameManager` can not do what was asked.
+#[derive(Clone, PartialEq, Eq)]
+pub enum GameManagerError {
+    /// There is no game with the id.
+    UnknownGame(String),
+    /// A game with the id already exists.
+    DuplicateGame(String),
+    /// Both colors of a game were given to the player.
+    SamePlayer(String),
+    /// The player does not play in the game.
+    NotAPlayer { game_id: String, player_id: String },
+}
+
+impl Display for GameManagerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameManagerError::UnknownGame(game_id) => {
+                write!(f, "Game Manager Error: there is no game '{game_id}'")
+            }
+            GameManagerError::DuplicateGame(game_id) => {
+                write!(f, "Game Manager Error: the game '{game_id}' already exists")
+            }
+            GameManagerError::SamePlayer(player_id) => write!(
+                f,
+                "Game Manager Error: '{player_id}' can not play both colors"
+            ),
+            GameManagerError::NotAPlayer { game_id, player_id } => write!(
+                f,
+                "Game Manager Error: '{player_id}' does not play in the game '{game_id}'"
+            ),
+        }
+    }
+}
+
+impl Debug for GameManagerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GameManagerError: {self}")
+    }
+}
+
+impl Error for GameManagerError {}
+
+impl From<GameManagerError> for ChessError {
+    fn from(error: GameManagerError) -> Self {
+        ChessError::GameManager(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn games_are_played_in_parallel_by_their_own_players() {
+        let manager = Arc::new(GameManager::new());
+        let events = manager.subscribe();
+        for index in 0..8 {
+            let game_id = format!("game-{index}");
+            let black = format!("black-{index}");
+            manager
+                .create_game(&game_id, ChessGame::new(), "white", &black)
+                .unwrap();
+        }
+        assert_eq!(
+            Err(GameManagerError::DuplicateGame(String::from("game-0"))),
+            manager.create_game("game-0", ChessGame::new(), "a", "b")
+        );
+        assert_eq!(8, manager.get_games_of_player("white").len());
+
+        let threads: Vec<_> = (0..8)
+            .map(|index| {
+                let manager = manager.clone();
+                thread::spawn(move || {
+                    let game_id = format!("game-{index}");
+                    let black = format!("black-{index}");
+                    for (player_id, lan) in [("white", "f2f3"), (&black, "e7e5"), ("white", "g2g4")]
+                    {
+                        manager.submit_move(&game_id, player_id, lan).unwrap();
+                    }
+                    manager.submit_move(&game_id, &black, "d8h4").unwrap()
+                })
+            })
+            .collect();
+        for thread in threads {
+            let replies = thread.join().unwrap();
+            assert_eq!(2, replies.len());
+            assert!(matches!(replies[0].1, ServerMessage::State(_)));
+        }
+
+        let ended = events
+            .try_iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    GameEvent::Ended {
+                        result: Some(_),
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(8, ended);
+        assert_eq!(
+            Err(GameManagerError::NotAPlayer {
+                game_id: String::from("game-0"),
+                player_id: String::from("black-1"),
+            }),
+            manager.submit("game-0", "black-1", ClientMessage::Resign)
+        );
+
+        let rejected = manager.submit_move("game-1", "white", "e2e4").unwrap();
+        assert!(matches!(
+            rejected.as_slice(),
+            [(player_id, ServerMessage::Rejected(_))] if player_id == "white"
+        ));
+        assert_eq!(4, manager.remove_game("game-1").unwrap().get_moves().len());
+        assert_eq!(7, manager.get_game_ids().len());
+    }
+}
@@ -0,0 +1,297 @@
+use crate::error::ChessError;
+use crate::Color;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::io::{Read, Write};
+
+pub mod ics;
+pub mod manager;
+mod session;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+pub use session::{GameSession, Recipient};
+
+/// The largest frame accepted, so a broken peer can not make the reader allocate without
+/// limit.
+pub const MAX_FRAME_LENGTH: usize = 1 << 20;
+
+/// A message sent by a player to the server.
+///
+/// Messages travel as UTF-8 text in length-prefixed frames, see `write_frame`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::net::ClientMessage;
+///
+/// let message = ClientMessage::Move(String::from("e2e4"));
+/// assert_eq!("move e2e4", message.encode());
+/// assert_eq!(Ok(message), ClientMessage::decode("move e2e4"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    /// Proposes a move in long algebraic notation, such as `e7e8q`.
+    Move(String),
+    /// Offers the opponent a draw.
+    OfferDraw,
+    /// Accepts the opponent's draw offer.
+    AcceptDraw,
+    /// Declines the opponent's draw offer.
+    DeclineDraw,
+    /// Resigns the game.
+    Resign,
+}
+
+impl ClientMessage {
+    /// Encodes the message as the text sent in a frame.
+    pub fn encode(&self) -> String {
+        match self {
+            ClientMessage::Move(lan) => format!("move {lan}"),
+            ClientMessage::OfferDraw => String::from("offer_draw"),
+            ClientMessage::AcceptDraw => String::from("accept_draw"),
+            ClientMessage::DeclineDraw => String::from("decline_draw"),
+            ClientMessage::Resign => String::from("resign"),
+        }
+    }
+
+    /// Decodes the text of a frame.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ClientMessage)` - The message.
+    /// * `Err(ProtocolError)` - If the text is not a client message.
+    pub fn decode(text: &str) -> Result<ClientMessage, ProtocolError> {
+        let (command, argument) = split_command(text);
+        match (command, argument) {
+            ("move", Some(lan)) => Ok(ClientMessage::Move(lan.to_string())),
+            ("offer_draw", None) => Ok(ClientMessage::OfferDraw),
+            ("accept_draw", None) => Ok(ClientMessage::AcceptDraw),
+            ("decline_draw", None) => Ok(ClientMessage::DeclineDraw),
+            ("resign", None) => Ok(ClientMessage::Resign),
+            _ => Err(ProtocolError::new(format!(
+                "unknown client message '{text}'"
+            ))),
+        }
+    }
+}
+
+/// A message sent by the server to a player.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::net::ServerMessage;
+/// use simple_chess::Color;
+///
+/// let message = ServerMessage::Welcome(Color::Black);
+/// assert_eq!("welcome black", message.encode());
+/// assert_eq!(Ok(message), ServerMessage::decode("welcome black"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    /// Tells a player which color they play, sent once after connecting.
+    Welcome(Color),
+    /// The state of the game as the JSON document of `codec::json`, sent to both players
+    /// after every change.
+    State(String),
+    /// A message of the player was not accepted, with the reason why.
+    Rejected(String),
+    /// A player offered a draw.
+    DrawOffered(Color),
+    /// A player declined the draw offer.
+    DrawDeclined(Color),
+}
+
+impl ServerMessage {
+    /// Encodes the message as the text sent in a frame.
+    pub fn encode(&self) -> String {
+        match self {
+            ServerMessage::Welcome(color) => format!("welcome {}", encode_color(*color)),
+            ServerMessage::State(json) => format!("state {json}"),
+            ServerMessage::Rejected(reason) => format!("rejected {reason}"),
+            ServerMessage::DrawOffered(color) => format!("draw_offered {}", encode_color(*color)),
+            ServerMessage::DrawDeclined(color) => {
+                format!("draw_declined {}", encode_color(*color))
+            }
+        }
+    }
+
+    /// Decodes the text of a frame.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ServerMessage)` - The message.
+    /// * `Err(ProtocolError)` - If the text is not a server message.
+    pub fn decode(text: &str) -> Result<ServerMessage, ProtocolError> {
+        let unknown = || ProtocolError::new(format!("unknown server message '{text}'"));
+        let (command, argument) = split_command(text);
+        let argument = argument.ok_or_else(unknown)?;
+        match command {
+            "welcome" => decode_color(argument).map(ServerMessage::Welcome),
+            "state" => Ok(ServerMessage::State(argument.to_string())),
+            "rejected" => Ok(ServerMessage::Rejected(argument.to_string())),
+            "draw_offered" => decode_color(argument).map(ServerMessage::DrawOffered),
+            "draw_declined" => decode_color(argument).map(ServerMessage::DrawDeclined),
+            _ => Err(unknown()),
+        }
+    }
+}
+
+/// Writes one frame, the length of the payload as a big-endian `u32` followed by the
+/// payload.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::net::{read_frame, write_frame};
+///
+/// let mut buffer = Vec::new();
+/// write_frame(&mut buffer, b"resign").unwrap();
+/// assert_eq!(b"resign".to_vec(), read_frame(&mut buffer.as_slice()).unwrap());
+/// ```
+pub fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> std::io::Result<()> {
+    writer.write_all(&encode_frame_length(payload.len())?)?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Reads one frame written by `write_frame`.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The payload.
+/// * `Err(std::io::Error)` - If the stream ends or the frame is longer than
+///   `MAX_FRAME_LENGTH`.
+pub fn read_frame<R: Read>(reader: &mut R) -> std::io::Result<Vec<u8>> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length)?;
+    let mut payload = vec![0; decode_frame_length(length)?];
+    reader.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+pub(crate) fn encode_frame_length(length: usize) -> std::io::Result<[u8; 4]> {
+    if length > MAX_FRAME_LENGTH {
+        return Err(invalid_data(ProtocolError::new(format!(
+            "a frame of {length} bytes is too long"
+        ))));
+    }
+    Ok((length as u32).to_be_bytes())
+}
+
+pub(crate) fn decode_frame_length(length: [u8; 4]) -> std::io::Result<usize> {
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_LENGTH {
+        return Err(invalid_data(ProtocolError::new(format!(
+            "a frame of {length} bytes is too long"
+        ))));
+    }
+    Ok(length)
+}
+
+pub(crate) fn invalid_data(error: ProtocolError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+}
+
+fn split_command(text: &str) -> (&str, Option<&str>) {
+    match text.trim().split_once(' ') {
+        Some((command, argument)) => (command, Some(argument.trim())),
+        None => (text.trim(), None),
+    }
+}
+
+fn encode_color(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+fn decode_color(text: &str) -> Result<Color, ProtocolError> {
+    match text {
+        "white" => Ok(Color::White),
+        "black" => Ok(Color::Black),
+        _ => Err(ProtocolError::new(format!("'{text}' is not a color"))),
+    }
+}
+
+/// Error returned when a message does not follow the protocol.
+#[derive(PartialEq, Eq)]
+pub struct ProtocolError {
+    reason: String,
+}
+
+impl ProtocolError {
+    pub(crate) fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for ProtocolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Protocol Error: {}", self.reason)
+    }
+}
+
+impl Debug for ProtocolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ProtocolError: {}", self.reason)
+    }
+}
+
+impl Error for ProtocolError {}
+
+impl From<ProtocolError> for ChessError {
+    fn from(error: ProtocolError) -> Self {
+        ChessError::ParseError(error.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_survive_encoding() {
+        for message in [
+            ClientMessage::Move(String::from("e7e8q")),
+            ClientMessage::OfferDraw,
+            ClientMessage::AcceptDraw,
+            ClientMessage::DeclineDraw,
+            ClientMessage::Resign,
+        ] {
+            assert_eq!(
+                Ok(message.clone()),
+                ClientMessage::decode(&message.encode())
+            );
+        }
+        for message in [
+            ServerMessage::Welcome(Color::White),
+            ServerMessage::State(String::from(r#"{"turn":"white"}"#)),
+            ServerMessage::Rejected(String::from("Illegal move: e2e5")),
+            ServerMessage::DrawOffered(Color::Black),
+            ServerMessage::DrawDeclined(Color::White),
+        ] {
+            assert_eq!(
+                Ok(message.clone()),
+                ServerMessage::decode(&message.encode())
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_messages_are_errors() {
+        assert!(ClientMessage::decode("move").is_err());
+        assert!(ClientMessage::decode("castle").is_err());
+        assert!(ServerMessage::decode("welcome green").is_err());
+    }
+
+    #[test]
+    fn long_frames_are_refused() {
+        let mut frame = ((MAX_FRAME_LENGTH + 1) as u32).to_be_bytes().to_vec();
+        frame.extend([0; 8]);
+        let error = read_frame(&mut frame.as_slice()).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+    }
+}
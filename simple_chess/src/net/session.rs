@@ -0,0 +1,183 @@
+use crate::chess_game_state_analyzer::GameState;
+use crate::codec::json::encode_game_state_as_json;
+use crate::codec::long_algebraic_notation::decode_move_from_string;
+use crate::error::ChessError;
+use crate::net::{ClientMessage, ServerMessage};
+use crate::{ChessGame, Color};
+
+/// Who a server message is sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recipient {
+    /// Both players.
+    Both,
+    /// Only the player of the color.
+    Player(Color),
+}
+
+/// The server side of a game between two remote players.
+///
+/// The session does no networking itself, it validates each message of a player against
+/// the game and returns the messages to send in answer, so it can sit behind any
+/// transport. See `net::tokio` for a TCP server built on it.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::net::{ClientMessage, GameSession, Recipient, ServerMessage};
+/// use simple_chess::{ChessGame, Color};
+///
+/// let mut session = GameSession::new(ChessGame::new());
+///
+/// let replies = session.handle(Color::Black, ClientMessage::Move(String::from("e7e5")));
+/// assert!(matches!(replies[0], (Recipient::Player(Color::Black), ServerMessage::Rejected(_))));
+///
+/// let replies = session.handle(Color::White, ClientMessage::Move(String::from("e2e4")));
+/// assert!(matches!(replies[0], (Recipient::Both, ServerMessage::State(_))));
+/// ```
+pub struct GameSession {
+    game: ChessGame,
+}
+
+impl GameSession {
+    /// Creates a session for a game.
+    pub fn new(game: ChessGame) -> Self {
+        Self { game }
+    }
+
+    /// Returns the game being played.
+    pub fn get_game(&self) -> &ChessGame {
+        &self.game
+    }
+
+    /// Returns the message holding the current state of the game.
+    pub fn get_state_message(&self) -> ServerMessage {
+        ServerMessage::State(encode_game_state_as_json(&self.game, None))
+    }
+
+    /// Returns `true` once the game has ended.
+    pub fn is_finished(&mut self) -> bool {
+        !matches!(
+            self.game.state(),
+            GameState::InProgress { .. } | GameState::Check { .. }
+        )
+    }
+
+    /// Applies a message of a player to the game.
+    ///
+    /// Moves are only accepted from the player to move, and draws can only be accepted or
+    /// declined by the opponent of the player who offered them. A message that is not
+    /// accepted is answered with `ServerMessage::Rejected` to its sender only.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player who sent the message.
+    /// * `message` - The message.
+    ///
+    /// # Returns
+    ///
+    /// The messages to send, with who to send them to.
+    pub fn handle(
+        &mut self,
+        color: Color,
+        message: ClientMessage,
+    ) -> Vec<(Recipient, ServerMessage)> {
+        let result = match message {
+            ClientMessage::Move(lan) => self.make_move(color, &lan),
+            ClientMessage::OfferDraw => self
+                .game
+                .offer_draw(color)
+                .map(|_| ServerMessage::DrawOffered(color)),
+            ClientMessage::AcceptDraw => self.check_draw_offered_to(color).and_then(|_| {
+                self.game.accept_draw()?;
+                Ok(self.get_state_message())
+            }),
+            ClientMessage::DeclineDraw => self.check_draw_offered_to(color).map(|_| {
+                self.game.decline_draw();
+                ServerMessage::DrawDeclined(color)
+            }),
+            ClientMessage::Resign => self.game.resign(color).map(|_| self.get_state_message()),
+        };
+
+        match result {
+            Ok(message) => vec![(Recipient::Both, message)],
+            Err(error) => vec![(
+                Recipient::Player(color),
+                ServerMessage::Rejected(error.to_string()),
+            )],
+        }
+    }
+
+    fn make_move(&mut self, color: Color, lan: &str) -> Result<ServerMessage, ChessError> {
+        if self.game.get_current_players_turn() != color {
+            return Err(ChessError::IllegalMove(format!(
+                "{lan} was sent out of turn"
+            )));
+        }
+        let chess_move = decode_move_from_string(&self.game, lan)?;
+        self.game.try_make_move(chess_move)?;
+        Ok(self.get_state_message())
+    }
+
+    fn check_draw_offered_to(&self, color: Color) -> Result<(), ChessError> {
+        if self.game.get_draw_offer() == Some(color.opposite()) {
+            Ok(())
+        } else {
+            Err(ChessError::IllegalMove(String::from(
+                "no draw has been offered by the opponent",
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_rejected(replies: &[(Recipient, ServerMessage)], color: Color) -> bool {
+        matches!(
+            replies,
+            [(Recipient::Player(player), ServerMessage::Rejected(_))] if *player == color
+        )
+    }
+
+    #[test]
+    fn illegal_moves_are_rejected() {
+        let mut session = GameSession::new(ChessGame::new());
+        let replies = session.handle(Color::White, ClientMessage::Move(String::from("e2e5")));
+
+        assert!(is_rejected(&replies, Color::White));
+        assert!(session.get_game().get_moves().is_empty());
+    }
+
+    #[test]
+    fn draws_need_the_opponent_to_accept() {
+        let mut session = GameSession::new(ChessGame::new());
+        assert_eq!(
+            vec![(Recipient::Both, ServerMessage::DrawOffered(Color::White))],
+            session.handle(Color::White, ClientMessage::OfferDraw)
+        );
+        assert!(is_rejected(
+            &session.handle(Color::White, ClientMessage::AcceptDraw),
+            Color::White
+        ));
+
+        session.handle(Color::Black, ClientMessage::AcceptDraw);
+        assert!(session.is_finished());
+        assert!(session
+            .get_state_message()
+            .encode()
+            .contains(r#""state":"draw_agreed""#));
+    }
+
+    #[test]
+    fn resigning_ends_the_game() {
+        let mut session = GameSession::new(ChessGame::new());
+        session.handle(Color::Black, ClientMessage::Resign);
+
+        assert!(session.is_finished());
+        assert!(is_rejected(
+            &session.handle(Color::White, ClientMessage::Move(String::from("e2e4"))),
+            Color::White
+        ));
+    }
+}
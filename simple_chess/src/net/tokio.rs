@@ -0,0 +1,239 @@
+use crate::net::{
+    decode_frame_length, encode_frame_length, invalid_data, ClientMessage, GameSession,
+    ProtocolError, Recipient, ServerMessage,
+};
+use crate::{ChessGame, Color};
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::mpsc;
+
+/// Writes one frame, like `net::write_frame`.
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, payload: &[u8]) -> io::Result<()> {
+    writer
+        .write_all(&encode_frame_length(payload.len())?)
+        .await?;
+    writer.write_all(payload).await?;
+    writer.flush().await
+}
+
+/// Reads one frame, like `net::read_frame`.
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length).await?;
+    let mut payload = vec![0; decode_frame_length(length)?];
+    reader.read_exact(&mut payload).await?;
+    Ok(payload)
+}
+
+/// Plays one game between the next two players to connect to a listener.
+///
+/// The first player to connect plays white and the second black, each is sent a
+/// `ServerMessage::Welcome` as they connect. Once both are there they are sent the state
+/// of the game, then the messages of both players are handled by a `GameSession` until
+/// the game ends. A player who disconnects before the end resigns.
+///
+/// # Arguments
+///
+/// * `listener` - The listener players connect to.
+/// * `game` - The game to play.
+///
+/// # Returns
+///
+/// * `Ok(ChessGame)` - The game once it has ended.
+/// * `Err(io::Error)` - If accepting a player or writing to them fails.
+pub async fn serve_game(listener: &TcpListener, game: ChessGame) -> io::Result<ChessGame> {
+    let (white_reader, mut white_writer) = accept_player(listener, Color::White).await?;
+    let (black_reader, mut black_writer) = accept_player(listener, Color::Black).await?;
+
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    for (color, reader) in [(Color::White, white_reader), (Color::Black, black_reader)] {
+        tokio::spawn(forward_messages(color, reader, sender.clone()));
+    }
+    drop(sender);
+
+    let mut session = GameSession::new(game);
+    for writer in [&mut white_writer, &mut black_writer] {
+        send(writer, &session.get_state_message()).await?;
+    }
+
+    while !session.is_finished() {
+        let Some((color, message)) = receiver.recv().await else {
+            break;
+        };
+        let replies = match message {
+            Ok(message) => session.handle(color, message),
+            Err(ReceiveError::Disconnected) => session.handle(color, ClientMessage::Resign),
+            Err(ReceiveError::Invalid(error)) => vec![(
+                Recipient::Player(color),
+                ServerMessage::Rejected(error.to_string()),
+            )],
+        };
+        for (recipient, message) in replies {
+            if recipient != Recipient::Player(Color::Black) {
+                // a player who has gone only misses the news
+                let _ = send(&mut white_writer, &message).await;
+            }
+            if recipient != Recipient::Player(Color::White) {
+                let _ = send(&mut black_writer, &message).await;
+            }
+        }
+    }
+    Ok(session.get_game().clone())
+}
+
+/// Accepts the next connection and welcomes the player as the given color.
+async fn accept_player(
+    listener: &TcpListener,
+    color: Color,
+) -> io::Result<(OwnedReadHalf, OwnedWriteHalf)> {
+    let (reader, mut writer) = listener.accept().await?.0.into_split();
+    send(&mut writer, &ServerMessage::Welcome(color)).await?;
+    Ok((reader, writer))
+}
+
+/// Why a message of a player could not be passed on.
+enum ReceiveError {
+    Disconnected,
+    Invalid(ProtocolError),
+}
+
+type PlayerMessage = (Color, Result<ClientMessage, ReceiveError>);
+
+/// Reads the messages of a player until they disconnect, passing them on to the game.
+async fn forward_messages(
+    color: Color,
+    mut reader: OwnedReadHalf,
+    sender: mpsc::UnboundedSender<PlayerMessage>,
+) {
+    loop {
+        let message = match read_frame(&mut reader).await {
+            Ok(payload) => match String::from_utf8(payload) {
+                Ok(text) => ClientMessage::decode(&text).map_err(ReceiveError::Invalid),
+                Err(_) => Err(ReceiveError::Invalid(ProtocolError::new(String::from(
+                    "a message is not valid UTF-8",
+                )))),
+            },
+            Err(_) => {
+                let _ = sender.send((color, Err(ReceiveError::Disconnected)));
+                return;
+            }
+        };
+        if sender.send((color, message)).is_err() {
+            return;
+        }
+    }
+}
+
+async fn send(writer: &mut OwnedWriteHalf, message: &ServerMessage) -> io::Result<()> {
+    write_frame(writer, message.encode().as_bytes()).await
+}
+
+/// A player connected to a server started with `serve_game`.
+pub struct Client {
+    stream: TcpStream,
+    color: Color,
+}
+
+impl Client {
+    /// Connects to a server and waits for the color to play.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Client)` - The connected player.
+    /// * `Err(io::Error)` - If the connection fails or the server does not send a
+    ///   `ServerMessage::Welcome` first.
+    pub async fn connect<A: ToSocketAddrs>(address: A) -> io::Result<Client> {
+        let mut stream = TcpStream::connect(address).await?;
+        let color = match receive_from(&mut stream).await? {
+            ServerMessage::Welcome(color) => color,
+            message => {
+                return Err(invalid_data(ProtocolError::new(format!(
+                    "expected a welcome, got '{}'",
+                    message.encode()
+                ))))
+            }
+        };
+        Ok(Client { stream, color })
+    }
+
+    /// Returns the color the player plays.
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    /// Sends a message to the server.
+    pub async fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        write_frame(&mut self.stream, message.encode().as_bytes()).await
+    }
+
+    /// Waits for the next message of the server.
+    pub async fn receive(&mut self) -> io::Result<ServerMessage> {
+        receive_from(&mut self.stream).await
+    }
+}
+
+async fn receive_from(stream: &mut TcpStream) -> io::Result<ServerMessage> {
+    let payload = read_frame(stream).await?;
+    let text = String::from_utf8(payload).map_err(|_| {
+        invalid_data(ProtocolError::new(String::from(
+            "a message is not valid UTF-8",
+        )))
+    })?;
+    ServerMessage::decode(&text).map_err(invalid_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_game_state_analyzer::GameState;
+
+    #[tokio::test]
+    async fn two_players_play_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server =
+            tokio::spawn(async move { serve_game(&listener, ChessGame::new()).await.unwrap() });
+
+        let mut white = Client::connect(address).await.unwrap();
+        let mut black = Client::connect(address).await.unwrap();
+        assert_eq!(Color::White, white.get_color());
+        assert_eq!(Color::Black, black.get_color());
+        for client in [&mut white, &mut black] {
+            assert!(matches!(
+                client.receive().await.unwrap(),
+                ServerMessage::State(_)
+            ));
+        }
+
+        black
+            .send(&ClientMessage::Move(String::from("e7e5")))
+            .await
+            .unwrap();
+        assert!(matches!(
+            black.receive().await.unwrap(),
+            ServerMessage::Rejected(_)
+        ));
+
+        white
+            .send(&ClientMessage::Move(String::from("e2e4")))
+            .await
+            .unwrap();
+        for client in [&mut white, &mut black] {
+            let ServerMessage::State(json) = client.receive().await.unwrap() else {
+                panic!("expected the state after the move");
+            };
+            assert!(json.contains(r#""turn":"black""#));
+        }
+
+        black.send(&ClientMessage::Resign).await.unwrap();
+        let mut game = server.await.unwrap();
+        assert_eq!(
+            GameState::Resigned {
+                winner: Color::White
+            },
+            game.get_game_state()
+        );
+    }
+}
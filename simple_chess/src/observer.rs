@@ -0,0 +1,235 @@
+use crate::chess_game_state_analyzer::GameState;
+use crate::piece::ChessPiece;
+use crate::{ChessGame, ChessMoveType, Color};
+
+/// Receives the events of a game as moves are made, so user interfaces and loggers do not
+/// have to compare boards to find out what happened.
+///
+/// Every callback does nothing by default, so an observer only implements the events it is
+/// interested in. Callbacks are given the game after the move has been made.
+///
+/// Observers are only called for moves made with `ChessGame::make_move` or
+/// `ChessGame::try_make_move`, and are not copied when a game is cloned, so searches that
+/// play moves on copies of a game do not fire events.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::observer::GameObserver;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessGame, ChessMoveType, Color};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// struct MoveCounter(Arc<AtomicUsize>);
+///
+/// impl GameObserver for MoveCounter {
+///     fn on_move_made(&mut self, _game: &ChessGame, _chess_move: &ChessMoveType) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let moves = Arc::new(AtomicUsize::new(0));
+/// let mut game = ChessGame::new();
+/// game.subscribe(Box::new(MoveCounter(Arc::clone(&moves))));
+///
+/// game.make_move(ChessMoveType::Move {
+///     original_position: (4, 1),
+///     new_position: (4, 3),
+///     piece: ChessPiece::new(PieceType::Pawn, Color::White),
+///     taken_piece: None,
+///     promotion: None,
+/// });
+/// assert_eq!(1, moves.load(Ordering::Relaxed));
+/// ```
+pub trait GameObserver: Send + Sync {
+    /// Called after every move.
+    fn on_move_made(&mut self, _game: &ChessGame, _chess_move: &ChessMoveType) {}
+
+    /// Called after a move that captured a piece, including en passant captures.
+    fn on_capture(&mut self, _game: &ChessGame, _taken_piece: &ChessPiece) {}
+
+    /// Called after a pawn was promoted, with the piece it was promoted to.
+    fn on_promotion(&mut self, _game: &ChessGame, _promoted_to: &ChessPiece) {}
+
+    /// Called after every move with the player whose turn it now is.
+    fn on_turn_changed(&mut self, _game: &ChessGame, _turn: Color) {}
+
+    /// Called after a move that put a player in check, including checkmate.
+    fn on_check(&mut self, _game: &ChessGame, _checked: Color) {}
+
+    /// Called after a move that ended the game by checkmate, stalemate, a dead position, the
+    /// seventy-five-move rule or a variant's own win condition, and when the game ends by
+    /// resignation, a draw agreement, adjudication or a player running out of time.
+    fn on_game_ended(&mut self, _game: &ChessGame, _state: &GameState) {}
+}
+
+/// The observers subscribed to a game.
+///
+/// Cloning gives an empty list, as a copy of a game is a separate game that the observers
+/// did not subscribe to.
+#[derive(Default)]
+pub(crate) struct Observers(Vec<Box<dyn GameObserver>>);
+
+impl Observers {
+    pub(crate) fn push(&mut self, observer: Box<dyn GameObserver>) {
+        self.0.push(observer);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Fires the events of a move that has just been made on the game.
+    pub(crate) fn notify(
+        &mut self,
+        game: &ChessGame,
+        chess_move: &ChessMoveType,
+        state: &GameState,
+    ) {
+        let (taken_piece, promotion) = match chess_move {
+            ChessMoveType::Move {
+                taken_piece,
+                promotion,
+                ..
+            } => (*taken_piece, *promotion),
+            ChessMoveType::EnPassant {
+                taken_piece,
+                promotion,
+                ..
+            } => (Some(*taken_piece), *promotion),
+            ChessMoveType::Castle { .. } => (None, None),
+        };
+        let turn = game.get_current_players_turn();
+
+        for observer in self.0.iter_mut() {
+            observer.on_move_made(game, chess_move);
+            if let Some(taken_piece) = &taken_piece {
+                observer.on_capture(game, taken_piece);
+            }
+            if let Some(promotion) = &promotion {
+                observer.on_promotion(game, promotion);
+            }
+            observer.on_turn_changed(game, turn);
+            match state {
+                GameState::Check { turn, .. } => observer.on_check(game, *turn),
+                GameState::Checkmate { winner } => {
+                    observer.on_check(game, winner.opposite());
+                    observer.on_game_ended(game, state);
+                }
+                GameState::Stalemate
+                | GameState::VariantWin { .. }
+                | GameState::DeadPosition
+                | GameState::SeventyFiveMoveRule => observer.on_game_ended(game, state),
+                GameState::InProgress { .. } => {}
+                GameState::Resigned { .. } | GameState::DrawAgreed => {
+                    observer.on_game_ended(game, state)
+                }
+            }
+        }
+    }
+
+    /// Fires the end of a game that ended without a move, by resignation, a draw
+    /// agreement or adjudication.
+    pub(crate) fn notify_game_ended(&mut self, game: &ChessGame, state: &GameState) {
+        for observer in self.0.iter_mut() {
+            observer.on_game_ended(game, state);
+        }
+    }
+}
+
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Observers::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::long_algebraic_notation::encode_move_as_string;
+    use crate::piece::PieceType::{Pawn, Queen, Rook};
+    use std::sync::{Arc, Mutex};
+
+    struct EventLog(Arc<Mutex<Vec<String>>>);
+
+    impl GameObserver for EventLog {
+        fn on_move_made(&mut self, _game: &ChessGame, chess_move: &ChessMoveType) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("move {}", encode_move_as_string(chess_move)));
+        }
+
+        fn on_capture(&mut self, _game: &ChessGame, taken_piece: &ChessPiece) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("capture {:?}", taken_piece.get_piece_type()));
+        }
+
+        fn on_promotion(&mut self, _game: &ChessGame, promoted_to: &ChessPiece) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("promotion {:?}", promoted_to.get_piece_type()));
+        }
+
+        fn on_turn_changed(&mut self, _game: &ChessGame, turn: Color) {
+            self.0.lock().unwrap().push(format!("turn {turn:?}"));
+        }
+
+        fn on_check(&mut self, _game: &ChessGame, checked: Color) {
+            self.0.lock().unwrap().push(format!("check {checked:?}"));
+        }
+
+        fn on_game_ended(&mut self, _game: &ChessGame, state: &GameState) {
+            self.0.lock().unwrap().push(format!("ended {state:?}"));
+        }
+    }
+
+    #[test]
+    fn a_capturing_promotion_with_mate_fires_every_event() {
+        let mut game = build_game_from_string("1r5k/P5pp/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        game.subscribe(Box::new(EventLog(Arc::clone(&log))));
+
+        game.make_move(ChessMoveType::Move {
+            original_position: (0, 6),
+            new_position: (1, 7),
+            piece: ChessPiece::new(Pawn, Color::White),
+            taken_piece: Some(ChessPiece::new(Rook, Color::Black)),
+            promotion: Some(ChessPiece::new(Queen, Color::White)),
+        });
+
+        assert_eq!(
+            vec![
+                "move a7b8q",
+                "capture Rook",
+                "promotion Queen",
+                "turn Black",
+                "check Black",
+                "ended Checkmate { winner: White }",
+            ],
+            *log.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn clones_of_a_game_do_not_notify_observers() {
+        let mut game = ChessGame::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        game.subscribe(Box::new(EventLog(Arc::clone(&log))));
+
+        let mut copy = game.clone();
+        copy.make_move(ChessMoveType::Move {
+            original_position: (4, 1),
+            new_position: (4, 3),
+            piece: ChessPiece::new(Pawn, Color::White),
+            taken_piece: None,
+            promotion: None,
+        });
+        assert!(log.lock().unwrap().is_empty());
+    }
+}
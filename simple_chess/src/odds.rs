@@ -0,0 +1,249 @@
+use crate::engine::Engine;
+use crate::setup::BoardSetup;
+use crate::{ChessGame, Color};
+
+/// How steeply the expected score rises with the evaluation, per centipawn.
+///
+/// This is the logistic model lichess fits to its games to turn an engine evaluation into
+/// a winning chance.
+const SCORE_SCALE: f64 = 0.003_682_08;
+
+/// The closest an expected score gets to `0.0` or `1.0`, which keeps ratings finite for
+/// won positions.
+const EXPECTED_SCORE_LIMIT: f64 = 0.001;
+
+/// A classic handicap, where the stronger player starts without some material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaterialOdds {
+    /// The f-pawn is removed.
+    Pawn,
+    /// The f-pawn is removed and the opponent moves first.
+    PawnAndMove,
+    /// The queen's knight is removed.
+    Knight,
+    /// The queen's rook is removed, along with castling on that side.
+    Rook,
+    /// The queen is removed.
+    Queen,
+}
+
+impl MaterialOdds {
+    /// Returns the name of the handicap, such as `"Knight odds"`.
+    pub fn get_name(&self) -> &str {
+        match self {
+            MaterialOdds::Pawn => "Pawn odds",
+            MaterialOdds::PawnAndMove => "Pawn and move odds",
+            MaterialOdds::Knight => "Knight odds",
+            MaterialOdds::Rook => "Rook odds",
+            MaterialOdds::Queen => "Queen odds",
+        }
+    }
+
+    /// Returns the standard setup with the handicap applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `giver` - The player giving the odds, who starts without the material.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::encode_game_as_string;
+    /// use simple_chess::odds::MaterialOdds;
+    /// use simple_chess::Color;
+    ///
+    /// let game = MaterialOdds::Rook.build_setup(Color::White).build_game().unwrap();
+    /// assert_eq!(
+    ///     "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w Kkq - 0 1",
+    ///     encode_game_as_string(&game)
+    /// );
+    /// ```
+    pub fn build_setup(&self, giver: Color) -> BoardSetup {
+        let back_rank = giver.relative_rank(0, 8);
+        let setup = BoardSetup::standard();
+        match self {
+            MaterialOdds::Pawn => setup.remove_piece(5, giver.relative_rank(1, 8)),
+            MaterialOdds::PawnAndMove => setup
+                .remove_piece(5, giver.relative_rank(1, 8))
+                .set_current_turn(giver.opposite()),
+            MaterialOdds::Knight => setup.remove_piece(1, back_rank),
+            MaterialOdds::Rook => {
+                let (white_long, black_long) = match giver {
+                    Color::White => (false, true),
+                    Color::Black => (true, false),
+                };
+                setup
+                    .remove_piece(0, back_rank)
+                    .set_castle_rights(true, white_long, true, black_long)
+            }
+            MaterialOdds::Queen => setup.remove_piece(3, back_rank),
+        }
+    }
+}
+
+/// The engine's estimate of how much a position favors one side, in rating points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OddsEstimate {
+    score: i32,
+    expected_score: f64,
+    elo_difference: f64,
+}
+
+impl OddsEstimate {
+    /// Returns the engine's evaluation of the position in centipawns, from White's point of
+    /// view.
+    pub fn get_score(&self) -> i32 {
+        self.score
+    }
+
+    /// Returns the share of the points White is expected to score between equal players,
+    /// between `0.0` and `1.0`.
+    pub fn get_expected_score(&self) -> f64 {
+        self.expected_score
+    }
+
+    /// Returns the rating difference the position is worth to White, negative when it
+    /// favors Black.
+    ///
+    /// A player rated this many points below their opponent is expected to score evenly
+    /// when starting from the position.
+    pub fn get_elo_difference(&self) -> f64 {
+        self.elo_difference
+    }
+}
+
+/// Estimates the rating impact of the imbalance in a position, such as a handicap.
+///
+/// The position is searched by the engine, its evaluation is turned into an expected score
+/// with the logistic model lichess fits to its games, and the expected score into a rating
+/// difference with the Elo formula. The estimate is a guide for matchmaking rather than a
+/// measurement, handicaps tend to be worth less between weaker players, who convert
+/// material advantages less reliably.
+///
+/// # Arguments
+///
+/// * `game` - The position to estimate, the game is not modified.
+/// * `engine` - The engine used to evaluate the position.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::Engine;
+/// use simple_chess::odds::{estimate_odds, MaterialOdds};
+/// use simple_chess::Color;
+///
+/// let mut engine = Engine::new().set_depth(2);
+/// let knight = MaterialOdds::Knight.build_setup(Color::White).build_game().unwrap();
+/// let queen = MaterialOdds::Queen.build_setup(Color::White).build_game().unwrap();
+///
+/// let knight_odds = estimate_odds(&knight, &mut engine);
+/// let queen_odds = estimate_odds(&queen, &mut engine);
+/// assert!(knight_odds.get_elo_difference() < 0.0);
+/// assert!(queen_odds.get_elo_difference() < knight_odds.get_elo_difference());
+/// ```
+pub fn estimate_odds(game: &ChessGame, engine: &mut Engine) -> OddsEstimate {
+    let result = engine.search(game, engine.get_depth());
+    let score = match game.get_current_players_turn() {
+        Color::White => result.get_score(),
+        Color::Black => -result.get_score(),
+    };
+    let expected_score = expected_score_from_centipawns(score);
+
+    OddsEstimate {
+        score,
+        expected_score,
+        elo_difference: elo_difference_from_expected_score(expected_score),
+    }
+}
+
+/// Converts an evaluation into the share of the points the favored side is expected to
+/// score.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::odds::expected_score_from_centipawns;
+///
+/// assert_eq!(0.5, expected_score_from_centipawns(0));
+/// assert!(expected_score_from_centipawns(300) > 0.7);
+/// ```
+pub fn expected_score_from_centipawns(centipawns: i32) -> f64 {
+    1.0 / (1.0 + (-SCORE_SCALE * centipawns as f64).exp())
+}
+
+/// Converts an expected score into the rating difference that gives it under the Elo
+/// formula.
+///
+/// Scores are clamped just short of `0.0` and `1.0`, where the difference would be
+/// infinite.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::odds::elo_difference_from_expected_score;
+///
+/// assert_eq!(0.0, elo_difference_from_expected_score(0.5));
+/// assert!((elo_difference_from_expected_score(0.76) - 200.0).abs() < 1.0);
+/// ```
+pub fn elo_difference_from_expected_score(expected_score: f64) -> f64 {
+    let expected_score = expected_score.clamp(EXPECTED_SCORE_LIMIT, 1.0 - EXPECTED_SCORE_LIMIT);
+    400.0 * (expected_score / (1.0 - expected_score)).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::encode_game_as_string;
+
+    #[test]
+    fn odds_setups_remove_the_givers_material() {
+        let fen = |odds: MaterialOdds, giver: Color| {
+            encode_game_as_string(&odds.build_setup(giver).build_game().unwrap())
+        };
+
+        assert_eq!(
+            "rnbqkbnr/ppppp1pp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            fen(MaterialOdds::Pawn, Color::Black)
+        );
+        assert_eq!(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPP1PP/RNBQKBNR b KQkq - 0 1",
+            fen(MaterialOdds::PawnAndMove, Color::White)
+        );
+        assert_eq!(
+            "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            fen(MaterialOdds::Knight, Color::Black)
+        );
+        assert_eq!(
+            "1nbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQk - 0 1",
+            fen(MaterialOdds::Rook, Color::Black)
+        );
+    }
+
+    #[test]
+    fn ratings_are_symmetric_and_finite() {
+        for centipawns in [50, 300, 900, 100_000] {
+            let white =
+                elo_difference_from_expected_score(expected_score_from_centipawns(centipawns));
+            let black =
+                elo_difference_from_expected_score(expected_score_from_centipawns(-centipawns));
+            assert!(white > 0.0 && white.is_finite());
+            assert!((white + black).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn estimates_are_from_whites_point_of_view() {
+        let mut engine = Engine::new().set_depth(1);
+        let white_gives = MaterialOdds::Queen.build_setup(Color::White);
+        let black_to_move = white_gives
+            .clone()
+            .set_current_turn(Color::Black)
+            .build_game()
+            .unwrap();
+
+        let estimate = estimate_odds(&black_to_move, &mut engine);
+        assert!(estimate.get_score() < -500);
+        assert!(estimate.get_expected_score() < 0.5);
+        assert!(estimate.get_elo_difference() < 0.0);
+    }
+}
@@ -0,0 +1,526 @@
+use crate::book::{encode_polyglot_move, Book, BookEntry};
+use crate::chess_game_move_analyzer::get_legal_moves;
+use crate::chess_move::ChessMoveType;
+use crate::codec::standard_algebraic_notation::encode_move_as_string;
+use crate::error::ChessError;
+use crate::repetition::PositionKey;
+use crate::ChessGame;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// A move from one position of an `OpeningTree` to another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningEdge {
+    chess_move: ChessMoveType,
+    san: String,
+    child: PositionKey,
+    count: usize,
+}
+
+impl OpeningEdge {
+    /// Returns the move.
+    pub fn get_move(&self) -> &ChessMoveType {
+        &self.chess_move
+    }
+
+    /// Returns the move in standard algebraic notation, such as `Nf3`.
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Returns the key of the position the move leads to.
+    pub fn get_child(&self) -> &PositionKey {
+        &self.child
+    }
+
+    /// Returns how many of the lines added to the tree play the move.
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+}
+
+/// A position of an `OpeningTree`, with the moves played from it.
+#[derive(Clone)]
+pub struct OpeningNode {
+    key: PositionKey,
+    game: ChessGame,
+    children: Vec<OpeningEdge>,
+    parents: Vec<PositionKey>,
+    count: usize,
+}
+
+impl OpeningNode {
+    /// Returns the key identifying the position.
+    pub fn get_key(&self) -> &PositionKey {
+        &self.key
+    }
+
+    /// Returns a game in the position, reached by the first move order added to the tree.
+    pub fn get_game(&self) -> &ChessGame {
+        &self.game
+    }
+
+    /// Returns the moves played from the position, in the order they were first added.
+    pub fn get_children(&self) -> &[OpeningEdge] {
+        &self.children
+    }
+
+    /// Returns the keys of the positions the position is reached from.
+    pub fn get_parents(&self) -> &[PositionKey] {
+        &self.parents
+    }
+
+    /// Returns how many of the lines added to the tree pass through the position.
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the position is reached from more than one position, and so by
+    /// more than one move order.
+    pub fn is_transposition(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// A repertoire of opening lines, where lines reaching the same position share a node.
+///
+/// Positions are identified by their `PositionKey`, which holds the pieces, side to move,
+/// castling rights and en passant square, so `1. Nf3 d5 2. d4` and `1. d4 d5 2. Nf3` lead to
+/// the same node and the moves prepared after it are shared by both move orders. The tree is
+/// a directed acyclic graph rather than a tree, apart from lines that repeat a position,
+/// which form a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::opening_tree::OpeningTree;
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::{ChessMoveType, Color};
+///
+/// let pawn_move = |color, from: (usize, usize), to| ChessMoveType::Move {
+///     original_position: from,
+///     new_position: to,
+///     piece: ChessPiece::new(PieceType::Pawn, color),
+///     taken_piece: None,
+///     promotion: None,
+/// };
+/// let d4 = pawn_move(Color::White, (3, 1), (3, 3));
+/// let c4 = pawn_move(Color::White, (2, 1), (2, 3));
+/// let e6 = pawn_move(Color::Black, (4, 6), (4, 5));
+///
+/// let mut tree = OpeningTree::new();
+/// let queens_gambit = tree.add_line(&[d4, e6, c4]).unwrap();
+/// let english = tree.add_line(&[c4, e6, d4]).unwrap();
+/// assert_eq!(queens_gambit, english);
+///
+/// let orders = tree.get_move_orders(&english);
+/// assert_eq!(2, orders.len());
+/// assert_eq!("d4", orders[0][0].get_san());
+/// assert_eq!("c4", orders[1][0].get_san());
+/// ```
+#[derive(Clone)]
+pub struct OpeningTree {
+    root: PositionKey,
+    nodes: HashMap<PositionKey, OpeningNode>,
+}
+
+impl OpeningTree {
+    /// Creates a tree starting from the standard starting position.
+    pub fn new() -> Self {
+        Self::from_position(&ChessGame::new())
+    }
+
+    /// Creates a tree starting from the current position of a game.
+    pub fn from_position(game: &ChessGame) -> Self {
+        let root = PositionKey::new(game);
+        let mut nodes = HashMap::new();
+        nodes.insert(root.clone(), new_node(root.clone(), game.clone()));
+        Self { root, nodes }
+    }
+
+    /// Returns the position the tree starts from.
+    pub fn get_root(&self) -> &OpeningNode {
+        &self.nodes[&self.root]
+    }
+
+    /// Returns the node of a position, if the position is in the tree.
+    pub fn get_node(&self, key: &PositionKey) -> Option<&OpeningNode> {
+        self.nodes.get(key)
+    }
+
+    /// Returns the node of the current position of a game, if the position is in the tree.
+    pub fn find_node(&self, game: &ChessGame) -> Option<&OpeningNode> {
+        self.get_node(&PositionKey::new(game))
+    }
+
+    /// Returns the number of positions in the tree, including the root.
+    pub fn get_node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Adds a line of moves played from the root, merging it into the positions already in
+    /// the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `moves` - The moves of the line, starting from the root position.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PositionKey)` - The key of the position the line ends in.
+    /// * `Err(OpeningTreeError)` - If a move is not legal, the tree is left unchanged.
+    pub fn add_line(&mut self, moves: &[ChessMoveType]) -> Result<PositionKey, OpeningTreeError> {
+        let mut game = self.get_root().game.clone();
+        let mut steps = Vec::new();
+        for (index, chess_move) in moves.iter().enumerate() {
+            if !get_legal_moves(&mut game).contains(chess_move) {
+                return Err(OpeningTreeError::new(format!(
+                    "move {} of the line is not legal",
+                    index + 1
+                )));
+            }
+            let san = encode_move_as_string(&game, chess_move);
+            game.make_move(*chess_move);
+            steps.push((*chess_move, san, game.clone()));
+        }
+
+        let mut parent = self.root.clone();
+        self.nodes
+            .get_mut(&parent)
+            .expect("the root is in the tree")
+            .count += 1;
+        for (chess_move, san, game) in steps {
+            let child = PositionKey::new(&game);
+
+            let parent_node = self
+                .nodes
+                .get_mut(&parent)
+                .expect("parents are in the tree");
+            match parent_node
+                .children
+                .iter_mut()
+                .find(|edge| edge.chess_move == chess_move)
+            {
+                Some(edge) => edge.count += 1,
+                None => parent_node.children.push(OpeningEdge {
+                    chess_move,
+                    san,
+                    child: child.clone(),
+                    count: 1,
+                }),
+            }
+
+            let child_node = self
+                .nodes
+                .entry(child.clone())
+                .or_insert_with(|| new_node(child.clone(), game));
+            child_node.count += 1;
+            if !child_node.parents.contains(&parent) {
+                child_node.parents.push(parent);
+            }
+            parent = child;
+        }
+        Ok(parent)
+    }
+
+    /// Returns every move order reaching a position from the root.
+    ///
+    /// Each move order is the list of moves played, and passes through any position at most
+    /// once, so lines that repeat a position do not produce endless move orders.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the position.
+    ///
+    /// # Returns
+    ///
+    /// The move orders, empty if the position is not in the tree and a single empty move
+    /// order for the root.
+    pub fn get_move_orders(&self, key: &PositionKey) -> Vec<Vec<&OpeningEdge>> {
+        let mut move_orders = Vec::new();
+        if let Some((key, _)) = self.nodes.get_key_value(key) {
+            let mut visited = HashSet::new();
+            let mut suffix = Vec::new();
+            self.collect_move_orders(key, &mut visited, &mut suffix, &mut move_orders);
+        }
+        move_orders
+    }
+
+    /// Removes the moves played by fewer than `min_count` of the lines added to the tree,
+    /// along with the positions that can no longer be reached from the root.
+    ///
+    /// # Returns
+    ///
+    /// The number of positions removed.
+    pub fn prune(&mut self, min_count: usize) -> usize {
+        for node in self.nodes.values_mut() {
+            node.children.retain(|edge| edge.count >= min_count);
+        }
+        self.remove_unreachable()
+    }
+
+    /// Removes a move from a position, along with the positions that can no longer be
+    /// reached from the root.
+    ///
+    /// # Returns
+    ///
+    /// The number of positions removed, `0` if the move was not in the tree.
+    pub fn remove_move(&mut self, key: &PositionKey, chess_move: &ChessMoveType) -> usize {
+        match self.nodes.get_mut(key) {
+            Some(node) => node.children.retain(|edge| edge.chess_move != *chess_move),
+            None => return 0,
+        }
+        self.remove_unreachable()
+    }
+
+    /// Builds a Polyglot opening book holding every move of the tree, weighted by how many
+    /// lines play it.
+    ///
+    /// Moves reached by several move orders are written once, so the book plays them from
+    /// any of those orders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board of the tree is not 8x8.
+    pub fn to_book(&self) -> Book {
+        let book = Book::new();
+        let mut entries = Vec::new();
+        for node in self.nodes.values() {
+            let key = book.get_key(&node.game);
+            for edge in &node.children {
+                entries.push(BookEntry::new(
+                    key,
+                    encode_polyglot_move(&edge.chess_move),
+                    edge.count.min(u16::MAX as usize) as u16,
+                    0,
+                ));
+            }
+        }
+        Book::from_entries(entries)
+    }
+
+    fn collect_move_orders<'a>(
+        &'a self,
+        key: &'a PositionKey,
+        visited: &mut HashSet<&'a PositionKey>,
+        suffix: &mut Vec<&'a OpeningEdge>,
+        move_orders: &mut Vec<Vec<&'a OpeningEdge>>,
+    ) {
+        if *key == self.root {
+            move_orders.push(suffix.iter().rev().copied().collect());
+            return;
+        }
+        visited.insert(key);
+        for parent in &self.nodes[key].parents {
+            if visited.contains(parent) {
+                continue;
+            }
+            for edge in &self.nodes[parent].children {
+                if edge.child == *key {
+                    suffix.push(edge);
+                    self.collect_move_orders(parent, visited, suffix, move_orders);
+                    suffix.pop();
+                }
+            }
+        }
+        visited.remove(key);
+    }
+
+    /// Removes the positions that can not be reached from the root, and the links to them.
+    fn remove_unreachable(&mut self) -> usize {
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::from([self.root.clone()]);
+        while let Some(key) = queue.pop_front() {
+            if reachable.insert(key.clone()) {
+                queue.extend(self.nodes[&key].children.iter().map(|e| e.child.clone()));
+            }
+        }
+
+        let node_count = self.nodes.len();
+        self.nodes.retain(|key, _| reachable.contains(key));
+        let links: HashSet<(PositionKey, PositionKey)> = self
+            .nodes
+            .values()
+            .flat_map(|node| {
+                node.children
+                    .iter()
+                    .map(|edge| (node.key.clone(), edge.child.clone()))
+            })
+            .collect();
+        for node in self.nodes.values_mut() {
+            node.parents
+                .retain(|parent| links.contains(&(parent.clone(), node.key.clone())));
+        }
+        node_count - self.nodes.len()
+    }
+}
+
+impl Default for OpeningTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn new_node(key: PositionKey, game: ChessGame) -> OpeningNode {
+    OpeningNode {
+        key,
+        game,
+        children: Vec::new(),
+        parents: Vec::new(),
+        count: 0,
+    }
+}
+
+/// Error returned when a line can not be added to an `OpeningTree`.
+pub struct OpeningTreeError {
+    reason: String,
+}
+
+impl OpeningTreeError {
+    fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for OpeningTreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Opening tree error: {}", self.reason)
+    }
+}
+
+impl Debug for OpeningTreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OpeningTreeError: {}", self.reason)
+    }
+}
+
+impl Error for OpeningTreeError {}
+
+impl From<OpeningTreeError> for ChessError {
+    fn from(error: OpeningTreeError) -> Self {
+        ChessError::IllegalMove(error.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A move given as the squares it is played from and to.
+    type Squares = ((usize, usize), (usize, usize));
+
+    /// Plays moves given as coordinate pairs, returning them as `ChessMoveType`s.
+    fn line(squares: &[Squares]) -> Vec<ChessMoveType> {
+        let mut game = ChessGame::new();
+        let mut moves = Vec::new();
+        for &(from, to) in squares {
+            let chess_move = get_legal_moves(&mut game)
+                .into_iter()
+                .find(|m| m.get_original_position() == from && m.get_new_position() == to)
+                .unwrap();
+            game.make_move(chess_move);
+            moves.push(chess_move);
+        }
+        moves
+    }
+
+    const NF3: Squares = ((6, 0), (5, 2));
+    const D4: Squares = ((3, 1), (3, 3));
+    const D5: Squares = ((3, 6), (3, 4));
+    const E4: Squares = ((4, 1), (4, 3));
+    const E5: Squares = ((4, 6), (4, 4));
+
+    #[test]
+    fn transpositions_share_a_node() {
+        let mut tree = OpeningTree::new();
+        let first = tree.add_line(&line(&[NF3, D5, D4])).unwrap();
+        let second = tree.add_line(&line(&[D4, D5, NF3])).unwrap();
+
+        assert_eq!(first, second);
+        // the root, the two first moves, their two replies and the shared position
+        assert_eq!(6, tree.get_node_count());
+        let node = tree.get_node(&first).unwrap();
+        assert!(node.is_transposition());
+        assert_eq!(2, node.get_count());
+        assert_eq!(2, tree.get_root().get_count());
+
+        let orders: Vec<Vec<&str>> = tree
+            .get_move_orders(&first)
+            .iter()
+            .map(|order| order.iter().map(|edge| edge.get_san()).collect())
+            .collect();
+        assert_eq!(
+            vec![vec!["Nf3", "d5", "d4"], vec!["d4", "d5", "Nf3"]],
+            orders
+        );
+    }
+
+    #[test]
+    fn repeated_positions_do_not_loop() {
+        let mut tree = OpeningTree::new();
+        let nf6 = ((6, 7), (5, 5));
+        let back = tree
+            .add_line(&line(&[NF3, nf6, (NF3.1, NF3.0), (nf6.1, nf6.0), E4]))
+            .unwrap();
+
+        assert_eq!(1, tree.get_move_orders(&back).len());
+        assert_eq!(
+            vec![Vec::<&OpeningEdge>::new()],
+            tree.get_move_orders(&tree.root)
+        );
+    }
+
+    #[test]
+    fn pruning_removes_rare_lines() {
+        let mut tree = OpeningTree::new();
+        let main_line = tree.add_line(&line(&[E4, E5])).unwrap();
+        tree.add_line(&line(&[E4, E5])).unwrap();
+        tree.add_line(&line(&[E4, D5])).unwrap();
+        let side_line = tree.add_line(&line(&[D4, D5])).unwrap();
+
+        assert_eq!(3, tree.prune(2));
+        assert!(tree.get_node(&main_line).is_some());
+        assert!(tree.get_node(&side_line).is_none());
+        assert_eq!(3, tree.get_node_count());
+    }
+
+    #[test]
+    fn removing_a_move_keeps_transposed_positions() {
+        let mut tree = OpeningTree::new();
+        let shared = tree.add_line(&line(&[NF3, D5, D4])).unwrap();
+        tree.add_line(&line(&[D4, D5, NF3])).unwrap();
+
+        let root = tree.root.clone();
+        let nf3 = line(&[NF3])[0];
+        assert_eq!(2, tree.remove_move(&root, &nf3));
+
+        let node = tree.get_node(&shared).unwrap();
+        assert!(!node.is_transposition());
+        assert_eq!(1, tree.get_move_orders(&shared).len());
+    }
+
+    #[test]
+    fn books_are_built_from_every_move_order() {
+        let mut tree = OpeningTree::new();
+        tree.add_line(&line(&[NF3, D5, D4, E5])).unwrap();
+        tree.add_line(&line(&[D4, D5, NF3])).unwrap();
+
+        let mut game = ChessGame::new();
+        for chess_move in line(&[D4, D5, NF3]) {
+            game.make_move(chess_move);
+        }
+        let book_moves = tree.to_book().get_moves(&game);
+        assert_eq!(1, book_moves.len());
+        assert_eq!(line(&[NF3, D5, D4, E5])[3], *book_moves[0].get_move());
+    }
+
+    #[test]
+    fn illegal_lines_are_rejected() {
+        let mut tree = OpeningTree::new();
+        let e4 = line(&[E4])[0];
+        assert!(tree.add_line(&[e4, e4]).is_err());
+        assert_eq!(1, tree.get_node_count());
+        assert_eq!(0, tree.get_root().get_count());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::chess_game_move_analyzer::get_legal_moves;
+use crate::{ChessGame, ChessMoveType};
+
+/// Counts the leaf nodes of the legal move tree of a game to the given depth.
+///
+/// Perft (performance test) walks every legal move sequence of `depth` plies from the
+/// current position. The resulting node counts are well known for many positions, which
+/// makes perft the standard way to validate a move generator.
+///
+/// # Arguments
+///
+/// * `game` - The game whose current position is the root of the tree.
+/// * `depth` - The number of plies to walk.
+///
+/// # Returns
+///
+/// * `u64` - The number of positions reached after exactly `depth` plies.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::perft::perft;
+/// use simple_chess::ChessGame;
+///
+/// let game = ChessGame::new();
+/// assert_eq!(20, perft(&game, 1));
+/// assert_eq!(400, perft(&game, 2));
+/// ```
+pub fn perft(game: &ChessGame, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let mut game = game.clone();
+    count_nodes(&mut game, depth)
+}
+
+/// Counts the leaf nodes below each legal move of a game.
+///
+/// This splits the result of `perft` by the first move played, which makes it possible to
+/// narrow down which move a faulty count comes from when comparing against another engine.
+///
+/// # Arguments
+///
+/// * `game` - The game whose current position is the root of the tree.
+/// * `depth` - The number of plies to walk, including the first move.
+///
+/// # Returns
+///
+/// * `Vec<(ChessMoveType, u64)>` - Each legal move paired with the number of positions
+///   reached after exactly `depth` plies starting with that move.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::perft::perft_divide;
+/// use simple_chess::ChessGame;
+///
+/// let divide = perft_divide(&ChessGame::new(), 2);
+/// assert_eq!(20, divide.len());
+/// assert!(divide.iter().all(|(_, nodes)| *nodes == 20));
+/// ```
+pub fn perft_divide(game: &ChessGame, depth: u32) -> Vec<(ChessMoveType, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    let mut game = game.clone();
+    get_legal_moves(&mut game)
+        .into_iter()
+        .map(|chess_move| {
+            let mut next = game.clone();
+            next.apply_move(chess_move);
+            (chess_move, count_nodes(&mut next, depth - 1))
+        })
+        .collect()
+}
+
+/// Counts the leaf nodes of the legal move tree of a game like `perft`, walking the
+/// subtrees of the legal moves on all cores.
+///
+/// Only available with the `rayon` feature.
+///
+/// # Arguments
+///
+/// * `game` - The game whose current position is the root of the tree.
+/// * `depth` - The number of plies to walk.
+///
+/// # Returns
+///
+/// * `u64` - The number of positions reached after exactly `depth` plies, the same as
+///   `perft`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::perft::{perft, perft_parallel};
+/// use simple_chess::ChessGame;
+///
+/// let game = ChessGame::new();
+/// assert_eq!(perft(&game, 3), perft_parallel(&game, 3));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn perft_parallel(game: &ChessGame, depth: u32) -> u64 {
+    use rayon::prelude::*;
+
+    if depth <= 1 {
+        return perft(game, depth);
+    }
+
+    let mut game = game.clone();
+    get_legal_moves(&mut game)
+        .into_par_iter()
+        .map(|chess_move| {
+            let mut next = game.clone();
+            next.apply_move(chess_move);
+            count_nodes(&mut next, depth - 1)
+        })
+        .sum()
+}
+
+fn count_nodes(game: &mut ChessGame, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let legal_moves = get_legal_moves(game);
+    if depth == 1 {
+        return legal_moves.len() as u64;
+    }
+
+    legal_moves
+        .into_iter()
+        .map(|chess_move| {
+            let mut next = game.clone();
+            next.apply_move(chess_move);
+            count_nodes(&mut next, depth - 1)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+    const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+    const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+    fn assert_perft(fen: &str, expected: &[u64]) {
+        let game = build_game_from_string(fen).unwrap();
+        for (depth, nodes) in expected.iter().enumerate() {
+            assert_eq!(
+                *nodes,
+                perft(&game, depth as u32 + 1),
+                "perft({}) of {}",
+                depth + 1,
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn depth_zero_is_a_single_node() {
+        assert_eq!(1, perft(&ChessGame::new(), 0));
+        assert!(perft_divide(&ChessGame::new(), 0).is_empty());
+    }
+
+    #[test]
+    fn starting_position() {
+        assert_perft(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            &[20, 400, 8902, 197281],
+        );
+    }
+
+    #[test]
+    #[ignore = "slow, run with --ignored in release mode"]
+    fn starting_position_deep() {
+        let game = ChessGame::new();
+        assert_eq!(4865609, perft(&game, 5));
+        assert_eq!(119060324, perft(&game, 6));
+    }
+
+    #[test]
+    fn kiwipete() {
+        assert_perft(KIWIPETE, &[48, 2039, 97862]);
+    }
+
+    #[test]
+    fn position_3() {
+        assert_perft(POSITION_3, &[14, 191, 2812, 43238]);
+    }
+
+    #[test]
+    fn position_4() {
+        assert_perft(POSITION_4, &[6, 264, 9467]);
+    }
+
+    #[test]
+    fn position_5() {
+        assert_perft(POSITION_5, &[44, 1486, 62379]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_perft_matches_perft() {
+        assert_eq!(1, perft_parallel(&ChessGame::new(), 0));
+        assert_eq!(20, perft_parallel(&ChessGame::new(), 1));
+        let game = build_game_from_string(KIWIPETE).unwrap();
+        assert_eq!(97862, perft_parallel(&game, 3));
+    }
+
+    #[test]
+    fn divide_sums_to_perft() {
+        let game = build_game_from_string(KIWIPETE).unwrap();
+        let divide = perft_divide(&game, 2);
+
+        assert_eq!(48, divide.len());
+        assert_eq!(
+            perft(&game, 2),
+            divide.iter().map(|(_, nodes)| nodes).sum::<u64>()
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::chess_move::ChessMoveType;
+use crate::piece::{bishop, knight, ChessPiece, PieceType};
+use crate::Color;
+use game_board::Board;
+
+pub fn as_utf_str(color: Color) -> &'static str {
+    // there are no chess symbols for the piece, so its letter is used
+    match color {
+        Color::White => "A",
+        Color::Black => "a",
+    }
+}
+
+pub fn as_fen_char(color: Color) -> char {
+    match color {
+        Color::White => 'A',
+        Color::Black => 'a',
+    }
+}
+
+pub fn as_binary(color: Color) -> u8 {
+    match color {
+        Color::White => 0b1_0000,
+        Color::Black => 0b1_0001,
+    }
+}
+
+/// The piece moves diagonally like a bishop, or jumps like a knight.
+pub fn possible_moves(
+    color: Color,
+    position: (usize, usize),
+    board: &Board<ChessPiece>,
+) -> Vec<ChessMoveType> {
+    let piece = ChessPiece::new(PieceType::Archbishop, color);
+    bishop::possible_moves(color, position, board)
+        .into_iter()
+        .chain(knight::possible_moves(color, position, board))
+        .map(|chess_move| match chess_move {
+            ChessMoveType::Move {
+                original_position,
+                new_position,
+                taken_piece,
+                ..
+            } => ChessMoveType::Move {
+                original_position,
+                new_position,
+                piece,
+                taken_piece,
+                promotion: None,
+            },
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_combine_the_slides_and_the_knight_jumps() {
+        let mut board = Board::build(10, 8).unwrap();
+        let moves = possible_moves(Color::White, (4, 3), &board);
+        assert_eq!(22, moves.len());
+        assert!(moves.contains(&ChessMoveType::Move {
+            original_position: (4, 3),
+            new_position: (6, 4),
+            piece: ChessPiece::new(PieceType::Archbishop, Color::White),
+            taken_piece: None,
+            promotion: None,
+        }));
+
+        board.place_piece(ChessPiece::new(PieceType::Pawn, Color::Black), 5, 4);
+        let captures: Vec<_> = possible_moves(Color::White, (4, 3), &board)
+            .into_iter()
+            .filter(|chess_move| {
+                matches!(
+                    chess_move,
+                    ChessMoveType::Move {
+                        taken_piece: Some(_),
+                        ..
+                    }
+                )
+            })
+            .collect();
+        assert_eq!(1, captures.len());
+    }
+}
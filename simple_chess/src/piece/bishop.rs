@@ -100,7 +100,8 @@ mod tests {
             (6, 1),
             (7, 0),
         ]
-        .map(|(new_col, new_row)| {
+        .into_iter()
+        .for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (2, 5),
                 new_position: (new_col, new_row),
@@ -124,16 +125,18 @@ mod tests {
         let moves = white_bishop.possible_moves((2, 5), board, None);
         assert_eq!(6, moves.len());
 
-        [(0, 7), (0, 3), (1, 6), (1, 4), (3, 6), (4, 7)].map(|(new_col, new_row)| {
-            let expected_move = Move {
-                original_position: (2, 5),
-                new_position: (new_col, new_row),
-                piece: ChessPiece::new(PieceType::Bishop, Color::White),
-                taken_piece: None,
-                promotion: None,
-            };
-            assert!(moves.contains(&expected_move));
-        });
+        [(0, 7), (0, 3), (1, 6), (1, 4), (3, 6), (4, 7)]
+            .into_iter()
+            .for_each(|(new_col, new_row)| {
+                let expected_move = Move {
+                    original_position: (2, 5),
+                    new_position: (new_col, new_row),
+                    piece: ChessPiece::new(PieceType::Bishop, Color::White),
+                    taken_piece: None,
+                    promotion: None,
+                };
+                assert!(moves.contains(&expected_move));
+            });
     }
 
     #[test]
@@ -154,11 +157,9 @@ mod tests {
             (3, 4, Some(King)),
             (3, 6, Some(Queen)),
         ]
-        .map(|(new_col, new_row, take)| {
-            let taken_piece = match take {
-                None => None,
-                Some(t) => Some(ChessPiece::new(t, White)),
-            };
+        .into_iter()
+        .for_each(|(new_col, new_row, take)| {
+            let taken_piece = take.map(|t| ChessPiece::new(t, White));
             let expected_move = Move {
                 original_position: (2, 5),
                 new_position: (new_col, new_row),
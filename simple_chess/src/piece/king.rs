@@ -1,4 +1,5 @@
 use crate::chess_move::ChessMoveType;
+use crate::engine::attack_tables::{get_squares, KING_ATTACKS};
 use crate::piece::ChessPiece;
 use crate::piece::PieceType::King;
 use crate::Color;
@@ -41,21 +42,23 @@ pub fn possible_moves(
         (-1, 1),
         (-1, -1),
     ];
-    for mv in moves.iter() {
-        let new_col = position.0 as i32 + mv.0;
-        let new_row = position.1 as i32 + mv.1;
-
-        if new_col < 0
-            || new_col >= board.get_width() as i32
-            || new_row < 0
-            || new_row >= board.get_height() as i32
-        {
-            continue;
-        }
-
-        let new_col = new_col as usize;
-        let new_row = new_row as usize;
-
+    // the squares of a standard board are looked up instead of computed
+    let targets: Vec<(usize, usize)> = if board.get_width() == 8 && board.get_height() == 8 {
+        get_squares(KING_ATTACKS[position.0 + position.1 * 8]).collect()
+    } else {
+        moves
+            .iter()
+            .map(|mv| (position.0 as i32 + mv.0, position.1 as i32 + mv.1))
+            .filter(|&(col, row)| {
+                col >= 0
+                    && col < board.get_width() as i32
+                    && row >= 0
+                    && row < board.get_height() as i32
+            })
+            .map(|(col, row)| (col as usize, row as usize))
+            .collect()
+    };
+    for (new_col, new_row) in targets {
         match board.get_piece_at_space(new_col, new_row) {
             Some(p) => {
                 if p.get_color() != color {
@@ -108,7 +111,8 @@ mod tests {
             (3, 3),
             (4, 3),
         ]
-        .map(|(new_col, new_row)| {
+        .into_iter()
+        .for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (3, 4),
                 new_position: (new_col, new_row),
@@ -129,16 +133,18 @@ mod tests {
         let moves = white_king.possible_moves((0, 0), board, None);
         assert_eq!(3, moves.len());
 
-        [(0, 1), (1, 1), (1, 0)].map(|(new_col, new_row)| {
-            let expected_move = Move {
-                original_position: (0, 0),
-                new_position: (new_col, new_row),
-                piece: ChessPiece::new(King, White),
-                taken_piece: None,
-                promotion: None,
-            };
-            assert!(moves.contains(&expected_move));
-        });
+        [(0, 1), (1, 1), (1, 0)]
+            .into_iter()
+            .for_each(|(new_col, new_row)| {
+                let expected_move = Move {
+                    original_position: (0, 0),
+                    new_position: (new_col, new_row),
+                    piece: ChessPiece::new(King, White),
+                    taken_piece: None,
+                    promotion: None,
+                };
+                assert!(moves.contains(&expected_move));
+            });
     }
 
     #[test]
@@ -170,7 +176,8 @@ mod tests {
             (3, 3),
             (4, 3),
         ]
-        .map(|(new_col, new_row)| {
+        .into_iter()
+        .for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (3, 4),
                 new_position: (new_col, new_row),
@@ -1,4 +1,5 @@
 use crate::chess_move::ChessMoveType;
+use crate::engine::attack_tables::{get_squares, KNIGHT_ATTACKS};
 use crate::piece::{ChessPiece, PieceType};
 use crate::Color;
 use game_board::Board;
@@ -42,30 +43,39 @@ pub fn possible_moves(
         (-2, -1),
     ];
 
-    for mv in moves.iter() {
-        let x = position.0 as i32 + mv.0;
-        let y = position.1 as i32 + mv.1;
-
-        if x >= 0 && x < board.get_width() as i32 && y >= 0 && y < board.get_height() as i32 {
-            if let Some(piece) = board.get_piece_at_space(x as usize, y as usize) {
-                if piece.get_color() != color {
-                    possible_moves.push(ChessMoveType::Move {
-                        original_position: position,
-                        new_position: (x as usize, y as usize),
-                        piece: ChessPiece::new(PieceType::Knight, color),
-                        taken_piece: Some(*piece),
-                        promotion: None,
-                    });
-                }
-            } else {
+    // the squares of a standard board are looked up instead of computed
+    let targets: Vec<(usize, usize)> = if board.get_width() == 8 && board.get_height() == 8 {
+        get_squares(KNIGHT_ATTACKS[position.0 + position.1 * 8]).collect()
+    } else {
+        moves
+            .iter()
+            .map(|mv| (position.0 as i32 + mv.0, position.1 as i32 + mv.1))
+            .filter(|&(x, y)| {
+                x >= 0 && x < board.get_width() as i32 && y >= 0 && y < board.get_height() as i32
+            })
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect()
+    };
+
+    for new_position in targets {
+        if let Some(piece) = board.get_piece_at_space(new_position.0, new_position.1) {
+            if piece.get_color() != color {
                 possible_moves.push(ChessMoveType::Move {
                     original_position: position,
-                    new_position: (x as usize, y as usize),
+                    new_position,
                     piece: ChessPiece::new(PieceType::Knight, color),
-                    taken_piece: None,
+                    taken_piece: Some(*piece),
                     promotion: None,
                 });
             }
+        } else {
+            possible_moves.push(ChessMoveType::Move {
+                original_position: position,
+                new_position,
+                piece: ChessPiece::new(PieceType::Knight, color),
+                taken_piece: None,
+                promotion: None,
+            });
         }
     }
 
@@ -99,7 +109,8 @@ mod tests {
             (5, 5),
             (6, 4),
         ]
-        .map(|(new_col, new_row)| {
+        .into_iter()
+        .for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (4, 3),
                 new_position: (new_col, new_row),
@@ -120,7 +131,7 @@ mod tests {
         let moves = black_knight.possible_moves((0, 0), board, None);
         assert_eq!(2, moves.len());
 
-        [(1, 2), (2, 1)].map(|(new_col, new_row)| {
+        [(1, 2), (2, 1)].into_iter().for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (0, 0),
                 new_position: (new_col, new_row),
@@ -162,7 +173,8 @@ mod tests {
             (3, 5, Knight),
             (2, 4, Bishop),
         ]
-        .map(|(new_col, new_row, taken_piece)| {
+        .into_iter()
+        .for_each(|(new_col, new_row, taken_piece)| {
             let expected_move = Move {
                 original_position: (4, 3),
                 new_position: (new_col, new_row),
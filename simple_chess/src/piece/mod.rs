@@ -1,16 +1,19 @@
 use crate::chess_move::ChessMoveType;
+use crate::ruleset::Ruleset;
 use crate::Color;
 use game_board::Board;
 use std::fmt::{Display, Formatter};
 
+mod archbishop;
 mod bishop;
+mod chancellor;
 mod king;
 mod knight;
 mod pawn;
 mod queen;
 mod rook;
 
-#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
 pub enum PieceType {
     Pawn,
     Rook,
@@ -18,13 +21,17 @@ pub enum PieceType {
     Bishop,
     Queen,
     King,
+    /// Moves like a bishop or a knight, used in Capablanca chess.
+    Archbishop,
+    /// Moves like a rook or a knight, used in Capablanca chess.
+    Chancellor,
 }
 
 /// ChessPiece represents a single simple_chess piece on the board.
 ///
 /// Each ChessPiece has a specific type (Pawn, Rook, Knight, Bishop, Queen, King)
 /// and a color (White or Black).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ChessPiece {
     piece_type: PieceType,
     color: Color,
@@ -109,6 +116,8 @@ impl ChessPiece {
             PieceType::Bishop => bishop::as_utf_str(self.color),
             PieceType::Knight => knight::as_utf_str(self.color),
             PieceType::Pawn => pawn::as_utf_str(self.color),
+            PieceType::Archbishop => archbishop::as_utf_str(self.color),
+            PieceType::Chancellor => chancellor::as_utf_str(self.color),
         }
     }
 
@@ -135,6 +144,8 @@ impl ChessPiece {
             PieceType::Bishop => bishop::as_fen_char(self.color),
             PieceType::Knight => knight::as_fen_char(self.color),
             PieceType::Pawn => pawn::as_fen_char(self.color),
+            PieceType::Archbishop => archbishop::as_fen_char(self.color),
+            PieceType::Chancellor => chancellor::as_fen_char(self.color),
         }
     }
 
@@ -146,6 +157,8 @@ impl ChessPiece {
             PieceType::Bishop => bishop::as_binary(self.color),
             PieceType::Queen => queen::as_binary(self.color),
             PieceType::King => king::as_binary(self.color),
+            PieceType::Archbishop => archbishop::as_binary(self.color),
+            PieceType::Chancellor => chancellor::as_binary(self.color),
         }
     }
 
@@ -161,9 +174,11 @@ impl ChessPiece {
     /// # Arguments
     ///
     /// * `position` - A tuple `(usize, usize)` representing the current position of the simple_chess piece
-    ///                on the board (row, column).
+    ///   on the board (row, column).
     /// * `board` - A reference to the `Board<ChessPiece>` which represents the current state of the
-    ///             simple_chess board, including all pieces and their positions.
+    ///   simple_chess board, including all pieces and their positions.
+    /// * `en_passant_target` - The square a pawn passed over with a double step on the last
+    ///   move, see `ChessGame::get_en_passant_target`.
     ///
     /// # Returns
     ///
@@ -186,7 +201,37 @@ impl ChessPiece {
         &self,
         position: (usize, usize),
         board: &Board<ChessPiece>,
-        last_move: Option<&ChessMoveType>,
+        en_passant_target: Option<(usize, usize)>,
+    ) -> Vec<ChessMoveType> {
+        self.possible_moves_with_ruleset(position, board, en_passant_target, &Ruleset::default())
+    }
+
+    /// Returns a vector of possible moves for the simple_chess piece from a given position on the
+    /// board, using the given `Ruleset` rather than the standard rules.
+    ///
+    /// This is useful for custom board heights or handicap setups where pawns do not start
+    /// or promote on the standard ranks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::piece::{ChessPiece, PieceType};
+    /// use simple_chess::ruleset::{PawnRules, Ruleset};
+    /// use simple_chess::Color;
+    /// use game_board::Board;
+    ///
+    /// let white_pawn = ChessPiece::new(PieceType::Pawn, Color::White);
+    /// let board = Board::build(8, 8).unwrap();
+    /// let ruleset = Ruleset::new().set_pawn_rules(PawnRules::new().set_can_double_step(false));
+    /// let moves = white_pawn.possible_moves_with_ruleset((0, 1), &board, None, &ruleset);
+    /// assert_eq!(1, moves.len());
+    /// ```
+    pub fn possible_moves_with_ruleset(
+        &self,
+        position: (usize, usize),
+        board: &Board<ChessPiece>,
+        en_passant_target: Option<(usize, usize)>,
+        ruleset: &Ruleset,
     ) -> Vec<ChessMoveType> {
         match self.piece_type {
             PieceType::King => king::possible_moves(self.color, position, board),
@@ -194,7 +239,15 @@ impl ChessPiece {
             PieceType::Rook => rook::possible_moves(self.color, position, board),
             PieceType::Bishop => bishop::possible_moves(self.color, position, board),
             PieceType::Knight => knight::possible_moves(self.color, position, board),
-            PieceType::Pawn => pawn::possible_moves(self.color, position, board, last_move),
+            PieceType::Archbishop => archbishop::possible_moves(self.color, position, board),
+            PieceType::Chancellor => chancellor::possible_moves(self.color, position, board),
+            PieceType::Pawn => pawn::possible_moves(
+                self.color,
+                position,
+                board,
+                en_passant_target,
+                ruleset.get_pawn_rules(),
+            ),
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::chess_move::ChessMoveType;
 use crate::piece::{ChessPiece, PieceType};
+use crate::ruleset::PawnRules;
 use crate::Color;
 use game_board::Board;
 
@@ -35,22 +36,17 @@ pub fn possible_moves(
     color: Color,
     position: (usize, usize),
     board: &Board<ChessPiece>,
-    last_move_type: Option<&ChessMoveType>,
+    en_passant_target: Option<(usize, usize)>,
+    pawn_rules: &PawnRules,
 ) -> Vec<ChessMoveType> {
     let mut possible_moves: Vec<ChessMoveType> = Vec::new();
 
-    let forward_direction = match color {
-        Color::White => 1,
-        Color::Black => -1,
-    };
+    let forward_direction = color.forward();
 
-    let promotion_row = match color {
-        Color::White => board.get_height() - 1,
-        Color::Black => 0,
-    };
+    let promotion_row = pawn_rules.get_promotion_row(color, board.get_height());
 
     let next_row = position.1 as i32 + forward_direction;
-    if next_row < 0 || next_row > board.get_height() as i32 {
+    if next_row < 0 || next_row >= board.get_height() as i32 {
         return possible_moves;
     }
 
@@ -68,25 +64,25 @@ pub fn possible_moves(
         ));
 
         // moving 2 spaces from starting row
-        let starting_row = match color {
-            Color::White => 1,
-            Color::Black => board.get_height() - 2,
-        };
-        if position.1 == starting_row {
+        let starting_row = pawn_rules.get_starting_row(color, board.get_height());
+        if pawn_rules.can_double_step()
+            && position.1 == starting_row
+            && next_row as usize != promotion_row
+        {
             let double_next_row = position.1 as i32 + 2 * forward_direction;
-            if double_next_row >= 0 && double_next_row < board.get_height() as i32 {
-                if board
+            if double_next_row >= 0
+                && double_next_row < board.get_height() as i32
+                && board
                     .get_piece_at_space(position.0, double_next_row as usize)
                     .is_none()
-                {
-                    possible_moves.append(&mut create_possible_moves(
-                        position,
-                        (position.0, double_next_row as usize),
-                        color,
-                        None,
-                        next_row as usize == promotion_row,
-                    ));
-                }
+            {
+                possible_moves.append(&mut create_possible_moves(
+                    position,
+                    (position.0, double_next_row as usize),
+                    color,
+                    None,
+                    double_next_row as usize == promotion_row,
+                ));
             }
         }
     }
@@ -106,33 +102,20 @@ pub fn possible_moves(
         }
 
         // En Passant
-        if let Some(ChessMoveType::Move {
-            piece,
-            new_position,
-            original_position,
-            ..
-        }) = last_move_type
-        {
-            if piece.piece_type == PieceType::Pawn && piece.color != color {
-                let rows_moved = if original_position.1 < new_position.1 {
-                    new_position.1 - original_position.1
-                } else {
-                    original_position.1 - new_position.1
-                };
-
-                if rows_moved == 2
-                    && new_position.0 == position.0 - 1
-                    && new_position.1 == position.1
-                {
-                    possible_moves.append(&mut create_possible_en_passant(
-                        position,
-                        (position.0 - 1, next_row as usize),
-                        color,
-                        *piece,
-                        *new_position,
-                        next_row as usize == promotion_row,
-                    ));
-                }
+        let target = (position.0 - 1, next_row as usize);
+        if en_passant_target == Some(target) {
+            if let Some(piece) = board
+                .get_piece_at_space(target.0, position.1)
+                .filter(|piece| piece.piece_type == PieceType::Pawn && piece.color != color)
+            {
+                possible_moves.append(&mut create_possible_en_passant(
+                    position,
+                    target,
+                    color,
+                    *piece,
+                    (target.0, position.1),
+                    next_row as usize == promotion_row,
+                ));
             }
         }
     }
@@ -152,33 +135,20 @@ pub fn possible_moves(
         }
 
         // En Passant
-        if let Some(ChessMoveType::Move {
-            piece,
-            new_position,
-            original_position,
-            ..
-        }) = last_move_type
-        {
-            if piece.piece_type == PieceType::Pawn && piece.color != color {
-                let rows_moved = if original_position.1 < new_position.1 {
-                    new_position.1 - original_position.1
-                } else {
-                    original_position.1 - new_position.1
-                };
-
-                if rows_moved == 2
-                    && new_position.0 == position.0 + 1
-                    && new_position.1 == position.1
-                {
-                    possible_moves.append(&mut create_possible_en_passant(
-                        position,
-                        (position.0 + 1, next_row as usize),
-                        color,
-                        *piece,
-                        *new_position,
-                        next_row as usize == promotion_row,
-                    ));
-                }
+        let target = (position.0 + 1, next_row as usize);
+        if en_passant_target == Some(target) {
+            if let Some(piece) = board
+                .get_piece_at_space(target.0, position.1)
+                .filter(|piece| piece.piece_type == PieceType::Pawn && piece.color != color)
+            {
+                possible_moves.append(&mut create_possible_en_passant(
+                    position,
+                    target,
+                    color,
+                    *piece,
+                    (target.0, position.1),
+                    next_row as usize == promotion_row,
+                ));
             }
         }
     }
@@ -257,6 +227,7 @@ mod tests {
     use super::*;
     use crate::codec::forsyth_edwards_notation::build_game_from_string;
     use crate::piece::PieceType::{Bishop, King, Knight, Pawn, Queen, Rook};
+    use crate::ruleset::Ruleset;
     use crate::ChessMoveType::{EnPassant, Move};
     use crate::Color::{Black, White};
 
@@ -319,7 +290,7 @@ mod tests {
         let moves = white_pawn.possible_moves((4, 1), board, None);
         assert_eq!(2, moves.len());
 
-        [(4, 2), (4, 3)].map(|(new_col, new_row)| {
+        [(4, 2), (4, 3)].into_iter().for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (4, 1),
                 new_position: (new_col, new_row),
@@ -341,7 +312,7 @@ mod tests {
         let moves = black_pawn.possible_moves((2, 6), board, None);
         assert_eq!(2, moves.len());
 
-        [(2, 5), (2, 4)].map(|(new_col, new_row)| {
+        [(2, 5), (2, 4)].into_iter().for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (2, 6),
                 new_position: (new_col, new_row),
@@ -363,7 +334,7 @@ mod tests {
         let moves = white_pawn.possible_moves((4, 1), board, None);
         assert_eq!(1, moves.len());
 
-        [(4, 2)].map(|(new_col, new_row)| {
+        [(4, 2)].into_iter().for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (4, 1),
                 new_position: (new_col, new_row),
@@ -385,7 +356,7 @@ mod tests {
         let moves = black_pawn.possible_moves((2, 6), board, None);
         assert_eq!(1, moves.len());
 
-        [(2, 5)].map(|(new_col, new_row)| {
+        [(2, 5)].into_iter().for_each(|(new_col, new_row)| {
             let expected_move = Move {
                 original_position: (2, 6),
                 new_position: (new_col, new_row),
@@ -407,12 +378,10 @@ mod tests {
         let moves = white_pawn.possible_moves((3, 3), board, None);
         assert_eq!(3, moves.len());
 
-        [(2, 4, Some(Knight)), (4, 4, Some(Queen)), (3, 4, None)].map(
-            |(new_col, new_row, taken_piece)| {
-                let taken_piece = match taken_piece {
-                    Some(p) => Some(ChessPiece::new(p, Black)),
-                    None => None,
-                };
+        [(2, 4, Some(Knight)), (4, 4, Some(Queen)), (3, 4, None)]
+            .into_iter()
+            .for_each(|(new_col, new_row, taken_piece)| {
+                let taken_piece = taken_piece.map(|p| ChessPiece::new(p, Black));
                 let expected_move = Move {
                     original_position: (3, 3),
                     new_position: (new_col, new_row),
@@ -422,8 +391,7 @@ mod tests {
                 };
 
                 assert!(moves.contains(&expected_move));
-            },
-        );
+            });
     }
 
     #[test]
@@ -435,12 +403,10 @@ mod tests {
         let moves = black_pawn.possible_moves((3, 4), board, None);
         assert_eq!(3, moves.len());
 
-        [(2, 3, Some(King)), (4, 3, Some(Bishop)), (3, 3, None)].map(
-            |(new_col, new_row, taken_piece)| {
-                let taken_piece = match taken_piece {
-                    Some(p) => Some(ChessPiece::new(p, White)),
-                    None => None,
-                };
+        [(2, 3, Some(King)), (4, 3, Some(Bishop)), (3, 3, None)]
+            .into_iter()
+            .for_each(|(new_col, new_row, taken_piece)| {
+                let taken_piece = taken_piece.map(|p| ChessPiece::new(p, White));
                 let expected_move = Move {
                     original_position: (3, 4),
                     new_position: (new_col, new_row),
@@ -450,8 +416,7 @@ mod tests {
                 };
 
                 assert!(moves.contains(&expected_move));
-            },
-        );
+            });
     }
 
     #[test]
@@ -460,7 +425,7 @@ mod tests {
         let game = build_game_from_string("8/8/4N3/4Pp2/8/8/8/8 w - f6 0 1").unwrap();
         let board = game.get_board();
 
-        let moves = white_pawn.possible_moves((4, 4), board, game.get_last_move());
+        let moves = white_pawn.possible_moves((4, 4), board, game.get_en_passant_target());
         assert_eq!(1, moves.len());
 
         let expected_move = EnPassant {
@@ -480,7 +445,7 @@ mod tests {
         let game = build_game_from_string("8/8/8/8/Pp6/1n6/8/8 w - a3 0 1").unwrap();
         let board = game.get_board();
 
-        let moves = black_pawn.possible_moves((1, 3), board, game.get_last_move());
+        let moves = black_pawn.possible_moves((1, 3), board, game.get_en_passant_target());
         assert_eq!(1, moves.len());
 
         let expected_move = EnPassant {
@@ -500,20 +465,22 @@ mod tests {
         let game = build_game_from_string("8/P7/8/8/8/8/8/8 w - - 0 1").unwrap();
         let board = game.get_board();
 
-        let moves = white_pawn.possible_moves((0, 6), board, game.get_last_move());
+        let moves = white_pawn.possible_moves((0, 6), board, game.get_en_passant_target());
 
         assert_eq!(4, moves.len());
 
-        [Queen, Rook, Bishop, Knight].map(|promotion_option| {
-            let expected_move = Move {
-                original_position: (0, 6),
-                new_position: (0, 7),
-                piece: ChessPiece::new(Pawn, White),
-                taken_piece: None,
-                promotion: Some(ChessPiece::new(promotion_option, White)),
-            };
-            assert!(moves.contains(&expected_move));
-        });
+        [Queen, Rook, Bishop, Knight]
+            .into_iter()
+            .for_each(|promotion_option| {
+                let expected_move = Move {
+                    original_position: (0, 6),
+                    new_position: (0, 7),
+                    piece: ChessPiece::new(Pawn, White),
+                    taken_piece: None,
+                    promotion: Some(ChessPiece::new(promotion_option, White)),
+                };
+                assert!(moves.contains(&expected_move));
+            });
     }
 
     #[test]
@@ -522,19 +489,84 @@ mod tests {
         let game = build_game_from_string("8/8/8/8/8/8/6p1/8 w - - 0 1").unwrap();
         let board = game.get_board();
 
-        let moves = black_pawn.possible_moves((6, 1), board, game.get_last_move());
+        let moves = black_pawn.possible_moves((6, 1), board, game.get_en_passant_target());
 
         assert_eq!(4, moves.len());
 
-        [Queen, Rook, Bishop, Knight].map(|promotion_option| {
-            let expected_move = Move {
-                original_position: (6, 1),
-                new_position: (6, 0),
-                piece: ChessPiece::new(Pawn, Black),
-                taken_piece: None,
-                promotion: Some(ChessPiece::new(promotion_option, Black)),
-            };
-            assert!(moves.contains(&expected_move));
-        });
+        [Queen, Rook, Bishop, Knight]
+            .into_iter()
+            .for_each(|promotion_option| {
+                let expected_move = Move {
+                    original_position: (6, 1),
+                    new_position: (6, 0),
+                    piece: ChessPiece::new(Pawn, Black),
+                    taken_piece: None,
+                    promotion: Some(ChessPiece::new(promotion_option, Black)),
+                };
+                assert!(moves.contains(&expected_move));
+            });
+    }
+
+    #[test]
+    fn pawn_can_not_double_step_when_rules_disallow_it() {
+        let white_pawn = ChessPiece::new(Pawn, White);
+        let game = build_game_from_string("8/8/8/8/8/8/4P3/8 w - - 0 1").unwrap();
+        let board = game.get_board();
+        let ruleset = Ruleset::new().set_pawn_rules(PawnRules::new().set_can_double_step(false));
+
+        let moves = white_pawn.possible_moves_with_ruleset((4, 1), board, None, &ruleset);
+        assert_eq!(1, moves.len());
+    }
+
+    #[test]
+    fn pawn_double_steps_from_configured_starting_rank() {
+        let black_pawn = ChessPiece::new(Pawn, Black);
+        let board = Board::build(8, 12).unwrap();
+        let ruleset = Ruleset::new().set_pawn_rules(PawnRules::new().set_starting_rank_offset(2));
+
+        let moves = black_pawn.possible_moves_with_ruleset((3, 9), &board, None, &ruleset);
+        assert_eq!(2, moves.len());
+        assert!(moves.contains(&Move {
+            original_position: (3, 9),
+            new_position: (3, 7),
+            piece: ChessPiece::new(Pawn, Black),
+            taken_piece: None,
+            promotion: None,
+        }));
+
+        let moves = black_pawn.possible_moves_with_ruleset((3, 10), &board, None, &ruleset);
+        assert_eq!(1, moves.len());
+    }
+
+    #[test]
+    fn pawn_promotes_on_configured_rank() {
+        let white_pawn = ChessPiece::new(Pawn, White);
+        let game = build_game_from_string("8/8/8/P7/8/8/8/8 w - - 0 1").unwrap();
+        let board = game.get_board();
+        let ruleset = Ruleset::new().set_pawn_rules(PawnRules::new().set_promotion_rank_offset(1));
+
+        let moves = white_pawn.possible_moves_with_ruleset((0, 5), board, None, &ruleset);
+        assert_eq!(4, moves.len());
+        [Queen, Rook, Bishop, Knight]
+            .into_iter()
+            .for_each(|promotion_option| {
+                assert!(moves.contains(&Move {
+                    original_position: (0, 5),
+                    new_position: (0, 6),
+                    piece: ChessPiece::new(Pawn, White),
+                    taken_piece: None,
+                    promotion: Some(ChessPiece::new(promotion_option, White)),
+                }));
+            });
+    }
+
+    #[test]
+    fn pawn_on_last_row_has_no_moves() {
+        let white_pawn = ChessPiece::new(Pawn, White);
+        let game = build_game_from_string("P7/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        let board = game.get_board();
+
+        let moves = white_pawn.possible_moves((0, 7), board, None);
+        assert_eq!(0, moves.len());
     }
 }
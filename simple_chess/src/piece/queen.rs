@@ -112,10 +112,11 @@ mod tests {
             (1, 6),
             (0, 7),
         ]
-        .map(|new_position| {
+        .into_iter()
+        .for_each(|new_position| {
             let expected_move = Move {
                 original_position: (5, 2),
-                new_position: new_position,
+                new_position,
                 piece: ChessPiece::new(Queen, White),
                 taken_piece: None,
                 promotion: None,
@@ -133,16 +134,18 @@ mod tests {
         let moves = white_queen.possible_moves((5, 2), board, None);
         assert_eq!(5, moves.len());
 
-        [(3, 4), (4, 3), (4, 2), (6, 2), (5, 3)].map(|new_position| {
-            let expected_move = Move {
-                original_position: (5, 2),
-                new_position: new_position,
-                piece: ChessPiece::new(Queen, White),
-                taken_piece: None,
-                promotion: None,
-            };
-            assert!(moves.contains(&expected_move));
-        });
+        [(3, 4), (4, 3), (4, 2), (6, 2), (5, 3)]
+            .into_iter()
+            .for_each(|new_position| {
+                let expected_move = Move {
+                    original_position: (5, 2),
+                    new_position,
+                    piece: ChessPiece::new(Queen, White),
+                    taken_piece: None,
+                    promotion: None,
+                };
+                assert!(moves.contains(&expected_move));
+            });
     }
 
     #[test]
@@ -169,11 +172,9 @@ mod tests {
             (6, 3, Some(Knight)),
             (5, 4, Some(Queen)),
         ]
-        .map(|(new_col, new_row, taken_piece)| {
-            let taken_piece = match taken_piece {
-                Some(piece) => Some(ChessPiece::new(piece, Black)),
-                None => None,
-            };
+        .into_iter()
+        .for_each(|(new_col, new_row, taken_piece)| {
+            let taken_piece = taken_piece.map(|piece| ChessPiece::new(piece, Black));
             let expected_move = Move {
                 original_position: (5, 2),
                 new_position: (new_col, new_row),
@@ -97,7 +97,8 @@ mod tests {
             (1, 5),
             (0, 5),
         ]
-        .map(|new_position| {
+        .into_iter()
+        .for_each(|new_position| {
             let expected_move = Move {
                 original_position: (6, 5),
                 new_position,
@@ -118,16 +119,18 @@ mod tests {
         let moves = black_rook.possible_moves((6, 5), board, None);
         assert_eq!(6, moves.len());
 
-        [(6, 6), (6, 4), (6, 3), (6, 2), (5, 5), (4, 5)].map(|new_position| {
-            let expected_move = Move {
-                original_position: (6, 5),
-                new_position,
-                piece: ChessPiece::new(Rook, Black),
-                taken_piece: None,
-                promotion: None,
-            };
-            assert!(moves.contains(&expected_move));
-        });
+        [(6, 6), (6, 4), (6, 3), (6, 2), (5, 5), (4, 5)]
+            .into_iter()
+            .for_each(|new_position| {
+                let expected_move = Move {
+                    original_position: (6, 5),
+                    new_position,
+                    piece: ChessPiece::new(Rook, Black),
+                    taken_piece: None,
+                    promotion: None,
+                };
+                assert!(moves.contains(&expected_move));
+            });
     }
 
     #[test]
@@ -151,11 +154,9 @@ mod tests {
             ((7, 5), Some(Pawn)),
             ((6, 1), Some(Bishop)),
         ]
-        .map(|(new_position, taken_piece)| {
-            let taken_piece = match taken_piece {
-                Some(piece) => Some(ChessPiece::new(piece, White)),
-                None => None,
-            };
+        .into_iter()
+        .for_each(|(new_position, taken_piece)| {
+            let taken_piece = taken_piece.map(|piece| ChessPiece::new(piece, White));
             let expected_move = Move {
                 original_position: (6, 5),
                 new_position,
@@ -0,0 +1,265 @@
+use crate::error::ChessError;
+use crate::piece::{ChessPiece, PieceType};
+use crate::square_name::SquareName;
+use crate::Color;
+use game_board::{get_square_name_from_row_and_col, Board};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// A square on a board of any size, given by its column and row.
+///
+/// `(0, 0)` is White's bottom left corner. Unlike `SquareName`, a position is not limited
+/// to an 8x8 board: columns past `z` are named `aa`, `ab` and so on, like the squares of
+/// `game_board`. Positions convert to and from the `(col, row)` tuples used by
+/// `ChessMoveType` and `Board`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::position::Position;
+///
+/// let e4: Position = "e4".parse().unwrap();
+/// assert_eq!(Position::new(4, 3), e4);
+/// assert_eq!((4, 3), e4.into());
+/// assert_eq!("e4", e4.to_string());
+///
+/// assert_eq!(Some(Position::new(5, 5)), e4.offset((1, 2)));
+/// assert_eq!(None, e4.offset((-5, 0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    col: usize,
+    row: usize,
+}
+
+impl Position {
+    /// Creates a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column of the square, `0` is the a-file.
+    /// * `row` - The row of the square, `0` is the first rank.
+    pub fn new(col: usize, row: usize) -> Self {
+        Self { col, row }
+    }
+
+    /// Returns the column of the square, `0` is the a-file.
+    pub fn get_col(&self) -> usize {
+        self.col
+    }
+
+    /// Returns the row of the square, `0` is the first rank.
+    pub fn get_row(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the `(col, row)` coordinates of the square.
+    pub fn get_col_and_row(&self) -> (usize, usize) {
+        (self.col, self.row)
+    }
+
+    /// Returns the position moved by a number of columns and rows, `None` if it would be
+    /// left of the a-file or below the first rank.
+    ///
+    /// The board size is not known to a position, so the result may be past the right or
+    /// top edge of the board.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The columns and rows to move by, such as `(1, 2)` for a knight.
+    pub fn offset(&self, offset: (i32, i32)) -> Option<Position> {
+        let col = self.col.checked_add_signed(offset.0 as isize)?;
+        let row = self.row.checked_add_signed(offset.1 as isize)?;
+        Some(Position::new(col, row))
+    }
+
+    /// Returns the squares from this square to the edge of a board in a direction, not
+    /// including this square.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The step between squares, such as `(1, 1)` for a diagonal.
+    /// * `width` - The width of the board.
+    /// * `height` - The height of the board.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::position::Position;
+    ///
+    /// let ray: Vec<String> = Position::new(5, 5)
+    ///     .ray((1, 1), 8, 8)
+    ///     .map(|position| position.to_string())
+    ///     .collect();
+    /// assert_eq!(vec!["g7", "h8"], ray);
+    /// ```
+    pub fn ray(
+        self,
+        direction: (i32, i32),
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = Position> {
+        // a ray without a direction would never leave the square
+        let mut current = (direction != (0, 0)).then_some(self);
+        std::iter::from_fn(move || {
+            current = current?
+                .offset(direction)
+                .filter(|next| next.col < width && next.row < height);
+            current
+        })
+    }
+}
+
+/// Returns a square moved by a number of columns and rows, `None` if it would be off the
+/// board.
+pub(crate) fn offset_square(
+    board: &Board<ChessPiece>,
+    square: (usize, usize),
+    offset: (i32, i32),
+) -> Option<(usize, usize)> {
+    Position::from(square)
+        .offset(offset)
+        .filter(|position| position.col < board.get_width() && position.row < board.get_height())
+        .map(|position| position.get_col_and_row())
+}
+
+/// Returns the square of a player's king, `None` if they have none.
+pub(crate) fn find_king(board: &Board<ChessPiece>, color: Color) -> Option<(usize, usize)> {
+    let king = ChessPiece::new(PieceType::King, color);
+    (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| (col, row)))
+        .find(|&(col, row)| board.get_piece_at_space(col, row) == Some(&king))
+}
+
+impl From<(usize, usize)> for Position {
+    fn from((col, row): (usize, usize)) -> Self {
+        Position::new(col, row)
+    }
+}
+
+impl From<Position> for (usize, usize) {
+    fn from(position: Position) -> Self {
+        position.get_col_and_row()
+    }
+}
+
+impl From<SquareName> for Position {
+    fn from(square: SquareName) -> Self {
+        Position::from(square.get_col_and_row())
+    }
+}
+
+impl FromStr for Position {
+    type Err = PositionError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let error = || PositionError {
+            name: name.to_string(),
+        };
+
+        let split = name
+            .find(|c: char| !c.is_ascii_lowercase())
+            .ok_or_else(error)?;
+        let (file, rank) = name.split_at(split);
+        if file.is_empty() || !rank.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        // files are numbered like spreadsheet columns, so `z` is followed by `aa`
+        let col = file.bytes().try_fold(0usize, |col, b| {
+            col.checked_mul(26)?.checked_add((b - b'a') as usize + 1)
+        });
+        let row = rank.parse::<usize>().ok().filter(|&rank| rank > 0);
+        match (col, row) {
+            (Some(col), Some(row)) => Ok(Position::new(col - 1, row - 1)),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl TryFrom<&str> for Position {
+    type Error = PositionError;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            get_square_name_from_row_and_col(self.col, self.row)
+        )
+    }
+}
+
+/// Error returned when a string is not the name of a square.
+pub struct PositionError {
+    name: String,
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not the name of a square", self.name)
+    }
+}
+
+impl Debug for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Error for PositionError {}
+
+impl From<PositionError> for ChessError {
+    fn from(error: PositionError) -> Self {
+        ChessError::ParseError(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_match_the_board_coordinates() {
+        for (col, row) in [(0, 0), (7, 7), (25, 1), (26, 0), (27, 99)] {
+            let position = Position::new(col, row);
+            assert_eq!(
+                get_square_name_from_row_and_col(col, row),
+                position.to_string()
+            );
+            assert_eq!(position, position.to_string().parse().unwrap());
+        }
+        for square in SquareName::all() {
+            assert_eq!(square.to_string(), Position::from(*square).to_string());
+        }
+    }
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        for name in ["", "e", "4", "e0", "E4", "4e", "e4e", "e-1"] {
+            assert!(Position::try_from(name).is_err(), "{} parsed", name);
+        }
+        assert_eq!(
+            "'e0' is not the name of a square",
+            "e0".parse::<Position>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn rays_stop_at_the_edge_of_the_board() {
+        let a1 = Position::new(0, 0);
+        assert_eq!(9, a1.ray((1, 1), 10, 10).count());
+        assert_eq!(0, a1.ray((-1, 0), 10, 10).count());
+        assert_eq!(0, a1.ray((0, 0), 10, 10).count());
+        assert_eq!(
+            vec![Position::new(0, 1), Position::new(0, 2)],
+            a1.ray((0, 1), 8, 3).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A counted event of move generation or search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Counter {
+    Nodes,
+    Clones,
+    CacheHits,
+    CacheMisses,
+}
+
+static NODES: AtomicU64 = AtomicU64::new(0);
+static CLONES: AtomicU64 = AtomicU64::new(0);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+impl Counter {
+    fn get_atomic(self) -> &'static AtomicU64 {
+        match self {
+            Counter::Nodes => &NODES,
+            Counter::Clones => &CLONES,
+            Counter::CacheHits => &CACHE_HITS,
+            Counter::CacheMisses => &CACHE_MISSES,
+        }
+    }
+}
+
+/// Adds to one of the counters.
+pub(crate) fn increment(counter: Counter, amount: u64) {
+    counter.get_atomic().fetch_add(amount, Ordering::Relaxed);
+}
+
+/// The counters collected across every game and engine since the last reset.
+///
+/// The counters are shared by all threads. They are meant to be read around the code being
+/// profiled, together with the `tracing` spans simple_chess enters for move generation
+/// (`legal_moves`), legality filtering (`legality_filter`) and search (`search` and
+/// `search_depth`).
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::engine::Engine;
+/// use simple_chess::profiling::{get_counters, reset_counters};
+/// use simple_chess::ChessGame;
+///
+/// reset_counters();
+/// Engine::new().search(&ChessGame::new(), 2);
+/// let counters = get_counters();
+///
+/// assert!(counters.get_nodes() > 0);
+/// assert!(counters.get_clones() > 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProfilingCounters {
+    nodes: u64,
+    clones: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+impl ProfilingCounters {
+    /// Returns the number of positions visited by the engine's search.
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Returns the number of games and boards copied by search and legality filtering.
+    pub fn get_clones(&self) -> u64 {
+        self.clones
+    }
+
+    /// Returns the number of times legal moves, game states or transposition table
+    /// entries were found already computed.
+    pub fn get_cache_hits(&self) -> u64 {
+        self.cache_hits
+    }
+
+    /// Returns the number of times legal moves, game states or transposition table
+    /// entries had to be computed.
+    pub fn get_cache_misses(&self) -> u64 {
+        self.cache_misses
+    }
+}
+
+/// Returns the counters collected since the last call to `reset_counters`.
+pub fn get_counters() -> ProfilingCounters {
+    ProfilingCounters {
+        nodes: NODES.load(Ordering::Relaxed),
+        clones: CLONES.load(Ordering::Relaxed),
+        cache_hits: CACHE_HITS.load(Ordering::Relaxed),
+        cache_misses: CACHE_MISSES.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets every counter back to zero.
+pub fn reset_counters() {
+    for counter in [
+        Counter::Nodes,
+        Counter::Clones,
+        Counter::CacheHits,
+        Counter::CacheMisses,
+    ] {
+        counter.get_atomic().store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChessGame;
+
+    #[test]
+    fn cached_legal_moves_count_as_hits() {
+        let before = get_counters();
+        let mut game = ChessGame::new();
+        game.legal_moves();
+        game.legal_moves();
+        let after = get_counters();
+
+        // other tests share the counters, so only a lower bound can be checked
+        assert!(after.get_cache_misses() > before.get_cache_misses());
+        assert!(after.get_cache_hits() > before.get_cache_hits());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::analysis::find_mate;
+use crate::analysis::tactics::{absolute_pins, forks, hanging_pieces, skewers};
+use crate::codec::forsyth_edwards_notation::{build_game_from_string, encode_game_as_string};
+use crate::engine::Engine;
+use crate::{ChessError, ChessGame, ChessMoveType};
+use std::fmt::{Display, Formatter};
+
+/// The longest forced mate, in moves of the attacking player, looked for in a position.
+const MATE_DEPTH: usize = 3;
+
+/// How many centipawns the best move has to be ahead of every other move, and ahead of
+/// equality, for a position to be a puzzle.
+const MIN_SWING: i32 = 200;
+
+/// The tactical idea behind a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleTheme {
+    /// The solution is a forced checkmate in the given number of moves.
+    Mate { moves: usize },
+    /// The first move attacks two or more pieces at once.
+    Fork,
+    /// The first move attacks a piece with a less valuable piece behind it.
+    Skewer,
+    /// The first move pins a piece against its king.
+    Pin,
+    /// The first move takes a piece that was not defended.
+    HangingPiece,
+    /// The first move is the only one that wins material or a decisive advantage.
+    Advantage,
+}
+
+impl Display for PuzzleTheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleTheme::Mate { moves } => write!(f, "mate in {moves}"),
+            PuzzleTheme::Fork => write!(f, "fork"),
+            PuzzleTheme::Skewer => write!(f, "skewer"),
+            PuzzleTheme::Pin => write!(f, "pin"),
+            PuzzleTheme::HangingPiece => write!(f, "hanging piece"),
+            PuzzleTheme::Advantage => write!(f, "advantage"),
+        }
+    }
+}
+
+/// A position with a single winning idea for the player to move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Puzzle {
+    fen: String,
+    solution_moves: Vec<ChessMoveType>,
+    theme: PuzzleTheme,
+}
+
+impl Puzzle {
+    /// Returns the position of the puzzle in Forsyth-Edwards Notation.
+    pub fn get_fen(&self) -> &str {
+        &self.fen
+    }
+
+    /// Returns the solution, the solver's moves alternating with the expected replies and
+    /// ending with a move of the solver.
+    pub fn get_solution_moves(&self) -> &[ChessMoveType] {
+        &self.solution_moves
+    }
+
+    /// Returns the tactical idea behind the puzzle.
+    pub fn get_theme(&self) -> PuzzleTheme {
+        self.theme
+    }
+}
+
+/// Checks whether the current position of a game is a puzzle for the player to move.
+///
+/// A position is a puzzle if the player to move has a forced mate in up to three moves, or
+/// if the best move found by the engine wins at least two pawns and is at least two pawns
+/// better than every other move. The engine searches to its own depth, see
+/// `Engine::set_depth`.
+///
+/// # Arguments
+///
+/// * `game` - The game to look at, the game is not modified.
+/// * `engine` - The engine used to find the solution.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::puzzle::{find_puzzle, PuzzleTheme};
+///
+/// // Nc7+ forks the king and the rook
+/// let game = build_game_from_string("r3k3/8/8/3N4/8/8/P7/4K3 w - - 0 1").unwrap();
+/// let puzzle = find_puzzle(&game, &mut Engine::new().set_depth(3)).unwrap();
+///
+/// assert_eq!(PuzzleTheme::Fork, puzzle.get_theme());
+/// assert_eq!((2, 6), puzzle.get_solution_moves()[0].get_new_position());
+/// ```
+pub fn find_puzzle(game: &ChessGame, engine: &mut Engine) -> Option<Puzzle> {
+    let fen = encode_game_as_string(game);
+    if let Some(solution_moves) = find_mate(game, MATE_DEPTH) {
+        return Some(Puzzle {
+            fen,
+            theme: PuzzleTheme::Mate {
+                moves: solution_moves.len().div_ceil(2),
+            },
+            solution_moves,
+        });
+    }
+
+    let results = engine.search_root_moves(game, engine.get_depth());
+    let best = results.first()?;
+    let second_score = results.get(1)?.get_score();
+    if best.get_score() < MIN_SWING || best.get_score() - second_score < MIN_SWING {
+        return None;
+    }
+
+    let mut solution_moves = best.get_principal_variation().to_vec();
+    if solution_moves.len().is_multiple_of(2) {
+        solution_moves.pop();
+    }
+    Some(Puzzle {
+        fen,
+        theme: get_theme(game, &solution_moves[0]),
+        solution_moves,
+    })
+}
+
+/// Reads a position in Forsyth-Edwards Notation and checks whether it is a puzzle, see
+/// `find_puzzle`.
+///
+/// # Returns
+///
+/// * `Ok(Option<Puzzle>)` - The puzzle, `None` if the position is not one.
+/// * `Err(ChessError::InvalidFen)` - If the string is not a valid position.
+pub fn find_puzzle_in_fen(fen: &str, engine: &mut Engine) -> Result<Option<Puzzle>, ChessError> {
+    let game = build_game_from_string(fen)?;
+    Ok(find_puzzle(&game, engine))
+}
+
+/// Scans every position of a game for puzzles, see `find_puzzle`.
+///
+/// Positions that are part of the solution of a puzzle already found are skipped, so a
+/// winning combination gives a single puzzle.
+///
+/// # Arguments
+///
+/// * `game` - The game to scan from the position it started from, the game is not
+///   modified.
+/// * `engine` - The engine used to find the solutions.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+/// use simple_chess::engine::Engine;
+/// use simple_chess::puzzle::{find_puzzles, PuzzleTheme};
+/// use simple_chess::ChessGame;
+///
+/// let mut game = ChessGame::new();
+/// for san in ["e4", "e5", "Qh5", "Nc6", "Bc4", "Nf6", "Qxf7#"] {
+///     let chess_move = decode_move_from_string(&game, san).unwrap();
+///     game.make_move(chess_move);
+/// }
+///
+/// let puzzles = find_puzzles(&game, &mut Engine::new().set_depth(2));
+/// assert!(puzzles
+///     .iter()
+///     .any(|puzzle| puzzle.get_theme() == PuzzleTheme::Mate { moves: 1 }));
+/// ```
+pub fn find_puzzles(game: &ChessGame, engine: &mut Engine) -> Vec<Puzzle> {
+    let mut position = game.get_starting_game();
+    let moves = game.get_moves();
+    let mut puzzles = Vec::new();
+    let mut skip_until = 0;
+
+    for ply in 0..=moves.len() {
+        if ply >= skip_until {
+            if let Some(puzzle) = find_puzzle(&position, engine) {
+                skip_until = ply + puzzle.get_solution_moves().len();
+                puzzles.push(puzzle);
+            }
+        }
+        if let Some(chess_move) = moves.get(ply) {
+            position.apply_move(*chess_move);
+        }
+    }
+    puzzles
+}
+
+/// Names the tactic played by the first move of a solution.
+fn get_theme(game: &ChessGame, first_move: &ChessMoveType) -> PuzzleTheme {
+    let color = game.get_current_players_turn();
+    let before = game.get_board();
+    let mut after = before.clone();
+    first_move.make_move(&mut after);
+    let square = first_move.get_new_position();
+
+    if forks(&after, color)
+        .iter()
+        .any(|fork| fork.get_square() == square)
+    {
+        PuzzleTheme::Fork
+    } else if skewers(&after, color)
+        .iter()
+        .any(|skewer| skewer.get_attacker_square() == square)
+    {
+        PuzzleTheme::Skewer
+    } else if absolute_pins(&after, color.opposite())
+        .iter()
+        .any(|pin| pin.get_pinned_by_square() == square)
+    {
+        PuzzleTheme::Pin
+    } else if hanging_pieces(before, color.opposite())
+        .iter()
+        .any(|hanging| hanging.get_square() == square)
+    {
+        PuzzleTheme::HangingPiece
+    } else {
+        PuzzleTheme::Advantage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forced_mates_are_mate_puzzles() {
+        let puzzle = find_puzzle_in_fen(
+            "7k/8/8/8/8/8/R7/1R4K1 w - - 0 1",
+            &mut Engine::new().set_depth(1),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(PuzzleTheme::Mate { moves: 2 }, puzzle.get_theme());
+        assert_eq!(3, puzzle.get_solution_moves().len());
+        assert_eq!("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1", puzzle.get_fen());
+    }
+
+    #[test]
+    fn quiet_positions_and_positions_with_many_good_moves_are_not_puzzles() {
+        let mut engine = Engine::new().set_depth(2);
+        assert_eq!(None, find_puzzle(&ChessGame::new(), &mut engine));
+
+        // the rook can be taken by either the queen or the knight
+        let two_ways = "4k3/8/8/3r4/8/4N3/8/3QK3 w - - 0 1";
+        assert_eq!(None, find_puzzle_in_fen(two_ways, &mut engine).unwrap());
+
+        assert!(find_puzzle_in_fen("not a position", &mut engine).is_err());
+    }
+
+    #[test]
+    fn free_pieces_are_hanging_piece_puzzles() {
+        let puzzle = find_puzzle_in_fen(
+            "4k3/8/8/3r4/8/8/8/3QK3 w - - 0 1",
+            &mut Engine::new().set_depth(2),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(PuzzleTheme::HangingPiece, puzzle.get_theme());
+        assert_eq!(1, puzzle.get_solution_moves().len() % 2);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::chess_game_state_analyzer::is_in_check;
+use crate::error::ChessError;
+use crate::piece::PieceType::{Bishop, King, Knight, Pawn, Queen, Rook};
+use crate::piece::{ChessPiece, PieceType};
+use crate::setup::BoardSetup;
+use crate::{ChessGame, Color};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// How many times pieces are placed at random before giving up on the constraints.
+const MAX_ATTEMPTS: usize = 10_000;
+
+/// The piece types picked from when the material is left random, pawns are listed more
+/// than once so they are the most common.
+const RANDOM_PIECE_TYPES: [PieceType; 8] = [Pawn, Pawn, Pawn, Knight, Bishop, Rook, Queen, Pawn];
+
+/// A small seeded random number generator, so a position can be generated again from the
+/// same seed.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::random_position::PositionRng;
+///
+/// let mut first = PositionRng::new(42);
+/// let mut second = PositionRng::new(42);
+/// assert_eq!(first.next_u64(), second.next_u64());
+/// assert!(first.next_below(10) < 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PositionRng {
+    state: u64,
+}
+
+impl PositionRng {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next random number using SplitMix64.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number from `0` up to, but not including, `bound`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is `0`.
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "the bound must be at least 1");
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Describes the positions `random_position` may generate.
+///
+/// By default each player gets up to four pieces besides their king, picked at random,
+/// and either player may be to move.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::piece::PieceType;
+/// use simple_chess::random_position::PositionConstraints;
+/// use simple_chess::Color;
+///
+/// // king and rook against king, white to move
+/// let constraints = PositionConstraints::new()
+///     .set_material(&[PieceType::Rook], &[])
+///     .set_current_turn(Color::White);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionConstraints {
+    white_pieces: Option<Vec<PieceType>>,
+    black_pieces: Option<Vec<PieceType>>,
+    max_random_pieces: usize,
+    current_turn: Option<Color>,
+}
+
+impl PositionConstraints {
+    /// Creates constraints allowing any material and either player to move.
+    pub fn new() -> Self {
+        Self {
+            white_pieces: None,
+            black_pieces: None,
+            max_random_pieces: 4,
+            current_turn: None,
+        }
+    }
+
+    /// Sets the exact material of both players.
+    ///
+    /// # Arguments
+    ///
+    /// * `white_pieces` - White's pieces besides the king.
+    /// * `black_pieces` - Black's pieces besides the king.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `PositionConstraints` with the material set.
+    pub fn set_material(mut self, white_pieces: &[PieceType], black_pieces: &[PieceType]) -> Self {
+        self.white_pieces = Some(white_pieces.to_vec());
+        self.black_pieces = Some(black_pieces.to_vec());
+        self
+    }
+
+    /// Sets the most pieces each player gets besides the king when the material is not set.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_random_pieces` - The largest number of pieces per player.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `PositionConstraints` with the limit set.
+    pub fn set_max_random_pieces(mut self, max_random_pieces: usize) -> Self {
+        self.max_random_pieces = max_random_pieces;
+        self
+    }
+
+    /// Sets the player to move, which is picked at random otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player to move.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `PositionConstraints` with the player to move set.
+    pub fn set_current_turn(mut self, color: Color) -> Self {
+        self.current_turn = Some(color);
+        self
+    }
+
+    fn get_pieces(&self, color: Color, rng: &mut PositionRng) -> Vec<PieceType> {
+        let pieces = match color {
+            Color::White => &self.white_pieces,
+            Color::Black => &self.black_pieces,
+        };
+        match pieces {
+            Some(pieces) => pieces.clone(),
+            None => (0..rng.next_below(self.max_random_pieces + 1))
+                .map(|_| RANDOM_PIECE_TYPES[rng.next_below(RANDOM_PIECE_TYPES.len())])
+                .collect(),
+        }
+    }
+}
+
+impl Default for PositionConstraints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generates a random legal position on a standard board.
+///
+/// Every position has exactly one king per player, no pawns on the first or last rank,
+/// and the player who is not to move is not in check. Neither player may castle and no
+/// en passant capture is possible. The same seed and constraints always give the same
+/// position, which makes the generator useful for fuzzing and endgame drills.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `constraints` - The material and player to move of the position.
+///
+/// # Returns
+///
+/// * `Ok(ChessGame)` - A game starting from the random position.
+/// * `Err(RandomPositionError)` - If the constraints cannot be met, for example when the
+///   material does not fit on the board or contains a king.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::piece::PieceType;
+/// use simple_chess::random_position::{random_position, PositionConstraints, PositionRng};
+/// use simple_chess::chess_game_state_analyzer::GameState;
+/// use simple_chess::Color;
+///
+/// let constraints = PositionConstraints::new()
+///     .set_material(&[PieceType::Rook], &[])
+///     .set_current_turn(Color::White);
+/// let mut game = random_position(&mut PositionRng::new(7), &constraints).unwrap();
+///
+/// assert_eq!(Color::White, game.get_current_players_turn());
+/// assert!(matches!(game.get_game_state(), GameState::InProgress { .. }));
+/// ```
+pub fn random_position(
+    rng: &mut PositionRng,
+    constraints: &PositionConstraints,
+) -> Result<ChessGame, RandomPositionError> {
+    let white_pieces = constraints.get_pieces(Color::White, rng);
+    let black_pieces = constraints.get_pieces(Color::Black, rng);
+    if white_pieces.contains(&King) || black_pieces.contains(&King) {
+        return Err(RandomPositionError::new(
+            "kings are always placed, they cannot be part of the material".to_string(),
+        ));
+    }
+    let pawns = white_pieces
+        .iter()
+        .chain(&black_pieces)
+        .filter(|&&piece_type| piece_type == Pawn)
+        .count();
+    if pawns > 48 || white_pieces.len() + black_pieces.len() + 2 > 64 {
+        return Err(RandomPositionError::new(
+            "the material does not fit on the board".to_string(),
+        ));
+    }
+
+    for _ in 0..MAX_ATTEMPTS {
+        let current_turn = constraints.current_turn.unwrap_or_else(|| {
+            if rng.next_below(2) == 0 {
+                Color::White
+            } else {
+                Color::Black
+            }
+        });
+        let mut setup = BoardSetup::new(8, 8).set_current_turn(current_turn);
+        let mut occupied = Vec::new();
+        for (color, pieces) in [(Color::White, &white_pieces), (Color::Black, &black_pieces)] {
+            for &piece_type in std::iter::once(&King).chain(pieces.iter()) {
+                let square = loop {
+                    let square = (rng.next_below(8), rng.next_below(8));
+                    let is_free = !occupied.contains(&square);
+                    let pawn_on_back_rank = piece_type == Pawn && (square.1 == 0 || square.1 == 7);
+                    if is_free && !pawn_on_back_rank {
+                        break square;
+                    }
+                };
+                occupied.push(square);
+                setup = setup.place_piece(ChessPiece::new(piece_type, color), square.0, square.1);
+            }
+        }
+
+        let board = setup
+            .build_board()
+            .map_err(|e| RandomPositionError::new(e.to_string()))?;
+        if !is_in_check(current_turn.opposite(), &board) {
+            return setup
+                .build_game()
+                .map_err(|e| RandomPositionError::new(e.to_string()));
+        }
+    }
+    Err(RandomPositionError::new(format!(
+        "no legal position was found in {MAX_ATTEMPTS} attempts"
+    )))
+}
+
+/// Error returned when `random_position` cannot meet its constraints.
+pub struct RandomPositionError {
+    reason: String,
+}
+
+impl RandomPositionError {
+    fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for RandomPositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot generate a random position: {}", self.reason)
+    }
+}
+
+impl Debug for RandomPositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RandomPositionError: {}", self.reason)
+    }
+}
+
+impl Error for RandomPositionError {}
+
+impl From<RandomPositionError> for ChessError {
+    fn from(error: RandomPositionError) -> Self {
+        ChessError::InvalidBoard(error.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::encode_game_as_string;
+
+    fn count_pieces(game: &ChessGame, color: Color, piece_type: PieceType) -> usize {
+        let board = game.get_board();
+        (0..8)
+            .flat_map(|col| (0..8).map(move |row| (col, row)))
+            .filter_map(|(col, row)| board.get_piece_at_space(col, row))
+            .filter(|piece| piece.get_color() == color && piece.get_piece_type() == piece_type)
+            .count()
+    }
+
+    #[test]
+    fn random_positions_are_legal() {
+        let mut rng = PositionRng::new(1);
+        let constraints = PositionConstraints::new().set_max_random_pieces(12);
+        for _ in 0..200 {
+            let game = random_position(&mut rng, &constraints).unwrap();
+            let board = game.get_board();
+
+            assert_eq!(1, count_pieces(&game, Color::White, King));
+            assert_eq!(1, count_pieces(&game, Color::Black, King));
+            for col in 0..8 {
+                for row in [0, 7] {
+                    assert!(board
+                        .get_piece_at_space(col, row)
+                        .is_none_or(|piece| piece.get_piece_type() != Pawn));
+                }
+            }
+            assert!(!is_in_check(
+                game.get_current_players_turn().opposite(),
+                board
+            ));
+        }
+    }
+
+    #[test]
+    fn material_constraints_are_respected() {
+        let constraints = PositionConstraints::new()
+            .set_material(&[Rook], &[])
+            .set_current_turn(Color::Black);
+        let game = random_position(&mut PositionRng::new(3), &constraints).unwrap();
+
+        assert_eq!(Color::Black, game.get_current_players_turn());
+        assert_eq!(1, count_pieces(&game, Color::White, Rook));
+        assert_eq!(0, count_pieces(&game, Color::Black, Rook));
+        assert_eq!(
+            encode_game_as_string(&game),
+            encode_game_as_string(
+                &random_position(&mut PositionRng::new(3), &constraints).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn impossible_constraints_are_an_error() {
+        let with_king = PositionConstraints::new().set_material(&[King], &[]);
+        assert!(random_position(&mut PositionRng::new(1), &with_king).is_err());
+
+        let too_many_pawns = PositionConstraints::new().set_material(&[Pawn; 40], &[Pawn; 10]);
+        assert!(random_position(&mut PositionRng::new(1), &too_many_pawns).is_err());
+    }
+}
@@ -0,0 +1,436 @@
+use crate::game_result::GameResult;
+use crate::match_statistics::elo_to_score;
+use crate::Color;
+use std::f64::consts::PI;
+
+/// The factor between Glicko ratings and the Glicko-2 scale.
+const GLICKO2_SCALE: f64 = 173.7178;
+
+/// The accuracy the Glicko-2 volatility is worked out to.
+const VOLATILITY_TOLERANCE: f64 = 0.000_001;
+
+/// A player's rating, with the deviation and volatility used by Glicko-2.
+///
+/// Elo calculators only change the value. New ratings start at 1500 with a deviation of
+/// 350 and a volatility of 0.06.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    value: f64,
+    deviation: f64,
+    volatility: f64,
+}
+
+impl Rating {
+    /// Creates a rating with the deviation and volatility of a new player.
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+
+    /// Sets how uncertain the rating is, the standard deviation of the player's strength.
+    pub fn set_deviation(mut self, deviation: f64) -> Self {
+        self.deviation = deviation;
+        self
+    }
+
+    /// Sets how erratic the player's results are expected to be.
+    pub fn set_volatility(mut self, volatility: f64) -> Self {
+        self.volatility = volatility;
+        self
+    }
+
+    /// Returns the rating.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns how uncertain the rating is.
+    pub fn get_deviation(&self) -> f64 {
+        self.deviation
+    }
+
+    /// Returns how erratic the player's results are expected to be.
+    pub fn get_volatility(&self) -> f64 {
+        self.volatility
+    }
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self::new(1500.0)
+    }
+}
+
+/// A player's profile, their name, rating and record.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::rating::{EloCalculator, Player, RatingCalculator};
+/// use simple_chess::ChessGame;
+/// use simple_chess::Color;
+///
+/// let mut white = Player::new("Ian");
+/// let mut black = Player::new("Magnus");
+///
+/// let mut game = ChessGame::new();
+/// game.resign(Color::Black).unwrap();
+/// EloCalculator::new().update(&mut white, &mut black, &game.get_result().unwrap());
+///
+/// assert_eq!(1516.0, white.get_rating().get_value());
+/// assert_eq!(1484.0, black.get_rating().get_value());
+/// assert_eq!(1, white.get_wins());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    name: String,
+    rating: Rating,
+    wins: usize,
+    draws: usize,
+    losses: usize,
+}
+
+impl Player {
+    /// Creates a player with the rating of a new player and no games.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            rating: Rating::default(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+
+    /// Sets the player's rating, for players who are already rated.
+    pub fn set_rating(mut self, rating: Rating) -> Self {
+        self.rating = rating;
+        self
+    }
+
+    /// Returns the player's name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the player's rating.
+    pub fn get_rating(&self) -> Rating {
+        self.rating
+    }
+
+    /// Returns the number of games the player has been rated for.
+    pub fn get_games_played(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Returns the number of rated games the player won.
+    pub fn get_wins(&self) -> usize {
+        self.wins
+    }
+
+    /// Returns the number of rated games the player drew.
+    pub fn get_draws(&self) -> usize {
+        self.draws
+    }
+
+    /// Returns the number of rated games the player lost.
+    pub fn get_losses(&self) -> usize {
+        self.losses
+    }
+
+    /// Records a score of `1`, `0.5` or `0`.
+    fn record_score(&mut self, score: f64) {
+        if score > 0.5 {
+            self.wins += 1;
+        } else if score < 0.5 {
+            self.losses += 1;
+        } else {
+            self.draws += 1;
+        }
+    }
+}
+
+/// Updates the ratings of two players from the result of a game between them.
+pub trait RatingCalculator {
+    /// Updates the ratings and records of both players from a game. Each player's new
+    /// rating is worked out from the opponent's rating before the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `white` - The player who played White.
+    /// * `black` - The player who played Black.
+    /// * `result` - The result of the game.
+    fn update(&self, white: &mut Player, black: &mut Player, result: &GameResult);
+}
+
+/// Rates players with the Elo system, moving each rating by the K-factor times the
+/// difference between the score and the expected score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloCalculator {
+    k_factor: f64,
+}
+
+impl EloCalculator {
+    /// Creates a calculator with a K-factor of 32.
+    pub fn new() -> Self {
+        Self { k_factor: 32.0 }
+    }
+
+    /// Sets the most a rating can change from one game.
+    pub fn set_k_factor(mut self, k_factor: f64) -> Self {
+        self.k_factor = k_factor;
+        self
+    }
+
+    /// Returns the most a rating can change from one game.
+    pub fn get_k_factor(&self) -> f64 {
+        self.k_factor
+    }
+
+    /// Returns the score a player is expected to make against an opponent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::rating::EloCalculator;
+    ///
+    /// let expected_score = EloCalculator::expected_score(1900.0, 1500.0);
+    /// assert_eq!(909, (expected_score * 1000.0).round() as i32);
+    /// ```
+    pub fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+        elo_to_score(rating - opponent_rating)
+    }
+
+    /// Returns a player's new rating after scoring `score`, `1` for a win, `0.5` for a draw
+    /// and `0` for a loss, against an opponent.
+    pub fn get_new_rating(&self, rating: f64, opponent_rating: f64, score: f64) -> f64 {
+        rating + self.k_factor * (score - Self::expected_score(rating, opponent_rating))
+    }
+}
+
+impl Default for EloCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RatingCalculator for EloCalculator {
+    fn update(&self, white: &mut Player, black: &mut Player, result: &GameResult) {
+        let (white_rating, black_rating) = (white.rating.value, black.rating.value);
+        let white_score = get_score(result, Color::White);
+        white.rating.value = self.get_new_rating(white_rating, black_rating, white_score);
+        black.rating.value = self.get_new_rating(black_rating, white_rating, 1.0 - white_score);
+        white.record_score(white_score);
+        black.record_score(1.0 - white_score);
+    }
+}
+
+/// Rates players with Mark Glickman's Glicko-2 system, which tracks how certain each
+/// rating is and how erratic each player's results are.
+///
+/// Glicko-2 is meant to rate a period of several games at once with `update_period`.
+/// Used as a `RatingCalculator` every game is a period of its own, which makes ratings
+/// move faster than the system intends.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::rating::{Glicko2Calculator, Player, Rating, RatingCalculator};
+/// use simple_chess::ChessGame;
+/// use simple_chess::Color;
+///
+/// let mut white = Player::new("Ian");
+/// let mut black = Player::new("Magnus").set_rating(Rating::new(1800.0).set_deviation(50.0));
+///
+/// let mut game = ChessGame::new();
+/// game.resign(Color::Black).unwrap();
+/// Glicko2Calculator::new().update(&mut white, &mut black, &game.get_result().unwrap());
+///
+/// // the uncertain rating moves much more than the certain one
+/// assert!(white.get_rating().get_value() > 1800.0);
+/// assert!(black.get_rating().get_value() > 1780.0);
+/// assert!(white.get_rating().get_deviation() < 350.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glicko2Calculator {
+    tau: f64,
+}
+
+impl Glicko2Calculator {
+    /// Creates a calculator whose volatility changes with a `tau` of `0.5`.
+    pub fn new() -> Self {
+        Self { tau: 0.5 }
+    }
+
+    /// Sets `tau`, how much volatilities may change from one period to the next, usually
+    /// between `0.3` and `1.2`.
+    pub fn set_tau(mut self, tau: f64) -> Self {
+        self.tau = tau;
+        self
+    }
+
+    /// Returns how much volatilities may change from one period to the next.
+    pub fn get_tau(&self) -> f64 {
+        self.tau
+    }
+
+    /// Updates a player's rating from the games they played in a rating period.
+    ///
+    /// A player who played no games in the period keeps their rating, but it becomes less
+    /// certain.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - The player to update.
+    /// * `results` - The rating of the opponent at the start of the period and the score
+    ///   against them, `1` for a win, `0.5` for a draw and `0` for a loss, of each game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::rating::{Glicko2Calculator, Player, Rating};
+    ///
+    /// let mut player = Player::new("Ian").set_rating(Rating::new(1500.0).set_deviation(50.0));
+    /// Glicko2Calculator::new().update_period(&mut player, &[]);
+    ///
+    /// assert_eq!(1500.0, player.get_rating().get_value());
+    /// assert_eq!(51, player.get_rating().get_deviation().round() as i32);
+    /// ```
+    pub fn update_period(&self, player: &mut Player, results: &[(Rating, f64)]) {
+        let mu = (player.rating.value - 1500.0) / GLICKO2_SCALE;
+        let phi = player.rating.deviation / GLICKO2_SCALE;
+        let sigma = player.rating.volatility;
+
+        if results.is_empty() {
+            player.rating.deviation = (phi * phi + sigma * sigma).sqrt() * GLICKO2_SCALE;
+            return;
+        }
+
+        let mut inverse_variance = 0.0;
+        let mut improvement = 0.0;
+        for (opponent, score) in results {
+            let opponent_mu = (opponent.value - 1500.0) / GLICKO2_SCALE;
+            let g = get_g(opponent.deviation / GLICKO2_SCALE);
+            let expected_score = 1.0 / (1.0 + (-g * (mu - opponent_mu)).exp());
+            inverse_variance += g * g * expected_score * (1.0 - expected_score);
+            improvement += g * (score - expected_score);
+        }
+        let variance = 1.0 / inverse_variance;
+        let delta = variance * improvement;
+
+        let new_sigma = self.get_new_volatility(phi, sigma, variance, delta);
+        let pre_period_phi = (phi * phi + new_sigma * new_sigma).sqrt();
+        let new_phi = 1.0 / (1.0 / (pre_period_phi * pre_period_phi) + 1.0 / variance).sqrt();
+        let new_mu = mu + new_phi * new_phi * improvement;
+
+        player.rating = Rating {
+            value: new_mu * GLICKO2_SCALE + 1500.0,
+            deviation: new_phi * GLICKO2_SCALE,
+            volatility: new_sigma,
+        };
+        for (_, score) in results {
+            player.record_score(*score);
+        }
+    }
+
+    /// Works out the new volatility with the Illinois algorithm of step 5 of the Glicko-2
+    /// paper.
+    fn get_new_volatility(&self, phi: f64, sigma: f64, variance: f64, delta: f64) -> f64 {
+        let tau = self.tau;
+        let a = (sigma * sigma).ln();
+        let f = |x: f64| {
+            let exp_x = x.exp();
+            let denominator = phi * phi + variance + exp_x;
+            exp_x * (delta * delta - phi * phi - variance - exp_x)
+                / (2.0 * denominator * denominator)
+                - (x - a) / (tau * tau)
+        };
+
+        let mut low = a;
+        let mut high = if delta * delta > phi * phi + variance {
+            (delta * delta - phi * phi - variance).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * tau) < 0.0 {
+                k += 1.0;
+            }
+            a - k * tau
+        };
+
+        let mut f_low = f(low);
+        let mut f_high = f(high);
+        while (high - low).abs() > VOLATILITY_TOLERANCE {
+            let middle = low + (low - high) * f_low / (f_high - f_low);
+            let f_middle = f(middle);
+            if f_middle * f_high <= 0.0 {
+                low = high;
+                f_low = f_high;
+            } else {
+                f_low /= 2.0;
+            }
+            high = middle;
+            f_high = f_middle;
+        }
+        (low / 2.0).exp()
+    }
+}
+
+impl Default for Glicko2Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RatingCalculator for Glicko2Calculator {
+    fn update(&self, white: &mut Player, black: &mut Player, result: &GameResult) {
+        let (white_rating, black_rating) = (white.rating, black.rating);
+        let white_score = get_score(result, Color::White);
+        self.update_period(white, &[(black_rating, white_score)]);
+        self.update_period(black, &[(white_rating, 1.0 - white_score)]);
+    }
+}
+
+/// Returns how much a game counts against an opponent with a rating deviation of `phi`,
+/// on the Glicko-2 scale.
+fn get_g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+/// Returns the score of a color in a game, `1` for a win, `0.5` for a draw and `0` for a
+/// loss.
+fn get_score(result: &GameResult, color: Color) -> f64 {
+    match result.get_winner() {
+        Some(winner) if winner == color => 1.0,
+        Some(_) => 0.0,
+        None => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glicko2_matches_the_example_of_the_paper() {
+        let mut player =
+            Player::new("example").set_rating(Rating::new(1500.0).set_deviation(200.0));
+        let results = [
+            (Rating::new(1400.0).set_deviation(30.0), 1.0),
+            (Rating::new(1550.0).set_deviation(100.0), 0.0),
+            (Rating::new(1700.0).set_deviation(300.0), 0.0),
+        ];
+        Glicko2Calculator::new().update_period(&mut player, &results);
+
+        let rating = player.get_rating();
+        assert!((rating.get_value() - 1464.06).abs() < 0.01);
+        assert!((rating.get_deviation() - 151.52).abs() < 0.01);
+        assert!((rating.get_volatility() - 0.05999).abs() < 0.00001);
+        assert_eq!(
+            (1, 0, 2),
+            (player.get_wins(), player.get_draws(), player.get_losses())
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use crate::piece::ChessPiece;
+use crate::render::svg::SvgRenderer;
+#[cfg(feature = "gif")]
+use crate::render::svg::{rasterize, RenderError};
+use crate::viewer::GameViewer;
+use crate::{ChessGame, ChessMoveType};
+use game_board::Board;
+#[cfg(feature = "gif")]
+use std::time::Duration;
+
+/// One position of a game replay, see `animate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    ply: usize,
+    board: Board<ChessPiece>,
+    last_move: Option<ChessMoveType>,
+}
+
+impl Frame {
+    /// Returns the number of moves played to reach the position.
+    pub fn get_ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Returns the board of the position.
+    pub fn get_board(&self) -> &Board<ChessPiece> {
+        &self.board
+    }
+
+    /// Returns the move that led to the position, `None` for the first frame.
+    pub fn get_last_move(&self) -> Option<&ChessMoveType> {
+        self.last_move.as_ref()
+    }
+
+    /// Draws the frame as an SVG image with the squares of its move highlighted.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The renderer setting the size, colors and perspective of the image.
+    pub fn to_svg(&self, renderer: &SvgRenderer) -> String {
+        renderer.render_after_move(&self.board, self.last_move.as_ref(), None)
+    }
+}
+
+/// Splits a game into the frames of a replay.
+///
+/// The first frame is the position the game started from, followed by one frame per move
+/// with that move highlighted when the frame is drawn.
+///
+/// # Arguments
+///
+/// * `game` - The game to replay.
+///
+/// # Returns
+///
+/// * `Vec<Frame>` - The frames in the order they were played.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::standard_algebraic_notation::decode_move_from_string;
+/// use simple_chess::render::animate;
+/// use simple_chess::render::svg::SvgRenderer;
+/// use simple_chess::ChessGame;
+///
+/// let mut game = ChessGame::new();
+/// for san in ["e4", "e5", "Nf3"] {
+///     game.make_move(decode_move_from_string(&game, san).unwrap());
+/// }
+///
+/// let frames = animate(&game);
+/// assert_eq!(4, frames.len());
+/// assert_eq!(game.get_board(), frames[3].get_board());
+/// let svg = frames[3].to_svg(&SvgRenderer::new());
+/// ```
+pub fn animate(game: &ChessGame) -> Vec<Frame> {
+    let mut viewer = GameViewer::new(game);
+    let mut frames = Vec::with_capacity(viewer.get_ply_count() + 1);
+    loop {
+        frames.push(Frame {
+            ply: viewer.get_ply(),
+            board: viewer.get_board().clone(),
+            last_move: viewer.get_last_move().copied(),
+        });
+        if !viewer.next() {
+            return frames;
+        }
+    }
+}
+
+/// Encodes frames as an animated GIF that loops forever.
+///
+/// # Arguments
+///
+/// * `frames` - The frames to show, such as the frames of `animate`.
+/// * `renderer` - The renderer setting the size, colors and perspective of the images.
+/// * `delay` - How long each frame is shown, rounded to hundredths of a second.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The bytes of the GIF file.
+/// * `Err(RenderError)` - If there are no frames, the image is too large for a GIF or a
+///   frame could not be drawn.
+#[cfg(feature = "gif")]
+pub fn encode_gif(
+    frames: &[Frame],
+    renderer: &SvgRenderer,
+    delay: Duration,
+) -> Result<Vec<u8>, RenderError> {
+    if frames.is_empty() {
+        return Err(RenderError::new(String::from("there are no frames")));
+    }
+    let renderer = renderer.clone().set_coordinates(false);
+    let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+
+    let images = frames
+        .iter()
+        .map(|frame| rasterize(&frame.to_svg(&renderer)))
+        .collect::<Result<Vec<_>, RenderError>>()?;
+    let too_large = || RenderError::new(String::from("the image is too large for a gif"));
+    let width = u16::try_from(images[0].width()).map_err(|_| too_large())?;
+    let height = u16::try_from(images[0].height()).map_err(|_| too_large())?;
+
+    let mut bytes = Vec::new();
+    let mut encoder = gif::Encoder::new(&mut bytes, width, height, &[])
+        .map_err(|e| RenderError::new(e.to_string()))?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|e| RenderError::new(e.to_string()))?;
+    for image in images {
+        let mut pixels = image.take();
+        let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+        gif_frame.delay = delay;
+        encoder
+            .write_frame(&gif_frame)
+            .map_err(|e| RenderError::new(e.to_string()))?;
+    }
+    drop(encoder);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::standard_algebraic_notation::play_moves;
+
+    #[test]
+    fn frames_follow_the_moves() {
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &["d4", "d5", "c4"]);
+        let frames = animate(&game);
+
+        assert_eq!(None, frames[0].get_last_move());
+        assert_eq!(ChessGame::new().get_board(), frames[0].get_board());
+        for (ply, frame) in frames.iter().enumerate().skip(1) {
+            assert_eq!(ply, frame.get_ply());
+            assert_eq!(Some(&game.get_moves()[ply - 1]), frame.get_last_move());
+            assert_eq!(game.position_at(ply).as_ref(), Some(frame.get_board()));
+        }
+    }
+
+    #[test]
+    fn the_moved_piece_is_highlighted() {
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &["e4"]);
+        let frames = animate(&game);
+        let renderer = SvgRenderer::new();
+
+        assert!(!frames[0].to_svg(&renderer).contains("fill-opacity=\"0.6\""));
+        assert_eq!(
+            2,
+            frames[1]
+                .to_svg(&renderer)
+                .matches("fill-opacity=\"0.6\"")
+                .count()
+        );
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn replays_are_encoded_as_gifs() {
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &["e4", "e5"]);
+        let frames = animate(&game);
+        let renderer = SvgRenderer::new().set_square_size(10);
+        let gif = encode_gif(&frames, &renderer, Duration::from_millis(500)).unwrap();
+
+        assert_eq!(b"GIF89a", &gif[..6]);
+        assert!(encode_gif(&[], &renderer, Duration::from_millis(500)).is_err());
+    }
+}
@@ -0,0 +1,345 @@
+use crate::chess_game_state_analyzer::is_in_check;
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, Color};
+use game_board::{get_square_name_from_row_and_col, Board};
+
+pub mod animation;
+pub mod svg;
+
+pub use animation::{animate, Frame};
+
+const RESET: &str = "\x1b[0m";
+
+/// The ANSI background colors a `BoardRenderer` paints squares with.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::render::BoardTheme;
+///
+/// // green and white squares with a yellow last move
+/// let theme = BoardTheme::new()
+///     .set_light_square("\x1b[47m")
+///     .set_dark_square("\x1b[42m")
+///     .set_last_move("\x1b[43m");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardTheme {
+    light_square: String,
+    dark_square: String,
+    last_move: String,
+    check: String,
+}
+
+impl BoardTheme {
+    /// Creates the theme of the board's `Display`, grey light squares on the terminal's own
+    /// background, with a blue last move and a red king in check.
+    pub fn new() -> Self {
+        Self {
+            light_square: String::from("\x1b[100m"),
+            dark_square: String::new(),
+            last_move: String::from("\x1b[44m"),
+            check: String::from("\x1b[41m"),
+        }
+    }
+
+    /// Sets the escape code starting a light square.
+    pub fn set_light_square(mut self, code: &str) -> Self {
+        self.light_square = code.to_string();
+        self
+    }
+
+    /// Sets the escape code starting a dark square.
+    pub fn set_dark_square(mut self, code: &str) -> Self {
+        self.dark_square = code.to_string();
+        self
+    }
+
+    /// Sets the escape code starting a square of the last move.
+    pub fn set_last_move(mut self, code: &str) -> Self {
+        self.last_move = code.to_string();
+        self
+    }
+
+    /// Sets the escape code starting the square of a king in check.
+    pub fn set_check(mut self, code: &str) -> Self {
+        self.check = code.to_string();
+        self
+    }
+}
+
+impl Default for BoardTheme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a square is marked when it is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Highlight {
+    None,
+    LastMove,
+    Check,
+}
+
+/// Draws boards as text for terminals.
+///
+/// By default boards are drawn like the board's `Display`: unicode pieces on ANSI colored
+/// squares from White's side. Each option can be changed with the builder methods. Without
+/// colors, empty squares are drawn as `.`, the squares of the last move are put in
+/// brackets such as `[P]` and a king in check in parentheses such as `(k)`.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::render::BoardRenderer;
+/// use simple_chess::{ChessGame, Color};
+///
+/// let renderer = BoardRenderer::new()
+///     .set_ascii(true)
+///     .set_colors(false)
+///     .set_labels(true)
+///     .set_perspective(Color::Black);
+/// let text = renderer.render(&ChessGame::new());
+///
+/// assert!(text.starts_with("1  R  N  B  K  Q  B  N  R \n2  P  P"));
+/// assert!(text.ends_with("   h  g  f  e  d  c  b  a \n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardRenderer {
+    ascii: bool,
+    labels: bool,
+    perspective: Color,
+    highlight_last_move: bool,
+    highlight_check: bool,
+    colors: bool,
+    theme: BoardTheme,
+}
+
+impl BoardRenderer {
+    /// Creates a renderer drawing boards like the board's `Display`, with the last move and
+    /// checks highlighted when a game is rendered.
+    pub fn new() -> Self {
+        Self {
+            ascii: false,
+            labels: false,
+            perspective: Color::White,
+            highlight_last_move: true,
+            highlight_check: true,
+            colors: true,
+            theme: BoardTheme::new(),
+        }
+    }
+
+    /// Sets whether pieces are drawn as their FEN letters instead of unicode symbols.
+    pub fn set_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Sets whether the files and ranks are written beside the board.
+    pub fn set_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the player whose side of the board is drawn at the bottom.
+    pub fn set_perspective(mut self, perspective: Color) -> Self {
+        self.perspective = perspective;
+        self
+    }
+
+    /// Sets whether the squares of the last move of a game are highlighted.
+    pub fn set_highlight_last_move(mut self, highlight: bool) -> Self {
+        self.highlight_last_move = highlight;
+        self
+    }
+
+    /// Sets whether the king of the player to move is highlighted when in check.
+    pub fn set_highlight_check(mut self, highlight: bool) -> Self {
+        self.highlight_check = highlight;
+        self
+    }
+
+    /// Sets whether ANSI escape codes are used, turn them off for terminals without
+    /// colors or for logs.
+    pub fn set_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Sets the colors squares are painted with.
+    pub fn set_theme(mut self, theme: BoardTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Draws the current position of a game, with its last move and check highlighted.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game to draw.
+    ///
+    /// # Returns
+    ///
+    /// A `String` with one line per rank, ending in a new line.
+    pub fn render(&self, game: &ChessGame) -> String {
+        let board = game.get_board();
+        let mut highlights = Vec::new();
+        if let Some(last_move) = game.get_last_move().filter(|_| self.highlight_last_move) {
+            highlights.push((last_move.get_original_position(), Highlight::LastMove));
+            highlights.push((last_move.get_new_position(), Highlight::LastMove));
+        }
+        let turn = game.get_current_players_turn();
+        if self.highlight_check && is_in_check(turn, board) {
+            if let Some(king) = find_piece(board, ChessPiece::new(PieceType::King, turn)) {
+                highlights.push((king, Highlight::Check));
+            }
+        }
+        self.render_squares(board, &highlights)
+    }
+
+    /// Draws a board without any highlights.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board to draw.
+    ///
+    /// # Returns
+    ///
+    /// A `String` with one line per rank, ending in a new line.
+    pub fn render_board(&self, board: &Board<ChessPiece>) -> String {
+        self.render_squares(board, &[])
+    }
+
+    fn render_squares(
+        &self,
+        board: &Board<ChessPiece>,
+        highlights: &[((usize, usize), Highlight)],
+    ) -> String {
+        let (width, height) = (board.get_width(), board.get_height());
+        let rows: Vec<usize> = match self.perspective {
+            Color::White => (0..height).rev().collect(),
+            Color::Black => (0..height).collect(),
+        };
+        let cols: Vec<usize> = match self.perspective {
+            Color::White => (0..width).collect(),
+            Color::Black => (0..width).rev().collect(),
+        };
+        let label_width = height.to_string().len();
+
+        let mut text = String::new();
+        for &row in &rows {
+            if self.labels {
+                text.push_str(&format!("{:<label_width$} ", row + 1));
+            }
+            for &col in &cols {
+                // a later highlight, such as a check, wins over the last move
+                let highlight = highlights
+                    .iter()
+                    .rev()
+                    .find(|(square, _)| *square == (col, row))
+                    .map_or(Highlight::None, |(_, highlight)| *highlight);
+                text.push_str(&self.render_square(board, col, row, highlight));
+            }
+            text.push('\n');
+        }
+        if self.labels {
+            text.push_str(&" ".repeat(label_width + 1));
+            for &col in &cols {
+                let name = get_square_name_from_row_and_col(col, 0);
+                text.push_str(&format!(
+                    " {} ",
+                    name.trim_end_matches(|c: char| c.is_ascii_digit())
+                ));
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    fn render_square(
+        &self,
+        board: &Board<ChessPiece>,
+        col: usize,
+        row: usize,
+        highlight: Highlight,
+    ) -> String {
+        let piece = board.get_piece_at_space(col, row);
+        let symbol = match piece {
+            Some(piece) if self.ascii => piece.as_fen_char().to_string(),
+            Some(piece) => piece.as_utf_str().to_string(),
+            None if self.colors => String::from(" "),
+            None => String::from("."),
+        };
+
+        if !self.colors {
+            return match highlight {
+                Highlight::None => format!(" {symbol} "),
+                Highlight::LastMove => format!("[{symbol}]"),
+                Highlight::Check => format!("({symbol})"),
+            };
+        }
+        let background = match highlight {
+            Highlight::LastMove => &self.theme.last_move,
+            Highlight::Check => &self.theme.check,
+            Highlight::None if (col + row) % 2 == 1 => &self.theme.light_square,
+            Highlight::None => &self.theme.dark_square,
+        };
+        format!("{background} {symbol} {RESET}")
+    }
+}
+
+impl Default for BoardRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn find_piece(board: &Board<ChessPiece>, piece: ChessPiece) -> Option<(usize, usize)> {
+    (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| (col, row)))
+        .find(|&(col, row)| board.get_piece_at_space(col, row) == Some(&piece))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+
+    #[test]
+    fn default_rendering_matches_the_board_display() {
+        let board = ChessGame::new().get_board().clone();
+        assert_eq!(board.to_string(), BoardRenderer::new().render_board(&board));
+    }
+
+    #[test]
+    fn plain_rendering_marks_last_move_and_check() {
+        let mut game = build_game_from_string("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+        game.make_move(decode_move_from_string(&game, "Ra8+").unwrap());
+        let text = BoardRenderer::new()
+            .set_ascii(true)
+            .set_colors(false)
+            .render(&game);
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!("[R] .  .  . (k) .  .  . ", lines[0]);
+        assert_eq!("[.] .  .  .  K  .  .  . ", lines[7]);
+    }
+
+    #[test]
+    fn highlights_can_be_turned_off() {
+        let mut game = ChessGame::new();
+        game.make_move(decode_move_from_string(&game, "e4").unwrap());
+        let renderer = BoardRenderer::new()
+            .set_colors(false)
+            .set_highlight_last_move(false);
+
+        assert!(!renderer.render(&game).contains('['));
+        assert!(renderer
+            .set_highlight_last_move(true)
+            .render(&game)
+            .contains('['));
+    }
+}
@@ -0,0 +1,504 @@
+use crate::chess_game_state_analyzer::is_in_check;
+use crate::error::ChessError;
+use crate::piece::{ChessPiece, PieceType};
+use crate::render::find_piece;
+use crate::{ChessGame, ChessMoveType, Color};
+use game_board::{get_square_name_from_row_and_col, Board};
+use std::error::Error;
+use std::fmt::Write;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The size pieces are drawn at before they are scaled to a square.
+const PIECE_SIZE: f64 = 45.0;
+
+/// An arrow drawn between two squares, such as a suggested move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arrow {
+    from: (usize, usize),
+    to: (usize, usize),
+}
+
+impl Arrow {
+    /// Creates an arrow from one square to another, given as `(column, row)`.
+    pub fn new(from: (usize, usize), to: (usize, usize)) -> Self {
+        Self { from, to }
+    }
+
+    /// Returns the square the arrow starts on.
+    pub fn get_from(&self) -> (usize, usize) {
+        self.from
+    }
+
+    /// Returns the square the arrow points to.
+    pub fn get_to(&self) -> (usize, usize) {
+        self.to
+    }
+}
+
+/// Draws boards as SVG images, for example to post positions to a web page or chat.
+///
+/// Pieces are drawn as shapes rather than font glyphs, so the image looks the same
+/// everywhere and can be rasterized without fonts. Only the optional coordinates are text.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::render::svg::{Arrow, SvgRenderer};
+/// use simple_chess::ChessGame;
+///
+/// let svg = SvgRenderer::new()
+///     .set_square_size(60)
+///     .set_arrows(&[Arrow::new((4, 1), (4, 3))])
+///     .render(&ChessGame::new());
+///
+/// assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="480" height="480""#));
+/// assert!(svg.ends_with("</svg>\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgRenderer {
+    square_size: u32,
+    perspective: Color,
+    coordinates: bool,
+    highlight_last_move: bool,
+    highlights: Vec<(usize, usize)>,
+    arrows: Vec<Arrow>,
+    light_color: String,
+    dark_color: String,
+    highlight_color: String,
+    check_color: String,
+    arrow_color: String,
+}
+
+impl SvgRenderer {
+    /// Creates a renderer drawing 45 pixel squares in brown and beige from White's side,
+    /// with the last move of a game highlighted.
+    pub fn new() -> Self {
+        Self {
+            square_size: 45,
+            perspective: Color::White,
+            coordinates: false,
+            highlight_last_move: true,
+            highlights: Vec::new(),
+            arrows: Vec::new(),
+            light_color: String::from("#f0d9b5"),
+            dark_color: String::from("#b58863"),
+            highlight_color: String::from("#cdd26a"),
+            check_color: String::from("#e03030"),
+            arrow_color: String::from("#15781b"),
+        }
+    }
+
+    /// Sets the width and height of a square in pixels.
+    pub fn set_square_size(mut self, square_size: u32) -> Self {
+        self.square_size = square_size.max(1);
+        self
+    }
+
+    /// Sets the player whose side of the board is drawn at the bottom.
+    pub fn set_perspective(mut self, perspective: Color) -> Self {
+        self.perspective = perspective;
+        self
+    }
+
+    /// Sets whether file letters and rank numbers are written on the edge squares.
+    pub fn set_coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Sets whether the squares of the last move of a game are highlighted.
+    pub fn set_highlight_last_move(mut self, highlight: bool) -> Self {
+        self.highlight_last_move = highlight;
+        self
+    }
+
+    /// Sets squares to highlight, given as `(column, row)`.
+    pub fn set_highlights(mut self, highlights: &[(usize, usize)]) -> Self {
+        self.highlights = highlights.to_vec();
+        self
+    }
+
+    /// Sets arrows to draw over the pieces.
+    pub fn set_arrows(mut self, arrows: &[Arrow]) -> Self {
+        self.arrows = arrows.to_vec();
+        self
+    }
+
+    /// Sets the colors of the light and dark squares, as SVG colors such as `#eeeed2`.
+    pub fn set_square_colors(mut self, light_color: &str, dark_color: &str) -> Self {
+        self.light_color = light_color.to_string();
+        self.dark_color = dark_color.to_string();
+        self
+    }
+
+    /// Sets the colors of highlighted squares and arrows.
+    pub fn set_highlight_colors(mut self, highlight_color: &str, arrow_color: &str) -> Self {
+        self.highlight_color = highlight_color.to_string();
+        self.arrow_color = arrow_color.to_string();
+        self
+    }
+
+    /// Draws the current position of a game, with its last move and a king in check
+    /// highlighted.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game to draw.
+    ///
+    /// # Returns
+    ///
+    /// A `String` holding the SVG document.
+    pub fn render(&self, game: &ChessGame) -> String {
+        let board = game.get_board();
+        let turn = game.get_current_players_turn();
+        let check = is_in_check(turn, board)
+            .then(|| find_piece(board, ChessPiece::new(PieceType::King, turn)))
+            .flatten();
+        self.render_after_move(board, game.get_last_move(), check)
+    }
+
+    /// Draws a board with the squares of the move that led to it highlighted, unless
+    /// highlighting the last move is turned off.
+    pub(crate) fn render_after_move(
+        &self,
+        board: &Board<ChessPiece>,
+        last_move: Option<&ChessMoveType>,
+        check: Option<(usize, usize)>,
+    ) -> String {
+        let mut highlights = self.highlights.clone();
+        if let Some(last_move) = last_move.filter(|_| self.highlight_last_move) {
+            highlights.push(last_move.get_original_position());
+            highlights.push(last_move.get_new_position());
+        }
+        self.render_svg(board, &highlights, check)
+    }
+
+    /// Draws a board with only the highlights and arrows of the renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board to draw.
+    ///
+    /// # Returns
+    ///
+    /// A `String` holding the SVG document.
+    pub fn render_board(&self, board: &Board<ChessPiece>) -> String {
+        self.render_svg(board, &self.highlights, None)
+    }
+
+    /// Draws the current position of a game like `render` and rasterizes it to a PNG image.
+    ///
+    /// Coordinates are left out of the image, as no fonts are loaded to draw them.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` - The bytes of the PNG file.
+    /// * `Err(RenderError)` - If the image could not be rasterized.
+    #[cfg(feature = "png")]
+    pub fn render_png(&self, game: &ChessGame) -> Result<Vec<u8>, RenderError> {
+        let svg = self.clone().set_coordinates(false).render(game);
+        rasterize(&svg)?
+            .encode_png()
+            .map_err(|e| RenderError::new(e.to_string()))
+    }
+
+    fn render_svg(
+        &self,
+        board: &Board<ChessPiece>,
+        highlights: &[(usize, usize)],
+        check: Option<(usize, usize)>,
+    ) -> String {
+        let size = self.square_size as f64;
+        let width = board.get_width() as f64 * size;
+        let height = board.get_height() as f64 * size;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        for row in 0..board.get_height() {
+            for col in 0..board.get_width() {
+                let (x, y) = self.get_square_corner(board, (col, row));
+                let color = if (col + row) % 2 == 1 {
+                    &self.light_color
+                } else {
+                    &self.dark_color
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{color}"/>"#
+                );
+                if highlights.contains(&(col, row)) {
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{}" fill-opacity="0.6"/>"#,
+                        self.highlight_color
+                    );
+                }
+                if check == Some((col, row)) {
+                    let _ = writeln!(
+                        svg,
+                        r#"<circle cx="{}" cy="{}" r="{}" fill="{}" fill-opacity="0.7"/>"#,
+                        x + size / 2.0,
+                        y + size / 2.0,
+                        size * 0.45,
+                        self.check_color
+                    );
+                }
+            }
+        }
+
+        if self.coordinates {
+            self.write_coordinates(&mut svg, board);
+        }
+
+        for row in 0..board.get_height() {
+            for col in 0..board.get_width() {
+                if let Some(piece) = board.get_piece_at_space(col, row) {
+                    let (x, y) = self.get_square_corner(board, (col, row));
+                    let _ = writeln!(
+                        svg,
+                        r##"<g transform="translate({x} {y}) scale({})" fill="{}" stroke="#000000" stroke-width="1.5" stroke-linejoin="round">{}</g>"##,
+                        size / PIECE_SIZE,
+                        match piece.get_color() {
+                            Color::White => "#ffffff",
+                            Color::Black => "#202020",
+                        },
+                        get_piece_shapes(piece.get_piece_type())
+                    );
+                }
+            }
+        }
+
+        for arrow in &self.arrows {
+            self.write_arrow(&mut svg, board, arrow);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn write_coordinates(&self, svg: &mut String, board: &Board<ChessPiece>) {
+        let size = self.square_size as f64;
+        let font_size = size * 0.22;
+        let (bottom_row, left_col) = match self.perspective {
+            Color::White => (0, 0),
+            Color::Black => (board.get_height() - 1, board.get_width() - 1),
+        };
+        for col in 0..board.get_width() {
+            let (x, y) = self.get_square_corner(board, (col, bottom_row));
+            let name = get_square_name_from_row_and_col(col, 0);
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="{font_size}" font-family="sans-serif" text-anchor="end">{}</text>"#,
+                x + size * 0.95,
+                y + size * 0.95,
+                name.trim_end_matches(|c: char| c.is_ascii_digit())
+            );
+        }
+        for row in 0..board.get_height() {
+            let (x, y) = self.get_square_corner(board, (left_col, row));
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="{font_size}" font-family="sans-serif">{}</text>"#,
+                x + size * 0.05,
+                y + font_size,
+                row + 1
+            );
+        }
+    }
+
+    fn write_arrow(&self, svg: &mut String, board: &Board<ChessPiece>, arrow: &Arrow) {
+        let size = self.square_size as f64;
+        let center = |square| {
+            let (x, y) = self.get_square_corner(board, square);
+            (x + size / 2.0, y + size / 2.0)
+        };
+        let (x1, y1) = center(arrow.from);
+        let (x2, y2) = center(arrow.to);
+        let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+        if length == 0.0 {
+            return;
+        }
+
+        // the line stops where the head starts so its end does not poke through the tip
+        let (dx, dy) = ((x2 - x1) / length, (y2 - y1) / length);
+        let head_length = size * 0.4;
+        let head_width = size * 0.25;
+        let (base_x, base_y) = (x2 - dx * head_length, y2 - dy * head_length);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x1}" y1="{y1}" x2="{base_x}" y2="{base_y}" stroke="{}" stroke-width="{}" stroke-opacity="0.8" stroke-linecap="round"/>"#,
+            self.arrow_color,
+            size * 0.15
+        );
+        let _ = writeln!(
+            svg,
+            r#"<polygon points="{x2},{y2} {},{} {},{}" fill="{}" fill-opacity="0.8"/>"#,
+            base_x - dy * head_width,
+            base_y + dx * head_width,
+            base_x + dy * head_width,
+            base_y - dx * head_width,
+            self.arrow_color
+        );
+    }
+
+    /// Returns the top left corner of a square in the image.
+    fn get_square_corner(
+        &self,
+        board: &Board<ChessPiece>,
+        (col, row): (usize, usize),
+    ) -> (f64, f64) {
+        let size = self.square_size as f64;
+        let (x, y) = match self.perspective {
+            Color::White => (col, board.get_height() - 1 - row),
+            Color::Black => (board.get_width() - 1 - col, row),
+        };
+        (x as f64 * size, y as f64 * size)
+    }
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws an SVG document into an image.
+#[cfg(feature = "png")]
+pub(crate) fn rasterize(svg: &str) -> Result<resvg::tiny_skia::Pixmap, RenderError> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default())
+        .map_err(|e| RenderError::new(e.to_string()))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| RenderError::new(String::from("the image is empty")))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Returns the shapes of a piece drawn in a 45 by 45 square.
+fn get_piece_shapes(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => concat!(
+            r#"<path d="M11 39 H34 V36 H11 Z"/>"#,
+            r#"<path d="M15 36 Q17 24 22.5 20 Q28 24 30 36 Z"/>"#,
+            r#"<circle cx="22.5" cy="14" r="5"/>"#
+        ),
+        PieceType::Knight => concat!(
+            r#"<path d="M11 39 H34 V36 H11 Z"/>"#,
+            r#"<path d="M13 36 H33 C33 26 31 16 24 11 L23 6 L20 10 L17 7 L16 12 C12 15 9 20 9 25 L12 27 L17 23 L20 23 C17 28 14 31 13 36 Z"/>"#
+        ),
+        PieceType::Bishop => concat!(
+            r#"<path d="M11 39 H34 V36 H11 Z"/>"#,
+            r#"<path d="M15 36 C15 28 18 20 22.5 13 C27 20 30 28 30 36 Z"/>"#,
+            r#"<circle cx="22.5" cy="10" r="2.5"/>"#
+        ),
+        PieceType::Rook => {
+            r#"<path d="M11 39 H34 V35 H32 L30 20 H33 V12 H29 V15 H25 V12 H20 V15 H16 V12 H12 V20 H15 L13 35 H11 Z"/>"#
+        }
+        PieceType::Queen => concat!(
+            r#"<path d="M11 39 H34 V36 L32 35 L36 18 L29 27 L29.5 14 L25 26 L22.5 12 L20 26 L15.5 14 L16 27 L9 18 L13 35 L11 36 Z"/>"#,
+            r#"<circle cx="9" cy="16" r="2"/><circle cx="15.5" cy="12" r="2"/><circle cx="22.5" cy="10" r="2"/>"#,
+            r#"<circle cx="29.5" cy="12" r="2"/><circle cx="36" cy="16" r="2"/>"#
+        ),
+        PieceType::King => concat!(
+            r#"<path d="M11 39 H34 V36 L32 35 L35 22 L27 25 L22.5 20 L18 25 L10 22 L13 35 L11 36 Z"/>"#,
+            r#"<path d="M21 6 H24 V10 H27 V13 H24 V20 H21 V13 H18 V10 H21 Z"/>"#
+        ),
+        // the compound pieces are drawn as a knight's head with a bishop's mitre or a rook's
+        // battlements on top
+        PieceType::Archbishop => concat!(
+            r#"<path d="M11 39 H34 V36 H11 Z"/>"#,
+            r#"<path d="M13 36 H33 C33 28 31 20 25 16 L23 12 L20 15 L17 13 L16 17 C12 20 10 24 10 28 L13 30 L17 27 L20 27 C17 31 14 33 13 36 Z"/>"#,
+            r#"<path d="M19 12 C19 9 21 6 22.5 4 C24 6 26 9 26 12 Z"/>"#
+        ),
+        PieceType::Chancellor => concat!(
+            r#"<path d="M11 39 H34 V36 H11 Z"/>"#,
+            r#"<path d="M13 36 H33 C33 28 31 20 25 16 L23 12 L20 15 L17 13 L16 17 C12 20 10 24 10 28 L13 30 L17 27 L20 27 C17 31 14 33 13 36 Z"/>"#,
+            r#"<path d="M17 12 V5 H20 V8 H21.5 V5 H23.5 V8 H25 V5 H28 V12 Z"/>"#
+        ),
+    }
+}
+
+/// Error returned when a board image can not be produced.
+pub struct RenderError {
+    reason: String,
+}
+
+impl RenderError {
+    #[cfg_attr(not(feature = "png"), allow(dead_code))]
+    pub(crate) fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Render Error: {}", self.reason)
+    }
+}
+
+impl Debug for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RenderError: {}", self.reason)
+    }
+}
+
+impl Error for RenderError {}
+
+impl From<RenderError> for ChessError {
+    fn from(error: RenderError) -> Self {
+        ChessError::Render(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+
+    #[test]
+    fn every_piece_is_drawn() {
+        let svg = SvgRenderer::new().render(&ChessGame::new());
+        assert_eq!(64, svg.matches("<rect").count());
+        assert_eq!(32, svg.matches("<g transform").count());
+    }
+
+    #[test]
+    fn perspective_flips_the_squares() {
+        let game = build_game_from_string("7k/8/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let white = SvgRenderer::new().set_square_size(10).render(&game);
+        let black = SvgRenderer::new()
+            .set_square_size(10)
+            .set_perspective(Color::Black)
+            .render(&game);
+
+        // the white king on a1 is at the bottom left for white and the top right for black
+        assert!(white.contains(r#"translate(0 70)"#));
+        assert!(black.contains(r#"translate(70 0)"#));
+    }
+
+    #[test]
+    fn highlights_checks_and_arrows_are_drawn() {
+        let game = build_game_from_string("4k3/8/8/8/8/8/8/4RK2 b - - 0 1").unwrap();
+        let svg = SvgRenderer::new()
+            .set_highlights(&[(0, 0)])
+            .set_arrows(&[Arrow::new((4, 7), (3, 7))])
+            .set_coordinates(true)
+            .render(&game);
+
+        assert_eq!(1, svg.matches("fill-opacity=\"0.6\"").count());
+        assert_eq!(1, svg.matches("#e03030").count());
+        assert_eq!(1, svg.matches("<polygon").count());
+        assert_eq!(16, svg.matches("<text").count());
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn boards_are_rasterized() {
+        let png = SvgRenderer::new().render_png(&ChessGame::new()).unwrap();
+        assert_eq!(b"\x89PNG", &png[..4]);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::codec::binary::encode_position_as_binary;
+use crate::zobrist::hash_position;
+use crate::ChessGame;
+use std::collections::HashMap;
+
+/// Identifies a position for the purpose of detecting repetitions.
+///
+/// A `PositionKey` pairs the Zobrist hash of a position with its compact binary encoding.
+/// The hash is used to quickly find candidate positions, and the encoding is used as a
+/// secondary key to tell apart the rare positions whose hashes collide.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PositionKey {
+    hash: u64,
+    encoded_position: Vec<u8>,
+}
+
+impl PositionKey {
+    /// Creates the `PositionKey` of the current position of a game.
+    ///
+    /// # Example
+    /// ```
+    /// use simple_chess::repetition::PositionKey;
+    /// use simple_chess::ChessGame;
+    ///
+    /// let key = PositionKey::new(&ChessGame::new());
+    /// assert_eq!(34, key.get_encoded_position().len());
+    /// ```
+    pub fn new(game: &ChessGame) -> Self {
+        Self {
+            hash: hash_position(game),
+            encoded_position: encode_position_as_binary(game),
+        }
+    }
+
+    /// Returns the Zobrist hash of the position.
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the compact binary encoding of the position.
+    pub fn get_encoded_position(&self) -> &[u8] {
+        &self.encoded_position
+    }
+}
+
+/// Counts how many times positions have occurred.
+///
+/// Positions are bucketed by Zobrist hash, and positions within a bucket are compared by
+/// their compact binary encoding, so a hash collision never causes two different positions
+/// to be counted as a repetition.
+///
+/// # Example
+/// ```
+/// use simple_chess::repetition::{PositionKey, RepetitionTable};
+/// use simple_chess::ChessGame;
+///
+/// let mut table = RepetitionTable::new();
+/// let key = PositionKey::new(&ChessGame::new());
+///
+/// assert_eq!(1, table.record(key.clone()));
+/// assert_eq!(2, table.record(key.clone()));
+/// assert_eq!(2, table.get_count(&key));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RepetitionTable {
+    positions: HashMap<u64, Vec<(Vec<u8>, usize)>>,
+}
+
+impl RepetitionTable {
+    /// Creates an empty `RepetitionTable`.
+    pub fn new() -> Self {
+        Self {
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Records an occurrence of a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The `PositionKey` of the position that occurred.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of times the position has occurred, including this one.
+    pub fn record(&mut self, key: PositionKey) -> usize {
+        let bucket = self.positions.entry(key.hash).or_default();
+        for (encoded_position, count) in bucket.iter_mut() {
+            if *encoded_position == key.encoded_position {
+                *count += 1;
+                return *count;
+            }
+        }
+        bucket.push((key.encoded_position, 1));
+        1
+    }
+
+    /// Returns the number of times a position has occurred.
+    pub fn get_count(&self, key: &PositionKey) -> usize {
+        match self.positions.get(&key.hash) {
+            Some(bucket) => bucket
+                .iter()
+                .find(|(encoded_position, _)| *encoded_position == key.encoded_position)
+                .map(|(_, count)| *count)
+                .unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Returns the highest number of times any single position has occurred.
+    pub fn get_max_count(&self) -> usize {
+        self.positions
+            .values()
+            .flatten()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Removes every recorded position.
+    ///
+    /// This should be called after irreversible moves, such as captures and pawn moves,
+    /// since no position before them can occur again.
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+
+    /// Returns `true` if no positions have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns every recorded position as its hash, encoding and count, sorted so the
+    /// order does not depend on how the positions are stored.
+    pub(crate) fn get_entries(&self) -> Vec<(u64, &[u8], usize)> {
+        let mut entries: Vec<(u64, &[u8], usize)> = self
+            .positions
+            .iter()
+            .flat_map(|(hash, bucket)| {
+                bucket.iter().map(move |(encoded_position, count)| {
+                    (*hash, encoded_position.as_slice(), *count)
+                })
+            })
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Sets how many times a position has occurred, for restoring a saved table.
+    pub(crate) fn set_count(&mut self, hash: u64, encoded_position: Vec<u8>, count: usize) {
+        let bucket = self.positions.entry(hash).or_default();
+        match bucket
+            .iter_mut()
+            .find(|(encoded, _)| *encoded == encoded_position)
+        {
+            Some((_, existing_count)) => *existing_count = count,
+            None => bucket.push((encoded_position, count)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colliding_hashes_are_told_apart_by_encoding() {
+        let mut table = RepetitionTable::new();
+        let key_a = PositionKey {
+            hash: 42,
+            encoded_position: vec![1, 2, 3],
+        };
+        let key_b = PositionKey {
+            hash: 42,
+            encoded_position: vec![3, 2, 1],
+        };
+
+        assert_eq!(1, table.record(key_a.clone()));
+        assert_eq!(1, table.record(key_b.clone()));
+        assert_eq!(2, table.record(key_a.clone()));
+        assert_eq!(2, table.get_count(&key_a));
+        assert_eq!(1, table.get_count(&key_b));
+        assert_eq!(2, table.get_max_count());
+    }
+
+    #[test]
+    fn clearing_the_table_forgets_positions() {
+        let mut table = RepetitionTable::new();
+        let key = PositionKey::new(&ChessGame::new());
+        table.record(key.clone());
+        assert!(!table.is_empty());
+
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(0, table.get_count(&key));
+        assert_eq!(0, table.get_max_count());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::Color;
+
+/// A collection of tunable rule parameters used when generating moves.
+///
+/// The default `Ruleset` describes standard chess. Custom board heights and
+/// handicap setups can adjust the parameters so that move generation does not
+/// rely on hard-coded rank numbers.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::ruleset::{PawnRules, Ruleset};
+///
+/// let ruleset = Ruleset::new().set_pawn_rules(PawnRules::new().set_can_double_step(false));
+/// assert!(!ruleset.get_pawn_rules().can_double_step());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ruleset {
+    pawn_rules: PawnRules,
+    study_mode: bool,
+    castling_distance: usize,
+}
+
+impl Ruleset {
+    /// Creates a new `Ruleset` describing standard chess.
+    pub fn new() -> Self {
+        Self {
+            pawn_rules: PawnRules::new(),
+            study_mode: false,
+            castling_distance: 2,
+        }
+    }
+
+    /// Creates a new `Ruleset` describing Capablanca chess, where the king moves three
+    /// squares when castling.
+    pub fn capablanca() -> Self {
+        Self::new().set_castling_distance(3)
+    }
+
+    /// Returns the rules used to generate pawn moves.
+    pub fn get_pawn_rules(&self) -> &PawnRules {
+        &self.pawn_rules
+    }
+
+    /// Sets the rules used to generate pawn moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `pawn_rules` - The `PawnRules` to use.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Ruleset` with the pawn rules set.
+    pub fn set_pawn_rules(mut self, pawn_rules: PawnRules) -> Self {
+        self.pawn_rules = pawn_rules;
+        self
+    }
+
+    /// Returns `true` if the ruleset is in study mode.
+    pub fn is_study_mode(&self) -> bool {
+        self.study_mode
+    }
+
+    /// Sets study mode, for teaching positions and composed exercises that do not have
+    /// exactly one king per side.
+    ///
+    /// In study mode every move a piece can make is legal, kings may be left attacked or
+    /// captured, and a game is never in check or checkmate. A position where the player to
+    /// move has no moves at all is a stalemate.
+    ///
+    /// # Arguments
+    ///
+    /// * `study_mode` - `true` to turn study mode on.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Ruleset` with study mode set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::chess_game_state_analyzer::GameState;
+    /// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+    /// use simple_chess::ruleset::Ruleset;
+    /// use simple_chess::{ChessGameBuilder, Color};
+    ///
+    /// // a lone knight with no kings on the board, for practicing knight moves
+    /// let position = build_game_from_string("8/8/8/8/3N4/8/8/8 w - - 0 1").unwrap();
+    /// let mut game = ChessGameBuilder::new()
+    ///     .set_board(position.get_board().clone())
+    ///     .set_current_turn(Color::White)
+    ///     .set_castle_rights(false, false, false, false)
+    ///     .set_ruleset(Ruleset::new().set_study_mode(true))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// match game.get_game_state() {
+    ///     GameState::InProgress { legal_moves, .. } => assert_eq!(8, legal_moves.len()),
+    ///     _ => panic!("the game should be in progress"),
+    /// }
+    /// ```
+    pub fn set_study_mode(mut self, study_mode: bool) -> Self {
+        self.study_mode = study_mode;
+        self
+    }
+
+    /// Returns the number of squares the king moves towards the rook when castling.
+    pub fn get_castling_distance(&self) -> usize {
+        self.castling_distance
+    }
+
+    /// Sets the number of squares the king moves towards the rook when castling, `2` in
+    /// standard chess and `3` in Capablanca chess. The rook always lands on the square the
+    /// king crossed last.
+    ///
+    /// # Arguments
+    ///
+    /// * `castling_distance` - The number of squares the king moves, at least `1`.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `Ruleset` with the castling distance set.
+    pub fn set_castling_distance(mut self, castling_distance: usize) -> Self {
+        self.castling_distance = castling_distance.max(1);
+        self
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pawn specific rule parameters.
+///
+/// Ranks are expressed as offsets so that the same rules apply to both colors
+/// and to boards of any height:
+///
+/// - `starting_rank_offset` is counted from the pawn owner's back rank, a value of `1`
+///   means white pawns start on the second row and black pawns on the second to last row.
+/// - `promotion_rank_offset` is counted from the opponent's back rank, a value of `0`
+///   means pawns promote on the last row of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PawnRules {
+    starting_rank_offset: usize,
+    can_double_step: bool,
+    promotion_rank_offset: usize,
+}
+
+impl PawnRules {
+    /// Creates a new `PawnRules` matching standard chess, pawns start one rank
+    /// in from their back rank, may move two squares from there, and promote on
+    /// the last rank.
+    pub fn new() -> Self {
+        Self {
+            starting_rank_offset: 1,
+            can_double_step: true,
+            promotion_rank_offset: 0,
+        }
+    }
+
+    /// Sets how many ranks in from their own back rank pawns start.
+    pub fn set_starting_rank_offset(mut self, starting_rank_offset: usize) -> Self {
+        self.starting_rank_offset = starting_rank_offset;
+        self
+    }
+
+    /// Sets if pawns on their starting rank may move two squares forward.
+    pub fn set_can_double_step(mut self, can_double_step: bool) -> Self {
+        self.can_double_step = can_double_step;
+        self
+    }
+
+    /// Sets how many ranks in from the opponent's back rank pawns promote.
+    pub fn set_promotion_rank_offset(mut self, promotion_rank_offset: usize) -> Self {
+        self.promotion_rank_offset = promotion_rank_offset;
+        self
+    }
+
+    /// Returns the offset of the starting rank from a pawn's own back rank.
+    pub fn get_starting_rank_offset(&self) -> usize {
+        self.starting_rank_offset
+    }
+
+    /// Returns the offset of the promotion rank from the opponent's back rank.
+    pub fn get_promotion_rank_offset(&self) -> usize {
+        self.promotion_rank_offset
+    }
+
+    /// Returns `true` if pawns on their starting rank may move two squares forward.
+    pub fn can_double_step(&self) -> bool {
+        self.can_double_step
+    }
+
+    /// Returns the row pawns of the given color start on.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the pawn.
+    /// * `board_height` - The height of the board the pawn is on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ruleset::PawnRules;
+    /// use simple_chess::Color::{Black, White};
+    ///
+    /// let rules = PawnRules::new();
+    /// assert_eq!(1, rules.get_starting_row(White, 8));
+    /// assert_eq!(6, rules.get_starting_row(Black, 8));
+    /// assert_eq!(8, rules.get_starting_row(Black, 10));
+    /// ```
+    pub fn get_starting_row(&self, color: Color, board_height: usize) -> usize {
+        let offset = self.starting_rank_offset.min(board_height - 1);
+        color.relative_rank(offset, board_height)
+    }
+
+    /// Returns the row pawns of the given color promote on.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the pawn.
+    /// * `board_height` - The height of the board the pawn is on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::ruleset::PawnRules;
+    /// use simple_chess::Color::{Black, White};
+    ///
+    /// let rules = PawnRules::new().set_promotion_rank_offset(1);
+    /// assert_eq!(6, rules.get_promotion_row(White, 8));
+    /// assert_eq!(1, rules.get_promotion_row(Black, 8));
+    /// ```
+    pub fn get_promotion_row(&self, color: Color, board_height: usize) -> usize {
+        let offset = self.promotion_rank_offset.min(board_height - 1);
+        color.relative_rank(board_height - 1 - offset, board_height)
+    }
+}
+
+impl Default for PawnRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color::{Black, White};
+
+    #[test]
+    fn default_rules_match_standard_chess() {
+        let rules = Ruleset::new();
+        let pawn_rules = rules.get_pawn_rules();
+
+        assert!(pawn_rules.can_double_step());
+        assert_eq!(1, pawn_rules.get_starting_row(White, 8));
+        assert_eq!(6, pawn_rules.get_starting_row(Black, 8));
+        assert_eq!(7, pawn_rules.get_promotion_row(White, 8));
+        assert_eq!(0, pawn_rules.get_promotion_row(Black, 8));
+    }
+
+    #[test]
+    fn rows_are_relative_to_board_height() {
+        let pawn_rules = PawnRules::new()
+            .set_starting_rank_offset(2)
+            .set_promotion_rank_offset(1);
+
+        assert_eq!(2, pawn_rules.get_starting_row(White, 12));
+        assert_eq!(9, pawn_rules.get_starting_row(Black, 12));
+        assert_eq!(10, pawn_rules.get_promotion_row(White, 12));
+        assert_eq!(1, pawn_rules.get_promotion_row(Black, 12));
+    }
+
+    #[test]
+    fn offsets_are_clamped_to_the_board() {
+        let pawn_rules = PawnRules::new()
+            .set_starting_rank_offset(10)
+            .set_promotion_rank_offset(10);
+
+        assert_eq!(2, pawn_rules.get_starting_row(White, 3));
+        assert_eq!(0, pawn_rules.get_starting_row(Black, 3));
+        assert_eq!(0, pawn_rules.get_promotion_row(White, 3));
+        assert_eq!(2, pawn_rules.get_promotion_row(Black, 3));
+    }
+}
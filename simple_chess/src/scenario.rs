@@ -0,0 +1,445 @@
+use crate::chess_game_state_analyzer::GameState;
+use crate::codec::long_algebraic_notation::encode_move_as_string;
+use crate::codec::standard_algebraic_notation::SanParser;
+use crate::error::ChessError;
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, ChessGameBuilder, ChessMoveType, Color};
+use game_board::{get_column_and_row_from_square_name, Board};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Sets up and checks a position from a short script, for writing tests that read like a
+/// description of the position.
+///
+/// Each line of the script is one command, blank lines and lines starting with `#` are
+/// ignored. The position is described first:
+///
+/// - `board 10x8` - The size of the board, `8x8` when not given. Must come first.
+/// - `white king e1` or `black pawns a7 b7 c7` - Places pieces, piece names may be plural.
+/// - `turn black` - The player to move, white when not given.
+/// - `castling Kq` - The castling rights in FEN letters, none when not given.
+/// - `halfmove 12` and `fullmove 40` - The fifty-move counter and the turn number.
+///
+/// Then moves can be played and the position checked, the first of these commands builds
+/// the game and no more pieces can be placed after it:
+///
+/// - `play e4` - Plays a move written in SAN, leniently, or in long algebraic notation.
+/// - `expect legal O-O` and `expect illegal Kf2` - Checks whether a move can be played.
+/// - `expect moves 20` - Checks the number of legal moves.
+/// - `expect state checkmate` - Checks the state of the game, one of `in progress`,
+///   `check`, `checkmate`, `stalemate`, `dead position` or `variant win`.
+///
+/// # Returns
+///
+/// * `Ok(ChessGame)` - The game after every command was run.
+/// * `Err(ScenarioError)` - The first command that could not be run or whose expectation
+///   was not met, with its line number.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::scenario::run_scenario;
+///
+/// let game = run_scenario(
+///     "
+///     white king e1, rook h1
+///     black king e8
+///     castling K
+///
+///     expect legal O-O
+///     play O-O
+///     expect illegal Kf8
+///     play Kd7
+///     expect moves 16
+///     ",
+/// )
+/// .unwrap();
+/// assert_eq!(2, game.get_moves().len());
+///
+/// let error = run_scenario("white king e1\nblack king e8\nexpect state check").err().unwrap();
+/// assert!(error.to_string().contains("line 3"));
+/// ```
+pub fn run_scenario(script: &str) -> Result<ChessGame, ScenarioError> {
+    let mut scenario = Scenario::new();
+    for (index, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        scenario
+            .run_command(line)
+            .map_err(|reason| ScenarioError::new(index + 1, line, reason))?;
+    }
+    scenario
+        .get_game()
+        .map_err(|reason| ScenarioError::new(script.lines().count(), "", reason))
+}
+
+struct Scenario {
+    board: Option<Board<ChessPiece>>,
+    builder: Option<ChessGameBuilder>,
+    game: Option<ChessGame>,
+}
+
+impl Scenario {
+    fn new() -> Self {
+        Self {
+            board: None,
+            builder: Some(
+                ChessGameBuilder::new()
+                    .set_current_turn(Color::White)
+                    .set_turn_number(1)
+                    .set_castle_rights(false, false, false, false),
+            ),
+            game: None,
+        }
+    }
+
+    fn is_untouched(&self) -> bool {
+        self.board.is_none() && self.game.is_none()
+    }
+
+    fn run_command(&mut self, line: &str) -> Result<(), String> {
+        let (command, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let arguments = arguments.trim();
+        match command {
+            "board" if self.is_untouched() => {
+                let (width, height) = arguments
+                    .split_once('x')
+                    .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+                    .ok_or_else(|| format!("'{arguments}' is not a board size such as 8x8"))?;
+                self.board = Some(Board::try_build(width, height).map_err(|e| e.to_string())?);
+                Ok(())
+            }
+            "board" => Err(String::from("the board size must be set first")),
+            "white" => self.place_pieces(Color::White, arguments),
+            "black" => self.place_pieces(Color::Black, arguments),
+            "turn" => {
+                let color = parse_color(arguments)?;
+                self.update_builder(|builder| builder.set_current_turn(color))
+            }
+            "castling" => {
+                let rights = if arguments == "-" { "" } else { arguments };
+                if let Some(invalid) = rights.chars().find(|c| !"KQkq".contains(*c)) {
+                    return Err(format!("'{invalid}' is not a castling right"));
+                }
+                self.update_builder(|builder| {
+                    builder.set_castle_rights(
+                        rights.contains('K'),
+                        rights.contains('Q'),
+                        rights.contains('k'),
+                        rights.contains('q'),
+                    )
+                })
+            }
+            "halfmove" => {
+                let counter = parse_number(arguments)?;
+                self.update_builder(|builder| builder.set_fifty_move_rule_counter(counter))
+            }
+            "fullmove" => {
+                let turn_number = parse_number(arguments)?;
+                self.update_builder(|builder| builder.set_turn_number(turn_number))
+            }
+            "play" => {
+                let game = self.get_game_mut()?;
+                let chess_move = find_move(game, arguments)?
+                    .ok_or_else(|| format!("'{arguments}' is not a legal move"))?;
+                game.make_move(chess_move);
+                Ok(())
+            }
+            "expect" => self.check_expectation(arguments),
+            _ => Err(format!("unknown command '{command}'")),
+        }
+    }
+
+    fn place_pieces(&mut self, color: Color, arguments: &str) -> Result<(), String> {
+        if self.game.is_some() {
+            return Err(String::from(
+                "pieces can not be placed after the game has started",
+            ));
+        }
+        let board = self
+            .board
+            .get_or_insert_with(|| Board::build(8, 8).expect("an 8x8 board can be built"));
+
+        // a list such as "king e1, rook a1 h1"
+        for group in arguments.split(',') {
+            let mut words = group.split_whitespace();
+            let name = words
+                .next()
+                .ok_or_else(|| String::from("a piece is missing"))?;
+            let piece = ChessPiece::new(parse_piece_type(name)?, color);
+            let mut placed = false;
+            for square in words {
+                let (col, row) = parse_square(square)?;
+                board
+                    .try_place_piece(piece, col, row)
+                    .map_err(|e| format!("{square} is not on the board, {e}"))?;
+                placed = true;
+            }
+            if !placed {
+                return Err(format!("no square was given for the {name}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn update_builder(
+        &mut self,
+        update: impl FnOnce(ChessGameBuilder) -> ChessGameBuilder,
+    ) -> Result<(), String> {
+        let builder = self.builder.take().ok_or_else(|| {
+            String::from("the position can not be changed after the game has started")
+        })?;
+        self.builder = Some(update(builder));
+        Ok(())
+    }
+
+    fn get_game_mut(&mut self) -> Result<&mut ChessGame, String> {
+        if self.game.is_none() {
+            let builder = self
+                .builder
+                .take()
+                .expect("the builder is only taken to build the game");
+            let board = self
+                .board
+                .take()
+                .ok_or_else(|| String::from("no pieces have been placed"))?;
+            self.game = Some(
+                builder
+                    .set_board(board)
+                    .build()
+                    .map_err(|e| e.to_string())?,
+            );
+        }
+        Ok(self.game.as_mut().expect("the game was built above"))
+    }
+
+    fn get_game(mut self) -> Result<ChessGame, String> {
+        self.get_game_mut()?;
+        Ok(self.game.expect("the game was built above"))
+    }
+
+    fn check_expectation(&mut self, arguments: &str) -> Result<(), String> {
+        let (kind, value) = arguments
+            .split_once(char::is_whitespace)
+            .map(|(kind, value)| (kind, value.trim()))
+            .unwrap_or((arguments, ""));
+        let game = self.get_game_mut()?;
+        match kind {
+            "legal" => match find_move(game, value)? {
+                Some(_) => Ok(()),
+                None => Err(format!("expected {value} to be legal")),
+            },
+            "illegal" => match find_move(game, value)? {
+                Some(_) => Err(format!("expected {value} to be illegal")),
+                None => Ok(()),
+            },
+            "moves" => {
+                let expected = parse_number(value)?;
+                let actual = game.get_cached_legal_moves().len();
+                if actual == expected {
+                    Ok(())
+                } else {
+                    Err(format!("expected {expected} legal moves, found {actual}"))
+                }
+            }
+            "state" => {
+                let actual = match game.get_game_state() {
+                    GameState::InProgress { .. } => "in progress",
+                    GameState::Check { .. } => "check",
+                    GameState::Checkmate { .. } => "checkmate",
+                    GameState::Stalemate => "stalemate",
+                    GameState::VariantWin { .. } => "variant win",
+                    GameState::Resigned { .. } => "resigned",
+                    GameState::DrawAgreed => "draw agreed",
+                    GameState::DeadPosition => "dead position",
+                    GameState::SeventyFiveMoveRule => "seventy-five-move rule",
+                };
+                if actual == value {
+                    Ok(())
+                } else {
+                    Err(format!("expected the state to be {value}, found {actual}"))
+                }
+            }
+            _ => Err(format!("unknown expectation '{kind}'")),
+        }
+    }
+}
+
+/// Finds the legal move a piece of move text describes, in SAN or long algebraic notation.
+fn find_move(game: &mut ChessGame, text: &str) -> Result<Option<ChessMoveType>, String> {
+    if text.is_empty() {
+        return Err(String::from("a move is missing"));
+    }
+    if let Ok(chess_move) = SanParser::new().set_lenient(true).parse(game, text) {
+        return Ok(Some(chess_move));
+    }
+    Ok(game
+        .get_cached_legal_moves()
+        .iter()
+        .find(|chess_move| encode_move_as_string(chess_move) == text)
+        .copied())
+}
+
+fn parse_piece_type(name: &str) -> Result<PieceType, String> {
+    let singular = name.strip_suffix('s').unwrap_or(name);
+    match singular {
+        "king" => Ok(PieceType::King),
+        "queen" => Ok(PieceType::Queen),
+        "rook" => Ok(PieceType::Rook),
+        "bishop" => Ok(PieceType::Bishop),
+        "knight" => Ok(PieceType::Knight),
+        "pawn" => Ok(PieceType::Pawn),
+        _ => Err(format!("'{name}' is not a piece")),
+    }
+}
+
+fn parse_square(name: &str) -> Result<(usize, usize), String> {
+    let letters = name.chars().take_while(char::is_ascii_lowercase).count();
+    let digits = &name[letters..];
+    let is_valid = letters > 0
+        && !digits.is_empty()
+        && digits.len() <= 6
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && !digits.starts_with('0');
+    if !is_valid {
+        return Err(format!("'{name}' is not a square"));
+    }
+    get_column_and_row_from_square_name(name).map_err(|_| format!("'{name}' is not a square"))
+}
+
+fn parse_color(name: &str) -> Result<Color, String> {
+    match name {
+        "white" => Ok(Color::White),
+        "black" => Ok(Color::Black),
+        _ => Err(format!("'{name}' is not a color")),
+    }
+}
+
+fn parse_number(text: &str) -> Result<usize, String> {
+    text.parse()
+        .map_err(|_| format!("'{text}' is not a number"))
+}
+
+/// Error returned when a scenario script can not be run or one of its expectations fails.
+pub struct ScenarioError {
+    reason: String,
+}
+
+impl ScenarioError {
+    fn new(line_number: usize, line: &str, reason: String) -> Self {
+        let reason = if line.is_empty() {
+            reason
+        } else {
+            format!("line {line_number} '{line}': {reason}")
+        };
+        Self { reason }
+    }
+}
+
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Scenario Error: {}", self.reason)
+    }
+}
+
+impl Debug for ScenarioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ScenarioError: {}", self.reason)
+    }
+}
+
+impl Error for ScenarioError {}
+
+impl From<ScenarioError> for ChessError {
+    fn from(error: ScenarioError) -> Self {
+        ChessError::Scenario(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::encode_game_as_string;
+
+    #[test]
+    fn scenarios_describe_the_same_position_as_fen() {
+        let game = run_scenario(
+            "
+            # the position after 1. e4
+            white king e1, queen d1, rooks a1 h1, bishops c1 f1, knights b1 g1
+            white pawns a2 b2 c2 d2 e4 f2 g2 h2
+            black king e8, queen d8, rooks a8 h8, bishops c8 f8, knights b8 g8
+            black pawns a7 b7 c7 d7 e7 f7 g7 h7
+            turn black
+            castling KQkq
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+            encode_game_as_string(&game)
+        );
+    }
+
+    #[test]
+    fn en_passant_is_set_up_by_playing_the_double_step() {
+        run_scenario(
+            "
+            white king e1, pawn e5
+            black king e8, pawn d7
+            turn black
+            play d5
+            expect legal exd6
+            play exd6
+            expect moves 4
+            ",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn checkmate_can_be_expected() {
+        run_scenario(
+            "
+            white king g1, rook a1
+            black king g8, pawns f7 g7 h7
+            expect illegal Ra9
+            play Ra8#
+            expect state checkmate
+            expect moves 0
+            ",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn mistakes_in_a_scenario_name_their_line() {
+        let errors = [
+            ("white king z", "line 1"),
+            ("white wizard e4", "'wizard' is not a piece"),
+            (
+                "white king e1\nblack king e8\nplay e4",
+                "'e4' is not a legal move",
+            ),
+            (
+                "white king e1\nblack king e8\nexpect moves 3",
+                "expected 3 legal moves, found 5",
+            ),
+            (
+                "white king e1\nplay Kd1\nwhite queen d8",
+                "after the game has started",
+            ),
+            ("white king e1\nboard 10x8", "must be set first"),
+            ("turn white", "no pieces have been placed"),
+        ];
+        for (script, message) in errors {
+            let error = run_scenario(script).err().unwrap().to_string();
+            assert!(error.contains(message), "{error}");
+        }
+
+        let game = run_scenario("board 10x8\nwhite king j1\nblack king a8").unwrap();
+        assert_eq!(10, game.get_board().get_width());
+    }
+}
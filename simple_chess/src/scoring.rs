@@ -0,0 +1,141 @@
+use crate::chess_game_state_analyzer::GameState;
+use crate::Color;
+
+/// How the end of a game is scored.
+///
+/// The `GameState` of a finished game tells how it ended, such as by stalemate or
+/// resignation. The scoring turns that into the outcome the players are credited with,
+/// which is usually the same, except that some events count a draw as a win for one side.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::scoring::{Outcome, Scoring};
+/// use simple_chess::{ChessGame, Color};
+///
+/// // black has less time but wins every drawn game
+/// let mut game = ChessGame::new();
+/// game.set_scoring(Scoring::Armageddon {
+///     draw_winner: Color::Black,
+/// });
+/// game.offer_draw(Color::White).unwrap();
+/// game.accept_draw().unwrap();
+///
+/// assert_eq!(
+///     Some(Outcome::Win {
+///         winner: Color::Black
+///     }),
+///     game.get_outcome()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scoring {
+    /// A won game is a win and a drawn game is a draw.
+    #[default]
+    Standard,
+    /// Armageddon, a drawn game counts as a win for `draw_winner`, usually black, who plays
+    /// with less time on the clock in exchange.
+    Armageddon { draw_winner: Color },
+}
+
+impl Scoring {
+    /// Returns the outcome a game that ended in a state is scored as.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state of the game.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Outcome)` - The scored outcome of a finished game.
+    /// * `None` - If the game is still in progress.
+    pub fn get_outcome(&self, state: &GameState) -> Option<Outcome> {
+        let outcome = match state {
+            GameState::InProgress { .. } | GameState::Check { .. } => return None,
+            GameState::Checkmate { winner }
+            | GameState::VariantWin { winner }
+            | GameState::Resigned { winner } => Outcome::Win { winner: *winner },
+            GameState::Stalemate
+            | GameState::DrawAgreed
+            | GameState::DeadPosition
+            | GameState::SeventyFiveMoveRule => Outcome::Draw,
+        };
+        match (self, outcome) {
+            (Scoring::Armageddon { draw_winner }, Outcome::Draw) => Some(Outcome::Win {
+                winner: *draw_winner,
+            }),
+            _ => Some(outcome),
+        }
+    }
+}
+
+/// The scored outcome of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The game was won by `winner`.
+    Win { winner: Color },
+    /// The game was drawn.
+    Draw,
+}
+
+impl Outcome {
+    /// Returns the winner of the game, `None` for a draw.
+    pub fn get_winner(&self) -> Option<Color> {
+        match self {
+            Outcome::Win { winner } => Some(*winner),
+            Outcome::Draw => None,
+        }
+    }
+
+    /// Returns the outcome as it is written in PGN, `1-0`, `0-1` or `1/2-1/2`.
+    pub fn as_pgn_str(&self) -> &'static str {
+        match self {
+            Outcome::Win {
+                winner: Color::White,
+            } => "1-0",
+            Outcome::Win {
+                winner: Color::Black,
+            } => "0-1",
+            Outcome::Draw => "1/2-1/2",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armageddon_only_changes_draws() {
+        let armageddon = Scoring::Armageddon {
+            draw_winner: Color::Black,
+        };
+        let white_wins = Outcome::Win {
+            winner: Color::White,
+        };
+
+        assert_eq!(
+            Some(Outcome::Draw),
+            Scoring::Standard.get_outcome(&GameState::Stalemate)
+        );
+        assert_eq!(
+            Some(Outcome::Win {
+                winner: Color::Black
+            }),
+            armageddon.get_outcome(&GameState::Stalemate)
+        );
+        assert_eq!(
+            Some(white_wins),
+            armageddon.get_outcome(&GameState::Checkmate {
+                winner: Color::White
+            })
+        );
+        assert_eq!(
+            None,
+            armageddon.get_outcome(&GameState::InProgress {
+                legal_moves: Vec::new(),
+                turn: Color::White,
+            })
+        );
+    }
+}
@@ -0,0 +1,523 @@
+use crate::chess_board::ChessBoard;
+use crate::codec::forsyth_edwards_notation::build_game_from_string;
+use crate::error::ChessError;
+use crate::piece::PieceType::{Archbishop, Bishop, Chancellor, King, Knight, Pawn, Queen, Rook};
+use crate::piece::{ChessPiece, PieceType};
+use crate::ruleset::Ruleset;
+use crate::validation::InvariantViolation;
+use crate::variant::{Capablanca, Horde, StandardChess, Variant};
+use crate::{ChessGame, ChessGameBuilder, Color};
+use game_board::Board;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+
+/// The back rank of standard chess, from the a-file to the h-file.
+const STANDARD_BACK_RANK: [PieceType; 8] =
+    [Rook, Knight, Bishop, Queen, King, Bishop, Knight, Rook];
+
+/// The back rank of Capablanca chess, from the a-file to the j-file.
+const CAPABLANCA_BACK_RANK: [PieceType; 10] = [
+    Rook, Knight, Archbishop, Bishop, Queen, King, Bishop, Chancellor, Knight, Rook,
+];
+
+/// Describes the starting position of a game, so variants with asymmetric armies can be
+/// set up without placing every piece by hand.
+///
+/// A setup starts from an empty board. Pieces are added with `place_piece`, `fill_row` and
+/// `set_back_rank`, where a later piece replaces an earlier one on the same square. Named
+/// setups such as `BoardSetup::standard` and `BoardSetup::horde` can be built on further,
+/// as can any position read with `BoardSetup::from_fen`.
+///
+/// Games are only built from setups that follow the rules of chess, see
+/// `ChessGame::validate`: each player has one king, unless the variant says otherwise, and
+/// the player who just moved is not in check.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::piece::{ChessPiece, PieceType};
+/// use simple_chess::setup::BoardSetup;
+/// use simple_chess::Color;
+///
+/// // standard chess where white starts without the queen's knight
+/// let game = BoardSetup::standard()
+///     .remove_piece(1, 0)
+///     .build_game()
+///     .unwrap();
+/// assert!(game.get_board().get_piece_at_space(1, 0).is_none());
+///
+/// // a king and rook against a row of pawns
+/// let board = BoardSetup::new(8, 8)
+///     .place_piece(ChessPiece::new(PieceType::King, Color::White), 4, 0)
+///     .place_piece(ChessPiece::new(PieceType::Rook, Color::White), 0, 0)
+///     .fill_row(ChessPiece::new(PieceType::Pawn, Color::Black), 6)
+///     .build_board()
+///     .unwrap();
+/// assert!(board.get_piece_at_space(7, 6).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardSetup {
+    width: usize,
+    height: usize,
+    pieces: Vec<((usize, usize), Option<ChessPiece>)>,
+    castle_rights: (bool, bool, bool, bool),
+    current_turn: Color,
+    en_passant_target: Option<(usize, usize)>,
+    halfmove_clock: usize,
+    turn_number: usize,
+    variant: Arc<dyn Variant>,
+    ruleset: Ruleset,
+}
+
+impl BoardSetup {
+    /// Creates a setup for an empty board of the given size, where neither player may
+    /// castle.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pieces: Vec::new(),
+            castle_rights: (false, false, false, false),
+            current_turn: Color::White,
+            en_passant_target: None,
+            halfmove_clock: 0,
+            turn_number: 1,
+            variant: Arc::new(StandardChess),
+            ruleset: Ruleset::new(),
+        }
+    }
+
+    /// Creates a setup from a position in FEN, which can then be changed further.
+    ///
+    /// The pieces, side to move, castling rights, en passant target and move counters are
+    /// taken from the position. Check counts of Three-check positions are not kept, and
+    /// the variant is standard chess unless set with `set_variant`, or Capablanca chess for
+    /// positions with 10 files.
+    ///
+    /// # Arguments
+    ///
+    /// * `fen` - The position, such as `"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"`.
+    ///
+    /// # Returns
+    ///
+    /// The setup, or a `BoardSetupError` if the position is not valid FEN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::encode_game_as_string;
+    /// use simple_chess::piece::{ChessPiece, PieceType};
+    /// use simple_chess::setup::BoardSetup;
+    /// use simple_chess::Color;
+    ///
+    /// let game = BoardSetup::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 3 40")
+    ///     .unwrap()
+    ///     .place_piece(ChessPiece::new(PieceType::Rook, Color::Black), 0, 7)
+    ///     .build_game()
+    ///     .unwrap();
+    /// assert_eq!("r3k3/8/8/8/8/8/4P3/4K3 b - - 3 40", encode_game_as_string(&game));
+    /// ```
+    pub fn from_fen(fen: &str) -> Result<Self, BoardSetupError> {
+        let game = build_game_from_string(fen).map_err(|e| BoardSetupError::new(e.to_string()))?;
+        let board = game.get_board();
+        let mut setup = Self::new(board.get_width(), board.get_height());
+        for (position, piece) in board.pieces() {
+            setup.pieces.push((position.into(), Some(*piece)));
+        }
+        // the game gives the long side first, the setup the short side
+        let (white_long, white_short, black_long, black_short) = game.get_castling_rights();
+        setup.castle_rights = (white_short, white_long, black_short, black_long);
+        setup.current_turn = game.get_current_players_turn();
+        setup.en_passant_target = game.get_en_passant_target();
+        setup.halfmove_clock = game.get_halfmove_clock();
+        setup.turn_number = game.get_turn_number();
+        setup.ruleset = *game.get_ruleset();
+        if game.get_variant().get_name() == Capablanca.get_name() {
+            setup.variant = Arc::new(Capablanca);
+        }
+        Ok(setup)
+    }
+
+    /// Creates the setup of standard chess.
+    pub fn standard() -> Self {
+        Self::new(8, 8)
+            .set_back_rank(Color::White, &STANDARD_BACK_RANK)
+            .set_back_rank(Color::Black, &STANDARD_BACK_RANK)
+            .fill_row(ChessPiece::new(Pawn, Color::White), 1)
+            .fill_row(ChessPiece::new(Pawn, Color::Black), 6)
+            .set_castle_rights(true, true, true, true)
+    }
+
+    /// Creates the setup of Horde, where white has 36 pawns and no king against the
+    /// standard black army, and plays by the rules of the [`Horde`] variant.
+    ///
+    /// White's pawns fill the first four ranks with four more on b5, c5, f5 and g5. Pawns
+    /// only move two squares from the second rank, white's first rank pawns step one
+    /// square at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::encode_game_as_string;
+    /// use simple_chess::setup::BoardSetup;
+    ///
+    /// let game = BoardSetup::horde().build_game().unwrap();
+    /// assert_eq!("Horde", game.get_variant().get_name());
+    /// assert_eq!(
+    ///     "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1",
+    ///     encode_game_as_string(&game)
+    /// );
+    /// ```
+    pub fn horde() -> Self {
+        let white_pawn = ChessPiece::new(Pawn, Color::White);
+        let mut setup = Self::new(8, 8)
+            .set_back_rank(Color::Black, &STANDARD_BACK_RANK)
+            .fill_row(ChessPiece::new(Pawn, Color::Black), 6)
+            .set_castle_rights(false, false, true, true)
+            .set_variant(Horde);
+        for row in 0..4 {
+            setup = setup.fill_row(white_pawn, row);
+        }
+        for col in [1, 2, 5, 6] {
+            setup = setup.place_piece(white_pawn, col, 4);
+        }
+        setup
+    }
+
+    /// Creates the setup of Capablanca chess on a 10x8 board, played by the rules of the
+    /// [`Capablanca`] variant.
+    ///
+    /// Each side has an archbishop between its queen's knight and bishop, and a chancellor
+    /// between its king's bishop and knight. The king castles three squares to either side,
+    /// landing on the c-file or the i-file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simple_chess::codec::forsyth_edwards_notation::encode_game_as_string;
+    /// use simple_chess::setup::BoardSetup;
+    ///
+    /// let game = BoardSetup::capablanca().build_game().unwrap();
+    /// assert_eq!(
+    ///     "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1",
+    ///     encode_game_as_string(&game)
+    /// );
+    /// ```
+    pub fn capablanca() -> Self {
+        Self::new(10, 8)
+            .set_back_rank(Color::White, &CAPABLANCA_BACK_RANK)
+            .set_back_rank(Color::Black, &CAPABLANCA_BACK_RANK)
+            .fill_row(ChessPiece::new(Pawn, Color::White), 1)
+            .fill_row(ChessPiece::new(Pawn, Color::Black), 6)
+            .set_castle_rights(true, true, true, true)
+            .set_variant(Capablanca)
+            .set_ruleset(Ruleset::capablanca())
+    }
+
+    /// Places a piece on a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `piece` - The piece to place.
+    /// * `col` - The column of the square.
+    /// * `row` - The row of the square.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - Returns the `BoardSetup` with the piece placed.
+    pub fn place_piece(mut self, piece: ChessPiece, col: usize, row: usize) -> Self {
+        self.pieces.push(((col, row), Some(piece)));
+        self
+    }
+
+    /// Removes any piece from a square.
+    pub fn remove_piece(mut self, col: usize, row: usize) -> Self {
+        self.pieces.push(((col, row), None));
+        self
+    }
+
+    /// Places a copy of a piece on every square of a row.
+    pub fn fill_row(mut self, piece: ChessPiece, row: usize) -> Self {
+        for col in 0..self.width {
+            self.pieces.push(((col, row), Some(piece)));
+        }
+        self
+    }
+
+    /// Places pieces along a player's back rank, starting on the first column.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player the pieces belong to, white's back rank is the first row and
+    ///   black's the last.
+    /// * `piece_types` - The pieces to place, from the first column onwards.
+    pub fn set_back_rank(mut self, color: Color, piece_types: &[PieceType]) -> Self {
+        let row = color.relative_rank(0, self.height);
+        for (col, piece_type) in piece_types.iter().enumerate() {
+            self.pieces
+                .push(((col, row), Some(ChessPiece::new(*piece_type, color))));
+        }
+        self
+    }
+
+    /// Sets which castling moves are available at the start of the game, in the same order
+    /// as `ChessGameBuilder::set_castle_rights`.
+    pub fn set_castle_rights(mut self, ws: bool, wl: bool, bs: bool, bl: bool) -> Self {
+        self.castle_rights = (ws, wl, bs, bl);
+        self
+    }
+
+    /// Sets the player who moves first, white unless set.
+    pub fn set_current_turn(mut self, color: Color) -> Self {
+        self.current_turn = color;
+        self
+    }
+
+    /// Sets the square a pawn skipped over with a double step on the last move, where it
+    /// can be taken en passant. There is no target unless set.
+    pub fn set_en_passant_target(mut self, col: usize, row: usize) -> Self {
+        self.en_passant_target = Some((col, row));
+        self
+    }
+
+    /// Sets the number of half-moves since the last capture or pawn move, `0` unless set.
+    pub fn set_halfmove_clock(mut self, halfmove_clock: usize) -> Self {
+        self.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    /// Sets the number of the turn the game starts on, `1` unless set.
+    pub fn set_turn_number(mut self, turn_number: usize) -> Self {
+        self.turn_number = turn_number;
+        self
+    }
+
+    /// Sets the variant games built from the setup are played with.
+    pub fn set_variant<V: Variant + 'static>(mut self, variant: V) -> Self {
+        self.variant = Arc::new(variant);
+        self
+    }
+
+    /// Sets the ruleset games built from the setup are played with, standard chess unless
+    /// set.
+    pub fn set_ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.ruleset = ruleset;
+        self
+    }
+
+    /// Builds the board of the setup.
+    ///
+    /// # Returns
+    ///
+    /// The board with every piece placed, or a `BoardSetupError` if the board size is
+    /// invalid or a piece was placed off the board.
+    pub fn build_board(&self) -> Result<Board<ChessPiece>, BoardSetupError> {
+        let mut board = Board::try_build(self.width, self.height)
+            .map_err(|e| BoardSetupError::new(e.to_string()))?;
+        for &((col, row), piece) in &self.pieces {
+            match piece {
+                Some(piece) => board.try_place_piece(piece, col, row),
+                None => board.try_remove_piece(col, row).map(|_| ()),
+            }
+            .map_err(|e| BoardSetupError::new(e.to_string()))?;
+        }
+        Ok(board)
+    }
+
+    /// Builds a game starting from the setup.
+    ///
+    /// # Returns
+    ///
+    /// The `ChessGame`, or a `BoardSetupError` if the board could not be built or the
+    /// position breaks the rules of chess, such as a player without a king or both kings in
+    /// check. The rules broken are given by `BoardSetupError::get_violations`.
+    pub fn build_game(&self) -> Result<ChessGame, BoardSetupError> {
+        let (ws, wl, bs, bl) = self.castle_rights;
+        let mut builder = ChessGameBuilder::new()
+            .set_board(self.build_board()?)
+            .set_current_turn(self.current_turn)
+            .set_turn_number(self.turn_number)
+            .set_fifty_move_rule_counter(self.halfmove_clock)
+            .set_castle_rights(ws, wl, bs, bl)
+            .set_ruleset(self.ruleset);
+        if let Some(target) = self.en_passant_target {
+            builder = builder.set_en_passant_target(target);
+        }
+        let mut game = builder
+            .build()
+            .map_err(|e| BoardSetupError::new(e.to_string()))?;
+        game.set_variant(self.variant.clone());
+        game.validate().map_err(|violations| {
+            let reasons: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+            BoardSetupError {
+                reason: reasons.join(", "),
+                violations,
+            }
+        })?;
+        Ok(game)
+    }
+}
+
+impl Default for BoardSetup {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Error returned when a `BoardSetup` does not describe a valid board.
+pub struct BoardSetupError {
+    reason: String,
+    violations: Vec<InvariantViolation>,
+}
+
+impl BoardSetupError {
+    fn new(reason: String) -> Self {
+        Self {
+            reason,
+            violations: Vec::new(),
+        }
+    }
+
+    /// Returns the rules of chess the position breaks, empty if the board itself could not
+    /// be built.
+    pub fn get_violations(&self) -> &[InvariantViolation] {
+        &self.violations
+    }
+}
+
+impl Display for BoardSetupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid board setup: {}", self.reason)
+    }
+}
+
+impl Debug for BoardSetupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BoardSetupError: {}", self.reason)
+    }
+}
+
+impl Error for BoardSetupError {}
+
+impl From<BoardSetupError> for ChessError {
+    fn from(error: BoardSetupError) -> Self {
+        ChessError::InvalidBoard(error.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_game_state_analyzer::GameState;
+    use crate::codec::forsyth_edwards_notation::{build_game_from_string, encode_game_as_string};
+
+    #[test]
+    fn standard_setup_matches_a_new_game() {
+        let game = BoardSetup::standard().build_game().unwrap();
+        assert_eq!(
+            encode_game_as_string(&ChessGame::new()),
+            encode_game_as_string(&game)
+        );
+    }
+
+    #[test]
+    fn horde_has_36_white_pawns_and_no_white_king() {
+        let mut game = BoardSetup::horde().build_game().unwrap();
+        let board = game.get_board();
+        let white_pieces: Vec<&ChessPiece> = (0..8)
+            .flat_map(|col| (0..8).map(move |row| (col, row)))
+            .filter_map(|(col, row)| board.get_piece_at_space(col, row))
+            .filter(|piece| piece.get_color() == Color::White)
+            .collect();
+
+        assert_eq!(36, white_pieces.len());
+        assert!(white_pieces
+            .iter()
+            .all(|piece| piece.get_piece_type() == Pawn));
+        assert!(matches!(
+            game.get_game_state(),
+            GameState::InProgress { .. }
+        ));
+    }
+
+    #[test]
+    fn pieces_off_the_board_are_an_error() {
+        let setup = BoardSetup::new(4, 4).place_piece(ChessPiece::new(King, Color::White), 4, 0);
+        assert!(setup.build_board().is_err());
+        assert!(BoardSetup::new(0, 4).build_game().is_err());
+    }
+
+    #[test]
+    fn positions_read_from_fen_keep_every_field() {
+        let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 7 31";
+        let setup = BoardSetup::from_fen(fen).unwrap();
+        assert_eq!(fen, encode_game_as_string(&setup.build_game().unwrap()));
+
+        let game = setup
+            .set_castle_rights(false, false, false, false)
+            .set_halfmove_clock(0)
+            .set_turn_number(2)
+            .place_piece(ChessPiece::new(Knight, Color::White), 1, 0)
+            .build_game()
+            .unwrap();
+        assert_eq!(
+            "r3k2r/8/8/3pP3/8/8/8/RN2K2R w - d6 0 2",
+            encode_game_as_string(&game)
+        );
+        assert!(BoardSetup::from_fen("not a position").is_err());
+    }
+
+    #[test]
+    fn positions_breaking_the_rules_are_rejected() {
+        let white_king = ChessPiece::new(King, Color::White);
+        let error = BoardSetup::new(8, 8)
+            .place_piece(white_king, 4, 0)
+            .build_game()
+            .err()
+            .unwrap();
+        assert_eq!(
+            &[InvariantViolation::WrongKingCount {
+                color: Color::Black,
+                count: 0,
+                expected: 1,
+            }],
+            error.get_violations()
+        );
+
+        // both kings in check
+        let error = BoardSetup::new(8, 8)
+            .place_piece(white_king, 4, 0)
+            .place_piece(ChessPiece::new(King, Color::Black), 4, 7)
+            .place_piece(ChessPiece::new(Rook, Color::White), 0, 7)
+            .place_piece(ChessPiece::new(Rook, Color::Black), 7, 0)
+            .build_game()
+            .err()
+            .unwrap();
+        assert!(error
+            .get_violations()
+            .contains(&InvariantViolation::BothKingsInCheck));
+        assert!(error.to_string().contains("both kings are in check"));
+    }
+
+    #[test]
+    fn horde_is_lost_when_every_white_piece_is_captured() {
+        let position = build_game_from_string("4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        let mut game = BoardSetup::new(8, 8)
+            .place_piece(ChessPiece::new(King, Color::Black), 4, 7)
+            .set_variant(Horde)
+            .build_game()
+            .unwrap();
+
+        assert_eq!(
+            encode_game_as_string(&position),
+            encode_game_as_string(&game)
+        );
+        assert_eq!(
+            GameState::VariantWin {
+                winner: Color::Black
+            },
+            game.get_game_state()
+        );
+    }
+}
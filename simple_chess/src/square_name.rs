@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// The name of a square on a standard 8x8 board.
+///
+/// Squares are named by file and rank, `A1` is White's bottom left corner at `(0, 0)` and
+/// `H8` is Black's at `(7, 7)`. Using `SquareName` instead of bare `(col, row)` tuples makes
+/// code working with standard chess positions easier to read and harder to get wrong.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::square_name::SquareName;
+///
+/// assert_eq!((4, 3), SquareName::E4.get_col_and_row());
+/// assert_eq!(Some(SquareName::E4), SquareName::from_col_and_row(4, 3));
+/// assert_eq!(SquareName::E4, "e4".parse().unwrap());
+/// assert_eq!("e4", SquareName::E4.to_string());
+/// ```
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SquareName {
+    A1, B1, C1, D1, E1, F1, G1, H1,
+    A2, B2, C2, D2, E2, F2, G2, H2,
+    A3, B3, C3, D3, E3, F3, G3, H3,
+    A4, B4, C4, D4, E4, F4, G4, H4,
+    A5, B5, C5, D5, E5, F5, G5, H5,
+    A6, B6, C6, D6, E6, F6, G6, H6,
+    A7, B7, C7, D7, E7, F7, G7, H7,
+    A8, B8, C8, D8, E8, F8, G8, H8,
+}
+
+use SquareName::*;
+
+#[rustfmt::skip]
+const ALL_SQUARES: [SquareName; 64] = [
+    A1, B1, C1, D1, E1, F1, G1, H1,
+    A2, B2, C2, D2, E2, F2, G2, H2,
+    A3, B3, C3, D3, E3, F3, G3, H3,
+    A4, B4, C4, D4, E4, F4, G4, H4,
+    A5, B5, C5, D5, E5, F5, G5, H5,
+    A6, B6, C6, D6, E6, F6, G6, H6,
+    A7, B7, C7, D7, E7, F7, G7, H7,
+    A8, B8, C8, D8, E8, F8, G8, H8,
+];
+
+impl SquareName {
+    /// Returns every square, ordered by rank and then by file, starting with `A1`.
+    pub fn all() -> &'static [SquareName; 64] {
+        &ALL_SQUARES
+    }
+
+    /// Returns the square at the given column and row, `None` if it is not on an 8x8 board.
+    ///
+    /// # Arguments
+    ///
+    /// * `col` - The column of the square, `0` is the a-file.
+    /// * `row` - The row of the square, `0` is the first rank.
+    pub fn from_col_and_row(col: usize, row: usize) -> Option<SquareName> {
+        if col < 8 && row < 8 {
+            Some(ALL_SQUARES[col + row * 8])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `(col, row)` coordinates of the square.
+    pub fn get_col_and_row(&self) -> (usize, usize) {
+        (self.get_col(), self.get_row())
+    }
+
+    /// Returns the column of the square, `0` is the a-file.
+    pub fn get_col(&self) -> usize {
+        *self as usize % 8
+    }
+
+    /// Returns the row of the square, `0` is the first rank.
+    pub fn get_row(&self) -> usize {
+        *self as usize / 8
+    }
+}
+
+impl From<SquareName> for (usize, usize) {
+    fn from(square: SquareName) -> Self {
+        square.get_col_and_row()
+    }
+}
+
+impl TryFrom<(usize, usize)> for SquareName {
+    type Error = SquareNameError;
+
+    fn try_from((col, row): (usize, usize)) -> Result<Self, Self::Error> {
+        SquareName::from_col_and_row(col, row).ok_or(SquareNameError {
+            name: format!("({}, {})", col, row),
+        })
+    }
+}
+
+impl FromStr for SquareName {
+    type Err = SquareNameError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let error = || SquareNameError {
+            name: name.to_string(),
+        };
+
+        let mut chars = name.chars();
+        let file = chars.next().ok_or_else(error)?.to_ascii_lowercase();
+        let rank = chars.next().ok_or_else(error)?;
+        if chars.next().is_some() || !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return Err(error());
+        }
+
+        let col = file as usize - 'a' as usize;
+        let row = rank as usize - '1' as usize;
+        Ok(ALL_SQUARES[col + row * 8])
+    }
+}
+
+impl Display for SquareName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let file = (b'a' + self.get_col() as u8) as char;
+        write!(f, "{}{}", file, self.get_row() + 1)
+    }
+}
+
+/// Error returned when a square name or coordinate is not a square on an 8x8 board.
+pub struct SquareNameError {
+    name: String,
+}
+
+impl Display for SquareNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a square on an 8x8 board", self.name)
+    }
+}
+
+impl Debug for SquareNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Error for SquareNameError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_board::get_square_name_from_row_and_col;
+
+    #[test]
+    fn names_match_the_board_coordinates() {
+        for square in SquareName::all() {
+            let (col, row) = square.get_col_and_row();
+            assert_eq!(
+                get_square_name_from_row_and_col(col, row),
+                square.to_string()
+            );
+            assert_eq!(Some(*square), SquareName::from_col_and_row(col, row));
+            assert_eq!(*square, square.to_string().parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn corners_and_tuples() {
+        assert_eq!((0, 0), A1.into());
+        assert_eq!((7, 7), <(usize, usize)>::from(H8));
+        assert_eq!(Ok(H1), SquareName::try_from((7, 0)).map_err(|_| ()));
+        assert!(SquareName::try_from((8, 0)).is_err());
+        assert_eq!(None, SquareName::from_col_and_row(0, 8));
+    }
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        assert_eq!(E4, "E4".parse().unwrap());
+        for name in ["", "e", "e9", "i1", "e44", "4e", "e0"] {
+            assert!(name.parse::<SquareName>().is_err(), "{} parsed", name);
+        }
+        assert_eq!(
+            "e9 is not a square on an 8x8 board",
+            "e9".parse::<SquareName>().unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::storage::{check_game_id, GameStore, StorageError, StoredGame};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// A `GameStore` keeping each game in a directory as two files, `<game id>.pgn` holding
+/// the game and `<game id>.fen` holding its current position.
+///
+/// The FEN file lets other tools read the position without replaying the game, and is
+/// checked against the replayed game when it is loaded. Files are written to a temporary
+/// file first and then renamed, so a crash while saving leaves the previous save intact.
+pub struct FileGameStore {
+    directory: PathBuf,
+}
+
+impl FileGameStore {
+    /// Creates a store keeping its games in a directory, creating the directory if it does
+    /// not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to keep the games in.
+    pub fn new<P: AsRef<Path>>(directory: P) -> Result<Self, StorageError> {
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory: directory.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Returns the directory the games are kept in.
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn get_path(&self, game_id: &str, extension: &str) -> PathBuf {
+        self.directory.join(format!("{game_id}.{extension}"))
+    }
+
+    fn write_file(&self, game_id: &str, extension: &str, contents: &str) -> std::io::Result<()> {
+        let temporary_path = self.get_path(game_id, &format!("{extension}.tmp"));
+        fs::write(&temporary_path, contents)?;
+        fs::rename(temporary_path, self.get_path(game_id, extension))
+    }
+}
+
+impl GameStore for FileGameStore {
+    fn save(&self, game_id: &str, game: &StoredGame) -> Result<(), StorageError> {
+        check_game_id(game_id)?;
+        self.write_file(game_id, "pgn", &game.to_pgn())?;
+        self.write_file(game_id, "fen", &format!("{}\n", game.to_fen()))?;
+        Ok(())
+    }
+
+    fn load(&self, game_id: &str) -> Result<Option<StoredGame>, StorageError> {
+        check_game_id(game_id)?;
+        let pgn = match fs::read_to_string(self.get_path(game_id, "pgn")) {
+            Ok(pgn) => pgn,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let game = StoredGame::from_pgn(&pgn)?;
+
+        let fen = fs::read_to_string(self.get_path(game_id, "fen"))?;
+        if fen.trim() != game.to_fen() {
+            return Err(StorageError::Corrupt(format!(
+                "the position of '{game_id}' does not match its moves"
+            )));
+        }
+        Ok(Some(game))
+    }
+
+    fn delete(&self, game_id: &str) -> Result<bool, StorageError> {
+        check_game_id(game_id)?;
+        let mut deleted = false;
+        for extension in ["pgn", "fen"] {
+            match fs::remove_file(self.get_path(game_id, extension)) {
+                Ok(()) => deleted = true,
+                Err(error) if error.kind() == ErrorKind::NotFound => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(deleted)
+    }
+
+    fn list(&self) -> Result<Vec<String>, StorageError> {
+        let mut game_ids = Vec::new();
+        for entry in fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "pgn") {
+                if let Some(game_id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    game_ids.push(game_id.to_string());
+                }
+            }
+        }
+        game_ids.sort();
+        Ok(game_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChessGame;
+
+    #[test]
+    fn games_are_saved_listed_and_deleted() {
+        let directory = std::env::temp_dir().join(format!(
+            "simple_chess_file_store_test_{}",
+            std::process::id()
+        ));
+        let store = FileGameStore::new(&directory).unwrap();
+        let game = StoredGame::new(ChessGame::new()).set_tag("White", "Ian");
+
+        store.save("b", &game).unwrap();
+        store.save("a", &game).unwrap();
+        assert_eq!(vec!["a", "b"], store.list().unwrap());
+        assert_eq!(
+            Some("Ian"),
+            store.load("a").unwrap().unwrap().get_tag("White")
+        );
+        assert!(matches!(
+            store.save("../a", &game),
+            Err(StorageError::InvalidGameId(_))
+        ));
+
+        fs::write(directory.join("a.fen"), "8/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        assert!(matches!(store.load("a"), Err(StorageError::Corrupt(_))));
+        assert!(store.delete("a").unwrap());
+        assert!(!store.delete("a").unwrap());
+        assert!(store.load("a").unwrap().is_none());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
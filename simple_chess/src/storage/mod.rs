@@ -0,0 +1,484 @@
+use crate::adjudication::Adjudication;
+use crate::clock::{ChessClock, TimeControl};
+use crate::codec::{forsyth_edwards_notation, portable_game_notation};
+use crate::error::ChessError;
+use crate::game_result::Termination;
+use crate::{ChessGame, Color};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod file_store;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use file_store::FileGameStore;
+
+/// Tags written by `StoredGame::to_pgn` that are read back into the game, its clock or its
+/// last move time rather than kept as tags.
+const RESTORED_TAGS: [&str; 12] = [
+    "Result",
+    "Termination",
+    "SetUp",
+    "FEN",
+    "TimeControl",
+    "WhiteTimeControl",
+    "BlackTimeControl",
+    "WhiteClock",
+    "BlackClock",
+    "ClockFlagged",
+    "DrawOffer",
+    "LastMoveTime",
+];
+
+/// Durable storage of games that last longer than the process playing them, such as
+/// correspondence games played over several days.
+///
+/// Games are saved under an id chosen by the caller, saving a game under an id that is
+/// already used replaces the saved game. Ids may only use ASCII letters, digits, `-` and
+/// `_`, so they can be used as file names.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::storage::{FileGameStore, GameStore, StoredGame};
+/// use simple_chess::ChessGame;
+///
+/// let directory = std::env::temp_dir().join("simple_chess_game_store_example");
+/// let store = FileGameStore::new(&directory).unwrap();
+///
+/// let mut game = ChessGame::new();
+/// let chess_move = game.legal_moves()[0];
+/// game.make_move(chess_move);
+/// store.save("game-1", &StoredGame::new(game)).unwrap();
+///
+/// let loaded = store.load("game-1").unwrap().unwrap();
+/// assert_eq!(1, loaded.get_game().get_moves().len());
+/// # std::fs::remove_dir_all(directory).unwrap();
+/// ```
+pub trait GameStore {
+    /// Saves a game, replacing any game saved under the same id.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The id to save the game under.
+    /// * `game` - The game to save.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the game was saved.
+    /// * `Err(StorageError)` - If the id is not valid or the game could not be written.
+    fn save(&self, game_id: &str, game: &StoredGame) -> Result<(), StorageError>;
+
+    /// Loads a saved game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The id the game was saved under.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(StoredGame))` - The saved game.
+    /// * `Ok(None)` - If no game is saved under the id.
+    /// * `Err(StorageError)` - If the id is not valid, or the game could not be read.
+    fn load(&self, game_id: &str) -> Result<Option<StoredGame>, StorageError>;
+
+    /// Deletes a saved game, returning `true` if there was a game to delete.
+    fn delete(&self, game_id: &str) -> Result<bool, StorageError>;
+
+    /// Returns the ids of every saved game, sorted.
+    fn list(&self) -> Result<Vec<String>, StorageError>;
+}
+
+/// A game as it is saved by a `GameStore`, with its clock, its PGN tags and the time its
+/// last move was made.
+///
+/// Games are saved as PGN, so the moves, the starting position, the result, a pending draw
+/// offer and the clock, to the second, survive saving. The record of each move's clock
+/// time is not saved, and a game that ended early is restored with the termination PGN can
+/// tell apart: a game decided by `ChessGame::adjudicate` is adjudicated again, other
+/// decisive results are restored as resignations and other draws as agreed draws.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::clock::{ChessClock, TimeControl};
+/// use simple_chess::storage::StoredGame;
+/// use simple_chess::{ChessGame, Color};
+/// use std::time::Duration;
+///
+/// let three_days = TimeControl::new(Duration::from_secs(3 * 24 * 60 * 60), Duration::ZERO);
+/// let stored = StoredGame::new(ChessGame::new())
+///     .set_clock(ChessClock::new(three_days))
+///     .set_tag("White", "Ian");
+///
+/// let restored = StoredGame::from_pgn(&stored.to_pgn()).unwrap();
+/// assert_eq!(Some("Ian"), restored.get_tag("White"));
+/// assert_eq!(
+///     Duration::from_secs(3 * 24 * 60 * 60),
+///     restored.get_clock().unwrap().get_remaining_time(Color::Black)
+/// );
+/// ```
+#[derive(Clone)]
+pub struct StoredGame {
+    game: ChessGame,
+    clock: Option<ChessClock>,
+    tags: Vec<(String, String)>,
+    last_move_time: Option<SystemTime>,
+}
+
+impl StoredGame {
+    /// Creates a stored game without a clock or tags.
+    pub fn new(game: ChessGame) -> Self {
+        Self {
+            game,
+            clock: None,
+            tags: Vec::new(),
+            last_move_time: None,
+        }
+    }
+
+    /// Sets the clock of the game.
+    pub fn set_clock(mut self, clock: ChessClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Sets a PGN tag, such as `("White", "Carlsen")`, replacing any tag with the same name.
+    pub fn set_tag(mut self, name: &str, value: &str) -> Self {
+        self.tags.retain(|(tag, _)| tag != name);
+        self.tags.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets when the last move was made, so the time the player to move has used can be
+    /// taken off their clock once the game is loaded again.
+    pub fn set_last_move_time(mut self, last_move_time: SystemTime) -> Self {
+        self.last_move_time = Some(last_move_time);
+        self
+    }
+
+    /// Returns the game.
+    pub fn get_game(&self) -> &ChessGame {
+        &self.game
+    }
+
+    /// Returns the game, to make moves in it.
+    pub fn get_game_mut(&mut self) -> &mut ChessGame {
+        &mut self.game
+    }
+
+    /// Returns the clock of the game, if it has one.
+    pub fn get_clock(&self) -> Option<&ChessClock> {
+        self.clock.as_ref()
+    }
+
+    /// Returns the clock of the game, to record moves on it.
+    pub fn get_clock_mut(&mut self) -> Option<&mut ChessClock> {
+        self.clock.as_mut()
+    }
+
+    /// Returns the PGN tags of the game, in the order they were set.
+    pub fn get_tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the value of a PGN tag, if it is set.
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns when the last move was made, if known.
+    pub fn get_last_move_time(&self) -> Option<SystemTime> {
+        self.last_move_time
+    }
+
+    /// Returns the game, dropping its clock and tags.
+    pub fn into_game(self) -> ChessGame {
+        self.game
+    }
+
+    /// Encodes the game in PGN, with the clock, the pending draw offer and the last move
+    /// time written as tags.
+    pub fn to_pgn(&self) -> String {
+        let mut tags = self.tags.clone();
+        if let Some(clock) = &self.clock {
+            tags.extend(clock.get_pgn_tags());
+            if let Some(color) = clock.get_flagged_player() {
+                tags.push((String::from("ClockFlagged"), encode_color(color)));
+            }
+        }
+        if let Some(color) = self.game.get_draw_offer() {
+            tags.push((String::from("DrawOffer"), encode_color(color)));
+        }
+        if let Some(last_move_time) = self.last_move_time {
+            let seconds = last_move_time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            tags.push((String::from("LastMoveTime"), seconds.to_string()));
+        }
+        portable_game_notation::encode_game_as_string(&self.game, &tags)
+    }
+
+    /// Returns the position of the game in Forsyth-Edwards Notation.
+    pub fn to_fen(&self) -> String {
+        forsyth_edwards_notation::encode_game_as_string(&self.game)
+    }
+
+    /// Decodes a game encoded by `to_pgn`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(StoredGame)` - The game.
+    /// * `Err(StorageError)` - If the PGN holds an illegal move or an invalid tag.
+    pub fn from_pgn(pgn: &str) -> Result<Self, StorageError> {
+        let all_tags = portable_game_notation::decode_tags(pgn);
+        let tag = |name: &str| {
+            all_tags
+                .iter()
+                .find(|(tag, _)| tag == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let corrupt = |name: &str, value: &str| {
+            StorageError::Corrupt(format!("'{value}' is not a valid {name} tag"))
+        };
+
+        let mut game = portable_game_notation::build_game_from_string(pgn)?;
+        if let Some(value) = tag("DrawOffer") {
+            let color = decode_color(value).ok_or_else(|| corrupt("DrawOffer", value))?;
+            game.offer_draw(color)?;
+        }
+        if let Some(result) = tag("Result") {
+            restore_ending(&mut game, result, tag("Termination"))?;
+        }
+
+        let clock = match (tag("TimeControl"), tag("WhiteClock"), tag("BlackClock")) {
+            (time_control, Some(white_clock), Some(black_clock)) => {
+                let parse_time_control = |name: &str| {
+                    let value = time_control.or(tag(name)).unwrap_or_default();
+                    TimeControl::from_pgn_str(value).ok_or_else(|| corrupt(name, value))
+                };
+                let flagged = match tag("ClockFlagged") {
+                    Some(value) => {
+                        Some(decode_color(value).ok_or_else(|| corrupt("ClockFlagged", value))?)
+                    }
+                    None => None,
+                };
+                Some(ChessClock::restore(
+                    parse_time_control("WhiteTimeControl")?,
+                    parse_time_control("BlackTimeControl")?,
+                    decode_clock_time(white_clock)
+                        .ok_or_else(|| corrupt("WhiteClock", white_clock))?,
+                    decode_clock_time(black_clock)
+                        .ok_or_else(|| corrupt("BlackClock", black_clock))?,
+                    game.get_current_players_turn(),
+                    flagged,
+                ))
+            }
+            _ => None,
+        };
+
+        let last_move_time = match tag("LastMoveTime") {
+            Some(value) => {
+                let seconds = value.parse().map_err(|_| corrupt("LastMoveTime", value))?;
+                Some(UNIX_EPOCH + Duration::from_secs(seconds))
+            }
+            None => None,
+        };
+
+        let tags = all_tags
+            .iter()
+            .filter(|(name, value)| !RESTORED_TAGS.contains(&name.as_str()) && value != "?")
+            .cloned()
+            .collect();
+        Ok(Self {
+            game,
+            clock,
+            tags,
+            last_move_time,
+        })
+    }
+}
+
+/// Ends a game that was over when it was saved but that PGN can not end by itself, such as
+/// a resigned game.
+fn restore_ending(
+    game: &mut ChessGame,
+    result: &str,
+    termination: Option<&str>,
+) -> Result<(), StorageError> {
+    let adjudication = match result {
+        "1-0" => Adjudication::Resignation {
+            winner: Color::White,
+        },
+        "0-1" => Adjudication::Resignation {
+            winner: Color::Black,
+        },
+        "1/2-1/2" => Adjudication::Draw,
+        _ => return Ok(()),
+    };
+    if game.get_result().is_some() {
+        return Ok(());
+    }
+
+    if termination == Some(Termination::Adjudication.as_pgn_str()) {
+        game.adjudicate(adjudication)?;
+    } else if termination == Some(Termination::TimeForfeit.as_pgn_str()) {
+        game.set_ending(Some((adjudication, Termination::TimeForfeit)));
+    } else if let Adjudication::Resignation { winner } = adjudication {
+        game.resign(winner.opposite())?;
+    } else {
+        if game.get_draw_offer().is_none() {
+            game.offer_draw(game.get_current_players_turn())?;
+        }
+        game.accept_draw()?;
+    }
+    Ok(())
+}
+
+/// Decodes a clock time written as `h:mm:ss`.
+fn decode_clock_time(value: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    for part in value.split(':') {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+fn encode_color(color: Color) -> String {
+    match color {
+        Color::White => String::from("White"),
+        Color::Black => String::from("Black"),
+    }
+}
+
+fn decode_color(value: &str) -> Option<Color> {
+    match value {
+        "White" => Some(Color::White),
+        "Black" => Some(Color::Black),
+        _ => None,
+    }
+}
+
+/// Returns an error if a game id could be misread as a path or is empty.
+pub(crate) fn check_game_id(game_id: &str) -> Result<(), StorageError> {
+    let is_valid = !game_id.is_empty()
+        && game_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if is_valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidGameId(game_id.to_string()))
+    }
+}
+
+/// Error returned when a game can not be saved or loaded.
+pub enum StorageError {
+    /// The game id uses characters other than ASCII letters, digits, `-` and `_`.
+    InvalidGameId(String),
+    /// A saved game could not be read back, with the reason why.
+    Corrupt(String),
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+    /// The SQLite database returned an error.
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+}
+
+impl Display for StorageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageError::InvalidGameId(game_id) => {
+                write!(f, "Storage Error: '{game_id}' is not a valid game id")
+            }
+            StorageError::Corrupt(reason) => write!(f, "Storage Error: corrupt game, {reason}"),
+            StorageError::Io(error) => write!(f, "Storage Error: {error}"),
+            #[cfg(feature = "sqlite")]
+            StorageError::Sqlite(error) => write!(f, "Storage Error: {error}"),
+        }
+    }
+}
+
+impl Debug for StorageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StorageError: {self}")
+    }
+}
+
+impl Error for StorageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StorageError::Io(error) => Some(error),
+            #[cfg(feature = "sqlite")]
+            StorageError::Sqlite(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<StorageError> for ChessError {
+    fn from(error: StorageError) -> Self {
+        ChessError::Storage(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for StorageError {
+    fn from(error: std::io::Error) -> Self {
+        StorageError::Io(error)
+    }
+}
+
+impl From<ChessError> for StorageError {
+    fn from(error: ChessError) -> Self {
+        StorageError::Corrupt(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+
+    #[test]
+    fn clocks_draw_offers_and_resignations_survive_saving() {
+        let mut game = ChessGame::new();
+        let mut clock = ChessClock::with_time_odds(
+            TimeControl::new(Duration::from_secs(86_400), Duration::from_secs(3_600)),
+            TimeControl::new(Duration::from_secs(43_200), Duration::ZERO),
+        );
+        for (san, elapsed) in [("e4", 4_000), ("e5", 600)] {
+            let chess_move = decode_move_from_string(&game, san).unwrap();
+            game.make_timed_move(chess_move, &mut clock, Duration::from_secs(elapsed))
+                .unwrap();
+        }
+        game.offer_draw(Color::White).unwrap();
+        let last_move_time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let stored = StoredGame::new(game)
+            .set_clock(clock.clone())
+            .set_tag("Event", "Correspondence")
+            .set_last_move_time(last_move_time);
+
+        let mut restored = StoredGame::from_pgn(&stored.to_pgn()).unwrap();
+        assert_eq!(Some(&clock), restored.get_clock());
+        assert_eq!(Some(Color::White), restored.get_game().get_draw_offer());
+        assert_eq!(Some(last_move_time), restored.get_last_move_time());
+        assert_eq!(
+            &[(String::from("Event"), String::from("Correspondence"))],
+            restored.get_tags()
+        );
+
+        restored.get_game_mut().resign(Color::Black).unwrap();
+        let mut resigned = StoredGame::from_pgn(&restored.to_pgn()).unwrap();
+        let result = resigned.get_game_mut().get_result().unwrap();
+        assert_eq!(Termination::Resignation, result.get_termination());
+        assert!(matches!(
+            StoredGame::from_pgn("[WhiteClock \"soon\"]\n[BlackClock \"0:01:00\"]\n\n*"),
+            Err(StorageError::Corrupt(_))
+        ));
+    }
+}
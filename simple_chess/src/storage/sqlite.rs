@@ -0,0 +1,116 @@
+use crate::storage::{check_game_id, GameStore, StorageError, StoredGame};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A `GameStore` keeping games in a SQLite database.
+///
+/// Games are kept in a `games` table, created if it does not exist, with the PGN and
+/// current position of each game and the time it was last saved, so other programs can
+/// query the games with SQL.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::storage::sqlite::SqliteGameStore;
+/// use simple_chess::storage::{GameStore, StoredGame};
+/// use simple_chess::ChessGame;
+///
+/// let store = SqliteGameStore::open_in_memory().unwrap();
+/// store.save("game-1", &StoredGame::new(ChessGame::new())).unwrap();
+///
+/// assert_eq!(vec!["game-1"], store.list().unwrap());
+/// ```
+pub struct SqliteGameStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteGameStore {
+    /// Opens the database at a path, creating it if it does not exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StorageError> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Opens a database kept in memory, which is lost when the store is dropped.
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Creates a store using a connection that is already open, creating the `games` table
+    /// if it does not exist.
+    pub fn from_connection(connection: Connection) -> Result<Self, StorageError> {
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS games (
+                id TEXT PRIMARY KEY,
+                pgn TEXT NOT NULL,
+                fen TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn get_connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // a panic while holding the lock can not leave the connection half updated
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl GameStore for SqliteGameStore {
+    fn save(&self, game_id: &str, game: &StoredGame) -> Result<(), StorageError> {
+        check_game_id(game_id)?;
+        let updated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.get_connection().execute(
+            "INSERT INTO games (id, pgn, fen, updated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (id) DO UPDATE
+             SET pgn = excluded.pgn, fen = excluded.fen, updated_at = excluded.updated_at",
+            params![game_id, game.to_pgn(), game.to_fen(), updated_at],
+        )?;
+        Ok(())
+    }
+
+    fn load(&self, game_id: &str) -> Result<Option<StoredGame>, StorageError> {
+        check_game_id(game_id)?;
+        let pgn: Option<String> = self
+            .get_connection()
+            .query_row(
+                "SELECT pgn FROM games WHERE id = ?1",
+                params![game_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        pgn.map(|pgn| StoredGame::from_pgn(&pgn)).transpose()
+    }
+
+    fn delete(&self, game_id: &str) -> Result<bool, StorageError> {
+        check_game_id(game_id)?;
+        let deleted = self
+            .get_connection()
+            .execute("DELETE FROM games WHERE id = ?1", params![game_id])?;
+        Ok(deleted > 0)
+    }
+
+    fn list(&self) -> Result<Vec<String>, StorageError> {
+        let connection = self.get_connection();
+        let mut statement = connection.prepare("SELECT id FROM games ORDER BY id")?;
+        let game_ids = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(game_ids)
+    }
+}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(error: rusqlite::Error) -> Self {
+        StorageError::Sqlite(error)
+    }
+}
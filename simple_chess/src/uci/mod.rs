@@ -0,0 +1,388 @@
+pub mod pool;
+
+use crate::codec::forsyth_edwards_notation::encode_game_as_string;
+use crate::error::ChessError;
+use crate::ChessGame;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long an engine has to answer a command when no other timeout is set.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long an engine has to exit after being told to quit before it is killed.
+const QUIT_GRACE_PERIOD: Duration = Duration::from_millis(200);
+
+/// The command used to start an engine process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineCommand {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl EngineCommand {
+    /// Creates a command running the given program without arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - The path or name of the engine executable, such as `stockfish`.
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            args: Vec::new(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Adds an argument passed to the program.
+    pub fn add_arg(mut self, arg: &str) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    /// Sets how long the engine has to answer a command, including the time it takes to
+    /// finish a search, before it is treated as unresponsive.
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the path or name of the engine executable.
+    pub fn get_program(&self) -> &str {
+        &self.program
+    }
+
+    /// Returns the arguments passed to the program.
+    pub fn get_args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns how long the engine has to answer a command.
+    pub fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+/// The evaluation reported by an engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UciScore {
+    /// The evaluation in centipawns, from the point of view of the side to move.
+    Centipawns(i32),
+    /// Mate in the given number of moves, negative when the side to move is being mated.
+    Mate(i32),
+}
+
+/// The result of an engine search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UciAnalysis {
+    best_move: String,
+    score: Option<UciScore>,
+    depth: u32,
+    principal_variation: Vec<String>,
+}
+
+impl UciAnalysis {
+    /// Returns the best move in long algebraic notation, such as `e2e4`.
+    pub fn get_best_move(&self) -> &str {
+        &self.best_move
+    }
+
+    /// Returns the last evaluation the engine reported, if it reported one.
+    pub fn get_score(&self) -> Option<UciScore> {
+        self.score
+    }
+
+    /// Returns the depth of the last evaluation the engine reported.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the line the engine expects to follow, in long algebraic notation.
+    pub fn get_principal_variation(&self) -> &[String] {
+        &self.principal_variation
+    }
+}
+
+/// A running engine process spoken to over UCI.
+///
+/// The process is told to quit, and killed if it does not, when the `UciEngine` is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use simple_chess::uci::{EngineCommand, UciEngine};
+/// use simple_chess::ChessGame;
+///
+/// let mut engine = UciEngine::spawn(&EngineCommand::new("stockfish")).unwrap();
+/// let analysis = engine.analyze(&ChessGame::new(), 12).unwrap();
+/// println!("{} plays {}", engine.get_name().unwrap_or("engine"), analysis.get_best_move());
+/// ```
+pub struct UciEngine {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    name: Option<String>,
+    timeout: Duration,
+}
+
+impl UciEngine {
+    /// Starts an engine process and waits for it to finish the UCI handshake.
+    ///
+    /// # Returns
+    ///
+    /// The running engine, or a `UciError` if the process could not be started or did not
+    /// answer `uciok` in time.
+    pub fn spawn(command: &EngineCommand) -> Result<Self, UciError> {
+        let mut child = Command::new(&command.program)
+            .args(&command.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| UciError::new(format!("unable to start '{}': {e}", command.program)))?;
+
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = Self {
+            child,
+            stdin,
+            lines,
+            name: None,
+            timeout: command.timeout,
+        };
+        engine.send("uci")?;
+        for line in engine.read_until("uciok")? {
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.name = Some(name.to_string());
+            }
+        }
+        Ok(engine)
+    }
+
+    /// Returns the name the engine reported during the handshake.
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns `true` if the process is still running and answers `isready` in time.
+    pub fn is_ready(&mut self) -> bool {
+        if !matches!(self.child.try_wait(), Ok(None)) {
+            return false;
+        }
+        self.send("isready").is_ok() && self.read_until("readyok").is_ok()
+    }
+
+    /// Tells the engine the next search is part of a new game, clearing its hash tables.
+    pub fn new_game(&mut self) -> Result<(), UciError> {
+        self.send("ucinewgame")?;
+        self.send("isready")?;
+        self.read_until("readyok").map(|_| ())
+    }
+
+    /// Sets an option the engine declared during the handshake, such as `Hash` or
+    /// `Threads`.
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), UciError> {
+        self.send(&format!("setoption name {name} value {value}"))
+    }
+
+    /// Searches the current position of a game to the given depth.
+    ///
+    /// # Returns
+    ///
+    /// The engine's best move and its last reported evaluation, or a `UciError` if the
+    /// engine stopped responding.
+    pub fn analyze(&mut self, game: &ChessGame, depth: u32) -> Result<UciAnalysis, UciError> {
+        self.send(&format!("position fen {}", encode_game_as_string(game)))?;
+        self.send(&format!("go depth {depth}"))?;
+
+        let mut analysis = UciAnalysis {
+            best_move: String::new(),
+            score: None,
+            depth: 0,
+            principal_variation: Vec::new(),
+        };
+        for line in self.read_until("bestmove")? {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("info") => parse_info(tokens, &mut analysis),
+                Some("bestmove") => {
+                    analysis.best_move = tokens.next().unwrap_or_default().to_string();
+                }
+                _ => {}
+            }
+        }
+        Ok(analysis)
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), UciError> {
+        writeln!(self.stdin, "{command}")
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| UciError::new(format!("unable to send '{command}': {e}")))
+    }
+
+    /// Reads lines until one starts with the token, returning every line read including it.
+    fn read_until(&mut self, token: &str) -> Result<Vec<String>, UciError> {
+        let deadline = Instant::now() + self.timeout;
+        let mut lines = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(remaining) {
+                Ok(line) => {
+                    let is_done = line.split_whitespace().next() == Some(token);
+                    lines.push(line);
+                    if is_done {
+                        return Ok(lines);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(UciError::new(format!("timed out waiting for '{token}'")))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(UciError::new(format!(
+                        "the engine exited while waiting for '{token}'"
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for UciEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let deadline = Instant::now() + QUIT_GRACE_PERIOD;
+        while Instant::now() < deadline {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn parse_info<'a>(mut tokens: impl Iterator<Item = &'a str>, analysis: &mut UciAnalysis) {
+    while let Some(token) = tokens.next() {
+        match token {
+            "depth" => {
+                if let Some(depth) = tokens.next().and_then(|depth| depth.parse().ok()) {
+                    analysis.depth = depth;
+                }
+            }
+            "score" => {
+                let kind = tokens.next();
+                let value = tokens.next().and_then(|value| value.parse().ok());
+                analysis.score = match (kind, value) {
+                    (Some("cp"), Some(value)) => Some(UciScore::Centipawns(value)),
+                    (Some("mate"), Some(value)) => Some(UciScore::Mate(value)),
+                    _ => analysis.score,
+                };
+            }
+            // the principal variation runs to the end of the line
+            "pv" => {
+                analysis.principal_variation = tokens.by_ref().map(str::to_string).collect();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Error returned when an engine process can not be started or stops responding.
+pub struct UciError {
+    reason: String,
+}
+
+impl UciError {
+    fn new(reason: String) -> Self {
+        Self { reason }
+    }
+}
+
+impl Display for UciError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UCI engine error: {}", self.reason)
+    }
+}
+
+impl Debug for UciError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UciError: {}", self.reason)
+    }
+}
+
+impl Error for UciError {}
+
+impl From<UciError> for ChessError {
+    fn from(error: UciError) -> Self {
+        ChessError::Engine(error.to_string())
+    }
+}
+
+#[cfg(all(test, unix))]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A shell script answering like a UCI engine, which exits when asked to search to
+    /// depth 99 to imitate a crash.
+    pub(crate) const FAKE_ENGINE: &str = r#"
+        while read -r line; do
+            case "$line" in
+                uci) echo "id name Fake Engine"; echo "uciok" ;;
+                isready) echo "readyok" ;;
+                "go depth 99") exit 1 ;;
+                go*) echo "info depth 3 score cp 25 nodes 100 pv e2e4 e7e5"; echo "bestmove e2e4 ponder e7e5" ;;
+                quit) exit 0 ;;
+            esac
+        done
+    "#;
+
+    pub(crate) fn fake_engine_command() -> EngineCommand {
+        EngineCommand::new("sh")
+            .add_arg("-c")
+            .add_arg(FAKE_ENGINE)
+            .set_timeout(Duration::from_secs(5))
+    }
+
+    #[test]
+    fn engines_report_their_analysis() {
+        let mut engine = UciEngine::spawn(&fake_engine_command()).unwrap();
+        assert_eq!(Some("Fake Engine"), engine.get_name());
+        assert!(engine.is_ready());
+
+        let analysis = engine.analyze(&ChessGame::new(), 3).unwrap();
+        assert_eq!("e2e4", analysis.get_best_move());
+        assert_eq!(Some(UciScore::Centipawns(25)), analysis.get_score());
+        assert_eq!(3, analysis.get_depth());
+        assert_eq!(
+            &[String::from("e2e4"), String::from("e7e5")],
+            analysis.get_principal_variation()
+        );
+    }
+
+    #[test]
+    fn crashed_engines_are_not_ready() {
+        let mut engine = UciEngine::spawn(&fake_engine_command()).unwrap();
+        assert!(engine.analyze(&ChessGame::new(), 99).is_err());
+        assert!(!engine.is_ready());
+    }
+
+    #[test]
+    fn missing_programs_can_not_be_spawned() {
+        assert!(UciEngine::spawn(&EngineCommand::new("/no/such/engine")).is_err());
+    }
+}
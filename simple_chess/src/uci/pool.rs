@@ -0,0 +1,201 @@
+use crate::uci::{EngineCommand, UciAnalysis, UciEngine, UciError};
+use crate::ChessGame;
+use std::sync::{Condvar, Mutex};
+
+/// A bounded pool of running UCI engines.
+///
+/// Engines are started the first time they are needed and kept running between requests,
+/// up to the size of the pool. When every engine is busy, callers wait for one to be
+/// returned. Before an engine is handed out it is checked with `isready`, and an engine that
+/// has crashed or stopped answering is replaced with a fresh process.
+///
+/// The pool can be shared between threads, for example behind an `Arc`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use simple_chess::uci::pool::EnginePool;
+/// use simple_chess::uci::EngineCommand;
+/// use simple_chess::ChessGame;
+///
+/// let pool = EnginePool::new(EngineCommand::new("stockfish"), 4);
+/// let analysis = pool.analyze(&ChessGame::new(), 12).unwrap();
+/// println!("best move {}", analysis.get_best_move());
+/// ```
+pub struct EnginePool {
+    command: EngineCommand,
+    size: usize,
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+struct PoolState {
+    idle: Vec<UciEngine>,
+    running: usize,
+}
+
+impl EnginePool {
+    /// Creates a pool running at most `size` engines with the given command.
+    ///
+    /// No engine is started until one is first needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is `0`.
+    pub fn new(command: EngineCommand, size: usize) -> Self {
+        assert!(
+            size > 0,
+            "an engine pool needs room for at least one engine"
+        );
+        Self {
+            command,
+            size,
+            state: Mutex::new(PoolState {
+                idle: Vec::new(),
+                running: 0,
+            }),
+            returned: Condvar::new(),
+        }
+    }
+
+    /// Returns the most engines the pool runs at once.
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of engine processes currently running, busy or idle.
+    pub fn get_running_count(&self) -> usize {
+        self.lock_state().running
+    }
+
+    /// Runs a function with exclusive use of a healthy engine, waiting for one if every
+    /// engine is busy.
+    ///
+    /// The engine goes back into the pool afterwards unless the function fails, in which
+    /// case the engine may be in an unknown state and its process is stopped.
+    ///
+    /// # Returns
+    ///
+    /// The result of the function, or a `UciError` if no engine could be started.
+    pub fn with_engine<R>(
+        &self,
+        f: impl FnOnce(&mut UciEngine) -> Result<R, UciError>,
+    ) -> Result<R, UciError> {
+        let mut engine = self.acquire()?;
+        let result = f(&mut engine);
+        match result {
+            Ok(_) => self.release(engine),
+            Err(_) => self.discard(engine),
+        }
+        result
+    }
+
+    /// Searches the current position of a game to the given depth with one of the pool's
+    /// engines.
+    pub fn analyze(&self, game: &ChessGame, depth: u32) -> Result<UciAnalysis, UciError> {
+        self.with_engine(|engine| engine.analyze(game, depth))
+    }
+
+    /// Takes an idle engine, starting one if the pool has room, and checks it is healthy.
+    fn acquire(&self) -> Result<UciEngine, UciError> {
+        let idle_engine = {
+            let mut state = self.lock_state();
+            loop {
+                if let Some(engine) = state.idle.pop() {
+                    break Some(engine);
+                }
+                if state.running < self.size {
+                    state.running += 1;
+                    break None;
+                }
+                state = self
+                    .returned
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+        };
+
+        if let Some(mut engine) = idle_engine {
+            if engine.is_ready() {
+                return Ok(engine);
+            }
+            // a crashed engine keeps its place in the pool for its replacement
+            drop(engine);
+        }
+        UciEngine::spawn(&self.command).inspect_err(|_| {
+            self.lock_state().running -= 1;
+            self.returned.notify_one();
+        })
+    }
+
+    fn release(&self, engine: UciEngine) {
+        self.lock_state().idle.push(engine);
+        self.returned.notify_one();
+    }
+
+    fn discard(&self, engine: UciEngine) {
+        drop(engine);
+        self.lock_state().running -= 1;
+        self.returned.notify_one();
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::uci::tests::fake_engine_command;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn engines_are_reused_between_requests() {
+        let pool = EnginePool::new(fake_engine_command(), 2);
+        assert_eq!(0, pool.get_running_count());
+
+        for _ in 0..3 {
+            let analysis = pool.analyze(&ChessGame::new(), 3).unwrap();
+            assert_eq!("e2e4", analysis.get_best_move());
+        }
+        assert_eq!(1, pool.get_running_count());
+    }
+
+    #[test]
+    fn concurrency_is_bounded_by_the_pool_size() {
+        let pool = Arc::new(EnginePool::new(fake_engine_command(), 2));
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || pool.analyze(&ChessGame::new(), 3).is_ok())
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
+        assert!(pool.get_running_count() <= 2);
+    }
+
+    #[test]
+    fn crashed_engines_are_replaced() {
+        let pool = EnginePool::new(fake_engine_command(), 1);
+        assert!(pool.analyze(&ChessGame::new(), 99).is_err());
+        assert_eq!(0, pool.get_running_count());
+
+        assert!(pool.analyze(&ChessGame::new(), 3).is_ok());
+        assert_eq!(1, pool.get_running_count());
+    }
+
+    #[test]
+    fn failing_to_start_frees_the_slot() {
+        let pool = EnginePool::new(EngineCommand::new("/no/such/engine"), 1);
+        assert!(pool.analyze(&ChessGame::new(), 3).is_err());
+        assert!(pool.analyze(&ChessGame::new(), 3).is_err());
+        assert_eq!(0, pool.get_running_count());
+    }
+}
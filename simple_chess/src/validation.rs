@@ -0,0 +1,336 @@
+use crate::chess_game_move_analyzer::has_room_to_castle;
+use crate::chess_game_state_analyzer::is_in_check;
+use crate::piece::ChessPiece;
+use crate::piece::PieceType::{King, Pawn, Rook};
+use crate::{ChessGame, Color};
+use game_board::{get_square_name_from_row_and_col, Board};
+use std::fmt::{Display, Formatter};
+
+/// A rule of chess broken by the current position of a game.
+///
+/// Squares are given as `(column, row)`, the `Display` implementation describes the
+/// problem in words.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::validation::InvariantViolation;
+/// use simple_chess::Color;
+///
+/// let game = build_game_from_string("4k3/8/8/8/8/8/8/4K1P1 w - - 0 1").unwrap();
+/// let violations = game.validate().unwrap_err();
+///
+/// assert_eq!(
+///     vec![InvariantViolation::PawnOnBackRank {
+///         square: (6, 0),
+///         color: Color::White,
+///     }],
+///     violations
+/// );
+/// assert_eq!("the white pawn on g1 is on a back rank", violations[0].to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A player has a different number of kings than the variant gives them.
+    WrongKingCount {
+        color: Color,
+        count: usize,
+        expected: usize,
+    },
+    /// A pawn stands on its own first rank or on the rank it promotes on.
+    PawnOnBackRank {
+        square: (usize, usize),
+        color: Color,
+    },
+    /// Both kings are in check at the same time.
+    BothKingsInCheck,
+    /// The king of the player who just moved is still in check.
+    OpponentInCheck { color: Color },
+    /// The game has an en passant target square, but the pawn that double stepped over it
+    /// is not beyond it or the square itself is occupied, so en passant can not be resolved.
+    InvalidEnPassant { square: (usize, usize) },
+    /// A player keeps the right to castle to a side while the king or the rook is not on
+    /// its starting square, or the board is too narrow for the king to castle.
+    InvalidCastlingRights { color: Color, short: bool },
+}
+
+impl Display for InvariantViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvariantViolation::WrongKingCount {
+                color,
+                count,
+                expected,
+            } => write!(
+                f,
+                "{} has {} kings instead of {}",
+                color_name(*color),
+                count,
+                expected
+            ),
+            InvariantViolation::PawnOnBackRank { square, color } => write!(
+                f,
+                "the {} pawn on {} is on a back rank",
+                color_name(*color),
+                square_name(*square)
+            ),
+            InvariantViolation::BothKingsInCheck => write!(f, "both kings are in check"),
+            InvariantViolation::OpponentInCheck { color } => write!(
+                f,
+                "the {} king is in check, but it is not {}'s turn",
+                color_name(*color),
+                color_name(*color)
+            ),
+            InvariantViolation::InvalidEnPassant { square } => write!(
+                f,
+                "the pawn that double stepped to {} can not be taken en passant",
+                square_name(*square)
+            ),
+            InvariantViolation::InvalidCastlingRights { color, short } => write!(
+                f,
+                "{} may castle {} without the king and rook on their starting squares",
+                color_name(*color),
+                if *short { "short" } else { "long" }
+            ),
+        }
+    }
+}
+
+/// Finds every rule of chess broken by the current position of a game.
+pub(crate) fn validate(game: &ChessGame) -> Result<(), Vec<InvariantViolation>> {
+    let board = game.get_board();
+    let variant = game.get_variant();
+    let study_mode = game.get_ruleset().is_study_mode();
+    let mut violations = Vec::new();
+
+    if !study_mode {
+        for color in [Color::White, Color::Black] {
+            let count = count_pieces(board, ChessPiece::new(King, color));
+            let expected = if variant.has_king(color) { 1 } else { 0 };
+            if count != expected {
+                violations.push(InvariantViolation::WrongKingCount {
+                    color,
+                    count,
+                    expected,
+                });
+            }
+        }
+    }
+
+    let pawn_rules = game.get_ruleset().get_pawn_rules();
+    let height = board.get_height();
+    for row in 0..height {
+        for col in 0..board.get_width() {
+            let Some(piece) = board.get_piece_at_space(col, row) else {
+                continue;
+            };
+            if piece.get_piece_type() != Pawn {
+                continue;
+            }
+            let color = piece.get_color();
+            let rank = color.relative_rank(row, height);
+            let promotion_rank =
+                color.relative_rank(pawn_rules.get_promotion_row(color, height), height);
+            // a player without a king, such as white in horde, may start with pawns on the
+            // first rank
+            let on_first_rank = rank == 0 && variant.has_king(color);
+            if on_first_rank || rank >= promotion_rank {
+                violations.push(InvariantViolation::PawnOnBackRank {
+                    square: (col, row),
+                    color,
+                });
+            }
+        }
+    }
+
+    if !study_mode {
+        let waiting = game.get_current_players_turn().opposite();
+        if is_in_check(waiting, board) {
+            if is_in_check(waiting.opposite(), board) {
+                violations.push(InvariantViolation::BothKingsInCheck);
+            } else {
+                violations.push(InvariantViolation::OpponentInCheck { color: waiting });
+            }
+        }
+    }
+
+    if let Some(square) = get_invalid_en_passant(game) {
+        violations.push(InvariantViolation::InvalidEnPassant { square });
+    }
+
+    let castling_distance = game.get_ruleset().get_castling_distance();
+    let (wl, ws, bl, bs) = game.get_castling_rights();
+    for (color, short, right) in [
+        (Color::White, false, wl),
+        (Color::White, true, ws),
+        (Color::Black, false, bl),
+        (Color::Black, true, bs),
+    ] {
+        if right && !can_castle_from_position(board, color, short, castling_distance) {
+            violations.push(InvariantViolation::InvalidCastlingRights { color, short });
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn count_pieces(board: &Board<ChessPiece>, piece: ChessPiece) -> usize {
+    (0..board.get_height())
+        .flat_map(|row| (0..board.get_width()).map(move |col| (col, row)))
+        .filter(|&(col, row)| board.get_piece_at_space(col, row) == Some(&piece))
+        .count()
+}
+
+/// Returns the square of the pawn that double stepped to allow the en passant capture if
+/// the position does not match the double step.
+fn get_invalid_en_passant(game: &ChessGame) -> Option<(usize, usize)> {
+    let (col, row) = game.get_en_passant_target()?;
+    let mover = game.get_current_players_turn().opposite();
+    let board = game.get_board();
+    let pawn_square = row
+        .checked_add_signed(mover.forward() as isize)
+        .filter(|pawn_row| *pawn_row < board.get_height())
+        .map(|pawn_row| (col, pawn_row));
+    let Some(pawn_square) = pawn_square else {
+        return Some((col, row));
+    };
+
+    let pawn_moved_away = board.get_piece_at_space(pawn_square.0, pawn_square.1)
+        != Some(&ChessPiece::new(Pawn, mover));
+    let passed_square_occupied = board.get_piece_at_space(col, row).is_some();
+    if pawn_moved_away || passed_square_occupied {
+        Some(pawn_square)
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if the king and the rook of a side stand where castling needs them.
+fn can_castle_from_position(
+    board: &Board<ChessPiece>,
+    color: Color,
+    short: bool,
+    distance: usize,
+) -> bool {
+    let width = board.get_width();
+    let row = color.relative_rank(0, board.get_height());
+    let rook_col = if short { width - 1 } else { 0 };
+    let has_rook = board.get_piece_at_space(rook_col, row) == Some(&ChessPiece::new(Rook, color));
+    let has_king = (1..width - 1).any(|col| {
+        board.get_piece_at_space(col, row) == Some(&ChessPiece::new(King, color))
+            && has_room_to_castle(col, width, short, distance)
+    });
+    has_rook && has_king
+}
+
+fn square_name((col, row): (usize, usize)) -> String {
+    get_square_name_from_row_and_col(col, row)
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::codec::standard_algebraic_notation::decode_move_from_string;
+    use crate::random_position::{random_position, PositionConstraints, PositionRng};
+    use crate::setup::BoardSetup;
+    use crate::ChessGameBuilder;
+
+    #[test]
+    fn positions_reached_by_playing_are_valid() {
+        let mut game = ChessGame::new();
+        assert_eq!(Ok(()), game.validate());
+        for san in [
+            "e4", "d5", "exd5", "c5", "dxc6", "Nf6", "cxb7", "Bd7", "bxa8=Q",
+        ] {
+            let chess_move = decode_move_from_string(&game, san).unwrap();
+            game.make_move(chess_move);
+            assert_eq!(Ok(()), game.validate(), "after {san}");
+        }
+
+        assert_eq!(Ok(()), BoardSetup::horde().build_game().unwrap().validate());
+
+        let mut rng = PositionRng::new(5);
+        for _ in 0..50 {
+            let position = random_position(&mut rng, &PositionConstraints::new()).unwrap();
+            assert_eq!(Ok(()), position.validate());
+        }
+    }
+
+    #[test]
+    fn kings_and_checks_are_validated() {
+        let no_black_king = build_game_from_string("8/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            Err(vec![InvariantViolation::WrongKingCount {
+                color: Color::Black,
+                count: 0,
+                expected: 1,
+            }]),
+            no_black_king.validate()
+        );
+
+        // black is in check but white is to move
+        let opponent_in_check = build_game_from_string("4k3/4R3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            Err(vec![InvariantViolation::OpponentInCheck {
+                color: Color::Black
+            }]),
+            opponent_in_check.validate()
+        );
+
+        let both_in_check = build_game_from_string("4k3/4R3/8/8/8/8/4r3/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            Err(vec![InvariantViolation::BothKingsInCheck]),
+            both_in_check.validate()
+        );
+    }
+
+    #[test]
+    fn castling_rights_need_the_king_and_rook_at_home() {
+        let game = build_game_from_string("4k3/8/8/8/8/8/8/4K2R w KQ - 0 1").unwrap();
+        assert_eq!(
+            Err(vec![InvariantViolation::InvalidCastlingRights {
+                color: Color::White,
+                short: false,
+            }]),
+            game.validate()
+        );
+    }
+
+    #[test]
+    fn en_passant_must_match_a_double_step() {
+        // the pawn that double stepped over e3 is missing
+        let board = build_game_from_string("4k3/8/8/8/8/8/8/4K3 b - - 0 1")
+            .unwrap()
+            .get_board()
+            .clone();
+        let game = ChessGameBuilder::new()
+            .set_board(board)
+            .set_current_turn(Color::Black)
+            .set_castle_rights(false, false, false, false)
+            .set_en_passant_target((4, 2))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Err(vec![InvariantViolation::InvalidEnPassant {
+                square: (4, 3)
+            }]),
+            game.validate()
+        );
+        let game = build_game_from_string("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").unwrap();
+        assert_eq!(Ok(()), game.validate());
+    }
+}
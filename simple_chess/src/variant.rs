@@ -0,0 +1,515 @@
+use crate::chess_game_state_analyzer::{is_dead_position, is_insufficient_material};
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, ChessMoveType, Color};
+use game_board::Board;
+use std::fmt::Debug;
+
+/// The number of checks that wins a game of Three-check.
+const THREE_CHECK_LIMIT: usize = 3;
+
+/// The rules of a chess variant that differ from standard chess.
+///
+/// A `ChessGame` consults its variant to end the game early, to adjust the legal moves of a
+/// position and to decide when there is too little material left to win. Every method has a
+/// default that follows standard chess, so a variant only overrides the rules it changes.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::GameState;
+/// use simple_chess::variant::Variant;
+/// use simple_chess::{ChessGame, ChessGameBuilder, ChessMoveType, Color};
+///
+/// /// A variant where kings may not move.
+/// #[derive(Debug)]
+/// struct FrozenKings;
+///
+/// impl Variant for FrozenKings {
+///     fn get_name(&self) -> &str {
+///         "Frozen Kings"
+///     }
+///
+///     fn filter_legal_moves(&self, game: &ChessGame, legal_moves: Vec<ChessMoveType>) -> Vec<ChessMoveType> {
+///         let board = game.get_board();
+///         legal_moves
+///             .into_iter()
+///             .filter(|chess_move| {
+///                 let (col, row) = chess_move.get_original_position();
+///                 board.get_piece_at_space(col, row).map(|piece| piece.get_piece_type())
+///                     != Some(simple_chess::piece::PieceType::King)
+///             })
+///             .collect()
+///     }
+/// }
+///
+/// let game = ChessGame::new();
+/// let mut game = ChessGameBuilder::new()
+///     .set_board(game.get_board().clone())
+///     .set_current_turn(Color::White)
+///     .set_variant(FrozenKings)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!("Frozen Kings", game.get_variant().get_name());
+/// match game.get_game_state() {
+///     GameState::InProgress { legal_moves, .. } => assert_eq!(20, legal_moves.len()),
+///     _ => panic!("the game should be in progress"),
+/// }
+/// ```
+pub trait Variant: Debug + Send + Sync {
+    /// Returns the name of the variant.
+    fn get_name(&self) -> &str;
+
+    /// Returns the winner of the game if one of the variant's own win conditions has been
+    /// met, such as a king reaching the center in King of the Hill.
+    ///
+    /// Checkmate is detected by the game itself and does not need to be handled here.
+    fn get_winner(&self, _game: &ChessGame) -> Option<Color> {
+        None
+    }
+
+    /// Adjusts the legal moves of the current position, removing moves the variant forbids
+    /// or adding moves it allows.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game whose position the moves were generated for.
+    /// * `legal_moves` - The moves that are legal in standard chess.
+    fn filter_legal_moves(
+        &self,
+        _game: &ChessGame,
+        legal_moves: Vec<ChessMoveType>,
+    ) -> Vec<ChessMoveType> {
+        legal_moves
+    }
+
+    /// Returns the number of checks a player must give to win, `None` for variants where
+    /// checks are not counted.
+    ///
+    /// The game only counts the checks each player gives when this returns `Some`, see
+    /// `ChessGame::get_checks_given`.
+    fn get_check_limit(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns `true` if neither player has enough material left to win, which allows a
+    /// draw to be claimed.
+    fn is_insufficient_material(&self, board: &Board<ChessPiece>) -> bool {
+        is_insufficient_material(board)
+    }
+
+    /// Returns `true` if no sequence of legal moves can end the game with a win, which
+    /// draws the game immediately.
+    fn is_dead_position(&self, board: &Board<ChessPiece>) -> bool {
+        is_dead_position(board)
+    }
+
+    /// Returns `true` if the player has a king, `false` for variants where one side plays
+    /// without one.
+    fn has_king(&self, _color: Color) -> bool {
+        true
+    }
+}
+
+/// Standard chess, the default variant of every game.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardChess;
+
+impl Variant for StandardChess {
+    fn get_name(&self) -> &str {
+        "Standard"
+    }
+}
+
+/// King of the Hill, a player also wins by moving their king onto one of the four center
+/// squares of the board.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::GameState;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::variant::KingOfTheHill;
+/// use simple_chess::{ChessGameBuilder, Color};
+///
+/// let position = build_game_from_string("4k3/8/8/4K3/8/8/8/8 b - - 0 1").unwrap();
+/// let mut game = ChessGameBuilder::new()
+///     .set_board(position.get_board().clone())
+///     .set_current_turn(Color::Black)
+///     .set_variant(KingOfTheHill)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(GameState::VariantWin { winner: Color::White }, game.get_game_state());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KingOfTheHill;
+
+impl Variant for KingOfTheHill {
+    fn get_name(&self) -> &str {
+        "King of the Hill"
+    }
+
+    fn get_winner(&self, game: &ChessGame) -> Option<Color> {
+        let board = game.get_board();
+        let center_cols = get_center(board.get_width());
+        let center_rows = get_center(board.get_height());
+        for &col in &center_cols {
+            for &row in &center_rows {
+                if let Some(piece) = board.get_piece_at_space(col, row) {
+                    if piece.get_piece_type() == PieceType::King {
+                        return Some(piece.get_color());
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn is_insufficient_material(&self, _board: &Board<ChessPiece>) -> bool {
+        // a lone king can still win by walking to the center
+        false
+    }
+
+    fn is_dead_position(&self, _board: &Board<ChessPiece>) -> bool {
+        false
+    }
+}
+
+/// Horde, white plays an army of pawns without a king against the standard black army.
+///
+/// White wins by checkmating black as usual, and black wins by capturing every white
+/// piece. A side without a king can never be checkmated, so black can always still win by
+/// capturing and the game is never drawn for insufficient material.
+///
+/// Use `BoardSetup::horde` from [`crate::setup`] to set up the starting position.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Horde;
+
+impl Variant for Horde {
+    fn get_name(&self) -> &str {
+        "Horde"
+    }
+
+    fn get_winner(&self, game: &ChessGame) -> Option<Color> {
+        let board = game.get_board();
+        let white_has_pieces = (0..board.get_width()).any(|col| {
+            (0..board.get_height()).any(|row| {
+                board
+                    .get_piece_at_space(col, row)
+                    .is_some_and(|piece| piece.get_color() == Color::White)
+            })
+        });
+        if white_has_pieces {
+            None
+        } else {
+            Some(Color::Black)
+        }
+    }
+
+    fn is_insufficient_material(&self, _board: &Board<ChessPiece>) -> bool {
+        false
+    }
+
+    fn is_dead_position(&self, _board: &Board<ChessPiece>) -> bool {
+        false
+    }
+
+    fn has_king(&self, color: Color) -> bool {
+        color == Color::Black
+    }
+}
+
+/// Three-check, a player also wins by giving check three times.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::GameState;
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::Color;
+///
+/// // white has given two checks and one more wins
+/// let game = build_game_from_string("4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1").unwrap();
+/// assert_eq!("Three-check", game.get_variant().get_name());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreeCheck;
+
+impl Variant for ThreeCheck {
+    fn get_name(&self) -> &str {
+        "Three-check"
+    }
+
+    fn get_winner(&self, game: &ChessGame) -> Option<Color> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| game.get_checks_given(color) >= THREE_CHECK_LIMIT)
+    }
+
+    fn get_check_limit(&self) -> Option<usize> {
+        Some(THREE_CHECK_LIMIT)
+    }
+}
+
+/// Capablanca chess, played on a 10x8 board where each side adds an archbishop, moving like a
+/// bishop or a knight, and a chancellor, moving like a rook or a knight, to the standard army.
+///
+/// Pawns may also promote to an archbishop or chancellor. Castling moves the king three
+/// squares rather than two, which is part of the game's [`Ruleset`](crate::ruleset::Ruleset)
+/// rather than the variant, see `Ruleset::set_castling_distance`.
+///
+/// Use `BoardSetup::capablanca` from [`crate::setup`] to set up the starting position, or
+/// read a 10x8 position from FEN.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::GameState;
+/// use simple_chess::setup::BoardSetup;
+///
+/// let mut game = BoardSetup::capablanca().build_game().unwrap();
+/// assert_eq!("Capablanca", game.get_variant().get_name());
+/// match game.get_game_state() {
+///     GameState::InProgress { legal_moves, .. } => assert_eq!(28, legal_moves.len()),
+///     _ => panic!("the game should be in progress"),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Capablanca;
+
+impl Variant for Capablanca {
+    fn get_name(&self) -> &str {
+        "Capablanca"
+    }
+
+    fn filter_legal_moves(
+        &self,
+        _game: &ChessGame,
+        legal_moves: Vec<ChessMoveType>,
+    ) -> Vec<ChessMoveType> {
+        let mut moves = Vec::with_capacity(legal_moves.len());
+        for chess_move in legal_moves {
+            moves.push(chess_move);
+            // every promotion is also offered as an archbishop and a chancellor, which keep
+            // the king exactly as safe as the queen does
+            if let ChessMoveType::Move {
+                promotion: Some(promotion),
+                ..
+            }
+            | ChessMoveType::EnPassant {
+                promotion: Some(promotion),
+                ..
+            } = chess_move
+            {
+                if promotion.get_piece_type() == PieceType::Queen {
+                    for piece_type in [PieceType::Archbishop, PieceType::Chancellor] {
+                        moves.push(with_promotion(
+                            chess_move,
+                            ChessPiece::new(piece_type, promotion.get_color()),
+                        ));
+                    }
+                }
+            }
+        }
+        moves
+    }
+}
+
+/// Returns a copy of a promoting move that promotes to another piece.
+fn with_promotion(chess_move: ChessMoveType, piece: ChessPiece) -> ChessMoveType {
+    match chess_move {
+        ChessMoveType::Move {
+            original_position,
+            new_position,
+            piece: pawn,
+            taken_piece,
+            ..
+        } => ChessMoveType::Move {
+            original_position,
+            new_position,
+            piece: pawn,
+            taken_piece,
+            promotion: Some(piece),
+        },
+        ChessMoveType::EnPassant {
+            original_position,
+            new_position,
+            piece: pawn,
+            taken_piece,
+            taken_piece_position,
+            ..
+        } => ChessMoveType::EnPassant {
+            original_position,
+            new_position,
+            piece: pawn,
+            taken_piece,
+            taken_piece_position,
+            promotion: Some(piece),
+        },
+        castle => castle,
+    }
+}
+
+/// Returns the center files or ranks of a board side, two for even lengths and one for odd.
+fn get_center(length: usize) -> Vec<usize> {
+    if length.is_multiple_of(2) {
+        vec![length / 2 - 1, length / 2]
+    } else {
+        vec![length / 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_game_state_analyzer::GameState;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::ChessGameBuilder;
+
+    fn build_variant_game<V: Variant + 'static>(fen: &str, variant: V) -> ChessGame {
+        let position = build_game_from_string(fen).unwrap();
+        ChessGameBuilder::new()
+            .set_board(position.get_board().clone())
+            .set_current_turn(position.get_current_players_turn())
+            .set_castle_rights(false, false, false, false)
+            .set_variant(variant)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn standard_chess_is_the_default() {
+        let game = ChessGame::new();
+        assert_eq!("Standard", game.get_variant().get_name());
+    }
+
+    #[test]
+    fn king_of_the_hill_is_won_on_the_center() {
+        let mut off_center = build_variant_game("4k3/8/8/8/8/8/8/4K3 w - - 0 1", KingOfTheHill);
+        assert!(matches!(
+            off_center.get_game_state(),
+            GameState::InProgress { .. }
+        ));
+
+        let mut on_center = build_variant_game("4k3/8/8/3K4/8/8/8/8 b - - 0 1", KingOfTheHill);
+        assert_eq!(
+            GameState::VariantWin {
+                winner: Color::White
+            },
+            on_center.get_game_state()
+        );
+    }
+
+    #[test]
+    fn horde_is_never_drawn_for_insufficient_material() {
+        // a lone white bishop against a lone king is a draw in standard chess
+        let standard = build_variant_game("4k3/8/8/8/8/8/8/2B5 w - - 0 1", StandardChess);
+        let horde = build_variant_game("4k3/8/8/8/8/8/8/2B5 w - - 0 1", Horde);
+
+        assert!(standard.can_claim_draw().is_some());
+        assert!(horde.can_claim_draw().is_none());
+    }
+
+    #[test]
+    fn third_check_wins_three_check() {
+        let mut game = build_variant_game("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", ThreeCheck);
+        game.set_checks_given(2, 0);
+        let check = ChessMoveType::Move {
+            original_position: (0, 0),
+            new_position: (0, 7),
+            piece: ChessPiece::new(PieceType::Rook, Color::White),
+            taken_piece: None,
+            promotion: None,
+        };
+
+        assert_eq!(
+            GameState::VariantWin {
+                winner: Color::White
+            },
+            game.make_move(check)
+        );
+        assert_eq!(3, game.get_checks_given(Color::White));
+    }
+
+    #[test]
+    fn checks_are_not_counted_without_a_check_limit() {
+        let mut game = build_variant_game("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", StandardChess);
+        game.make_move(ChessMoveType::Move {
+            original_position: (0, 0),
+            new_position: (0, 7),
+            piece: ChessPiece::new(PieceType::Rook, Color::White),
+            taken_piece: None,
+            promotion: None,
+        });
+        assert_eq!(0, game.get_checks_given(Color::White));
+    }
+
+    #[test]
+    fn capablanca_kings_castle_three_squares() {
+        let mut game =
+            build_game_from_string("r4k3r/10/10/10/10/10/10/R4K3R w KQkq - 0 1").unwrap();
+        let castles: Vec<ChessMoveType> = match game.get_game_state() {
+            GameState::InProgress { legal_moves, .. } => legal_moves
+                .into_iter()
+                .filter(|chess_move| matches!(chess_move, ChessMoveType::Castle { .. }))
+                .collect(),
+            _ => panic!("the game should be in progress"),
+        };
+        assert_eq!(
+            vec![
+                ChessMoveType::Castle {
+                    rook_original_position: (0, 0),
+                    rook_new_position: (3, 0),
+                    king_original_position: (5, 0),
+                    king_new_position: (2, 0),
+                },
+                ChessMoveType::Castle {
+                    rook_original_position: (9, 0),
+                    rook_new_position: (7, 0),
+                    king_original_position: (5, 0),
+                    king_new_position: (8, 0),
+                },
+            ],
+            castles
+        );
+
+        // the king may not pass through d1, which the rook on d8 attacks
+        let mut attacked =
+            build_game_from_string("r2r1k3r/10/10/10/10/10/10/R4K3R w KQ - 0 1").unwrap();
+        match attacked.get_game_state() {
+            GameState::InProgress { legal_moves, .. } => {
+                assert!(!legal_moves.contains(&castles[0]))
+            }
+            _ => panic!("the game should be in progress"),
+        }
+    }
+
+    #[test]
+    fn capablanca_pawns_promote_to_compound_pieces() {
+        let mut game = build_game_from_string("5k4/1P8/10/10/10/10/10/5K4 w - - 0 1").unwrap();
+        let promotions: Vec<PieceType> = match game.get_game_state() {
+            GameState::InProgress { legal_moves, .. } => legal_moves
+                .into_iter()
+                .filter_map(|chess_move| match chess_move {
+                    ChessMoveType::Move { promotion, .. } => promotion,
+                    _ => None,
+                })
+                .map(|piece| piece.get_piece_type())
+                .collect(),
+            _ => panic!("the game should be in progress"),
+        };
+        assert_eq!(6, promotions.len());
+        assert!(promotions.contains(&PieceType::Archbishop));
+        assert!(promotions.contains(&PieceType::Chancellor));
+    }
+
+    #[test]
+    fn lone_kings_are_not_a_draw_in_king_of_the_hill() {
+        let standard = build_variant_game("4k3/8/8/8/8/8/8/4K3 w - - 0 1", StandardChess);
+        let hill = build_variant_game("4k3/8/8/8/8/8/8/4K3 w - - 0 1", KingOfTheHill);
+
+        assert!(standard.can_claim_draw().is_some());
+        assert!(hill.can_claim_draw().is_none());
+    }
+}
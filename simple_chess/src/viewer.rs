@@ -0,0 +1,180 @@
+use crate::piece::ChessPiece;
+use crate::{ChessGame, ChessMoveType};
+use game_board::Board;
+
+/// Steps through the positions of a game, for example to let a player scrub through a
+/// finished game in an analysis view.
+///
+/// The viewer keeps a single board and plays or takes back one move at a time, so moving
+/// to a neighbouring position is cheap however long the game is. Ply `0` is the position
+/// before the first move of the game, and the last ply is the current position.
+///
+/// # Examples
+///
+/// ```
+/// use simple_chess::chess_game_state_analyzer::GameState;
+/// use simple_chess::viewer::GameViewer;
+/// use simple_chess::ChessGame;
+///
+/// let mut game = ChessGame::new();
+/// for _ in 0..4 {
+///     if let GameState::InProgress { legal_moves, .. } = game.get_game_state() {
+///         game.make_move(legal_moves[0]);
+///     }
+/// }
+///
+/// let mut viewer = GameViewer::new(&game);
+/// assert_eq!(0, viewer.get_ply());
+/// assert_eq!(&ChessGame::new().get_board().clone(), viewer.get_board());
+///
+/// assert!(viewer.next());
+/// viewer.jump_to(4);
+/// assert_eq!(game.get_board(), viewer.get_board());
+/// assert!(!viewer.next());
+///
+/// assert!(viewer.prev());
+/// assert_eq!(3, viewer.get_ply());
+/// ```
+pub struct GameViewer<'a> {
+    moves: &'a [ChessMoveType],
+    board: Board<ChessPiece>,
+    ply: usize,
+}
+
+impl<'a> GameViewer<'a> {
+    /// Creates a viewer showing the position before the first move of a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game to step through, its moves must be the moves that were played to
+    ///   reach its board.
+    pub fn new(game: &'a ChessGame) -> Self {
+        let moves = game.get_moves().as_slice();
+        let mut viewer = Self {
+            moves,
+            board: game.get_board().clone(),
+            ply: moves.len(),
+        };
+        viewer.jump_to(0);
+        viewer
+    }
+
+    /// Returns the board of the position being shown.
+    pub fn get_board(&self) -> &Board<ChessPiece> {
+        &self.board
+    }
+
+    /// Returns the number of moves played to reach the position being shown.
+    pub fn get_ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Returns the number of moves in the game, which is the ply of its last position.
+    pub fn get_ply_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns the move that led to the position being shown, `None` at the start of the game.
+    pub fn get_last_move(&self) -> Option<&'a ChessMoveType> {
+        self.ply.checked_sub(1).map(|index| &self.moves[index])
+    }
+
+    /// Moves forward one ply.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the viewer moved, `false` if it was already at the end of the game.
+    // not an iterator, as stepping back and forth does not consume the viewer
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> bool {
+        match self.moves.get(self.ply) {
+            Some(chess_move) => {
+                chess_move.make_move(&mut self.board);
+                self.ply += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves back one ply.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the viewer moved, `false` if it was already at the start of the game.
+    pub fn prev(&mut self) -> bool {
+        match self.get_last_move() {
+            Some(chess_move) => {
+                chess_move.undo_move(&mut self.board);
+                self.ply -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves to the position after the given number of moves, stopping at the end of the
+    /// game if there are fewer moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `ply` - The number of moves played to reach the position to show.
+    pub fn jump_to(&mut self, ply: usize) {
+        let ply = ply.min(self.moves.len());
+        while self.ply < ply {
+            self.next();
+        }
+        while self.ply > ply {
+            self.prev();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::standard_algebraic_notation::{decode_move_from_string, play_moves};
+
+    #[test]
+    fn every_position_of_a_game_can_be_revisited() {
+        let sans = [
+            "e4", "d5", "exd5", "Qxd5", "Nc3", "Qe5+", "Be2", "Qxe2+", "Ngxe2",
+        ];
+        let mut replayed = ChessGame::new();
+        let mut game = ChessGame::new();
+        play_moves(&mut game, &sans);
+        let mut viewer = GameViewer::new(&game);
+
+        for (ply, san) in sans.iter().enumerate() {
+            assert_eq!(replayed.get_board(), viewer.get_board());
+            assert_eq!(Some(replayed.get_board().clone()), game.position_at(ply));
+            let chess_move = decode_move_from_string(&replayed, san).unwrap();
+            replayed.make_move(chess_move);
+            assert!(viewer.next());
+            assert_eq!(Some(&chess_move), viewer.get_last_move());
+        }
+        assert_eq!(game.get_board(), viewer.get_board());
+        assert_eq!(None, game.position_at(sans.len() + 1));
+
+        viewer.jump_to(0);
+        assert_eq!(ChessGame::new().get_board(), viewer.get_board());
+        assert!(!viewer.prev());
+    }
+
+    #[test]
+    fn castling_and_en_passant_are_taken_back() {
+        let mut game = ChessGame::new();
+        play_moves(
+            &mut game,
+            &[
+                "e4", "Nf6", "e5", "d5", "exd6", "e6", "Nf3", "Be7", "Bc4", "O-O",
+            ],
+        );
+        let mut viewer = GameViewer::new(&game);
+        viewer.jump_to(game.get_moves().len());
+        viewer.jump_to(4);
+
+        assert_eq!(Some(viewer.get_board().clone()), game.position_at(4));
+        assert!(viewer.get_board().get_piece_at_space(3, 4).is_some());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::codec::json::{encode_game_state_as_json, encode_moves_as_json};
+use crate::codec::standard_algebraic_notation::decode_move_from_string;
+use crate::codec::{forsyth_edwards_notation, long_algebraic_notation, portable_game_notation};
+use crate::error::ChessError;
+use crate::square_name::SquareName;
+use crate::{ChessGame, ChessMoveType, Color};
+use wasm_bindgen::prelude::*;
+
+/// A game exposed to JavaScript as `Game`.
+///
+/// Squares are passed as names such as `"e4"`, and moves and states are returned as the
+/// JSON documents of `codec::json`, so a web client can run the rules without a server.
+/// Methods that can fail throw an `Error` with the reason.
+///
+/// ```js
+/// import { Game } from "simple_chess";
+///
+/// const game = new Game();
+/// game.makeMove("e2", "e4");
+/// const state = JSON.parse(game.state());
+/// ```
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame {
+    game: ChessGame,
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    /// Creates a game in the standard starting position.
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> WasmGame {
+        WasmGame {
+            game: ChessGame::new(),
+        }
+    }
+
+    /// Creates a game from a string in Forsyth-Edwards Notation.
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str) -> Result<WasmGame, JsError> {
+        let game = forsyth_edwards_notation::build_game_from_string(fen).map_err(to_js_error)?;
+        Ok(WasmGame { game })
+    }
+
+    /// Creates a game from the first game of a PGN string.
+    #[wasm_bindgen(js_name = fromPgn)]
+    pub fn from_pgn(pgn: &str) -> Result<WasmGame, JsError> {
+        let game = portable_game_notation::build_game_from_string(pgn).map_err(to_js_error)?;
+        Ok(WasmGame { game })
+    }
+
+    /// Returns the current position in Forsyth-Edwards Notation.
+    #[wasm_bindgen(js_name = toFen)]
+    pub fn to_fen(&self) -> String {
+        forsyth_edwards_notation::encode_game_as_string(&self.game)
+    }
+
+    /// Returns the game in PGN without any tags besides the result and starting position.
+    #[wasm_bindgen(js_name = toPgn)]
+    pub fn to_pgn(&self) -> String {
+        portable_game_notation::encode_game_as_string(&self.game, &[])
+    }
+
+    /// Returns the player to move, `"white"` or `"black"`.
+    pub fn turn(&self) -> String {
+        match self.game.get_current_players_turn() {
+            Color::White => String::from("white"),
+            Color::Black => String::from("black"),
+        }
+    }
+
+    /// Returns the piece on a square as its FEN letter, uppercase for white, or `undefined`
+    /// for an empty square.
+    #[wasm_bindgen(js_name = pieceAt)]
+    pub fn piece_at(&self, square: &str) -> Result<Option<String>, JsError> {
+        let (col, row) = parse_square(square).map_err(to_js_error)?;
+        Ok(self
+            .game
+            .get_board()
+            .get_piece_at_space(col, row)
+            .map(|piece| piece.as_fen_char().to_string()))
+    }
+
+    /// Returns the legal moves as a JSON array, each move with its `san`, `uci`, `from`
+    /// and `to` squares and whether it `gives_check`.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&mut self) -> String {
+        let moves = self.game.legal_moves().to_vec();
+        encode_moves_as_json(&self.game, &moves)
+    }
+
+    /// Returns the legal moves of the piece on a square as a JSON array, like `legalMoves`.
+    #[wasm_bindgen(js_name = legalMovesFrom)]
+    pub fn legal_moves_from(&mut self, square: &str) -> Result<String, JsError> {
+        let (col, row) = parse_square(square).map_err(to_js_error)?;
+        let moves = self.game.legal_moves_from(col, row);
+        Ok(encode_moves_as_json(&self.game, &moves))
+    }
+
+    /// Makes a move given by its squares, with the promotion piece as a lowercase letter
+    /// such as `"q"`, and returns the new state as JSON.
+    #[wasm_bindgen(js_name = makeMove)]
+    pub fn make_move(
+        &mut self,
+        from: &str,
+        to: &str,
+        promotion: Option<String>,
+    ) -> Result<String, JsError> {
+        let chess_move = self
+            .find_move(from, to, promotion.as_deref())
+            .map_err(to_js_error)?;
+        self.play(chess_move).map_err(to_js_error)
+    }
+
+    /// Makes a move given in Standard Algebraic Notation, such as `"Nf3"`, and returns the
+    /// new state as JSON.
+    #[wasm_bindgen(js_name = makeSanMove)]
+    pub fn make_san_move(&mut self, san: &str) -> Result<String, JsError> {
+        let chess_move = decode_move_from_string(&self.game, san).map_err(to_js_error)?;
+        self.play(chess_move).map_err(to_js_error)
+    }
+
+    /// Returns the state of the game as the JSON document of
+    /// `codec::json::encode_game_state_as_json`.
+    pub fn state(&self) -> String {
+        encode_game_state_as_json(&self.game, None)
+    }
+}
+
+impl WasmGame {
+    /// Finds the legal move between two squares.
+    fn find_move(
+        &self,
+        from: &str,
+        to: &str,
+        promotion: Option<&str>,
+    ) -> Result<ChessMoveType, ChessError> {
+        parse_square(from)?;
+        parse_square(to)?;
+        let lan = format!("{from}{to}{}", promotion.unwrap_or_default());
+        long_algebraic_notation::decode_move_from_string(&self.game, &lan)
+    }
+
+    fn play(&mut self, chess_move: ChessMoveType) -> Result<String, ChessError> {
+        self.game.try_make_move(chess_move)?;
+        Ok(self.state())
+    }
+}
+
+fn parse_square(square: &str) -> Result<(usize, usize), ChessError> {
+    square
+        .parse::<SquareName>()
+        .map(|name| name.get_col_and_row())
+        .map_err(|e| ChessError::ParseError(e.to_string()))
+}
+
+fn to_js_error(error: ChessError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_are_made_from_square_names() {
+        let mut game = WasmGame::new();
+        let e4 = game.find_move("e2", "e4", None).unwrap();
+        let state = game.play(e4).unwrap();
+
+        assert!(state.contains(r#""turn":"black""#));
+        assert_eq!("black", game.turn());
+        assert_eq!(Some(String::from("P")), game.piece_at("e4").unwrap());
+        assert!(game.to_pgn().ends_with("1. e4 *\n"));
+    }
+
+    #[test]
+    fn promotions_need_the_promotion_piece() {
+        let mut game = WasmGame::from_fen("8/4P1k1/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert!(matches!(
+            game.find_move("e7", "e8", None),
+            Err(ChessError::IllegalMove(_))
+        ));
+        let promotion = game.find_move("e7", "e8", Some("q")).unwrap();
+        game.play(promotion).unwrap();
+
+        assert_eq!(Some(String::from("Q")), game.piece_at("e8").unwrap());
+    }
+
+    #[test]
+    fn legal_moves_are_json() {
+        let mut game = WasmGame::new();
+        let moves = game.legal_moves_from("g1").unwrap();
+
+        assert!(moves.starts_with('['));
+        assert!(moves.contains(r#""san":"Nf3""#));
+        assert!(moves.contains(r#""san":"Nh3""#));
+        assert_eq!(20, game.legal_moves().matches("\"san\"").count());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::piece::{ChessPiece, PieceType};
+use crate::{ChessGame, Color};
+use game_board::Board;
+
+const SEED: u64 = 0x5EED_C4E5_5B0A_4D00;
+const SIDE_TO_MOVE_INDEX: u64 = 0;
+const CASTLING_RIGHTS_INDEX: u64 = 1;
+const EN_PASSANT_INDEX: u64 = 5;
+const PIECE_SQUARE_INDEX: u64 = 1024;
+const FAIRY_PIECE_SQUARE_INDEX: u64 = 1 << 40;
+
+/// Computes a Zobrist hash of the pieces on a board.
+///
+/// Every piece on every square contributes a pseudo-random 64-bit key, and the keys are
+/// combined with XOR. Two boards with the same pieces on the same squares always hash to the
+/// same value, boards that differ hash to different values with very high probability.
+///
+/// Keys are derived from the square index rather than read from a fixed table, so boards of
+/// any size can be hashed.
+///
+/// # Example
+/// ```
+/// use simple_chess::zobrist::hash_board;
+/// use simple_chess::ChessGame;
+///
+/// let game = ChessGame::new();
+/// assert_eq!(hash_board(game.get_board()), hash_board(&game.get_board().clone()));
+/// ```
+pub fn hash_board(board: &Board<ChessPiece>) -> u64 {
+    let mut hash = 0;
+    for row in 0..board.get_height() {
+        for col in 0..board.get_width() {
+            if let Some(piece) = board.get_piece_at_space(col, row) {
+                hash ^= piece_square_key(piece, col + row * board.get_width());
+            }
+        }
+    }
+    hash
+}
+
+/// Computes a Zobrist hash of a game position.
+///
+/// In addition to the pieces on the board, the hash includes the side to move, the castling
+/// rights and the en passant target square (only when an en passant capture is possible), so
+/// that two positions hash to the same value only when the same moves are available in both.
+///
+/// # Example
+/// ```
+/// use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+/// use simple_chess::zobrist::hash_position;
+///
+/// let white_to_move = build_game_from_string("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+/// let black_to_move = build_game_from_string("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+/// assert_ne!(hash_position(&white_to_move), hash_position(&black_to_move));
+/// ```
+pub fn hash_position(game: &ChessGame) -> u64 {
+    let mut hash = hash_board(game.get_board());
+
+    if game.get_current_players_turn() == Color::Black {
+        hash ^= key(SIDE_TO_MOVE_INDEX);
+    }
+
+    let (wl, ws, bl, bs) = game.get_castling_rights();
+    for (index, right) in [wl, ws, bl, bs].iter().enumerate() {
+        if *right {
+            hash ^= key(CASTLING_RIGHTS_INDEX + index as u64);
+        }
+    }
+
+    if let Some((col, _)) = get_en_passant_target(game) {
+        hash ^= key(EN_PASSANT_INDEX + col as u64);
+    }
+
+    hash
+}
+
+/// Returns the square a pawn could capture en passant on, if an en passant capture is
+/// available in the current position.
+pub(crate) fn get_en_passant_target(game: &ChessGame) -> Option<(usize, usize)> {
+    let (col, row) = game.get_en_passant_target()?;
+    let capturing_color = game.get_current_players_turn();
+    let pawn_row = row.checked_add_signed(-capturing_color.forward() as isize)?;
+
+    let board = game.get_board();
+    if pawn_row >= board.get_height() {
+        return None;
+    }
+    let can_be_taken = [-1i32, 1].iter().any(|offset| {
+        let neighbour_col = col as i32 + offset;
+        if neighbour_col < 0 || neighbour_col >= board.get_width() as i32 {
+            return false;
+        }
+        match board.get_piece_at_space(neighbour_col as usize, pawn_row) {
+            Some(neighbour) => {
+                neighbour.get_piece_type() == PieceType::Pawn
+                    && neighbour.get_color() == capturing_color
+            }
+            None => false,
+        }
+    });
+    can_be_taken.then_some((col, row))
+}
+
+fn piece_square_key(piece: &ChessPiece, square_index: usize) -> u64 {
+    let code = piece.as_binary() as u64;
+    // the codes of archbishops and chancellors do not fit in four bits, so they are keyed
+    // apart from the standard pieces rather than spilling into the next square's keys
+    if code < 16 {
+        key(PIECE_SQUARE_INDEX + square_index as u64 * 16 + code)
+    } else {
+        key(FAIRY_PIECE_SQUARE_INDEX + square_index as u64 * 16 + code - 16)
+    }
+}
+
+/// Produces a pseudo-random key for an index using the SplitMix64 finalizer.
+fn key(index: u64) -> u64 {
+    let mut z = SEED.wrapping_add(index.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::forsyth_edwards_notation::build_game_from_string;
+    use crate::piece::PieceType::Knight;
+    use crate::ChessMoveType::Move;
+    use crate::Color::White;
+
+    #[test]
+    fn transposed_positions_have_the_same_hash() {
+        let mut game_a = ChessGame::new();
+        let mut game_b = ChessGame::new();
+
+        let knight_move = |from: (usize, usize), to: (usize, usize), color: Color| Move {
+            original_position: from,
+            new_position: to,
+            piece: ChessPiece::new(Knight, color),
+            taken_piece: None,
+            promotion: None,
+        };
+
+        game_a.make_move(knight_move((1, 0), (2, 2), White));
+        game_a.make_move(knight_move((1, 7), (2, 5), Color::Black));
+        game_a.make_move(knight_move((6, 0), (5, 2), White));
+
+        game_b.make_move(knight_move((6, 0), (5, 2), White));
+        game_b.make_move(knight_move((1, 7), (2, 5), Color::Black));
+        game_b.make_move(knight_move((1, 0), (2, 2), White));
+
+        assert_eq!(hash_position(&game_a), hash_position(&game_b));
+        assert_ne!(hash_position(&game_a), hash_position(&ChessGame::new()));
+    }
+
+    #[test]
+    fn castling_rights_change_the_hash() {
+        let all_rights = build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let no_rights = build_game_from_string("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+
+        assert_eq!(
+            hash_board(all_rights.get_board()),
+            hash_board(no_rights.get_board())
+        );
+        assert_ne!(hash_position(&all_rights), hash_position(&no_rights));
+    }
+
+    #[test]
+    fn en_passant_only_changes_the_hash_when_a_capture_is_possible() {
+        let capturable = build_game_from_string("4k3/8/8/3Pp3/8/8/8/4K3 w - e6 0 1").unwrap();
+        let capturable_no_ep = build_game_from_string("4k3/8/8/3Pp3/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(Some((4, 5)), get_en_passant_target(&capturable));
+        assert_ne!(hash_position(&capturable), hash_position(&capturable_no_ep));
+
+        let not_capturable = build_game_from_string("4k3/8/8/4p3/8/8/8/4K3 w - e6 0 1").unwrap();
+        let not_capturable_no_ep =
+            build_game_from_string("4k3/8/8/4p3/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(None, get_en_passant_target(&not_capturable));
+        assert_eq!(
+            hash_position(&not_capturable),
+            hash_position(&not_capturable_no_ep)
+        );
+    }
+}
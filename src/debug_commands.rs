@@ -0,0 +1,146 @@
+use simple_chess::codec::forsyth_edwards_notation::build_game_from_string;
+use simple_chess::codec::long_algebraic_notation::encode_move_as_string;
+use simple_chess::engine::Engine;
+use simple_chess::perft::{perft, perft_divide};
+use simple_chess::ChessGame;
+use std::time::Instant;
+
+/// The positions searched by `bench`, chosen to cover castling, en passant, promotions
+/// and checks.
+const BENCH_POSITIONS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+];
+
+/// Counts the positions reached from a position after a number of plies.
+///
+/// Arguments are `<depth> [--fen <fen>]`, the starting position is used when no FEN is given.
+pub fn run_perft(args: &[String]) -> Result<(), String> {
+    let (depth, game) = parse_depth_and_position(args)?;
+
+    let started = Instant::now();
+    let nodes = perft(&game, depth);
+    let elapsed = started.elapsed();
+
+    println!("Nodes searched: {nodes}");
+    println!(
+        "Time: {} ms ({} nodes per second)",
+        elapsed.as_millis(),
+        nodes_per_second(nodes, elapsed.as_secs_f64())
+    );
+    Ok(())
+}
+
+/// Counts the positions reached below each legal move of a position, in the format used
+/// by other engines so the counts can be compared move by move.
+///
+/// Arguments are `<depth> [--fen <fen>]`, the starting position is used when no FEN is given.
+pub fn run_divide(args: &[String]) -> Result<(), String> {
+    let (depth, game) = parse_depth_and_position(args)?;
+
+    let mut divide: Vec<(String, u64)> = perft_divide(&game, depth)
+        .into_iter()
+        .map(|(chess_move, nodes)| (encode_move_as_string(&chess_move), nodes))
+        .collect();
+    divide.sort();
+
+    for (chess_move, nodes) in &divide {
+        println!("{chess_move}: {nodes}");
+    }
+    println!();
+    println!("Moves: {}", divide.len());
+    println!(
+        "Nodes searched: {}",
+        divide.iter().map(|(_, nodes)| nodes).sum::<u64>()
+    );
+    Ok(())
+}
+
+/// Runs perft and an engine search on a fixed set of positions and reports the speed of
+/// each, useful to compare the performance of two builds.
+///
+/// Arguments are `[depth]`, the depth of both perft and the search, 3 when not given.
+pub fn run_bench(args: &[String]) -> Result<(), String> {
+    let depth = match args.first() {
+        Some(depth) => parse_depth(depth)?,
+        None => 3,
+    };
+
+    let mut engine = Engine::new();
+    let mut total_nodes = 0;
+    let started = Instant::now();
+    for fen in BENCH_POSITIONS {
+        let game = build_game_from_string(fen).map_err(|e| e.to_string())?;
+
+        let perft_started = Instant::now();
+        let perft_nodes = perft(&game, depth);
+        let perft_elapsed = perft_started.elapsed().as_secs_f64();
+
+        let search_started = Instant::now();
+        let result = engine.search(&game, depth);
+        let search_elapsed = search_started.elapsed().as_secs_f64();
+
+        println!("{fen}");
+        println!(
+            "  perft  {perft_nodes:>10} nodes {:>10} nps",
+            nodes_per_second(perft_nodes, perft_elapsed)
+        );
+        println!(
+            "  search {:>10} nodes {:>10} nps, score {}",
+            result.get_nodes(),
+            nodes_per_second(result.get_nodes(), search_elapsed),
+            result.get_score()
+        );
+        total_nodes += perft_nodes + result.get_nodes();
+    }
+
+    let elapsed = started.elapsed();
+    println!();
+    println!("Nodes: {total_nodes}");
+    println!(
+        "Time: {} ms ({} nodes per second)",
+        elapsed.as_millis(),
+        nodes_per_second(total_nodes, elapsed.as_secs_f64())
+    );
+    Ok(())
+}
+
+fn parse_depth_and_position(args: &[String]) -> Result<(u32, ChessGame), String> {
+    let depth = match args.first() {
+        Some(depth) => parse_depth(depth)?,
+        None => {
+            return Err(String::from(
+                "Missing depth, expected <depth> [--fen <fen>]",
+            ))
+        }
+    };
+
+    let game = match args.get(1).map(String::as_str) {
+        Some("--fen") => {
+            let fen = args[2..].join(" ");
+            if fen.is_empty() {
+                return Err(String::from("Missing FEN after --fen"));
+            }
+            build_game_from_string(&fen).map_err(|e| e.to_string())?
+        }
+        Some(unknown) => return Err(format!("Unknown argument '{unknown}'")),
+        None => ChessGame::new(),
+    };
+    Ok((depth, game))
+}
+
+fn parse_depth(depth: &str) -> Result<u32, String> {
+    depth
+        .parse()
+        .map_err(|_| format!("Unable to parse '{depth}' as a depth"))
+}
+
+fn nodes_per_second(nodes: u64, seconds: f64) -> u64 {
+    if seconds > 0.0 {
+        (nodes as f64 / seconds) as u64
+    } else {
+        nodes
+    }
+}
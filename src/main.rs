@@ -1,11 +1,33 @@
+use game_board::Board;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
-use game_board::Board;
 use simple_chess::chess_game_state_analyzer::GameState;
-use simple_chess::{ChessGame, ChessMoveType, Color};
 use simple_chess::piece::ChessPiece;
+use simple_chess::{ChessGame, ChessMoveType, Color};
+
+mod debug_commands;
+mod spectator;
+
+/// A subcommand, run with the arguments following its name.
+type Command = fn(&[String]) -> Result<(), String>;
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let command: Option<Command> = match args.get(1).map(String::as_str) {
+        Some("spectate") => Some(spectator::run),
+        Some("perft") => Some(debug_commands::run_perft),
+        Some("divide") => Some(debug_commands::run_divide),
+        Some("bench") => Some(debug_commands::run_bench),
+        _ => None,
+    };
+    if let Some(command) = command {
+        if let Err(e) = command(&args[2..]) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let mut game = ChessGame::new();
     println!("Welcome to Chess Game!");
     println!("Its {:?}'s turn", game.get_current_players_turn());
@@ -43,13 +65,41 @@ fn main() {
                 println!("{}", game.get_board());
                 break;
             }
+            GameState::VariantWin { winner } => {
+                println!("{:?} wins!", winner);
+                println!("{}", game.get_board());
+                break;
+            }
+            GameState::Resigned { winner } => {
+                println!("{:?} resigns, {:?} wins!", winner.opposite(), winner);
+                println!("{}", game.get_board());
+                break;
+            }
+            GameState::DrawAgreed => {
+                println!("Draw agreed");
+                println!("{}", game.get_board());
+                break;
+            }
+            GameState::DeadPosition => {
+                println!("Draw, neither player can checkmate");
+                println!("{}", game.get_board());
+                break;
+            }
+            GameState::SeventyFiveMoveRule => {
+                println!("Draw by the seventy-five-move rule");
+                println!("{}", game.get_board());
+                break;
+            }
         };
 
         state = game.make_move(next_move);
     }
 }
 
-fn list_moves_and_select_one(moves: Vec<ChessMoveType>, board: &Board<ChessPiece>) -> ChessMoveType {
+fn list_moves_and_select_one(
+    moves: Vec<ChessMoveType>,
+    board: &Board<ChessPiece>,
+) -> ChessMoveType {
     println!("{}", board);
     for (index, chess_move) in moves.iter().enumerate() {
         println!("{}. {}", index, chess_move);
@@ -0,0 +1,213 @@
+use simple_chess::adjudication::Adjudicator;
+use simple_chess::chess_game_state_analyzer::GameState;
+use simple_chess::clock::{ChessClock, TimeControl};
+use simple_chess::engine::skill_level::SkillLevel;
+use simple_chess::engine::time_manager::TimeManager;
+use simple_chess::engine::Engine;
+use simple_chess::game_result::Termination;
+use simple_chess::render::BoardRenderer;
+use simple_chess::{ChessGame, Color};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Creates an engine playing at a skill level, `beginner`, `intermediate`, `advanced`,
+/// `full` for full strength, or a rough Elo such as `1200`.
+fn build_engine(skill: &str) -> Result<Engine, String> {
+    let skill_level = match skill {
+        "beginner" => SkillLevel::beginner(),
+        "intermediate" => SkillLevel::intermediate(),
+        "advanced" => SkillLevel::advanced(),
+        "full" => return Ok(Engine::new()),
+        _ => SkillLevel::from_elo(skill.parse().map_err(|_| {
+            format!("Unknown engine '{skill}', expected a skill level such as 'beginner' or an Elo")
+        })?),
+    };
+    Ok(Engine::new().set_skill_level(skill_level))
+}
+
+/// Runs two engines against each other, rendering the board, the evaluation of the engine
+/// that just moved and the clocks in the terminal after every move.
+///
+/// Arguments are `[white engine] [black engine] [delay in milliseconds]`, all optional,
+/// where an engine is a skill level accepted by `build_engine`, followed by any of the
+/// adjudication flags:
+///
+/// - `--resign <centipawns> <plies>` resigns for a side that the engines have scored at
+///   least `centipawns` behind for `plies` consecutive plies.
+/// - `--draw <min ply> <centipawns> <plies>` draws the game once the engines' scores have
+///   stayed within `centipawns` of equal for `plies` consecutive plies, after `min ply`
+///   plies.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (positional, mut adjudicator) = parse_adjudication_flags(args)?;
+    let white_name = positional
+        .first()
+        .map(String::as_str)
+        .unwrap_or("intermediate");
+    let black_name = positional.get(1).map(String::as_str).unwrap_or("beginner");
+    let mut white = build_engine(white_name)?;
+    let mut black = build_engine(black_name)?;
+    let delay = match positional.get(2) {
+        Some(delay) => delay
+            .parse()
+            .map_err(|_| format!("Unable to parse '{delay}' as a delay in milliseconds"))?,
+        None => 500,
+    };
+
+    let renderer = BoardRenderer::new();
+    let mut game = ChessGame::new();
+    let mut clock = ChessClock::new(TimeControl::new(
+        Duration::from_secs(300),
+        Duration::from_secs(2),
+    ));
+    let mut state = game.get_game_state();
+    let mut evaluation = None;
+
+    loop {
+        render(
+            &renderer,
+            &game,
+            &clock,
+            (white_name, black_name),
+            evaluation,
+        );
+
+        if let Some(reason) = game.can_claim_draw() {
+            println!("Draw by {:?}", reason);
+            break;
+        }
+
+        match state {
+            GameState::InProgress { .. } | GameState::Check { .. } => {}
+            GameState::Checkmate { winner } => {
+                println!("Checkmate! {:?} wins!", winner);
+                break;
+            }
+            GameState::Stalemate => {
+                println!("Stalemate!");
+                break;
+            }
+            GameState::VariantWin { winner } => {
+                println!("{:?} wins!", winner);
+                break;
+            }
+            GameState::Resigned { winner } => {
+                match game.get_result().map(|result| result.get_termination()) {
+                    Some(Termination::TimeForfeit) => println!(
+                        "{:?} ran out of time, {:?} wins!",
+                        winner.opposite(),
+                        winner
+                    ),
+                    _ => println!("{:?} resigns, {:?} wins!", winner.opposite(), winner),
+                }
+                break;
+            }
+            GameState::DrawAgreed => {
+                match game.get_result().map(|result| result.get_termination()) {
+                    Some(Termination::Adjudication) => println!("Draw by adjudication"),
+                    _ => println!("Draw agreed"),
+                }
+                break;
+            }
+            GameState::DeadPosition => {
+                println!("Draw, neither player can checkmate");
+                break;
+            }
+            GameState::SeventyFiveMoveRule => {
+                println!("Draw by the seventy-five-move rule");
+                break;
+            }
+        };
+
+        let turn = game.get_current_players_turn();
+        let engine = match turn {
+            Color::White => &mut white,
+            Color::Black => &mut black,
+        };
+
+        let started = Instant::now();
+        let limits = TimeManager::from_clock(&clock, turn).get_limits();
+        let result = engine.search_with_time_limits(&game, limits);
+        let next_move = *result
+            .get_best_move()
+            .ok_or("The engine found no move to play")?;
+        // engine scores are from the side to move, the adjudicator scores from White
+        let score = match turn {
+            Color::White => result.get_score(),
+            Color::Black => -result.get_score(),
+        };
+        evaluation = Some((score, result.is_mate_score()));
+
+        state = game
+            .make_timed_move(next_move, &mut clock, started.elapsed())
+            .map_err(|e| e.to_string())?;
+        if let Some(adjudication) = adjudicator.record_score(score) {
+            state = game.adjudicate(adjudication).map_err(|e| e.to_string())?;
+        }
+        thread::sleep(Duration::from_millis(delay));
+    }
+
+    Ok(())
+}
+
+/// Splits the adjudication flags from the positional arguments.
+fn parse_adjudication_flags(args: &[String]) -> Result<(Vec<String>, Adjudicator), String> {
+    let mut positional = Vec::new();
+    let mut adjudicator = Adjudicator::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--resign" => {
+                let threshold = parse_flag_value(args.next(), "--resign")?;
+                let plies = parse_flag_value(args.next(), "--resign")?;
+                adjudicator = adjudicator.set_resign_rule(threshold, plies);
+            }
+            "--draw" => {
+                let min_ply = parse_flag_value(args.next(), "--draw")?;
+                let margin = parse_flag_value(args.next(), "--draw")?;
+                let plies = parse_flag_value(args.next(), "--draw")?;
+                adjudicator = adjudicator.set_draw_rule(min_ply, margin, plies);
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+    Ok((positional, adjudicator))
+}
+
+fn parse_flag_value<T: std::str::FromStr>(value: Option<&String>, flag: &str) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {flag}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("Unable to parse '{value}' as a value for {flag}"))
+}
+
+fn render(
+    renderer: &BoardRenderer,
+    game: &ChessGame,
+    clock: &ChessClock,
+    (white_name, black_name): (&str, &str),
+    evaluation: Option<(i32, bool)>,
+) {
+    // clear the terminal and move the cursor to the top left
+    print!("\x1b[2J\x1b[H");
+    println!(
+        "White ({white_name}) {}   Black ({black_name}) {}",
+        format_remaining_time(clock.get_remaining_time(Color::White)),
+        format_remaining_time(clock.get_remaining_time(Color::Black)),
+    );
+    print!("{}", renderer.render(game));
+    match evaluation {
+        Some((score, true)) if score > 0 => println!("Evaluation: White mates"),
+        Some((_, true)) => println!("Evaluation: Black mates"),
+        Some((score, false)) => println!("Evaluation: {:+.2}", score as f64 / 100.0),
+        None => println!("Evaluation: -"),
+    }
+    match game.get_last_move() {
+        Some(last_move) => println!("Last move: {}", last_move),
+        None => println!("Last move: -"),
+    }
+}
+
+fn format_remaining_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}